@@ -304,6 +304,9 @@ export class FvttApiWrapper {
    * @param {number} [args.width] - Scene width (optional, defaults to image width)
    * @param {number} [args.height] - Scene height (optional, defaults to image height)
    * @param {number} [args.grid_size] - Grid size in pixels (optional, default 100)
+   * @param {string} [args.grid_type] - Grid type: square, gridless, hex_odd_q, hex_even_q, hex_odd_r, hex_even_r (optional, default square)
+   * @param {number} [args.grid_distance] - Distance covered by one grid space (optional)
+   * @param {string} [args.grid_units] - Units for grid distance, e.g. "pc" (optional)
    * @param {string|null} [args.folder] - Name of folder to place the scene in
    * @param {string} [args.pack_id] - Compendium pack ID (optional, creates in world if omitted)
    * @param {Object} userContext
@@ -341,10 +344,12 @@ export class FvttApiWrapper {
         },
         grid: {
           size: args.grid_size || 100,
-          type: CONST.GRID_TYPES.SQUARE,
+          type: this._getGridType(args.grid_type),
         },
         padding: 0,
       };
+      if (args.grid_distance !== undefined) sceneData.grid.distance = args.grid_distance;
+      if (args.grid_units !== undefined) sceneData.grid.units = args.grid_units;
 
       // Add folder if specified (for world documents)
       if (args.folder && !args.pack_id) {
@@ -400,6 +405,48 @@ export class FvttApiWrapper {
     }
   }
 
+  /**
+   * Get recent chat messages, including dice roll results
+   * @param {Object} args - Query arguments
+   * @param {string} [args.since] - ISO timestamp; only messages after this are returned
+   * @param {number} [args.limit] - Maximum number of messages (most recent kept, default 200)
+   * @param {Object} userContext
+   * @returns {Object}
+   */
+  static getChatLog(args, userContext) {
+    if (userContext.role < CONST.USER_ROLES.GAMEMASTER) {
+      return { error: "Only GMs can read the chat log" };
+    }
+
+    const since = args.since ? Date.parse(args.since) : null;
+    if (args.since && Number.isNaN(since)) {
+      return { error: `Invalid timestamp: ${args.since}` };
+    }
+    const limit = args.limit ?? 200;
+
+    const messages = game.messages.contents
+      .filter((m) => since === null || m.timestamp > since)
+      // Whispers to other users stay private even from the GM's summary
+      .filter((m) => m.visible)
+      .slice(-limit)
+      .map((m) => {
+        const div = document.createElement("div");
+        div.innerHTML = m.content;
+        const entry = {
+          timestamp: new Date(m.timestamp).toISOString(),
+          speaker: m.speaker?.alias ?? m.author?.name ?? null,
+          content: div.textContent.trim(),
+        };
+        if (m.flavor) entry.flavor = m.flavor;
+        if (m.rolls?.length) {
+          entry.rolls = m.rolls.map((r) => ({ formula: r.formula, total: r.total }));
+        }
+        return entry;
+      });
+
+    return { messages };
+  }
+
   /**
    * Get game system capabilities
    * @returns {Object}
@@ -860,6 +907,22 @@ export class FvttApiWrapper {
     }));
   }
 
+  /**
+   * Get FVTT grid type constant for a grid type name
+   * @private
+   */
+  static _getGridType(gridType) {
+    const typeMap = {
+      gridless: CONST.GRID_TYPES.GRIDLESS,
+      square: CONST.GRID_TYPES.SQUARE,
+      hex_odd_r: CONST.GRID_TYPES.HEXODDR,
+      hex_even_r: CONST.GRID_TYPES.HEXEVENR,
+      hex_odd_q: CONST.GRID_TYPES.HEXODDQ,
+      hex_even_q: CONST.GRID_TYPES.HEXEVENQ,
+    };
+    return typeMap[gridType?.toLowerCase()] ?? CONST.GRID_TYPES.SQUARE;
+  }
+
   /**
    * Get folder type for a document type
    * @private
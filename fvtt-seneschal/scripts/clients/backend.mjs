@@ -67,6 +67,73 @@ export class BackendClient {
     return response.json();
   }
 
+  /**
+   * List models installed on the Ollama host
+   * @returns {Promise<Array>} Models with name, size (bytes) and modified_at
+   */
+  async listLocalModels() {
+    const response = await fetch(`${this.baseUrl}/api/models/local`, {
+      method: "GET",
+      headers: this.headers,
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(`${response.status}: ${errorBody.message || response.statusText}`);
+    }
+    return response.json();
+  }
+
+  /**
+   * Start pulling a model; progress arrives as model_pull_progress WebSocket messages
+   * @param {string} model - Model name with optional tag
+   * @returns {Promise<Object>} { model, started }
+   */
+  async pullModel(model) {
+    const response = await fetch(`${this.baseUrl}/api/models/pull`, {
+      method: "POST",
+      headers: this.headers,
+      body: JSON.stringify({ model }),
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(`${response.status}: ${errorBody.message || response.statusText}`);
+    }
+    return response.json();
+  }
+
+  /**
+   * Get the latest progress of model pulls started since the backend started
+   * @returns {Promise<Array>}
+   */
+  async getModelPulls() {
+    const response = await fetch(`${this.baseUrl}/api/models/pull`, {
+      method: "GET",
+      headers: this.headers,
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(`${response.status}: ${errorBody.message || response.statusText}`);
+    }
+    return response.json();
+  }
+
+  /**
+   * Delete a model from the Ollama host
+   * @param {string} model - Installed model name including tag
+   * @returns {Promise<Object>}
+   */
+  async deleteModel(model) {
+    const response = await fetch(`${this.baseUrl}/api/models/${encodeURIComponent(model)}`, {
+      method: "DELETE",
+      headers: this.headers,
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(`${response.status}: ${errorBody.message || response.statusText}`);
+    }
+    return response.json();
+  }
+
   /**
    * List documents
    * @returns {Promise<Array>}
@@ -86,7 +153,8 @@ export class BackendClient {
    * Upload a document
    * @param {File} file - The file to upload
    * @param {Object} metadata - Document metadata
-   * @param {Function} onProgress - Progress callback
+   * @param {Function} onProgress - Progress callback, called with a percentage and,
+   *   once the server has the file, its server-side stage
    * @returns {Promise<Object>}
    */
   async uploadDocument(file, metadata, onProgress) {
@@ -97,10 +165,23 @@ export class BackendClient {
     if (metadata.tags) {
       formData.append("tags", metadata.tags);
     }
+    if (metadata.password) {
+      formData.append("password", metadata.password);
+    }
+
+    // The server reports receiving, hashing and queueing the file over the
+    // WebSocket under this token
+    const uploadToken = foundry.utils.randomID();
+    const unsubscribe = globalThis.seneschalWS?.on("upload_progress", (msg) => {
+      if (msg.upload_token !== uploadToken || !onProgress) return;
+      const percent = msg.total ? Math.round((msg.bytes / msg.total) * 100) : 100;
+      onProgress(percent, msg.stage);
+    });
 
     return new Promise((resolve, reject) => {
       const xhr = new XMLHttpRequest();
       xhr.open("POST", `${this.baseUrl}/api/documents`);
+      xhr.setRequestHeader("X-Upload-Token", uploadToken);
 
       // Set a long timeout for PDF processing (5 minutes)
       xhr.timeout = 300000;
@@ -151,7 +232,7 @@ export class BackendClient {
       });
 
       xhr.send(formData);
-    });
+    }).finally(() => unsubscribe?.());
   }
 
   /**
@@ -176,6 +257,7 @@ export class BackendClient {
    * @param {string} updates.title - Document title
    * @param {string} updates.access_level - Access level (player, trusted, assistant, gm_only)
    * @param {string} [updates.tags] - Comma-separated tags
+   * @param {number} [updates.priority] - Search priority, -10 to 10
    * @returns {Promise<Object>} Updated document
    */
   async updateDocument(documentId, updates) {
@@ -249,6 +331,8 @@ export class BackendClient {
    * @param {number} [params.user_role] - User role for access filtering
    * @param {string} [params.document_id] - Filter by document ID
    * @param {number} [params.page_number] - Filter by page number
+   * @param {string[]} [params.tags] - Filter by image tags
+   * @param {string} [params.tags_match] - "any" (default) or "all"
    * @param {number} [params.limit] - Maximum number of results
    * @returns {Promise<Array>} List of images
    */
@@ -257,6 +341,8 @@ export class BackendClient {
     if (params.user_role) query.set("user_role", params.user_role);
     if (params.document_id) query.set("document_id", params.document_id);
     if (params.page_number) query.set("page_number", params.page_number);
+    if (params.tags?.length) query.set("tags", params.tags.join(","));
+    if (params.tags_match) query.set("tags_match", params.tags_match);
     if (params.limit) query.set("limit", params.limit);
 
     const url = `${this.baseUrl}/api/images${query.toString() ? "?" + query.toString() : ""}`;
@@ -298,6 +384,31 @@ export class BackendClient {
     return response.blob();
   }
 
+  /**
+   * Get a rendered page of a PDF document
+   * @param {string} documentId - Document ID
+   * @param {number} page - Page number (1-indexed)
+   * @param {Object} [options]
+   * @param {number} [options.dpi] - Resolution (36-300, default 150)
+   * @param {string} [options.format] - "webp" (default) or "png"
+   * @returns {Promise<Blob>} Page image blob
+   */
+  async getDocumentPage(documentId, page, { dpi, format } = {}) {
+    const params = new URLSearchParams();
+    if (dpi) params.set("dpi", dpi);
+    if (format) params.set("format", format);
+    const query = params.toString() ? `?${params}` : "";
+    const response = await fetch(
+      `${this.baseUrl}/api/documents/${documentId}/pages/${page}/render${query}`,
+      { headers: this.headers }
+    );
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(`${response.status}: ${errorBody.message || response.statusText}`);
+    }
+    return response.blob();
+  }
+
   /**
    * Request delivery of an image to FVTT assets
    * @param {string} imageId - Image ID
@@ -370,4 +481,107 @@ export class BackendClient {
     }
     return response.json();
   }
+
+  /**
+   * Get settings as they apply to one world, with the keys it overrides
+   * @param {string} worldId - FVTT world ID
+   * @returns {Promise<Object>} Settings response with settings, overridden and world_keys
+   */
+  async getWorldSettings(worldId) {
+    const response = await fetch(
+      `${this.baseUrl}/api/settings/worlds/${encodeURIComponent(worldId)}`,
+      {
+        method: "GET",
+        headers: this.headers,
+      }
+    );
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(errorBody.message || `Failed to get world settings: ${response.statusText}`);
+    }
+    return response.json();
+  }
+
+  /**
+   * Override settings for one world
+   * @param {string} worldId - FVTT world ID
+   * @param {Object} settings - Key-value pairs to override. Use null to revert to the global value.
+   * @returns {Promise<Object>} Updated world settings response
+   */
+  async updateWorldSettings(worldId, settings) {
+    const response = await fetch(
+      `${this.baseUrl}/api/settings/worlds/${encodeURIComponent(worldId)}`,
+      {
+        method: "PUT",
+        headers: this.headers,
+        body: JSON.stringify({ settings }),
+      }
+    );
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(
+        errorBody.message || `Failed to update world settings: ${response.statusText}`
+      );
+    }
+    return response.json();
+  }
+
+  // ==================== Campaign Clock API ====================
+
+  /**
+   * Get the campaign's current Imperial date
+   * @param {string} worldId - FVTT world ID
+   * @returns {Promise<Object>} Response with `date` ("187-1105"), or null if the clock isn't set
+   */
+  async getCampaignDate(worldId) {
+    const params = new URLSearchParams({ world_id: worldId });
+    const response = await fetch(`${this.baseUrl}/api/clock?${params}`, {
+      method: "GET",
+      headers: this.headers,
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(errorBody.message || `Failed to get campaign date: ${response.statusText}`);
+    }
+    return response.json();
+  }
+
+  // ==================== NPC Registry API ====================
+
+  /**
+   * Add an NPC to the registry, or update the one with the same name
+   * @param {Object} npc - name, description, stat_block_id and/or actor_uuid
+   * @param {string} worldId - FVTT world the NPC belongs to
+   * @returns {Promise<Object>} The saved NPC
+   */
+  async setNpc(npc, worldId) {
+    const response = await fetch(`${this.baseUrl}/api/npcs`, {
+      method: "POST",
+      headers: { ...this.headers, "X-Seneschal-World": worldId },
+      body: JSON.stringify(npc),
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(errorBody.message || `Failed to save NPC: ${response.statusText}`);
+    }
+    return response.json();
+  }
+
+  // ==================== Admin API ====================
+
+  /**
+   * Get corpus statistics and service health
+   * @returns {Promise<Object>} Document/chunk/image counts, Ollama status, model usage, auto-import status
+   */
+  async getAdminStats() {
+    const response = await fetch(`${this.baseUrl}/api/admin/stats`, {
+      method: "GET",
+      headers: this.headers,
+    });
+    if (!response.ok) {
+      const errorBody = await response.json().catch(() => ({}));
+      throw new Error(errorBody.message || `Failed to get admin stats: ${response.statusText}`);
+    }
+    return response.json();
+  }
 }
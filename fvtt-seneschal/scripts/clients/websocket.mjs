@@ -5,6 +5,7 @@
 import { MODULE_ID, SETTINGS } from "../constants.mjs";
 import { getSetting, buildUserContext } from "../utils.mjs";
 import { ToolExecutor } from "../tools/index.mjs";
+import { showSharedAnswer } from "../ui/share-answer.mjs";
 
 /**
  * WebSocket client for real-time updates from the backend
@@ -100,6 +101,11 @@ export class WebSocketClient {
       user_name: ctx.user_name,
       role: ctx.role,
       session_id: this.sessionId,
+      owned_actor_ids: ctx.owned_actor_ids,
+      character_id: ctx.character_id,
+      world_id: game.world.id,
+      allow_write_tools: getSetting(SETTINGS.ALLOW_WRITE_TOOLS),
+      system_id: game.system.id,
     });
   }
 
@@ -127,17 +133,68 @@ export class WebSocketClient {
       case "captioning_progress":
         this._emit("captioning_progress", msg);
         break;
+      case "upload_progress":
+        this._emit("upload_progress", msg);
+        break;
+      case "journal_sync_result":
+        this._emit("journal_sync_result", msg);
+        break;
+      case "conversation_modes":
+        this._emit("conversation_modes", msg);
+        break;
+      case "tool_budgets":
+        this._emit("tool_budgets", msg);
+        break;
+      case "tools_registered":
+        this._emit("tools_registered", msg);
+        break;
+      case "shared_answer":
+        showSharedAnswer(msg);
+        break;
+      case "share_answer_result":
+        ui.notifications.info(
+          game.i18n.format("SENESCHAL.ShareAnswer.Shared", { count: msg.recipients })
+        );
+        break;
+      case "model_pull_progress":
+        this._emit("model_pull_progress", msg);
+        break;
+      case "log_event":
+        this._emit("log_event", msg);
+        break;
+      // Progress of MCP tool chains
+      case "tool_call_started":
+      case "tool_call_finished":
+      case "tool_call_awaiting_approval":
+      case "tool_chain_paused":
+        this._emit("tool_progress", msg);
+        break;
+      case "disk_space_status":
+        this._notifyDiskSpace(msg);
+        break;
       case "pong":
         // Keepalive acknowledged
         break;
       case "error":
         console.error(`${MODULE_ID} | WebSocket server error:`, msg);
+        if (msg.code === "share_failed") ui.notifications.error(msg.message);
         this._emit("error", msg);
         break;
 
+      // Write tool call held for GM approval
+      case "chat_approval_required":
+        this._promptApproval(msg);
+        break;
+
       // MCP external tool call - execute in FVTT and send result back
       case "chat_tool_call": {
-        this._executeToolCall(msg.conversation_id, msg.id, msg.tool, msg.args);
+        this._executeToolCall(
+          msg.conversation_id,
+          msg.id,
+          msg.tool,
+          msg.args,
+          msg.correlation_id,
+        );
         break;
       }
 
@@ -170,6 +227,99 @@ export class WebSocketClient {
     this.send({ type: "unsubscribe_documents" });
   }
 
+  /**
+   * Watch the backend log live (GM only). Events arrive as log_event
+   * messages until unsubscribed; subscribing again replaces the filter.
+   * @param {string} level - Most verbose level: "error", "warn", "info", "debug" or "trace"
+   * @param {string[]} [targets] - Modules to watch, e.g. "ingestion"; empty for the whole service
+   */
+  subscribeToLogs(level, targets = []) {
+    this.send({ type: "subscribe_logs", level, targets });
+  }
+
+  /**
+   * Stop watching the backend log
+   */
+  unsubscribeFromLogs() {
+    this.send({ type: "unsubscribe_logs" });
+  }
+
+  /**
+   * Set the retrieval mode of an MCP conversation (GM only). The server
+   * replies with a conversation_modes message.
+   * @param {"auto_rag"|"manual_tools"|"no_tools"} mode - Retrieval mode
+   * @param {string|null} [sessionId] - MCP session; omit to set the mode new conversations start in
+   */
+  setConversationMode(mode, sessionId = null) {
+    this.send({ type: "set_conversation_mode", mode, session_id: sessionId });
+  }
+
+  /**
+   * Request the retrieval modes of MCP conversations (GM only). The server
+   * replies with a conversation_modes message.
+   */
+  requestConversationModes() {
+    this.send({ type: "get_conversation_modes" });
+  }
+
+  /**
+   * Set the tool-use budget of an MCP conversation (GM only): a prep
+   * session can allow long tool chains while table questions stay quick.
+   * Limits left out use the backend settings, and all are capped at the
+   * admin's ceilings. The server replies with a tool_budgets message.
+   * @param {Object} budget
+   * @param {number} [budget.toolCallPauseThreshold] - Tool calls before pausing to check in
+   * @param {number} [budget.timePauseThresholdSecs] - Seconds before pausing to check in
+   * @param {number} [budget.hardTimeoutSecs] - Seconds before a turn's tool calls stop
+   * @param {string|null} [sessionId] - MCP session; omit to set the budget new conversations start with
+   */
+  setToolBudget(budget, sessionId = null) {
+    this.send({
+      type: "set_tool_budget",
+      session_id: sessionId,
+      tool_call_pause_threshold: budget.toolCallPauseThreshold ?? null,
+      time_pause_threshold_secs: budget.timePauseThresholdSecs ?? null,
+      hard_timeout_secs: budget.hardTimeoutSecs ?? null,
+    });
+  }
+
+  /**
+   * Request the tool-use budgets of MCP conversations (GM only). The server
+   * replies with a tool_budgets message.
+   */
+  requestToolBudgets() {
+    this.send({ type: "get_tool_budgets" });
+  }
+
+  /**
+   * Register tools from other modules for this world, replacing those
+   * registered before (GM only). The server replies with a
+   * tools_registered message.
+   * @param {Object[]} tools - Tool definitions
+   */
+  registerTools(tools) {
+    this.send({ type: "register_tools", tools });
+  }
+
+  /**
+   * Share an answer with connected players (GM only). GM-only content is
+   * removed by the server, and documents the answer cites limit who gets it.
+   * @param {string} content - Answer HTML or text
+   * @param {Object} [options]
+   * @param {string|null} [options.title] - Dialog title shown to players
+   * @param {number|null} [options.accessLevel] - Lowest player role to share with
+   * @param {string[]} [options.documentIds] - Documents the answer cites
+   */
+  shareAnswer(content, { title = null, accessLevel = null, documentIds = [] } = {}) {
+    this.send({
+      type: "share_answer",
+      content,
+      title,
+      access_level: accessLevel,
+      document_ids: documentIds,
+    });
+  }
+
   /**
    * Send a tool result via WebSocket
    * @param {string} conversationId - MCP request ID
@@ -230,10 +380,12 @@ export class WebSocketClient {
    * @param {string} toolCallId - Tool call ID
    * @param {string} tool - Tool name
    * @param {Object} args - Tool arguments
+   * @param {string} [correlationId] - Correlation ID of the MCP tool call, for matching server logs
    * @private
    */
-  async _executeToolCall(conversationId, toolCallId, tool, args) {
-    console.log(`${MODULE_ID} | Executing tool call: ${tool}`, args);
+  async _executeToolCall(conversationId, toolCallId, tool, args, correlationId) {
+    const trace = correlationId ? ` [${correlationId}]` : "";
+    console.log(`${MODULE_ID} | Executing tool call: ${tool}${trace}`, args);
 
     try {
       // Build user context from current FVTT user
@@ -245,9 +397,9 @@ export class WebSocketClient {
       // Send result back to server
       this.sendToolResult(conversationId, toolCallId, result);
 
-      console.log(`${MODULE_ID} | Tool call completed: ${tool}`);
+      console.log(`${MODULE_ID} | Tool call completed: ${tool}${trace}`);
     } catch (error) {
-      console.error(`${MODULE_ID} | Tool call failed: ${tool}`, error);
+      console.error(`${MODULE_ID} | Tool call failed: ${tool}${trace}`, error);
 
       // Send error result back to server
       this.sendToolResult(conversationId, toolCallId, {
@@ -257,6 +409,52 @@ export class WebSocketClient {
     }
   }
 
+  /**
+   * Ask the GM to approve a tool call and send the answer back
+   * @param {Object} msg - chat_approval_required message
+   * @private
+   */
+  async _promptApproval(msg) {
+    const args = Handlebars.escapeExpression(JSON.stringify(msg.args, null, 2));
+    const prompt = game.i18n.format("SENESCHAL.Approval.Prompt", {
+      tool: Handlebars.escapeExpression(msg.tool),
+      seconds: msg.timeout_secs,
+    });
+    const approved = await Dialog.confirm({
+      title: game.i18n.localize("SENESCHAL.Approval.Title"),
+      content: `<p>${prompt}</p><pre>${args}</pre>`,
+      yes: () => true,
+      no: () => false,
+      defaultYes: false,
+    });
+    this.send({
+      type: "tool_approval",
+      request_id: msg.request_id,
+      approved: Boolean(approved),
+    });
+  }
+
+  /**
+   * Tell the GM that the backend's free disk space crossed a threshold
+   * @param {Object} msg - disk_space_status message
+   * @private
+   */
+  _notifyDiskSpace(msg) {
+    const free = Math.floor(msg.free_bytes / (1024 * 1024));
+    switch (msg.level) {
+      case "critical":
+        ui.notifications.error(game.i18n.format("SENESCHAL.DiskSpace.Critical", { free }), {
+          permanent: true,
+        });
+        break;
+      case "low":
+        ui.notifications.warn(game.i18n.format("SENESCHAL.DiskSpace.Low", { free }));
+        break;
+      default:
+        ui.notifications.info(game.i18n.format("SENESCHAL.DiskSpace.Ok", { free }));
+    }
+  }
+
   /**
    * Start the ping interval for keepalive
    * @private
@@ -8,4 +8,6 @@ export const SETTINGS = {
   BACKEND_URL: "backendUrl",
   ENABLE_PLAYER_ACCESS: "enablePlayerAccess",
   MAX_ACTIONS_PER_REQUEST: "maxActionsPerRequest",
+  SYNC_JOURNALS: "syncJournals",
+  ALLOW_WRITE_TOOLS: "allowWriteTools",
 };
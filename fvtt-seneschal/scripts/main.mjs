@@ -10,9 +10,16 @@ import { BackendClient } from "./clients/backend.mjs";
 import { WebSocketClient } from "./clients/websocket.mjs";
 import { FvttApiWrapper } from "./api/index.mjs";
 import { ToolExecutor } from "./tools/index.mjs";
+import { registerTool, startCustomToolSync, unregisterTool } from "./tools/custom.mjs";
 import { DocumentManagementDialog } from "./ui/dialogs/documents.mjs";
 import { ImageBrowserDialog } from "./ui/dialogs/images.mjs";
 import { BackendSettingsDialog } from "./ui/dialogs/settings.mjs";
+import { JournalSync } from "./sync/journals.mjs";
+import { AssetUsageReporter } from "./sync/asset-usage.mjs";
+import { startCampaignDateDisplay } from "./ui/campaign-date.mjs";
+import { startToolProgressDisplay } from "./ui/tool-progress.mjs";
+import { registerNpcContextOption } from "./ui/npc-registry.mjs";
+import { registerShareAnswerContextOption } from "./ui/share-answer.mjs";
 
 // Re-export for advanced usage
 export {
@@ -76,6 +83,24 @@ function registerSettings() {
     },
   });
 
+  game.settings.register(MODULE_ID, SETTINGS.SYNC_JOURNALS, {
+    name: game.i18n.localize("SENESCHAL.Settings.SyncJournals"),
+    hint: game.i18n.localize("SENESCHAL.Settings.SyncJournalsHint"),
+    scope: "world",
+    config: true,
+    type: Boolean,
+    default: false,
+  });
+
+  game.settings.register(MODULE_ID, SETTINGS.ALLOW_WRITE_TOOLS, {
+    name: game.i18n.localize("SENESCHAL.Settings.AllowWriteTools"),
+    hint: game.i18n.localize("SENESCHAL.Settings.AllowWriteToolsHint"),
+    scope: "client",
+    config: true,
+    type: Boolean,
+    default: true,
+  });
+
   // Register document management menu
   game.settings.registerMenu(MODULE_ID, "documentManagement", {
     name: game.i18n.localize("SENESCHAL.Documents.MenuName"),
@@ -94,6 +119,11 @@ function registerSettings() {
 Hooks.once("init", () => {
   console.log(`${MODULE_ID} | Initializing Seneschal`);
   registerSettings();
+  registerNpcContextOption();
+  registerShareAnswerContextOption();
+
+  // Other modules register their own tools through the module API
+  game.modules.get(MODULE_ID).api = { registerTool, unregisterTool };
 });
 
 Hooks.once("ready", async () => {
@@ -116,5 +146,21 @@ Hooks.once("ready", async () => {
       console.error(`${MODULE_ID} | WebSocket connection failed:`, error);
       // Will auto-reconnect in the background
     }
+
+    // Keep world journal entries indexed on the backend (GM client only)
+    if (game.user.isGM && getSetting(SETTINGS.SYNC_JOURNALS)) {
+      globalThis.seneschalJournalSync = new JournalSync(globalThis.seneschalWS);
+      globalThis.seneschalJournalSync.start();
+    }
+
+    // Tell the backend which world documents use delivered images (GM client only)
+    if (game.user.isGM) {
+      globalThis.seneschalAssetUsage = new AssetUsageReporter(globalThis.seneschalWS);
+      globalThis.seneschalAssetUsage.start();
+      startToolProgressDisplay(globalThis.seneschalWS);
+      startCustomToolSync(globalThis.seneschalWS);
+    }
+
+    startCampaignDateDisplay();
   }
 });
@@ -0,0 +1,165 @@
+/**
+ * Asset usage reporting - tells the backend which world documents use images it delivered
+ */
+
+/** Delivered files live under the FVTT assets directory */
+const ASSET_PREFIX = "assets/";
+
+/** Delay before reporting an edited document, so rapid edits collapse into one report */
+const REPORT_DEBOUNCE_MS = 2000;
+
+/**
+ * Image paths each reported document type can reference
+ */
+const DOCUMENT_PATHS = {
+  scene: (scene) => [
+    scene.background?.src,
+    scene.foreground,
+    ...scene.tiles.map((tile) => tile.texture?.src),
+  ],
+  actor: (actor) => [actor.img, actor.prototypeToken?.texture?.src],
+  item: (item) => [item.img],
+  journal_entry: (journal) => journal.pages.map((page) => page.src),
+};
+
+/**
+ * Reports the delivered asset paths of scenes, actors, items and journal entries
+ * when they're created, changed or deleted. The backend ignores paths it didn't
+ * deliver, and each report replaces the previous one for the document.
+ */
+export class AssetUsageReporter {
+  /**
+   * @param {WebSocketClient} ws - Connected WebSocket client
+   */
+  constructor(ws) {
+    this.ws = ws;
+    this.pending = new Map(); // "type:id" -> timeout handle
+  }
+
+  /**
+   * Register hooks and report all documents whenever the connection is (re-)established
+   */
+  start() {
+    this.ws.on("connected", () => this.reportAll());
+
+    const watch = (documentName, type, parentOf = (doc) => doc) => {
+      const onChange = (doc) => {
+        const target = parentOf(doc);
+        if (target) this.scheduleReport(type, target);
+      };
+      Hooks.on(`create${documentName}`, onChange);
+      Hooks.on(`update${documentName}`, onChange);
+    };
+    watch("Scene", "scene");
+    watch("Actor", "actor");
+    watch("Item", "item", (item) => (item.parent ? null : item));
+    watch("JournalEntry", "journal_entry");
+    watch("Tile", "scene", (tile) => tile.parent);
+    watch("JournalEntryPage", "journal_entry", (page) => page.parent);
+    Hooks.on("deleteTile", (tile) => tile.parent && this.scheduleReport("scene", tile.parent));
+    Hooks.on(
+      "deleteJournalEntryPage",
+      (page) => page.parent && this.scheduleReport("journal_entry", page.parent)
+    );
+
+    Hooks.on("deleteScene", (scene) => this.clear("scene", scene));
+    Hooks.on("deleteActor", (actor) => this.clear("actor", actor));
+    Hooks.on("deleteItem", (item) => !item.parent && this.clear("item", item));
+    Hooks.on("deleteJournalEntry", (journal) => this.clear("journal_entry", journal));
+
+    if (this.ws.authenticated) this.reportAll();
+  }
+
+  /**
+   * Report every world document that uses a delivered asset
+   */
+  reportAll() {
+    if (!this._isReportingClient()) return;
+    const collections = {
+      scene: game.scenes,
+      actor: game.actors,
+      item: game.items,
+      journal_entry: game.journal,
+    };
+    for (const [type, collection] of Object.entries(collections)) {
+      for (const doc of collection) {
+        if (this._paths(type, doc).length) this.report(type, doc);
+      }
+    }
+  }
+
+  /**
+   * Report a document after a short delay
+   * @param {string} type - Document type
+   * @param {foundry.abstract.Document} doc
+   */
+  scheduleReport(type, doc) {
+    if (!this._isReportingClient()) return;
+    const key = `${type}:${doc.id}`;
+    clearTimeout(this.pending.get(key));
+    this.pending.set(
+      key,
+      setTimeout(() => {
+        this.pending.delete(key);
+        this.report(type, doc);
+      }, REPORT_DEBOUNCE_MS)
+    );
+  }
+
+  /**
+   * Send the delivered asset paths a document uses
+   * @param {string} type - Document type
+   * @param {foundry.abstract.Document} doc
+   */
+  report(type, doc) {
+    this._send(type, doc, this._paths(type, doc));
+  }
+
+  /**
+   * Tell the backend a deleted document no longer uses any assets
+   * @param {string} type - Document type
+   * @param {foundry.abstract.Document} doc
+   */
+  clear(type, doc) {
+    if (!this._isReportingClient()) return;
+    clearTimeout(this.pending.get(`${type}:${doc.id}`));
+    this.pending.delete(`${type}:${doc.id}`);
+    this._send(type, doc, []);
+  }
+
+  /**
+   * @private
+   */
+  _send(type, doc, paths) {
+    this.ws.send({
+      type: "asset_usage",
+      document_type: type,
+      document_id: doc.id,
+      document_name: doc.name,
+      paths,
+    });
+  }
+
+  /**
+   * Distinct delivered asset paths a document references
+   * @param {string} type - Document type
+   * @param {foundry.abstract.Document} doc
+   * @returns {string[]}
+   * @private
+   */
+  _paths(type, doc) {
+    const paths = DOCUMENT_PATHS[type](doc).filter(
+      (path) => typeof path === "string" && path.startsWith(ASSET_PREFIX)
+    );
+    return [...new Set(paths)];
+  }
+
+  /**
+   * Only the active GM reports, so multiple connected GMs don't send duplicates
+   * @returns {boolean}
+   * @private
+   */
+  _isReportingClient() {
+    return game.users.activeGM?.isSelf ?? false;
+  }
+}
@@ -0,0 +1,134 @@
+/**
+ * Journal entry sync - keeps world journal entries indexed on the backend
+ */
+
+import { MODULE_ID } from "../constants.mjs";
+
+/** Delay before sending an edited journal, so rapid edits collapse into one sync */
+const SYNC_DEBOUNCE_MS = 2000;
+
+/**
+ * Ships journal entries to the backend over WebSocket and re-syncs them when they change.
+ * The backend skips entries whose content hasn't changed, so a full sync on connect is cheap.
+ */
+export class JournalSync {
+  /**
+   * @param {WebSocketClient} ws - Connected WebSocket client
+   */
+  constructor(ws) {
+    this.ws = ws;
+    this.pending = new Map(); // journal id -> timeout handle
+  }
+
+  /**
+   * Register hooks and sync all journals whenever the connection is (re-)established
+   */
+  start() {
+    this.ws.on("connected", () => this.syncAll());
+    this.ws.on("journal_sync_result", (msg) => {
+      if (msg.status === "failed") {
+        console.warn(`${MODULE_ID} | Journal sync failed for ${msg.journal_id}:`, msg.error);
+      }
+    });
+
+    const onJournalChange = (journal) => this.scheduleSync(journal);
+    const onPageChange = (page) => page.parent && this.scheduleSync(page.parent);
+
+    Hooks.on("createJournalEntry", onJournalChange);
+    Hooks.on("updateJournalEntry", onJournalChange);
+    Hooks.on("createJournalEntryPage", onPageChange);
+    Hooks.on("updateJournalEntryPage", onPageChange);
+    Hooks.on("deleteJournalEntryPage", onPageChange);
+    Hooks.on("deleteJournalEntry", (journal) => {
+      if (!this._isSyncingClient()) return;
+      this.ws.send({ type: "journal_remove", journal_id: journal.id });
+    });
+
+    if (this.ws.authenticated) this.syncAll();
+  }
+
+  /**
+   * Send every journal entry in the world
+   */
+  syncAll() {
+    if (!this._isSyncingClient()) return;
+    console.log(`${MODULE_ID} | Syncing ${game.journal.size} journal entries`);
+    for (const journal of game.journal) {
+      this.sync(journal);
+    }
+  }
+
+  /**
+   * Sync a journal entry after a short delay
+   * @param {JournalEntry} journal
+   */
+  scheduleSync(journal) {
+    if (!this._isSyncingClient()) return;
+    clearTimeout(this.pending.get(journal.id));
+    this.pending.set(
+      journal.id,
+      setTimeout(() => {
+        this.pending.delete(journal.id);
+        this.sync(journal);
+      }, SYNC_DEBOUNCE_MS)
+    );
+  }
+
+  /**
+   * Send a single journal entry's text pages to the backend
+   * @param {JournalEntry} journal
+   */
+  sync(journal) {
+    const pages = journal.pages.contents
+      .filter((page) => page.type === "text")
+      .sort((a, b) => a.sort - b.sort)
+      .map((page) => ({
+        name: page.name,
+        html: this._stripSecrets(page.text?.content ?? ""),
+        access_level: this._accessLevel(page),
+      }));
+
+    this.ws.send({
+      type: "journal_sync",
+      journal_id: journal.id,
+      name: journal.name,
+      pages,
+    });
+  }
+
+  /**
+   * Pages players can observe are indexed at player level; everything else is GM only.
+   * A page inheriting its ownership uses the journal's.
+   * @param {JournalEntryPage} page
+   * @returns {number}
+   * @private
+   */
+  _accessLevel(page) {
+    const { INHERIT, NONE, OBSERVER } = CONST.DOCUMENT_OWNERSHIP_LEVELS;
+    let level = page.ownership?.default ?? INHERIT;
+    if (level === INHERIT) level = page.parent?.ownership?.default ?? NONE;
+    return level >= OBSERVER ? CONST.USER_ROLES.PLAYER : CONST.USER_ROLES.GAMEMASTER;
+  }
+
+  /**
+   * Remove secret blocks, which only GMs see in Foundry
+   * @param {string} html
+   * @returns {string}
+   * @private
+   */
+  _stripSecrets(html) {
+    const container = document.createElement("div");
+    container.innerHTML = html;
+    for (const secret of container.querySelectorAll("section.secret")) secret.remove();
+    return container.innerHTML;
+  }
+
+  /**
+   * Only the active GM syncs, so multiple connected GMs don't send duplicates
+   * @returns {boolean}
+   * @private
+   */
+  _isSyncingClient() {
+    return game.users.activeGM?.isSelf ?? false;
+  }
+}
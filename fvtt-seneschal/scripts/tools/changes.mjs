@@ -0,0 +1,162 @@
+/**
+ * Change tracking for world documents changed by tools, so the backend can
+ * keep an undo log
+ */
+
+import { FvttApiWrapper } from "../api/index.mjs";
+
+/**
+ * World documents each write tool changes. Embedded items and journal pages
+ * are tracked as updates to their parent document.
+ */
+const CHANGE_TARGETS = {
+  create_actor: { type: "actor", operation: "create" },
+  update_actor: { type: "actor", idArg: "actor_id", operation: "update" },
+  delete_actor: { type: "actor", idArg: "actor_id", operation: "delete" },
+  add_actor_item: { type: "actor", idArg: "actor_id", operation: "update" },
+  update_actor_item: { type: "actor", idArg: "actor_id", operation: "update" },
+  delete_actor_item: { type: "actor", idArg: "actor_id", operation: "update" },
+  create_item: { type: "item", operation: "create" },
+  update_item: { type: "item", idArg: "item_id", operation: "update" },
+  delete_item: { type: "item", idArg: "item_id", operation: "delete" },
+  create_scene: { type: "scene", operation: "create" },
+  update_scene: { type: "scene", idArg: "scene_id", operation: "update" },
+  delete_scene: { type: "scene", idArg: "scene_id", operation: "delete" },
+  create_journal: { type: "journal_entry", operation: "create" },
+  update_journal: { type: "journal_entry", idArg: "journal_id", operation: "update" },
+  delete_journal: { type: "journal_entry", idArg: "journal_id", operation: "delete" },
+  add_journal_page: { type: "journal_entry", idArg: "journal_id", operation: "update" },
+  update_journal_page: { type: "journal_entry", idArg: "journal_id", operation: "update" },
+  delete_journal_page: { type: "journal_entry", idArg: "journal_id", operation: "update" },
+  reorder_journal_pages: { type: "journal_entry", idArg: "journal_id", operation: "update" },
+  create_rollable_table: { type: "rollable_table", operation: "create" },
+  update_rollable_table: { type: "rollable_table", idArg: "table_id", operation: "update" },
+  delete_rollable_table: { type: "rollable_table", idArg: "table_id", operation: "delete" },
+};
+
+/**
+ * The world document a tool call changes, if it's tracked
+ * @param {string} tool - Tool name
+ * @param {Object} args - Tool arguments
+ * @returns {{type: string, id: string|undefined, operation: string}|null}
+ */
+function changeTarget(tool, args) {
+  if (args?.pack_id) return null;
+
+  if (tool === "fvtt_write") {
+    if (!FvttApiWrapper._getCollection(args.document_type ?? "")) return null;
+    return { type: args.document_type, id: args.data?.id, operation: args.operation };
+  }
+
+  const target = CHANGE_TARGETS[tool];
+  if (!target) return null;
+  return { type: target.type, id: target.idArg && args[target.idArg], operation: target.operation };
+}
+
+/**
+ * Run a tool, attaching the change it made to a world document as `_change`
+ * @param {string} tool - Tool name
+ * @param {Object} args - Tool arguments
+ * @param {Function} run - Runs the tool
+ * @returns {Promise<Object>}
+ */
+export async function trackChange(tool, args, run) {
+  const target = changeTarget(tool, args);
+  if (!target) return run();
+
+  const collection = FvttApiWrapper._getCollection(target.type);
+  const before = target.id ? (collection.get(target.id)?.toObject() ?? null) : null;
+  const result = await run();
+  if (!result || result.error || result.success === false) return result;
+
+  const id = target.id ?? result.id;
+  if (!id) return result;
+  const doc = collection.get(id);
+
+  return {
+    ...result,
+    _change: {
+      document_type: target.type,
+      document_id: id,
+      document_name: doc?.name ?? before?.name ?? null,
+      operation: target.operation,
+      before,
+      after: target.operation === "delete" ? null : (doc?.toObject() ?? null),
+    },
+  };
+}
+
+/**
+ * Make a document's embedded collections match a snapshot
+ * @param {foundry.abstract.Document} doc - Document to restore
+ * @param {Object} snapshot - The document's earlier `toObject()`
+ */
+async function restoreEmbedded(doc, snapshot) {
+  for (const [embeddedName, field] of Object.entries(doc.constructor.metadata.embedded ?? {})) {
+    const wanted = snapshot[field];
+    if (!Array.isArray(wanted)) continue;
+
+    const wantedIds = new Set(wanted.map((entry) => entry._id));
+    const currentIds = new Set(doc[field]?.map((entry) => entry.id) ?? []);
+    const removed = [...currentIds].filter((id) => !wantedIds.has(id));
+    const updated = wanted.filter((entry) => currentIds.has(entry._id));
+    const recreated = wanted.filter((entry) => !currentIds.has(entry._id));
+
+    if (removed.length) await doc.deleteEmbeddedDocuments(embeddedName, removed);
+    if (updated.length) {
+      await doc.updateEmbeddedDocuments(embeddedName, updated, { diff: false, recursive: false });
+    }
+    if (recreated.length) {
+      await doc.createEmbeddedDocuments(embeddedName, recreated, { keepId: true });
+    }
+  }
+}
+
+/**
+ * Revert a change recorded by `trackChange`
+ * @param {Object} args - The change: document_type, document_id, operation, before
+ * @param {Object} userContext - User context
+ * @returns {Promise<Object>}
+ */
+export async function undoChange(args, userContext) {
+  if (userContext.role < CONST.USER_ROLES.GAMEMASTER) {
+    return { error: "Only GMs can undo changes" };
+  }
+
+  const collection = FvttApiWrapper._getCollection(args.document_type ?? "");
+  if (!collection) {
+    return { error: `Unknown document type: ${args.document_type}` };
+  }
+  const doc = collection.get(args.document_id);
+
+  try {
+    switch (args.operation) {
+      case "create":
+        if (!doc) return { error: "Document not found" };
+        await doc.delete();
+        return { success: true };
+      case "update": {
+        if (!doc) return { error: "Document not found" };
+        if (!args.before) return { error: "No earlier version of the document was recorded" };
+        const embeddedFields = Object.values(doc.constructor.metadata.embedded ?? {});
+        const fields = Object.fromEntries(
+          Object.entries(args.before).filter(([key]) => !embeddedFields.includes(key))
+        );
+        await doc.update(fields, { diff: false, recursive: false });
+        await restoreEmbedded(doc, args.before);
+        return { success: true };
+      }
+      case "delete": {
+        if (doc) return { error: "Document already exists" };
+        if (!args.before) return { error: "No copy of the deleted document was recorded" };
+        const cls = FvttApiWrapper._getDocumentClass(args.document_type);
+        const restored = await cls.create(args.before, { keepId: true });
+        return { success: true, id: restored.id };
+      }
+      default:
+        return { error: `Unknown operation: ${args.operation}` };
+    }
+  } catch (error) {
+    return { error: error.message };
+  }
+}
@@ -0,0 +1,99 @@
+/**
+ * Tools registered by other modules
+ *
+ * System-specific modules can offer MCP clients their own tools without a
+ * backend release: they register a definition and a handler here, and the
+ * GM client sends the definitions to the backend when it connects. Calls
+ * come back as chat_tool_call messages like the built-in FVTT tools.
+ *
+ * @example
+ * game.modules.get("fvtt-seneschal").api.registerTool(
+ *   {
+ *     name: "pf2e_spell_lookup",
+ *     description: "Look up a spell in the world's compendiums",
+ *     parameters: {
+ *       type: "object",
+ *       properties: { name: { type: "string", description: "Spell name" } },
+ *       required: ["name"],
+ *     },
+ *     readOnly: true,
+ *   },
+ *   async (args, userContext) => ({ spell: await findSpell(args.name) })
+ * );
+ */
+
+import { MODULE_ID } from "../constants.mjs";
+
+/** Registered tools by name */
+const customTools = new Map();
+
+/** Called when the set of tools changes */
+let onChange = null;
+
+/**
+ * Register a tool, replacing any registered under the same name
+ * @param {Object} definition - Tool definition
+ * @param {string} definition.name - Tool name: lowercase letters, digits and underscores
+ * @param {string} definition.description - What the tool does, for the model
+ * @param {Object} [definition.parameters] - JSON Schema of the arguments (type "object")
+ * @param {number} [definition.requiredRole=4] - Lowest FVTT role that may call it
+ * @param {boolean} [definition.readOnly=false] - Whether it only reads world data
+ * @param {Function} handler - async (args, userContext) => result
+ */
+export function registerTool(definition, handler) {
+  if (typeof handler !== "function") {
+    throw new Error(`Seneschal tool ${definition?.name} needs a handler function`);
+  }
+  customTools.set(definition.name, { definition, handler });
+  onChange?.();
+}
+
+/**
+ * Remove a registered tool
+ * @param {string} name - Tool name
+ */
+export function unregisterTool(name) {
+  if (customTools.delete(name)) onChange?.();
+}
+
+/**
+ * The handler of a registered tool
+ * @param {string} name - Tool name
+ * @returns {Function|undefined}
+ */
+export function customToolHandler(name) {
+  return customTools.get(name)?.handler;
+}
+
+/**
+ * Definitions of registered tools, as sent to the backend
+ * @returns {Object[]}
+ */
+export function customToolDefinitions() {
+  return [...customTools.values()].map(({ definition }) => ({
+    name: definition.name,
+    description: definition.description,
+    parameters: definition.parameters ?? { type: "object", properties: {} },
+    required_role: definition.requiredRole ?? 4,
+    read_only: definition.readOnly ?? false,
+  }));
+}
+
+/**
+ * Send registered tools to the backend on connect and whenever they change
+ * (GM client only)
+ * @param {WebSocketClient} ws - WebSocket client
+ */
+export function startCustomToolSync(ws) {
+  const sync = () => {
+    if (ws.authenticated) ws.registerTools(customToolDefinitions());
+  };
+  onChange = sync;
+  ws.on("connected", sync);
+  ws.on("tools_registered", (msg) => {
+    for (const { name, reason } of msg.rejected) {
+      console.warn(`${MODULE_ID} | Tool ${name} was refused by the backend: ${reason}`);
+    }
+  });
+  sync();
+}
@@ -3,19 +3,30 @@
  */
 
 import { FvttApiWrapper } from "../api/index.mjs";
+import { trackChange, undoChange } from "./changes.mjs";
+import { customToolHandler } from "./custom.mjs";
 
 /**
  * Executes FVTT tools requested by the backend
  */
 export class ToolExecutor {
   /**
-   * Execute a tool
+   * Execute a tool, attaching the change it made to a world document as
+   * `_change` for the backend's undo log
    * @param {string} tool - Tool name
    * @param {Object} args - Tool arguments
    * @param {Object} userContext - User context
    * @returns {Promise<Object>}
    */
   static async execute(tool, args, userContext) {
+    return trackChange(tool, args, () => this._run(tool, args, userContext));
+  }
+
+  /**
+   * Run a tool
+   * @private
+   */
+  static async _run(tool, args, userContext) {
     switch (tool) {
       case "fvtt_read":
         return FvttApiWrapper.read(args.document_type, args.document_id, userContext);
@@ -29,6 +40,9 @@ export class ToolExecutor {
       case "dice_roll":
         return FvttApiWrapper.rollDice(args.formula, args.label, userContext);
 
+      case "chat_log":
+        return FvttApiWrapper.getChatLog(args, userContext);
+
       case "system_schema":
         return FvttApiWrapper.getSystemCapabilities();
 
@@ -245,8 +259,15 @@ export class ToolExecutor {
       case "export_to_compendium":
         return FvttApiWrapper.exportToCompendium(args, userContext);
 
-      default:
+      // Undo log
+      case "fvtt_undo_change":
+        return undoChange(args, userContext);
+
+      default: {
+        const handler = customToolHandler(tool);
+        if (handler) return handler(args, userContext);
         return { error: `Unknown tool: ${tool}` };
+      }
     }
   }
 }
@@ -0,0 +1,47 @@
+/**
+ * Campaign date display
+ *
+ * Shows the campaign clock's current Imperial date above the player list.
+ * The date is fetched on load and refreshed periodically, since MCP clients
+ * advance it as time passes in play.
+ */
+
+import { MODULE_ID } from "../constants.mjs";
+import { BackendClient } from "../clients/backend.mjs";
+
+/** How often to refresh the date, in milliseconds */
+const REFRESH_INTERVAL_MS = 5 * 60 * 1000;
+
+let currentDate = null;
+
+/**
+ * Fetch the current date and re-render the player list
+ */
+async function refreshCampaignDate() {
+  try {
+    const { date } = await new BackendClient().getCampaignDate(game.world.id);
+    currentDate = date;
+  } catch (error) {
+    console.warn(`${MODULE_ID} | Could not fetch the campaign date:`, error);
+    currentDate = null;
+  }
+  ui.players?.render();
+}
+
+/**
+ * Start showing the campaign date
+ */
+export function startCampaignDateDisplay() {
+  Hooks.on("renderPlayers", (_app, html) => {
+    html.querySelector(".seneschal-campaign-date")?.remove();
+    if (!currentDate) return;
+
+    const element = document.createElement("div");
+    element.classList.add("seneschal-campaign-date");
+    element.textContent = game.i18n.format("SENESCHAL.CampaignDate", { date: currentDate });
+    html.prepend(element);
+  });
+
+  refreshCampaignDate();
+  setInterval(refreshCampaignDate, REFRESH_INTERVAL_MS);
+}
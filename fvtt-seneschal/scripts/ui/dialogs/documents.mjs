@@ -6,6 +6,15 @@ import { MODULE_ID } from "../../constants.mjs";
 import { BackendClient } from "../../clients/backend.mjs";
 import { ImageBrowserDialog } from "./images.mjs";
 
+/** Labels for server-side upload stages reported over the WebSocket */
+const UPLOAD_STAGE_LABELS = {
+  receiving: "SENESCHAL.Documents.Uploading",
+  received: "SENESCHAL.Documents.UploadReceived",
+  hashing: "SENESCHAL.Documents.UploadHashing",
+  validated: "SENESCHAL.Documents.UploadValidated",
+  queued: "SENESCHAL.Documents.UploadQueued",
+};
+
 /**
  * Dialog for managing documents in the Seneschal backend
  */
@@ -29,6 +38,7 @@ export class DocumentManagementDialog extends FormApplication {
     this.isLoading = false;
     this.error = null;
     this.uploadProgress = null;
+    this.uploadStage = null; // Server-side upload stage, once the file is sent
     this.processingDoc = null; // Document ID currently being re-processed (images)
     this._wsUnsubscribeDoc = null; // WebSocket document event unsubscribe function
     this._wsUnsubscribeCaptioning = null; // WebSocket captioning event unsubscribe function
@@ -67,6 +77,7 @@ export class DocumentManagementDialog extends FormApplication {
       isLoading: this.isLoading,
       error: this.error,
       uploadProgress: this.uploadProgress,
+      uploadStage: this.uploadStage,
       processingDoc: this.processingDoc,
     };
   }
@@ -218,6 +229,8 @@ export class DocumentManagementDialog extends FormApplication {
         phaseText = `${game.i18n.localize("SENESCHAL.Documents.PhaseEmbedding")} (${doc.processing_progress}/${doc.processing_total})`;
       } else if (doc.processing_phase === "extracting_images") {
         phaseText = game.i18n.localize("SENESCHAL.Documents.PhaseExtractingImages");
+      } else if (doc.processing_phase === "waiting_for_disk_space") {
+        phaseText = game.i18n.localize("SENESCHAL.Documents.PhaseWaitingForDiskSpace");
       } else if (doc.processing_phase === "captioning") {
         phaseText = `${game.i18n.localize("SENESCHAL.Documents.PhaseCaptioning")} (${doc.processing_progress}/${doc.processing_total})`;
       } else if (doc.processing_phase) {
@@ -400,8 +413,10 @@ export class DocumentManagementDialog extends FormApplication {
 
     const accessLevel = form.querySelector('select[name="access_level"]').value;
     const tags = form.querySelector('input[name="tags"]').value.trim();
+    const password = form.querySelector('input[name="password"]').value;
 
     this.uploadProgress = 0;
+    this.uploadStage = null;
     this.render(false);
 
     try {
@@ -411,15 +426,20 @@ export class DocumentManagementDialog extends FormApplication {
           title,
           accessLevel,
           tags: tags || undefined,
+          password: password || undefined,
         },
-        (progress) => {
+        (progress, stage) => {
           this.uploadProgress = progress;
+          this.uploadStage = UPLOAD_STAGE_LABELS[stage]
+            ? game.i18n.localize(UPLOAD_STAGE_LABELS[stage])
+            : null;
           this.render(false);
         }
       );
 
       ui.notifications.info(game.i18n.localize("SENESCHAL.Documents.UploadSuccess"));
       this.uploadProgress = null;
+      this.uploadStage = null;
 
       // Clear form
       form.reset();
@@ -433,6 +453,7 @@ export class DocumentManagementDialog extends FormApplication {
         { permanent: true }
       );
       this.uploadProgress = null;
+      this.uploadStage = null;
       this.render(false);
     }
   }
@@ -448,6 +469,7 @@ export class DocumentManagementDialog extends FormApplication {
     const currentTitle = row.dataset.documentTitle;
     const currentAccess = row.dataset.documentAccess;
     const currentTags = row.dataset.documentTags;
+    const currentPriority = row.dataset.documentPriority || "0";
 
     // Create the edit dialog content
     const content = `
@@ -469,6 +491,11 @@ export class DocumentManagementDialog extends FormApplication {
           <label for="edit-tags">${game.i18n.localize("SENESCHAL.Documents.Tags")}</label>
           <input type="text" id="edit-tags" name="tags" value="${currentTags}" placeholder="${game.i18n.localize("SENESCHAL.Documents.TagsPlaceholder")}" />
         </div>
+        <div class="form-group">
+          <label for="edit-priority">${game.i18n.localize("SENESCHAL.Documents.Priority")}</label>
+          <input type="number" id="edit-priority" name="priority" value="${currentPriority}" min="-10" max="10" step="1" />
+          <p class="hint">${game.i18n.localize("SENESCHAL.Documents.PriorityHint")}</p>
+        </div>
       </form>
     `;
 
@@ -483,6 +510,7 @@ export class DocumentManagementDialog extends FormApplication {
             const title = html.find("#edit-title").val().trim();
             const accessLevel = html.find("#edit-access").val();
             const tags = html.find("#edit-tags").val().trim();
+            const priority = Number(html.find("#edit-priority").val()) || 0;
 
             if (!title) {
               ui.notifications.error(game.i18n.localize("SENESCHAL.Documents.TitleRequired"));
@@ -494,6 +522,7 @@ export class DocumentManagementDialog extends FormApplication {
                 title,
                 access_level: accessLevel,
                 tags: tags || undefined,
+                priority,
               });
               ui.notifications.info(game.i18n.localize("SENESCHAL.Documents.EditSuccess"));
               await this._loadDocuments();
@@ -34,6 +34,44 @@ const SETTING_CATEGORIES = {
       },
     },
   },
+  modelRouting: {
+    label: "SENESCHAL.Settings.Backend.Section.ModelRouting",
+    fields: {
+      "model_routing.chat": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.ModelRouting.Chat",
+        hint: "SENESCHAL.Settings.Backend.ModelRouting.ChatHint",
+      },
+      "model_routing.summarization": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.ModelRouting.Summarization",
+        hint: "SENESCHAL.Settings.Backend.ModelRouting.SummarizationHint",
+      },
+      "model_routing.captioning": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.ModelRouting.Captioning",
+        hint: "SENESCHAL.Settings.Backend.ModelRouting.CaptioningHint",
+      },
+      "model_routing.query_expansion": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.ModelRouting.QueryExpansion",
+        hint: "SENESCHAL.Settings.Backend.ModelRouting.QueryExpansionHint",
+      },
+      "model_routing.title_generation": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.ModelRouting.TitleGeneration",
+        hint: "SENESCHAL.Settings.Backend.ModelRouting.TitleGenerationHint",
+      },
+      "model_routing.failover_cooldown_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.ModelRouting.FailoverCooldown",
+        hint: "SENESCHAL.Settings.Backend.ModelRouting.FailoverCooldownHint",
+        min: 0,
+        max: 3600,
+        step: 30,
+      },
+    },
+  },
   llm: {
     label: "SENESCHAL.Settings.Backend.Section.LLM",
     fields: {
@@ -79,6 +117,47 @@ const SETTING_CATEGORIES = {
         max: 512,
         step: 16,
       },
+      "embeddings.normalize_unicode": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Embeddings.NormalizeUnicode",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.NormalizeUnicodeHint",
+      },
+      "embeddings.repair_hyphenation": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Embeddings.RepairHyphenation",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.RepairHyphenationHint",
+      },
+      "embeddings.strip_headers_footers": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Embeddings.StripHeadersFooters",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.StripHeadersFootersHint",
+      },
+      "embeddings.lowercase": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Embeddings.Lowercase",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.LowercaseHint",
+      },
+      "embeddings.priority_boost": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Embeddings.PriorityBoost",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.PriorityBoostHint",
+        min: 0,
+        max: 0.5,
+        step: 0.01,
+      },
+      "embeddings.query_expansion": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Embeddings.QueryExpansion",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.QueryExpansionHint",
+      },
+      "embeddings.min_answer_confidence": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Embeddings.MinAnswerConfidence",
+        hint: "SENESCHAL.Settings.Backend.Embeddings.MinAnswerConfidenceHint",
+        min: 0,
+        max: 1,
+        step: 0.05,
+      },
     },
   },
   agentic: {
@@ -88,7 +167,7 @@ const SETTING_CATEGORIES = {
         type: "number",
         label: "SENESCHAL.Settings.Backend.Agentic.HardTimeout",
         hint: "SENESCHAL.Settings.Backend.Agentic.HardTimeoutHint",
-        min: 60,
+        min: 0,
         max: 1800,
         step: 30,
       },
@@ -108,6 +187,67 @@ const SETTING_CATEGORIES = {
         max: 4294967295,
         step: 1,
       },
+      "agentic_loop.max_tool_call_pause_threshold": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.MaxToolCallPauseThreshold",
+        hint: "SENESCHAL.Settings.Backend.Agentic.MaxToolCallPauseThresholdHint",
+        min: 1,
+        max: 4294967295,
+        step: 1,
+      },
+      "agentic_loop.max_hard_timeout_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.MaxHardTimeout",
+        hint: "SENESCHAL.Settings.Backend.Agentic.MaxHardTimeoutHint",
+        min: 60,
+        max: 7200,
+        step: 60,
+      },
+      "agentic_loop.tool_result_max_tokens": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.ToolResultMaxTokens",
+        hint: "SENESCHAL.Settings.Backend.Agentic.ToolResultMaxTokensHint",
+        min: 500,
+        max: 100000,
+        step: 500,
+      },
+      "agentic_loop.turn_result_token_budget": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.TurnResultTokenBudget",
+        hint: "SENESCHAL.Settings.Backend.Agentic.TurnResultTokenBudgetHint",
+        min: 1000,
+        max: 1000000,
+        step: 1000,
+      },
+      "agentic_loop.repeat_window_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.RepeatWindow",
+        hint: "SENESCHAL.Settings.Backend.Agentic.RepeatWindowHint",
+        min: 0,
+        max: 3600,
+        step: 10,
+      },
+      "agentic_loop.repeat_nudge_threshold": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.RepeatNudgeThreshold",
+        hint: "SENESCHAL.Settings.Backend.Agentic.RepeatNudgeThresholdHint",
+        min: 0,
+        max: 20,
+        step: 1,
+      },
+      "agentic_loop.require_write_approval": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Agentic.RequireWriteApproval",
+        hint: "SENESCHAL.Settings.Backend.Agentic.RequireWriteApprovalHint",
+      },
+      "agentic_loop.approval_timeout_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Agentic.ApprovalTimeout",
+        hint: "SENESCHAL.Settings.Backend.Agentic.ApprovalTimeoutHint",
+        min: 30,
+        max: 3600,
+        step: 30,
+      },
     },
   },
   limits: {
@@ -121,6 +261,138 @@ const SETTING_CATEGORIES = {
         max: 1073741824,
         step: 1048576,
       },
+      "limits.allowed_document_types": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Limits.AllowedDocumentTypes",
+        hint: "SENESCHAL.Settings.Backend.Limits.AllowedDocumentTypesHint",
+      },
+      "limits.max_archive_uncompressed_bytes": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Limits.MaxArchiveSize",
+        hint: "SENESCHAL.Settings.Backend.Limits.MaxArchiveSizeHint",
+        min: 1048576,
+        max: 17179869184,
+        step: 1048576,
+      },
+    },
+  },
+  digest: {
+    label: "SENESCHAL.Settings.Backend.Section.Digest",
+    fields: {
+      "digest.schedule": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Digest.Schedule",
+        hint: "SENESCHAL.Settings.Backend.Digest.ScheduleHint",
+      },
+      "digest.journal_folder": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Digest.JournalFolder",
+        hint: "SENESCHAL.Settings.Backend.Digest.JournalFolderHint",
+      },
+    },
+  },
+  maintenance: {
+    label: "SENESCHAL.Settings.Backend.Section.Maintenance",
+    fields: {
+      "maintenance.schedule": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Maintenance.Schedule",
+        hint: "SENESCHAL.Settings.Backend.Maintenance.ScheduleHint",
+      },
+      "maintenance.vacuum_idle_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Maintenance.VacuumIdle",
+        hint: "SENESCHAL.Settings.Backend.Maintenance.VacuumIdleHint",
+        min: 0,
+        max: 86400,
+        step: 60,
+      },
+    },
+  },
+  retention: {
+    label: "SENESCHAL.Settings.Backend.Section.Retention",
+    fields: {
+      "retention.source_files_days": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Retention.SourceFiles",
+        hint: "SENESCHAL.Settings.Backend.Retention.SourceFilesHint",
+        min: 0,
+        max: 3650,
+        step: 1,
+      },
+      "retention.uncaptioned_images_days": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Retention.UncaptionedImages",
+        hint: "SENESCHAL.Settings.Backend.Retention.UncaptionedImagesHint",
+        min: 0,
+        max: 3650,
+        step: 1,
+      },
+      "retention.image_description_cache_days": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Retention.ImageDescriptions",
+        hint: "SENESCHAL.Settings.Backend.Retention.ImageDescriptionsHint",
+        min: 0,
+        max: 3650,
+        step: 1,
+      },
+      "retention.audit_log_days": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Retention.AuditLog",
+        hint: "SENESCHAL.Settings.Backend.Retention.AuditLogHint",
+        min: 0,
+        max: 3650,
+        step: 1,
+      },
+    },
+  },
+  disk: {
+    label: "SENESCHAL.Settings.Backend.Section.Disk",
+    fields: {
+      "disk.min_free_mb": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Disk.MinFree",
+        hint: "SENESCHAL.Settings.Backend.Disk.MinFreeHint",
+        min: 0,
+        max: 1048576,
+        step: 256,
+      },
+      "disk.warn_free_mb": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Disk.WarnFree",
+        hint: "SENESCHAL.Settings.Backend.Disk.WarnFreeHint",
+        min: 0,
+        max: 1048576,
+        step: 256,
+      },
+    },
+  },
+  tts: {
+    label: "SENESCHAL.Settings.Backend.Section.Tts",
+    fields: {
+      "tts.url": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Tts.Url",
+        hint: "SENESCHAL.Settings.Backend.Tts.UrlHint",
+      },
+      "tts.engine": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Tts.Engine",
+        hint: "SENESCHAL.Settings.Backend.Tts.EngineHint",
+      },
+      "tts.voice": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Tts.Voice",
+        hint: "SENESCHAL.Settings.Backend.Tts.VoiceHint",
+      },
+      "tts.timeout_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Tts.Timeout",
+        hint: "SENESCHAL.Settings.Backend.Tts.TimeoutHint",
+        min: 5,
+        max: 600,
+        step: 5,
+      },
     },
   },
   advanced: {
@@ -136,6 +408,21 @@ const SETTING_CATEGORIES = {
         label: "SENESCHAL.Settings.Backend.Advanced.McpPath",
         hint: "SENESCHAL.Settings.Backend.Advanced.McpPathHint",
       },
+      "mcp.world_id": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Advanced.McpWorldId",
+        hint: "SENESCHAL.Settings.Backend.Advanced.McpWorldIdHint",
+      },
+      "mcp.restrict_write_tools": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Advanced.McpRestrictWriteTools",
+        hint: "SENESCHAL.Settings.Backend.Advanced.McpRestrictWriteToolsHint",
+      },
+      "mcp.default_style": {
+        type: "text",
+        label: "SENESCHAL.Settings.Backend.Advanced.McpDefaultStyle",
+        hint: "SENESCHAL.Settings.Backend.Advanced.McpDefaultStyleHint",
+      },
       "traveller_map.base_url": {
         type: "text",
         label: "SENESCHAL.Settings.Backend.Advanced.TravellerMapUrl",
@@ -149,6 +436,19 @@ const SETTING_CATEGORIES = {
         max: 120,
         step: 5,
       },
+      "traveller_map.cache_ttl_secs": {
+        type: "number",
+        label: "SENESCHAL.Settings.Backend.Advanced.TravellerMapCacheTtl",
+        hint: "SENESCHAL.Settings.Backend.Advanced.TravellerMapCacheTtlHint",
+        min: 0,
+        max: 31536000,
+        step: 3600,
+      },
+      "traveller_map.offline": {
+        type: "checkbox",
+        label: "SENESCHAL.Settings.Backend.Advanced.TravellerMapOffline",
+        hint: "SENESCHAL.Settings.Backend.Advanced.TravellerMapOfflineHint",
+      },
       "traveller_worlds.base_url": {
         type: "text",
         label: "SENESCHAL.Settings.Backend.Advanced.TravellerWorldsUrl",
@@ -163,6 +463,12 @@ const SETTING_CATEGORIES = {
   },
 };
 
+/** Log levels offered for the live log, least verbose first */
+const LOG_LEVELS = ["error", "warn", "info", "debug", "trace"];
+
+/** Log lines kept in the live log box */
+const MAX_LOG_LINES = 500;
+
 /**
  * Backend Settings Dialog
  */
@@ -184,6 +490,13 @@ export class BackendSettingsDialog extends FormApplication {
     this.settings = {};
     this.overridden = [];
     this.models = [];
+    this.localModels = [];
+    this.modelPulls = {};
+    this._wsUnsubscribePull = null;
+    this._wsUnsubscribeLog = null;
+    this.logLines = [];
+    this.logLevel = "info";
+    this.logTargets = "";
     this.isLoading = true;
     this.error = null;
     this.pendingChanges = {};
@@ -235,18 +548,141 @@ export class BackendSettingsDialog extends FormApplication {
 
     return {
       categories,
+      localModels: this.localModels.map((m) => ({
+        name: m.name,
+        size: this._formatSize(m.size),
+        modified: m.modified_at ? new Date(m.modified_at).toLocaleDateString() : "",
+      })),
+      modelPulls: Object.values(this.modelPulls).map((p) => ({
+        model: p.model,
+        status: p.status,
+        error: p.error,
+        percent: p.total ? Math.floor(((p.completed || 0) / p.total) * 100) : null,
+      })),
+      logLevels: LOG_LEVELS.map((level) => ({ value: level, selected: level === this.logLevel })),
+      logTargets: this.logTargets,
+      logWatching: !!this._wsUnsubscribeLog,
+      logAvailable: !!globalThis.seneschalWS?.authenticated,
       isLoading: this.isLoading,
       error: this.error,
     };
   }
 
+  /**
+   * Format a byte count for display
+   */
+  _formatSize(bytes) {
+    if (!bytes) return "";
+    const gb = bytes / 1024 ** 3;
+    return gb >= 1 ? `${gb.toFixed(1)} GB` : `${Math.round(bytes / 1024 ** 2)} MB`;
+  }
+
   async _render(force = false, options = {}) {
     await super._render(force, options);
     if (force) {
+      this._subscribeToModelPulls();
       await this._loadSettings();
     }
   }
 
+  /**
+   * Listen for model pull progress over the WebSocket
+   * @private
+   */
+  _subscribeToModelPulls() {
+    if (this._wsUnsubscribePull || !globalThis.seneschalWS?.authenticated) return;
+
+    this._wsUnsubscribePull = globalThis.seneschalWS.on("model_pull_progress", (update) => {
+      this.modelPulls[update.model] = update;
+      if (update.done) {
+        if (update.error) {
+          ui.notifications.error(
+            game.i18n.format("SENESCHAL.Settings.Backend.ManageModels.PullFailed", {
+              model: update.model,
+              error: update.error,
+            })
+          );
+        } else {
+          ui.notifications.info(
+            game.i18n.format("SENESCHAL.Settings.Backend.ManageModels.PullComplete", {
+              model: update.model,
+            })
+          );
+        }
+        // Refresh model lists so the new model can be selected
+        this._loadSettings();
+      } else {
+        this.render(false);
+      }
+    });
+  }
+
+  /**
+   * Start or stop watching the backend log. Events are appended to the log
+   * box directly, so watching doesn't re-render the form and lose edits.
+   * @private
+   */
+  _toggleLogStream(html) {
+    const ws = globalThis.seneschalWS;
+    if (this._wsUnsubscribeLog) {
+      this._stopLogStream();
+      this.render(false);
+      return;
+    }
+    if (!ws?.authenticated) return;
+
+    this.logLevel = html.find(".seneschal-log-level").val() || "info";
+    this.logTargets = html.find(".seneschal-log-targets").val()?.trim() ?? "";
+    const targets = this.logTargets
+      .split(",")
+      .map((t) => t.trim())
+      .filter(Boolean);
+
+    this.logLines = [];
+    this._wsUnsubscribeLog = ws.on("log_event", (event) => this._appendLogLine(event));
+    ws.subscribeToLogs(this.logLevel, targets);
+    this.render(false);
+  }
+
+  /**
+   * @private
+   */
+  _stopLogStream() {
+    if (!this._wsUnsubscribeLog) return;
+    this._wsUnsubscribeLog();
+    this._wsUnsubscribeLog = null;
+    globalThis.seneschalWS?.unsubscribeFromLogs();
+  }
+
+  /**
+   * Add a log event to the log box, keeping the most recent lines
+   * @private
+   */
+  _appendLogLine(event) {
+    const time = new Date(event.timestamp).toLocaleTimeString();
+    const target = event.target.replace(/^seneschal_service::/, "");
+    const fields = event.fields ? ` ${event.fields}` : "";
+    this.logLines.push(`${time} ${event.level.padEnd(5)} ${target}: ${event.message}${fields}`);
+    if (this.logLines.length > MAX_LOG_LINES) {
+      this.logLines.splice(0, this.logLines.length - MAX_LOG_LINES);
+    }
+
+    const box = this.element?.find(".seneschal-log-lines")[0];
+    if (!box) return;
+    const atBottom = box.scrollTop + box.clientHeight >= box.scrollHeight - 4;
+    box.textContent = this.logLines.join("\n");
+    if (atBottom) box.scrollTop = box.scrollHeight;
+  }
+
+  close(options) {
+    if (this._wsUnsubscribePull) {
+      this._wsUnsubscribePull();
+      this._wsUnsubscribePull = null;
+    }
+    this._stopLogStream();
+    return super.close(options);
+  }
+
   async _loadSettings() {
     const backendUrl = getSetting(SETTINGS.BACKEND_URL);
     if (!backendUrl) {
@@ -281,14 +717,18 @@ export class BackendSettingsDialog extends FormApplication {
       const client = new BackendClient();
 
       // Load settings and models in parallel
-      const [settingsResponse, models] = await Promise.all([
+      const [settingsResponse, models, localModels, modelPulls] = await Promise.all([
         client.getSettings(),
         client.getModels(),
+        client.listLocalModels(),
+        client.getModelPulls(),
       ]);
 
       this.settings = settingsResponse.settings;
       this.overridden = settingsResponse.overridden;
       this.models = models;
+      this.localModels = localModels;
+      this.modelPulls = Object.fromEntries(modelPulls.map((p) => [p.model, p]));
       this.isLoading = false;
       this.pendingChanges = {};
       this.render(false);
@@ -311,6 +751,64 @@ export class BackendSettingsDialog extends FormApplication {
       const key = ev.currentTarget.dataset.key;
       await this._resetSetting(key);
     });
+
+    // Model management
+    html.find(".seneschal-pull-model").click(() => {
+      const model = html.find(".seneschal-pull-model-name").val()?.trim();
+      if (model) this._pullModel(model);
+    });
+    html.find(".seneschal-delete-model").click(async (ev) => {
+      await this._deleteModel(ev.currentTarget.dataset.model);
+    });
+
+    // Live log
+    html.find(".seneschal-toggle-log").click(() => this._toggleLogStream(html));
+    const box = html.find(".seneschal-log-lines")[0];
+    if (box) {
+      box.textContent = this.logLines.join("\n");
+      box.scrollTop = box.scrollHeight;
+    }
+  }
+
+  async _pullModel(model) {
+    try {
+      const client = new BackendClient();
+      const result = await client.pullModel(model);
+      if (!result.started) {
+        ui.notifications.warn(
+          game.i18n.format("SENESCHAL.Settings.Backend.ManageModels.PullRunning", { model })
+        );
+        return;
+      }
+      ui.notifications.info(
+        game.i18n.format("SENESCHAL.Settings.Backend.ManageModels.PullStarted", { model })
+      );
+      this.modelPulls[model] = { model, status: "starting", done: false };
+      this.render(false);
+    } catch (error) {
+      ui.notifications.error(error.message);
+    }
+  }
+
+  async _deleteModel(model) {
+    const confirmed = await Dialog.confirm({
+      title: game.i18n.localize("SENESCHAL.Settings.Backend.ManageModels.Delete"),
+      content: `<p>${game.i18n.format("SENESCHAL.Settings.Backend.ManageModels.DeleteConfirm", { model })}</p>`,
+      yes: () => true,
+      no: () => false,
+    });
+    if (!confirmed) return;
+
+    try {
+      const client = new BackendClient();
+      await client.deleteModel(model);
+      ui.notifications.info(
+        game.i18n.format("SENESCHAL.Settings.Backend.ManageModels.Deleted", { model })
+      );
+      await this._loadSettings();
+    } catch (error) {
+      ui.notifications.error(error.message);
+    }
   }
 
   async _resetSetting(key) {
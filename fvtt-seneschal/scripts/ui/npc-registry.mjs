@@ -0,0 +1,60 @@
+/**
+ * NPC registry integration
+ *
+ * Adds an Actor directory context option so the GM can register an actor
+ * as a recurring NPC, letting MCP clients look them up and relate them to
+ * other NPCs.
+ */
+
+import { BackendClient } from "../clients/backend.mjs";
+
+/**
+ * Strip HTML from an actor's biography
+ * @param {string} html
+ * @returns {string}
+ */
+function plainText(html) {
+  const element = document.createElement("div");
+  element.innerHTML = html ?? "";
+  return element.textContent.trim();
+}
+
+/**
+ * Send an actor to the NPC registry
+ * @param {Actor} actor
+ */
+async function addActorToRegistry(actor) {
+  try {
+    const biography = plainText(actor.system?.biography ?? actor.system?.details?.biography?.value);
+    await new BackendClient().setNpc(
+      {
+        name: actor.name,
+        actor_uuid: actor.uuid,
+        description: biography || null,
+      },
+      game.world.id
+    );
+    ui.notifications.info(game.i18n.format("SENESCHAL.NpcRegistry.Added", { name: actor.name }));
+  } catch (error) {
+    ui.notifications.error(
+      game.i18n.format("SENESCHAL.NpcRegistry.Failed", { name: actor.name, error: error.message })
+    );
+  }
+}
+
+/**
+ * Register the "Add to Seneschal NPCs" Actor directory context option (GM only)
+ */
+export function registerNpcContextOption() {
+  Hooks.on("getActorContextOptions", (_app, options) => {
+    options.push({
+      name: "SENESCHAL.NpcRegistry.Add",
+      icon: '<i class="fas fa-address-book"></i>',
+      condition: () => game.user.isGM && new BackendClient().isConfigured(),
+      callback: (li) => {
+        const actor = game.actors.get(li.dataset.entryId);
+        if (actor) addActorToRegistry(actor);
+      },
+    });
+  });
+}
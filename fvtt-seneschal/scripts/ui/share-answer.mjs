@@ -0,0 +1,43 @@
+/**
+ * Sharing answers with players
+ *
+ * Adds a chat message context option so the GM can share an answer with
+ * connected players. The backend removes secret blocks and GM notes before
+ * broadcasting it, and players see it in a dialog.
+ */
+
+/**
+ * Register the "Share with Players" chat message context option (GM only)
+ */
+export function registerShareAnswerContextOption() {
+  Hooks.on("getChatMessageContextOptions", (_app, options) => {
+    options.push({
+      name: "SENESCHAL.ShareAnswer.Share",
+      icon: '<i class="fas fa-share-from-square"></i>',
+      condition: () => game.user.isGM && Boolean(globalThis.seneschalWS?.authenticated),
+      callback: (li) => {
+        const message = game.messages.get(li.dataset.messageId);
+        if (!message) return;
+        globalThis.seneschalWS.shareAnswer(message.content, {
+          title: message.speaker?.alias ?? null,
+        });
+      },
+    });
+  });
+}
+
+/**
+ * Show an answer the GM shared
+ * @param {Object} msg - shared_answer message
+ */
+export function showSharedAnswer(msg) {
+  const content = Handlebars.escapeExpression(msg.content).replace(/\n/g, "<br>");
+  new Dialog({
+    title: msg.title ?? game.i18n.localize("SENESCHAL.ShareAnswer.Title"),
+    content: `<p>${content}</p>`,
+    buttons: {
+      close: { label: game.i18n.localize("Close") },
+    },
+    default: "close",
+  }).render(true);
+}
@@ -0,0 +1,86 @@
+/**
+ * Tool chain progress display (GM only)
+ *
+ * Shows what MCP conversations are doing above the player list: the tool
+ * running and how far into the turn it is, calls waiting for approval with
+ * their place in the queue, and chains paused for the user. A status is
+ * cleared once its conversation has been quiet for a while.
+ */
+
+/** How long a finished or paused status stays up, in milliseconds */
+const CLEAR_AFTER_MS = 15 * 1000;
+
+/** Latest status line per conversation */
+const statuses = new Map();
+
+/**
+ * Describe a progress message
+ * @param {Object} msg - tool_call_* or tool_chain_paused message
+ * @returns {string}
+ */
+function describe(msg) {
+  switch (msg.type) {
+    case "tool_call_started":
+      return game.i18n.format("SENESCHAL.ToolProgress.Running", { tool: msg.tool, call: msg.call });
+    case "tool_call_finished":
+      return game.i18n.format(
+        msg.success ? "SENESCHAL.ToolProgress.Finished" : "SENESCHAL.ToolProgress.Failed",
+        { tool: msg.tool, call: msg.call, seconds: (msg.elapsed_ms / 1000).toFixed(1) }
+      );
+    case "tool_call_awaiting_approval":
+      return game.i18n.format("SENESCHAL.ToolProgress.AwaitingApproval", {
+        tool: msg.tool,
+        position: msg.queue_position,
+      });
+    case "tool_chain_paused":
+      return game.i18n.format(
+        msg.stopped ? "SENESCHAL.ToolProgress.Stopped" : "SENESCHAL.ToolProgress.Paused",
+        { calls: msg.calls, seconds: msg.elapsed_secs }
+      );
+    default:
+      return "";
+  }
+}
+
+/**
+ * Record a progress message and re-render the player list
+ * @param {Object} msg - Progress message
+ */
+function onProgress(msg) {
+  const key = msg.session_id ?? "";
+  const previous = statuses.get(key);
+  if (previous) clearTimeout(previous.timer);
+
+  // A running call stays up until it finishes
+  const timer =
+    msg.type === "tool_call_started"
+      ? null
+      : setTimeout(() => {
+          statuses.delete(key);
+          ui.players?.render();
+        }, CLEAR_AFTER_MS);
+  statuses.set(key, { text: describe(msg), timer });
+  ui.players?.render();
+}
+
+/**
+ * Start showing tool chain progress
+ * @param {WebSocketClient} ws - Connected WebSocket client
+ */
+export function startToolProgressDisplay(ws) {
+  ws.on("tool_progress", onProgress);
+
+  Hooks.on("renderPlayers", (_app, html) => {
+    html.querySelector(".seneschal-tool-progress")?.remove();
+    if (statuses.size === 0) return;
+
+    const element = document.createElement("div");
+    element.classList.add("seneschal-tool-progress");
+    for (const { text } of statuses.values()) {
+      const line = document.createElement("div");
+      line.textContent = text;
+      element.append(line);
+    }
+    html.prepend(element);
+  });
+}
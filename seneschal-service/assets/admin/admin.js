@@ -0,0 +1,398 @@
+// Seneschal admin UI: document, image, job and settings management over the
+// REST API, for installs managed without the FVTT module.
+
+const ACCESS_LABELS = {
+  player: "Player",
+  trusted: "Trusted",
+  assistant: "Assistant",
+  gm_only: "GM only",
+};
+
+// Refresh interval for the jobs tab while it is open
+const JOBS_POLL_MS = 3000;
+
+let jobsTimer = null;
+let settingsSnapshot = {};
+
+/**
+ * Call the REST API, throwing the server's error message on failure
+ * @param {string} path - Path below /api
+ * @param {RequestInit} [options] - Fetch options
+ * @returns {Promise<any>} Parsed JSON response
+ */
+async function api(path, options = {}) {
+  const response = await fetch(`/api${path}`, options);
+  const body = await response.json().catch(() => null);
+  if (!response.ok) {
+    throw new Error(body?.message || `${response.status} ${response.statusText}`);
+  }
+  return body;
+}
+
+function jsonOptions(method, body) {
+  return {
+    method,
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(body),
+  };
+}
+
+function showNotice(message, isError = false) {
+  const notice = document.getElementById("notice");
+  notice.textContent = message;
+  notice.classList.toggle("error", isError);
+  notice.hidden = false;
+}
+
+function clearNotice() {
+  document.getElementById("notice").hidden = true;
+}
+
+/**
+ * Build an element with text content and attributes
+ * @param {string} tag - Element name
+ * @param {string} [text] - Text content
+ * @param {Object} [attrs] - Attributes to set
+ * @returns {HTMLElement}
+ */
+function el(tag, text, attrs = {}) {
+  const element = document.createElement(tag);
+  if (text !== undefined && text !== null) element.textContent = String(text);
+  for (const [name, value] of Object.entries(attrs)) element.setAttribute(name, value);
+  return element;
+}
+
+function row(cells) {
+  const tr = el("tr");
+  for (const cell of cells) {
+    const td = el("td");
+    if (cell instanceof Node) td.append(cell);
+    else td.textContent = cell ?? "";
+    tr.append(td);
+  }
+  return tr;
+}
+
+function progress(done, total) {
+  if (!total) return "";
+  return `${done ?? 0} / ${total} (${Math.round(((done ?? 0) / total) * 100)}%)`;
+}
+
+// === Documents ===
+
+async function loadDocuments() {
+  const documents = await api("/documents");
+  const rows = document.getElementById("document-rows");
+  rows.replaceChildren();
+
+  for (const doc of documents) {
+    const remove = el("button", "Delete");
+    remove.addEventListener("click", () => deleteDocument(doc));
+    const status = el("span", doc.processing_status, {
+      class: `status-${doc.processing_status}`,
+    });
+    if (doc.processing_error) status.title = doc.processing_error;
+    const priority = el("input", null, {
+      type: "number",
+      min: "-10",
+      max: "10",
+      step: "1",
+      class: "priority",
+      title: "Search favors documents with higher priority",
+    });
+    priority.value = doc.priority ?? 0;
+    priority.addEventListener("change", () => setDocumentPriority(doc, Number(priority.value)));
+
+    rows.append(
+      row([
+        doc.title,
+        ACCESS_LABELS[doc.access_level] ?? doc.access_level,
+        doc.tags.join(", "),
+        status,
+        doc.chunk_count,
+        doc.image_count,
+        priority,
+        remove,
+      ]),
+    );
+  }
+
+  const select = document.querySelector("#image-filter select[name=document_id]");
+  const selected = select.value;
+  select.replaceChildren(el("option", "All documents", { value: "" }));
+  for (const doc of documents) select.append(el("option", doc.title, { value: doc.id }));
+  select.value = selected;
+
+  return documents;
+}
+
+async function setDocumentPriority(doc, priority) {
+  try {
+    await api(
+      `/documents/${encodeURIComponent(doc.id)}`,
+      jsonOptions("PUT", {
+        title: doc.title,
+        access_level: doc.access_level,
+        tags: doc.tags.join(","),
+        priority,
+      }),
+    );
+    showNotice(`Priority of "${doc.title}" set to ${priority}.`);
+  } catch (error) {
+    showNotice(error.message, true);
+    await loadDocuments();
+  }
+}
+
+async function deleteDocument(doc) {
+  if (!confirm(`Delete "${doc.title}" with its chunks and images?`)) return;
+  try {
+    const result = await api(`/documents/${encodeURIComponent(doc.id)}`, { method: "DELETE" });
+    showNotice(result.message);
+    await loadDocuments();
+  } catch (error) {
+    showNotice(error.message, true);
+  }
+}
+
+async function uploadDocument(event) {
+  event.preventDefault();
+  const form = event.target;
+  const button = form.querySelector("button");
+  button.disabled = true;
+  try {
+    const doc = await api("/documents", { method: "POST", body: new FormData(form) });
+    showNotice(`Uploaded "${doc.title}"; processing continues in the background.`);
+    form.reset();
+    await loadDocuments();
+  } catch (error) {
+    showNotice(error.message, true);
+  } finally {
+    button.disabled = false;
+  }
+}
+
+// === Images ===
+
+async function loadImages(event) {
+  event?.preventDefault();
+  const form = document.getElementById("image-filter");
+  const params = new URLSearchParams({ limit: "200" });
+  for (const [name, value] of new FormData(form)) {
+    if (value) params.set(name, value);
+  }
+
+  const { images } = await api(`/images?${params}`);
+  const grid = document.getElementById("image-grid");
+  grid.replaceChildren();
+  if (images.length === 0) {
+    grid.append(el("p", "No images."));
+    return;
+  }
+
+  for (const image of images) {
+    const figure = el("figure");
+    const link = el("a", null, {
+      href: `/api/images/${encodeURIComponent(image.id)}/data`,
+      target: "_blank",
+    });
+    link.append(
+      el("img", null, {
+        src: `/api/images/${encodeURIComponent(image.id)}/data?size=thumb`,
+        alt: image.description ?? "",
+        loading: "lazy",
+      }),
+    );
+    const caption = el("figcaption", `${image.document_title}, p. ${image.page_number}`);
+    if (image.description) caption.title = image.description;
+    if (image.usage) {
+      const placements = image.usage.flatMap((usage) => usage.placements);
+      const usedIn = placements.map((p) => p.document_name ?? p.document_id).join(", ");
+      caption.append(el("small", usedIn ? ` Used in ${usedIn}` : " Delivered"));
+      caption.title = [caption.title, ...image.usage.map((usage) => usage.fvtt_path)]
+        .filter(Boolean)
+        .join("\n");
+    }
+    figure.append(link, caption);
+    grid.append(figure);
+  }
+}
+
+// === Jobs ===
+
+async function loadJobs() {
+  const [documents, pulls] = await Promise.all([loadDocuments(), api("/models/pull")]);
+
+  const jobRows = document.getElementById("job-rows");
+  jobRows.replaceChildren();
+  const active = documents.filter(
+    (doc) =>
+      doc.processing_status !== "completed" ||
+      ["pending", "in_progress", "failed"].includes(doc.captioning_status),
+  );
+  if (active.length === 0) jobRows.append(row(["Nothing is processing.", "", "", ""]));
+  for (const doc of active) {
+    const phase = doc.processing_phase ? `${doc.processing_phase} ` : "";
+    jobRows.append(
+      row([
+        doc.title,
+        doc.processing_error
+          ? `${doc.processing_status}: ${doc.processing_error}`
+          : doc.processing_status,
+        phase + progress(doc.processing_progress, doc.processing_total),
+        `${doc.captioning_status} ${progress(doc.captioning_progress, doc.captioning_total)}`,
+      ]),
+    );
+  }
+
+  const pullRows = document.getElementById("pull-rows");
+  pullRows.replaceChildren();
+  for (const pull of pulls) {
+    pullRows.append(
+      row([pull.model, pull.error ?? pull.status, progress(pull.completed, pull.total)]),
+    );
+  }
+}
+
+async function pullModel(event) {
+  event.preventDefault();
+  const form = event.target;
+  try {
+    const result = await api("/models/pull", jsonOptions("POST", { model: form.model.value }));
+    showNotice(result.started ? `Pulling ${result.model}.` : `${result.model} is already being pulled.`);
+    form.reset();
+    await loadJobs();
+  } catch (error) {
+    showNotice(error.message, true);
+  }
+}
+
+async function runMaintenance(event) {
+  const button = event.target;
+  button.disabled = true;
+  showNotice("Running maintenance...");
+  try {
+    const report = await api("/admin/maintenance", jsonOptions("POST", { vacuum: true }));
+    document.getElementById("maintenance-report").textContent = JSON.stringify(report, null, 2);
+    clearNotice();
+  } catch (error) {
+    showNotice(error.message, true);
+  } finally {
+    button.disabled = false;
+  }
+}
+
+// === Settings ===
+
+async function loadSettings() {
+  const { settings, overridden } = await api("/settings");
+  settingsSnapshot = settings;
+  const rows = document.getElementById("setting-rows");
+  rows.replaceChildren();
+
+  for (const key of Object.keys(settings).sort()) {
+    const value = settings[key];
+    const input =
+      typeof value === "boolean"
+        ? el("input", null, { type: "checkbox", name: key })
+        : el("input", null, { type: typeof value === "number" ? "number" : "text", name: key });
+    if (typeof value === "boolean") input.checked = value;
+    else {
+      input.value = value === null ? "" : typeof value === "object" ? JSON.stringify(value) : value;
+      if (typeof value === "number") input.step = "any";
+    }
+
+    const revert = el("button", "Revert", { type: "button" });
+    revert.disabled = !overridden.includes(key);
+    revert.addEventListener("click", () => saveSettings({ [key]: null }));
+
+    const cell = el("span", overridden.includes(key) ? "yes " : "no ");
+    cell.append(revert);
+    rows.append(row([key, input, cell]));
+  }
+}
+
+/**
+ * Read an edited setting back into the type of its current value
+ * @param {HTMLInputElement} input - The setting's input
+ * @param {any} current - Current value of the setting
+ * @returns {any}
+ */
+function inputValue(input, current) {
+  if (typeof current === "boolean") return input.checked;
+  if (typeof current === "number") return Number(input.value);
+  if (current !== null && typeof current === "object") return JSON.parse(input.value);
+  return input.value;
+}
+
+async function saveSettings(changes) {
+  if (Object.keys(changes).length === 0) {
+    showNotice("No settings changed.");
+    return;
+  }
+  try {
+    await api("/settings", jsonOptions("PUT", { settings: changes }));
+    showNotice(`Saved ${Object.keys(changes).length} setting(s).`);
+    await loadSettings();
+  } catch (error) {
+    showNotice(error.message, true);
+  }
+}
+
+function submitSettings(event) {
+  event.preventDefault();
+  const changes = {};
+  try {
+    for (const input of event.target.querySelectorAll("input[name]")) {
+      const current = settingsSnapshot[input.name];
+      const value = inputValue(input, current);
+      if (JSON.stringify(value) !== JSON.stringify(current)) changes[input.name] = value;
+    }
+  } catch (error) {
+    showNotice(`Invalid value: ${error.message}`, true);
+    return;
+  }
+  saveSettings(changes);
+}
+
+// === Tabs ===
+
+const TAB_LOADERS = {
+  documents: loadDocuments,
+  images: loadImages,
+  jobs: loadJobs,
+  settings: loadSettings,
+};
+
+async function showTab(name) {
+  clearInterval(jobsTimer);
+  jobsTimer = null;
+  for (const button of document.querySelectorAll("nav button")) {
+    button.classList.toggle("active", button.dataset.tab === name);
+  }
+  for (const section of document.querySelectorAll("main section")) {
+    section.hidden = section.id !== name;
+  }
+
+  try {
+    await TAB_LOADERS[name]();
+    if (name === "jobs") {
+      jobsTimer = setInterval(() => loadJobs().catch((e) => showNotice(e.message, true)), JOBS_POLL_MS);
+    }
+  } catch (error) {
+    showNotice(error.message, true);
+  }
+}
+
+for (const button of document.querySelectorAll("nav button")) {
+  button.addEventListener("click", () => showTab(button.dataset.tab));
+}
+document.getElementById("upload-form").addEventListener("submit", uploadDocument);
+document.getElementById("image-filter").addEventListener("submit", (event) =>
+  loadImages(event).catch((e) => showNotice(e.message, true)),
+);
+document.getElementById("pull-form").addEventListener("submit", pullModel);
+document.getElementById("run-maintenance").addEventListener("click", runMaintenance);
+document.getElementById("settings-form").addEventListener("submit", submitSettings);
+
+showTab("documents");
@@ -2,16 +2,23 @@
 //!
 //! This module provides the REST API endpoints for:
 //! - Health and metrics monitoring
-//! - Document management
+//! - Admin statistics and retrieval evaluation
+//! - Ollama model management and usage reports
+//! - Document management, versions and errata
 //! - Image management
-//! - Search functionality
+//! - Search functionality and retrieval inspection
+//! - Campaign timeline, clock, memory and tasks
+//! - The NPC registry and relationship graph
+//! - Player handouts
+//! - Text-to-speech clips
+//! - The optional built-in admin UI
 //! - WebSocket connections
 
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     extract::{DefaultBodyLimit, State, WebSocketUpgrade},
-    http::{StatusCode, header},
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{delete, get, post, put},
 };
 use serde::Serialize;
@@ -22,25 +29,88 @@ use tower_http::trace::TraceLayer;
 use tracing::info;
 
 use crate::config::RuntimeConfig;
-use crate::error::{I18nError, ServiceError};
+use crate::error::{I18nError, ProcessingError, ServiceError};
 use crate::service::SeneschalService;
+use crate::tls::{ClientCertificate, require_client_certificate};
 use crate::websocket::{WebSocketManager, handle_ws_connection};
 
+pub mod admin;
+pub mod admin_ui;
+pub mod changes;
+pub mod document_upload;
+pub mod document_versions;
 pub mod documents;
+pub mod errata;
+pub mod evaluation;
+pub mod handouts;
+pub mod image_batch;
+pub mod image_tokens;
 pub mod images;
+pub mod inspect;
+pub mod memories;
+pub mod models;
+pub mod npcs;
 pub mod search;
 pub mod settings;
+pub mod speech;
+pub mod tasks;
+pub mod timeline;
+pub mod usage;
+use admin::{
+    admin_stats_handler, get_trace_handler, list_traces_handler, run_diagnostics_handler,
+    run_maintenance_handler, run_retention_handler,
+};
+use changes::{list_changes_handler, undo_change_handler, undo_last_change_handler};
+use document_upload::upload_document_handler;
+use document_versions::{
+    get_version_page_handler, list_document_versions_handler, upload_document_version_handler,
+};
 use documents::{
-    delete_document_handler, delete_document_images_handler, get_document_handler,
-    list_documents_handler, reextract_document_images_handler, update_document_handler,
-    upload_document_handler,
+    add_access_rule_handler, delete_access_rule_handler, delete_document_handler,
+    delete_document_images_handler, get_document_handler, list_access_rules_handler,
+    list_documents_handler, recaption_document_images_handler, reextract_document_images_handler,
+    reindex_document_handler, related_documents_handler, render_document_page_handler,
+    supply_pdf_password_handler, update_document_handler,
+};
+use errata::{add_errata_handler, delete_errata_handler, list_errata_handler};
+use evaluation::{
+    add_eval_question_handler, delete_eval_question_handler, list_eval_questions_handler,
+    list_eval_runs_handler, run_evaluation_handler,
 };
+use handouts::{compose_handout_handler, get_handout_handler};
+use image_batch::{
+    batch_access_level_handler, batch_delete_handler, batch_deliver_handler, batch_tags_handler,
+};
+use image_tokens::create_token_handler;
 use images::{
     delete_image_handler, deliver_image_handler, get_document_images_handler,
-    get_image_data_handler, get_image_handler, list_images_handler, search_images_handler,
+    get_image_data_handler, get_image_handler, get_image_tags_handler, list_images_handler,
+    remove_image_tag_handler, search_images_by_example_handler, search_images_handler,
+    set_image_tags_handler,
+};
+use inspect::{inspect_call_handler, inspect_chunks_handler};
+use memories::{
+    add_memory_handler, delete_memory_handler, list_memories_handler, update_memory_handler,
+};
+use models::{
+    delete_model_handler, list_local_models_handler, model_pull_status_handler, pull_model_handler,
+};
+use npcs::{
+    delete_npc_handler, delete_npc_relation_handler, list_npcs_handler, npc_graph_handler,
+    relate_npcs_handler, set_npc_handler,
+};
+use search::{search_handler, similar_chunks_handler};
+use settings::{
+    get_settings_handler, get_world_settings_handler, update_settings_handler,
+    update_world_settings_handler,
+};
+use speech::{get_speech_handler, synthesize_speech_handler};
+use tasks::{add_task_handler, complete_task_handler, delete_task_handler, list_tasks_handler};
+use timeline::{
+    add_timeline_event_handler, delete_timeline_event_handler, extract_document_timeline_handler,
+    get_clock_handler, list_timeline_handler, set_clock_handler,
 };
-use search::search_handler;
-use settings::{get_settings_handler, update_settings_handler};
+use usage::usage_report_handler;
 
 /// Application state
 pub struct AppState {
@@ -74,9 +144,16 @@ pub fn router(service: Arc<SeneschalService>, runtime_config: &RuntimeConfig) ->
     // Use the configured max document size for uploads
     let max_body_size = runtime_config.dynamic().limits.max_document_size_bytes as usize;
 
-    let api_routes = Router::new()
+    let mut api_routes = Router::new()
         // Model endpoints
         .route("/models", get(models_handler))
+        .route("/models/local", get(list_local_models_handler))
+        .route(
+            "/models/pull",
+            get(model_pull_status_handler).post(pull_model_handler),
+        )
+        // Model names may contain '/' (e.g. "hf.co/org/model:tag")
+        .route("/models/{*name}", delete(delete_model_handler))
         // Document endpoints - with larger body limit for file uploads
         .route("/documents", get(list_documents_handler))
         .route(
@@ -86,38 +163,240 @@ pub fn router(service: Arc<SeneschalService>, runtime_config: &RuntimeConfig) ->
         .route("/documents/{id}", get(get_document_handler))
         .route("/documents/{id}", put(update_document_handler))
         .route("/documents/{id}", delete(delete_document_handler))
+        .route(
+            "/documents/{id}/versions",
+            get(list_document_versions_handler)
+                .post(upload_document_version_handler)
+                .layer(DefaultBodyLimit::max(max_body_size)),
+        )
+        .route(
+            "/documents/{id}/versions/{version}/pages/{page}",
+            get(get_version_page_handler),
+        )
+        .route("/documents/{id}/related", get(related_documents_handler))
         .route("/documents/{id}/images", get(get_document_images_handler))
         .route(
             "/documents/{id}/images",
             delete(delete_document_images_handler),
         )
+        .route(
+            "/documents/{id}/pages/{page}/render",
+            get(render_document_page_handler),
+        )
         .route(
             "/documents/{id}/images/extract",
             post(reextract_document_images_handler),
         )
+        .route("/documents/{id}/reindex", post(reindex_document_handler))
+        .route(
+            "/documents/{id}/pdf-password",
+            put(supply_pdf_password_handler),
+        )
+        .route(
+            "/documents/{id}/images/recaption",
+            post(recaption_document_images_handler),
+        )
+        .route(
+            "/documents/{id}/timeline",
+            post(extract_document_timeline_handler),
+        )
+        // Page/section access rules
+        .route(
+            "/documents/{id}/access-rules",
+            get(list_access_rules_handler).post(add_access_rule_handler),
+        )
+        .route(
+            "/documents/{id}/access-rules/{rule_id}",
+            delete(delete_access_rule_handler),
+        )
+        // Errata endpoints
+        .route("/errata", get(list_errata_handler).post(add_errata_handler))
+        .route("/errata/{id}", delete(delete_errata_handler))
         // Search endpoint
         .route("/search", post(search_handler))
+        .route("/chunks/{id}/similar", get(similar_chunks_handler))
+        // Retrieval inspection endpoints
+        .route(
+            "/inspect/documents/{id}/chunks",
+            get(inspect_chunks_handler),
+        )
+        .route("/inspect/calls/{id}", get(inspect_call_handler))
+        // Timeline endpoints
+        .route(
+            "/timeline",
+            get(list_timeline_handler).post(add_timeline_event_handler),
+        )
+        .route("/timeline/{id}", delete(delete_timeline_event_handler))
+        .route("/clock", get(get_clock_handler).put(set_clock_handler))
+        // Campaign memory endpoints
+        .route(
+            "/memories",
+            get(list_memories_handler).post(add_memory_handler),
+        )
+        .route(
+            "/memories/{id}",
+            put(update_memory_handler).delete(delete_memory_handler),
+        )
+        // Campaign task endpoints
+        .route("/tasks", get(list_tasks_handler).post(add_task_handler))
+        .route("/tasks/{id}", delete(delete_task_handler))
+        .route("/tasks/{id}/complete", post(complete_task_handler))
+        // Undo log endpoints
+        .route("/changes", get(list_changes_handler))
+        .route("/changes/undo", post(undo_last_change_handler))
+        .route("/changes/{id}/undo", post(undo_change_handler))
+        // Handout endpoints
+        .route("/handouts", post(compose_handout_handler))
+        .route("/handouts/{file_name}", get(get_handout_handler))
+        // Model usage endpoints
+        .route("/usage", get(usage_report_handler))
+        // Text-to-speech endpoints
+        .route("/speech", post(synthesize_speech_handler))
+        .route("/speech/{file_name}", get(get_speech_handler))
+        // NPC registry endpoints
+        .route("/npcs", get(list_npcs_handler).post(set_npc_handler))
+        .route("/npcs/graph", get(npc_graph_handler))
+        .route("/npcs/relations", post(relate_npcs_handler))
+        .route("/npcs/relations/{id}", delete(delete_npc_relation_handler))
+        .route("/npcs/{id}", delete(delete_npc_handler))
         // Image endpoints
         .route("/images", get(list_images_handler))
         .route("/images/search", post(search_images_handler))
+        .route(
+            "/images/search/by-image",
+            post(search_images_by_example_handler).layer(DefaultBodyLimit::max(max_body_size)),
+        )
         .route("/images/{id}", get(get_image_handler))
         .route("/images/{id}", delete(delete_image_handler))
         .route("/images/{id}/data", get(get_image_data_handler))
         .route("/images/{id}/deliver", post(deliver_image_handler))
+        .route("/images/{id}/token", post(create_token_handler))
+        .route("/images/{id}/tags", get(get_image_tags_handler))
+        .route("/images/{id}/tags", put(set_image_tags_handler))
+        .route("/images/{id}/tags/{tag}", delete(remove_image_tag_handler))
+        .route(
+            "/images/batch/access-level",
+            post(batch_access_level_handler),
+        )
+        .route("/images/batch/tags", post(batch_tags_handler))
+        .route("/images/batch/delete", post(batch_delete_handler))
+        .route("/images/batch/deliver", post(batch_deliver_handler))
         // Settings endpoints
         .route("/settings", get(get_settings_handler))
-        .route("/settings", put(update_settings_handler));
+        .route("/settings", put(update_settings_handler))
+        .route(
+            "/settings/worlds/{world_id}",
+            get(get_world_settings_handler).put(update_world_settings_handler),
+        )
+        // Admin endpoints
+        .route("/admin/stats", get(admin_stats_handler))
+        .route("/admin/maintenance", post(run_maintenance_handler))
+        .route("/admin/retention", post(run_retention_handler))
+        .route("/admin/diagnostics", post(run_diagnostics_handler))
+        .route("/admin/traces", get(list_traces_handler))
+        .route("/admin/traces/{id}", get(get_trace_handler))
+        .route(
+            "/admin/eval/questions",
+            get(list_eval_questions_handler).post(add_eval_question_handler),
+        )
+        .route(
+            "/admin/eval/questions/{id}",
+            delete(delete_eval_question_handler),
+        )
+        .route(
+            "/admin/eval/runs",
+            get(list_eval_runs_handler).post(run_evaluation_handler),
+        );
+
+    let server = &runtime_config.static_config.server;
+    if server.requires_gm_client_cert() {
+        api_routes = api_routes.layer(axum::middleware::from_fn(require_client_certificate));
+    }
 
-    Router::new()
+    let mut router = Router::new()
         .route("/health", get(health_handler))
         .route("/metrics", get(metrics_handler))
         .route("/ws", get(ws_handler))
-        .nest("/api", api_routes)
+        .nest("/api", api_routes);
+
+    if server.admin_ui {
+        let mut admin_ui_routes = admin_ui::routes();
+        if server.requires_gm_client_cert() {
+            admin_ui_routes =
+                admin_ui_routes.layer(axum::middleware::from_fn(require_client_certificate));
+        }
+        router = router.merge(admin_ui_routes);
+        info!("Admin UI enabled at /admin/");
+    }
+
+    router
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
 
+// === World Partitioning ===
+
+/// Header naming the FVTT world a request is for
+const WORLD_HEADER: &str = "x-seneschal-world";
+
+/// The FVTT world a request is for, from the `X-Seneschal-World` header.
+/// Requests without one see every world's documents.
+pub(crate) fn request_world(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(WORLD_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// === Cached Files ===
+
+/// Serve a generated file with an ETag derived from `key` and the file's size
+/// and modification time, answering 304 when the client's copy is current
+pub(crate) fn cached_file_response(
+    path: &std::path::Path,
+    key: &str,
+    mime_type: String,
+    headers: &HeaderMap,
+) -> Result<Response, ServiceError> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let etag = format!("\"{}-{:x}-{:x}\"", key, metadata.len(), modified);
+
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, "private, max-age=86400".to_string()),
+    ];
+
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        })
+    {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let data = std::fs::read(path).map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+
+    Ok((
+        StatusCode::OK,
+        cache_headers,
+        [(header::CONTENT_TYPE, mime_type)],
+        data,
+    )
+        .into_response())
+}
+
 // === Health & Metrics ===
 
 async fn health_handler(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
@@ -175,10 +454,27 @@ seneschal_documents_total 0
 
 // === WebSocket ===
 
-async fn ws_handler(ws: WebSocketUpgrade, State(state): State<Arc<AppState>>) -> impl IntoResponse {
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    client_cert: Option<Extension<ClientCertificate>>,
+) -> impl IntoResponse {
     info!("WebSocket upgrade request received");
+    // GM sessions need the same client certificate as the GM HTTP endpoints
+    let gm_allowed = !state
+        .service
+        .runtime_config
+        .static_config
+        .server
+        .requires_gm_client_cert()
+        || client_cert.is_some_and(|Extension(cert)| cert.verified);
     ws.on_upgrade(move |socket| {
-        handle_ws_connection(socket, state.ws_manager.clone(), state.service.clone())
+        handle_ws_connection(
+            socket,
+            state.ws_manager.clone(),
+            state.service.clone(),
+            gm_allowed,
+        )
     })
 }
 
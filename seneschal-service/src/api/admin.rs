@@ -0,0 +1,218 @@
+//! Admin API endpoints.
+//!
+//! A consolidated view of corpus statistics and service health for the
+//! FVTT settings UI, on-demand database maintenance and retention runs, an
+//! end-to-end self-test, and traces of recent MCP tool calls.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::auto_import::AutoImportRun;
+use crate::call_trace::CallTrace;
+use crate::db::CorpusStats;
+use crate::error::{I18nError, ServiceError};
+use crate::ollama::ModelInfo;
+use crate::service::{
+    DiagnosticsReport, DiskUsage, MaintenanceReport, ModelRoute, RetentionReport,
+};
+use crate::usage::ModelUsage;
+
+use super::AppState;
+
+/// Response for GET /api/admin/stats
+#[derive(Serialize)]
+pub struct AdminStatsResponse {
+    pub uptime_seconds: u64,
+    pub corpus: CorpusStats,
+    /// Free space and the size of each kind of stored data; None if it
+    /// couldn't be measured
+    pub disk: Option<DiskUsage>,
+    pub ollama: OllamaStatus,
+    /// Requests per model since startup
+    pub model_usage: BTreeMap<String, ModelUsage>,
+    pub auto_import: AutoImportStatus,
+    /// Most recent database maintenance run since startup
+    pub last_maintenance: Option<MaintenanceReport>,
+    /// Most recent retention run since startup
+    pub last_retention: Option<RetentionReport>,
+}
+
+/// Ollama availability and installed models
+#[derive(Serialize)]
+pub struct OllamaStatus {
+    pub available: bool,
+    pub base_url: String,
+    pub default_model: String,
+    pub vision_model: String,
+    pub embedding_model: String,
+    /// Models per task, in failover order
+    pub routes: Vec<ModelRoute>,
+    pub models: Vec<ModelInfo>,
+    /// Set when the model list couldn't be retrieved
+    pub error: Option<String>,
+}
+
+/// Auto-import configuration and most recent run
+#[derive(Serialize)]
+pub struct AutoImportStatus {
+    pub enabled: bool,
+    pub last_run: Option<AutoImportRun>,
+}
+
+/// GET /api/admin/stats - corpus statistics and service health
+pub async fn admin_stats_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<AdminStatsResponse>, I18nError> {
+    let service = &state.service;
+    let corpus = service
+        .db
+        .get_corpus_stats()
+        .map_err(|e| state.i18n_error(e))?;
+
+    let disk_service = service.clone();
+    let disk = match tokio::task::spawn_blocking(move || disk_service.disk_usage()).await {
+        Ok(Ok(usage)) => Some(usage),
+        Ok(Err(e)) => {
+            warn!(error = %e, "Failed to measure disk usage");
+            None
+        }
+        Err(e) => {
+            warn!(error = %e, "Disk usage task panicked");
+            None
+        }
+    };
+
+    let config = service.runtime_config.dynamic();
+    let storage = &service.runtime_config.static_config.storage;
+    let available = service.ollama.health_check().await.unwrap_or(false);
+    let (models, error) = if available {
+        match service.ollama.list_models().await {
+            Ok(models) => (models, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        }
+    } else {
+        (Vec::new(), Some("Ollama unavailable".to_string()))
+    };
+
+    Ok(Json(AdminStatsResponse {
+        uptime_seconds: state.start_time.elapsed().as_secs(),
+        corpus,
+        disk,
+        ollama: OllamaStatus {
+            available,
+            base_url: config.ollama.base_url.clone(),
+            default_model: config.ollama.default_model.clone(),
+            vision_model: config.ollama.vision_model.clone(),
+            embedding_model: config.embeddings.model.clone(),
+            routes: service.model_routes(),
+            models,
+            error,
+        },
+        model_usage: service.model_usage.snapshot(),
+        auto_import: AutoImportStatus {
+            enabled: storage.auto_import_dir.is_some() || storage.obsidian_vault_dir.is_some(),
+            last_run: service.last_auto_import.lock().unwrap().clone(),
+        },
+        last_maintenance: service.last_maintenance.lock().unwrap().clone(),
+        last_retention: service.last_retention.lock().unwrap().clone(),
+    }))
+}
+
+/// Request for POST /api/admin/maintenance
+#[derive(Deserialize)]
+pub struct MaintenanceRequest {
+    /// Also VACUUM, if nothing is using the database (default true)
+    #[serde(default = "default_vacuum")]
+    pub vacuum: bool,
+}
+
+fn default_vacuum() -> bool {
+    true
+}
+
+/// POST /api/admin/maintenance - check integrity, remove orphaned rows and
+/// files, and vacuum
+pub async fn run_maintenance_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<MaintenanceRequest>,
+) -> Result<Json<MaintenanceReport>, I18nError> {
+    let service = state.service.clone();
+    let report = tokio::task::spawn_blocking(move || service.run_maintenance(request.vacuum))
+        .await
+        .map_err(|e| {
+            state.i18n_error(ServiceError::Internal {
+                message: e.to_string(),
+            })
+        })?
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(report))
+}
+
+/// POST /api/admin/retention - delete data older than its retention policy now
+pub async fn run_retention_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<RetentionReport>, I18nError> {
+    let service = state.service.clone();
+    let report = tokio::task::spawn_blocking(move || service.run_retention())
+        .await
+        .map_err(|e| {
+            state.i18n_error(ServiceError::Internal {
+                message: e.to_string(),
+            })
+        })?
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(report))
+}
+
+/// POST /api/admin/diagnostics - exercise Ollama, PDF ingestion, search,
+/// Traveller Map and the FVTT assets directory, and report each check
+pub async fn run_diagnostics_handler(
+    State(state): State<Arc<AppState>>,
+) -> Json<DiagnosticsReport> {
+    Json(state.service.run_diagnostics().await)
+}
+
+/// Query parameters for GET /api/admin/traces
+#[derive(Deserialize)]
+pub struct TraceParams {
+    /// Only calls from this MCP session
+    pub session_id: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// GET /api/admin/traces - stage timings of recent MCP tool calls, newest first
+pub async fn list_traces_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TraceParams>,
+) -> Json<Vec<CallTrace>> {
+    Json(
+        state
+            .service
+            .call_traces
+            .recent(params.session_id.as_deref(), params.limit.unwrap_or(50)),
+    )
+}
+
+/// GET /api/admin/traces/{id} - stage timings of one MCP tool call
+pub async fn get_trace_handler(
+    State(state): State<Arc<AppState>>,
+    Path(correlation_id): Path<String>,
+) -> Result<Json<CallTrace>, I18nError> {
+    state
+        .service
+        .call_traces
+        .get(&correlation_id)
+        .map(Json)
+        .ok_or_else(|| {
+            state.i18n_error(ServiceError::InvalidRequest {
+                message: format!("No trace for correlation ID {}", correlation_id),
+            })
+        })
+}
@@ -0,0 +1,47 @@
+//! Built-in admin UI.
+//!
+//! A small static page over the REST API for uploading and managing
+//! documents, browsing images, watching processing jobs and editing
+//! settings, for installs run without the FVTT module. The assets are
+//! compiled into the binary and only served when `server.admin_ui` is set.
+
+use axum::{
+    Router,
+    http::header,
+    response::{Html, IntoResponse, Redirect},
+    routing::get,
+};
+use std::sync::Arc;
+
+use crate::api::AppState;
+
+const INDEX_HTML: &str = include_str!("../../assets/admin/index.html");
+const ADMIN_JS: &str = include_str!("../../assets/admin/admin.js");
+const ADMIN_CSS: &str = include_str!("../../assets/admin/admin.css");
+
+/// Routes serving the admin UI below `/admin`
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/admin", get(|| async { Redirect::permanent("/admin/") }))
+        .route("/admin/", get(index_handler))
+        .route("/admin/admin.js", get(script_handler))
+        .route("/admin/admin.css", get(stylesheet_handler))
+}
+
+async fn index_handler() -> Html<&'static str> {
+    Html(INDEX_HTML)
+}
+
+async fn script_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/javascript; charset=utf-8")],
+        ADMIN_JS,
+    )
+}
+
+async fn stylesheet_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/css; charset=utf-8")],
+        ADMIN_CSS,
+    )
+}
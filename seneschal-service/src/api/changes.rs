@@ -0,0 +1,62 @@
+//! Undo log API endpoints.
+//!
+//! Handlers for the GM to review changes tools made to FVTT world documents
+//! and revert them.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::FvttChange;
+use crate::error::I18nError;
+
+use super::AppState;
+
+/// Change list parameters
+#[derive(Deserialize)]
+pub struct ChangeParams {
+    pub limit: Option<usize>,
+    /// Also list changes that were already undone
+    #[serde(default)]
+    pub include_undone: bool,
+}
+
+/// List changes to the MCP world, most recent first
+pub async fn list_changes_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ChangeParams>,
+) -> Result<Json<Vec<FvttChange>>, I18nError> {
+    let changes = state
+        .service
+        .list_fvtt_changes(params.include_undone, params.limit.unwrap_or(50))
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(changes))
+}
+
+/// Revert the most recent change still in effect
+pub async fn undo_last_change_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<FvttChange>, I18nError> {
+    let change = state
+        .service
+        .undo_fvtt_change(None)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(change))
+}
+
+/// Revert a change
+pub async fn undo_change_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<FvttChange>, I18nError> {
+    let change = state
+        .service
+        .undo_fvtt_change(Some(&id))
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(change))
+}
@@ -0,0 +1,231 @@
+//! Document upload endpoint.
+//!
+//! Reads the multipart upload and reports its server-side stages over the
+//! WebSocket for clients that send an upload token.
+
+use axum::{
+    Json,
+    extract::{Multipart, State},
+    http::{HeaderMap, header},
+};
+use std::sync::Arc;
+
+use crate::db::Document;
+use crate::error::{I18nError, ServiceError};
+use crate::ingestion::pdf::layout::ColumnLayout;
+use crate::tools::AccessLevel;
+
+use super::{AppState, request_world};
+
+/// Header carrying the client's token for upload progress messages
+const UPLOAD_TOKEN_HEADER: &str = "x-upload-token";
+
+/// Bytes received between upload progress messages
+const RECEIVE_PROGRESS_BYTES: usize = 8 * 1024 * 1024;
+
+/// Upload a new document to the request's world.
+///
+/// A `shared` field of `true` makes the document visible from every world.
+/// With an `X-Upload-Token` header, the server-side stages of the upload are
+/// broadcast as `upload_progress` WebSocket messages carrying that token.
+pub async fn upload_document_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Result<Json<Document>, I18nError> {
+    let upload_token = headers
+        .get(UPLOAD_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let result = receive_upload(&state, &headers, upload_token.as_deref(), multipart).await;
+    if let (Err(e), Some(token)) = (&result, &upload_token) {
+        state.service.broadcast_upload_progress(
+            token,
+            "failed",
+            None,
+            None,
+            None,
+            Some(&e.error.user_message(&e.i18n, &e.locale)),
+        );
+    }
+    result
+}
+
+async fn receive_upload(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    upload_token: Option<&str>,
+    mut multipart: Multipart,
+) -> Result<Json<Document>, I18nError> {
+    // The whole request body, so slightly more than the file itself
+    let body_size = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    let mut file_data: Option<(Vec<u8>, String)> = None;
+    let mut title: Option<String> = None;
+    let mut access_level = AccessLevel::GmOnly;
+    let mut tags: Vec<String> = Vec::new();
+    let mut vision_model: Option<String> = None;
+    let mut strip_page_furniture: Option<bool> = None;
+    let mut pdf_layout: Option<ColumnLayout> = None;
+    let mut pdf_password: Option<String> = None;
+    let mut shared = false;
+
+    while let Ok(Some(mut field)) = multipart.next_field().await {
+        let name = field.name().unwrap_or("").to_string();
+
+        match name.as_str() {
+            "file" => {
+                let filename = field.file_name().unwrap_or("document").to_string();
+                let mut data = Vec::new();
+                let mut reported = 0;
+                while let Some(chunk) = field.chunk().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })? {
+                    data.extend_from_slice(&chunk);
+                    if let Some(token) = upload_token
+                        && data.len() - reported >= RECEIVE_PROGRESS_BYTES
+                    {
+                        reported = data.len();
+                        state.service.broadcast_upload_progress(
+                            token,
+                            "receiving",
+                            Some(data.len() as u64),
+                            body_size,
+                            None,
+                            None,
+                        );
+                    }
+                }
+                if let Some(token) = upload_token {
+                    state.service.broadcast_upload_progress(
+                        token,
+                        "received",
+                        Some(data.len() as u64),
+                        Some(data.len() as u64),
+                        None,
+                        None,
+                    );
+                }
+                file_data = Some((data, filename));
+            }
+            "title" => {
+                title = Some(field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?);
+            }
+            "access_level" => {
+                let level_str = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                access_level = match level_str.as_str() {
+                    "player" => AccessLevel::Player,
+                    "trusted" => AccessLevel::Trusted,
+                    "assistant" => AccessLevel::Assistant,
+                    _ => AccessLevel::GmOnly,
+                };
+            }
+            "tags" => {
+                let tags_str = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                tags = tags_str.split(',').map(|s| s.trim().to_string()).collect();
+            }
+            "vision_model" => {
+                let model = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                if !model.is_empty() {
+                    vision_model = Some(model);
+                }
+            }
+            "strip_page_furniture" => {
+                let value = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                strip_page_furniture = match value.trim() {
+                    "true" | "1" | "on" => Some(true),
+                    "false" | "0" | "off" => Some(false),
+                    _ => None,
+                };
+            }
+            "pdf_layout" => {
+                let value = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                pdf_layout = ColumnLayout::parse(&value);
+            }
+            "password" => {
+                let value = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                if !value.is_empty() {
+                    pdf_password = Some(value);
+                }
+            }
+            "shared" => {
+                let value = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                shared = matches!(value.trim(), "true" | "1" | "on");
+            }
+            _ => {}
+        }
+    }
+
+    let (data, filename) = file_data.ok_or_else(|| {
+        state.i18n_error(ServiceError::InvalidRequest {
+            message: "No file provided".to_string(),
+        })
+    })?;
+
+    let title = title.unwrap_or_else(|| filename.clone());
+
+    let mut document = state
+        .service
+        .upload_document_with_progress(
+            &data,
+            &filename,
+            &title,
+            access_level,
+            tags,
+            vision_model,
+            strip_page_furniture,
+            pdf_layout,
+            pdf_password.as_deref(),
+            upload_token,
+        )
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+
+    if let Some(world_id) = request_world(headers).filter(|_| !shared) {
+        state
+            .service
+            .db
+            .set_document_world(&document.id, Some(&world_id))
+            .map_err(|e| state.i18n_error(e))?;
+        document.world_id = Some(world_id);
+    }
+
+    Ok(Json(document))
+}
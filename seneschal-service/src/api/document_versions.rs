@@ -0,0 +1,133 @@
+//! Document version API endpoints.
+
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::db::DocumentVersion;
+use crate::error::{I18nError, ServiceError};
+
+use super::AppState;
+
+/// Query parameters for reading a page of a version
+#[derive(Deserialize)]
+pub struct VersionPageParams {
+    pub user_role: Option<u8>,
+}
+
+/// A page's text as it was in a version
+#[derive(Serialize)]
+pub struct VersionPageResponse {
+    pub document_id: String,
+    pub version: u32,
+    pub page: i32,
+    pub text: String,
+}
+
+/// List a document's versions, oldest first
+pub async fn list_document_versions_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<DocumentVersion>>, I18nError> {
+    if state
+        .service
+        .db
+        .get_document(&id)
+        .map_err(|e| state.i18n_error(e))?
+        .is_none()
+    {
+        return Err(state.i18n_error(ServiceError::DocumentNotFound { document_id: id }));
+    }
+
+    let versions = state
+        .service
+        .db
+        .list_document_versions(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(versions))
+}
+
+/// Upload a new version of a document's source file.
+///
+/// Only pages whose text changed are re-chunked and re-embedded.
+pub async fn upload_document_version_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<DocumentVersion>, I18nError> {
+    let mut file_data: Option<(Vec<u8>, String)> = None;
+    let mut pdf_password: Option<String> = None;
+
+    while let Ok(Some(field)) = multipart.next_field().await {
+        match field.name() {
+            Some("file") => {
+                let filename = field.file_name().unwrap_or("document").to_string();
+                let data = field.bytes().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                file_data = Some((data.to_vec(), filename));
+            }
+            Some("password") => {
+                let value = field.text().await.map_err(|e| {
+                    state.i18n_error(ServiceError::InvalidRequest {
+                        message: e.to_string(),
+                    })
+                })?;
+                if !value.is_empty() {
+                    pdf_password = Some(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (data, filename) = file_data.ok_or_else(|| {
+        state.i18n_error(ServiceError::InvalidRequest {
+            message: "No file provided".to_string(),
+        })
+    })?;
+
+    let version = state
+        .service
+        .upload_document_version(&id, &data, &filename, pdf_password.as_deref())
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(version))
+}
+
+/// Read a page as it was in a version of the document
+pub async fn get_version_page_handler(
+    State(state): State<Arc<AppState>>,
+    Path((id, version, page)): Path<(String, u32, i32)>,
+    Query(params): Query<VersionPageParams>,
+) -> Result<Json<VersionPageResponse>, I18nError> {
+    let user_role = params.user_role.unwrap_or(4); // Default to GM
+    let chunks = state
+        .service
+        .db
+        .get_version_page_chunks(&id, version, page, user_role)
+        .map_err(|e| state.i18n_error(e))?;
+    if chunks.is_empty() {
+        return Err(state.i18n_error(ServiceError::InvalidRequest {
+            message: format!(
+                "No content found for page {} of version {} of document {}",
+                page, version, id
+            ),
+        }));
+    }
+
+    Ok(Json(VersionPageResponse {
+        document_id: id,
+        version,
+        page,
+        text: chunks
+            .iter()
+            .map(|c| c.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    }))
+}
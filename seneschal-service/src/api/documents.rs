@@ -1,20 +1,24 @@
 //! Document API endpoints.
 //!
-//! Handlers for document CRUD operations including upload, listing,
-//! update, delete, and image management.
+//! Handlers for document CRUD operations including listing, update,
+//! delete, and image management. Uploads are in [`super::document_upload`].
 
 use axum::{
     Json,
-    extract::{Multipart, Path, Query, State},
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Response,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::db::Document;
+use crate::db::{Document, DocumentAccessRule};
 use crate::error::{I18nError, ServiceError};
+use crate::ingestion::pdf::page_render::{DEFAULT_RENDER_DPI, PageImageFormat};
+use crate::service::{CaptionPreset, RelatedDocument, SeneschalService};
 use crate::tools::AccessLevel;
 
-use super::AppState;
+use super::{AppState, cached_file_response, request_world};
 
 /// List documents query parameters
 #[derive(Deserialize)]
@@ -35,6 +39,10 @@ pub struct UpdateDocumentRequest {
     pub title: String,
     pub access_level: String,
     pub tags: Option<String>,
+    /// Move the document to a world; empty shares it with every world
+    pub world_id: Option<String>,
+    /// Search priority, from -10 to 10
+    pub priority: Option<i32>,
 }
 
 /// Response for image deletion
@@ -58,102 +66,82 @@ pub struct ReextractImagesResponse {
     pub message: String,
 }
 
-/// List all documents accessible by the user
+/// Response for a re-index request
+#[derive(Serialize)]
+pub struct ReindexDocumentResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Request to supply an encrypted PDF's password
+#[derive(Deserialize)]
+pub struct PdfPasswordRequest {
+    pub password: String,
+}
+
+/// Response for a supplied PDF password
+#[derive(Serialize)]
+pub struct PdfPasswordResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Request to re-caption document images
+#[derive(Deserialize)]
+pub struct RecaptionImagesRequest {
+    /// Specific images to re-caption; if empty, the page range (or whole document) is used
+    #[serde(default)]
+    pub image_ids: Vec<String>,
+    pub start_page: Option<i32>,
+    pub end_page: Option<i32>,
+    pub vision_model: Option<String>,
+    #[serde(default)]
+    pub preset: CaptionPreset,
+}
+
+/// Response for a re-caption request
+#[derive(Serialize)]
+pub struct RecaptionImagesResponse {
+    pub success: bool,
+    pub queued_count: usize,
+    pub message: String,
+}
+
+/// Request to add a page/section access rule
+#[derive(Deserialize)]
+pub struct AddAccessRuleRequest {
+    pub start_page: Option<i32>,
+    pub end_page: Option<i32>,
+    pub section_pattern: Option<String>,
+    pub access_level: AccessLevel,
+}
+
+/// Response for an added access rule
+#[derive(Serialize)]
+pub struct AddAccessRuleResponse {
+    pub rule: DocumentAccessRule,
+    pub chunks_affected: usize,
+    pub images_affected: usize,
+}
+
+/// List all documents accessible by the user in the request's world
 pub async fn list_documents_handler(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Query(params): Query<ListDocumentsParams>,
 ) -> Result<Json<Vec<Document>>, I18nError> {
     let user_role = params.user_role.unwrap_or(4); // Default to GM access
+    let world_id = request_world(&headers);
     let documents = state
         .service
         .list_documents(user_role)
-        .map_err(|e| state.i18n_error(e))?;
+        .map_err(|e| state.i18n_error(e))?
+        .into_iter()
+        .filter(|d| d.in_world(world_id.as_deref()))
+        .collect();
     Ok(Json(documents))
 }
 
-/// Upload a new document
-pub async fn upload_document_handler(
-    State(state): State<Arc<AppState>>,
-    mut multipart: Multipart,
-) -> Result<Json<Document>, I18nError> {
-    let mut file_data: Option<(Vec<u8>, String)> = None;
-    let mut title: Option<String> = None;
-    let mut access_level = AccessLevel::GmOnly;
-    let mut tags: Vec<String> = Vec::new();
-    let mut vision_model: Option<String> = None;
-
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let name = field.name().unwrap_or("").to_string();
-
-        match name.as_str() {
-            "file" => {
-                let filename = field.file_name().unwrap_or("document").to_string();
-                let data = field.bytes().await.map_err(|e| {
-                    state.i18n_error(ServiceError::InvalidRequest {
-                        message: e.to_string(),
-                    })
-                })?;
-                file_data = Some((data.to_vec(), filename));
-            }
-            "title" => {
-                title = Some(field.text().await.map_err(|e| {
-                    state.i18n_error(ServiceError::InvalidRequest {
-                        message: e.to_string(),
-                    })
-                })?);
-            }
-            "access_level" => {
-                let level_str = field.text().await.map_err(|e| {
-                    state.i18n_error(ServiceError::InvalidRequest {
-                        message: e.to_string(),
-                    })
-                })?;
-                access_level = match level_str.as_str() {
-                    "player" => AccessLevel::Player,
-                    "trusted" => AccessLevel::Trusted,
-                    "assistant" => AccessLevel::Assistant,
-                    _ => AccessLevel::GmOnly,
-                };
-            }
-            "tags" => {
-                let tags_str = field.text().await.map_err(|e| {
-                    state.i18n_error(ServiceError::InvalidRequest {
-                        message: e.to_string(),
-                    })
-                })?;
-                tags = tags_str.split(',').map(|s| s.trim().to_string()).collect();
-            }
-            "vision_model" => {
-                let model = field.text().await.map_err(|e| {
-                    state.i18n_error(ServiceError::InvalidRequest {
-                        message: e.to_string(),
-                    })
-                })?;
-                if !model.is_empty() {
-                    vision_model = Some(model);
-                }
-            }
-            _ => {}
-        }
-    }
-
-    let (data, filename) = file_data.ok_or_else(|| {
-        state.i18n_error(ServiceError::InvalidRequest {
-            message: "No file provided".to_string(),
-        })
-    })?;
-
-    let title = title.unwrap_or_else(|| filename.clone());
-
-    let document = state
-        .service
-        .upload_document(&data, &filename, &title, access_level, tags, vision_model)
-        .await
-        .map_err(|e| state.i18n_error(e))?;
-
-    Ok(Json(document))
-}
-
 /// Get a specific document by ID
 pub async fn get_document_handler(
     State(state): State<Arc<AppState>>,
@@ -169,6 +157,33 @@ pub async fn get_document_handler(
     Ok(Json(document))
 }
 
+/// Related documents query parameters
+#[derive(Deserialize)]
+pub struct RelatedDocumentsParams {
+    pub user_role: Option<u8>,
+    pub limit: Option<usize>,
+}
+
+/// Get the documents most related to a document
+pub async fn related_documents_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(params): Query<RelatedDocumentsParams>,
+) -> Result<Json<Vec<RelatedDocument>>, I18nError> {
+    let user_role = params.user_role.unwrap_or(4); // Default to GM access
+    let related = state
+        .service
+        .related_documents(
+            &id,
+            user_role,
+            request_world(&headers).as_deref(),
+            params.limit.unwrap_or(10),
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(related))
+}
+
 /// Delete a document
 pub async fn delete_document_handler(
     State(state): State<Arc<AppState>>,
@@ -189,7 +204,7 @@ pub async fn delete_document_handler(
     }
 }
 
-/// Update document metadata (title, access_level, tags)
+/// Update document metadata (title, access_level, tags, world, priority)
 pub async fn update_document_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -211,6 +226,19 @@ pub async fn update_document_handler(
         .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
         .unwrap_or_default();
 
+    if let Some(priority) = request.priority
+        && !(-10..=10).contains(&priority)
+    {
+        return Err(state.i18n_error(ServiceError::InvalidRequest {
+            message: "Priority must be between -10 and 10".to_string(),
+        }));
+    }
+
+    let previous = state
+        .service
+        .db
+        .get_document(&id)
+        .map_err(|e| state.i18n_error(e))?;
     let updated = state
         .service
         .update_document(&id, &request.title, access_level, tags)
@@ -219,6 +247,26 @@ pub async fn update_document_handler(
     if !updated {
         return Err(state.i18n_error(ServiceError::DocumentNotFound { document_id: id }));
     }
+    if previous.is_some_and(|d| d.title != request.title || d.access_level != access_level) {
+        SeneschalService::refresh_document_summary(state.service.clone(), &id);
+    }
+
+    if let Some(world_id) = &request.world_id {
+        let world_id = Some(world_id.trim()).filter(|w| !w.is_empty());
+        state
+            .service
+            .db
+            .set_document_world(&id, world_id)
+            .map_err(|e| state.i18n_error(e))?;
+    }
+
+    if let Some(priority) = request.priority {
+        state
+            .service
+            .db
+            .set_document_priority(&id, priority)
+            .map_err(|e| state.i18n_error(e))?;
+    }
 
     // Return the updated document
     let document = state
@@ -264,3 +312,173 @@ pub async fn reextract_document_images_handler(
         message: "Image re-extraction queued".to_string(),
     }))
 }
+
+/// Re-process a document from its stored source file (queues for async processing)
+pub async fn reindex_document_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ReindexDocumentResponse>, I18nError> {
+    state
+        .service
+        .reindex_document(&id)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(ReindexDocumentResponse {
+        success: true,
+        message: "Document queued for re-indexing".to_string(),
+    }))
+}
+
+/// Supply the password of an encrypted PDF, which is only kept in memory
+pub async fn supply_pdf_password_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<PdfPasswordRequest>,
+) -> Result<Json<PdfPasswordResponse>, I18nError> {
+    // Checking the password opens the PDF, so keep it off the async runtime
+    let service = state.service.clone();
+    tokio::task::spawn_blocking(move || service.supply_pdf_password(&id, &request.password))
+        .await
+        .map_err(|e| {
+            state.i18n_error(ServiceError::Internal {
+                message: e.to_string(),
+            })
+        })?
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(PdfPasswordResponse {
+        success: true,
+        message: "PDF password accepted".to_string(),
+    }))
+}
+
+/// Re-caption selected images of a document (queues for async processing)
+pub async fn recaption_document_images_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<RecaptionImagesRequest>,
+) -> Result<Json<RecaptionImagesResponse>, I18nError> {
+    let count = state
+        .service
+        .recaption_images(
+            &id,
+            &request.image_ids,
+            request.start_page,
+            request.end_page,
+            request.vision_model,
+            request.preset,
+        )
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(RecaptionImagesResponse {
+        success: true,
+        queued_count: count,
+        message: format!("Queued {} images for re-captioning", count),
+    }))
+}
+
+/// List page/section access rules for a document
+pub async fn list_access_rules_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<DocumentAccessRule>>, I18nError> {
+    let rules = state
+        .service
+        .db
+        .get_document_access_rules(&id)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(rules))
+}
+
+/// Add a page/section access rule to a document
+pub async fn add_access_rule_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<AddAccessRuleRequest>,
+) -> Result<Json<AddAccessRuleResponse>, I18nError> {
+    let (rule, chunks_affected, images_affected) = state
+        .service
+        .add_document_access_rule(
+            &id,
+            request.start_page,
+            request.end_page,
+            request.section_pattern,
+            request.access_level,
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    SeneschalService::refresh_document_summary(state.service.clone(), &id);
+
+    Ok(Json(AddAccessRuleResponse {
+        rule,
+        chunks_affected,
+        images_affected,
+    }))
+}
+
+/// Delete a page/section access rule
+pub async fn delete_access_rule_handler(
+    State(state): State<Arc<AppState>>,
+    Path((id, rule_id)): Path<(String, String)>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .delete_document_access_rule(&id, &rule_id)
+        .map_err(|e| state.i18n_error(e))?;
+    if deleted {
+        SeneschalService::refresh_document_summary(state.service.clone(), &id);
+    }
+
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Access rule deleted".to_string()
+        } else {
+            "Access rule not found".to_string()
+        },
+    }))
+}
+
+/// Page render query parameters
+#[derive(Deserialize)]
+pub struct RenderPageParams {
+    /// Resolution, clamped to 36-300 (default 150)
+    pub dpi: Option<u32>,
+    /// `webp` (default) or `png`
+    #[serde(default)]
+    pub format: PageImageFormat,
+    pub user_role: Option<u8>,
+}
+
+/// GET /api/documents/{id}/pages/{page}/render - rasterize a PDF page (1-indexed)
+pub async fn render_document_page_handler(
+    State(state): State<Arc<AppState>>,
+    Path((id, page)): Path<(String, u32)>,
+    Query(params): Query<RenderPageParams>,
+    headers: HeaderMap,
+) -> Result<Response, I18nError> {
+    let dpi = params.dpi.unwrap_or(DEFAULT_RENDER_DPI);
+    let user_role = params.user_role.unwrap_or(4); // Default to GM access
+
+    // Rendering loads the PDF and rasterizes the page, so keep it off the async runtime
+    let service = state.service.clone();
+    let document_id = id.clone();
+    let path = tokio::task::spawn_blocking(move || {
+        service.rendered_page_path(&document_id, page, dpi, params.format, user_role)
+    })
+    .await
+    .map_err(|e| {
+        state.i18n_error(ServiceError::Internal {
+            message: e.to_string(),
+        })
+    })?
+    .map_err(|e| state.i18n_error(e))?;
+
+    cached_file_response(
+        &path,
+        &format!("{}-page{}-{}-{}", id, page, dpi, params.format.extension()),
+        params.format.mime_type().to_string(),
+        &headers,
+    )
+    .map_err(|e| state.i18n_error(e))
+}
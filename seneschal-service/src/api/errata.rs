@@ -0,0 +1,81 @@
+//! Errata API endpoints.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::Errata;
+use crate::error::I18nError;
+
+use super::AppState;
+use super::documents::DeleteResponse;
+
+/// Request to mark a document (or pages of one) as errata for another
+#[derive(Deserialize)]
+pub struct AddErrataRequest {
+    pub errata_document_id: String,
+    /// Pages of the errata document with the corrections (default: all)
+    pub errata_start_page: Option<i32>,
+    pub errata_end_page: Option<i32>,
+    pub target_document_id: String,
+    /// Corrected pages of the target document (default: all)
+    pub target_start_page: Option<i32>,
+    pub target_end_page: Option<i32>,
+    /// Publication date of the errata, e.g. "2023-05"
+    pub issued: Option<String>,
+    pub note: Option<String>,
+}
+
+/// List errata links
+pub async fn list_errata_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Errata>>, I18nError> {
+    let errata = state
+        .service
+        .db
+        .list_errata()
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(errata))
+}
+
+/// Add an errata link
+pub async fn add_errata_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AddErrataRequest>,
+) -> Result<Json<Errata>, I18nError> {
+    let errata = state
+        .service
+        .add_errata(
+            &request.errata_document_id,
+            (request.errata_start_page, request.errata_end_page),
+            &request.target_document_id,
+            (request.target_start_page, request.target_end_page),
+            request.issued,
+            request.note,
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(errata))
+}
+
+/// Delete an errata link
+pub async fn delete_errata_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_errata(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Errata deleted".to_string()
+        } else {
+            format!("Errata not found: {}", id)
+        },
+    }))
+}
@@ -0,0 +1,118 @@
+//! Evaluation API endpoints.
+//!
+//! Handlers for managing evaluation questions and running them against the
+//! current retrieval and model configuration.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::{EvalQuestion, EvalRun};
+use crate::error::I18nError;
+
+use super::AppState;
+use super::documents::DeleteResponse;
+
+/// Request to add an evaluation question
+#[derive(Deserialize)]
+pub struct AddEvalQuestionRequest {
+    pub question: String,
+    pub expected_answer: Option<String>,
+    pub document_id: Option<String>,
+    #[serde(default)]
+    pub expected_pages: Vec<i32>,
+}
+
+/// Request to run the evaluation questions
+#[derive(Deserialize)]
+pub struct RunEvaluationRequest {
+    /// Results searched per question (default 10)
+    pub top_k: Option<usize>,
+    /// Also generate and score answers with the default model (slow)
+    #[serde(default)]
+    pub generate_answers: bool,
+}
+
+/// Evaluation run listing parameters
+#[derive(Deserialize)]
+pub struct ListEvalRunsParams {
+    pub limit: Option<usize>,
+}
+
+/// List evaluation questions
+pub async fn list_eval_questions_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<EvalQuestion>>, I18nError> {
+    let questions = state
+        .service
+        .db
+        .list_eval_questions()
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(questions))
+}
+
+/// Add an evaluation question
+pub async fn add_eval_question_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AddEvalQuestionRequest>,
+) -> Result<Json<EvalQuestion>, I18nError> {
+    let question = state
+        .service
+        .add_eval_question(
+            &request.question,
+            request.expected_answer,
+            request.document_id,
+            request.expected_pages,
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(question))
+}
+
+/// Delete an evaluation question
+pub async fn delete_eval_question_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_eval_question(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Evaluation question deleted".to_string()
+        } else {
+            format!("Evaluation question not found: {}", id)
+        },
+    }))
+}
+
+/// Run all evaluation questions and return the scores
+pub async fn run_evaluation_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<RunEvaluationRequest>,
+) -> Result<Json<EvalRun>, I18nError> {
+    let run = state
+        .service
+        .run_evaluation(request.top_k, request.generate_answers)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(run))
+}
+
+/// List recent evaluation runs, newest first
+pub async fn list_eval_runs_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListEvalRunsParams>,
+) -> Result<Json<Vec<EvalRun>>, I18nError> {
+    let runs = state
+        .service
+        .db
+        .list_eval_runs(params.limit.unwrap_or(20))
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(runs))
+}
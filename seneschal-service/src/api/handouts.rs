@@ -0,0 +1,79 @@
+//! Player handout API endpoints.
+//!
+//! Handlers for composing a handout and downloading the rendered file,
+//! which the FVTT module does when it can't be written to assets directly.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Response,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::I18nError;
+use crate::service::{HandoutFormat, HandoutRequest, HandoutTemplate};
+use crate::tools::AccessLevel;
+
+use super::{AppState, cached_file_response};
+
+/// Request to compose a handout
+#[derive(Deserialize)]
+pub struct ComposeHandoutRequest {
+    pub title: String,
+    pub markdown: String,
+    #[serde(default)]
+    pub template: HandoutTemplate,
+    #[serde(default)]
+    pub format: HandoutFormat,
+    #[serde(default)]
+    pub image_ids: Vec<String>,
+}
+
+/// POST /api/handouts - render a handout and deliver it to FVTT
+pub async fn compose_handout_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ComposeHandoutRequest>,
+) -> Result<Json<serde_json::Value>, I18nError> {
+    let handout = state
+        .service
+        .compose_handout(
+            &HandoutRequest {
+                title: request.title,
+                markdown: request.markdown,
+                template: request.template,
+                format: request.format,
+                image_ids: request.image_ids,
+            },
+            AccessLevel::GmOnly as u8,
+        )
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+
+    let download_url = format!("/api/handouts/{}", handout.file_name);
+    let mut body = serde_json::to_value(&handout).unwrap_or_default();
+    body["download_url"] = serde_json::Value::String(download_url);
+    Ok(Json(body))
+}
+
+/// GET /api/handouts/{file_name} - download a rendered handout
+pub async fn get_handout_handler(
+    State(state): State<Arc<AppState>>,
+    Path(file_name): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, I18nError> {
+    let path = state
+        .service
+        .handout_path(&file_name)
+        .map_err(|e| state.i18n_error(e))?;
+    let mime_type = if file_name.ends_with(".png") {
+        HandoutFormat::Png
+    } else {
+        HandoutFormat::Pdf
+    }
+    .mime_type();
+
+    cached_file_response(&path, &file_name, mime_type.to_string(), &headers)
+        .map_err(|e| state.i18n_error(e))
+}
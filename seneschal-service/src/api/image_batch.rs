@@ -0,0 +1,95 @@
+//! Batch image API endpoints.
+//!
+//! Each endpoint applies one operation to a list of image IDs and returns
+//! a report of which images succeeded and which failed.
+
+use axum::{Json, extract::State};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::I18nError;
+use crate::service::ImageBatchReport;
+use crate::tools::AccessLevel;
+
+use super::AppState;
+
+/// Batch access level request
+#[derive(Deserialize)]
+pub struct BatchAccessLevelRequest {
+    pub image_ids: Vec<String>,
+    /// Omit or null to inherit the document's access level
+    pub access_level: Option<AccessLevel>,
+}
+
+/// Batch tagging request
+#[derive(Deserialize)]
+pub struct BatchTagsRequest {
+    pub image_ids: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+/// Batch delete request
+#[derive(Deserialize)]
+pub struct BatchDeleteRequest {
+    pub image_ids: Vec<String>,
+}
+
+/// Batch delivery request
+#[derive(Deserialize)]
+pub struct BatchDeliverRequest {
+    pub image_ids: Vec<String>,
+    /// Folder relative to the FVTT assets directory; defaults to each image's own path
+    pub target_folder: Option<String>,
+}
+
+/// Set the access level of a set of images
+pub async fn batch_access_level_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BatchAccessLevelRequest>,
+) -> Result<Json<ImageBatchReport>, I18nError> {
+    let report = state
+        .service
+        .batch_set_image_access_level(&request.image_ids, request.access_level)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(report))
+}
+
+/// Add tags to a set of images
+pub async fn batch_tags_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BatchTagsRequest>,
+) -> Result<Json<ImageBatchReport>, I18nError> {
+    let report = state
+        .service
+        .batch_add_image_tags(&request.image_ids, &request.tags)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(report))
+}
+
+/// Delete a set of images
+pub async fn batch_delete_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BatchDeleteRequest>,
+) -> Result<Json<ImageBatchReport>, I18nError> {
+    let report = state
+        .service
+        .batch_delete_images(&request.image_ids)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(report))
+}
+
+/// Deliver a set of images to the FVTT assets directory
+pub async fn batch_deliver_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BatchDeliverRequest>,
+) -> Result<Json<ImageBatchReport>, I18nError> {
+    let report = state
+        .service
+        .batch_deliver_images(&request.image_ids, request.target_folder.as_deref())
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,64 @@
+//! Token cutout API endpoint.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::{I18nError, ServiceError};
+use crate::service::ImageDelivery;
+
+use super::AppState;
+use super::images::ImageDto;
+
+/// Token creation request
+#[derive(Deserialize)]
+pub struct CreateTokenRequest {
+    /// Edge length in pixels (default 400)
+    pub size: Option<u32>,
+    /// Ring color as `#rrggbb`; no ring if omitted
+    pub ring_color: Option<String>,
+    /// Also deliver the token to the FVTT assets directory
+    #[serde(default)]
+    pub deliver: bool,
+}
+
+/// Token creation response
+#[derive(Serialize)]
+pub struct CreateTokenResponse {
+    pub image: ImageDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery: Option<ImageDelivery>,
+}
+
+/// Create a circular token from an image
+pub async fn create_token_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<CreateTokenRequest>,
+) -> Result<Json<CreateTokenResponse>, I18nError> {
+    // Decoding and resampling the art is CPU-bound
+    let service = state.service.clone();
+    let (token, delivery) = tokio::task::spawn_blocking(move || {
+        let token = service.create_token_image(&id, request.size, request.ring_color.as_deref())?;
+        let delivery = request
+            .deliver
+            .then(|| service.deliver_token_image(&token))
+            .transpose()?;
+        Ok::<_, ServiceError>((token, delivery))
+    })
+    .await
+    .map_err(|e| {
+        state.i18n_error(ServiceError::Internal {
+            message: e.to_string(),
+        })
+    })?
+    .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(CreateTokenResponse {
+        image: ImageDto::from(token),
+        delivery,
+    }))
+}
@@ -1,23 +1,27 @@
 //! Image API endpoints.
 //!
-//! Handlers for image listing, searching, retrieval, deletion, and delivery.
+//! Handlers for image listing, searching, retrieval, tagging, deletion, and delivery.
 
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::{StatusCode, header},
-    response::{IntoResponse, Response},
+    extract::{Multipart, Path, Query, State},
+    http::HeaderMap,
+    response::Response,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::config::AssetsAccess;
-use crate::db::{DocumentImage, DocumentImageWithAccess};
-use crate::error::{I18nError, ProcessingError, ServiceError};
+use std::collections::HashMap;
+
+use crate::db::{DocumentImage, DocumentImageWithAccess, ImageTags, ImageUsage};
+use crate::error::{I18nError, ServiceError};
 use crate::ingestion::IngestionService;
+use crate::ingestion::thumbnails::ImageSize;
+use crate::service::ImageDelivery;
+use crate::tools::{SearchFilters, TagMatch};
 
-use super::AppState;
 use super::documents::DeleteResponse;
+use super::{AppState, cached_file_response, request_world};
 
 /// Image listing query parameters
 #[derive(Deserialize)]
@@ -27,6 +31,13 @@ pub struct ListImagesParams {
     pub page_number: Option<i32>,
     pub start_page: Option<i32>,
     pub end_page: Option<i32>,
+    /// Comma-separated tags; images without their own tags match on their document's
+    pub tags: Option<String>,
+    /// `any` (default) or `all`
+    pub tags_match: Option<String>,
+    /// Leave out images already delivered to the request's world
+    #[serde(default)]
+    pub exclude_used: bool,
     pub limit: Option<usize>,
 }
 
@@ -49,6 +60,27 @@ pub struct ImageDto {
     pub height: Option<u32>,
     pub description: Option<String>,
     pub created_at: String,
+    /// Only filled in for single-image lookups (inherited from the document if
+    /// the image has none of its own)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Where the image was delivered to FVTT and the documents using it
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub usage: Vec<ImageUsage>,
+}
+
+impl ImageDto {
+    /// The DTO with the image's usage taken from `usages`
+    fn with_usage(
+        img: DocumentImageWithAccess,
+        usages: &mut HashMap<String, Vec<ImageUsage>>,
+    ) -> Self {
+        let usage = usages.remove(&img.image.id).unwrap_or_default();
+        Self {
+            usage,
+            ..Self::from(img)
+        }
+    }
 }
 
 impl From<DocumentImageWithAccess> for ImageDto {
@@ -64,6 +96,8 @@ impl From<DocumentImageWithAccess> for ImageDto {
             height: img.image.height,
             description: img.image.description,
             created_at: img.image.created_at.to_rfc3339(),
+            tags: Vec::new(),
+            usage: Vec::new(),
         }
     }
 }
@@ -103,12 +137,25 @@ pub struct DocumentImagesResponse {
     pub images: Vec<SimpleImageDto>,
 }
 
+/// Image data query parameters
+#[derive(Deserialize)]
+pub struct ImageDataParams {
+    /// Size variant: `thumb`, `medium`, or `full` (default)
+    #[serde(default)]
+    pub size: ImageSize,
+}
+
 /// Image search request
 #[derive(Deserialize)]
 pub struct SearchImagesRequest {
     pub query: String,
     pub user_role: Option<u8>,
     pub limit: Option<usize>,
+    pub tags: Option<Vec<String>>,
+    pub tags_match: Option<String>,
+    /// Leave out images already delivered to the request's world
+    #[serde(default)]
+    pub exclude_used: bool,
 }
 
 /// Image search response
@@ -125,6 +172,14 @@ pub struct SearchImageResult {
     pub similarity: f32,
 }
 
+/// Search-by-example response
+#[derive(Serialize)]
+pub struct SimilarImagesResponse {
+    /// Vision model description of the example image
+    pub description: Option<String>,
+    pub images: Vec<SearchImageResult>,
+}
+
 /// Image delivery request
 #[derive(Deserialize)]
 pub struct DeliverImageRequest {
@@ -146,6 +201,7 @@ pub struct DeliverImageResponse {
 /// List images with optional filters
 pub async fn list_images_handler(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Query(params): Query<ListImagesParams>,
 ) -> Result<Json<ListImagesResponse>, I18nError> {
     let images = state
@@ -156,12 +212,26 @@ pub async fn list_images_handler(
             params.document_id.as_deref(),
             params.start_page.or(params.page_number), // page_number as start for backwards compat
             params.end_page.or(params.page_number),   // page_number as end for backwards compat
+            image_filters(
+                params
+                    .tags
+                    .as_deref()
+                    .map(|tags| tags.split(',').map(str::to_string).collect()),
+                params.tags_match.as_deref(),
+                params.exclude_used,
+                &headers,
+            )
+            .as_ref(),
             params.limit.unwrap_or(100),
         )
         .map_err(|e| state.i18n_error(e))?;
+    let mut usages = image_usages(&state, &headers)?;
 
     Ok(Json(ListImagesResponse {
-        images: images.into_iter().map(ImageDto::from).collect(),
+        images: images
+            .into_iter()
+            .map(|img| ImageDto::with_usage(img, &mut usages))
+            .collect(),
     }))
 }
 
@@ -184,6 +254,7 @@ pub async fn get_document_images_handler(
 /// Search images by semantic similarity
 pub async fn search_images_handler(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(request): Json<SearchImagesRequest>,
 ) -> Result<Json<SearchImagesResponse>, I18nError> {
     // Generate embedding for the query
@@ -202,11 +273,75 @@ pub async fn search_images_handler(
             &embedding,
             request.user_role.unwrap_or(4), // Default to GM
             request.limit.unwrap_or(20),
+            image_filters(
+                request.tags,
+                request.tags_match.as_deref(),
+                request.exclude_used,
+                &headers,
+            )
+            .as_ref(),
         )
         .map_err(|e| state.i18n_error(e))?;
+    let mut usages = image_usages(&state, &headers)?;
 
     Ok(Json(SearchImagesResponse {
         images: results
+            .into_iter()
+            .map(|(img, score)| SearchImageResult {
+                image: ImageDto::with_usage(img, &mut usages),
+                similarity: score,
+            })
+            .collect(),
+    }))
+}
+
+/// Search images similar to an uploaded example image
+///
+/// Multipart fields: `image` (required), `user_role`, `limit`.
+pub async fn search_images_by_example_handler(
+    State(state): State<Arc<AppState>>,
+    mut multipart: Multipart,
+) -> Result<Json<SimilarImagesResponse>, I18nError> {
+    let mut image_data: Option<Vec<u8>> = None;
+    let mut user_role: u8 = 4; // Default to GM
+    let mut limit: usize = 20;
+
+    while let Ok(Some(field)) = multipart.next_field().await {
+        let name = field.name().unwrap_or("").to_string();
+        let invalid = |e: axum::extract::multipart::MultipartError| {
+            state.i18n_error(ServiceError::InvalidRequest {
+                message: e.to_string(),
+            })
+        };
+
+        match name.as_str() {
+            "image" => image_data = Some(field.bytes().await.map_err(invalid)?.to_vec()),
+            "user_role" => {
+                user_role = field.text().await.map_err(invalid)?.parse().unwrap_or(4);
+            }
+            "limit" => {
+                limit = field.text().await.map_err(invalid)?.parse().unwrap_or(20);
+            }
+            _ => {}
+        }
+    }
+
+    let image_data = image_data.ok_or_else(|| {
+        state.i18n_error(ServiceError::InvalidRequest {
+            message: "No image provided".to_string(),
+        })
+    })?;
+
+    let similar = state
+        .service
+        .search_images_by_example(&image_data, user_role, limit)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(SimilarImagesResponse {
+        description: similar.description,
+        images: similar
+            .images
             .into_iter()
             .map(|(img, score)| SearchImageResult {
                 image: ImageDto::from(img),
@@ -219,6 +354,7 @@ pub async fn search_images_handler(
 /// Get a specific image by ID
 pub async fn get_image_handler(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Path(id): Path<String>,
 ) -> Result<Json<ImageDto>, I18nError> {
     let image = state
@@ -232,7 +368,18 @@ pub async fn get_image_handler(
             })
         })?;
 
-    Ok(Json(ImageDto::from(image)))
+    let tags = state
+        .service
+        .db
+        .get_image_tags(&id)
+        .map_err(|e| state.i18n_error(e))?;
+
+    let mut usages = image_usages(&state, &headers)?;
+
+    Ok(Json(ImageDto {
+        tags: tags.tags,
+        ..ImageDto::with_usage(image, &mut usages)
+    }))
 }
 
 /// Delete an image
@@ -255,10 +402,15 @@ pub async fn delete_image_handler(
     }
 }
 
-/// Get raw image data
+/// Get raw image data, optionally downscaled
+///
+/// Responses carry an ETag derived from the served file, so browsers can revalidate
+/// without re-downloading.
 pub async fn get_image_data_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
+    Query(params): Query<ImageDataParams>,
+    headers: HeaderMap,
 ) -> Result<Response, I18nError> {
     let image = state
         .service
@@ -271,16 +423,33 @@ pub async fn get_image_data_handler(
             })
         })?;
 
-    // Read the image file
-    let data = std::fs::read(&image.image.internal_path)
-        .map_err(|e| state.i18n_error(ServiceError::Processing(ProcessingError::Io(e))))?;
-
-    Ok((
-        StatusCode::OK,
-        [(header::CONTENT_TYPE, image.image.mime_type)],
-        data,
+    // Generating a variant decodes the full image, so keep it off the async runtime
+    let service = state.service.clone();
+    let source = image.image.clone();
+    let path =
+        tokio::task::spawn_blocking(move || service.image_variant_path(&source, params.size))
+            .await
+            .map_err(|e| {
+                state.i18n_error(ServiceError::Internal {
+                    message: e.to_string(),
+                })
+            })?
+            .map_err(|e| state.i18n_error(e))?;
+
+    // Variants are always WebP; the original keeps its stored type
+    let mime_type = if path == std::path::Path::new(&image.image.internal_path) {
+        image.image.mime_type
+    } else {
+        "image/webp".to_string()
+    };
+
+    cached_file_response(
+        &path,
+        &format!("{}-{}", id, params.size.as_str()),
+        mime_type,
+        &headers,
     )
-        .into_response())
+    .map_err(|e| state.i18n_error(e))
 }
 
 /// Deliver an image to FVTT assets directory
@@ -311,42 +480,143 @@ pub async fn deliver_image_handler(
         .to_string()
     });
 
-    // The FVTT path is what FVTT uses to reference the file (prepend assets/)
-    let fvtt_path = format!("assets/{}", relative_path);
-
-    // Check if we can write directly
-    match state
+    let delivery = state
         .service
-        .runtime_config
-        .static_config
-        .fvtt
-        .check_assets_access()
-    {
-        AssetsAccess::Direct(assets_dir) => {
-            // Create target directory
-            let full_path = assets_dir.join(&relative_path);
-            if let Some(parent) = full_path.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| {
-                    state.i18n_error(ServiceError::Processing(ProcessingError::Io(e)))
-                })?;
-            }
-
-            // Copy file
-            std::fs::copy(&image.image.internal_path, &full_path)
-                .map_err(|e| state.i18n_error(ServiceError::Processing(ProcessingError::Io(e))))?;
+        .deliver_image(&image, &relative_path)
+        .map_err(|e| state.i18n_error(e))?;
 
-            Ok(Json(DeliverImageResponse {
-                mode: "direct".to_string(),
-                fvtt_path: Some(fvtt_path),
-                image_id: None,
-                suggested_path: None,
-            }))
-        }
-        AssetsAccess::Shuttle => Ok(Json(DeliverImageResponse {
+    Ok(Json(match delivery {
+        ImageDelivery::Direct { fvtt_path } => DeliverImageResponse {
+            mode: "direct".to_string(),
+            fvtt_path: Some(fvtt_path),
+            image_id: None,
+            suggested_path: None,
+        },
+        ImageDelivery::Shuttle { suggested_path } => DeliverImageResponse {
             mode: "shuttle".to_string(),
             fvtt_path: None,
             image_id: Some(id),
-            suggested_path: Some(fvtt_path),
-        })),
+            suggested_path: Some(suggested_path),
+        },
+    }))
+}
+
+/// Image tags replacement request
+#[derive(Deserialize)]
+pub struct SetImageTagsRequest {
+    pub tags: Vec<String>,
+}
+
+/// Get an image's tags
+pub async fn get_image_tags_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ImageTags>, I18nError> {
+    if state
+        .service
+        .db
+        .get_document_image(&id)
+        .map_err(|e| state.i18n_error(e))?
+        .is_none()
+    {
+        return Err(state.i18n_error(ServiceError::ImageNotFound { image_id: id }));
+    }
+
+    let tags = state
+        .service
+        .db
+        .get_image_tags(&id)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(tags))
+}
+
+/// Replace an image's tags (an empty list inherits the document's tags)
+pub async fn set_image_tags_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<SetImageTagsRequest>,
+) -> Result<Json<ImageTags>, I18nError> {
+    let tags: Vec<String> = request
+        .tags
+        .iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
+    let found = state
+        .service
+        .db
+        .set_image_tags(&id, &tags)
+        .map_err(|e| state.i18n_error(e))?;
+    if !found {
+        return Err(state.i18n_error(ServiceError::ImageNotFound { image_id: id }));
     }
+
+    let tags = state
+        .service
+        .db
+        .get_image_tags(&id)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(tags))
+}
+
+/// Remove one tag from an image
+pub async fn remove_image_tag_handler(
+    State(state): State<Arc<AppState>>,
+    Path((id, tag)): Path<(String, String)>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let removed = state
+        .service
+        .db
+        .remove_image_tag(&id, &tag)
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(DeleteResponse {
+        success: removed,
+        message: if removed {
+            "Tag removed".to_string()
+        } else {
+            "Image does not have this tag".to_string()
+        },
+    }))
+}
+
+/// A filter from request parameters and the request's world, if either is set
+fn image_filters(
+    tags: Option<Vec<String>>,
+    tags_match: Option<&str>,
+    exclude_used: bool,
+    headers: &HeaderMap,
+) -> Option<SearchFilters> {
+    let tags: Vec<String> = tags
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let tags_match = match tags_match {
+        Some("all") => TagMatch::All,
+        _ => TagMatch::Any,
+    };
+    let world_id = request_world(headers);
+    (!tags.is_empty() || world_id.is_some() || exclude_used).then_some(SearchFilters {
+        tags,
+        tags_match,
+        world_id,
+        exclude_used,
+    })
+}
+
+/// Usage of delivered images in the request's world, or in all worlds
+fn image_usages(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<HashMap<String, Vec<ImageUsage>>, I18nError> {
+    state
+        .service
+        .db
+        .image_usages(request_world(headers).as_deref())
+        .map_err(|e| state.i18n_error(e))
 }
@@ -0,0 +1,85 @@
+//! Retrieval inspection API endpoints.
+//!
+//! For diagnosing why an answer came from the wrong book: a document's
+//! chunks as indexed, with nearest neighbors and full-text matches, and the
+//! exact text a past MCP tool call gave the model.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::call_trace::CallTrace;
+use crate::error::{I18nError, ServiceError};
+use crate::service::ChunkPage;
+
+use super::AppState;
+
+/// Query parameters for GET /api/inspect/documents/{id}/chunks
+#[derive(Deserialize)]
+pub struct InspectChunksParams {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    /// Query whose full-text matches to mark in each chunk
+    pub q: Option<String>,
+    /// Nearest neighbors to list per chunk (default 5)
+    pub neighbors: Option<usize>,
+}
+
+/// Response for GET /api/inspect/calls/{id}
+#[derive(Serialize)]
+pub struct InspectCallResponse {
+    pub trace: CallTrace,
+    /// The result text the model was given, after compaction
+    pub model_text: Option<String>,
+}
+
+/// GET /api/inspect/documents/{id}/chunks - a page of a document's chunks
+/// with nearest neighbors and full-text matches
+pub async fn inspect_chunks_handler(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<String>,
+    Query(params): Query<InspectChunksParams>,
+) -> Result<Json<ChunkPage>, I18nError> {
+    let service = state.service.clone();
+    let page = tokio::task::spawn_blocking(move || {
+        service.inspect_document_chunks(
+            &document_id,
+            params.offset.unwrap_or(0),
+            params.limit.unwrap_or(20),
+            params.q.as_deref(),
+            params.neighbors.unwrap_or(5),
+        )
+    })
+    .await
+    .map_err(|e| {
+        state.i18n_error(ServiceError::Internal {
+            message: e.to_string(),
+        })
+    })?
+    .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(page))
+}
+
+/// GET /api/inspect/calls/{id} - a past MCP tool call and the text it gave
+/// the model
+pub async fn inspect_call_handler(
+    State(state): State<Arc<AppState>>,
+    Path(correlation_id): Path<String>,
+) -> Result<Json<InspectCallResponse>, I18nError> {
+    let trace = state
+        .service
+        .call_traces
+        .get(&correlation_id)
+        .ok_or_else(|| {
+            state.i18n_error(ServiceError::InvalidRequest {
+                message: format!("No trace for correlation ID {}", correlation_id),
+            })
+        })?;
+    Ok(Json(InspectCallResponse {
+        model_text: trace.model_text.clone(),
+        trace,
+    }))
+}
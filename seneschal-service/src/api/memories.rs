@@ -0,0 +1,105 @@
+//! Campaign memory API endpoints.
+//!
+//! Handlers for the GM to review, correct and remove remembered campaign
+//! facts.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::CampaignMemory;
+use crate::error::I18nError;
+
+use super::documents::DeleteResponse;
+use super::{AppState, request_world};
+
+/// Memory list parameters
+#[derive(Deserialize)]
+pub struct MemoryParams {
+    pub limit: Option<usize>,
+}
+
+/// Request to remember a fact
+#[derive(Deserialize)]
+pub struct AddMemoryRequest {
+    pub fact: String,
+    /// Share the fact with every world instead of the request's world
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// Request to replace a remembered fact
+#[derive(Deserialize)]
+pub struct UpdateMemoryRequest {
+    pub fact: String,
+}
+
+/// List memories visible from the request's world, most recently changed first
+pub async fn list_memories_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<MemoryParams>,
+) -> Result<Json<Vec<CampaignMemory>>, I18nError> {
+    let memories = state
+        .service
+        .db
+        .list_memories(
+            request_world(&headers).as_deref(),
+            params.limit.unwrap_or(200),
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(memories))
+}
+
+/// Remember a fact in the request's world
+pub async fn add_memory_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<AddMemoryRequest>,
+) -> Result<Json<CampaignMemory>, I18nError> {
+    let world_id = request_world(&headers).filter(|_| !request.shared);
+    let memory = state
+        .service
+        .add_memory(&request.fact, world_id)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(memory))
+}
+
+/// Replace a remembered fact
+pub async fn update_memory_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateMemoryRequest>,
+) -> Result<Json<CampaignMemory>, I18nError> {
+    let memory = state
+        .service
+        .update_memory(&id, &request.fact)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(memory))
+}
+
+/// Forget a remembered fact
+pub async fn delete_memory_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_memory(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Memory deleted".to_string()
+        } else {
+            format!("Memory not found: {}", id)
+        },
+    }))
+}
@@ -0,0 +1,96 @@
+//! Ollama model management API endpoints.
+//!
+//! Handlers for listing installed models, pulling new ones (progress is
+//! broadcast over the WebSocket as `model_pull_progress`), and deleting them.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::{I18nError, ServiceError};
+use crate::ollama::LocalModel;
+use crate::websocket::ModelPullUpdate;
+
+use super::AppState;
+
+/// Request to pull a model
+#[derive(Deserialize)]
+pub struct PullModelRequest {
+    pub model: String,
+}
+
+/// Response for a pull request
+#[derive(Serialize)]
+pub struct PullModelResponse {
+    pub model: String,
+    /// False if a pull of this model was already running
+    pub started: bool,
+}
+
+/// Response for model deletion
+#[derive(Serialize)]
+pub struct DeleteModelResponse {
+    pub success: bool,
+    pub model: String,
+}
+
+/// GET /api/models/local - installed models with size and modification date
+pub async fn list_local_models_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<LocalModel>>, I18nError> {
+    let models = state
+        .service
+        .list_local_models()
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(models))
+}
+
+/// POST /api/models/pull - start pulling a model in the background
+pub async fn pull_model_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<PullModelRequest>,
+) -> Result<(StatusCode, Json<PullModelResponse>), I18nError> {
+    let model = request.model.trim();
+    if model.is_empty() {
+        return Err(state.i18n_error(ServiceError::InvalidRequest {
+            message: "Model name is required".to_string(),
+        }));
+    }
+
+    let started = state.service.start_model_pull(model);
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(PullModelResponse {
+            model: model.to_string(),
+            started,
+        }),
+    ))
+}
+
+/// GET /api/models/pull - latest progress of pulls started since startup
+pub async fn model_pull_status_handler(
+    State(state): State<Arc<AppState>>,
+) -> Json<Vec<ModelPullUpdate>> {
+    Json(state.service.model_pull_status())
+}
+
+/// DELETE /api/models/{name} - delete an installed model
+pub async fn delete_model_handler(
+    State(state): State<Arc<AppState>>,
+    Path(model): Path<String>,
+) -> Result<Json<DeleteModelResponse>, I18nError> {
+    state
+        .service
+        .delete_model(&model)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteModelResponse {
+        success: true,
+        model,
+    }))
+}
@@ -0,0 +1,177 @@
+//! NPC registry API endpoints.
+//!
+//! Handlers for adding NPCs (e.g. from FVTT actors), recording their
+//! relationships, and exporting the relationship graph.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::Npc;
+use crate::error::I18nError;
+use crate::service::{NpcLink, NpcUpdate};
+
+use super::documents::DeleteResponse;
+use super::{AppState, request_world};
+
+/// Request to add or update an NPC
+#[derive(Deserialize)]
+pub struct SetNpcRequest {
+    pub npc_id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub stat_block_id: Option<String>,
+    pub actor_uuid: Option<String>,
+    /// Share the NPC with every world instead of the request's world
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// Request to record a relationship
+#[derive(Deserialize)]
+pub struct RelateNpcsRequest {
+    /// Name or ID of the NPC the relationship is from
+    pub source: String,
+    pub relation: String,
+    /// Name or ID of the NPC the relationship is to
+    pub target: String,
+    pub notes: Option<String>,
+}
+
+/// Graph export format
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    /// Graphviz DOT
+    Dot,
+}
+
+/// Graph export parameters
+#[derive(Deserialize)]
+pub struct GraphParams {
+    #[serde(default)]
+    pub format: GraphFormat,
+}
+
+/// List NPCs visible from the request's world, by name
+pub async fn list_npcs_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<Npc>>, I18nError> {
+    let npcs = state
+        .service
+        .db
+        .list_npcs(request_world(&headers).as_deref())
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(npcs))
+}
+
+/// Add an NPC to the request's world, or update one matched by ID or name
+pub async fn set_npc_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<SetNpcRequest>,
+) -> Result<Json<Npc>, I18nError> {
+    let world_id = request_world(&headers).filter(|_| !request.shared);
+    let npc = state
+        .service
+        .set_npc(
+            world_id,
+            NpcUpdate {
+                npc_id: request.npc_id,
+                name: request.name,
+                description: request.description,
+                stat_block_id: request.stat_block_id,
+                actor_uuid: request.actor_uuid,
+            },
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(npc))
+}
+
+/// Remove an NPC and their relationships
+pub async fn delete_npc_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_npc(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "NPC deleted".to_string()
+        } else {
+            format!("NPC not found: {}", id)
+        },
+    }))
+}
+
+/// Record a relationship between two NPCs in the request's world
+pub async fn relate_npcs_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<RelateNpcsRequest>,
+) -> Result<Json<NpcLink>, I18nError> {
+    let link = state
+        .service
+        .relate_npcs(
+            request_world(&headers),
+            &request.source,
+            &request.relation,
+            &request.target,
+            request.notes,
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(link))
+}
+
+/// Remove a relationship
+pub async fn delete_npc_relation_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_npc_relation(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Relationship deleted".to_string()
+        } else {
+            format!("Relationship not found: {}", id)
+        },
+    }))
+}
+
+/// Export the request's world's NPCs and relationships as JSON or Graphviz DOT
+pub async fn npc_graph_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<GraphParams>,
+) -> Result<Response, I18nError> {
+    let graph = state
+        .service
+        .npc_graph(request_world(&headers).as_deref())
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(match params.format {
+        GraphFormat::Json => Json(graph).into_response(),
+        GraphFormat::Dot => (
+            [(header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")],
+            graph.to_dot(),
+        )
+            .into_response(),
+    })
+}
@@ -2,14 +2,19 @@
 //!
 //! Handlers for semantic and text search operations.
 
-use axum::{Json, extract::State};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::error::I18nError;
+use crate::search::{DEFAULT_SNIPPET_CHARS, ErrataStatus, SearchResult, Snippet};
 use crate::tools::{SearchFilters, TagMatch};
 
-use super::AppState;
+use super::{AppState, request_world};
 
 /// Search request
 #[derive(Deserialize)]
@@ -19,6 +24,19 @@ pub struct SearchRequest {
     pub limit: Option<usize>,
     pub tags: Option<Vec<String>>,
     pub tags_match: Option<String>,
+    /// Snippet window length in bytes (default 300)
+    pub snippet_chars: Option<usize>,
+    /// Center snippets of results without query terms on the sentence
+    /// closest in meaning, at the cost of embedding their sentences
+    #[serde(default)]
+    pub semantic_highlight: bool,
+}
+
+/// Similar chunk query parameters
+#[derive(Deserialize)]
+pub struct SimilarChunksParams {
+    pub user_role: Option<u8>,
+    pub limit: Option<usize>,
 }
 
 /// Search response
@@ -36,20 +54,29 @@ pub struct SearchResultDto {
     pub section_title: Option<String>,
     pub page_number: Option<i32>,
     pub similarity: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errata: Option<ErrataStatus>,
+    /// Window around the best-matching sentence, with match offsets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<Snippet>,
 }
 
 /// Perform semantic search across documents
 pub async fn search_handler(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(request): Json<SearchRequest>,
 ) -> Result<Json<SearchResponse>, I18nError> {
-    let filters = if request.tags.is_some() || request.tags_match.is_some() {
+    let world_id = request_world(&headers);
+    let filters = if request.tags.is_some() || request.tags_match.is_some() || world_id.is_some() {
         Some(SearchFilters {
             tags: request.tags.unwrap_or_default(),
             tags_match: match request.tags_match.as_deref() {
                 Some("all") => TagMatch::All,
                 _ => TagMatch::Any,
             },
+            world_id,
+            ..Default::default()
         })
     } else {
         None
@@ -66,7 +93,46 @@ pub async fn search_handler(
         .await
         .map_err(|e| state.i18n_error(e))?;
 
-    Ok(Json(SearchResponse {
+    let snippets = state
+        .service
+        .search
+        .snippets(
+            &request.query,
+            &results,
+            request
+                .snippet_chars
+                .unwrap_or(DEFAULT_SNIPPET_CHARS)
+                .max(40),
+            request.semantic_highlight,
+        )
+        .await;
+
+    Ok(Json(search_response(results, snippets)))
+}
+
+/// Find chunks similar to a chunk, from other pages and documents
+pub async fn similar_chunks_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(params): Query<SimilarChunksParams>,
+) -> Result<Json<SearchResponse>, I18nError> {
+    let results = state
+        .service
+        .search_chunks_like(
+            &id,
+            params.user_role.unwrap_or(4), // Default to GM
+            params.limit.unwrap_or(10),
+            request_world(&headers).as_deref(),
+        )
+        .map_err(|e| state.i18n_error(e))?;
+
+    Ok(Json(search_response(results, Vec::new())))
+}
+
+fn search_response(results: Vec<SearchResult>, snippets: Vec<Snippet>) -> SearchResponse {
+    let mut snippets = snippets.into_iter();
+    SearchResponse {
         results: results
             .into_iter()
             .map(|r| SearchResultDto {
@@ -76,7 +142,9 @@ pub async fn search_handler(
                 section_title: r.chunk.section_title,
                 page_number: r.chunk.page_number,
                 similarity: r.similarity,
+                errata: r.errata,
+                snippet: snippets.next(),
             })
             .collect(),
-    }))
+    }
 }
@@ -1,12 +1,15 @@
 //! Settings API endpoints for managing backend configuration via FVTT module.
 
-use axum::{Json, extract::State};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::api::AppState;
-use crate::config::DynamicConfig;
+use crate::config::{DynamicConfig, RuntimeConfig};
 use crate::error::{I18nError, ServiceError};
 
 /// Response for GET /api/settings
@@ -72,3 +75,59 @@ pub async fn update_settings_handler(
     // Return updated settings
     get_settings_handler(State(state)).await
 }
+
+/// Response for GET /api/settings/worlds/{world_id}
+#[derive(Debug, Serialize)]
+pub struct WorldSettingsResponse {
+    pub world_id: String,
+    /// Settings as they apply to the world (defaults + DB + world overrides)
+    pub settings: HashMap<String, serde_json::Value>,
+    /// Which keys the world overrides
+    pub overridden: Vec<String>,
+    /// Keys a world may override
+    pub world_keys: Vec<&'static str>,
+}
+
+/// GET /api/settings/worlds/{world_id} - settings as they apply to a world
+pub async fn get_world_settings_handler(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<WorldSettingsResponse>, I18nError> {
+    let db = &state.service.db;
+    let overrides = db
+        .get_world_settings(&world_id)
+        .map_err(|e| state.i18n_error(e))?;
+    let config = RuntimeConfig::for_world(db, &world_id).map_err(|e| state.i18n_error(e))?;
+
+    let mut overridden: Vec<String> = overrides.into_keys().collect();
+    overridden.sort();
+    Ok(Json(WorldSettingsResponse {
+        world_id,
+        settings: config.to_key_value_map(),
+        overridden,
+        world_keys: DynamicConfig::world_keys(),
+    }))
+}
+
+/// PUT /api/settings/worlds/{world_id} - override settings for one world
+pub async fn update_world_settings_handler(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Json(request): Json<UpdateSettingsRequest>,
+) -> Result<Json<WorldSettingsResponse>, I18nError> {
+    for key in request.settings.keys() {
+        if !DynamicConfig::is_world_key(key) {
+            return Err(state.i18n_error(ServiceError::InvalidRequest {
+                message: format!("Setting can't be overridden per world: {}", key),
+            }));
+        }
+    }
+
+    state
+        .service
+        .update_world_settings(&world_id, request.settings)
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+
+    get_world_settings_handler(State(state), Path(world_id)).await
+}
@@ -0,0 +1,77 @@
+//! Text-to-speech API endpoints.
+//!
+//! Handlers for synthesizing speech from an answer or read-aloud passage,
+//! and downloading the clip, which the FVTT module does when it can't be
+//! written to assets directly.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Response,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{I18nError, ServiceError};
+use crate::service::SpeechSource;
+use crate::tools::AccessLevel;
+use crate::tts::SPEECH_MIME_TYPE;
+
+use super::{AppState, cached_file_response, request_world};
+
+/// Request to synthesize speech; exactly one of `text` and `passage_id`
+#[derive(Deserialize)]
+pub struct SynthesizeSpeechRequest {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub passage_id: Option<String>,
+}
+
+/// POST /api/speech - synthesize speech and deliver it to FVTT
+pub async fn synthesize_speech_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<SynthesizeSpeechRequest>,
+) -> Result<Json<serde_json::Value>, I18nError> {
+    let source = match (request.text, request.passage_id) {
+        (Some(text), None) => SpeechSource::Text(text),
+        (None, Some(passage_id)) => SpeechSource::ReadAloud(passage_id),
+        _ => {
+            return Err(state.i18n_error(ServiceError::InvalidRequest {
+                message: "Give either text or passage_id".to_string(),
+            }));
+        }
+    };
+
+    let clip = state
+        .service
+        .synthesize_speech(
+            &source,
+            AccessLevel::GmOnly as u8,
+            request_world(&headers).as_deref(),
+        )
+        .await
+        .map_err(|e| state.i18n_error(e))?;
+
+    let download_url = format!("/api/speech/{}", clip.file_name);
+    let mut body = serde_json::to_value(&clip).unwrap_or_default();
+    body["download_url"] = serde_json::Value::String(download_url);
+    Ok(Json(body))
+}
+
+/// GET /api/speech/{file_name} - stream a synthesized clip
+pub async fn get_speech_handler(
+    State(state): State<Arc<AppState>>,
+    Path(file_name): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, I18nError> {
+    let path = state
+        .service
+        .speech_path(&file_name)
+        .map_err(|e| state.i18n_error(e))?;
+
+    cached_file_response(&path, &file_name, SPEECH_MIME_TYPE.to_string(), &headers)
+        .map_err(|e| state.i18n_error(e))
+}
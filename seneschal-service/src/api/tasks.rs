@@ -0,0 +1,99 @@
+//! Campaign task API endpoints.
+//!
+//! Handlers for the GM to review, add and check off prep tasks and
+//! reminders.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::db::CampaignTask;
+use crate::error::I18nError;
+
+use super::documents::DeleteResponse;
+use super::{AppState, request_world};
+
+/// Task list parameters
+#[derive(Deserialize)]
+pub struct TaskParams {
+    #[serde(default)]
+    pub include_completed: bool,
+    pub limit: Option<usize>,
+}
+
+/// Request to add a task
+#[derive(Deserialize)]
+pub struct AddTaskRequest {
+    pub description: String,
+    /// Share the task with every world instead of the request's world
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// List tasks visible from the request's world, open ones first
+pub async fn list_tasks_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<TaskParams>,
+) -> Result<Json<Vec<CampaignTask>>, I18nError> {
+    let tasks = state
+        .service
+        .db
+        .list_tasks(
+            request_world(&headers).as_deref(),
+            params.include_completed,
+            params.limit.unwrap_or(200),
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(tasks))
+}
+
+/// Add a task in the request's world
+pub async fn add_task_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<AddTaskRequest>,
+) -> Result<Json<CampaignTask>, I18nError> {
+    let world_id = request_world(&headers).filter(|_| !request.shared);
+    let task = state
+        .service
+        .add_task(&request.description, world_id, None)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(task))
+}
+
+/// Mark a task done
+pub async fn complete_task_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<CampaignTask>, I18nError> {
+    let task = state
+        .service
+        .complete_task(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(task))
+}
+
+/// Delete a task
+pub async fn delete_task_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_task(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Task deleted".to_string()
+        } else {
+            format!("Task not found: {}", id)
+        },
+    }))
+}
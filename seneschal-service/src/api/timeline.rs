@@ -0,0 +1,199 @@
+//! Campaign timeline API endpoints.
+//!
+//! Handlers for querying the timeline, adding and removing events by hand,
+//! extracting dated events from a document, and reading or setting the
+//! campaign clock.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::db::TimelineEvent;
+use crate::error::{I18nError, ServiceError};
+use crate::ingestion::timeline::ImperialDate;
+use crate::tools::AccessLevel;
+
+use super::documents::DeleteResponse;
+use super::{AppState, request_world};
+
+/// Timeline query parameters
+#[derive(Deserialize)]
+pub struct TimelineParams {
+    /// Earliest date, as "1105" or "187-1105"
+    pub from: Option<String>,
+    /// Latest date (a bare year includes the whole year)
+    pub to: Option<String>,
+    /// Text matched against event descriptions and locations
+    pub query: Option<String>,
+    pub document_id: Option<String>,
+    pub user_role: Option<u8>,
+    pub limit: Option<usize>,
+}
+
+/// Request to add a timeline event
+#[derive(Deserialize)]
+pub struct AddTimelineEventRequest {
+    pub date: String,
+    pub description: String,
+    pub location: Option<String>,
+    #[serde(default)]
+    pub access_level: AccessLevel,
+    pub document_id: Option<String>,
+}
+
+/// Response for timeline extraction
+#[derive(Serialize)]
+pub struct ExtractTimelineResponse {
+    pub success: bool,
+    pub event_count: usize,
+}
+
+fn parse_date(state: &AppState, value: Option<&str>) -> Result<Option<ImperialDate>, I18nError> {
+    value
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| {
+            ImperialDate::parse(v).ok_or_else(|| {
+                state.i18n_error(ServiceError::InvalidRequest {
+                    message: format!("Invalid Imperial date: {}", v),
+                })
+            })
+        })
+        .transpose()
+}
+
+/// List timeline events, oldest first
+pub async fn list_timeline_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<TimelineParams>,
+) -> Result<Json<Vec<TimelineEvent>>, I18nError> {
+    let user_role = params.user_role.unwrap_or(4); // Default to GM access
+    let from = parse_date(&state, params.from.as_deref())?;
+    let to = parse_date(&state, params.to.as_deref())?;
+    let events = state
+        .service
+        .db
+        .query_timeline(
+            from,
+            to,
+            params.query.as_deref().filter(|q| !q.is_empty()),
+            params.document_id.as_deref(),
+            user_role,
+            request_world(&headers).as_deref(),
+            params.limit.unwrap_or(200),
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(events))
+}
+
+/// Add a timeline event by hand
+pub async fn add_timeline_event_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AddTimelineEventRequest>,
+) -> Result<Json<TimelineEvent>, I18nError> {
+    let date = parse_date(&state, Some(&request.date))?.ok_or_else(|| {
+        state.i18n_error(ServiceError::InvalidRequest {
+            message: "Missing date".to_string(),
+        })
+    })?;
+    let event = state
+        .service
+        .add_timeline_event(
+            date,
+            &request.description,
+            request.location,
+            request.access_level,
+            request.document_id,
+        )
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(event))
+}
+
+/// Delete a timeline event
+pub async fn delete_timeline_event_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DeleteResponse>, I18nError> {
+    let deleted = state
+        .service
+        .db
+        .delete_timeline_event(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(DeleteResponse {
+        success: deleted,
+        message: if deleted {
+            "Timeline event deleted".to_string()
+        } else {
+            format!("Timeline event not found: {}", id)
+        },
+    }))
+}
+
+/// Extract dated events from a document, replacing those extracted before
+pub async fn extract_document_timeline_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ExtractTimelineResponse>, I18nError> {
+    let event_count = state
+        .service
+        .extract_document_timeline(&id)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(ExtractTimelineResponse {
+        success: true,
+        event_count,
+    }))
+}
+
+/// Campaign clock query parameters
+#[derive(Deserialize)]
+pub struct ClockParams {
+    /// FVTT world; defaults to the MCP world
+    pub world_id: Option<String>,
+}
+
+/// Request to set the campaign clock
+#[derive(Deserialize)]
+pub struct SetClockRequest {
+    pub world_id: Option<String>,
+    pub date: String,
+}
+
+/// The campaign clock
+#[derive(Serialize)]
+pub struct ClockResponse {
+    /// `None` until the clock has been set
+    pub date: Option<ImperialDate>,
+}
+
+/// Get a world's current Imperial date
+pub async fn get_clock_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ClockParams>,
+) -> Result<Json<ClockResponse>, I18nError> {
+    let date = state
+        .service
+        .campaign_date(params.world_id.as_deref())
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(ClockResponse { date }))
+}
+
+/// Set a world's current Imperial date
+pub async fn set_clock_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SetClockRequest>,
+) -> Result<Json<ClockResponse>, I18nError> {
+    let date = parse_date(&state, Some(&request.date))?.ok_or_else(|| {
+        state.i18n_error(ServiceError::InvalidRequest {
+            message: "Missing date".to_string(),
+        })
+    })?;
+    let date = state
+        .service
+        .set_campaign_date(request.world_id.as_deref(), date)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(ClockResponse { date: Some(date) }))
+}
@@ -0,0 +1,56 @@
+//! Model usage API endpoints.
+//!
+//! Reports how many Ollama requests, tokens and how much time went to each
+//! feature, model, tool, user or conversation over a period, to find what is
+//! keeping the model host busy.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::db::{UsageGrouping, UsageTotals};
+use crate::error::I18nError;
+
+use super::AppState;
+
+/// Usage report query parameters
+#[derive(Deserialize)]
+pub struct UsageParams {
+    #[serde(default)]
+    pub group_by: UsageGrouping,
+    /// Start of the period (RFC 3339)
+    pub since: Option<DateTime<Utc>>,
+    /// End of the period, exclusive (RFC 3339)
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Usage report response
+#[derive(Serialize)]
+pub struct UsageReport {
+    pub group_by: UsageGrouping,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub rows: Vec<UsageTotals>,
+}
+
+/// Usage totals for a period, the groups taking the most time first
+pub async fn usage_report_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<UsageParams>,
+) -> Result<Json<UsageReport>, I18nError> {
+    let rows = state
+        .service
+        .db
+        .usage_report(params.group_by, params.since, params.until)
+        .map_err(|e| state.i18n_error(e))?;
+    Ok(Json(UsageReport {
+        group_by: params.group_by,
+        since: params.since,
+        until: params.until,
+        rows,
+    }))
+}
@@ -4,7 +4,16 @@
 //! automatically imports them into the system. Successfully imported files
 //! are deleted (since they're now stored in the documents directory). Failed
 //! imports are moved to a `failed/` subdirectory.
+//!
+//! An Obsidian vault can be watched separately; see [`vault`].
+
+mod vault;
 
+pub use vault::start_vault_import_worker;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -24,6 +33,20 @@ const FAILED_DIRECTORY: &str = "failed";
 /// Interval between directory scans (in seconds)
 const POLL_INTERVAL_SECS: u64 = 10;
 
+/// Metadata key of an encrypted document's path in the import directory
+const IMPORT_PATH_KEY: &str = "auto_import_path";
+
+/// Outcome of the most recent auto-import attempt
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoImportRun {
+    /// Path relative to the auto-import directory
+    pub file: String,
+    /// "imported", "duplicate", "reingested" (vault notes) or "failed"
+    pub outcome: &'static str,
+    pub error: Option<String>,
+    pub finished_at: DateTime<Utc>,
+}
+
 /// Start the auto-import worker.
 ///
 /// This should be called once on server startup if `auto_import_dir` is configured.
@@ -113,7 +136,29 @@ async fn scan_and_process_one(
 
     debug!(file = %display_path, "Processing auto-import file");
 
-    match process_file(service, file_path).await {
+    let relative = file_path.strip_prefix(auto_import_dir).unwrap_or(file_path);
+    let pdf_password = directory_password(
+        &service
+            .runtime_config
+            .static_config
+            .storage
+            .auto_import_pdf_passwords,
+        relative,
+    );
+    let result = process_file(service, file_path, relative, pdf_password).await;
+    let (outcome, error) = match &result {
+        Ok(ProcessResult::Imported) => ("imported", None),
+        Ok(ProcessResult::Duplicate { .. }) => ("duplicate", None),
+        Err(e) => ("failed", Some(e.to_string())),
+    };
+    *service.last_auto_import.lock().unwrap() = Some(AutoImportRun {
+        file: display_path.clone(),
+        outcome,
+        error,
+        finished_at: Utc::now(),
+    });
+
+    match result {
         Ok(ProcessResult::Imported) => {
             // Delete the original file - it's now stored in the documents directory
             if let Err(e) = std::fs::remove_file(file_path) {
@@ -220,13 +265,57 @@ fn cleanup_empty_dirs(start: Option<&Path>, base_dir: &Path) {
 }
 
 /// Check if a file has a supported extension.
-fn is_supported_format(path: &Path) -> bool {
+pub(crate) fn is_supported_format(path: &Path) -> bool {
     path.extension()
         .and_then(|ext| ext.to_str())
         .map(|ext| SUPPORTED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
         .unwrap_or(false)
 }
 
+/// Password configured for the nearest directory containing `relative_path`
+fn directory_password<'a>(
+    passwords: &'a HashMap<PathBuf, String>,
+    relative_path: &Path,
+) -> Option<&'a str> {
+    relative_path
+        .ancestors()
+        .skip(1)
+        .find_map(|dir| passwords.get(dir))
+        .map(String::as_str)
+}
+
+/// Give encrypted auto-imported documents their passwords back after a
+/// restart, from the directory they were imported from. Returns how many
+/// were restored.
+pub fn restore_pdf_passwords(service: &SeneschalService) -> ServiceResult<usize> {
+    let passwords = &service
+        .runtime_config
+        .static_config
+        .storage
+        .auto_import_pdf_passwords;
+    if passwords.is_empty() {
+        return Ok(0);
+    }
+
+    let mut restored = 0;
+    for document in service.db.list_documents(None)? {
+        if !document.is_encrypted() {
+            continue;
+        }
+        let password = document
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(IMPORT_PATH_KEY))
+            .and_then(|v| v.as_str())
+            .and_then(|path| directory_password(passwords, Path::new(path)));
+        if password.is_some() {
+            service.remember_pdf_password(&document.id, password);
+            restored += 1;
+        }
+    }
+    Ok(restored)
+}
+
 /// Result of processing a file.
 enum ProcessResult {
     /// File was successfully imported
@@ -239,6 +328,8 @@ enum ProcessResult {
 async fn process_file(
     service: &SeneschalService,
     file_path: &Path,
+    relative: &Path,
+    pdf_password: Option<&str>,
 ) -> ServiceResult<ProcessResult> {
     let filename = file_path
         .file_name()
@@ -265,21 +356,40 @@ async fn process_file(
         .unwrap_or(filename)
         .to_string();
 
-    // Use upload_document with default settings:
+    // Upload with default settings, validated like API uploads:
     // - access_level: GmOnly (as per requirements)
     // - tags: empty (as per requirements)
     // - vision_model: None (no captioning for auto-import)
     let document = service
-        .upload_document(
+        .upload_document_with_progress(
             &content,
             filename,
             &title,
             AccessLevel::GmOnly,
             vec![],
             None,
+            None,
+            None,
+            pdf_password,
+            None,
         )
         .await?;
 
+    // Passwords are only kept in memory; the path finds it again after a restart
+    if document.is_encrypted() {
+        let mut metadata = match document.metadata {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        metadata.insert(
+            IMPORT_PATH_KEY.to_string(),
+            serde_json::json!(relative.to_string_lossy()),
+        );
+        service
+            .db
+            .update_document_metadata(&document.id, Some(serde_json::Value::Object(metadata)))?;
+    }
+
     info!(
         doc_id = %document.id,
         title = %title,
@@ -310,4 +420,25 @@ mod tests {
         assert!(!is_supported_format(&PathBuf::from("test.jpg")));
         assert!(!is_supported_format(&PathBuf::from("test")));
     }
+
+    #[test]
+    fn test_directory_password() {
+        let passwords = HashMap::from([
+            (PathBuf::from(""), "root".to_string()),
+            (PathBuf::from("mongoose"), "2300ad".to_string()),
+        ]);
+
+        let password = |path: &str| directory_password(&passwords, Path::new(path));
+        assert_eq!(password("core.pdf"), Some("root"));
+        assert_eq!(password("mongoose/high-guard.pdf"), Some("2300ad"));
+        assert_eq!(
+            password("mongoose/supplements/vehicles.pdf"),
+            Some("2300ad")
+        );
+        assert_eq!(password("other/book.pdf"), Some("root"));
+        assert_eq!(
+            directory_password(&HashMap::new(), Path::new("a/b.pdf")),
+            None
+        );
+    }
 }
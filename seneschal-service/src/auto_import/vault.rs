@@ -0,0 +1,304 @@
+//! Obsidian vault import.
+//!
+//! Notes are imported in place and never moved or deleted. Each is tracked by
+//! its path within the vault (`vault_path` in document metadata) and
+//! re-ingested when its content changes. Frontmatter tags become document
+//! tags, and `[[wiki links]]` are resolved to the linked notes' documents and
+//! stored in metadata as `wiki_links`.
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use crate::db::Document;
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::hash::compute_content_hash;
+use crate::ingestion::markdown::obsidian::{
+    frontmatter_tags, split_frontmatter, wiki_link_targets,
+};
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+
+use super::AutoImportRun;
+
+/// Interval between vault scans (in seconds)
+const VAULT_POLL_INTERVAL_SECS: u64 = 30;
+
+/// Start the Obsidian vault import worker.
+///
+/// This should be called once on server startup if `obsidian_vault_dir` is configured.
+pub fn start_vault_import_worker(service: Arc<SeneschalService>, vault_dir: PathBuf) {
+    tokio::spawn(async move {
+        info!(path = %vault_dir.display(), "Obsidian vault import worker started");
+
+        // Notes that failed to import, by path, with the hash that failed
+        let mut failed: HashMap<String, String> = HashMap::new();
+
+        loop {
+            match sync_vault(&service, &vault_dir, &mut failed).await {
+                Ok(0) => {}
+                Ok(changed) => info!(notes = changed, "Synced Obsidian vault"),
+                Err(e) => error!(error = %e, "Obsidian vault scan error"),
+            }
+            tokio::time::sleep(Duration::from_secs(VAULT_POLL_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Recursively collect markdown notes, skipping hidden entries such as
+/// `.obsidian/` and `.trash/`.
+fn collect_notes(dir: &Path, notes: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'))
+        {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_notes(&path, notes)?;
+        } else if path.is_file()
+            && path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        {
+            notes.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Import new notes and re-ingest changed ones, then refresh wiki link
+/// resolution. Returns the number of notes imported or re-ingested.
+async fn sync_vault(
+    service: &SeneschalService,
+    vault_dir: &Path,
+    failed: &mut HashMap<String, String>,
+) -> ServiceResult<usize> {
+    let mut notes = Vec::new();
+    collect_notes(vault_dir, &mut notes)
+        .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+    notes.sort();
+
+    let existing: HashMap<String, Document> = service
+        .db
+        .list_documents(None)?
+        .into_iter()
+        .filter_map(|doc| Some((vault_path(&doc)?.to_string(), doc)))
+        .collect();
+
+    let mut changed = 0;
+    for note in &notes {
+        let relative = note
+            .strip_prefix(vault_dir)
+            .unwrap_or(note)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let content =
+            std::fs::read(note).map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+        let hash = compute_content_hash(&content);
+        let document = existing.get(&relative);
+        if document.is_some_and(|doc| doc.file_hash.as_deref() == Some(hash.as_str()))
+            || failed.get(&relative) == Some(&hash)
+        {
+            continue;
+        }
+
+        debug!(note = %relative, "Syncing Obsidian note");
+        let result = sync_note(service, note, &relative, &content, document).await;
+        let (outcome, error) = match &result {
+            Ok(None) => continue,
+            Ok(Some(outcome)) => (*outcome, None),
+            Err(e) => ("failed", Some(e.to_string())),
+        };
+        *service.last_auto_import.lock().unwrap() = Some(AutoImportRun {
+            file: relative.clone(),
+            outcome,
+            error,
+            finished_at: Utc::now(),
+        });
+
+        match result {
+            Ok(_) => {
+                failed.remove(&relative);
+                changed += 1;
+            }
+            Err(e) => {
+                warn!(note = %relative, error = %e, "Failed to import Obsidian note");
+                failed.insert(relative, hash);
+            }
+        }
+    }
+
+    if changed > 0 {
+        resolve_wiki_links(service)?;
+    }
+    Ok(changed)
+}
+
+/// Import or re-ingest one note. Returns `None` for notes with no content.
+async fn sync_note(
+    service: &SeneschalService,
+    path: &Path,
+    relative: &str,
+    content: &[u8],
+    document: Option<&Document>,
+) -> ServiceResult<Option<&'static str>> {
+    let text = String::from_utf8_lossy(content);
+    let (frontmatter, body) = split_frontmatter(&text);
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let tags = frontmatter.map(frontmatter_tags).unwrap_or_default();
+    let links: Vec<serde_json::Value> = wiki_link_targets(body)
+        .into_iter()
+        .map(|target| serde_json::json!({ "target": target, "document_id": null }))
+        .collect();
+    let metadata = serde_json::json!({
+        "vault_path": relative,
+        "wiki_links": links,
+    });
+
+    if let Some(document) = document {
+        service.reingest_document(&document.id, content, tags, Some(metadata))?;
+        return Ok(Some("reingested"));
+    }
+
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("note.md");
+    let title = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(filename);
+    let document = service
+        .upload_document(
+            content,
+            filename,
+            title,
+            AccessLevel::GmOnly,
+            tags,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    service
+        .db
+        .update_document_metadata(&document.id, Some(metadata))?;
+
+    info!(doc_id = %document.id, note = %relative, "Imported Obsidian note");
+    Ok(Some("imported"))
+}
+
+/// Point every vault document's wiki links at the documents of the notes they name.
+fn resolve_wiki_links(service: &SeneschalService) -> ServiceResult<()> {
+    let documents: Vec<Document> = service
+        .db
+        .list_documents(None)?
+        .into_iter()
+        .filter(|doc| vault_path(doc).is_some())
+        .collect();
+    let index = note_index(
+        documents
+            .iter()
+            .filter_map(|doc| Some((vault_path(doc)?, doc.id.as_str()))),
+    );
+
+    for document in &documents {
+        let Some(mut metadata) = document.metadata.clone() else {
+            continue;
+        };
+        let Some(links) = metadata
+            .get_mut("wiki_links")
+            .and_then(|links| links.as_array_mut())
+        else {
+            continue;
+        };
+
+        let mut updated = false;
+        for link in links {
+            let resolved = link
+                .get("target")
+                .and_then(|target| target.as_str())
+                .and_then(|target| index.get(&target.to_lowercase()))
+                .map(|id| serde_json::Value::from(*id))
+                .unwrap_or(serde_json::Value::Null);
+            if link.get("document_id") != Some(&resolved) {
+                link["document_id"] = resolved;
+                updated = true;
+            }
+        }
+
+        if updated {
+            service
+                .db
+                .update_document_metadata(&document.id, Some(metadata))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Map lowercased link targets to document IDs.
+///
+/// As in Obsidian, a note can be linked by its path within the vault or by
+/// its name alone; when names collide the first path in sort order wins.
+fn note_index<'a>(notes: impl Iterator<Item = (&'a str, &'a str)>) -> HashMap<String, &'a str> {
+    let mut notes: Vec<(String, &str)> = notes
+        .map(|(path, id)| {
+            let note = path.strip_suffix(".md").unwrap_or(path).to_lowercase();
+            (note, id)
+        })
+        .collect();
+    notes.sort();
+
+    let mut index = HashMap::new();
+    for (note, id) in &notes {
+        let name = note.rsplit('/').next().unwrap_or(note);
+        index.entry(name.to_string()).or_insert(*id);
+    }
+    for (note, id) in notes {
+        index.insert(note, id);
+    }
+    index
+}
+
+/// The note path of a document imported from the vault
+fn vault_path(document: &Document) -> Option<&str> {
+    document.metadata.as_ref()?.get("vault_path")?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_index() {
+        let index = note_index(
+            [
+                ("Systems/Regina.md", "regina"),
+                ("NPCs/Captain Reyes.md", "reyes"),
+                ("Archive/Captain Reyes.md", "old-reyes"),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(index.get("regina"), Some(&"regina"));
+        assert_eq!(index.get("systems/regina"), Some(&"regina"));
+        assert_eq!(index.get("captain reyes"), Some(&"old-reyes"));
+        assert_eq!(index.get("npcs/captain reyes"), Some(&"reyes"));
+        assert_eq!(index.get("efate"), None);
+    }
+}
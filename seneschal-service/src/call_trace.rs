@@ -0,0 +1,236 @@
+//! Correlation IDs and stage timings for MCP tool calls.
+//!
+//! Each tool call gets a correlation ID and runs inside a tracing span that
+//! carries it, so log lines from the tool, its Ollama requests and the GM
+//! client round trip can be grouped. The stages of the call are timed into
+//! a trace, and the most recent traces are kept in memory, with the text
+//! each call returned to the model, for debugging slow or wrong answers.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{Instrument, info_span};
+
+/// Traces kept in memory; older ones are dropped
+const MAX_TRACES: usize = 200;
+
+tokio::task_local! {
+    static CURRENT: Arc<Mutex<CallTrace>>;
+}
+
+/// A timed part of a tool call
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceStage {
+    pub name: String,
+    /// Milliseconds from the start of the call to the start of the stage
+    pub offset_ms: u64,
+    pub duration_ms: u64,
+    pub ok: bool,
+}
+
+/// Timings of one tool call
+#[derive(Debug, Clone, Serialize)]
+pub struct CallTrace {
+    pub correlation_id: String,
+    pub tool: String,
+    pub session_id: Option<String>,
+    /// Name the MCP client gave when it initialized the session
+    pub client: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// Unset while the call is running
+    pub duration_ms: Option<u64>,
+    pub ok: Option<bool>,
+    pub stages: Vec<TraceStage>,
+    /// The result text returned to the model, after compaction
+    #[serde(skip)]
+    pub model_text: Option<String>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+impl CallTrace {
+    fn new(
+        correlation_id: &str,
+        tool: &str,
+        session_id: Option<&str>,
+        client: Option<&str>,
+    ) -> Self {
+        Self {
+            correlation_id: correlation_id.to_string(),
+            tool: tool.to_string(),
+            session_id: session_id.map(String::from),
+            client: client.map(String::from),
+            started_at: Utc::now(),
+            duration_ms: None,
+            ok: None,
+            stages: Vec::new(),
+            model_text: None,
+            started: Instant::now(),
+        }
+    }
+
+    fn add_stage(&mut self, name: &str, started: Instant, ok: bool) {
+        self.stages.push(TraceStage {
+            name: name.to_string(),
+            offset_ms: started.saturating_duration_since(self.started).as_millis() as u64,
+            duration_ms: started.elapsed().as_millis() as u64,
+            ok,
+        });
+    }
+}
+
+/// Recent tool call traces, newest last
+#[derive(Debug, Default)]
+pub struct CallTraceStore {
+    traces: Mutex<VecDeque<CallTrace>>,
+}
+
+impl CallTraceStore {
+    /// Run a tool call under a correlation ID, keeping its trace when it finishes
+    pub async fn run<T, E>(
+        &self,
+        correlation_id: &str,
+        tool: &str,
+        session_id: Option<&str>,
+        client: Option<&str>,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let trace = Arc::new(Mutex::new(CallTrace::new(
+            correlation_id,
+            tool,
+            session_id,
+            client,
+        )));
+        let span = info_span!("tool_call", correlation_id = %correlation_id, tool = %tool);
+        let result = CURRENT.scope(trace.clone(), call.instrument(span)).await;
+
+        let mut finished = trace.lock().unwrap().clone();
+        finished.duration_ms = Some(finished.started.elapsed().as_millis() as u64);
+        finished.ok = Some(result.is_ok());
+
+        let mut traces = self.traces.lock().unwrap();
+        if traces.len() >= MAX_TRACES {
+            traces.pop_front();
+        }
+        traces.push_back(finished);
+
+        result
+    }
+
+    /// The trace of a call by correlation ID
+    pub fn get(&self, correlation_id: &str) -> Option<CallTrace> {
+        self.traces
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|t| t.correlation_id == correlation_id)
+            .cloned()
+    }
+
+    /// Keep the text a finished call returned to the model
+    pub fn set_model_text(&self, correlation_id: &str, text: String) {
+        if let Some(trace) = self
+            .traces
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .rev()
+            .find(|t| t.correlation_id == correlation_id)
+        {
+            trace.model_text = Some(text);
+        }
+    }
+
+    /// Most recent traces first, optionally only those of one MCP session
+    pub fn recent(&self, session_id: Option<&str>, limit: usize) -> Vec<CallTrace> {
+        self.traces
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|t| session_id.is_none_or(|s| t.session_id.as_deref() == Some(s)))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Record a stage of the tool call running on this task that began at
+/// `started` and has just ended. Does nothing outside a traced call.
+pub fn record_stage(name: &str, started: Instant, ok: bool) {
+    let _ = CURRENT.try_with(|trace| trace.lock().unwrap().add_stage(name, started, ok));
+}
+
+/// The tool call running on this task, if any: (tool, session ID, client)
+pub fn current_call() -> Option<(String, Option<String>, Option<String>)> {
+    CURRENT
+        .try_with(|trace| {
+            let trace = trace.lock().unwrap();
+            (
+                trace.tool.clone(),
+                trace.session_id.clone(),
+                trace.client.clone(),
+            )
+        })
+        .ok()
+}
+
+/// Correlation ID of the tool call running on this task, if any
+pub fn current_correlation_id() -> Option<String> {
+    CURRENT
+        .try_with(|trace| trace.lock().unwrap().correlation_id.clone())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traced_call() {
+        let store = CallTraceStore::default();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let result: Result<u32, String> = runtime.block_on(store.run(
+            "call-1",
+            "document_search",
+            Some("session"),
+            None,
+            async {
+                assert_eq!(current_correlation_id().as_deref(), Some("call-1"));
+                assert_eq!(
+                    current_call(),
+                    Some((
+                        "document_search".to_string(),
+                        Some("session".to_string()),
+                        None
+                    ))
+                );
+                record_stage("ollama nomic-embed-text", Instant::now(), true);
+                Ok(3)
+            },
+        ));
+        assert_eq!(result, Ok(3));
+        assert!(current_correlation_id().is_none());
+        record_stage("outside", Instant::now(), true);
+
+        let trace = store.get("call-1").unwrap();
+        assert_eq!(trace.ok, Some(true));
+        assert!(trace.duration_ms.is_some());
+        let stages: Vec<&str> = trace.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(stages, ["ollama nomic-embed-text"]);
+        store.set_model_text("call-1", "3 results".to_string());
+        assert_eq!(
+            store.get("call-1").unwrap().model_text.as_deref(),
+            Some("3 results")
+        );
+        assert_eq!(store.recent(Some("other"), 10).len(), 0);
+        assert_eq!(store.recent(Some("session"), 10).len(), 1);
+    }
+}
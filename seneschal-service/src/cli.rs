@@ -0,0 +1,479 @@
+//! Command-line administration.
+//!
+//! Without arguments (or with `serve`) the binary runs the service. The
+//! other subcommands are a client for a running service: they go through
+//! the REST API, so they behave the same against a local or remote server
+//! and ingestion still runs on the server's processing workers.
+
+use std::path::{Path, PathBuf};
+
+use reqwest::multipart::{Form, Part};
+use serde_json::Value;
+
+use crate::auto_import::is_supported_format;
+use crate::config::load_static_config;
+
+pub const USAGE: &str = "\
+Usage: seneschal-service [--server URL] [COMMAND]
+
+Commands:
+  serve                     Run the service (default)
+  list [--json]             List documents
+  ingest PATH...            Upload files, or supported files below folders
+      [--access-level LEVEL] [--tags TAG,...] [--world WORLD_ID]
+      [--password PASSWORD]  (for encrypted PDFs)
+  reindex (ID... | --all)   Re-process documents from their stored files
+  search QUERY [--limit N]  Search document chunks
+  export [--output FILE]    Write documents, campaign memory, timeline and
+                            errata as JSON (stdout by default)
+  help                      Show this help
+
+The server URL defaults to SENESCHAL_URL, then to the configured host and
+port. Servers requiring a GM client certificate can't be reached this way.";
+
+/// Outcome of a client command; partial failures are errors too
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// A parsed command line
+#[derive(Debug, PartialEq)]
+pub struct Cli {
+    /// Base URL of the service, when given with `--server`
+    pub server: Option<String>,
+    pub command: Command,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Serve,
+    Help,
+    List {
+        json: bool,
+    },
+    Ingest {
+        paths: Vec<PathBuf>,
+        access_level: Option<String>,
+        tags: Option<String>,
+        world: Option<String>,
+        password: Option<String>,
+    },
+    Reindex {
+        document_ids: Vec<String>,
+        all: bool,
+    },
+    Search {
+        query: String,
+        limit: usize,
+    },
+    Export {
+        output: Option<PathBuf>,
+    },
+}
+
+/// Parse the arguments after the program name
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Cli, String> {
+    let mut args = args.into_iter();
+    let mut server = None;
+    let mut positional = Vec::new();
+    let mut options: Vec<(String, Option<String>)> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--server" => server = Some(args.next().ok_or("--server needs a URL")?),
+            "-h" | "--help" => positional.insert(0, "help".to_string()),
+            "--json" | "--all" => options.push((arg, None)),
+            flag if flag.starts_with("--") => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", flag))?;
+                options.push((arg, Some(value)));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let name = positional.next().unwrap_or_else(|| "serve".to_string());
+    let rest: Vec<String> = positional.collect();
+    let option = |name: &str| {
+        options
+            .iter()
+            .find(|(flag, _)| flag == name)
+            .and_then(|(_, value)| value.clone())
+    };
+    let flag = |name: &str| options.iter().any(|(flag, _)| flag == name);
+
+    let allowed: &[&str] = match name.as_str() {
+        "list" => &["--json"],
+        "ingest" => &["--access-level", "--tags", "--world", "--password"],
+        "reindex" => &["--all"],
+        "search" => &["--limit"],
+        "export" => &["--output"],
+        _ => &[],
+    };
+    if let Some((unknown, _)) = options.iter().find(|(f, _)| !allowed.contains(&f.as_str())) {
+        return Err(format!("Unknown option {} for {}", unknown, name));
+    }
+
+    let command = match name.as_str() {
+        "serve" => Command::Serve,
+        "help" => Command::Help,
+        "list" => Command::List {
+            json: flag("--json"),
+        },
+        "ingest" if rest.is_empty() => return Err("ingest needs at least one path".to_string()),
+        "ingest" => Command::Ingest {
+            paths: rest.iter().map(PathBuf::from).collect(),
+            access_level: option("--access-level"),
+            tags: option("--tags"),
+            world: option("--world"),
+            password: option("--password"),
+        },
+        "reindex" if rest.is_empty() != flag("--all") => {
+            return Err("reindex needs document IDs or --all".to_string());
+        }
+        "reindex" => Command::Reindex {
+            document_ids: rest.clone(),
+            all: flag("--all"),
+        },
+        "search" if rest.is_empty() => return Err("search needs a query".to_string()),
+        "search" => Command::Search {
+            query: rest.join(" "),
+            limit: option("--limit")
+                .map(|limit| limit.parse().map_err(|_| "--limit needs a number"))
+                .transpose()?
+                .unwrap_or(10),
+        },
+        "export" => Command::Export {
+            output: option("--output").map(PathBuf::from),
+        },
+        other => return Err(format!("Unknown command: {}", other)),
+    };
+
+    if !rest.is_empty() && matches!(command, Command::List { .. } | Command::Export { .. }) {
+        return Err(format!("Unexpected argument: {}", rest[0]));
+    }
+
+    Ok(Cli { server, command })
+}
+
+/// Run a client command against the service
+pub async fn run(cli: Cli) -> CliResult {
+    let base = match cli.server.or_else(|| std::env::var("SENESCHAL_URL").ok()) {
+        Some(url) => url,
+        None => configured_url()?,
+    };
+    let client = ApiClient {
+        http: reqwest::Client::new(),
+        base: format!("{}/api", base.trim_end_matches('/')),
+    };
+
+    match cli.command {
+        Command::Serve => Ok(()),
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Command::List { json } => list(&client, json).await,
+        Command::Ingest {
+            paths,
+            access_level,
+            tags,
+            world,
+            password,
+        } => {
+            ingest(
+                &client,
+                &paths,
+                access_level.as_deref(),
+                tags.as_deref(),
+                world.as_deref(),
+                password.as_deref(),
+            )
+            .await
+        }
+        Command::Reindex { document_ids, all } => reindex(&client, document_ids, all).await,
+        Command::Search { query, limit } => search(&client, &query, limit).await,
+        Command::Export { output } => export(&client, output.as_deref()).await,
+    }
+}
+
+/// Local URL of the service from the static config
+fn configured_url() -> Result<String, Box<dyn std::error::Error>> {
+    let server = load_static_config()?.server;
+    let host = match server.host.as_str() {
+        "0.0.0.0" | "::" | "[::]" => "127.0.0.1",
+        host => host,
+    };
+    let scheme = if server.tls.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    Ok(format!("{}://{}:{}", scheme, host, server.port))
+}
+
+struct ApiClient {
+    http: reqwest::Client,
+    base: String,
+}
+
+impl ApiClient {
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, String> {
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(body
+                .get("message")
+                .and_then(|m| m.as_str())
+                .map_or_else(|| status.to_string(), String::from))
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<Value, String> {
+        self.send(self.http.get(format!("{}{}", self.base, path)))
+            .await
+    }
+
+    async fn post(&self, path: &str, body: &Value) -> Result<Value, String> {
+        self.send(self.http.post(format!("{}{}", self.base, path)).json(body))
+            .await
+    }
+}
+
+async fn list(client: &ApiClient, json: bool) -> CliResult {
+    let documents = client.get("/documents").await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&documents)?);
+        return Ok(());
+    }
+
+    for doc in documents.as_array().into_iter().flatten() {
+        println!(
+            "{}  {:<10} {:>6} chunks  {}",
+            text(doc, "id"),
+            text(doc, "processing_status"),
+            doc.get("chunk_count").and_then(|c| c.as_u64()).unwrap_or(0),
+            text(doc, "title"),
+        );
+    }
+    Ok(())
+}
+
+async fn ingest(
+    client: &ApiClient,
+    paths: &[PathBuf],
+    access_level: Option<&str>,
+    tags: Option<&str>,
+    world: Option<&str>,
+    password: Option<&str>,
+) -> CliResult {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(supported_files_under(path));
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut failed = 0;
+    for file in &files {
+        let result = async {
+            let data = std::fs::read(file).map_err(|e| e.to_string())?;
+            let filename = file
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "document".to_string());
+            let mut form = Form::new().part("file", Part::bytes(data).file_name(filename));
+            if let Some(level) = access_level {
+                form = form.text("access_level", level.to_string());
+            }
+            if let Some(tags) = tags {
+                form = form.text("tags", tags.to_string());
+            }
+            if let Some(password) = password {
+                form = form.text("password", password.to_string());
+            }
+
+            let mut request = client
+                .http
+                .post(format!("{}/documents", client.base))
+                .multipart(form);
+            if let Some(world) = world {
+                request = request.header("X-Seneschal-World", world);
+            }
+            client.send(request).await
+        }
+        .await;
+
+        match result {
+            Ok(doc) => println!("{} -> {}", file.display(), text(&doc, "id")),
+            Err(e) => {
+                eprintln!("{}: {}", file.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("Uploaded {} of {} files", files.len() - failed, files.len());
+    if failed > 0 {
+        return Err(format!("{} uploads failed", failed).into());
+    }
+    Ok(())
+}
+
+async fn reindex(client: &ApiClient, document_ids: Vec<String>, all: bool) -> CliResult {
+    let document_ids = if all {
+        client
+            .get("/documents")
+            .await?
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|doc| text(doc, "id").to_string())
+            .collect()
+    } else {
+        document_ids
+    };
+
+    let mut failed = 0;
+    for id in &document_ids {
+        match client
+            .post(&format!("/documents/{}/reindex", id), &Value::Null)
+            .await
+        {
+            Ok(_) => println!("{} queued", id),
+            Err(e) => {
+                eprintln!("{}: {}", id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} documents failed", failed, document_ids.len()).into());
+    }
+    Ok(())
+}
+
+async fn search(client: &ApiClient, query: &str, limit: usize) -> CliResult {
+    let response = client
+        .post(
+            "/search",
+            &serde_json::json!({"query": query, "user_role": 4, "limit": limit}),
+        )
+        .await?;
+
+    for result in response["results"].as_array().into_iter().flatten() {
+        let similarity = result["similarity"].as_f64().unwrap_or(0.0);
+        let page = result["page_number"]
+            .as_i64()
+            .map(|p| format!(" p.{}", p))
+            .unwrap_or_default();
+        println!(
+            "[{:.3}] {}{} {}",
+            similarity,
+            text(result, "document_id"),
+            page,
+            text(result, "section_title"),
+        );
+        println!("    {}\n", text(result, "content").replace('\n', "\n    "));
+    }
+    Ok(())
+}
+
+async fn export(client: &ApiClient, output: Option<&Path>) -> CliResult {
+    let export = serde_json::json!({
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "documents": client.get("/documents").await?,
+        "memories": client.get("/memories").await?,
+        "timeline": client.get("/timeline").await?,
+        "errata": client.get("/errata").await?,
+    });
+    let json = serde_json::to_string_pretty(&export)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, json)?;
+            eprintln!("Exported to {}", path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// String field of a JSON object, empty when missing
+fn text<'a>(value: &'a Value, field: &str) -> &'a str {
+    value.get(field).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+/// Supported document files below a folder, in name order
+fn supported_files_under(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if is_supported_format(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Cli, String> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_str("").unwrap().command, Command::Serve);
+        assert_eq!(
+            parse_str("--server http://gm:8080 search jump drive --limit 5").unwrap(),
+            Cli {
+                server: Some("http://gm:8080".to_string()),
+                command: Command::Search {
+                    query: "jump drive".to_string(),
+                    limit: 5,
+                },
+            }
+        );
+        assert_eq!(
+            parse_str("ingest books --tags core,mgt2e").unwrap().command,
+            Command::Ingest {
+                paths: vec![PathBuf::from("books")],
+                access_level: None,
+                tags: Some("core,mgt2e".to_string()),
+                world: None,
+                password: None,
+            }
+        );
+        assert_eq!(
+            parse_str("reindex --all").unwrap().command,
+            Command::Reindex {
+                document_ids: vec![],
+                all: true,
+            }
+        );
+
+        assert!(parse_str("reindex").is_err());
+        assert!(parse_str("reindex abc --all").is_err());
+        assert!(parse_str("list --limit 3").is_err());
+        assert!(parse_str("search").is_err());
+        assert!(parse_str("frobnicate").is_err());
+    }
+}
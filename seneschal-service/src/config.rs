@@ -5,10 +5,11 @@
 //! - Dynamic config: Ollama, embeddings, processing settings (hot-reloadable via API)
 //!
 //! Configuration sources (in order of precedence):
-//! 1. Database settings (highest priority, for dynamic config only)
-//! 2. Environment variables (SENESCHAL__ prefix)
-//! 3. config.toml file
-//! 4. Default values
+//! 1. Per-world database settings for the MCP world (a subset of dynamic keys)
+//! 2. Database settings (for dynamic config only)
+//! 3. Environment variables (SENESCHAL__ prefix)
+//! 4. config.toml file
+//! 5. Default values
 
 mod dynamic_config;
 mod loader;
@@ -21,9 +22,12 @@ use crate::db::Database;
 use crate::error::ServiceResult;
 
 // Re-export public types from submodules
-pub use dynamic_config::{DynamicConfig, EmbeddingsConfig, ImageExtractionConfig, OllamaConfig};
+pub use dynamic_config::{
+    AgenticLoopConfig, DiskConfig, DynamicConfig, EmbeddingsConfig, ImageExtractionConfig,
+    McpConfig, OllamaConfig, TravellerMapConfig, TtsConfig,
+};
 pub use loader::{load_dynamic_config, load_static_config};
-pub use static_config::{AssetsAccess, StaticConfig};
+pub use static_config::{AssetsAccess, StaticConfig, TlsConfig};
 
 // ==================== RuntimeConfig (combines static + dynamic) ====================
 
@@ -53,9 +57,7 @@ impl RuntimeConfig {
         let static_config = load_static_config()?;
 
         // Load dynamic config defaults from env/file, then apply DB overrides
-        let mut dynamic = load_dynamic_config()?;
-        let db_settings = db.get_all_settings()?;
-        dynamic.merge_from_db(&db_settings);
+        let dynamic = build_dynamic(db, None)?;
 
         Ok(Self {
             static_config,
@@ -65,10 +67,29 @@ impl RuntimeConfig {
 
     /// Rebuild dynamic config from file/env defaults + DB and swap atomically
     pub fn reload_from_db(&self, db: &Database) -> ServiceResult<()> {
-        let mut dynamic = load_dynamic_config()?;
-        let db_settings = db.get_all_settings()?;
-        dynamic.merge_from_db(&db_settings);
-        self.update_dynamic(dynamic);
+        self.update_dynamic(build_dynamic(db, None)?);
         Ok(())
     }
+
+    /// Dynamic config as it applies to a world: the global settings with the
+    /// world's own overrides on top
+    pub fn for_world(db: &Database, world_id: &str) -> ServiceResult<DynamicConfig> {
+        build_dynamic(db, Some(world_id))
+    }
+}
+
+/// Build dynamic config from file/env defaults, DB settings and a world's
+/// overrides. Without a world given, the MCP world's overrides apply, since
+/// MCP clients are where the assistant works.
+fn build_dynamic(db: &Database, world_id: Option<&str>) -> ServiceResult<DynamicConfig> {
+    let mut dynamic = load_dynamic_config()?;
+    dynamic.merge_from_db(&db.get_all_settings()?);
+
+    let world_id = world_id
+        .map(str::to_string)
+        .unwrap_or_else(|| dynamic.mcp.world_id.clone());
+    if !world_id.is_empty() {
+        dynamic.merge_from_db(&db.get_world_settings(&world_id)?);
+    }
+    Ok(dynamic)
 }
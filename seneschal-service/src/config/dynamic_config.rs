@@ -10,13 +10,16 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 pub use schemas::{
-    AgenticLoopConfig, EmbeddingsConfig, ImageExtractionConfig, LimitsConfig, McpConfig,
-    OllamaConfig, TravellerMapConfig, TravellerWorldsConfig,
+    AgenticLoopConfig, CaptioningConfig, DigestConfig, DiskConfig, EmbeddingsConfig,
+    ImageExtractionConfig, LimitsConfig, MaintenanceConfig, McpConfig, ModelRoutingConfig,
+    OllamaConfig, RetentionConfig, TravellerMapConfig, TravellerWorldsConfig, TtsConfig,
 };
 
 use defaults::{
-    default_agentic_loop, default_embeddings, default_image_extraction, default_limits,
-    default_mcp, default_ollama, default_traveller_map, default_traveller_worlds,
+    default_agentic_loop, default_captioning, default_digest, default_disk, default_embeddings,
+    default_image_extraction, default_limits, default_maintenance, default_mcp,
+    default_model_routing, default_ollama, default_retention, default_traveller_map,
+    default_traveller_worlds, default_tts,
 };
 
 /// Dynamic configuration that can be updated at runtime via API
@@ -26,6 +29,9 @@ pub struct DynamicConfig {
     #[serde(default = "default_ollama")]
     pub ollama: OllamaConfig,
 
+    #[serde(default = "default_model_routing")]
+    pub model_routing: ModelRoutingConfig,
+
     #[serde(default = "default_embeddings")]
     pub embeddings: EmbeddingsConfig,
 
@@ -38,6 +44,24 @@ pub struct DynamicConfig {
     #[serde(default = "default_agentic_loop")]
     pub agentic_loop: AgenticLoopConfig,
 
+    #[serde(default = "default_captioning")]
+    pub captioning: CaptioningConfig,
+
+    #[serde(default = "default_digest")]
+    pub digest: DigestConfig,
+
+    #[serde(default = "default_maintenance")]
+    pub maintenance: MaintenanceConfig,
+
+    #[serde(default = "default_retention")]
+    pub retention: RetentionConfig,
+
+    #[serde(default = "default_disk")]
+    pub disk: DiskConfig,
+
+    #[serde(default = "default_tts")]
+    pub tts: TtsConfig,
+
     #[serde(default = "default_image_extraction")]
     pub image_extraction: ImageExtractionConfig,
 
@@ -53,4 +77,14 @@ impl DynamicConfig {
     pub fn valid_keys() -> HashSet<&'static str> {
         keys::valid_keys()
     }
+
+    /// Keys a world may override
+    pub fn world_keys() -> Vec<&'static str> {
+        keys::WORLD_SETTING_KEYS.to_vec()
+    }
+
+    /// Whether a world may override a setting
+    pub fn is_world_key(key: &str) -> bool {
+        keys::WORLD_SETTING_KEYS.contains(&key)
+    }
 }
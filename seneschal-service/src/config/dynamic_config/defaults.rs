@@ -1,8 +1,11 @@
 //! Default value functions for DynamicConfig.
 
+use std::collections::BTreeMap;
+
 use super::schemas::{
-    AgenticLoopConfig, EmbeddingsConfig, ImageExtractionConfig, LimitsConfig, McpConfig,
-    OllamaConfig, TravellerMapConfig, TravellerWorldsConfig,
+    AgenticLoopConfig, CaptioningConfig, DigestConfig, DiskConfig, EmbeddingsConfig,
+    ImageExtractionConfig, LimitsConfig, MaintenanceConfig, McpConfig, ModelRoutingConfig,
+    OllamaConfig, RetentionConfig, TravellerMapConfig, TravellerWorldsConfig, TtsConfig,
 };
 
 // ==================== Top-level Section Defaults ====================
@@ -22,6 +25,13 @@ pub(crate) fn default_embeddings() -> EmbeddingsConfig {
         model: default_embedding_model(),
         chunk_size: default_chunk_size(),
         chunk_overlap: default_chunk_overlap(),
+        normalize_unicode: default_normalize_unicode(),
+        repair_hyphenation: default_repair_hyphenation(),
+        strip_headers_footers: default_strip_headers_footers(),
+        lowercase: false,
+        priority_boost: default_priority_boost(),
+        query_expansion: false,
+        min_answer_confidence: default_min_answer_confidence(),
     }
 }
 
@@ -29,12 +39,19 @@ pub(crate) fn default_mcp() -> McpConfig {
     McpConfig {
         path: default_mcp_path(),
         enabled: default_mcp_enabled(),
+        world_id: String::new(),
+        restrict_write_tools: false,
+        instructions_template: default_mcp_instructions_template(),
+        style_profiles: default_mcp_style_profiles(),
+        default_style: String::new(),
     }
 }
 
 pub(crate) fn default_limits() -> LimitsConfig {
     LimitsConfig {
         max_document_size_bytes: default_max_document_size(),
+        allowed_document_types: default_allowed_document_types(),
+        max_archive_uncompressed_bytes: default_max_archive_uncompressed_bytes(),
     }
 }
 
@@ -43,7 +60,70 @@ pub(crate) fn default_agentic_loop() -> AgenticLoopConfig {
         tool_call_pause_threshold: default_tool_call_pause_threshold(),
         time_pause_threshold_secs: default_time_pause_threshold_secs(),
         hard_timeout_secs: default_hard_timeout_secs(),
+        max_tool_call_pause_threshold: default_max_tool_call_pause_threshold(),
+        max_time_pause_threshold_secs: default_max_time_pause_threshold_secs(),
+        max_hard_timeout_secs: default_max_hard_timeout_secs(),
         external_tool_timeout_secs: default_external_tool_timeout_secs(),
+        tool_result_max_tokens: default_tool_result_max_tokens(),
+        turn_result_token_budget: default_turn_result_token_budget(),
+        repeat_window_secs: default_repeat_window_secs(),
+        repeat_nudge_threshold: default_repeat_nudge_threshold(),
+        require_write_approval: false,
+        approval_timeout_secs: default_approval_timeout_secs(),
+    }
+}
+
+pub(crate) fn default_captioning() -> CaptioningConfig {
+    CaptioningConfig {
+        concurrency: default_captioning_concurrency(),
+        interactive_pause_secs: default_interactive_pause_secs(),
+        vision_base_url: String::new(),
+    }
+}
+
+pub(crate) fn default_model_routing() -> ModelRoutingConfig {
+    ModelRoutingConfig {
+        failover_cooldown_secs: default_failover_cooldown(),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn default_digest() -> DigestConfig {
+    DigestConfig {
+        schedule: String::new(),
+        journal_folder: default_digest_journal_folder(),
+    }
+}
+
+pub(crate) fn default_maintenance() -> MaintenanceConfig {
+    MaintenanceConfig {
+        schedule: String::new(),
+        vacuum_idle_secs: default_vacuum_idle_secs(),
+    }
+}
+
+pub(crate) fn default_retention() -> RetentionConfig {
+    RetentionConfig {
+        source_files_days: 0,
+        uncaptioned_images_days: 0,
+        image_description_cache_days: 0,
+        audit_log_days: 0,
+    }
+}
+
+pub(crate) fn default_disk() -> DiskConfig {
+    DiskConfig {
+        min_free_mb: default_disk_min_free_mb(),
+        warn_free_mb: default_disk_warn_free_mb(),
+    }
+}
+
+pub(crate) fn default_tts() -> TtsConfig {
+    TtsConfig {
+        url: String::new(),
+        engine: default_tts_engine(),
+        voice: String::new(),
+        timeout_secs: default_tts_timeout(),
     }
 }
 
@@ -52,6 +132,13 @@ pub(crate) fn default_image_extraction() -> ImageExtractionConfig {
         background_area_threshold: default_background_area_threshold(),
         background_min_pages: default_background_min_pages(),
         text_overlap_min_dpi: default_text_overlap_min_dpi(),
+        thumbnail_size: default_thumbnail_size(),
+        medium_size: default_medium_size(),
+        vector_region_min_objects: default_vector_region_min_objects(),
+        min_image_area: default_min_image_area(),
+        max_aspect_ratio: default_max_aspect_ratio(),
+        suppress_near_duplicates: default_suppress_near_duplicates(),
+        repeated_image_max_pages: default_repeated_image_max_pages(),
     }
 }
 
@@ -97,6 +184,26 @@ pub(crate) fn default_chunk_overlap() -> usize {
 
 // ==================== MCP Defaults ====================
 
+pub(crate) fn default_normalize_unicode() -> bool {
+    true
+}
+
+pub(crate) fn default_repair_hyphenation() -> bool {
+    true
+}
+
+pub(crate) fn default_strip_headers_footers() -> bool {
+    true
+}
+
+pub(crate) fn default_priority_boost() -> f32 {
+    0.05
+}
+
+pub(crate) fn default_min_answer_confidence() -> f32 {
+    0.35
+}
+
 pub(crate) fn default_mcp_path() -> String {
     "/mcp".to_string()
 }
@@ -105,12 +212,44 @@ pub(crate) fn default_mcp_enabled() -> bool {
     true
 }
 
+pub(crate) fn default_mcp_instructions_template() -> String {
+    "Seneschal Program MCP server for game master assistance, document search, and Foundry VTT integration.\n\n{style}".to_string()
+}
+
+pub(crate) fn default_mcp_style_profiles() -> BTreeMap<String, String> {
+    [
+        (
+            "rules-lawyer",
+            "Answer concisely and precisely, like a rules lawyer. Quote the rule, cite the book and page, and call out edge cases and conflicting rules. No flavour text.",
+        ),
+        (
+            "narrator",
+            "Answer in character as a verbose narrator. Describe scenes vividly and voice NPCs, but keep game mechanics accurate and put any rules in a short note at the end.",
+        ),
+        (
+            "prep",
+            "Answer as session prep notes: short bullet points grouped under headings, with page references and stat block IDs, and no prose paragraphs.",
+        ),
+    ]
+    .into_iter()
+    .map(|(name, instructions)| (name.to_string(), instructions.to_string()))
+    .collect()
+}
+
 // ==================== Limits Defaults ====================
 
 pub(crate) fn default_max_document_size() -> u64 {
     104_857_600 // 100MB
 }
 
+pub(crate) fn default_allowed_document_types() -> String {
+    "pdf,epub,md,markdown,txt,text".to_string()
+}
+
+pub(crate) fn default_max_archive_uncompressed_bytes() -> u64 {
+    1_073_741_824 // 1GB
+}
+
 // ==================== Agentic Loop Defaults ====================
 
 pub(crate) fn default_tool_call_pause_threshold() -> u32 {
@@ -122,13 +261,77 @@ pub(crate) fn default_time_pause_threshold_secs() -> u64 {
 }
 
 pub(crate) fn default_hard_timeout_secs() -> u64 {
-    300
+    0 // Disabled
+}
+
+pub(crate) fn default_max_tool_call_pause_threshold() -> u32 {
+    u32::MAX
+}
+
+pub(crate) fn default_max_time_pause_threshold_secs() -> u64 {
+    u64::MAX
+}
+
+pub(crate) fn default_max_hard_timeout_secs() -> u64 {
+    1800
 }
 
 pub(crate) fn default_external_tool_timeout_secs() -> u64 {
     30
 }
 
+pub(crate) fn default_tool_result_max_tokens() -> usize {
+    4000
+}
+
+pub(crate) fn default_turn_result_token_budget() -> usize {
+    24000
+}
+
+pub(crate) fn default_repeat_window_secs() -> u64 {
+    120
+}
+
+pub(crate) fn default_repeat_nudge_threshold() -> u32 {
+    2
+}
+
+pub(crate) fn default_approval_timeout_secs() -> u64 {
+    300
+}
+
+// ==================== Captioning Defaults ====================
+
+pub(crate) fn default_captioning_concurrency() -> usize {
+    1
+}
+
+pub(crate) fn default_interactive_pause_secs() -> u64 {
+    15
+}
+
+// ==================== Digest Defaults ====================
+
+pub(crate) fn default_digest_journal_folder() -> String {
+    "Seneschal Digests".to_string()
+}
+
+// ==================== Maintenance Defaults ====================
+
+pub(crate) fn default_vacuum_idle_secs() -> u64 {
+    600
+}
+
+// ==================== Disk Space Defaults ====================
+
+pub(crate) fn default_disk_min_free_mb() -> u64 {
+    1024
+}
+
+pub(crate) fn default_disk_warn_free_mb() -> u64 {
+    5 * 1024
+}
+
 // ==================== Image Extraction Defaults ====================
 
 pub(crate) fn default_background_area_threshold() -> f64 {
@@ -143,6 +346,51 @@ pub(crate) fn default_text_overlap_min_dpi() -> f64 {
     300.0
 }
 
+pub(crate) fn default_thumbnail_size() -> u32 {
+    256
+}
+
+pub(crate) fn default_medium_size() -> u32 {
+    1024
+}
+
+pub(crate) fn default_vector_region_min_objects() -> usize {
+    40
+}
+
+pub(crate) fn default_min_image_area() -> f64 {
+    // Half an inch square
+    1296.0
+}
+
+pub(crate) fn default_max_aspect_ratio() -> f64 {
+    8.0
+}
+
+pub(crate) fn default_suppress_near_duplicates() -> bool {
+    true
+}
+
+pub(crate) fn default_repeated_image_max_pages() -> usize {
+    5
+}
+
+// ==================== Model Routing Defaults ====================
+
+pub(crate) fn default_failover_cooldown() -> u64 {
+    5 * 60
+}
+
+// ==================== TTS Defaults ====================
+
+pub(crate) fn default_tts_engine() -> String {
+    "piper".to_string()
+}
+
+pub(crate) fn default_tts_timeout() -> u64 {
+    60
+}
+
 // ==================== Traveller Map Defaults ====================
 
 pub(crate) fn default_traveller_map_url() -> String {
@@ -153,6 +401,10 @@ pub(crate) fn default_traveller_map_timeout() -> u64 {
     30
 }
 
+pub(crate) fn default_traveller_map_cache_ttl() -> u64 {
+    7 * 24 * 60 * 60 // One week; canon sector data rarely changes
+}
+
 // ==================== Traveller Worlds Defaults ====================
 
 pub(crate) fn default_traveller_worlds_url() -> String {
@@ -9,25 +9,100 @@ pub const VALID_SETTING_KEYS: &[&str] = &[
     "ollama.vision_model",
     "ollama.temperature",
     "ollama.request_timeout_secs",
+    "model_routing.chat",
+    "model_routing.summarization",
+    "model_routing.captioning",
+    "model_routing.query_expansion",
+    "model_routing.title_generation",
+    "model_routing.failover_cooldown_secs",
     "embeddings.model",
     "embeddings.chunk_size",
     "embeddings.chunk_overlap",
+    "embeddings.normalize_unicode",
+    "embeddings.repair_hyphenation",
+    "embeddings.strip_headers_footers",
+    "embeddings.lowercase",
+    "embeddings.priority_boost",
+    "embeddings.query_expansion",
+    "embeddings.min_answer_confidence",
     "mcp.path",
     "mcp.enabled",
+    "mcp.world_id",
+    "mcp.restrict_write_tools",
+    "mcp.instructions_template",
+    "mcp.style_profiles",
+    "mcp.default_style",
     "limits.max_document_size_bytes",
+    "limits.allowed_document_types",
+    "limits.max_archive_uncompressed_bytes",
     "agentic_loop.tool_call_pause_threshold",
     "agentic_loop.time_pause_threshold_secs",
     "agentic_loop.hard_timeout_secs",
+    "agentic_loop.max_tool_call_pause_threshold",
+    "agentic_loop.max_time_pause_threshold_secs",
+    "agentic_loop.max_hard_timeout_secs",
     "agentic_loop.external_tool_timeout_secs",
+    "agentic_loop.tool_result_max_tokens",
+    "agentic_loop.turn_result_token_budget",
+    "agentic_loop.repeat_window_secs",
+    "agentic_loop.repeat_nudge_threshold",
+    "agentic_loop.require_write_approval",
+    "agentic_loop.approval_timeout_secs",
+    "captioning.concurrency",
+    "captioning.interactive_pause_secs",
+    "captioning.vision_base_url",
+    "digest.schedule",
+    "digest.journal_folder",
+    "maintenance.schedule",
+    "maintenance.vacuum_idle_secs",
+    "retention.source_files_days",
+    "retention.uncaptioned_images_days",
+    "retention.image_description_cache_days",
+    "retention.audit_log_days",
+    "disk.min_free_mb",
+    "disk.warn_free_mb",
+    "tts.url",
+    "tts.engine",
+    "tts.voice",
+    "tts.timeout_secs",
     "image_extraction.background_area_threshold",
     "image_extraction.background_min_pages",
     "image_extraction.text_overlap_min_dpi",
+    "image_extraction.thumbnail_size",
+    "image_extraction.medium_size",
+    "image_extraction.vector_region_min_objects",
+    "image_extraction.min_image_area",
+    "image_extraction.max_aspect_ratio",
+    "image_extraction.suppress_near_duplicates",
+    "image_extraction.repeated_image_max_pages",
     "traveller_map.base_url",
     "traveller_map.timeout_secs",
+    "traveller_map.cache_ttl_secs",
+    "traveller_map.offline",
     "traveller_worlds.base_url",
     "traveller_worlds.chrome_path",
 ];
 
+/// Keys a world may override for itself. They shape how the assistant
+/// behaves in a campaign; settings that affect the shared library, models or
+/// infrastructure stay global.
+pub const WORLD_SETTING_KEYS: &[&str] = &[
+    "ollama.temperature",
+    "model_routing.chat",
+    "embeddings.priority_boost",
+    "embeddings.query_expansion",
+    "embeddings.min_answer_confidence",
+    "mcp.instructions_template",
+    "mcp.style_profiles",
+    "mcp.default_style",
+    "agentic_loop.tool_call_pause_threshold",
+    "agentic_loop.time_pause_threshold_secs",
+    "agentic_loop.hard_timeout_secs",
+    "agentic_loop.require_write_approval",
+    "digest.journal_folder",
+    "tts.voice",
+];
+
 /// Get all valid setting keys as a HashSet
 pub fn valid_keys() -> HashSet<&'static str> {
     VALID_SETTING_KEYS.iter().copied().collect()
@@ -31,6 +31,32 @@ impl DynamicConfig {
             serde_json::json!(self.ollama.request_timeout_secs),
         );
 
+        // Model routing settings
+        map.insert(
+            "model_routing.chat".to_string(),
+            serde_json::Value::String(self.model_routing.chat.clone()),
+        );
+        map.insert(
+            "model_routing.summarization".to_string(),
+            serde_json::Value::String(self.model_routing.summarization.clone()),
+        );
+        map.insert(
+            "model_routing.captioning".to_string(),
+            serde_json::Value::String(self.model_routing.captioning.clone()),
+        );
+        map.insert(
+            "model_routing.query_expansion".to_string(),
+            serde_json::Value::String(self.model_routing.query_expansion.clone()),
+        );
+        map.insert(
+            "model_routing.title_generation".to_string(),
+            serde_json::Value::String(self.model_routing.title_generation.clone()),
+        );
+        map.insert(
+            "model_routing.failover_cooldown_secs".to_string(),
+            serde_json::json!(self.model_routing.failover_cooldown_secs),
+        );
+
         // Embeddings settings
         map.insert(
             "embeddings.model".to_string(),
@@ -44,6 +70,34 @@ impl DynamicConfig {
             "embeddings.chunk_overlap".to_string(),
             serde_json::json!(self.embeddings.chunk_overlap),
         );
+        map.insert(
+            "embeddings.normalize_unicode".to_string(),
+            serde_json::json!(self.embeddings.normalize_unicode),
+        );
+        map.insert(
+            "embeddings.repair_hyphenation".to_string(),
+            serde_json::json!(self.embeddings.repair_hyphenation),
+        );
+        map.insert(
+            "embeddings.strip_headers_footers".to_string(),
+            serde_json::json!(self.embeddings.strip_headers_footers),
+        );
+        map.insert(
+            "embeddings.lowercase".to_string(),
+            serde_json::json!(self.embeddings.lowercase),
+        );
+        map.insert(
+            "embeddings.priority_boost".to_string(),
+            serde_json::json!(self.embeddings.priority_boost),
+        );
+        map.insert(
+            "embeddings.query_expansion".to_string(),
+            serde_json::json!(self.embeddings.query_expansion),
+        );
+        map.insert(
+            "embeddings.min_answer_confidence".to_string(),
+            serde_json::json!(self.embeddings.min_answer_confidence),
+        );
 
         // MCP settings
         map.insert(
@@ -54,12 +108,40 @@ impl DynamicConfig {
             "mcp.enabled".to_string(),
             serde_json::json!(self.mcp.enabled),
         );
+        map.insert(
+            "mcp.world_id".to_string(),
+            serde_json::Value::String(self.mcp.world_id.clone()),
+        );
+        map.insert(
+            "mcp.restrict_write_tools".to_string(),
+            serde_json::json!(self.mcp.restrict_write_tools),
+        );
+        map.insert(
+            "mcp.instructions_template".to_string(),
+            serde_json::Value::String(self.mcp.instructions_template.clone()),
+        );
+        map.insert(
+            "mcp.style_profiles".to_string(),
+            serde_json::json!(self.mcp.style_profiles),
+        );
+        map.insert(
+            "mcp.default_style".to_string(),
+            serde_json::Value::String(self.mcp.default_style.clone()),
+        );
 
         // Limits settings
         map.insert(
             "limits.max_document_size_bytes".to_string(),
             serde_json::json!(self.limits.max_document_size_bytes),
         );
+        map.insert(
+            "limits.allowed_document_types".to_string(),
+            serde_json::Value::String(self.limits.allowed_document_types.clone()),
+        );
+        map.insert(
+            "limits.max_archive_uncompressed_bytes".to_string(),
+            serde_json::json!(self.limits.max_archive_uncompressed_bytes),
+        );
 
         // Agentic loop settings
         map.insert(
@@ -74,10 +156,126 @@ impl DynamicConfig {
             "agentic_loop.hard_timeout_secs".to_string(),
             serde_json::json!(self.agentic_loop.hard_timeout_secs),
         );
+        map.insert(
+            "agentic_loop.max_tool_call_pause_threshold".to_string(),
+            serde_json::json!(self.agentic_loop.max_tool_call_pause_threshold),
+        );
+        map.insert(
+            "agentic_loop.max_time_pause_threshold_secs".to_string(),
+            serde_json::json!(self.agentic_loop.max_time_pause_threshold_secs),
+        );
+        map.insert(
+            "agentic_loop.max_hard_timeout_secs".to_string(),
+            serde_json::json!(self.agentic_loop.max_hard_timeout_secs),
+        );
         map.insert(
             "agentic_loop.external_tool_timeout_secs".to_string(),
             serde_json::json!(self.agentic_loop.external_tool_timeout_secs),
         );
+        map.insert(
+            "agentic_loop.tool_result_max_tokens".to_string(),
+            serde_json::json!(self.agentic_loop.tool_result_max_tokens),
+        );
+        map.insert(
+            "agentic_loop.turn_result_token_budget".to_string(),
+            serde_json::json!(self.agentic_loop.turn_result_token_budget),
+        );
+        map.insert(
+            "agentic_loop.repeat_window_secs".to_string(),
+            serde_json::json!(self.agentic_loop.repeat_window_secs),
+        );
+        map.insert(
+            "agentic_loop.repeat_nudge_threshold".to_string(),
+            serde_json::json!(self.agentic_loop.repeat_nudge_threshold),
+        );
+        map.insert(
+            "agentic_loop.require_write_approval".to_string(),
+            serde_json::json!(self.agentic_loop.require_write_approval),
+        );
+        map.insert(
+            "agentic_loop.approval_timeout_secs".to_string(),
+            serde_json::json!(self.agentic_loop.approval_timeout_secs),
+        );
+
+        // Captioning settings
+        map.insert(
+            "captioning.concurrency".to_string(),
+            serde_json::json!(self.captioning.concurrency),
+        );
+        map.insert(
+            "captioning.interactive_pause_secs".to_string(),
+            serde_json::json!(self.captioning.interactive_pause_secs),
+        );
+        map.insert(
+            "captioning.vision_base_url".to_string(),
+            serde_json::Value::String(self.captioning.vision_base_url.clone()),
+        );
+
+        // Digest settings
+        map.insert(
+            "digest.schedule".to_string(),
+            serde_json::Value::String(self.digest.schedule.clone()),
+        );
+        map.insert(
+            "digest.journal_folder".to_string(),
+            serde_json::Value::String(self.digest.journal_folder.clone()),
+        );
+
+        // Maintenance settings
+        map.insert(
+            "maintenance.schedule".to_string(),
+            serde_json::Value::String(self.maintenance.schedule.clone()),
+        );
+        map.insert(
+            "maintenance.vacuum_idle_secs".to_string(),
+            serde_json::json!(self.maintenance.vacuum_idle_secs),
+        );
+
+        // Retention settings
+        map.insert(
+            "retention.source_files_days".to_string(),
+            serde_json::json!(self.retention.source_files_days),
+        );
+        map.insert(
+            "retention.uncaptioned_images_days".to_string(),
+            serde_json::json!(self.retention.uncaptioned_images_days),
+        );
+        map.insert(
+            "retention.image_description_cache_days".to_string(),
+            serde_json::json!(self.retention.image_description_cache_days),
+        );
+        map.insert(
+            "retention.audit_log_days".to_string(),
+            serde_json::json!(self.retention.audit_log_days),
+        );
+
+        // Disk space settings
+        map.insert(
+            "disk.min_free_mb".to_string(),
+            serde_json::json!(self.disk.min_free_mb),
+        );
+        map.insert(
+            "disk.warn_free_mb".to_string(),
+            serde_json::json!(self.disk.warn_free_mb),
+        );
+
+        // TTS settings
+        map.insert(
+            "tts.url".to_string(),
+            serde_json::Value::String(self.tts.url.clone()),
+        );
+        map.insert(
+            "tts.engine".to_string(),
+            serde_json::Value::String(self.tts.engine.clone()),
+        );
+        map.insert(
+            "tts.voice".to_string(),
+            serde_json::Value::String(self.tts.voice.clone()),
+        );
+        map.insert(
+            "tts.timeout_secs".to_string(),
+            serde_json::json!(self.tts.timeout_secs),
+        );
 
         // Image extraction settings
         map.insert(
@@ -92,6 +290,34 @@ impl DynamicConfig {
             "image_extraction.text_overlap_min_dpi".to_string(),
             serde_json::json!(self.image_extraction.text_overlap_min_dpi),
         );
+        map.insert(
+            "image_extraction.thumbnail_size".to_string(),
+            serde_json::json!(self.image_extraction.thumbnail_size),
+        );
+        map.insert(
+            "image_extraction.medium_size".to_string(),
+            serde_json::json!(self.image_extraction.medium_size),
+        );
+        map.insert(
+            "image_extraction.vector_region_min_objects".to_string(),
+            serde_json::json!(self.image_extraction.vector_region_min_objects),
+        );
+        map.insert(
+            "image_extraction.min_image_area".to_string(),
+            serde_json::json!(self.image_extraction.min_image_area),
+        );
+        map.insert(
+            "image_extraction.max_aspect_ratio".to_string(),
+            serde_json::json!(self.image_extraction.max_aspect_ratio),
+        );
+        map.insert(
+            "image_extraction.suppress_near_duplicates".to_string(),
+            serde_json::json!(self.image_extraction.suppress_near_duplicates),
+        );
+        map.insert(
+            "image_extraction.repeated_image_max_pages".to_string(),
+            serde_json::json!(self.image_extraction.repeated_image_max_pages),
+        );
 
         // Traveller Map settings
         map.insert(
@@ -102,6 +328,14 @@ impl DynamicConfig {
             "traveller_map.timeout_secs".to_string(),
             serde_json::json!(self.traveller_map.timeout_secs),
         );
+        map.insert(
+            "traveller_map.cache_ttl_secs".to_string(),
+            serde_json::json!(self.traveller_map.cache_ttl_secs),
+        );
+        map.insert(
+            "traveller_map.offline".to_string(),
+            serde_json::Value::Bool(self.traveller_map.offline),
+        );
 
         // Traveller Worlds settings
         map.insert(
@@ -156,6 +390,38 @@ impl DynamicConfig {
                 }
             }
 
+            // Model routing settings
+            "model_routing.chat" => {
+                if let Some(v) = value.as_str() {
+                    self.model_routing.chat = v.trim().to_string();
+                }
+            }
+            "model_routing.summarization" => {
+                if let Some(v) = value.as_str() {
+                    self.model_routing.summarization = v.trim().to_string();
+                }
+            }
+            "model_routing.captioning" => {
+                if let Some(v) = value.as_str() {
+                    self.model_routing.captioning = v.trim().to_string();
+                }
+            }
+            "model_routing.query_expansion" => {
+                if let Some(v) = value.as_str() {
+                    self.model_routing.query_expansion = v.trim().to_string();
+                }
+            }
+            "model_routing.title_generation" => {
+                if let Some(v) = value.as_str() {
+                    self.model_routing.title_generation = v.trim().to_string();
+                }
+            }
+            "model_routing.failover_cooldown_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.model_routing.failover_cooldown_secs = v;
+                }
+            }
+
             // Embeddings settings
             "embeddings.model" => {
                 if let Some(v) = value.as_str() {
@@ -172,6 +438,41 @@ impl DynamicConfig {
                     self.embeddings.chunk_overlap = v as usize;
                 }
             }
+            "embeddings.normalize_unicode" => {
+                if let Some(v) = value.as_bool() {
+                    self.embeddings.normalize_unicode = v;
+                }
+            }
+            "embeddings.repair_hyphenation" => {
+                if let Some(v) = value.as_bool() {
+                    self.embeddings.repair_hyphenation = v;
+                }
+            }
+            "embeddings.strip_headers_footers" => {
+                if let Some(v) = value.as_bool() {
+                    self.embeddings.strip_headers_footers = v;
+                }
+            }
+            "embeddings.lowercase" => {
+                if let Some(v) = value.as_bool() {
+                    self.embeddings.lowercase = v;
+                }
+            }
+            "embeddings.priority_boost" => {
+                if let Some(v) = value.as_f64() {
+                    self.embeddings.priority_boost = v as f32;
+                }
+            }
+            "embeddings.query_expansion" => {
+                if let Some(v) = value.as_bool() {
+                    self.embeddings.query_expansion = v;
+                }
+            }
+            "embeddings.min_answer_confidence" => {
+                if let Some(v) = value.as_f64() {
+                    self.embeddings.min_answer_confidence = v as f32;
+                }
+            }
 
             // MCP settings
             "mcp.path" => {
@@ -184,6 +485,31 @@ impl DynamicConfig {
                     self.mcp.enabled = v;
                 }
             }
+            "mcp.world_id" => {
+                if let Some(v) = value.as_str() {
+                    self.mcp.world_id = v.trim().to_string();
+                }
+            }
+            "mcp.restrict_write_tools" => {
+                if let Some(v) = value.as_bool() {
+                    self.mcp.restrict_write_tools = v;
+                }
+            }
+            "mcp.instructions_template" => {
+                if let Some(v) = value.as_str() {
+                    self.mcp.instructions_template = v.to_string();
+                }
+            }
+            "mcp.style_profiles" => {
+                if let Ok(v) = serde_json::from_value(value.clone()) {
+                    self.mcp.style_profiles = v;
+                }
+            }
+            "mcp.default_style" => {
+                if let Some(v) = value.as_str() {
+                    self.mcp.default_style = v.trim().to_string();
+                }
+            }
 
             // Limits settings
             "limits.max_document_size_bytes" => {
@@ -191,6 +517,16 @@ impl DynamicConfig {
                     self.limits.max_document_size_bytes = v;
                 }
             }
+            "limits.allowed_document_types" => {
+                if let Some(v) = value.as_str() {
+                    self.limits.allowed_document_types = v.to_string();
+                }
+            }
+            "limits.max_archive_uncompressed_bytes" => {
+                if let Some(v) = value.as_u64() {
+                    self.limits.max_archive_uncompressed_bytes = v;
+                }
+            }
 
             // Agentic loop settings
             "agentic_loop.tool_call_pause_threshold" => {
@@ -208,11 +544,153 @@ impl DynamicConfig {
                     self.agentic_loop.hard_timeout_secs = v;
                 }
             }
+            "agentic_loop.max_tool_call_pause_threshold" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.max_tool_call_pause_threshold = v as u32;
+                }
+            }
+            "agentic_loop.max_time_pause_threshold_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.max_time_pause_threshold_secs = v;
+                }
+            }
+            "agentic_loop.max_hard_timeout_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.max_hard_timeout_secs = v;
+                }
+            }
             "agentic_loop.external_tool_timeout_secs" => {
                 if let Some(v) = value.as_u64() {
                     self.agentic_loop.external_tool_timeout_secs = v;
                 }
             }
+            "agentic_loop.tool_result_max_tokens" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.tool_result_max_tokens = v as usize;
+                }
+            }
+            "agentic_loop.turn_result_token_budget" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.turn_result_token_budget = v as usize;
+                }
+            }
+            "agentic_loop.repeat_window_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.repeat_window_secs = v;
+                }
+            }
+            "agentic_loop.repeat_nudge_threshold" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.repeat_nudge_threshold = v as u32;
+                }
+            }
+            "agentic_loop.require_write_approval" => {
+                if let Some(v) = value.as_bool() {
+                    self.agentic_loop.require_write_approval = v;
+                }
+            }
+            "agentic_loop.approval_timeout_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.agentic_loop.approval_timeout_secs = v;
+                }
+            }
+
+            // Captioning settings
+            "captioning.concurrency" => {
+                if let Some(v) = value.as_u64() {
+                    self.captioning.concurrency = (v as usize).max(1);
+                }
+            }
+            "captioning.interactive_pause_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.captioning.interactive_pause_secs = v;
+                }
+            }
+            "captioning.vision_base_url" => {
+                if let Some(v) = value.as_str() {
+                    self.captioning.vision_base_url = v.to_string();
+                }
+            }
+
+            // Digest settings
+            "digest.schedule" => {
+                if let Some(v) = value.as_str() {
+                    self.digest.schedule = v.trim().to_string();
+                }
+            }
+            "digest.journal_folder" => {
+                if let Some(v) = value.as_str() {
+                    self.digest.journal_folder = v.to_string();
+                }
+            }
+
+            // Maintenance settings
+            "maintenance.schedule" => {
+                if let Some(v) = value.as_str() {
+                    self.maintenance.schedule = v.trim().to_string();
+                }
+            }
+            "maintenance.vacuum_idle_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.maintenance.vacuum_idle_secs = v;
+                }
+            }
+
+            // Retention settings
+            "retention.source_files_days" => {
+                if let Some(v) = value.as_u64() {
+                    self.retention.source_files_days = v;
+                }
+            }
+            "retention.uncaptioned_images_days" => {
+                if let Some(v) = value.as_u64() {
+                    self.retention.uncaptioned_images_days = v;
+                }
+            }
+            "retention.image_description_cache_days" => {
+                if let Some(v) = value.as_u64() {
+                    self.retention.image_description_cache_days = v;
+                }
+            }
+            "retention.audit_log_days" => {
+                if let Some(v) = value.as_u64() {
+                    self.retention.audit_log_days = v;
+                }
+            }
+
+            // Disk space settings
+            "disk.min_free_mb" => {
+                if let Some(v) = value.as_u64() {
+                    self.disk.min_free_mb = v;
+                }
+            }
+            "disk.warn_free_mb" => {
+                if let Some(v) = value.as_u64() {
+                    self.disk.warn_free_mb = v;
+                }
+            }
+
+            // TTS settings
+            "tts.url" => {
+                if let Some(v) = value.as_str() {
+                    self.tts.url = v.trim().to_string();
+                }
+            }
+            "tts.engine" => {
+                if let Some(v) = value.as_str() {
+                    self.tts.engine = v.trim().to_lowercase();
+                }
+            }
+            "tts.voice" => {
+                if let Some(v) = value.as_str() {
+                    self.tts.voice = v.trim().to_string();
+                }
+            }
+            "tts.timeout_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.tts.timeout_secs = v;
+                }
+            }
 
             // Image extraction settings
             "image_extraction.background_area_threshold" => {
@@ -230,6 +708,41 @@ impl DynamicConfig {
                     self.image_extraction.text_overlap_min_dpi = v;
                 }
             }
+            "image_extraction.thumbnail_size" => {
+                if let Some(v) = value.as_u64() {
+                    self.image_extraction.thumbnail_size = v as u32;
+                }
+            }
+            "image_extraction.medium_size" => {
+                if let Some(v) = value.as_u64() {
+                    self.image_extraction.medium_size = v as u32;
+                }
+            }
+            "image_extraction.vector_region_min_objects" => {
+                if let Some(v) = value.as_u64() {
+                    self.image_extraction.vector_region_min_objects = v as usize;
+                }
+            }
+            "image_extraction.min_image_area" => {
+                if let Some(v) = value.as_f64() {
+                    self.image_extraction.min_image_area = v;
+                }
+            }
+            "image_extraction.max_aspect_ratio" => {
+                if let Some(v) = value.as_f64() {
+                    self.image_extraction.max_aspect_ratio = v;
+                }
+            }
+            "image_extraction.suppress_near_duplicates" => {
+                if let Some(v) = value.as_bool() {
+                    self.image_extraction.suppress_near_duplicates = v;
+                }
+            }
+            "image_extraction.repeated_image_max_pages" => {
+                if let Some(v) = value.as_u64() {
+                    self.image_extraction.repeated_image_max_pages = v as usize;
+                }
+            }
 
             // Traveller Map settings
             "traveller_map.base_url" => {
@@ -242,6 +755,16 @@ impl DynamicConfig {
                     self.traveller_map.timeout_secs = v;
                 }
             }
+            "traveller_map.cache_ttl_secs" => {
+                if let Some(v) = value.as_u64() {
+                    self.traveller_map.cache_ttl_secs = v;
+                }
+            }
+            "traveller_map.offline" => {
+                if let Some(v) = value.as_bool() {
+                    self.traveller_map.offline = v;
+                }
+            }
 
             // Traveller Worlds settings
             "traveller_worlds.base_url" => {
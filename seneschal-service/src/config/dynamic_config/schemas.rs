@@ -1,11 +1,15 @@
 //! Configuration struct definitions for DynamicConfig sections.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use super::defaults::{
-    default_background_area_threshold, default_background_min_pages, default_text_overlap_min_dpi,
-    default_traveller_map_timeout, default_traveller_map_url, default_traveller_worlds_url,
+    default_background_area_threshold, default_background_min_pages, default_max_aspect_ratio,
+    default_medium_size, default_min_image_area, default_repeated_image_max_pages,
+    default_suppress_near_duplicates, default_text_overlap_min_dpi, default_thumbnail_size,
+    default_traveller_map_cache_ttl, default_traveller_map_timeout, default_traveller_map_url,
+    default_traveller_worlds_url, default_vector_region_min_objects,
 };
 
 /// Ollama LLM configuration
@@ -28,6 +32,36 @@ pub struct OllamaConfig {
     pub request_timeout_secs: u64,
 }
 
+/// Models used per task, each a comma-separated list tried in order.
+/// Empty lists fall back: chat to `ollama.default_model`, captioning to
+/// `ollama.vision_model`, and the other tasks to the chat list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelRoutingConfig {
+    /// Chat answers and general requests (e.g. validating ingestion candidates)
+    #[serde(default)]
+    pub chat: String,
+
+    /// Document abstracts, session recaps and digests
+    #[serde(default)]
+    pub summarization: String,
+
+    /// Vision models for image captioning and search by image
+    #[serde(default)]
+    pub captioning: String,
+
+    #[serde(default)]
+    pub query_expansion: String,
+
+    /// Titles for saved notes given without one
+    #[serde(default)]
+    pub title_generation: String,
+
+    /// A model that failed is tried after the task's other models for this
+    /// many seconds
+    #[serde(default = "super::defaults::default_failover_cooldown")]
+    pub failover_cooldown_secs: u64,
+}
+
 /// Embeddings configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingsConfig {
@@ -39,6 +73,41 @@ pub struct EmbeddingsConfig {
 
     #[serde(default = "super::defaults::default_chunk_overlap")]
     pub chunk_overlap: usize,
+
+    /// Apply Unicode NFKC normalization (ligatures, full-width and other
+    /// compatibility characters) to text before embedding. Requires restart.
+    #[serde(default = "super::defaults::default_normalize_unicode")]
+    pub normalize_unicode: bool,
+
+    /// Rejoin words hyphenated across line breaks before chunking. Requires restart.
+    #[serde(default = "super::defaults::default_repair_hyphenation")]
+    pub repair_hyphenation: bool,
+
+    /// Strip running headers, footers and page numbers from PDFs during
+    /// extraction, for documents that don't set `strip_page_furniture`
+    /// themselves
+    #[serde(default = "super::defaults::default_strip_headers_footers")]
+    pub strip_headers_footers: bool,
+
+    /// Lowercase text before embedding. Requires restart.
+    #[serde(default)]
+    pub lowercase: bool,
+
+    /// Search score multiplier per step of document priority: a chunk's
+    /// score is scaled by `1 + priority_boost × priority`
+    #[serde(default = "super::defaults::default_priority_boost")]
+    pub priority_boost: f32,
+
+    /// Also search abbreviation expansions and spelling fixes of game terms
+    /// in queries, merging the results
+    #[serde(default)]
+    pub query_expansion: bool,
+
+    /// `document_search` answers "not found in the library" instead of
+    /// returning results when their retrieval confidence is below this
+    /// (0 always returns results)
+    #[serde(default = "super::defaults::default_min_answer_confidence")]
+    pub min_answer_confidence: f32,
 }
 
 /// MCP server configuration
@@ -49,6 +118,29 @@ pub struct McpConfig {
 
     #[serde(default = "super::defaults::default_mcp_enabled")]
     pub enabled: bool,
+
+    /// FVTT world MCP clients work in; external tools prefer GMs connected to
+    /// it. Empty routes to any GM.
+    #[serde(default)]
+    pub world_id: String,
+
+    /// Only route write tools to GM clients that opted in with the
+    /// "Allow MCP write tools" client setting
+    #[serde(default)]
+    pub restrict_write_tools: bool,
+
+    /// Server instructions sent to MCP clients at initialize; `{style}` is
+    /// replaced by the default style profile's instructions
+    #[serde(default = "super::defaults::default_mcp_instructions_template")]
+    pub instructions_template: String,
+
+    /// Named answer style instructions, offered to MCP clients as prompts
+    #[serde(default = "super::defaults::default_mcp_style_profiles")]
+    pub style_profiles: BTreeMap<String, String>,
+
+    /// Style profile merged into the instructions (empty for none)
+    #[serde(default)]
+    pub default_style: String,
 }
 
 /// Size limits
@@ -56,6 +148,15 @@ pub struct McpConfig {
 pub struct LimitsConfig {
     #[serde(default = "super::defaults::default_max_document_size")]
     pub max_document_size_bytes: u64,
+
+    /// Comma-separated file extensions accepted for upload and auto-import
+    #[serde(default = "super::defaults::default_allowed_document_types")]
+    pub allowed_document_types: String,
+
+    /// Largest total uncompressed size of an EPUB archive; larger (or
+    /// implausibly compressed) archives are rejected as ZIP bombs
+    #[serde(default = "super::defaults::default_max_archive_uncompressed_bytes")]
+    pub max_archive_uncompressed_bytes: u64,
 }
 
 /// Agentic loop configuration
@@ -69,19 +170,164 @@ pub struct AgenticLoopConfig {
     #[serde(default = "super::defaults::default_time_pause_threshold_secs")]
     pub time_pause_threshold_secs: u64,
 
-    /// Hard timeout in seconds (cannot continue past this)
+    /// Hard timeout in seconds (cannot continue past this); 0 disables it
     #[serde(default = "super::defaults::default_hard_timeout_secs")]
     pub hard_timeout_secs: u64,
 
+    /// Highest tool call pause threshold a conversation may set for itself
+    #[serde(default = "super::defaults::default_max_tool_call_pause_threshold")]
+    pub max_tool_call_pause_threshold: u32,
+
+    /// Highest time pause threshold a conversation may set, in seconds
+    #[serde(default = "super::defaults::default_max_time_pause_threshold_secs")]
+    pub max_time_pause_threshold_secs: u64,
+
+    /// Highest hard timeout a conversation may set, in seconds
+    #[serde(default = "super::defaults::default_max_hard_timeout_secs")]
+    pub max_hard_timeout_secs: u64,
+
     /// Timeout waiting for external tool result from client in seconds
     #[serde(default = "super::defaults::default_external_tool_timeout_secs")]
     pub external_tool_timeout_secs: u64,
+
+    /// Tool results longer than this many tokens are truncated
+    #[serde(default = "super::defaults::default_tool_result_max_tokens")]
+    pub tool_result_max_tokens: usize,
+
+    /// Tokens of tool results per turn before results shrink to a minimum
+    #[serde(default = "super::defaults::default_turn_result_token_budget")]
+    pub turn_result_token_budget: usize,
+
+    /// Identical tool calls within this many seconds replay the earlier result (0 disables)
+    #[serde(default = "super::defaults::default_repeat_window_secs")]
+    pub repeat_window_secs: u64,
+
+    /// Repeats of an identical call before the model is nudged to change course (0 never nudges)
+    #[serde(default = "super::defaults::default_repeat_nudge_threshold")]
+    pub repeat_nudge_threshold: u32,
+
+    /// Tools that change the world or write assets wait for a GM to approve
+    /// each call
+    #[serde(default)]
+    pub require_write_approval: bool,
+
+    /// Time to wait for a GM to approve a call before it is refused, in seconds
+    #[serde(default = "super::defaults::default_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
 }
 
 impl AgenticLoopConfig {
     pub fn external_tool_timeout(&self) -> Duration {
         Duration::from_secs(self.external_tool_timeout_secs)
     }
+
+    pub fn repeat_window(&self) -> Duration {
+        Duration::from_secs(self.repeat_window_secs)
+    }
+
+    pub fn approval_timeout(&self) -> Duration {
+        Duration::from_secs(self.approval_timeout_secs)
+    }
+}
+
+/// Image captioning worker configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptioningConfig {
+    /// Images captioned at once per vision model
+    #[serde(default = "super::defaults::default_captioning_concurrency")]
+    pub concurrency: usize,
+
+    /// Captioning waits until no MCP tool call has been made for this many seconds (0 disables)
+    #[serde(default = "super::defaults::default_interactive_pause_secs")]
+    pub interactive_pause_secs: u64,
+
+    /// Ollama URL for vision models; empty uses `ollama.base_url`. Requires restart.
+    #[serde(default)]
+    pub vision_base_url: String,
+}
+
+/// Scheduled digest of newly ingested documents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    /// Cron expression (minute hour day-of-month month day-of-week, server local
+    /// time) for posting the digest; empty disables it
+    #[serde(default)]
+    pub schedule: String,
+
+    /// FVTT journal folder the digest is written to
+    #[serde(default = "super::defaults::default_digest_journal_folder")]
+    pub journal_folder: String,
+}
+
+/// Scheduled database maintenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Cron expression (server local time) for checking integrity, removing
+    /// orphaned rows and files, and vacuuming; empty disables it
+    #[serde(default)]
+    pub schedule: String,
+
+    /// VACUUM only runs after this many seconds without MCP tool calls, and
+    /// while no document is processing
+    #[serde(default = "super::defaults::default_vacuum_idle_secs")]
+    pub vacuum_idle_secs: u64,
+}
+
+/// How long each kind of data is kept. 0 keeps it forever.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Days after processing to keep uploaded source files. Without the
+    /// source, a document can't be reindexed, re-extracted or rendered.
+    #[serde(default)]
+    pub source_files_days: u64,
+
+    /// Days to keep extracted images that never got a caption
+    #[serde(default)]
+    pub uncaptioned_images_days: u64,
+
+    /// Days to keep cached vision descriptions of FVTT images
+    #[serde(default)]
+    pub image_description_cache_days: u64,
+
+    /// Days to keep audit logs: FVTT world changes (and their undo data)
+    /// and model usage
+    #[serde(default)]
+    pub audit_log_days: u64,
+}
+
+/// Free space thresholds for the data directory's filesystem. 0 disables
+/// a threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskConfig {
+    /// Below this many MB free, uploads are refused and image extraction
+    /// waits for space
+    #[serde(default = "super::defaults::default_disk_min_free_mb")]
+    pub min_free_mb: u64,
+
+    /// Below this many MB free, GMs are warned that space is running low
+    #[serde(default = "super::defaults::default_disk_warn_free_mb")]
+    pub warn_free_mb: u64,
+}
+
+/// Text-to-speech synthesis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsConfig {
+    /// Base URL of the TTS server (e.g. "http://localhost:5000"); empty
+    /// disables speech synthesis
+    #[serde(default)]
+    pub url: String,
+
+    /// Server API: "piper" (Piper HTTP server) or "coqui" (Coqui TTS server)
+    #[serde(default = "super::defaults::default_tts_engine")]
+    pub engine: String,
+
+    /// Voice (Piper) or speaker ID (Coqui); empty uses the server's default
+    #[serde(default)]
+    pub voice: String,
+
+    /// Request timeout in seconds
+    #[serde(default = "super::defaults::default_tts_timeout")]
+    pub timeout_secs: u64,
 }
 
 /// Image extraction configuration
@@ -99,6 +345,37 @@ pub struct ImageExtractionConfig {
     /// Minimum DPI for region renders that include text or vector overlaps.
     #[serde(default = "default_text_overlap_min_dpi")]
     pub text_overlap_min_dpi: f64,
+
+    /// Longest edge in pixels of `thumb` size image variants.
+    #[serde(default = "default_thumbnail_size")]
+    pub thumbnail_size: u32,
+
+    /// Longest edge in pixels of `medium` size image variants.
+    #[serde(default = "default_medium_size")]
+    pub medium_size: u32,
+
+    /// Minimum number of vector drawing objects in a cluster for it to be rendered
+    /// as an image (maps and deck plans drawn without raster art). 0 disables.
+    #[serde(default = "default_vector_region_min_objects")]
+    pub vector_region_min_objects: usize,
+
+    /// Images smaller than this on the page, in square PDF points, are skipped.
+    #[serde(default = "default_min_image_area")]
+    pub min_image_area: f64,
+
+    /// Images whose longer side is more than this many times the shorter side
+    /// (rules, border strips) are skipped. 0 disables.
+    #[serde(default = "default_max_aspect_ratio")]
+    pub max_aspect_ratio: f64,
+
+    /// Skip images that look the same as one already extracted from another page.
+    #[serde(default = "default_suppress_near_duplicates")]
+    pub suppress_near_duplicates: bool,
+
+    /// Images that look the same on more than this many pages are decoration
+    /// and skipped entirely. 0 disables.
+    #[serde(default = "default_repeated_image_max_pages")]
+    pub repeated_image_max_pages: usize,
 }
 
 impl Default for ImageExtractionConfig {
@@ -107,6 +384,13 @@ impl Default for ImageExtractionConfig {
             background_area_threshold: default_background_area_threshold(),
             background_min_pages: default_background_min_pages(),
             text_overlap_min_dpi: default_text_overlap_min_dpi(),
+            thumbnail_size: default_thumbnail_size(),
+            medium_size: default_medium_size(),
+            vector_region_min_objects: default_vector_region_min_objects(),
+            min_image_area: default_min_image_area(),
+            max_aspect_ratio: default_max_aspect_ratio(),
+            suppress_near_duplicates: default_suppress_near_duplicates(),
+            repeated_image_max_pages: default_repeated_image_max_pages(),
         }
     }
 }
@@ -121,6 +405,14 @@ pub struct TravellerMapConfig {
     /// Request timeout in seconds
     #[serde(default = "default_traveller_map_timeout")]
     pub timeout_secs: u64,
+
+    /// How long cached API responses and map images are served before refetching
+    #[serde(default = "default_traveller_map_cache_ttl")]
+    pub cache_ttl_secs: u64,
+
+    /// Serve only cached responses and never contact the API (for games without internet)
+    #[serde(default)]
+    pub offline: bool,
 }
 
 impl Default for TravellerMapConfig {
@@ -128,6 +420,8 @@ impl Default for TravellerMapConfig {
         Self {
             base_url: default_traveller_map_url(),
             timeout_secs: default_traveller_map_timeout(),
+            cache_ttl_secs: default_traveller_map_cache_ttl(),
+            offline: false,
         }
     }
 }
@@ -2,6 +2,7 @@
 //! These settings affect server binding or require restart to change.
 
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Static configuration that cannot be changed at runtime
@@ -26,6 +27,37 @@ pub struct ServerConfig {
 
     #[serde(default = "default_port")]
     pub port: u16,
+
+    /// Serve HTTPS directly instead of plain HTTP
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+
+    /// Serve the built-in admin UI at `/admin` for document, image and
+    /// settings management without the FVTT module
+    #[serde(default)]
+    pub admin_ui: bool,
+}
+
+/// TLS termination configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    /// PEM certificate chain
+    pub cert_path: PathBuf,
+
+    /// PEM private key
+    pub key_path: PathBuf,
+
+    /// PEM bundle of CAs whose client certificates are accepted. Clients
+    /// without a certificate can still connect unless `require_client_cert_for_gm`
+    /// applies to the endpoint.
+    #[serde(default)]
+    pub client_ca_path: Option<PathBuf>,
+
+    /// Require a client certificate signed by `client_ca_path` for GM
+    /// endpoints (the REST API and MCP). The WebSocket, health and metrics
+    /// endpoints stay open since player clients use them too.
+    #[serde(default)]
+    pub require_client_cert_for_gm: bool,
 }
 
 /// Storage configuration
@@ -38,6 +70,18 @@ pub struct StorageConfig {
     /// imported. Files are moved to processed/ or failed/ subdirectories after import.
     #[serde(default)]
     pub auto_import_dir: Option<PathBuf>,
+
+    /// Passwords for encrypted PDFs in the auto-import directory, keyed by
+    /// subdirectory relative to it ("" for the directory itself). A file
+    /// uses the password of its nearest configured directory.
+    #[serde(default)]
+    pub auto_import_pdf_passwords: HashMap<PathBuf, String>,
+
+    /// Optional Obsidian vault. Notes are imported in place (never moved or
+    /// deleted), re-ingested when they change, and their wiki links and
+    /// frontmatter tags are carried into document metadata and tags.
+    #[serde(default)]
+    pub obsidian_vault_dir: Option<PathBuf>,
 }
 
 /// FVTT integration configuration
@@ -58,6 +102,15 @@ pub enum AssetsAccess {
     Shuttle,
 }
 
+impl ServerConfig {
+    /// Whether GM endpoints only accept connections with a verified client certificate
+    pub fn requires_gm_client_cert(&self) -> bool {
+        self.tls
+            .as_ref()
+            .is_some_and(|tls| tls.require_client_cert_for_gm)
+    }
+}
+
 impl FvttConfig {
     /// Check if we can write directly to FVTT assets
     pub fn check_assets_access(&self) -> AssetsAccess {
@@ -90,6 +143,8 @@ pub(crate) fn default_server() -> ServerConfig {
     ServerConfig {
         host: default_host(),
         port: default_port(),
+        tls: None,
+        admin_ui: false,
     }
 }
 
@@ -105,6 +160,8 @@ pub(crate) fn default_storage() -> StorageConfig {
     StorageConfig {
         data_dir: default_data_dir(),
         auto_import_dir: None,
+        auto_import_pdf_passwords: HashMap::new(),
+        obsidian_vault_dir: None,
     }
 }
 
@@ -0,0 +1,164 @@
+//! Retrieval modes of MCP conversations.
+//!
+//! A conversation (an MCP session) either searches the documents on its
+//! own, uses tools only when asked, or doesn't use tools at all, for
+//! brainstorming that shouldn't be steered by the rulebooks. GM clients set
+//! the mode over the WebSocket, for new conversations or for one already
+//! under way. The server instructions carry the mode's guidance at
+//! initialize; a conversation whose mode changes afterwards is told on its
+//! next tool call.
+
+use std::sync::Mutex;
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+/// How a conversation uses document retrieval
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationMode {
+    /// Search the documents before answering rules and setting questions
+    #[default]
+    AutoRag,
+    /// Use tools only when asked to
+    ManualTools,
+    /// Don't use tools
+    NoTools,
+}
+
+impl ConversationMode {
+    /// The mode's name, as sent over the WebSocket
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AutoRag => "auto_rag",
+            Self::ManualTools => "manual_tools",
+            Self::NoTools => "no_tools",
+        }
+    }
+
+    /// Guidance for the model in this mode
+    pub fn guidance(self) -> &'static str {
+        match self {
+            Self::AutoRag => {
+                "Search the game documents with document_search before answering rules, setting or adventure questions, and cite the book and page."
+            }
+            Self::ManualTools => {
+                "This conversation is for brainstorming: answer from the conversation and your own ideas, and only search the documents or use other tools when asked to."
+            }
+            Self::NoTools => {
+                "Tools are off for this conversation: answer from the conversation and your own ideas without calling any tools."
+            }
+        }
+    }
+}
+
+/// A conversation's mode
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMode {
+    pub session_id: String,
+    pub mode: ConversationMode,
+}
+
+/// Modes of open conversations, and the mode new ones start in
+#[derive(Debug, Default)]
+pub struct ConversationModes {
+    default: Mutex<ConversationMode>,
+    /// Modes set for single conversations
+    overrides: DashMap<String, ConversationMode>,
+    /// The mode each open conversation was last told about
+    announced: DashMap<String, ConversationMode>,
+}
+
+impl ConversationModes {
+    /// The mode new conversations start in
+    pub fn default_mode(&self) -> ConversationMode {
+        *self.default.lock().unwrap()
+    }
+
+    /// A conversation's mode
+    pub fn mode(&self, session_id: Option<&str>) -> ConversationMode {
+        session_id
+            .and_then(|id| self.overrides.get(id).map(|mode| *mode))
+            .unwrap_or_else(|| self.default_mode())
+    }
+
+    /// Set one conversation's mode, or without a session the mode of new
+    /// conversations and open ones not set individually
+    pub fn set(&self, session_id: Option<&str>, mode: ConversationMode) {
+        match session_id {
+            Some(id) => {
+                self.overrides.insert(id.to_string(), mode);
+            }
+            None => *self.default.lock().unwrap() = mode,
+        }
+    }
+
+    /// Start a conversation in the current default mode, returning it
+    pub fn start(&self, session_id: &str) -> ConversationMode {
+        let mode = self.default_mode();
+        self.announced.insert(session_id.to_string(), mode);
+        mode
+    }
+
+    /// The conversation's mode if it changed since the conversation was
+    /// last told, marking it told
+    pub fn take_change(&self, session_id: &str) -> Option<ConversationMode> {
+        let mode = self.mode(Some(session_id));
+        let mut announced = self.announced.entry(session_id.to_string()).or_insert(mode);
+        (*announced != mode).then(|| {
+            *announced = mode;
+            mode
+        })
+    }
+
+    /// Whether the conversation has started and not ended
+    pub fn is_open(&self, session_id: &str) -> bool {
+        self.announced.contains_key(session_id)
+    }
+
+    /// Forget an ended conversation
+    pub fn end(&self, session_id: &str) {
+        self.overrides.remove(session_id);
+        self.announced.remove(session_id);
+    }
+
+    /// Modes of open conversations
+    pub fn sessions(&self) -> Vec<SessionMode> {
+        let mut sessions: Vec<SessionMode> = self
+            .announced
+            .iter()
+            .map(|entry| SessionMode {
+                session_id: entry.key().clone(),
+                mode: self.mode(Some(entry.key())),
+            })
+            .collect();
+        sessions.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_changes_are_announced_once() {
+        let modes = ConversationModes::default();
+        modes.set(None, ConversationMode::ManualTools);
+        assert_eq!(modes.start("a"), ConversationMode::ManualTools);
+        assert!(modes.is_open("a"));
+        assert_eq!(modes.take_change("a"), None);
+
+        modes.set(Some("a"), ConversationMode::NoTools);
+        assert_eq!(modes.take_change("a"), Some(ConversationMode::NoTools));
+        assert_eq!(modes.take_change("a"), None);
+
+        modes.set(None, ConversationMode::AutoRag);
+        assert_eq!(modes.mode(Some("a")), ConversationMode::NoTools);
+        assert_eq!(modes.mode(Some("b")), ConversationMode::AutoRag);
+
+        modes.end("a");
+        assert_eq!(modes.mode(Some("a")), ConversationMode::AutoRag);
+        assert!(modes.sessions().is_empty());
+    }
+}
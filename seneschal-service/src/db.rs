@@ -3,16 +3,49 @@
 //! This module provides the `Database` struct and all database operations
 //! organized into submodules by domain.
 
+mod access_rules;
+mod artifacts;
+mod centroids;
+mod chunk_inspection;
 mod chunks;
+mod clock;
+mod digests;
+mod document_versions;
 mod documents;
+mod encounter_tables;
+mod errata;
+mod evaluation;
+mod fvtt_changes;
+mod glossary;
+mod image_grids;
+mod image_tags;
+mod image_usage;
 mod images;
+mod maintenance;
+mod memories;
 mod migrations;
 pub mod models;
+mod npcs;
+mod plot_hooks;
+mod read_aloud;
+mod retention;
 mod settings;
+mod stat_blocks;
+mod stats;
+mod summaries;
+mod tasks;
+mod timeline;
+mod usage;
+mod world_systems;
 
+pub(crate) use chunks::cosine_similarity;
 pub use models::{
-    CaptioningStatus, Chunk, Document, DocumentImage, DocumentImageWithAccess, ImageType,
-    ProcessingStatus,
+    CampaignMemory, CampaignTask, CaptioningStatus, Chunk, CorpusStats, Document,
+    DocumentAccessRule, DocumentImage, DocumentImageWithAccess, DocumentVersion, Errata,
+    EvalQuestion, EvalResult, EvalRun, EvalSettings, FvttChange, GlossaryEntry, ImageGrid,
+    ImageTags, ImageType, ImageUsage, Npc, NpcRelation, PageHash, ProcessingStatus,
+    ReadAloudPassage, StatBlock, TimelineEvent, TimelineSource, UsageGrouping, UsageRecord,
+    UsageTotals,
 };
 
 use rusqlite::Connection;
@@ -54,3 +87,21 @@ impl Database {
         Ok(db)
     }
 }
+
+#[cfg(test)]
+impl Database {
+    /// An empty in-memory database with every migration applied
+    pub(crate) fn open_in_memory() -> Self {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        migrations::run_migrations(&conn).unwrap();
+        Self {
+            conn: Mutex::new(conn),
+        }
+    }
+
+    /// Run SQL directly, for setting up test fixtures
+    pub(crate) fn execute_test_sql(&self, sql: &str) {
+        self.conn.lock().unwrap().execute_batch(sql).unwrap();
+    }
+}
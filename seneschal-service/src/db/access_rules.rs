@@ -0,0 +1,341 @@
+//! Page and section access rules.
+//!
+//! Rules override a document's access level for a page range and/or section
+//! title pattern. They are stored separately from chunks and images so they
+//! survive re-chunking and image re-extraction; `apply_document_access_rules`
+//! recomputes the effective levels from scratch.
+
+use rusqlite::params;
+
+use super::Database;
+use super::models::DocumentAccessRule;
+use crate::error::{DatabaseError, ServiceResult};
+
+/// Chunk filter for a rule: ?1 document_id, ?3 start_page, ?4 end_page, ?5 section_pattern
+const CHUNK_RULE_FILTER: &str = r#"
+    document_id = ?1
+    AND (?3 IS NULL OR page_number >= ?3)
+    AND (?4 IS NULL OR page_number <= ?4)
+    AND (?5 IS NULL OR section_title LIKE '%' || ?5 || '%')
+"#;
+
+/// Image filter for a rule, with the same parameters as `CHUNK_RULE_FILTER`
+const IMAGE_RULE_FILTER: &str = r#"
+    document_id = ?1
+    AND (?3 IS NULL OR page_number >= ?3)
+    AND (?4 IS NULL OR page_number <= ?4)
+    AND (?5 IS NULL OR page_number IN (
+        SELECT page_number FROM chunks
+        WHERE document_id = ?1 AND section_title LIKE '%' || ?5 || '%'
+    ))
+"#;
+
+impl Database {
+    /// Insert an access rule
+    pub fn insert_document_access_rule(&self, rule: &DocumentAccessRule) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            r#"
+            INSERT INTO document_access_rules (id, document_id, start_page, end_page, section_pattern, access_level, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                rule.id,
+                rule.document_id,
+                rule.start_page,
+                rule.end_page,
+                rule.section_pattern,
+                rule.access_level as u8,
+                rule.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Get access rules for a document, oldest first (later rules take precedence)
+    pub fn get_document_access_rules(
+        &self,
+        document_id: &str,
+    ) -> ServiceResult<Vec<DocumentAccessRule>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, document_id, start_page, end_page, section_pattern, access_level, created_at
+                FROM document_access_rules
+                WHERE document_id = ?1
+                ORDER BY created_at, rowid
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![document_id], DocumentAccessRule::from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Delete an access rule from a document
+    pub fn delete_document_access_rule(
+        &self,
+        document_id: &str,
+        rule_id: &str,
+    ) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "DELETE FROM document_access_rules WHERE id = ?1 AND document_id = ?2",
+                params![rule_id, document_id],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(rows > 0)
+    }
+
+    /// Recompute chunk and image access levels for a document.
+    ///
+    /// Everything is reset to the document's own level, then each rule is applied
+    /// in creation order. Images have no section title, so section rules apply to
+    /// images on pages where a matching section's chunks appear.
+    pub fn apply_document_access_rules(&self, document_id: &str) -> ServiceResult<()> {
+        let rules = self.get_document_access_rules(document_id)?;
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        tx.execute(
+            r#"
+            UPDATE chunks
+            SET access_level = (SELECT access_level FROM documents WHERE id = ?1)
+            WHERE document_id = ?1
+            "#,
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+        tx.execute(
+            "UPDATE document_images SET access_level = NULL WHERE document_id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        for rule in &rules {
+            let rule_params = params![
+                document_id,
+                rule.access_level as u8,
+                rule.start_page,
+                rule.end_page,
+                rule.section_pattern
+            ];
+            tx.execute(
+                &format!(
+                    "UPDATE chunks SET access_level = ?2 WHERE {}",
+                    CHUNK_RULE_FILTER
+                ),
+                rule_params,
+            )
+            .map_err(DatabaseError::Query)?;
+            tx.execute(
+                &format!(
+                    "UPDATE document_images SET access_level = ?2 WHERE {}",
+                    IMAGE_RULE_FILTER
+                ),
+                rule_params,
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Count the chunks and images a rule covers
+    pub fn count_access_rule_matches(
+        &self,
+        rule: &DocumentAccessRule,
+    ) -> ServiceResult<(usize, usize)> {
+        let conn = self.conn.lock().unwrap();
+
+        // ?2 is unused here but binding it keeps parameter numbering shared with the updates
+        let rule_params = params![
+            rule.document_id,
+            rule.access_level as u8,
+            rule.start_page,
+            rule.end_page,
+            rule.section_pattern
+        ];
+        let chunks: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM chunks WHERE {}", CHUNK_RULE_FILTER),
+                rule_params,
+                |row| row.get(0),
+            )
+            .map_err(DatabaseError::Query)?;
+        let images: i64 = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM document_images WHERE {}",
+                    IMAGE_RULE_FILTER
+                ),
+                rule_params,
+                |row| row.get(0),
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok((chunks as usize, images as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::AccessLevel;
+
+    /// A player-visible document with two chunks per page on pages 1-4, the
+    /// last two pages in a "Referee" section, and one image per page
+    fn fixture() -> Database {
+        let db = Database::open_in_memory();
+        let mut sql =
+            "INSERT INTO documents (id, title, access_level) VALUES ('doc', 'Doc', 1);".to_string();
+        for page in 1..=4 {
+            let section = if page > 2 {
+                "Referee Notes"
+            } else {
+                "Player Guide"
+            };
+            for index in 0..2 {
+                sql.push_str(&format!(
+                    "INSERT INTO chunks (id, document_id, content, chunk_index, page_number, section_title, access_level) \
+                     VALUES ('c{page}-{index}', 'doc', 'text', {}, {page}, '{section}', 1);",
+                    page * 2 + index
+                ));
+            }
+            sql.push_str(&format!(
+                "INSERT INTO document_images (id, document_id, page_number, image_index, internal_path) \
+                 VALUES ('i{page}', 'doc', {page}, 0, 'i{page}.webp');"
+            ));
+        }
+        db.execute_test_sql(&sql);
+        db
+    }
+
+    fn rule(
+        id: &str,
+        pages: (Option<i32>, Option<i32>),
+        section: Option<&str>,
+        access_level: AccessLevel,
+    ) -> DocumentAccessRule {
+        DocumentAccessRule {
+            id: id.to_string(),
+            document_id: "doc".to_string(),
+            start_page: pages.0,
+            end_page: pages.1,
+            section_pattern: section.map(str::to_string),
+            access_level,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Chunk access levels by page, one entry per chunk
+    fn chunk_levels(db: &Database) -> Vec<(i32, u8)> {
+        let conn = db.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT page_number, access_level FROM chunks ORDER BY chunk_index")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    /// Image access level overrides by page (None inherits the document's)
+    fn image_levels(db: &Database) -> Vec<Option<u8>> {
+        let conn = db.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT access_level FROM document_images ORDER BY page_number")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn add(db: &Database, rule: &DocumentAccessRule) {
+        db.insert_document_access_rule(rule).unwrap();
+        db.apply_document_access_rules("doc").unwrap();
+    }
+
+    #[test]
+    fn test_page_rule() {
+        let db = fixture();
+        let pages = rule("r1", (Some(2), Some(3)), None, AccessLevel::GmOnly);
+        add(&db, &pages);
+
+        let levels: Vec<u8> = chunk_levels(&db).into_iter().map(|(_, l)| l).collect();
+        assert_eq!(levels, vec![1, 1, 4, 4, 4, 4, 1, 1]);
+        assert_eq!(image_levels(&db), vec![None, Some(4), Some(4), None]);
+        assert_eq!(db.count_access_rule_matches(&pages).unwrap(), (4, 2));
+    }
+
+    #[test]
+    fn test_section_rule_covers_images_on_its_pages() {
+        let db = fixture();
+        add(
+            &db,
+            &rule("r1", (None, None), Some("Referee"), AccessLevel::Assistant),
+        );
+
+        let levels: Vec<u8> = chunk_levels(&db).into_iter().map(|(_, l)| l).collect();
+        assert_eq!(levels, vec![1, 1, 1, 1, 3, 3, 3, 3]);
+        assert_eq!(image_levels(&db), vec![None, None, Some(3), Some(3)]);
+    }
+
+    #[test]
+    fn test_overlapping_rules_later_wins() {
+        let db = fixture();
+        add(
+            &db,
+            &rule("r1", (None, None), Some("Referee"), AccessLevel::GmOnly),
+        );
+        // Page 4 handed back to trusted players inside the GM-only section
+        add(
+            &db,
+            &rule("r2", (Some(4), Some(4)), None, AccessLevel::Trusted),
+        );
+
+        let levels: Vec<(i32, u8)> = chunk_levels(&db);
+        assert!(levels.iter().filter(|(p, _)| *p == 3).all(|(_, l)| *l == 4));
+        assert!(levels.iter().filter(|(p, _)| *p == 4).all(|(_, l)| *l == 2));
+        assert_eq!(image_levels(&db), vec![None, None, Some(4), Some(2)]);
+    }
+
+    #[test]
+    fn test_removing_a_rule_restores_levels() {
+        let db = fixture();
+        add(
+            &db,
+            &rule("r1", (None, None), Some("Referee"), AccessLevel::GmOnly),
+        );
+        add(
+            &db,
+            &rule("r2", (Some(1), Some(1)), None, AccessLevel::Trusted),
+        );
+
+        assert!(db.delete_document_access_rule("doc", "r1").unwrap());
+        assert!(!db.delete_document_access_rule("doc", "r1").unwrap());
+        db.apply_document_access_rules("doc").unwrap();
+
+        let levels: Vec<u8> = chunk_levels(&db).into_iter().map(|(_, l)| l).collect();
+        assert_eq!(levels, vec![2, 2, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(image_levels(&db), vec![Some(2), None, None, None]);
+        assert_eq!(db.get_document_access_rules("doc").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Tool result artifact operations.
+//!
+//! Artifacts belong to the MCP session whose tool call produced them and
+//! are only readable by that session.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, params};
+use uuid::Uuid;
+
+use super::Database;
+use super::models::ToolArtifact;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Store a tool result as an artifact, returning its ID
+    pub fn insert_tool_artifact(
+        &self,
+        session_id: &str,
+        tool: &str,
+        content: &str,
+    ) -> ServiceResult<String> {
+        let conn = self.conn.lock().unwrap();
+        // Short enough for the model to copy back reliably
+        let id = format!("art_{}", &Uuid::new_v4().simple().to_string()[..12]);
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO tool_artifacts (id, session_id, tool, content, created_at, last_accessed_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![id, session_id, tool, content, now],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(id)
+    }
+
+    /// Get one of a session's artifacts, marking it as recently used
+    pub fn get_tool_artifact(
+        &self,
+        id: &str,
+        session_id: &str,
+    ) -> ServiceResult<Option<ToolArtifact>> {
+        let conn = self.conn.lock().unwrap();
+        let touched = conn
+            .execute(
+                "UPDATE tool_artifacts SET last_accessed_at = ?1 WHERE id = ?2 AND session_id = ?3",
+                params![Utc::now().to_rfc3339(), id, session_id],
+            )
+            .map_err(DatabaseError::Query)?;
+        if touched == 0 {
+            return Ok(None);
+        }
+
+        let artifact = conn
+            .query_row(
+                "SELECT id, tool, content, created_at FROM tool_artifacts WHERE id = ?1",
+                params![id],
+                |row| {
+                    let created_at: String = row.get(3)?;
+                    Ok(ToolArtifact {
+                        id: row.get(0)?,
+                        tool: row.get(1)?,
+                        content: row.get(2)?,
+                        created_at: DateTime::parse_from_rfc3339(&created_at)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_else(|_| Utc::now()),
+                    })
+                },
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+
+        Ok(artifact)
+    }
+
+    /// Delete all of a session's artifacts, returning how many were removed
+    pub fn delete_session_artifacts(&self, session_id: &str) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute(
+                "DELETE FROM tool_artifacts WHERE session_id = ?1",
+                params![session_id],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(deleted)
+    }
+
+    /// Delete artifacts not read or written since a cutoff, returning how many
+    /// were removed
+    pub fn delete_idle_artifacts(&self, idle_since: DateTime<Utc>) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute(
+                "DELETE FROM tool_artifacts WHERE last_accessed_at < ?1",
+                params![idle_since.to_rfc3339()],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(deleted)
+    }
+}
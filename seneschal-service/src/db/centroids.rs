@@ -0,0 +1,147 @@
+//! Document centroid operations.
+//!
+//! A document's centroid is the mean of its chunk embeddings, a cheap
+//! stand-in for "what the whole document is about" when relating documents
+//! to each other.
+
+use std::collections::HashMap;
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::chunks::cosine_similarity;
+use super::documents::world_filter_sql;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Recompute a document's centroid from its chunk embeddings.
+    ///
+    /// The centroid is removed when the document has no embedded chunks.
+    pub fn update_document_centroid(&self, document_id: &str) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT e.embedding FROM chunk_embeddings e \
+                 JOIN chunks c ON c.id = e.chunk_id WHERE c.document_id = ?1",
+            )
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map(params![document_id], |row| row.get::<_, Vec<u8>>(0))
+            .map_err(DatabaseError::Query)?;
+
+        let mut sum: Vec<f32> = Vec::new();
+        let mut count = 0usize;
+        for row in rows {
+            let embedding = decode_embedding(&row.map_err(DatabaseError::Query)?);
+            if sum.is_empty() {
+                sum = vec![0.0; embedding.len()];
+            }
+            if embedding.len() != sum.len() {
+                continue;
+            }
+            for (total, value) in sum.iter_mut().zip(&embedding) {
+                *total += value;
+            }
+            count += 1;
+        }
+
+        if count == 0 {
+            conn.execute(
+                "DELETE FROM document_centroids WHERE document_id = ?1",
+                params![document_id],
+            )
+            .map_err(DatabaseError::Query)?;
+            return Ok(());
+        }
+
+        let centroid_bytes: Vec<u8> = sum
+            .iter()
+            .flat_map(|total| (total / count as f32).to_le_bytes())
+            .collect();
+        conn.execute(
+            "INSERT OR REPLACE INTO document_centroids (document_id, embedding) VALUES (?1, ?2)",
+            params![document_id, centroid_bytes],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// IDs of documents with embedded chunks but no centroid
+    pub fn get_documents_without_centroid(&self) -> ServiceResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT c.document_id FROM chunks c \
+                 JOIN chunk_embeddings e ON e.chunk_id = c.id \
+                 WHERE NOT EXISTS (SELECT 1 FROM document_centroids dc WHERE dc.document_id = c.document_id)",
+            )
+            .map_err(DatabaseError::Query)?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(DatabaseError::Query)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(ids)
+    }
+
+    /// Similarity of each other accessible document's centroid to a document's,
+    /// among the documents visible from a world.
+    ///
+    /// Empty when the document has no centroid.
+    pub fn get_centroid_similarities(
+        &self,
+        document_id: &str,
+        max_access_level: u8,
+        world_id: Option<&str>,
+    ) -> ServiceResult<HashMap<String, f32>> {
+        let conn = self.conn.lock().unwrap();
+
+        let centroid: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT embedding FROM document_centroids WHERE document_id = ?1",
+                params![document_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+        let Some(centroid) = centroid.map(|bytes| decode_embedding(&bytes)) else {
+            return Ok(HashMap::new());
+        };
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT dc.document_id, dc.embedding FROM document_centroids dc \
+                 JOIN documents d ON d.id = dc.document_id \
+                 WHERE d.access_level <= ?1 AND dc.document_id != ?2{}",
+                world_filter_sql("dc.document_id", 3)
+            ))
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map(params![max_access_level, document_id, world_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        let mut similarities = HashMap::new();
+        for row in rows {
+            let (other_id, bytes) = row.map_err(DatabaseError::Query)?;
+            similarities.insert(
+                other_id,
+                cosine_similarity(&centroid, &decode_embedding(&bytes)),
+            );
+        }
+
+        Ok(similarities)
+    }
+}
+
+fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
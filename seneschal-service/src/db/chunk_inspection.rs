@@ -0,0 +1,129 @@
+//! Chunk inspection queries for debugging retrieval.
+
+use std::collections::HashMap;
+
+use rusqlite::params;
+
+use super::Database;
+use super::models::Chunk;
+use crate::error::{DatabaseError, ServiceResult};
+
+/// Wraps FTS matches in highlighted text; control characters can't appear
+/// in extracted content
+const MATCH_OPEN: char = '\u{2}';
+const MATCH_CLOSE: char = '\u{3}';
+
+/// Byte ranges of the marked matches in highlighted text, as offsets into
+/// the text without the markers
+fn match_spans(highlighted: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    let mut start = None;
+    for c in highlighted.chars() {
+        match c {
+            MATCH_OPEN => start = Some(offset),
+            MATCH_CLOSE => {
+                if let Some(start) = start.take() {
+                    spans.push((start, offset));
+                }
+            }
+            _ => offset += c.len_utf8(),
+        }
+    }
+    spans
+}
+
+impl Database {
+    /// A page of a document's chunks in order, each with whether it has an
+    /// embedding (tags not loaded)
+    pub fn get_document_chunk_page(
+        &self,
+        document_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> ServiceResult<Vec<(Chunk, bool)>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT c.id, c.document_id, c.content, c.chunk_index, c.page_number,
+                       c.section_title, c.access_level, c.metadata, c.created_at,
+                       e.chunk_id IS NOT NULL
+                FROM chunks c
+                LEFT JOIN chunk_embeddings e ON e.chunk_id = c.id
+                WHERE c.document_id = ?1
+                ORDER BY c.chunk_index
+                LIMIT ?2 OFFSET ?3
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![document_id, limit as i64, offset as i64], |row| {
+                Ok((Chunk::from_row(row, vec![])?, row.get(9)?))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Byte ranges of a document's full-text index matches for any word of
+    /// the query, by chunk ID
+    pub fn highlight_document_chunks(
+        &self,
+        document_id: &str,
+        query: &str,
+    ) -> ServiceResult<HashMap<String, Vec<(usize, usize)>>> {
+        let fts_query = query
+            .split_whitespace()
+            .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        if fts_query.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT chunk_id, highlight(chunks_fts, 0, char(2), char(3))
+                FROM chunks_fts
+                WHERE chunks_fts MATCH ?1 AND document_id = ?2
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![fts_query, document_id], |row| {
+                let highlighted: String = row.get(1)?;
+                Ok((row.get(0)?, match_spans(&highlighted)))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_spans() {
+        let highlighted = "The \u{2}jump\u{3} drive — \u{2}Jump\u{3}-2";
+        let text: String = highlighted
+            .chars()
+            .filter(|c| *c != MATCH_OPEN && *c != MATCH_CLOSE)
+            .collect();
+        let spans = match_spans(highlighted);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(&text[spans[0].0..spans[0].1], "jump");
+        assert_eq!(&text[spans[1].0..spans[1].1], "Jump");
+    }
+}
@@ -4,54 +4,20 @@
 //! insert, search (full-text and semantic), and embedding management.
 
 use chrono::Utc;
-use rusqlite::params;
+use rusqlite::{Connection, OptionalExtension, params};
 
 use super::Database;
+use super::documents::world_filter_sql;
 use super::models::Chunk;
 use crate::error::{DatabaseError, ServiceResult};
+use crate::ingestion::hash::compute_chunk_hash;
 use crate::tools::AccessLevel;
 
 impl Database {
     /// Insert a chunk
     pub fn insert_chunk(&self, chunk: &Chunk) -> ServiceResult<()> {
         let conn = self.conn.lock().unwrap();
-
-        let metadata_json = chunk
-            .metadata
-            .as_ref()
-            .map(serde_json::to_string)
-            .transpose()
-            .map_err(DatabaseError::Serialization)?;
-
-        conn.execute(
-            r#"
-            INSERT INTO chunks (id, document_id, content, chunk_index, page_number, section_title, access_level, metadata, created_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
-            "#,
-            params![
-                chunk.id,
-                chunk.document_id,
-                chunk.content,
-                chunk.chunk_index,
-                chunk.page_number,
-                chunk.section_title,
-                chunk.access_level as u8,
-                metadata_json,
-                chunk.created_at.to_rfc3339(),
-            ],
-        )
-        .map_err(DatabaseError::Query)?;
-
-        // Insert tags
-        for tag in &chunk.tags {
-            conn.execute(
-                "INSERT OR IGNORE INTO chunk_tags (chunk_id, tag) VALUES (?1, ?2)",
-                params![chunk.id, tag],
-            )
-            .map_err(DatabaseError::Query)?;
-        }
-
-        Ok(())
+        insert_chunk_row(&conn, chunk)
     }
 
     /// Insert chunk embedding
@@ -70,6 +36,70 @@ impl Database {
         Ok(())
     }
 
+    /// Reuse the embedding of an identical chunk (same content hash) from any document.
+    ///
+    /// Returns true if an embedding was copied, in which case the chunk doesn't need
+    /// to be sent to the embedding model.
+    pub fn copy_duplicate_embedding(&self, chunk_id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let copied = conn
+            .execute(
+                r#"
+                INSERT OR REPLACE INTO chunk_embeddings (chunk_id, embedding)
+                SELECT c.id, e.embedding
+                FROM chunks c
+                JOIN chunks dup ON dup.content_hash = c.content_hash AND dup.id != c.id
+                JOIN chunk_embeddings e ON e.chunk_id = dup.id
+                WHERE c.id = ?1
+                LIMIT 1
+                "#,
+                params![chunk_id],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(copied > 0)
+    }
+
+    /// Get a chunk by ID (tags not loaded)
+    pub fn get_chunk(&self, chunk_id: &str) -> ServiceResult<Option<Chunk>> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.query_row(
+            r#"
+            SELECT id, document_id, content, chunk_index, page_number, section_title,
+                   access_level, metadata, created_at
+            FROM chunks
+            WHERE id = ?1
+            "#,
+            params![chunk_id],
+            |row| Chunk::from_row(row, vec![]),
+        )
+        .optional()
+        .map_err(|e| DatabaseError::Query(e).into())
+    }
+
+    /// Get the embedding stored for a chunk
+    pub fn get_chunk_embedding(&self, chunk_id: &str) -> ServiceResult<Option<Vec<f32>>> {
+        let conn = self.conn.lock().unwrap();
+
+        let embedding_bytes: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT embedding FROM chunk_embeddings WHERE chunk_id = ?1",
+                params![chunk_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+
+        Ok(embedding_bytes.map(|bytes| {
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        }))
+    }
+
     /// Get all chunks for a specific page of a document
     pub fn get_chunks_by_page(
         &self,
@@ -184,6 +214,12 @@ impl Database {
     }
 
     /// Search chunks by embedding similarity (brute force for now)
+    ///
+    /// Chunks with identical content (e.g. the same paragraph in a core book and an
+    /// SRD extract) are collapsed to the best-scoring copy so they don't crowd out
+    /// other results. Each score is scaled by `1 + priority_boost × priority` of
+    /// the chunk's document; pass 0 for raw similarity.
+    #[allow(clippy::too_many_arguments)]
     pub fn search_chunks(
         &self,
         query_embedding: &[f32],
@@ -191,6 +227,8 @@ impl Database {
         limit: usize,
         tag_filter: Option<&[String]>,
         tag_match_all: bool,
+        world_id: Option<&str>,
+        priority_boost: f32,
     ) -> ServiceResult<Vec<(Chunk, f32)>> {
         let conn = self.conn.lock().unwrap();
 
@@ -198,9 +236,11 @@ impl Database {
         let mut sql = String::from(
             r#"
             SELECT c.id, c.document_id, c.content, c.chunk_index, c.page_number,
-                   c.section_title, c.access_level, c.metadata, c.created_at, e.embedding
+                   c.section_title, c.access_level, c.metadata, c.created_at, e.embedding,
+                   c.content_hash, d.priority
             FROM chunks c
             JOIN chunk_embeddings e ON c.id = e.chunk_id
+            JOIN documents d ON d.id = c.document_id
             WHERE c.access_level <= ?1
             "#,
         );
@@ -227,8 +267,6 @@ impl Database {
             }
         }
 
-        let mut stmt = conn.prepare(&sql).map_err(DatabaseError::Query)?;
-
         // Build params
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(max_access_level)];
         if let Some(tags) = tag_filter {
@@ -236,6 +274,12 @@ impl Database {
                 params_vec.push(Box::new(tag.clone()));
             }
         }
+        if let Some(world_id) = world_id {
+            sql.push_str(&world_filter_sql("c.document_id", params_vec.len() + 1));
+            params_vec.push(Box::new(world_id.to_string()));
+        }
+
+        let mut stmt = conn.prepare(&sql).map_err(DatabaseError::Query)?;
 
         let params_refs: Vec<&dyn rusqlite::ToSql> =
             params_vec.iter().map(|p| p.as_ref()).collect();
@@ -243,16 +287,19 @@ impl Database {
         let rows = stmt
             .query_map(params_refs.as_slice(), |row| {
                 let embedding_bytes: Vec<u8> = row.get(9)?;
+                let content_hash: Option<String> = row.get(10)?;
+                let priority: i32 = row.get(11)?;
                 let chunk = Chunk::from_row(row, vec![])?;
-                Ok((chunk, embedding_bytes))
+                Ok((chunk, embedding_bytes, content_hash, priority))
             })
             .map_err(DatabaseError::Query)?;
 
         // Calculate similarities and sort
-        let mut results: Vec<(Chunk, f32)> = Vec::new();
+        let mut results: Vec<(Chunk, f32, Option<String>)> = Vec::new();
 
         for row in rows {
-            let (mut chunk, embedding_bytes) = row.map_err(DatabaseError::Query)?;
+            let (mut chunk, embedding_bytes, content_hash, priority) =
+                row.map_err(DatabaseError::Query)?;
 
             // Convert bytes back to f32 slice
             let embedding: Vec<f32> = embedding_bytes
@@ -260,8 +307,9 @@ impl Database {
                 .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
                 .collect();
 
-            // Calculate cosine similarity
-            let similarity = cosine_similarity(query_embedding, &embedding);
+            // Calculate cosine similarity, weighted by the document's priority
+            let similarity = cosine_similarity(query_embedding, &embedding)
+                * (1.0 + priority_boost * priority as f32).max(0.0);
 
             // Load tags
             let mut tag_stmt = conn
@@ -273,14 +321,20 @@ impl Database {
                 .filter_map(|r| r.ok())
                 .collect();
 
-            results.push((chunk, similarity));
+            results.push((chunk, similarity, content_hash));
         }
 
         // Sort by similarity (descending)
         results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-        // Take top N
-        results.truncate(limit);
+        // Drop lower-scoring duplicates, then take top N
+        let mut seen_hashes = std::collections::HashSet::new();
+        let results: Vec<(Chunk, f32)> = results
+            .into_iter()
+            .filter(|(_, _, hash)| hash.as_ref().is_none_or(|h| seen_hashes.insert(h.clone())))
+            .map(|(chunk, similarity, _)| (chunk, similarity))
+            .take(limit)
+            .collect();
 
         Ok(results)
     }
@@ -331,6 +385,52 @@ impl Database {
         Ok(chunks)
     }
 
+    /// Get all chunks for a document in order (tags not loaded)
+    pub fn get_document_chunks(&self, document_id: &str) -> ServiceResult<Vec<Chunk>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, document_id, content, chunk_index, page_number,
+                       section_title, access_level, metadata, created_at
+                FROM chunks
+                WHERE document_id = ?1
+                ORDER BY chunk_index
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![document_id], |row| Chunk::from_row(row, vec![]))
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Delete all chunks (and their embeddings and tags) for a document, along
+    /// with the centroid computed from them.
+    /// Used when a document's source content is replaced and must be re-chunked.
+    pub fn delete_document_chunks(&self, document_id: &str) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "DELETE FROM chunks WHERE document_id = ?1",
+                params![document_id],
+            )
+            .map_err(DatabaseError::Query)?;
+        conn.execute(
+            "DELETE FROM document_centroids WHERE document_id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(rows)
+    }
+
     /// Get count of chunks for a document
     pub fn get_chunk_count(&self, document_id: &str) -> ServiceResult<usize> {
         let conn = self.conn.lock().unwrap();
@@ -345,8 +445,50 @@ impl Database {
     }
 }
 
+/// Insert a chunk and its tags as part of the document's latest version
+pub(super) fn insert_chunk_row(conn: &Connection, chunk: &Chunk) -> ServiceResult<()> {
+    let metadata_json = chunk
+        .metadata
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(DatabaseError::Serialization)?;
+
+    conn.execute(
+        r#"
+        INSERT INTO chunks (id, document_id, content, chunk_index, page_number, section_title, access_level, metadata, created_at, content_hash, version)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
+                COALESCE((SELECT MAX(version) FROM document_versions WHERE document_id = ?2), 1))
+        "#,
+        params![
+            chunk.id,
+            chunk.document_id,
+            chunk.content,
+            chunk.chunk_index,
+            chunk.page_number,
+            chunk.section_title,
+            chunk.access_level as u8,
+            metadata_json,
+            chunk.created_at.to_rfc3339(),
+            compute_chunk_hash(&chunk.content),
+        ],
+    )
+    .map_err(DatabaseError::Query)?;
+
+    // Insert tags
+    for tag in &chunk.tags {
+        conn.execute(
+            "INSERT OR IGNORE INTO chunk_tags (chunk_id, tag) VALUES (?1, ?2)",
+            params![chunk.id, tag],
+        )
+        .map_err(DatabaseError::Query)?;
+    }
+
+    Ok(())
+}
+
 /// Calculate cosine similarity between two vectors
-pub(super) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
         return 0.0;
     }
@@ -0,0 +1,50 @@
+//! Campaign clock operations.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use crate::error::{DatabaseError, ServiceResult};
+use crate::ingestion::timeline::ImperialDate;
+
+impl Database {
+    /// The current in-game date for a world, if the clock has been set
+    pub fn get_campaign_date(&self, world_id: &str) -> ServiceResult<Option<ImperialDate>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT year, day FROM campaign_clocks WHERE world_id = ?1",
+            params![world_id],
+            |row| {
+                Ok(ImperialDate {
+                    year: row.get(0)?,
+                    day: Some(row.get(1)?),
+                })
+            },
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Set a world's in-game date
+    pub fn set_campaign_date(&self, world_id: &str, date: ImperialDate) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO campaign_clocks (world_id, year, day, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(world_id) DO UPDATE SET
+                year = excluded.year,
+                day = excluded.day,
+                updated_at = excluded.updated_at
+            "#,
+            params![
+                world_id,
+                date.year,
+                date.day.unwrap_or(1),
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Ingestion digest history operations.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, params};
+use uuid::Uuid;
+
+use super::Database;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Record a posted ingestion digest
+    pub fn insert_ingestion_digest(
+        &self,
+        covered_until: DateTime<Utc>,
+        document_count: usize,
+        journal_id: Option<&str>,
+    ) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO ingestion_digests (id, covered_until, document_count, journal_id, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                Uuid::new_v4().to_string(),
+                covered_until.to_rfc3339(),
+                document_count as i64,
+                journal_id,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Where the most recent digest stopped, if one has been posted
+    pub fn get_last_digest_covered_until(&self) -> ServiceResult<Option<DateTime<Utc>>> {
+        let conn = self.conn.lock().unwrap();
+        let covered_until: Option<String> = conn
+            .query_row(
+                "SELECT covered_until FROM ingestion_digests ORDER BY covered_until DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+
+        Ok(covered_until
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
+}
@@ -0,0 +1,239 @@
+//! Document versions and the chunks they superseded.
+//!
+//! Live chunks carry the version that created them. When a new version
+//! replaces a page, the page's chunks (and their embeddings) move to
+//! `superseded_chunks`, marked with the version that replaced them, so any
+//! version's text can still be read back.
+
+use chrono::{DateTime, Utc};
+use rusqlite::types::Type;
+use rusqlite::{OptionalExtension, Row, params};
+
+use super::Database;
+use super::chunks::insert_chunk_row;
+use super::models::{Chunk, DocumentVersion, PageHash};
+use crate::error::{DatabaseError, ServiceResult};
+
+fn version_from_row(row: &Row<'_>) -> rusqlite::Result<DocumentVersion> {
+    let version: i64 = row.get(1)?;
+    let page_hashes_json: Option<String> = row.get(4)?;
+    let changed_pages_json: Option<String> = row.get(5)?;
+    let created_at_str: String = row.get(6)?;
+    let invalid_json = |column: usize| {
+        move |e: serde_json::Error| {
+            rusqlite::Error::FromSqlConversionFailure(column, Type::Text, Box::new(e))
+        }
+    };
+    Ok(DocumentVersion {
+        document_id: row.get(0)?,
+        version: version as u32,
+        file_path: row.get(2)?,
+        file_hash: row.get(3)?,
+        page_hashes: page_hashes_json
+            .map(|json| serde_json::from_str(&json).map_err(invalid_json(4)))
+            .transpose()?,
+        changed_pages: changed_pages_json
+            .map(|json| serde_json::from_str(&json).map_err(invalid_json(5)))
+            .transpose()?,
+        created_at: DateTime::parse_from_rfc3339(&created_at_str)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+impl Database {
+    /// Record a version of a document
+    pub fn insert_document_version(&self, version: &DocumentVersion) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let page_hashes_json = version
+            .page_hashes
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(DatabaseError::Serialization)?;
+        let changed_pages_json = version
+            .changed_pages
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(DatabaseError::Serialization)?;
+        conn.execute(
+            r#"
+            INSERT INTO document_versions (document_id, version, file_path, file_hash, page_hashes, changed_pages, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                version.document_id,
+                version.version as i64,
+                version.file_path,
+                version.file_hash,
+                page_hashes_json,
+                changed_pages_json,
+                version.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// All recorded versions of a document, oldest first
+    pub fn list_document_versions(&self, document_id: &str) -> ServiceResult<Vec<DocumentVersion>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT document_id, version, file_path, file_hash, page_hashes, changed_pages, created_at
+                FROM document_versions
+                WHERE document_id = ?1
+                ORDER BY version
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![document_id], version_from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// The latest version of a document if it hasn't been compared against
+    /// the indexed chunks yet
+    pub fn get_pending_document_version(&self, document_id: &str) -> ServiceResult<Option<u32>> {
+        let conn = self.conn.lock().unwrap();
+
+        let version: Option<i64> = conn
+            .query_row(
+                r#"
+                SELECT version FROM document_versions
+                WHERE document_id = ?1 AND page_hashes IS NULL
+                  AND version = (SELECT MAX(version) FROM document_versions WHERE document_id = ?1)
+                "#,
+                params![document_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+
+        Ok(version.map(|v| v as u32))
+    }
+
+    /// Swap the chunks of changed pages for those of a new version, in one
+    /// transaction.
+    ///
+    /// The changed pages' current chunks are superseded. Chunks kept from
+    /// unchanged pages are renumbered to their position in the new version.
+    pub fn apply_document_version(
+        &self,
+        document_id: &str,
+        version: u32,
+        page_hashes: &[PageHash],
+        changed_pages: &[Option<i32>],
+        new_chunks: &[Chunk],
+        renumbered: &[(String, i32)],
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+        let now = Utc::now().to_rfc3339();
+
+        for page in changed_pages {
+            tx.execute(
+                r#"
+                INSERT OR REPLACE INTO superseded_chunks
+                    (id, document_id, version, superseded_in, content, chunk_index, page_number,
+                     section_title, access_level, metadata, embedding, created_at, superseded_at)
+                SELECT c.id, c.document_id, c.version, ?3, c.content, c.chunk_index, c.page_number,
+                       c.section_title, c.access_level, c.metadata, e.embedding, c.created_at, ?4
+                FROM chunks c
+                LEFT JOIN chunk_embeddings e ON e.chunk_id = c.id
+                WHERE c.document_id = ?1 AND c.page_number IS ?2
+                "#,
+                params![document_id, page, version as i64, now],
+            )
+            .map_err(DatabaseError::Query)?;
+            tx.execute(
+                "DELETE FROM chunks WHERE document_id = ?1 AND page_number IS ?2",
+                params![document_id, page],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        for chunk in new_chunks {
+            insert_chunk_row(&tx, chunk)?;
+        }
+        for (chunk_id, chunk_index) in renumbered {
+            tx.execute(
+                "UPDATE chunks SET chunk_index = ?2 WHERE id = ?1",
+                params![chunk_id, chunk_index],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        let page_hashes_json =
+            serde_json::to_string(page_hashes).map_err(DatabaseError::Serialization)?;
+        let changed_pages_json =
+            serde_json::to_string(changed_pages).map_err(DatabaseError::Serialization)?;
+        tx.execute(
+            r#"
+            UPDATE document_versions SET page_hashes = ?3, changed_pages = ?4
+            WHERE document_id = ?1 AND version = ?2
+            "#,
+            params![
+                document_id,
+                version as i64,
+                page_hashes_json,
+                changed_pages_json
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        tx.commit().map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// The chunks of a page as they were in a version of the document
+    pub fn get_version_page_chunks(
+        &self,
+        document_id: &str,
+        version: u32,
+        page_number: i32,
+        max_access_level: u8,
+    ) -> ServiceResult<Vec<Chunk>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, document_id, content, chunk_index, page_number, section_title,
+                       access_level, metadata, created_at
+                FROM chunks
+                WHERE document_id = ?1 AND page_number = ?2 AND access_level <= ?3
+                  AND version <= ?4
+                UNION ALL
+                SELECT id, document_id, content, chunk_index, page_number, section_title,
+                       access_level, metadata, created_at
+                FROM superseded_chunks
+                WHERE document_id = ?1 AND page_number = ?2 AND access_level <= ?3
+                  AND version <= ?4 AND superseded_in > ?4
+                ORDER BY chunk_index
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![document_id, page_number, max_access_level, version as i64],
+                |row| Chunk::from_row(row, vec![]),
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
@@ -24,8 +24,8 @@ impl Database {
 
         conn.execute(
             r#"
-            INSERT INTO documents (id, title, file_path, file_hash, access_level, metadata, created_at, updated_at, processing_status, processing_error, processing_phase, processing_progress, processing_total, captioning_status, captioning_error, captioning_progress, captioning_total)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+            INSERT INTO documents (id, title, file_path, file_hash, access_level, metadata, created_at, updated_at, processing_status, processing_error, processing_phase, processing_progress, processing_total, captioning_status, captioning_error, captioning_progress, captioning_total, world_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
             "#,
             params![
                 doc.id,
@@ -45,6 +45,7 @@ impl Database {
                 doc.captioning_error,
                 doc.captioning_progress.map(|p| p as i64),
                 doc.captioning_total.map(|t| t as i64),
+                doc.world_id,
             ],
         )
         .map_err(DatabaseError::Query)?;
@@ -71,7 +72,8 @@ impl Database {
                  (SELECT COUNT(*) FROM chunks WHERE document_id = d.id) as chunk_count, \
                  (SELECT COUNT(*) FROM document_images WHERE document_id = d.id) as image_count, \
                  d.processing_phase, d.processing_progress, d.processing_total, \
-                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total \
+                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total, \
+                 d.summary, d.outline, d.world_id, d.priority \
                  FROM documents d WHERE d.id = ?1",
                 params![id],
                 |row| Document::from_row(row, vec![]),
@@ -122,6 +124,28 @@ impl Database {
         .map_err(Into::into)
     }
 
+    /// Find the document created from a Foundry VTT journal entry.
+    pub fn get_document_by_fvtt_journal_id(
+        &self,
+        journal_id: &str,
+    ) -> ServiceResult<Option<Document>> {
+        let id: Option<String> = {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT id FROM documents WHERE json_extract(metadata, '$.fvtt_journal_id') = ?1",
+                params![journal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?
+        };
+
+        match id {
+            Some(id) => self.get_document(&id),
+            None => Ok(None),
+        }
+    }
+
     /// Update a document's file_hash.
     pub fn update_document_hash(&self, document_id: &str, file_hash: &str) -> ServiceResult<bool> {
         let conn = self.conn.lock().unwrap();
@@ -136,6 +160,25 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// Point a document at a new source file.
+    pub fn update_document_file(
+        &self,
+        document_id: &str,
+        file_path: &str,
+        file_hash: &str,
+    ) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "UPDATE documents SET file_path = ?1, file_hash = ?2, updated_at = datetime('now') WHERE id = ?3",
+                params![file_path, file_hash, document_id],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(rows > 0)
+    }
+
     /// Get all documents without a file_hash (for backfill migration).
     /// Only returns documents with a file_path set (so we can compute the hash).
     pub fn get_documents_without_hash(&self) -> ServiceResult<Vec<Document>> {
@@ -147,7 +190,8 @@ impl Database {
                  (SELECT COUNT(*) FROM chunks WHERE document_id = d.id) as chunk_count, \
                  (SELECT COUNT(*) FROM document_images WHERE document_id = d.id) as image_count, \
                  d.processing_phase, d.processing_progress, d.processing_total, \
-                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total \
+                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total, \
+                 d.summary, d.outline, d.world_id, d.priority \
                  FROM documents d WHERE d.file_hash IS NULL AND d.file_path IS NOT NULL ORDER BY d.created_at"
             )
             .map_err(DatabaseError::Query)?;
@@ -177,7 +221,8 @@ impl Database {
                      (SELECT COUNT(*) FROM chunks WHERE document_id = d.id) as chunk_count, \
                      (SELECT COUNT(*) FROM document_images WHERE document_id = d.id) as image_count, \
                      d.processing_phase, d.processing_progress, d.processing_total, \
-                     d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total \
+                     d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total, \
+                     d.summary, d.outline, d.world_id, d.priority \
                      FROM documents d WHERE d.access_level <= ?1 ORDER BY d.title"
                 )
                 .map_err(DatabaseError::Query)?;
@@ -194,7 +239,8 @@ impl Database {
                      (SELECT COUNT(*) FROM chunks WHERE document_id = d.id) as chunk_count, \
                      (SELECT COUNT(*) FROM document_images WHERE document_id = d.id) as image_count, \
                      d.processing_phase, d.processing_progress, d.processing_total, \
-                     d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total \
+                     d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total, \
+                     d.summary, d.outline, d.world_id, d.priority \
                      FROM documents d ORDER BY d.title"
                 )
                 .map_err(DatabaseError::Query)?;
@@ -338,6 +384,38 @@ impl Database {
         Ok(true)
     }
 
+    /// Move a document to a world, or share it with every world (`None`)
+    pub fn set_document_world(
+        &self,
+        document_id: &str,
+        world_id: Option<&str>,
+    ) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "UPDATE documents SET world_id = ?1, updated_at = datetime('now') WHERE id = ?2",
+                params![world_id, document_id],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(rows > 0)
+    }
+
+    /// Set how strongly search favors a document's chunks
+    pub fn set_document_priority(&self, document_id: &str, priority: i32) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "UPDATE documents SET priority = ?1, updated_at = datetime('now') WHERE id = ?2",
+                params![priority, document_id],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(rows > 0)
+    }
+
     /// Get the next document pending processing (oldest first)
     /// Used by the document processing worker queue
     pub fn get_next_pending_document(&self) -> ServiceResult<Option<Document>> {
@@ -349,7 +427,8 @@ impl Database {
                  (SELECT COUNT(*) FROM chunks WHERE document_id = d.id) as chunk_count, \
                  (SELECT COUNT(*) FROM document_images WHERE document_id = d.id) as image_count, \
                  d.processing_phase, d.processing_progress, d.processing_total, \
-                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total \
+                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total, \
+                 d.summary, d.outline, d.world_id, d.priority \
                  FROM documents d WHERE d.processing_status = 'processing' ORDER BY d.created_at ASC LIMIT 1",
                 [],
                 |row| Document::from_row(row, vec![]),
@@ -389,42 +468,44 @@ impl Database {
         Ok(rows > 0)
     }
 
-    /// Get the next document needing captioning (oldest first)
-    /// Prioritizes in_progress documents (to resume interrupted work) over pending ones
-    /// Used by the captioning worker queue
-    pub fn get_next_pending_captioning_document(&self) -> ServiceResult<Option<Document>> {
+    /// Get all documents needing captioning (oldest first)
+    /// In-progress documents (interrupted work to resume) come before pending ones
+    /// Used by the captioning worker, which runs one document per vision model at a time
+    pub fn get_pending_captioning_documents(&self) -> ServiceResult<Vec<Document>> {
         let conn = self.conn.lock().unwrap();
 
-        let doc = conn
-            .query_row(
+        let mut stmt = conn
+            .prepare(
                 "SELECT d.id, d.title, d.file_path, d.file_hash, d.access_level, d.metadata, d.created_at, d.updated_at, d.processing_status, d.processing_error, \
                  (SELECT COUNT(*) FROM chunks WHERE document_id = d.id) as chunk_count, \
                  (SELECT COUNT(*) FROM document_images WHERE document_id = d.id) as image_count, \
                  d.processing_phase, d.processing_progress, d.processing_total, \
-                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total \
+                 d.captioning_status, d.captioning_error, d.captioning_progress, d.captioning_total, \
+                 d.summary, d.outline, d.world_id, d.priority \
                  FROM documents d WHERE d.captioning_status IN ('in_progress', 'pending') \
-                 ORDER BY CASE d.captioning_status WHEN 'in_progress' THEN 0 ELSE 1 END, d.created_at ASC LIMIT 1",
-                [],
-                |row| Document::from_row(row, vec![]),
+                 ORDER BY CASE d.captioning_status WHEN 'in_progress' THEN 0 ELSE 1 END, d.created_at ASC",
             )
-            .optional()
             .map_err(DatabaseError::Query)?;
 
-        if let Some(mut doc) = doc {
-            // Load tags
-            let mut stmt = conn
-                .prepare("SELECT tag FROM document_tags WHERE document_id = ?1")
-                .map_err(DatabaseError::Query)?;
-            let tags: Vec<String> = stmt
+        let mut docs: Vec<Document> = stmt
+            .query_map([], |row| Document::from_row(row, vec![]))
+            .map_err(DatabaseError::Query)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)?;
+
+        // Load tags
+        let mut tag_stmt = conn
+            .prepare("SELECT tag FROM document_tags WHERE document_id = ?1")
+            .map_err(DatabaseError::Query)?;
+        for doc in &mut docs {
+            doc.tags = tag_stmt
                 .query_map(params![doc.id], |row| row.get(0))
                 .map_err(DatabaseError::Query)?
                 .filter_map(|r| r.ok())
                 .collect();
-            doc.tags = tags;
-            Ok(Some(doc))
-        } else {
-            Ok(None)
         }
+
+        Ok(docs)
     }
 
     /// Update captioning status
@@ -479,3 +560,124 @@ impl Database {
         Ok(rows > 0)
     }
 }
+
+/// SQL condition limiting rows to documents visible from a world (its own
+/// and shared ones), with the world bound to `?{param}`; a NULL world lets
+/// every row through. `document_id` is the column holding the row's
+/// document ID.
+pub(super) fn world_filter_sql(document_id: &str, param: usize) -> String {
+    format!(
+        " AND (?{param} IS NULL OR EXISTS (SELECT 1 FROM documents wd WHERE wd.id = {document_id} \
+         AND (wd.world_id IS NULL OR wd.world_id = ?{param})))"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A shared document and one in each of worlds "a" and "b", each with a
+    /// chunk, glossary entry, stat block, timeline event, summary, centroid
+    /// and read-aloud passage
+    fn fixture() -> Database {
+        let db = Database::open_in_memory();
+        let mut sql = String::new();
+        for (id, world) in [("shared", "NULL"), ("a", "'a'"), ("b", "'b'")] {
+            sql.push_str(&format!(
+                "INSERT INTO documents (id, title, access_level, world_id) VALUES ('{id}', '{id}', 1, {world});
+                 INSERT INTO chunks (id, document_id, content, chunk_index, page_number, access_level)
+                     VALUES ('c-{id}', '{id}', 'text', 0, 1, 1);
+                 INSERT INTO glossary (document_id, term, pages, source_page) VALUES ('{id}', 'term-{id}', '[1]', 1);
+                 INSERT INTO stat_blocks (id, document_id, chunk_id, name, kind, data, raw_text)
+                     VALUES ('sb-{id}', '{id}', 'c-{id}', 'block-{id}', 'npc', '{{}}', '');
+                 INSERT INTO timeline_events (id, year, sort_key, description, source, document_id, access_level)
+                     VALUES ('e-{id}', 1105, 1105000, 'event-{id}', 'document', '{id}', 1);
+                 INSERT INTO document_summary_embeddings (document_id, embedding) VALUES ('{id}', X'0000803F');
+                 INSERT INTO document_centroids (document_id, embedding) VALUES ('{id}', X'0000803F');
+                 INSERT INTO read_aloud_passages (id, document_id, chunk_id, page_number, text, created_at)
+                     VALUES ('ra-{id}', '{id}', 'c-{id}', 1, 'passage-{id}', '2024-01-01T00:00:00Z');"
+            ));
+        }
+        sql.push_str(
+            "INSERT INTO timeline_events (id, year, sort_key, description, source, access_level)
+                 VALUES ('e-gm', 1105, 1105001, 'event-gm', 'manual', 1);",
+        );
+        db.execute_test_sql(&sql);
+        db
+    }
+
+    fn sorted(mut values: Vec<String>) -> Vec<String> {
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_world_filter_on_library_lookups() {
+        let db = fixture();
+
+        assert_eq!(
+            sorted(db.glossary_terms(4, Some("a")).unwrap()),
+            vec!["term-a", "term-shared"]
+        );
+        assert_eq!(db.glossary_terms(4, None).unwrap().len(), 3);
+        assert_eq!(
+            db.lookup_glossary("term", None, 4, Some("b"), 10)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let blocks = db
+            .search_stat_blocks(None, None, None, 4, Some("a"), 10)
+            .unwrap();
+        assert_eq!(
+            sorted(blocks.into_iter().map(|b| b.name).collect()),
+            vec!["block-a", "block-shared"]
+        );
+        assert!(db.get_stat_block("sb-b", 4, Some("a")).unwrap().is_none());
+        assert!(db.get_stat_block("sb-b", 4, Some("b")).unwrap().is_some());
+
+        let passages = db
+            .find_read_aloud(None, None, Some("passage"), 4, Some("a"), 10)
+            .unwrap();
+        assert_eq!(
+            sorted(passages.into_iter().map(|p| p.text).collect()),
+            vec!["passage-a", "passage-shared"]
+        );
+        assert!(db.get_read_aloud("ra-b", 4, Some("a")).unwrap().is_none());
+        assert!(db.get_read_aloud("ra-b", 4, None).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_world_filter_on_timeline_summaries_and_centroids() {
+        let db = fixture();
+
+        // Events without a document belong to no world's library and always show
+        let events = db
+            .query_timeline(None, None, None, None, 4, Some("a"), 10)
+            .unwrap();
+        assert_eq!(
+            sorted(events.into_iter().map(|e| e.description).collect()),
+            vec!["event-a", "event-gm", "event-shared"]
+        );
+
+        let summaries = db
+            .search_document_summaries(&[1.0], 4, Some("b"), 10)
+            .unwrap();
+        assert_eq!(
+            sorted(summaries.into_iter().map(|(id, _)| id).collect()),
+            vec!["b", "shared"]
+        );
+
+        let similar = db
+            .get_centroid_similarities("shared", 4, Some("a"))
+            .unwrap();
+        assert_eq!(sorted(similar.into_keys().collect()), vec!["a"]);
+        assert_eq!(
+            db.get_centroid_similarities("shared", 4, None)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
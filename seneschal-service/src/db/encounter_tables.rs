@@ -0,0 +1,49 @@
+//! Cached animal encounter tables.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// A world's saved encounter tables, as the UWP they were generated
+    /// for and their JSON
+    pub fn get_encounter_tables(
+        &self,
+        world_id: &str,
+        world_key: &str,
+    ) -> ServiceResult<Option<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let tables = conn
+            .query_row(
+                "SELECT uwp, tables FROM encounter_tables WHERE world_id = ?1 AND world_key = ?2",
+                params![world_id, world_key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+        Ok(tables)
+    }
+
+    pub fn save_encounter_tables(
+        &self,
+        world_id: &str,
+        world_key: &str,
+        uwp: &str,
+        tables: &str,
+    ) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO encounter_tables (world_id, world_key, uwp, tables, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                world_id,
+                world_key,
+                uwp,
+                tables,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+}
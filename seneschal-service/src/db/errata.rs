@@ -0,0 +1,72 @@
+//! Errata links between documents.
+
+use rusqlite::params;
+
+use super::Database;
+use super::models::Errata;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Insert an errata link
+    pub fn insert_errata(&self, errata: &Errata) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            r#"
+            INSERT INTO errata (id, errata_document_id, errata_start_page, errata_end_page,
+                                target_document_id, target_start_page, target_end_page,
+                                issued, note, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#,
+            params![
+                errata.id,
+                errata.errata_document_id,
+                errata.errata_start_page,
+                errata.errata_end_page,
+                errata.target_document_id,
+                errata.target_start_page,
+                errata.target_end_page,
+                errata.issued,
+                errata.note,
+                errata.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// All errata links, oldest first
+    pub fn list_errata(&self) -> ServiceResult<Vec<Errata>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, errata_document_id, errata_start_page, errata_end_page,
+                       target_document_id, target_start_page, target_end_page,
+                       issued, note, created_at
+                FROM errata
+                ORDER BY created_at
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map([], Errata::from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Delete an errata link, returning whether it existed
+    pub fn delete_errata(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM errata WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+}
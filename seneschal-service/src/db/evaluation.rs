@@ -0,0 +1,166 @@
+//! Evaluation questions and run results.
+//!
+//! Runs keep the settings they ran with alongside their scores, so results
+//! from different embedding models or chunking settings can be compared.
+
+use chrono::{DateTime, Utc};
+use rusqlite::types::Type;
+use rusqlite::{Row, params};
+
+use super::Database;
+use super::models::{EvalQuestion, EvalRun};
+use crate::error::{DatabaseError, ServiceResult};
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn question_from_row(row: &Row<'_>) -> rusqlite::Result<EvalQuestion> {
+    let pages_json: String = row.get(4)?;
+    let created_at_str: String = row.get(5)?;
+    Ok(EvalQuestion {
+        id: row.get(0)?,
+        question: row.get(1)?,
+        expected_answer: row.get(2)?,
+        document_id: row.get(3)?,
+        expected_pages: serde_json::from_str(&pages_json).unwrap_or_default(),
+        created_at: parse_timestamp(&created_at_str),
+    })
+}
+
+fn run_from_row(row: &Row<'_>) -> rusqlite::Result<EvalRun> {
+    let settings_json: String = row.get(1)?;
+    let question_count: i64 = row.get(2)?;
+    let results_json: String = row.get(6)?;
+    let created_at_str: String = row.get(7)?;
+    let invalid_json = |column: usize| {
+        move |e: serde_json::Error| {
+            rusqlite::Error::FromSqlConversionFailure(column, Type::Text, Box::new(e))
+        }
+    };
+    Ok(EvalRun {
+        id: row.get(0)?,
+        settings: serde_json::from_str(&settings_json).map_err(invalid_json(1))?,
+        question_count: question_count as usize,
+        hit_rate: row.get(3)?,
+        mean_reciprocal_rank: row.get(4)?,
+        grounding_score: row.get(5)?,
+        results: serde_json::from_str(&results_json).map_err(invalid_json(6))?,
+        created_at: parse_timestamp(&created_at_str),
+    })
+}
+
+impl Database {
+    /// Add an evaluation question
+    pub fn insert_eval_question(&self, question: &EvalQuestion) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let pages_json = serde_json::to_string(&question.expected_pages)
+            .map_err(DatabaseError::Serialization)?;
+        conn.execute(
+            r#"
+            INSERT INTO eval_questions (id, question, expected_answer, document_id, expected_pages, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                question.id,
+                question.question,
+                question.expected_answer,
+                question.document_id,
+                pages_json,
+                question.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// All evaluation questions, oldest first
+    pub fn list_eval_questions(&self) -> ServiceResult<Vec<EvalQuestion>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, question, expected_answer, document_id, expected_pages, created_at
+                FROM eval_questions
+                ORDER BY created_at
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map([], question_from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Delete an evaluation question, returning whether it existed
+    pub fn delete_eval_question(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM eval_questions WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+
+    /// Store a completed evaluation run
+    pub fn insert_eval_run(&self, run: &EvalRun) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let settings_json =
+            serde_json::to_string(&run.settings).map_err(DatabaseError::Serialization)?;
+        let results_json =
+            serde_json::to_string(&run.results).map_err(DatabaseError::Serialization)?;
+        conn.execute(
+            r#"
+            INSERT INTO eval_runs (id, settings, question_count, hit_rate, mean_reciprocal_rank, grounding_score, results, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+            params![
+                run.id,
+                settings_json,
+                run.question_count as i64,
+                run.hit_rate,
+                run.mean_reciprocal_rank,
+                run.grounding_score,
+                results_json,
+                run.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Most recent evaluation runs, newest first
+    pub fn list_eval_runs(&self, limit: usize) -> ServiceResult<Vec<EvalRun>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT id, settings, question_count, hit_rate, mean_reciprocal_rank,
+                       grounding_score, results, created_at
+                FROM eval_runs
+                ORDER BY created_at DESC
+                LIMIT ?1
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![limit as i64], run_from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
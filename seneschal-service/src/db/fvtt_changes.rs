@@ -0,0 +1,123 @@
+//! Undo log operations.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::models::FvttChange;
+use crate::error::{DatabaseError, ServiceResult};
+
+const CHANGE_COLUMNS: &str = "id, world_id, tool, document_type, document_id, document_name, operation, before_json, after_json, session_id, created_at, undone_at";
+
+impl Database {
+    /// Record a change a tool made to a world document
+    pub fn insert_fvtt_change(&self, change: &FvttChange) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO fvtt_changes (id, world_id, tool, document_type, document_id,
+                document_name, operation, before_json, after_json, session_id, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            "#,
+            params![
+                change.id,
+                change.world_id,
+                change.tool,
+                change.document_type,
+                change.document_id,
+                change.document_name,
+                change.operation,
+                change.before.as_ref().map(|v| v.to_string()),
+                change.after.as_ref().map(|v| v.to_string()),
+                change.session_id,
+                change.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    pub fn get_fvtt_change(&self, id: &str) -> ServiceResult<Option<FvttChange>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("SELECT {} FROM fvtt_changes WHERE id = ?1", CHANGE_COLUMNS),
+            params![id],
+            FvttChange::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// A world's changes, most recent first
+    pub fn list_fvtt_changes(
+        &self,
+        world_id: &str,
+        include_undone: bool,
+        limit: usize,
+    ) -> ServiceResult<Vec<FvttChange>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM fvtt_changes
+                WHERE world_id = ?1 AND (?2 OR undone_at IS NULL)
+                ORDER BY created_at DESC, rowid DESC
+                LIMIT ?3
+                "#,
+                CHANGE_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![world_id, include_undone, limit as i64],
+                FvttChange::from_row,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// The latest change to a document that hasn't been undone
+    pub fn latest_fvtt_change_for_document(
+        &self,
+        world_id: &str,
+        document_type: &str,
+        document_id: &str,
+    ) -> ServiceResult<Option<FvttChange>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                r#"
+                SELECT {}
+                FROM fvtt_changes
+                WHERE world_id = ?1 AND document_type = ?2 AND document_id = ?3
+                    AND undone_at IS NULL
+                ORDER BY created_at DESC, rowid DESC
+                LIMIT 1
+                "#,
+                CHANGE_COLUMNS
+            ),
+            params![world_id, document_type, document_id],
+            FvttChange::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Mark a change undone, returning whether it was still pending
+    pub fn mark_fvtt_change_undone(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn
+            .execute(
+                "UPDATE fvtt_changes SET undone_at = ?1 WHERE id = ?2 AND undone_at IS NULL",
+                params![chrono::Utc::now().to_rfc3339(), id],
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(updated > 0)
+    }
+}
@@ -0,0 +1,129 @@
+//! Index and glossary term operations.
+
+use rusqlite::params;
+
+use super::Database;
+use super::documents::world_filter_sql;
+use super::models::GlossaryEntry;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Replace all glossary entries for a document
+    pub fn replace_document_glossary(
+        &self,
+        document_id: &str,
+        entries: &[GlossaryEntry],
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        tx.execute(
+            "DELETE FROM glossary WHERE document_id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        for entry in entries {
+            tx.execute(
+                r#"
+                INSERT INTO glossary (document_id, term, pages, definition, source_page)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![
+                    document_id,
+                    entry.term,
+                    serde_json::to_string(&entry.pages).unwrap_or_else(|_| "[]".to_string()),
+                    entry.definition,
+                    entry.source_page,
+                ],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Look up a term, exact matches first, then prefix and substring matches.
+    ///
+    /// Entries are only returned if a chunk on the page they were read from
+    /// is accessible at the given level, and their document from the world.
+    pub fn lookup_glossary(
+        &self,
+        term: &str,
+        document_id: Option<&str>,
+        max_access_level: u8,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<GlossaryEntry>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT g.document_id, g.term, g.pages, g.definition, g.source_page
+                FROM glossary g
+                WHERE g.term LIKE '%' || ?1 || '%'
+                  AND (?2 IS NULL OR g.document_id = ?2)
+                  AND EXISTS (
+                      SELECT 1 FROM chunks c
+                      WHERE c.document_id = g.document_id
+                        AND c.page_number IS g.source_page
+                        AND c.access_level <= ?3
+                  )
+                  {}
+                ORDER BY g.term = ?1 DESC, g.term LIKE ?1 || '%' DESC, length(g.term), g.term
+                LIMIT ?4
+                "#,
+                world_filter_sql("g.document_id", 5)
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![term, document_id, max_access_level, limit as i64, world_id],
+                GlossaryEntry::from_row,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Distinct glossary terms with an entry readable at the given level
+    /// from the given world
+    pub fn glossary_terms(
+        &self,
+        max_access_level: u8,
+        world_id: Option<&str>,
+    ) -> ServiceResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT DISTINCT g.term
+                FROM glossary g
+                WHERE EXISTS (
+                    SELECT 1 FROM chunks c
+                    WHERE c.document_id = g.document_id
+                      AND c.page_number IS g.source_page
+                      AND c.access_level <= ?1
+                )
+                {}
+                "#,
+                world_filter_sql("g.document_id", 2)
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![max_access_level, world_id], |row| row.get(0))
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
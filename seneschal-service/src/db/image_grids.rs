@@ -0,0 +1,60 @@
+//! Grids detected on map images.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::models::ImageGrid;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Store an image's grid, replacing any earlier detection
+    pub fn set_image_grid(&self, grid: &ImageGrid) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO image_grids
+                (image_id, pitch, offset_x, offset_y, columns, rows, confidence)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                grid.image_id,
+                grid.pitch,
+                grid.offset_x,
+                grid.offset_y,
+                grid.columns,
+                grid.rows,
+                grid.confidence,
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Get an image's grid, if one was detected
+    pub fn get_image_grid(&self, image_id: &str) -> ServiceResult<Option<ImageGrid>> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.query_row(
+            r#"
+            SELECT image_id, pitch, offset_x, offset_y, columns, rows, confidence
+            FROM image_grids WHERE image_id = ?1
+            "#,
+            params![image_id],
+            |row| {
+                Ok(ImageGrid {
+                    image_id: row.get(0)?,
+                    pitch: row.get(1)?,
+                    offset_x: row.get(2)?,
+                    offset_y: row.get(3)?,
+                    columns: row.get(4)?,
+                    rows: row.get(5)?,
+                    confidence: row.get(6)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| DatabaseError::Query(e).into())
+    }
+}
@@ -0,0 +1,224 @@
+//! Image tags and batch image updates.
+//!
+//! Images without tags of their own inherit their document's tags, so a
+//! tag filter matches a whole tagged document until individual images are
+//! curated. Batch updates return the IDs they changed, so callers can
+//! report the rest as missing.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::documents::world_filter_sql;
+use super::models::ImageTags;
+use crate::error::{DatabaseError, ServiceResult};
+use crate::tools::{AccessLevel, SearchFilters, TagMatch};
+
+impl Database {
+    /// Set the access level override of several images.
+    ///
+    /// `None` makes the images inherit their document's level again. Overrides
+    /// are replaced when the document's access rules are next applied.
+    pub fn set_images_access_level(
+        &self,
+        image_ids: &[String],
+        access_level: Option<AccessLevel>,
+    ) -> ServiceResult<Vec<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let mut updated = Vec::new();
+        for image_id in image_ids {
+            let rows = tx
+                .execute(
+                    "UPDATE document_images SET access_level = ?2 WHERE id = ?1",
+                    params![image_id, access_level.map(|level| level as u8)],
+                )
+                .map_err(DatabaseError::Query)?;
+            if rows > 0 {
+                updated.push(image_id.clone());
+            }
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+        Ok(updated)
+    }
+
+    /// Add tags to several images, skipping tags an image already has
+    pub fn add_image_tags(
+        &self,
+        image_ids: &[String],
+        tags: &[String],
+    ) -> ServiceResult<Vec<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let mut tagged = Vec::new();
+        for image_id in image_ids {
+            let exists = tx
+                .query_row(
+                    "SELECT 1 FROM document_images WHERE id = ?1",
+                    params![image_id],
+                    |_| Ok(()),
+                )
+                .optional()
+                .map_err(DatabaseError::Query)?
+                .is_some();
+            if !exists {
+                continue;
+            }
+
+            for tag in tags {
+                tx.execute(
+                    "INSERT OR IGNORE INTO image_tags (image_id, tag) VALUES (?1, ?2)",
+                    params![image_id, tag],
+                )
+                .map_err(DatabaseError::Query)?;
+            }
+            tagged.push(image_id.clone());
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+        Ok(tagged)
+    }
+
+    /// Replace an image's own tags; false if the image doesn't exist.
+    ///
+    /// An empty list makes the image inherit its document's tags again.
+    pub fn set_image_tags(&self, image_id: &str, tags: &[String]) -> ServiceResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let exists = tx
+            .query_row(
+                "SELECT 1 FROM document_images WHERE id = ?1",
+                params![image_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?
+            .is_some();
+        if !exists {
+            return Ok(false);
+        }
+
+        tx.execute(
+            "DELETE FROM image_tags WHERE image_id = ?1",
+            params![image_id],
+        )
+        .map_err(DatabaseError::Query)?;
+        for tag in tags {
+            tx.execute(
+                "INSERT OR IGNORE INTO image_tags (image_id, tag) VALUES (?1, ?2)",
+                params![image_id, tag],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+        Ok(true)
+    }
+
+    /// Remove one of an image's own tags; false if it didn't have it
+    pub fn remove_image_tag(&self, image_id: &str, tag: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "DELETE FROM image_tags WHERE image_id = ?1 AND tag = ?2",
+                params![image_id, tag],
+            )
+            .map_err(DatabaseError::Query)?;
+
+        Ok(rows > 0)
+    }
+
+    /// Get an image's tags, alphabetically, falling back to its document's
+    pub fn get_image_tags(&self, image_id: &str) -> ServiceResult<ImageTags> {
+        let conn = self.conn.lock().unwrap();
+
+        let query = |sql: &str| -> rusqlite::Result<Vec<String>> {
+            let mut stmt = conn.prepare(sql)?;
+            let rows = stmt.query_map(params![image_id], |row| row.get(0))?;
+            rows.collect()
+        };
+
+        let tags = query("SELECT tag FROM image_tags WHERE image_id = ?1 ORDER BY tag")
+            .map_err(DatabaseError::Query)?;
+        if !tags.is_empty() {
+            return Ok(ImageTags {
+                tags,
+                inherited: false,
+            });
+        }
+
+        let tags = query(
+            r#"
+            SELECT dt.tag FROM document_tags dt
+            JOIN document_images di ON di.document_id = dt.document_id
+            WHERE di.id = ?1
+            ORDER BY dt.tag
+            "#,
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(ImageTags {
+            tags,
+            inherited: true,
+        })
+    }
+}
+
+/// SQL conditions restricting image `di` to a filter's tags and world, and
+/// the values to bind for them from `?{first_param}` on. An image without
+/// tags of its own matches on its document's tags.
+pub(super) fn image_filter_sql(
+    filters: &SearchFilters,
+    first_param: usize,
+) -> (String, Vec<String>) {
+    let mut sql = image_tag_filter_sql(filters, first_param);
+    let mut values = filters.tags.clone();
+    if let Some(world_id) = &filters.world_id {
+        sql.push_str(&world_filter_sql(
+            "di.document_id",
+            first_param + values.len(),
+        ));
+        values.push(world_id.clone());
+    }
+    if filters.exclude_used {
+        let world = match &filters.world_id {
+            Some(world_id) => {
+                values.push(world_id.clone());
+                format!(
+                    " AND (idl.world_id = ?{} OR idl.world_id = '')",
+                    first_param + values.len() - 1
+                )
+            }
+            None => String::new(),
+        };
+        sql.push_str(&format!(
+            " AND NOT EXISTS (SELECT 1 FROM image_deliveries idl WHERE idl.image_id = di.id{})",
+            world
+        ));
+    }
+    (sql, values)
+}
+
+fn image_tag_filter_sql(filters: &SearchFilters, first_param: usize) -> String {
+    if filters.tags.is_empty() {
+        return String::new();
+    }
+
+    let conditions: Vec<String> = (first_param..first_param + filters.tags.len())
+        .map(|param| {
+            format!(
+                "(EXISTS (SELECT 1 FROM image_tags it WHERE it.image_id = di.id AND it.tag = ?{param}) \
+                 OR (NOT EXISTS (SELECT 1 FROM image_tags it WHERE it.image_id = di.id) \
+                 AND EXISTS (SELECT 1 FROM document_tags dt WHERE dt.document_id = di.document_id AND dt.tag = ?{param})))"
+            )
+        })
+        .collect();
+
+    match filters.tags_match {
+        TagMatch::All => format!(" AND {}", conditions.join(" AND ")),
+        TagMatch::Any => format!(" AND ({})", conditions.join(" OR ")),
+    }
+}
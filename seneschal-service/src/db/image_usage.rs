@@ -0,0 +1,155 @@
+//! Image delivery and usage tracking operations.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+use super::Database;
+use super::models::{ImagePlacement, ImageUsage};
+use crate::error::{DatabaseError, ServiceResult};
+
+fn parse_time(value: String) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(&value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+impl Database {
+    /// Record that an image was delivered to an FVTT path, replacing an
+    /// earlier image delivered to the same path
+    pub fn record_image_delivery(
+        &self,
+        world_id: &str,
+        fvtt_path: &str,
+        image_id: &str,
+    ) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO image_deliveries (world_id, fvtt_path, image_id, delivered_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(world_id, fvtt_path) DO UPDATE SET
+                image_id = excluded.image_id,
+                delivered_at = excluded.delivered_at
+            "#,
+            params![world_id, fvtt_path, image_id, Utc::now().to_rfc3339()],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// Replace the delivered paths a world document is reported to use,
+    /// returning how many of `paths` were delivered images
+    ///
+    /// Deliveries made without an MCP world match reports from any world.
+    pub fn set_image_placements(
+        &self,
+        world_id: &str,
+        document_type: &str,
+        document_id: &str,
+        document_name: Option<&str>,
+        paths: &[String],
+    ) -> ServiceResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+        tx.execute(
+            r#"
+            DELETE FROM image_placements
+            WHERE (world_id = ?1 OR world_id = '') AND document_type = ?2 AND document_id = ?3
+            "#,
+            params![world_id, document_type, document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        let reported_at = Utc::now().to_rfc3339();
+        let mut placed = 0;
+        for path in paths {
+            placed += tx
+                .execute(
+                    r#"
+                    INSERT OR IGNORE INTO image_placements
+                        (world_id, fvtt_path, document_type, document_id, document_name, reported_at)
+                    SELECT world_id, fvtt_path, ?3, ?4, ?5, ?6
+                    FROM image_deliveries
+                    WHERE (world_id = ?1 OR world_id = '') AND fvtt_path = ?2
+                    "#,
+                    params![
+                        world_id,
+                        path,
+                        document_type,
+                        document_id,
+                        document_name,
+                        reported_at
+                    ],
+                )
+                .map_err(DatabaseError::Query)?;
+        }
+        tx.commit().map_err(DatabaseError::Query)?;
+        Ok(placed)
+    }
+
+    /// Deliveries of each image and where they're used, keyed by image ID.
+    /// With a world, only deliveries for it (and those made without a world).
+    pub fn image_usages(
+        &self,
+        world_id: Option<&str>,
+    ) -> ServiceResult<HashMap<String, Vec<ImageUsage>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT d.image_id, d.world_id, d.fvtt_path, d.delivered_at,
+                       p.document_type, p.document_id, p.document_name, p.reported_at
+                FROM image_deliveries d
+                LEFT JOIN image_placements p
+                    ON p.world_id = d.world_id AND p.fvtt_path = d.fvtt_path
+                WHERE ?1 IS NULL OR d.world_id = ?1 OR d.world_id = ''
+                ORDER BY d.delivered_at, p.reported_at
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![world_id], |row| {
+                let placement = match row.get::<_, Option<String>>(4)? {
+                    Some(document_type) => Some(ImagePlacement {
+                        document_type,
+                        document_id: row.get(5)?,
+                        document_name: row.get(6)?,
+                        reported_at: parse_time(row.get(7)?),
+                    }),
+                    None => None,
+                };
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ImageUsage {
+                        world_id: row.get(1)?,
+                        fvtt_path: row.get(2)?,
+                        delivered_at: parse_time(row.get(3)?),
+                        placements: Vec::new(),
+                    },
+                    placement,
+                ))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        let mut usages: HashMap<String, Vec<ImageUsage>> = HashMap::new();
+        for row in rows {
+            let (image_id, usage, placement) = row.map_err(DatabaseError::Query)?;
+            let entries = usages.entry(image_id).or_default();
+            let index = match entries
+                .iter()
+                .position(|u| u.world_id == usage.world_id && u.fvtt_path == usage.fvtt_path)
+            {
+                Some(index) => index,
+                None => {
+                    entries.push(usage);
+                    entries.len() - 1
+                }
+            };
+            entries[index].placements.extend(placement);
+        }
+        Ok(usages)
+    }
+}
@@ -7,9 +7,10 @@ use rusqlite::{OptionalExtension, params};
 
 use super::Database;
 use super::chunks::cosine_similarity;
-use super::models::{DocumentImage, DocumentImageWithAccess};
+use super::image_tags::image_filter_sql;
+use super::models::{DocumentImage, DocumentImageWithAccess, ImageType};
 use crate::error::{DatabaseError, ServiceResult};
-use crate::tools::AccessLevel;
+use crate::tools::{AccessLevel, SearchFilters};
 
 impl Database {
     /// Insert a document image
@@ -65,6 +66,27 @@ impl Database {
         Ok(())
     }
 
+    /// Get the description embedding for an image, if it has been captioned
+    pub fn get_image_embedding(&self, image_id: &str) -> ServiceResult<Option<Vec<f32>>> {
+        let conn = self.conn.lock().unwrap();
+
+        let embedding_bytes: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT embedding FROM document_image_embeddings WHERE image_id = ?1",
+                params![image_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+
+        Ok(embedding_bytes.map(|bytes| {
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        }))
+    }
+
     /// Get a document image by ID (with access control info)
     pub fn get_document_image(&self, id: &str) -> ServiceResult<Option<DocumentImageWithAccess>> {
         let conn = self.conn.lock().unwrap();
@@ -74,7 +96,7 @@ impl Database {
             SELECT di.id, di.document_id, di.page_number, di.image_index, di.internal_path,
                    di.mime_type, di.width, di.height, di.description, di.created_at,
                    di.source_pages, di.image_type, di.source_image_id, di.has_region_render,
-                   d.title, d.access_level
+                   d.title, COALESCE(di.access_level, d.access_level)
             FROM document_images di
             JOIN documents d ON di.document_id = d.id
             WHERE di.id = ?1
@@ -102,6 +124,7 @@ impl Database {
         document_id: Option<&str>,
         start_page: Option<i32>,
         end_page: Option<i32>,
+        filters: Option<&SearchFilters>,
         limit: usize,
     ) -> ServiceResult<Vec<DocumentImageWithAccess>> {
         let conn = self.conn.lock().unwrap();
@@ -111,10 +134,10 @@ impl Database {
             SELECT di.id, di.document_id, di.page_number, di.image_index, di.internal_path,
                    di.mime_type, di.width, di.height, di.description, di.created_at,
                    di.source_pages, di.image_type, di.source_image_id, di.has_region_render,
-                   d.title, d.access_level
+                   d.title, COALESCE(di.access_level, d.access_level)
             FROM document_images di
             JOIN documents d ON di.document_id = d.id
-            WHERE d.access_level <= ?1
+            WHERE COALESCE(di.access_level, d.access_level) <= ?1
             "#,
         );
 
@@ -131,6 +154,15 @@ impl Database {
             sql.push_str(&format!(" AND di.page_number <= ?{}", param_idx));
             param_idx += 1;
         }
+        let filter_values = match filters {
+            Some(filters) => {
+                let (filter_sql, values) = image_filter_sql(filters, param_idx);
+                sql.push_str(&filter_sql);
+                param_idx += values.len();
+                values
+            }
+            None => Vec::new(),
+        };
 
         sql.push_str(&format!(
             " ORDER BY d.title, di.page_number, di.image_index LIMIT ?{}",
@@ -149,6 +181,9 @@ impl Database {
         if let Some(page) = end_page {
             params_vec.push(Box::new(page));
         }
+        for value in filter_values {
+            params_vec.push(Box::new(value));
+        }
         params_vec.push(Box::new(limit as i32));
 
         let params_refs: Vec<&dyn rusqlite::ToSql> =
@@ -177,26 +212,37 @@ impl Database {
         query_embedding: &[f32],
         max_access_level: u8,
         limit: usize,
+        filters: Option<&SearchFilters>,
     ) -> ServiceResult<Vec<(DocumentImageWithAccess, f32)>> {
         let conn = self.conn.lock().unwrap();
 
-        let mut stmt = conn
-            .prepare(
-                r#"
-                SELECT di.id, di.document_id, di.page_number, di.image_index, di.internal_path,
-                       di.mime_type, di.width, di.height, di.description, di.created_at,
-                       di.source_pages, di.image_type, di.source_image_id, di.has_region_render,
-                       d.title, d.access_level, e.embedding
-                FROM document_images di
-                JOIN documents d ON di.document_id = d.id
-                JOIN document_image_embeddings e ON di.id = e.image_id
-                WHERE d.access_level <= ?1
-                "#,
-            )
-            .map_err(DatabaseError::Query)?;
+        let mut sql = String::from(
+            r#"
+            SELECT di.id, di.document_id, di.page_number, di.image_index, di.internal_path,
+                   di.mime_type, di.width, di.height, di.description, di.created_at,
+                   di.source_pages, di.image_type, di.source_image_id, di.has_region_render,
+                   d.title, COALESCE(di.access_level, d.access_level), e.embedding
+            FROM document_images di
+            JOIN documents d ON di.document_id = d.id
+            JOIN document_image_embeddings e ON di.id = e.image_id
+            WHERE COALESCE(di.access_level, d.access_level) <= ?1
+            "#,
+        );
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(max_access_level)];
+        if let Some(filters) = filters {
+            let (filter_sql, values) = image_filter_sql(filters, 2);
+            sql.push_str(&filter_sql);
+            for value in values {
+                params_vec.push(Box::new(value));
+            }
+        }
+
+        let mut stmt = conn.prepare(&sql).map_err(DatabaseError::Query)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
 
         let rows = stmt
-            .query_map(params![max_access_level], |row| {
+            .query_map(params_refs.as_slice(), |row| {
                 let image = DocumentImage::from_row(row)?;
                 let access_level_u8: u8 = row.get(15)?;
                 let embedding_bytes: Vec<u8> = row.get(16)?;
@@ -275,6 +321,70 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// Clear descriptions and embeddings so the captioning worker picks images up again.
+    ///
+    /// Selects the given image IDs, or every image in the page range (open-ended bounds
+    /// when None), and returns how many images were cleared.
+    pub fn clear_image_descriptions(
+        &self,
+        document_id: &str,
+        image_ids: &[String],
+        start_page: Option<i32>,
+        end_page: Option<i32>,
+    ) -> ServiceResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let selected: Vec<String> = if image_ids.is_empty() {
+            let mut stmt = tx
+                .prepare(
+                    r#"
+                    SELECT id FROM document_images
+                    WHERE document_id = ?1
+                      AND (?2 IS NULL OR page_number >= ?2)
+                      AND (?3 IS NULL OR page_number <= ?3)
+                    "#,
+                )
+                .map_err(DatabaseError::Query)?;
+            stmt.query_map(params![document_id, start_page, end_page], |row| row.get(0))
+                .map_err(DatabaseError::Query)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Query)?
+        } else {
+            let mut stmt = tx
+                .prepare("SELECT id FROM document_images WHERE id = ?1 AND document_id = ?2")
+                .map_err(DatabaseError::Query)?;
+            let mut found = Vec::new();
+            for id in image_ids {
+                if let Some(id) = stmt
+                    .query_row(params![id, document_id], |row| row.get(0))
+                    .optional()
+                    .map_err(DatabaseError::Query)?
+                {
+                    found.push(id);
+                }
+            }
+            found
+        };
+
+        for id in &selected {
+            tx.execute(
+                "UPDATE document_images SET description = NULL WHERE id = ?1",
+                params![id],
+            )
+            .map_err(DatabaseError::Query)?;
+            tx.execute(
+                "DELETE FROM document_image_embeddings WHERE image_id = ?1",
+                params![id],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(selected.len())
+    }
+
     /// Delete all images for a document (returns the internal paths for file cleanup)
     pub fn delete_document_images(&self, document_id: &str) -> ServiceResult<Vec<String>> {
         let conn = self.conn.lock().unwrap();
@@ -328,6 +438,26 @@ impl Database {
         }
     }
 
+    /// The next free image index for derived images of a type on a page
+    pub fn next_image_index(
+        &self,
+        document_id: &str,
+        page_number: i32,
+        image_type: ImageType,
+    ) -> ServiceResult<i32> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            r#"
+            SELECT COALESCE(MAX(image_index) + 1, 0) FROM document_images
+            WHERE document_id = ?1 AND page_number = ?2 AND image_type = ?3
+            "#,
+            params![document_id, page_number, image_type.as_str()],
+            |row| row.get(0),
+        )
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
     /// Get count of images for a document
     pub fn get_image_count(&self, document_id: &str) -> ServiceResult<usize> {
         let conn = self.conn.lock().unwrap();
@@ -370,4 +500,70 @@ impl Database {
 
         Ok(images)
     }
+
+    /// Get a cached vision description and embedding for an FVTT image path
+    pub fn get_fvtt_image_description(
+        &self,
+        image_path: &str,
+        vision_model: &str,
+    ) -> ServiceResult<Option<(String, Option<Vec<f32>>)>> {
+        let conn = self.conn.lock().unwrap();
+
+        let row: Option<(String, Option<Vec<u8>>)> = conn
+            .query_row(
+                r#"
+                SELECT description, embedding FROM fvtt_image_descriptions
+                WHERE image_path = ?1 AND source = 'data' AND vision_model = ?2
+                "#,
+                params![image_path, vision_model],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+
+        Ok(row.map(|(description, embedding_bytes)| {
+            let embedding = embedding_bytes.map(|bytes| {
+                bytes
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect()
+            });
+            (description, embedding)
+        }))
+    }
+
+    /// Cache a vision description and embedding for an FVTT image path
+    pub fn upsert_fvtt_image_description(
+        &self,
+        image_path: &str,
+        vision_model: &str,
+        description: &str,
+        embedding: &[f32],
+    ) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let embedding_bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
+
+        conn.execute(
+            r#"
+            INSERT INTO fvtt_image_descriptions (id, image_path, source, description, embedding, vision_model)
+            VALUES (?1, ?2, 'data', ?3, ?4, ?5)
+            ON CONFLICT(image_path, source) DO UPDATE SET
+                description = excluded.description,
+                embedding = excluded.embedding,
+                vision_model = excluded.vision_model,
+                updated_at = datetime('now')
+            "#,
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                image_path,
+                description,
+                embedding_bytes,
+                vision_model
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
 }
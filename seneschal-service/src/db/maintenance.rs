@@ -0,0 +1,141 @@
+//! Database maintenance operations.
+
+use rusqlite::params;
+
+use super::Database;
+use super::models::ProcessingStatus;
+use crate::error::{DatabaseError, ServiceResult};
+
+/// Rows whose parent is gone. Foreign keys cascade deletes now, but
+/// databases created before they were enforced, and table rebuilds during
+/// migrations, can leave these behind.
+const ORPHAN_DELETES: &[&str] = &[
+    "DELETE FROM chunks WHERE document_id NOT IN (SELECT id FROM documents)",
+    "DELETE FROM chunk_embeddings WHERE chunk_id NOT IN (SELECT id FROM chunks)",
+    "DELETE FROM chunk_tags WHERE chunk_id NOT IN (SELECT id FROM chunks)",
+    "DELETE FROM document_tags WHERE document_id NOT IN (SELECT id FROM documents)",
+    "DELETE FROM document_images WHERE document_id NOT IN (SELECT id FROM documents)",
+    "DELETE FROM document_image_embeddings WHERE image_id NOT IN (SELECT id FROM document_images)",
+    "DELETE FROM document_summary_embeddings WHERE document_id NOT IN (SELECT id FROM documents)",
+    "DELETE FROM document_centroids WHERE document_id NOT IN (SELECT id FROM documents)",
+];
+
+impl Database {
+    /// Run SQLite's integrity check, returning the problems it reports
+    /// (empty when the database is sound)
+    pub fn integrity_check(&self) -> ServiceResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("PRAGMA integrity_check")
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(DatabaseError::Query)?;
+        let messages = rows
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)?;
+        Ok(messages.into_iter().filter(|m| m != "ok").collect())
+    }
+
+    /// Delete rows whose parent row no longer exists, returning how many were removed
+    pub fn prune_orphaned_rows(&self) -> ServiceResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let mut removed = 0;
+        for statement in ORPHAN_DELETES {
+            removed += tx.execute(statement, []).map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(removed)
+    }
+
+    /// Stored image paths of documents that aren't being processed, with
+    /// their image IDs
+    pub fn settled_image_paths(&self) -> ServiceResult<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT i.id, i.internal_path
+                FROM document_images i
+                JOIN documents d ON d.id = i.document_id
+                WHERE d.processing_status != ?1
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map(params![ProcessingStatus::Processing.as_str()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Every file path the database refers to: images, and the source files
+    /// of documents and their versions
+    pub fn referenced_file_paths(&self) -> ServiceResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT internal_path FROM document_images
+                UNION SELECT file_path FROM documents WHERE file_path IS NOT NULL
+                UNION SELECT file_path FROM document_versions
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Delete image rows (and, by cascade, their embeddings and tags),
+    /// returning how many were removed
+    pub fn delete_images_by_id(&self, image_ids: &[String]) -> ServiceResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let mut removed = 0;
+        for id in image_ids {
+            removed += tx
+                .execute("DELETE FROM document_images WHERE id = ?1", params![id])
+                .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(removed)
+    }
+
+    /// Size of the main database file in bytes
+    pub fn database_size(&self) -> ServiceResult<u64> {
+        let conn = self.conn.lock().unwrap();
+        let size: i64 = conn
+            .query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(size.max(0) as u64)
+    }
+
+    /// Rebuild the database file to give free pages back to the filesystem,
+    /// then truncate the write-ahead log
+    pub fn vacuum(&self) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")
+            .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! Campaign memory operations.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::chunks::cosine_similarity;
+use super::models::CampaignMemory;
+use crate::error::{DatabaseError, ServiceResult};
+
+const MEMORY_COLUMNS: &str = "id, fact, world_id, created_at, updated_at";
+
+fn embedding_bytes(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+impl Database {
+    /// Store a memory with the embedding of its fact
+    pub fn insert_memory(&self, memory: &CampaignMemory, embedding: &[f32]) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO campaign_memories (id, fact, world_id, embedding, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                memory.id,
+                memory.fact,
+                memory.world_id,
+                embedding_bytes(embedding),
+                memory.created_at.to_rfc3339(),
+                memory.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// Replace a memory's fact, returning whether it existed
+    pub fn update_memory(&self, id: &str, fact: &str, embedding: &[f32]) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn
+            .execute(
+                "UPDATE campaign_memories SET fact = ?1, embedding = ?2, updated_at = ?3 WHERE id = ?4",
+                params![
+                    fact,
+                    embedding_bytes(embedding),
+                    chrono::Utc::now().to_rfc3339(),
+                    id
+                ],
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(updated > 0)
+    }
+
+    /// Delete a memory, returning whether it existed
+    pub fn delete_memory(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM campaign_memories WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+
+    pub fn get_memory(&self, id: &str) -> ServiceResult<Option<CampaignMemory>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM campaign_memories WHERE id = ?1",
+                MEMORY_COLUMNS
+            ),
+            params![id],
+            CampaignMemory::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Memories visible from a world (shared ones included; all of them when
+    /// no world is given), most recently changed first
+    pub fn list_memories(
+        &self,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<CampaignMemory>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM campaign_memories
+                WHERE ?1 IS NULL OR world_id IS NULL OR world_id = ?1
+                ORDER BY updated_at DESC
+                LIMIT ?2
+                "#,
+                MEMORY_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![world_id, limit as i64], CampaignMemory::from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Rank memories visible from a world by similarity to a query embedding,
+    /// best first
+    pub fn search_memories(
+        &self,
+        query_embedding: &[f32],
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<(CampaignMemory, f32)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}, embedding
+                FROM campaign_memories
+                WHERE ?1 IS NULL OR world_id IS NULL OR world_id = ?1
+                "#,
+                MEMORY_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![world_id], |row| {
+                let memory = CampaignMemory::from_row(row)?;
+                let embedding_bytes: Vec<u8> = row.get(5)?;
+                Ok((memory, embedding_bytes))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let (memory, embedding_bytes) = row.map_err(DatabaseError::Query)?;
+            let embedding: Vec<f32> = embedding_bytes
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect();
+            results.push((memory, cosine_similarity(query_embedding, &embedding)));
+        }
+
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+}
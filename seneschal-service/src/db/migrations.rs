@@ -2,9 +2,13 @@
 //!
 //! This module contains all database migrations and schema setup.
 
+mod campaign;
+mod library;
+
 use rusqlite::Connection;
 
 use crate::error::{DatabaseError, ServiceResult};
+use crate::ingestion::hash::compute_chunk_hash;
 
 /// Run all database migrations.
 ///
@@ -133,6 +137,34 @@ pub(super) fn run_migrations(conn: &Connection) -> ServiceResult<()> {
     run_settings_table_migration(conn)?;
     run_image_type_rename_migration(conn)?;
     run_drop_conversations_table_migration(conn)?;
+    run_chunk_content_hash_migration(conn)?;
+    library::run_access_rules_migration(conn)?;
+    library::run_stat_blocks_migration(conn)?;
+    library::run_ingestion_digests_migration(conn)?;
+    library::run_document_summaries_migration(conn)?;
+    library::run_document_centroids_migration(conn)?;
+    library::run_tool_artifacts_migration(conn)?;
+    library::run_glossary_migration(conn)?;
+    library::run_timeline_migration(conn)?;
+    library::run_image_tags_migration(conn)?;
+    library::run_image_grids_migration(conn)?;
+    library::run_evaluation_migration(conn)?;
+    library::run_world_partition_migration(conn)?;
+    library::run_document_versions_migration(conn)?;
+    library::run_errata_migration(conn)?;
+    library::run_campaign_memory_migration(conn)?;
+    library::run_campaign_clock_migration(conn)?;
+    library::run_npc_registry_migration(conn)?;
+    library::run_plot_hooks_migration(conn)?;
+    library::run_document_priority_migration(conn)?;
+    library::run_fvtt_changes_migration(conn)?;
+    library::run_image_usage_migration(conn)?;
+    library::run_campaign_tasks_migration(conn)?;
+    library::run_read_aloud_migration(conn)?;
+    library::run_model_usage_migration(conn)?;
+    campaign::run_encounter_tables_migration(conn)?;
+    campaign::run_world_systems_migration(conn)?;
+    campaign::run_world_settings_migration(conn)?;
 
     Ok(())
 }
@@ -515,3 +547,54 @@ fn run_drop_conversations_table_migration(conn: &Connection) -> ServiceResult<()
 
     Ok(())
 }
+
+/// Migration: Add content_hash column to chunks for cross-document deduplication
+fn run_chunk_content_hash_migration(conn: &Connection) -> ServiceResult<()> {
+    let has_content_hash: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('chunks') WHERE name='content_hash'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+        > 0;
+
+    if !has_content_hash {
+        conn.execute_batch(
+            r#"
+            ALTER TABLE chunks ADD COLUMN content_hash TEXT;
+            CREATE INDEX IF NOT EXISTS idx_chunks_content_hash ON chunks(content_hash);
+            "#,
+        )
+        .map_err(|e| DatabaseError::Migration {
+            message: format!("Failed to add content_hash column: {}", e),
+        })?;
+
+        // Backfill hashes for existing chunks (SQLite has no SHA-256 built in)
+        let existing: Vec<(String, String)> = {
+            let mut stmt = conn
+                .prepare("SELECT id, content FROM chunks")
+                .map_err(|e| DatabaseError::Migration {
+                    message: format!("Failed to read chunks for hash backfill: {}", e),
+                })?;
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| DatabaseError::Migration {
+                    message: format!("Failed to read chunks for hash backfill: {}", e),
+                })?
+                .filter_map(|r| r.ok())
+                .collect()
+        };
+
+        for (id, content) in existing {
+            conn.execute(
+                "UPDATE chunks SET content_hash = ?1 WHERE id = ?2",
+                rusqlite::params![compute_chunk_hash(&content), id],
+            )
+            .map_err(|e| DatabaseError::Migration {
+                message: format!("Failed to backfill chunk content_hash: {}", e),
+            })?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Migrations for campaign data generated at the table rather than derived
+//! from the document library.
+
+use rusqlite::Connection;
+
+use crate::error::{DatabaseError, ServiceResult};
+
+/// Migration: Cache generated animal encounter tables per world
+pub(super) fn run_encounter_tables_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- world_id is the campaign ('' when no MCP world is configured);
+        -- world_key names the planet the tables were generated for
+        CREATE TABLE IF NOT EXISTS encounter_tables (
+            world_id TEXT NOT NULL,
+            world_key TEXT NOT NULL,
+            uwp TEXT NOT NULL,
+            tables TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (world_id, world_key)
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create encounter_tables table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Per-world settings overrides
+pub(super) fn run_world_settings_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS world_settings (
+            world_id TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (world_id, key)
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create world_settings table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Remember the game system each FVTT world runs
+pub(super) fn run_world_systems_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- system_id is FVTT's game.system.id, as reported by the world's GM client
+        CREATE TABLE IF NOT EXISTS world_systems (
+            world_id TEXT PRIMARY KEY,
+            system_id TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create world_systems table: {}", e),
+    })?;
+
+    Ok(())
+}
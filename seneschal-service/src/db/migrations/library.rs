@@ -0,0 +1,706 @@
+//! Migrations for access rules and for data derived from the document
+//! library: stat blocks, ingestion digests, summaries and centroids.
+
+use rusqlite::Connection;
+
+use crate::error::{DatabaseError, ServiceResult};
+
+/// Migration: Add page/section access rules and per-image access level overrides
+pub(super) fn run_access_rules_migration(conn: &Connection) -> ServiceResult<()> {
+    let has_image_access_level: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('document_images') WHERE name='access_level'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+        > 0;
+
+    if !has_image_access_level {
+        conn.execute_batch(
+            r#"
+            -- NULL means the image inherits its document's access level
+            ALTER TABLE document_images ADD COLUMN access_level INTEGER;
+
+            CREATE TABLE IF NOT EXISTS document_access_rules (
+                id TEXT PRIMARY KEY,
+                document_id TEXT NOT NULL,
+                start_page INTEGER,
+                end_page INTEGER,
+                section_pattern TEXT,
+                access_level INTEGER NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_document_access_rules_document ON document_access_rules(document_id);
+            "#,
+        )
+        .map_err(|e| DatabaseError::Migration {
+            message: format!("Failed to add access rules: {}", e),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Migration: Add extracted NPC/creature stat blocks
+pub(super) fn run_stat_blocks_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- Access is taken from the source chunk so page/section access rules apply
+        CREATE TABLE IF NOT EXISTS stat_blocks (
+            id TEXT PRIMARY KEY,
+            document_id TEXT NOT NULL,
+            chunk_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            page_number INTEGER,
+            data TEXT NOT NULL,
+            raw_text TEXT NOT NULL,
+            validated INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE,
+            FOREIGN KEY (chunk_id) REFERENCES chunks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_stat_blocks_document ON stat_blocks(document_id);
+        CREATE INDEX IF NOT EXISTS idx_stat_blocks_name ON stat_blocks(name);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create stat_blocks table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add history of posted ingestion digests
+pub(super) fn run_ingestion_digests_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- covered_until is where the next digest starts looking for new documents
+        CREATE TABLE IF NOT EXISTS ingestion_digests (
+            id TEXT PRIMARY KEY,
+            covered_until TEXT NOT NULL,
+            document_count INTEGER NOT NULL,
+            journal_id TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create ingestion_digests table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add per-document summaries, outlines and summary embeddings
+pub(super) fn run_document_summaries_migration(conn: &Connection) -> ServiceResult<()> {
+    let has_summary: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('documents') WHERE name='summary'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+        > 0;
+
+    if !has_summary {
+        conn.execute_batch(
+            r#"
+            ALTER TABLE documents ADD COLUMN summary TEXT;
+            -- JSON array of section headings
+            ALTER TABLE documents ADD COLUMN outline TEXT;
+
+            CREATE TABLE IF NOT EXISTS document_summary_embeddings (
+                document_id TEXT PRIMARY KEY,
+                embedding BLOB NOT NULL,
+                FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .map_err(|e| DatabaseError::Migration {
+            message: format!("Failed to add document summaries: {}", e),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Migration: Add per-document embedding centroids for related-document lookups
+pub(super) fn run_document_centroids_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- Mean of the document's chunk embeddings; cleared when it is re-chunked
+        CREATE TABLE IF NOT EXISTS document_centroids (
+            document_id TEXT PRIMARY KEY,
+            embedding BLOB NOT NULL,
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create document_centroids table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add the tool result artifact store
+pub(super) fn run_tool_artifacts_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- Full text of oversized tool results, readable in slices by the MCP
+        -- session that produced them
+        CREATE TABLE IF NOT EXISTS tool_artifacts (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            content TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            last_accessed_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_tool_artifacts_session ON tool_artifacts(session_id);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create tool_artifacts table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add terms harvested from document indexes and glossaries
+pub(super) fn run_glossary_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- pages is a JSON array of PDF page numbers; access is checked against
+        -- the chunks on source_page so page access rules apply
+        CREATE TABLE IF NOT EXISTS glossary (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            document_id TEXT NOT NULL,
+            term TEXT NOT NULL COLLATE NOCASE,
+            pages TEXT NOT NULL,
+            definition TEXT,
+            source_page INTEGER,
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_glossary_document ON glossary(document_id);
+        CREATE INDEX IF NOT EXISTS idx_glossary_term ON glossary(term);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create glossary table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add the campaign timeline
+pub(super) fn run_timeline_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- Extracted events take their access from the source chunk so page
+        -- access rules apply; manual events carry their own access level
+        CREATE TABLE IF NOT EXISTS timeline_events (
+            id TEXT PRIMARY KEY,
+            year INTEGER NOT NULL,
+            day INTEGER,
+            sort_key INTEGER NOT NULL,
+            description TEXT NOT NULL,
+            location TEXT,
+            source TEXT NOT NULL,
+            document_id TEXT,
+            chunk_id TEXT,
+            page_number INTEGER,
+            access_level INTEGER NOT NULL DEFAULT 4,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE,
+            FOREIGN KEY (chunk_id) REFERENCES chunks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_timeline_events_sort ON timeline_events(sort_key);
+        CREATE INDEX IF NOT EXISTS idx_timeline_events_document ON timeline_events(document_id);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create timeline_events table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add tags on individual images
+pub(super) fn run_image_tags_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS image_tags (
+            image_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (image_id, tag),
+            FOREIGN KEY (image_id) REFERENCES document_images(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_image_tags_tag ON image_tags(tag);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create image_tags table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+pub(super) fn run_image_grids_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS image_grids (
+            image_id TEXT PRIMARY KEY,
+            pitch REAL NOT NULL,
+            offset_x REAL NOT NULL,
+            offset_y REAL NOT NULL,
+            columns INTEGER NOT NULL,
+            rows INTEGER NOT NULL,
+            confidence REAL NOT NULL,
+            FOREIGN KEY (image_id) REFERENCES document_images(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create image_grids table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+pub(super) fn run_world_partition_migration(conn: &Connection) -> ServiceResult<()> {
+    let has_world_id: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('documents') WHERE name='world_id'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+        > 0;
+
+    if !has_world_id {
+        conn.execute_batch(
+            r#"
+            -- NULL means the document is shared by every world
+            ALTER TABLE documents ADD COLUMN world_id TEXT;
+
+            CREATE INDEX IF NOT EXISTS idx_documents_world ON documents(world_id);
+            "#,
+        )
+        .map_err(|e| DatabaseError::Migration {
+            message: format!("Failed to add world_id column: {}", e),
+        })?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn run_evaluation_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS eval_questions (
+            id TEXT PRIMARY KEY,
+            question TEXT NOT NULL,
+            expected_answer TEXT,
+            document_id TEXT,
+            expected_pages TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS eval_runs (
+            id TEXT PRIMARY KEY,
+            settings TEXT NOT NULL,
+            question_count INTEGER NOT NULL,
+            hit_rate REAL NOT NULL,
+            mean_reciprocal_rank REAL NOT NULL,
+            grounding_score REAL,
+            results TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_eval_runs_created ON eval_runs(created_at);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create evaluation tables: {}", e),
+    })?;
+
+    Ok(())
+}
+
+pub(super) fn run_document_versions_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS document_versions (
+            document_id TEXT NOT NULL,
+            version INTEGER NOT NULL,
+            file_path TEXT NOT NULL,
+            file_hash TEXT NOT NULL,
+            page_hashes TEXT,
+            changed_pages TEXT,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (document_id, version),
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+        );
+
+        -- Chunks replaced by a later version, kept (with their embeddings) so
+        -- earlier versions stay readable
+        CREATE TABLE IF NOT EXISTS superseded_chunks (
+            id TEXT PRIMARY KEY,
+            document_id TEXT NOT NULL,
+            version INTEGER NOT NULL,
+            superseded_in INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            chunk_index INTEGER NOT NULL,
+            page_number INTEGER,
+            section_title TEXT,
+            access_level INTEGER NOT NULL,
+            metadata TEXT,
+            embedding BLOB,
+            created_at TEXT NOT NULL,
+            superseded_at TEXT NOT NULL,
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_superseded_chunks_page ON superseded_chunks(document_id, page_number);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create document version tables: {}", e),
+    })?;
+
+    let has_version: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('chunks') WHERE name='version'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+        > 0;
+
+    if !has_version {
+        conn.execute(
+            "ALTER TABLE chunks ADD COLUMN version INTEGER NOT NULL DEFAULT 1",
+            [],
+        )
+        .map_err(|e| DatabaseError::Migration {
+            message: format!("Failed to add chunk version column: {}", e),
+        })?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn run_errata_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS errata (
+            id TEXT PRIMARY KEY,
+            errata_document_id TEXT NOT NULL,
+            errata_start_page INTEGER,
+            errata_end_page INTEGER,
+            target_document_id TEXT NOT NULL,
+            target_start_page INTEGER,
+            target_end_page INTEGER,
+            issued TEXT,
+            note TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (errata_document_id) REFERENCES documents(id) ON DELETE CASCADE,
+            FOREIGN KEY (target_document_id) REFERENCES documents(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_errata_target ON errata(target_document_id);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create errata table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add campaign memory (canonical facts with embeddings)
+pub(super) fn run_campaign_memory_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaign_memories (
+            id TEXT PRIMARY KEY,
+            fact TEXT NOT NULL,
+            world_id TEXT,
+            embedding BLOB NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_campaign_memories_world ON campaign_memories(world_id);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create campaign_memories table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add the campaign clock (current Imperial date per world)
+pub(super) fn run_campaign_clock_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- world_id is '' when no MCP world is configured
+        CREATE TABLE IF NOT EXISTS campaign_clocks (
+            world_id TEXT PRIMARY KEY,
+            year INTEGER NOT NULL,
+            day INTEGER NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create campaign_clocks table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add the NPC registry and relationship graph
+pub(super) fn run_npc_registry_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaign_npcs (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            world_id TEXT,
+            description TEXT,
+            stat_block_id TEXT,
+            actor_uuid TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_campaign_npcs_world ON campaign_npcs(world_id);
+        CREATE INDEX IF NOT EXISTS idx_campaign_npcs_name ON campaign_npcs(name COLLATE NOCASE);
+
+        CREATE TABLE IF NOT EXISTS npc_relations (
+            id TEXT PRIMARY KEY,
+            source_id TEXT NOT NULL,
+            target_id TEXT NOT NULL,
+            relation TEXT NOT NULL,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            UNIQUE (source_id, target_id, relation),
+            FOREIGN KEY (source_id) REFERENCES campaign_npcs(id) ON DELETE CASCADE,
+            FOREIGN KEY (target_id) REFERENCES campaign_npcs(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_npc_relations_target ON npc_relations(target_id);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create NPC registry tables: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Track which adventure passages have been surfaced as plot hooks
+pub(super) fn run_plot_hooks_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- world_id is '' when no MCP world is configured
+        CREATE TABLE IF NOT EXISTS surfaced_plot_hooks (
+            world_id TEXT NOT NULL,
+            chunk_id TEXT NOT NULL,
+            surfaced_at TEXT NOT NULL,
+            PRIMARY KEY (world_id, chunk_id),
+            FOREIGN KEY (chunk_id) REFERENCES chunks(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create surfaced_plot_hooks table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add a search priority to documents
+pub(super) fn run_document_priority_migration(conn: &Connection) -> ServiceResult<()> {
+    let has_priority: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('documents') WHERE name='priority'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+        > 0;
+
+    if !has_priority {
+        conn.execute_batch("ALTER TABLE documents ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;")
+            .map_err(|e| DatabaseError::Migration {
+                message: format!("Failed to add priority column: {}", e),
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Migration: Log changes tools make to FVTT world documents, for undo
+pub(super) fn run_fvtt_changes_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- world_id is '' when no MCP world is configured; before_json and
+        -- after_json are the document's data around the change, when known
+        CREATE TABLE IF NOT EXISTS fvtt_changes (
+            id TEXT PRIMARY KEY,
+            world_id TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            document_type TEXT NOT NULL,
+            document_id TEXT NOT NULL,
+            document_name TEXT,
+            operation TEXT NOT NULL,
+            before_json TEXT,
+            after_json TEXT,
+            session_id TEXT,
+            created_at TEXT NOT NULL,
+            undone_at TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_fvtt_changes_world ON fvtt_changes(world_id, created_at);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create fvtt_changes table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Track where delivered images are used in FVTT
+pub(super) fn run_image_usage_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- world_id is '' when no MCP world is configured
+        CREATE TABLE IF NOT EXISTS image_deliveries (
+            world_id TEXT NOT NULL,
+            fvtt_path TEXT NOT NULL,
+            image_id TEXT NOT NULL,
+            delivered_at TEXT NOT NULL,
+            PRIMARY KEY (world_id, fvtt_path),
+            FOREIGN KEY (image_id) REFERENCES document_images(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_image_deliveries_image ON image_deliveries(image_id);
+
+        -- World documents reported by the FVTT module to use a delivered path
+        CREATE TABLE IF NOT EXISTS image_placements (
+            world_id TEXT NOT NULL,
+            fvtt_path TEXT NOT NULL,
+            document_type TEXT NOT NULL,
+            document_id TEXT NOT NULL,
+            document_name TEXT,
+            reported_at TEXT NOT NULL,
+            PRIMARY KEY (world_id, fvtt_path, document_type, document_id),
+            FOREIGN KEY (world_id, fvtt_path)
+                REFERENCES image_deliveries(world_id, fvtt_path) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_image_placements_document
+            ON image_placements(document_type, document_id);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create image usage tables: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add campaign tasks (prep TODOs and reminders)
+pub(super) fn run_campaign_tasks_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaign_tasks (
+            id TEXT PRIMARY KEY,
+            description TEXT NOT NULL,
+            world_id TEXT,
+            session_id TEXT,
+            created_at TEXT NOT NULL,
+            completed_at TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_campaign_tasks_world ON campaign_tasks(world_id, completed_at);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create campaign_tasks table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add read-aloud passages detected in adventures
+pub(super) fn run_read_aloud_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        -- Access is taken from the source chunk so page/section access rules apply
+        CREATE TABLE IF NOT EXISTS read_aloud_passages (
+            id TEXT PRIMARY KEY,
+            document_id TEXT NOT NULL,
+            chunk_id TEXT NOT NULL,
+            page_number INTEGER,
+            section_title TEXT,
+            text TEXT NOT NULL,
+            validated INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE,
+            FOREIGN KEY (chunk_id) REFERENCES chunks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_read_aloud_document
+            ON read_aloud_passages(document_id, page_number);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create read_aloud_passages table: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// Migration: Add the per-request model usage log
+pub(super) fn run_model_usage_migration(conn: &Connection) -> ServiceResult<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS model_usage (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            model TEXT NOT NULL,
+            feature TEXT NOT NULL,
+            tool TEXT,
+            conversation_id TEXT,
+            user_name TEXT,
+            prompt_tokens INTEGER,
+            completion_tokens INTEGER,
+            duration_ms INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_model_usage_created ON model_usage(created_at);
+        "#,
+    )
+    .map_err(|e| DatabaseError::Migration {
+        message: format!("Failed to create model_usage table: {}", e),
+    })?;
+
+    Ok(())
+}
@@ -2,10 +2,31 @@
 //!
 //! This module contains the data structures for database records.
 
+mod errata;
+mod evaluation;
+mod fvtt_change;
+mod memory;
+mod npc;
+mod task;
+mod usage;
+
+pub use errata::Errata;
+pub use evaluation::{EvalQuestion, EvalResult, EvalRun, EvalSettings};
+pub use fvtt_change::FvttChange;
+pub use memory::CampaignMemory;
+pub use npc::{Npc, NpcRelation};
+pub use task::CampaignTask;
+pub use usage::{UsageGrouping, UsageRecord, UsageTotals};
+
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use rusqlite::Row;
 use serde::{Deserialize, Serialize};
 
+use crate::error::ServiceError;
+use crate::ingestion::statblocks::StatBlockKind;
+use crate::ingestion::timeline::ImperialDate;
 use crate::tools::AccessLevel;
 
 /// Processing status for documents
@@ -113,11 +134,52 @@ pub struct Document {
     /// Total images to caption
     #[serde(skip_serializing_if = "Option::is_none")]
     pub captioning_total: Option<usize>,
+    /// Short LLM-written summary, generated after chunking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Table-of-contents outline of the document's major sections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline: Option<Vec<String>>,
+    /// FVTT world the document belongs to; shared by every world when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world_id: Option<String>,
+    /// Search preference relative to other documents; positive favors the
+    /// document's chunks, negative demotes them
+    #[serde(default)]
+    pub priority: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl Document {
+    /// Whether the document is visible from a world (every document is
+    /// visible when no world is given)
+    pub fn in_world(&self, world_id: Option<&str>) -> bool {
+        match (world_id, &self.world_id) {
+            (Some(world), Some(own)) => world == own,
+            _ => true,
+        }
+    }
+
+    /// Path of the document's source file; documents only lose it to the
+    /// retention policy
+    pub fn source_file(&self) -> Result<&str, ServiceError> {
+        self.file_path
+            .as_deref()
+            .ok_or_else(|| ServiceError::SourceExpired {
+                document_id: self.id.clone(),
+            })
+    }
+
+    /// Whether the source file is an encrypted PDF
+    pub fn is_encrypted(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("encrypted"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
     pub(crate) fn from_row(row: &Row<'_>, tags: Vec<String>) -> Result<Self, rusqlite::Error> {
         let access_level_u8: u8 = row.get(4)?;
         let metadata_str: Option<String> = row.get(5)?;
@@ -134,6 +196,10 @@ impl Document {
         let captioning_error: Option<String> = row.get(16)?;
         let captioning_progress: Option<i64> = row.get(17)?;
         let captioning_total: Option<i64> = row.get(18)?;
+        let summary: Option<String> = row.get(19)?;
+        let outline_str: Option<String> = row.get(20)?;
+        let world_id: Option<String> = row.get(21)?;
+        let priority: i32 = row.get(22)?;
 
         Ok(Self {
             id: row.get(0)?,
@@ -154,6 +220,10 @@ impl Document {
             captioning_error,
             captioning_progress: captioning_progress.map(|p| p as usize),
             captioning_total: captioning_total.map(|t| t as usize),
+            summary,
+            outline: outline_str.and_then(|s| serde_json::from_str(&s).ok()),
+            world_id,
+            priority,
             created_at: DateTime::parse_from_rfc3339(&created_at_str)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
@@ -164,6 +234,29 @@ impl Document {
     }
 }
 
+/// Hash of one page's extracted text (`page` is unset for unpaginated text)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageHash {
+    pub page: Option<i32>,
+    pub hash: String,
+}
+
+/// An uploaded revision of a document's source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentVersion {
+    pub document_id: String,
+    /// Starts at 1 for the originally uploaded file
+    pub version: u32,
+    pub file_path: String,
+    pub file_hash: String,
+    /// Unset until the version has been processed
+    pub page_hashes: Option<Vec<PageHash>>,
+    /// Pages whose chunks this version replaced; unset for the first version
+    /// and until processed
+    pub changed_pages: Option<Vec<Option<i32>>>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Chunk record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Chunk {
@@ -213,6 +306,8 @@ pub enum ImageType {
     Background,
     /// Rendered content (full page or region)
     Render,
+    /// Circular token cut out of another image
+    Token,
 }
 
 impl ImageType {
@@ -221,6 +316,7 @@ impl ImageType {
             ImageType::Individual => "individual",
             ImageType::Background => "background",
             ImageType::Render => "render",
+            ImageType::Token => "token",
         }
     }
 
@@ -228,6 +324,7 @@ impl ImageType {
         match s {
             "background" => ImageType::Background,
             "render" | "region_render" => ImageType::Render,
+            "token" => ImageType::Token,
             _ => ImageType::Individual,
         }
     }
@@ -297,3 +394,274 @@ pub struct DocumentImageWithAccess {
     pub document_title: String,
     pub access_level: AccessLevel,
 }
+
+/// An image's tags and whether they come from its document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageTags {
+    pub tags: Vec<String>,
+    /// True when the image has no tags of its own
+    pub inherited: bool,
+}
+
+/// A delivery of an image into the FVTT assets directory, and the world
+/// documents reported to use it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageUsage {
+    /// FVTT world the image was delivered for; '' when no MCP world is configured
+    pub world_id: String,
+    pub fvtt_path: String,
+    pub delivered_at: DateTime<Utc>,
+    pub placements: Vec<ImagePlacement>,
+}
+
+/// A world document using a delivered image
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePlacement {
+    pub document_type: String,
+    pub document_id: String,
+    pub document_name: Option<String>,
+    pub reported_at: DateTime<Utc>,
+}
+
+/// The square grid detected on a map image, in image pixels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageGrid {
+    pub image_id: String,
+    /// Width of one grid square
+    pub pitch: f32,
+    /// Position of the first vertical and horizontal grid line
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub columns: u32,
+    pub rows: u32,
+    pub confidence: f32,
+}
+
+/// Access level override for part of a document (a page range and/or section title pattern)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentAccessRule {
+    pub id: String,
+    pub document_id: String,
+    /// First page the rule applies to (inclusive); open-ended if None
+    pub start_page: Option<i32>,
+    /// Last page the rule applies to (inclusive); open-ended if None
+    pub end_page: Option<i32>,
+    /// Case-insensitive substring matched against chunk section titles
+    pub section_pattern: Option<String>,
+    pub access_level: AccessLevel,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DocumentAccessRule {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let access_level_u8: u8 = row.get(5)?;
+        let created_at_str: String = row.get(6)?;
+
+        Ok(Self {
+            id: row.get(0)?,
+            document_id: row.get(1)?,
+            start_page: row.get(2)?,
+            end_page: row.get(3)?,
+            section_pattern: row.get(4)?,
+            access_level: AccessLevel::from_u8(access_level_u8),
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+/// NPC or creature stat block extracted from a document chunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatBlock {
+    pub id: String,
+    pub document_id: String,
+    pub chunk_id: String,
+    pub name: String,
+    pub kind: StatBlockKind,
+    pub page_number: Option<i32>,
+    /// Structured fields (characteristics, skills, weapons, ...)
+    pub data: serde_json::Value,
+    pub raw_text: String,
+    /// Whether the LLM confirmed this is a stat block (false if validation was unavailable)
+    pub validated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StatBlock {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let kind_str: String = row.get(4)?;
+        let data_str: String = row.get(6)?;
+        let created_at_str: String = row.get(9)?;
+
+        Ok(Self {
+            id: row.get(0)?,
+            document_id: row.get(1)?,
+            chunk_id: row.get(2)?,
+            name: row.get(3)?,
+            kind: StatBlockKind::from_str_lossy(&kind_str),
+            page_number: row.get(5)?,
+            data: serde_json::from_str(&data_str).unwrap_or(serde_json::Value::Null),
+            raw_text: row.get(7)?,
+            validated: row.get(8)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+/// Boxed text an adventure has the GM read to the players, kept verbatim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadAloudPassage {
+    pub id: String,
+    pub document_id: String,
+    pub chunk_id: String,
+    pub page_number: Option<i32>,
+    /// Section (usually the scene) the passage's chunk belongs to
+    pub section_title: Option<String>,
+    pub text: String,
+    /// Whether the LLM confirmed this is read-aloud text (false if validation was unavailable)
+    pub validated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReadAloudPassage {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let created_at_str: String = row.get(7)?;
+
+        Ok(Self {
+            id: row.get(0)?,
+            document_id: row.get(1)?,
+            chunk_id: row.get(2)?,
+            page_number: row.get(3)?,
+            section_title: row.get(4)?,
+            text: row.get(5)?,
+            validated: row.get(6)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+/// Aggregate counts across the document corpus
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CorpusStats {
+    /// Document count keyed by processing status
+    pub documents_by_status: HashMap<String, usize>,
+    /// Document count keyed by captioning status
+    pub documents_by_captioning_status: HashMap<String, usize>,
+    pub chunks: usize,
+    pub chunks_with_embeddings: usize,
+    pub images: usize,
+    pub images_with_captions: usize,
+    pub fvtt_image_descriptions: usize,
+    pub stat_blocks: usize,
+    /// Size of the main database file (excluding the WAL)
+    pub db_size_bytes: u64,
+}
+
+/// Full text of an oversized tool result, kept for slicing on demand
+#[derive(Debug, Clone)]
+pub struct ToolArtifact {
+    pub id: String,
+    pub tool: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Term harvested from a document's index or glossary
+#[derive(Debug, Clone, Serialize)]
+pub struct GlossaryEntry {
+    pub document_id: String,
+    pub term: String,
+    /// PDF page numbers covering the term
+    pub pages: Vec<i32>,
+    /// Definition, for terms from a glossary
+    pub definition: Option<String>,
+    /// Page the entry was read from
+    pub source_page: Option<i32>,
+}
+
+impl GlossaryEntry {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let pages_str: String = row.get(2)?;
+
+        Ok(Self {
+            document_id: row.get(0)?,
+            term: row.get(1)?,
+            pages: serde_json::from_str(&pages_str).unwrap_or_default(),
+            definition: row.get(3)?,
+            source_page: row.get(4)?,
+        })
+    }
+}
+
+/// Where a timeline event came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineSource {
+    /// Found in a document's text
+    Extracted,
+    /// Added by the GM
+    Manual,
+}
+
+impl TimelineSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelineSource::Extracted => "extracted",
+            TimelineSource::Manual => "manual",
+        }
+    }
+
+    pub fn from_str_lossy(value: &str) -> Self {
+        match value {
+            "extracted" => TimelineSource::Extracted,
+            _ => TimelineSource::Manual,
+        }
+    }
+}
+
+/// A dated event in the campaign timeline
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEvent {
+    pub id: String,
+    pub date: ImperialDate,
+    pub description: String,
+    pub location: Option<String>,
+    pub source: TimelineSource,
+    pub document_id: Option<String>,
+    pub chunk_id: Option<String>,
+    pub page_number: Option<i32>,
+    pub access_level: AccessLevel,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TimelineEvent {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let day: Option<u16> = row.get(2)?;
+        let source_str: String = row.get(5)?;
+        let access_level_u8: u8 = row.get(9)?;
+        let created_at_str: String = row.get(10)?;
+
+        Ok(Self {
+            id: row.get(0)?,
+            date: ImperialDate {
+                year: row.get(1)?,
+                day,
+            },
+            description: row.get(3)?,
+            location: row.get(4)?,
+            source: TimelineSource::from_str_lossy(&source_str),
+            document_id: row.get(6)?,
+            chunk_id: row.get(7)?,
+            page_number: row.get(8)?,
+            access_level: AccessLevel::from_u8(access_level_u8),
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
@@ -0,0 +1,64 @@
+//! Errata records.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+/// A document (or page range of one) correcting a page range of another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Errata {
+    pub id: String,
+    pub errata_document_id: String,
+    /// Pages of the errata document holding the corrections; the whole
+    /// document if both are None
+    pub errata_start_page: Option<i32>,
+    pub errata_end_page: Option<i32>,
+    pub target_document_id: String,
+    /// First and last corrected page (inclusive); open-ended if None
+    pub target_start_page: Option<i32>,
+    pub target_end_page: Option<i32>,
+    /// When the errata was published, as given (e.g. "2023-05")
+    pub issued: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+fn page_in(page: Option<i32>, start: Option<i32>, end: Option<i32>) -> bool {
+    if start.is_none() && end.is_none() {
+        return true;
+    }
+    page.is_some_and(|page| start.is_none_or(|s| page >= s) && end.is_none_or(|e| page <= e))
+}
+
+impl Errata {
+    /// Whether this errata corrects a page of a document
+    pub fn supersedes(&self, document_id: &str, page: Option<i32>) -> bool {
+        document_id == self.target_document_id
+            && page_in(page, self.target_start_page, self.target_end_page)
+    }
+
+    /// Whether a page of a document holds this errata's corrections
+    pub fn corrects_with(&self, document_id: &str, page: Option<i32>) -> bool {
+        document_id == self.errata_document_id
+            && page_in(page, self.errata_start_page, self.errata_end_page)
+    }
+
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let created_at_str: String = row.get(9)?;
+
+        Ok(Self {
+            id: row.get(0)?,
+            errata_document_id: row.get(1)?,
+            errata_start_page: row.get(2)?,
+            errata_end_page: row.get(3)?,
+            target_document_id: row.get(4)?,
+            target_start_page: row.get(5)?,
+            target_end_page: row.get(6)?,
+            issued: row.get(7)?,
+            note: row.get(8)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
@@ -0,0 +1,61 @@
+//! Evaluation question and run records.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A question with known source pages, for measuring retrieval quality
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalQuestion {
+    pub id: String,
+    pub question: String,
+    /// Reference answer, compared against generated answers
+    pub expected_answer: Option<String>,
+    /// Document the answer is in; any document when unset
+    pub document_id: Option<String>,
+    /// Pages holding the answer; any page of the document when empty
+    pub expected_pages: Vec<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Retrieval and model settings an evaluation ran with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalSettings {
+    pub embedding_model: String,
+    pub chunk_size: usize,
+    pub chunk_overlap: usize,
+    pub top_k: usize,
+    /// Model that generated answers, when answers were generated
+    pub answer_model: Option<String>,
+}
+
+/// How one question fared in an evaluation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalResult {
+    pub question_id: String,
+    pub question: String,
+    /// 1-based position of the first retrieved chunk from an expected page
+    pub rank: Option<usize>,
+    /// `document_id:page` of each retrieved chunk, best first
+    pub retrieved: Vec<String>,
+    pub answer: Option<String>,
+    /// Fraction of the answer's words found in the retrieved text (0-1)
+    pub grounding: Option<f32>,
+    /// Fraction of the reference answer's words found in the answer (0-1)
+    pub answer_recall: Option<f32>,
+    pub error: Option<String>,
+}
+
+/// A completed evaluation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalRun {
+    pub id: String,
+    pub settings: EvalSettings,
+    pub question_count: usize,
+    /// Fraction of questions with an expected page in the top results
+    pub hit_rate: f32,
+    pub mean_reciprocal_rank: f32,
+    /// Mean grounding of generated answers
+    pub grounding_score: Option<f32>,
+    pub results: Vec<EvalResult>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,53 @@
+//! Undo log records.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+/// A change a tool made to an FVTT world document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FvttChange {
+    pub id: String,
+    /// FVTT world the change was made in; '' when no MCP world is configured
+    pub world_id: String,
+    pub tool: String,
+    pub document_type: String,
+    pub document_id: String,
+    pub document_name: Option<String>,
+    /// create, update or delete
+    pub operation: String,
+    /// The document's data before the change, when it existed
+    pub before: Option<serde_json::Value>,
+    /// The document's data after the change, unless it was deleted
+    pub after: Option<serde_json::Value>,
+    /// MCP session that made the change
+    pub session_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+impl FvttChange {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())
+        };
+        let json = |value: Option<String>| value.and_then(|v| serde_json::from_str(&v).ok());
+
+        Ok(Self {
+            id: row.get(0)?,
+            world_id: row.get(1)?,
+            tool: row.get(2)?,
+            document_type: row.get(3)?,
+            document_id: row.get(4)?,
+            document_name: row.get(5)?,
+            operation: row.get(6)?,
+            before: json(row.get(7)?),
+            after: json(row.get(8)?),
+            session_id: row.get(9)?,
+            created_at: parse(row.get(10)?),
+            undone_at: row.get::<_, Option<String>>(11)?.map(parse),
+        })
+    }
+}
@@ -0,0 +1,34 @@
+//! Campaign memory records.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+/// A canonical fact about the campaign ("the party owns the Highndry")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignMemory {
+    pub id: String,
+    pub fact: String,
+    /// FVTT world the fact belongs to; shared by all worlds if None
+    pub world_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CampaignMemory {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())
+        };
+
+        Ok(Self {
+            id: row.get(0)?,
+            fact: row.get(1)?,
+            world_id: row.get(2)?,
+            created_at: parse(row.get(3)?),
+            updated_at: parse(row.get(4)?),
+        })
+    }
+}
@@ -0,0 +1,66 @@
+//! Campaign NPC registry records.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+fn parse_timestamp(value: String) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(&value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+/// A recurring NPC the campaign keeps consistent across sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Npc {
+    pub id: String,
+    pub name: String,
+    /// FVTT world the NPC belongs to; shared by all worlds if None
+    pub world_id: Option<String>,
+    pub description: Option<String>,
+    /// Extracted stat block the NPC was created from
+    pub stat_block_id: Option<String>,
+    /// FVTT actor representing the NPC
+    pub actor_uuid: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Npc {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            world_id: row.get(2)?,
+            description: row.get(3)?,
+            stat_block_id: row.get(4)?,
+            actor_uuid: row.get(5)?,
+            created_at: parse_timestamp(row.get(6)?),
+            updated_at: parse_timestamp(row.get(7)?),
+        })
+    }
+}
+
+/// A directed relationship between two NPCs ("rival of", "employed by")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpcRelation {
+    pub id: String,
+    pub source_id: String,
+    pub target_id: String,
+    pub relation: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NpcRelation {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        Ok(Self {
+            id: row.get(0)?,
+            source_id: row.get(1)?,
+            target_id: row.get(2)?,
+            relation: row.get(3)?,
+            notes: row.get(4)?,
+            created_at: parse_timestamp(row.get(5)?),
+        })
+    }
+}
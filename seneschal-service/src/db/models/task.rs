@@ -0,0 +1,38 @@
+//! Campaign task records.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+/// A prep TODO or reminder carried over to later conversations
+/// ("stat up the Aslan patrol before next session")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignTask {
+    pub id: String,
+    pub description: String,
+    /// FVTT world the task belongs to; shared by all worlds if None
+    pub world_id: Option<String>,
+    /// MCP session the task was added in, if any
+    pub session_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl CampaignTask {
+    pub(crate) fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())
+        };
+
+        Ok(Self {
+            id: row.get(0)?,
+            description: row.get(1)?,
+            world_id: row.get(2)?,
+            session_id: row.get(3)?,
+            created_at: parse(row.get(4)?),
+            completed_at: row.get::<_, Option<String>>(5)?.map(parse),
+        })
+    }
+}
@@ -0,0 +1,62 @@
+//! Model usage records.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One Ollama request, attributed to what made it
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageRecord {
+    pub model: String,
+    /// What the request was for (e.g. chat, captioning, embedding)
+    pub feature: String,
+    /// MCP tool call the request ran under, if any
+    pub tool: Option<String>,
+    /// MCP session the tool call belonged to
+    pub conversation_id: Option<String>,
+    /// MCP client name the session was started with
+    pub user_name: Option<String>,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a usage report is grouped by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGrouping {
+    #[default]
+    Feature,
+    Model,
+    Tool,
+    User,
+    Conversation,
+}
+
+impl UsageGrouping {
+    pub(crate) fn column(self) -> &'static str {
+        match self {
+            UsageGrouping::Feature => "feature",
+            UsageGrouping::Model => "model",
+            UsageGrouping::Tool => "tool",
+            UsageGrouping::User => "user_name",
+            UsageGrouping::Conversation => "conversation_id",
+        }
+    }
+}
+
+/// Totals for one group of a usage report
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageTotals {
+    /// The group's feature, model, tool, user or conversation; None for
+    /// requests without one (e.g. background ingestion has no tool)
+    pub key: Option<String>,
+    pub requests: u64,
+    pub failures: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_duration_ms: u64,
+    pub avg_duration_ms: u64,
+    pub max_duration_ms: u64,
+}
@@ -0,0 +1,224 @@
+//! NPC registry and relationship operations.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::models::{Npc, NpcRelation};
+use crate::error::{DatabaseError, ServiceResult};
+
+const NPC_COLUMNS: &str =
+    "id, name, world_id, description, stat_block_id, actor_uuid, created_at, updated_at";
+
+const RELATION_COLUMNS: &str = "id, source_id, target_id, relation, notes, created_at";
+
+impl Database {
+    pub fn insert_npc(&self, npc: &Npc) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO campaign_npcs (id, name, world_id, description, stat_block_id, actor_uuid, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+            params![
+                npc.id,
+                npc.name,
+                npc.world_id,
+                npc.description,
+                npc.stat_block_id,
+                npc.actor_uuid,
+                npc.created_at.to_rfc3339(),
+                npc.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// Save an NPC's name, description and links, returning whether it existed
+    pub fn update_npc(&self, npc: &Npc) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn
+            .execute(
+                r#"
+                UPDATE campaign_npcs
+                SET name = ?1, description = ?2, stat_block_id = ?3, actor_uuid = ?4, updated_at = ?5
+                WHERE id = ?6
+                "#,
+                params![
+                    npc.name,
+                    npc.description,
+                    npc.stat_block_id,
+                    npc.actor_uuid,
+                    chrono::Utc::now().to_rfc3339(),
+                    npc.id
+                ],
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(updated > 0)
+    }
+
+    /// Delete an NPC and its relationships, returning whether it existed
+    pub fn delete_npc(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM campaign_npcs WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+
+    pub fn get_npc(&self, id: &str) -> ServiceResult<Option<Npc>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("SELECT {} FROM campaign_npcs WHERE id = ?1", NPC_COLUMNS),
+            params![id],
+            Npc::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Find an NPC visible from a world by name, ignoring case. The world's
+    /// own NPC wins over a shared one of the same name.
+    pub fn find_npc(&self, name: &str, world_id: Option<&str>) -> ServiceResult<Option<Npc>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                r#"
+                SELECT {}
+                FROM campaign_npcs
+                WHERE name = ?1 COLLATE NOCASE
+                  AND (?2 IS NULL OR world_id IS NULL OR world_id = ?2)
+                ORDER BY world_id IS NULL
+                LIMIT 1
+                "#,
+                NPC_COLUMNS
+            ),
+            params![name, world_id],
+            Npc::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// NPCs visible from a world (shared ones included; all of them when no
+    /// world is given), by name
+    pub fn list_npcs(&self, world_id: Option<&str>) -> ServiceResult<Vec<Npc>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM campaign_npcs
+                WHERE ?1 IS NULL OR world_id IS NULL OR world_id = ?1
+                ORDER BY name COLLATE NOCASE
+                "#,
+                NPC_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![world_id], Npc::from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Record a relationship, replacing the notes of an identical one
+    pub fn upsert_npc_relation(&self, relation: &NpcRelation) -> ServiceResult<NpcRelation> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO npc_relations (id, source_id, target_id, relation, notes, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(source_id, target_id, relation) DO UPDATE SET notes = excluded.notes
+            "#,
+            params![
+                relation.id,
+                relation.source_id,
+                relation.target_id,
+                relation.relation,
+                relation.notes,
+                relation.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM npc_relations WHERE source_id = ?1 AND target_id = ?2 AND relation = ?3",
+                RELATION_COLUMNS
+            ),
+            params![relation.source_id, relation.target_id, relation.relation],
+            NpcRelation::from_row,
+        )
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Remove a relationship, returning whether it existed
+    pub fn delete_npc_relation(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM npc_relations WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+
+    /// Relationships from or to an NPC
+    pub fn list_npc_relations(&self, npc_id: &str) -> ServiceResult<Vec<NpcRelation>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM npc_relations
+                WHERE source_id = ?1 OR target_id = ?1
+                ORDER BY created_at
+                "#,
+                RELATION_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![npc_id], NpcRelation::from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Relationships between NPCs that are both visible from a world
+    pub fn list_world_npc_relations(
+        &self,
+        world_id: Option<&str>,
+    ) -> ServiceResult<Vec<NpcRelation>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT r.id, r.source_id, r.target_id, r.relation, r.notes, r.created_at
+                FROM npc_relations r
+                JOIN campaign_npcs s ON s.id = r.source_id
+                JOIN campaign_npcs t ON t.id = r.target_id
+                WHERE ?1 IS NULL
+                   OR ((s.world_id IS NULL OR s.world_id = ?1)
+                       AND (t.world_id IS NULL OR t.world_id = ?1))
+                ORDER BY r.created_at
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![world_id], NpcRelation::from_row)
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,53 @@
+//! Surfaced plot hook tracking.
+
+use std::collections::HashSet;
+
+use rusqlite::params;
+
+use super::Database;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Chunks already surfaced as plot hooks in a world
+    pub fn surfaced_plot_hooks(&self, world_id: &str) -> ServiceResult<HashSet<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT chunk_id FROM surfaced_plot_hooks WHERE world_id = ?1")
+            .map_err(DatabaseError::Query)?;
+        let ids = stmt
+            .query_map(params![world_id], |row| row.get(0))
+            .map_err(DatabaseError::Query)?
+            .collect::<Result<HashSet<String>, _>>()
+            .map_err(DatabaseError::Query)?;
+        Ok(ids)
+    }
+
+    pub fn mark_plot_hooks_surfaced(
+        &self,
+        world_id: &str,
+        chunk_ids: &[String],
+    ) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        let now = chrono::Utc::now().to_rfc3339();
+        for chunk_id in chunk_ids {
+            conn.execute(
+                "INSERT OR IGNORE INTO surfaced_plot_hooks (world_id, chunk_id, surfaced_at) VALUES (?1, ?2, ?3)",
+                params![world_id, chunk_id, now],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+        Ok(())
+    }
+
+    /// Forget a world's surfaced plot hooks, returning how many there were
+    pub fn clear_surfaced_plot_hooks(&self, world_id: &str) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute(
+                "DELETE FROM surfaced_plot_hooks WHERE world_id = ?1",
+                params![world_id],
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted)
+    }
+}
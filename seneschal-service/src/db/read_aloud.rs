@@ -0,0 +1,165 @@
+//! Read-aloud passage operations.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::documents::world_filter_sql;
+use super::models::ReadAloudPassage;
+use crate::error::{DatabaseError, ServiceResult};
+use crate::ingestion::read_aloud::READ_ALOUD_TAG;
+
+const PASSAGE_COLUMNS: &str = r#"
+    ra.id, ra.document_id, ra.chunk_id, ra.page_number, ra.section_title,
+    ra.text, ra.validated, ra.created_at
+"#;
+
+impl Database {
+    /// Replace all read-aloud passages for a document, tagging the chunks
+    /// that hold them
+    pub fn replace_document_read_aloud(
+        &self,
+        document_id: &str,
+        passages: &[ReadAloudPassage],
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        tx.execute(
+            "DELETE FROM read_aloud_passages WHERE document_id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+        tx.execute(
+            r#"
+            DELETE FROM chunk_tags
+            WHERE tag = ?1 AND chunk_id IN (SELECT id FROM chunks WHERE document_id = ?2)
+            "#,
+            params![READ_ALOUD_TAG, document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        for passage in passages {
+            tx.execute(
+                r#"
+                INSERT INTO read_aloud_passages (id, document_id, chunk_id, page_number, section_title, text, validated, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "#,
+                params![
+                    passage.id,
+                    passage.document_id,
+                    passage.chunk_id,
+                    passage.page_number,
+                    passage.section_title,
+                    passage.text,
+                    passage.validated,
+                    passage.created_at.to_rfc3339(),
+                ],
+            )
+            .map_err(DatabaseError::Query)?;
+            tx.execute(
+                "INSERT OR IGNORE INTO chunk_tags (chunk_id, tag) VALUES (?1, ?2)",
+                params![passage.chunk_id, READ_ALOUD_TAG],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Count read-aloud passages found in a document
+    pub fn get_read_aloud_count(&self, document_id: &str) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM read_aloud_passages WHERE document_id = ?1",
+                params![document_id],
+                |row| row.get(0),
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(count as usize)
+    }
+
+    /// A read-aloud passage by ID, if its source chunk is within the access level
+    /// and its document is in the world (or shared)
+    pub fn get_read_aloud(
+        &self,
+        passage_id: &str,
+        max_access_level: u8,
+        world_id: Option<&str>,
+    ) -> ServiceResult<Option<ReadAloudPassage>> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.query_row(
+            &format!(
+                r#"
+                SELECT {}
+                FROM read_aloud_passages ra
+                JOIN chunks c ON ra.chunk_id = c.id
+                WHERE ra.id = ?1 AND c.access_level <= ?2
+                {}
+                "#,
+                PASSAGE_COLUMNS,
+                world_filter_sql("ra.document_id", 3)
+            ),
+            params![passage_id, max_access_level, world_id],
+            ReadAloudPassage::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Read-aloud passages by document, page and scene (matched against the
+    /// section title or the text), in page order, filtered by the source
+    /// chunk's access level and the document's world
+    pub fn find_read_aloud(
+        &self,
+        document_id: Option<&str>,
+        page_number: Option<i32>,
+        scene: Option<&str>,
+        max_access_level: u8,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<ReadAloudPassage>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM read_aloud_passages ra
+                JOIN chunks c ON ra.chunk_id = c.id
+                WHERE c.access_level <= ?1
+                  AND (?2 IS NULL OR ra.document_id = ?2)
+                  AND (?3 IS NULL OR ra.page_number = ?3)
+                  AND (?4 IS NULL OR ra.section_title LIKE '%' || ?4 || '%' OR ra.text LIKE '%' || ?4 || '%')
+                  {}
+                ORDER BY ra.document_id, ra.page_number, c.chunk_index
+                LIMIT ?5
+                "#,
+                PASSAGE_COLUMNS,
+                world_filter_sql("ra.document_id", 6)
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![
+                    max_access_level,
+                    document_id,
+                    page_number,
+                    scene,
+                    limit as i64,
+                    world_id
+                ],
+                ReadAloudPassage::from_row,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
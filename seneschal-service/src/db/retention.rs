@@ -0,0 +1,173 @@
+//! Retention policy queries.
+//!
+//! Timestamps are stored both as RFC 3339 and as SQLite's `datetime('now')`
+//! format, so comparisons go through `datetime()` on both sides.
+
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+use super::Database;
+use super::models::{CaptioningStatus, ProcessingStatus};
+use crate::error::{DatabaseError, ServiceResult};
+
+fn sqlite_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl Database {
+    /// Documents processed before `cutoff` that still keep their source file,
+    /// as (document ID, file path). Sources that are read again later are
+    /// left alone: PDFs (page renders, image extraction), FVTT journals and
+    /// vault notes (re-synced in place) and versioned documents.
+    pub fn expired_source_files(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> ServiceResult<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT d.id, d.file_path FROM documents d
+                WHERE d.file_path IS NOT NULL
+                  AND d.processing_status = ?1
+                  AND d.captioning_status NOT IN (?2, ?3)
+                  AND datetime(d.updated_at) < datetime(?4)
+                  AND lower(d.file_path) NOT LIKE '%.pdf'
+                  AND json_extract(d.metadata, '$.fvtt_journal_id') IS NULL
+                  AND json_extract(d.metadata, '$.vault_path') IS NULL
+                  AND NOT EXISTS (
+                      SELECT 1 FROM document_versions dv
+                      WHERE dv.document_id = d.id OR dv.file_path = d.file_path
+                  )
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map(
+                params![
+                    ProcessingStatus::Completed.as_str(),
+                    CaptioningStatus::Pending.as_str(),
+                    CaptioningStatus::InProgress.as_str(),
+                    sqlite_time(cutoff),
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Forget a document's source file after it was deleted
+    pub fn clear_document_file(&self, document_id: &str) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE documents SET file_path = NULL WHERE id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// Images extracted before `cutoff` that have no caption and aren't
+    /// waiting for one, as (image ID, file path)
+    pub fn expired_uncaptioned_images(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> ServiceResult<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT i.id, i.internal_path FROM document_images i
+                JOIN documents d ON d.id = i.document_id
+                WHERE (i.description IS NULL OR i.description = '')
+                  AND d.processing_status != ?1
+                  AND d.captioning_status NOT IN (?2, ?3)
+                  AND datetime(i.created_at) < datetime(?4)
+                "#,
+            )
+            .map_err(DatabaseError::Query)?;
+        let rows = stmt
+            .query_map(
+                params![
+                    ProcessingStatus::Processing.as_str(),
+                    CaptioningStatus::Pending.as_str(),
+                    CaptioningStatus::InProgress.as_str(),
+                    sqlite_time(cutoff),
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Delete cached FVTT image descriptions last updated before `cutoff`
+    pub fn delete_image_descriptions_before(&self, cutoff: DateTime<Utc>) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM fvtt_image_descriptions WHERE datetime(updated_at) < datetime(?1)",
+            params![sqlite_time(cutoff)],
+        )
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Delete FVTT change records and model usage logged before `cutoff`
+    pub fn delete_audit_entries_before(&self, cutoff: DateTime<Utc>) -> ServiceResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let cutoff = sqlite_time(cutoff);
+        let mut removed = 0;
+        for table in ["fvtt_changes", "model_usage"] {
+            removed += tx
+                .execute(
+                    &format!(
+                        "DELETE FROM {} WHERE datetime(created_at) < datetime(?1)",
+                        table
+                    ),
+                    params![cutoff],
+                )
+                .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_still_needed_are_kept() {
+        let db = Database::open_in_memory();
+        let mut sql = String::new();
+        for (id, path, metadata) in [
+            ("epub", "book.epub", "NULL"),
+            ("pdf", "book.PDF", "NULL"),
+            ("journal", "journal.md", "'{\"fvtt_journal_id\": \"j1\"}'"),
+            ("note", "note.md", "'{\"vault_path\": \"npcs/note.md\"}'"),
+            ("versioned", "rules.md", "NULL"),
+        ] {
+            sql.push_str(&format!(
+                "INSERT INTO documents (id, title, file_path, metadata, processing_status, updated_at)
+                     VALUES ('{id}', '{id}', '{path}', {metadata}, 'completed', '2020-01-01 00:00:00');"
+            ));
+        }
+        sql.push_str(
+            "INSERT INTO document_versions (document_id, version, file_path, file_hash, created_at)
+                 VALUES ('versioned', 1, 'rules_v1.md', '', '2020-01-01T00:00:00Z');",
+        );
+        db.execute_test_sql(&sql);
+
+        let expired = db.expired_source_files(Utc::now()).unwrap();
+        assert_eq!(expired, vec![("epub".to_string(), "book.epub".to_string())]);
+    }
+}
@@ -37,6 +37,70 @@ impl Database {
         Ok(settings)
     }
 
+    /// Get a world's settings overrides as a map
+    pub fn get_world_settings(
+        &self,
+        world_id: &str,
+    ) -> ServiceResult<HashMap<String, serde_json::Value>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM world_settings WHERE world_id = ?1")
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![world_id], |row| {
+                let key: String = row.get(0)?;
+                let value_str: String = row.get(1)?;
+                Ok((key, value_str))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        let mut settings = HashMap::new();
+        for row in rows {
+            let (key, value_str) = row.map_err(DatabaseError::Query)?;
+            if let Ok(value) = serde_json::from_str(&value_str) {
+                settings.insert(key, value);
+            }
+        }
+
+        Ok(settings)
+    }
+
+    /// Set a world's settings overrides
+    /// Null values delete the override (revert to the global setting)
+    pub fn set_world_settings(
+        &self,
+        world_id: &str,
+        settings: HashMap<String, serde_json::Value>,
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        for (key, value) in settings {
+            if value.is_null() {
+                tx.execute(
+                    "DELETE FROM world_settings WHERE world_id = ?1 AND key = ?2",
+                    params![world_id, key],
+                )
+                .map_err(DatabaseError::Query)?;
+            } else {
+                let value_str =
+                    serde_json::to_string(&value).map_err(DatabaseError::Serialization)?;
+                tx.execute(
+                    "INSERT INTO world_settings (world_id, key, value, updated_at) VALUES (?1, ?2, ?3, datetime('now')) \
+                     ON CONFLICT(world_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                    params![world_id, key, value_str],
+                )
+                .map_err(DatabaseError::Query)?;
+            }
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
     /// Set multiple settings in a single transaction
     /// Null values delete the setting (revert to default)
     pub fn set_settings(&self, settings: HashMap<String, serde_json::Value>) -> ServiceResult<()> {
@@ -62,3 +126,32 @@ impl Database {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_settings_are_per_world() {
+        let db = Database::open_in_memory();
+        db.set_world_settings(
+            "a",
+            HashMap::from([
+                ("ollama.temperature".to_string(), serde_json::json!(0.2)),
+                ("tts.voice".to_string(), serde_json::json!("narrator")),
+            ]),
+        )
+        .unwrap();
+        db.set_world_settings(
+            "a",
+            HashMap::from([("tts.voice".to_string(), serde_json::Value::Null)]),
+        )
+        .unwrap();
+
+        let a = db.get_world_settings("a").unwrap();
+        assert_eq!(a.len(), 1);
+        assert_eq!(a["ollama.temperature"], serde_json::json!(0.2));
+        assert!(db.get_world_settings("b").unwrap().is_empty());
+        assert!(db.get_all_settings().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Extracted NPC/creature stat block operations.
+
+use rusqlite::params;
+
+use super::Database;
+use super::documents::world_filter_sql;
+use super::models::StatBlock;
+use crate::error::{DatabaseError, ServiceResult};
+
+const STAT_BLOCK_COLUMNS: &str = r#"
+    sb.id, sb.document_id, sb.chunk_id, sb.name, sb.kind, sb.page_number,
+    sb.data, sb.raw_text, sb.validated, sb.created_at
+"#;
+
+impl Database {
+    /// Replace all stat blocks for a document
+    pub fn replace_document_stat_blocks(
+        &self,
+        document_id: &str,
+        stat_blocks: &[StatBlock],
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        tx.execute(
+            "DELETE FROM stat_blocks WHERE document_id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        for block in stat_blocks {
+            tx.execute(
+                r#"
+                INSERT INTO stat_blocks (id, document_id, chunk_id, name, kind, page_number, data, raw_text, validated, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                "#,
+                params![
+                    block.id,
+                    block.document_id,
+                    block.chunk_id,
+                    block.name,
+                    block.kind.as_str(),
+                    block.page_number,
+                    block.data.to_string(),
+                    block.raw_text,
+                    block.validated,
+                    block.created_at.to_rfc3339(),
+                ],
+            )
+            .map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Count stat blocks extracted from a document
+    pub fn get_stat_block_count(&self, document_id: &str) -> ServiceResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM stat_blocks WHERE document_id = ?1",
+                params![document_id],
+                |row| row.get(0),
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(count as usize)
+    }
+
+    /// Search stat blocks by name or content, filtered by the source chunk's
+    /// access level and the world its document belongs to
+    pub fn search_stat_blocks(
+        &self,
+        query: Option<&str>,
+        kind: Option<&str>,
+        document_id: Option<&str>,
+        max_access_level: u8,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<StatBlock>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM stat_blocks sb
+                JOIN chunks c ON sb.chunk_id = c.id
+                WHERE c.access_level <= ?1
+                  AND (?2 IS NULL OR sb.name LIKE '%' || ?2 || '%' OR sb.raw_text LIKE '%' || ?2 || '%')
+                  AND (?3 IS NULL OR sb.kind = ?3)
+                  AND (?4 IS NULL OR sb.document_id = ?4)
+                  {}
+                ORDER BY (sb.name LIKE '%' || COALESCE(?2, '') || '%') DESC, sb.name
+                LIMIT ?5
+                "#,
+                STAT_BLOCK_COLUMNS,
+                world_filter_sql("sb.document_id", 6)
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![
+                    max_access_level,
+                    query,
+                    kind,
+                    document_id,
+                    limit as i64,
+                    world_id
+                ],
+                StatBlock::from_row,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+
+    /// Get a stat block if the source chunk is accessible at the given level
+    /// from the given world
+    pub fn get_stat_block(
+        &self,
+        id: &str,
+        max_access_level: u8,
+        world_id: Option<&str>,
+    ) -> ServiceResult<Option<StatBlock>> {
+        let conn = self.conn.lock().unwrap();
+
+        let result = conn.query_row(
+            &format!(
+                r#"
+                SELECT {}
+                FROM stat_blocks sb
+                JOIN chunks c ON sb.chunk_id = c.id
+                WHERE sb.id = ?1 AND c.access_level <= ?2 {}
+                "#,
+                STAT_BLOCK_COLUMNS,
+                world_filter_sql("sb.document_id", 3)
+            ),
+            params![id, max_access_level, world_id],
+            StatBlock::from_row,
+        );
+
+        match result {
+            Ok(block) => Ok(Some(block)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(DatabaseError::Query(e).into()),
+        }
+    }
+}
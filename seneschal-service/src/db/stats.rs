@@ -0,0 +1,66 @@
+//! Corpus statistics.
+//!
+//! This module contains read-only aggregate queries for the admin dashboard.
+
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+
+use super::{CorpusStats, Database};
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Count documents, chunks, embeddings, images and captions
+    pub fn get_corpus_stats(&self) -> ServiceResult<CorpusStats> {
+        let conn = self.conn.lock().unwrap();
+
+        let count = |sql: &str| -> ServiceResult<usize> {
+            conn.query_row(sql, [], |row| row.get::<_, i64>(0))
+                .map(|n| n as usize)
+                .map_err(|e| DatabaseError::Query(e).into())
+        };
+
+        let page_count: i64 = conn
+            .query_row("PRAGMA page_count", [], |row| row.get(0))
+            .map_err(DatabaseError::Query)?;
+        let page_size: i64 = conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))
+            .map_err(DatabaseError::Query)?;
+
+        Ok(CorpusStats {
+            documents_by_status: count_grouped(&conn, "processing_status")?,
+            documents_by_captioning_status: count_grouped(&conn, "captioning_status")?,
+            chunks: count("SELECT COUNT(*) FROM chunks")?,
+            chunks_with_embeddings: count("SELECT COUNT(*) FROM chunk_embeddings")?,
+            images: count("SELECT COUNT(*) FROM document_images")?,
+            images_with_captions: count(
+                "SELECT COUNT(*) FROM document_images WHERE description IS NOT NULL AND description != ''",
+            )?,
+            fvtt_image_descriptions: count("SELECT COUNT(*) FROM fvtt_image_descriptions")?,
+            stat_blocks: count("SELECT COUNT(*) FROM stat_blocks")?,
+            db_size_bytes: (page_count * page_size) as u64,
+        })
+    }
+}
+
+/// Document counts grouped by a status column
+fn count_grouped(conn: &Connection, column: &str) -> ServiceResult<HashMap<String, usize>> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {column}, COUNT(*) FROM documents GROUP BY {column}"
+        ))
+        .map_err(DatabaseError::Query)?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+        })
+        .map_err(DatabaseError::Query)?;
+
+    let mut counts = HashMap::new();
+    for row in rows {
+        let (status, n) = row.map_err(DatabaseError::Query)?;
+        counts.insert(status, n);
+    }
+    Ok(counts)
+}
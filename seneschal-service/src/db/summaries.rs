@@ -0,0 +1,106 @@
+//! Document summary and outline operations.
+
+use rusqlite::params;
+
+use super::Database;
+use super::chunks::cosine_similarity;
+use super::documents::world_filter_sql;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Store a document's summary, outline and summary embedding
+    pub fn update_document_summary(
+        &self,
+        document_id: &str,
+        summary: &str,
+        outline: &[String],
+        embedding: &[f32],
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        let outline_json = serde_json::to_string(outline).map_err(DatabaseError::Serialization)?;
+        tx.execute(
+            "UPDATE documents SET summary = ?1, outline = ?2, updated_at = datetime('now') WHERE id = ?3",
+            params![summary, outline_json, document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        let embedding_bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
+        tx.execute(
+            "INSERT OR REPLACE INTO document_summary_embeddings (document_id, embedding) VALUES (?1, ?2)",
+            params![document_id, embedding_bytes],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Remove a document's summary, outline and summary embedding
+    pub fn clear_document_summary(&self, document_id: &str) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        tx.execute(
+            "UPDATE documents SET summary = NULL, outline = NULL, updated_at = datetime('now') WHERE id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+        tx.execute(
+            "DELETE FROM document_summary_embeddings WHERE document_id = ?1",
+            params![document_id],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Rank documents by how closely their summary matches a query embedding.
+    ///
+    /// Returns IDs of documents visible from the world with their
+    /// similarity, best first.
+    pub fn search_document_summaries(
+        &self,
+        query_embedding: &[f32],
+        max_access_level: u8,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<(String, f32)>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT e.document_id, e.embedding FROM document_summary_embeddings e \
+                 JOIN documents d ON d.id = e.document_id WHERE d.access_level <= ?1{}",
+                world_filter_sql("e.document_id", 2)
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(params![max_access_level, world_id], |row| {
+                let document_id: String = row.get(0)?;
+                let embedding_bytes: Vec<u8> = row.get(1)?;
+                Ok((document_id, embedding_bytes))
+            })
+            .map_err(DatabaseError::Query)?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let (document_id, embedding_bytes) = row.map_err(DatabaseError::Query)?;
+            let embedding: Vec<f32> = embedding_bytes
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect();
+            results.push((document_id, cosine_similarity(query_embedding, &embedding)));
+        }
+
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,99 @@
+//! Campaign task operations.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use super::models::CampaignTask;
+use crate::error::{DatabaseError, ServiceResult};
+
+const TASK_COLUMNS: &str = "id, description, world_id, session_id, created_at, completed_at";
+
+impl Database {
+    pub fn insert_task(&self, task: &CampaignTask) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO campaign_tasks (id, description, world_id, session_id, created_at, completed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                task.id,
+                task.description,
+                task.world_id,
+                task.session_id,
+                task.created_at.to_rfc3339(),
+                task.completed_at.map(|t| t.to_rfc3339()),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    pub fn get_task(&self, id: &str) -> ServiceResult<Option<CampaignTask>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("SELECT {} FROM campaign_tasks WHERE id = ?1", TASK_COLUMNS),
+            params![id],
+            CampaignTask::from_row,
+        )
+        .optional()
+        .map_err(DatabaseError::Query)
+        .map_err(Into::into)
+    }
+
+    /// Mark a task done, returning whether it was open
+    pub fn complete_task(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn
+            .execute(
+                "UPDATE campaign_tasks SET completed_at = ?1 WHERE id = ?2 AND completed_at IS NULL",
+                params![chrono::Utc::now().to_rfc3339(), id],
+            )
+            .map_err(DatabaseError::Query)?;
+        Ok(updated > 0)
+    }
+
+    /// Delete a task, returning whether it existed
+    pub fn delete_task(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM campaign_tasks WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+
+    /// Tasks visible from a world (shared ones included; all of them when no
+    /// world is given), open ones first, oldest first within each group
+    pub fn list_tasks(
+        &self,
+        world_id: Option<&str>,
+        include_completed: bool,
+        limit: usize,
+    ) -> ServiceResult<Vec<CampaignTask>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM campaign_tasks
+                WHERE (?1 IS NULL OR world_id IS NULL OR world_id = ?1)
+                  AND (?2 OR completed_at IS NULL)
+                ORDER BY completed_at IS NOT NULL, created_at
+                LIMIT ?3
+                "#,
+                TASK_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![world_id, include_completed, limit as i64],
+                CampaignTask::from_row,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
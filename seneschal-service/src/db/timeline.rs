@@ -0,0 +1,136 @@
+//! Campaign timeline event operations.
+
+use rusqlite::{Connection, params};
+
+use super::Database;
+use super::models::{TimelineEvent, TimelineSource};
+use crate::error::{DatabaseError, ServiceResult};
+use crate::ingestion::timeline::ImperialDate;
+
+const TIMELINE_COLUMNS: &str = r#"
+    e.id, e.year, e.day, e.description, e.location, e.source, e.document_id,
+    e.chunk_id, e.page_number, COALESCE(c.access_level, e.access_level), e.created_at
+"#;
+
+fn insert_event(conn: &Connection, event: &TimelineEvent) -> rusqlite::Result<usize> {
+    conn.execute(
+        r#"
+        INSERT INTO timeline_events (id, year, day, sort_key, description, location, source, document_id, chunk_id, page_number, access_level, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+        "#,
+        params![
+            event.id,
+            event.date.year,
+            event.date.day,
+            event.date.sort_key(),
+            event.description,
+            event.location,
+            event.source.as_str(),
+            event.document_id,
+            event.chunk_id,
+            event.page_number,
+            event.access_level as u8,
+            event.created_at.to_rfc3339(),
+        ],
+    )
+}
+
+impl Database {
+    /// Replace the events extracted from a document, keeping manual ones
+    pub fn replace_document_timeline(
+        &self,
+        document_id: &str,
+        events: &[TimelineEvent],
+    ) -> ServiceResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(DatabaseError::Query)?;
+
+        tx.execute(
+            "DELETE FROM timeline_events WHERE document_id = ?1 AND source = ?2",
+            params![document_id, TimelineSource::Extracted.as_str()],
+        )
+        .map_err(DatabaseError::Query)?;
+
+        for event in events {
+            insert_event(&tx, event).map_err(DatabaseError::Query)?;
+        }
+
+        tx.commit().map_err(DatabaseError::Query)?;
+
+        Ok(())
+    }
+
+    /// Add a single event
+    pub fn insert_timeline_event(&self, event: &TimelineEvent) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        insert_event(&conn, event).map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// Delete an event, returning whether it existed
+    pub fn delete_timeline_event(&self, id: &str) -> ServiceResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute("DELETE FROM timeline_events WHERE id = ?1", params![id])
+            .map_err(DatabaseError::Query)?;
+        Ok(deleted > 0)
+    }
+
+    /// Events between two dates (inclusive; a bare year covers the whole
+    /// year), oldest first, optionally matching text in the description or
+    /// location. Events read from documents of other worlds are left out.
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_timeline(
+        &self,
+        from: Option<ImperialDate>,
+        to: Option<ImperialDate>,
+        text: Option<&str>,
+        document_id: Option<&str>,
+        max_access_level: u8,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<TimelineEvent>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {}
+                FROM timeline_events e
+                LEFT JOIN chunks c ON e.chunk_id = c.id
+                WHERE COALESCE(c.access_level, e.access_level) <= ?1
+                  AND (?2 IS NULL OR e.sort_key >= ?2)
+                  AND (?3 IS NULL OR e.sort_key <= ?3)
+                  AND (?4 IS NULL OR e.description LIKE '%' || ?4 || '%' OR e.location LIKE '%' || ?4 || '%')
+                  AND (?5 IS NULL OR e.document_id = ?5)
+                  AND (e.document_id IS NULL OR ?7 IS NULL OR EXISTS (
+                      SELECT 1 FROM documents wd WHERE wd.id = e.document_id
+                        AND (wd.world_id IS NULL OR wd.world_id = ?7)
+                  ))
+                ORDER BY e.sort_key, e.created_at
+                LIMIT ?6
+                "#,
+                TIMELINE_COLUMNS
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![
+                    max_access_level,
+                    from.map(|date| date.sort_key()),
+                    to.map(|date| date.end_sort_key()),
+                    text,
+                    document_id,
+                    limit as i64,
+                    world_id
+                ],
+                TimelineEvent::from_row,
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,84 @@
+//! Model usage log operations.
+
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+use super::Database;
+use super::models::{UsageGrouping, UsageRecord, UsageTotals};
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// Log one Ollama request
+    pub fn insert_usage_record(&self, record: &UsageRecord) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO model_usage (model, feature, tool, conversation_id, user_name, prompt_tokens, completion_tokens, duration_ms, success, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#,
+            params![
+                record.model,
+                record.feature,
+                record.tool,
+                record.conversation_id,
+                record.user_name,
+                record.prompt_tokens.map(|n| n as i64),
+                record.completion_tokens.map(|n| n as i64),
+                record.duration_ms as i64,
+                record.success,
+                record.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+
+    /// Request counts, tokens and time in a period, grouped and busiest first
+    pub fn usage_report(
+        &self,
+        group_by: UsageGrouping,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> ServiceResult<Vec<UsageTotals>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT {column}, COUNT(*), SUM(success = 0),
+                       COALESCE(SUM(prompt_tokens), 0), COALESCE(SUM(completion_tokens), 0),
+                       SUM(duration_ms), MAX(duration_ms)
+                FROM model_usage
+                WHERE (?1 IS NULL OR created_at >= ?1) AND (?2 IS NULL OR created_at < ?2)
+                GROUP BY {column}
+                ORDER BY SUM(duration_ms) DESC
+                "#,
+                column = group_by.column()
+            ))
+            .map_err(DatabaseError::Query)?;
+
+        let rows = stmt
+            .query_map(
+                params![since.map(|t| t.to_rfc3339()), until.map(|t| t.to_rfc3339())],
+                |row| {
+                    let requests = row.get::<_, i64>(1)? as u64;
+                    let total_duration_ms = row.get::<_, i64>(5)? as u64;
+                    Ok(UsageTotals {
+                        key: row.get(0)?,
+                        requests,
+                        failures: row.get::<_, i64>(2)? as u64,
+                        prompt_tokens: row.get::<_, i64>(3)? as u64,
+                        completion_tokens: row.get::<_, i64>(4)? as u64,
+                        total_duration_ms,
+                        avg_duration_ms: total_duration_ms / requests.max(1),
+                        max_duration_ms: row.get::<_, i64>(6)? as u64,
+                    })
+                },
+            )
+            .map_err(DatabaseError::Query)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Query)
+            .map_err(Into::into)
+    }
+}
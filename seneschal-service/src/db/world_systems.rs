@@ -0,0 +1,32 @@
+//! Game systems of FVTT worlds.
+
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use crate::error::{DatabaseError, ServiceResult};
+
+impl Database {
+    /// The FVTT system ID a world was last reported to run
+    pub fn get_world_system(&self, world_id: &str) -> ServiceResult<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        let system_id = conn
+            .query_row(
+                "SELECT system_id FROM world_systems WHERE world_id = ?1",
+                params![world_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DatabaseError::Query)?;
+        Ok(system_id)
+    }
+
+    pub fn set_world_system(&self, world_id: &str, system_id: &str) -> ServiceResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO world_systems (world_id, system_id, updated_at) VALUES (?1, ?2, ?3)",
+            params![world_id, system_id, chrono::Utc::now().to_rfc3339()],
+        )
+        .map_err(DatabaseError::Query)?;
+        Ok(())
+    }
+}
@@ -44,6 +44,19 @@ pub enum ServiceError {
     #[error("Image not found: {image_id}")]
     ImageNotFound { image_id: String },
 
+    #[error("Chunk not found: {chunk_id}")]
+    ChunkNotFound { chunk_id: String },
+
+    #[error(
+        "The source file of document {document_id} was deleted by the retention policy; upload the document again to use it"
+    )]
+    SourceExpired { document_id: String },
+
+    #[error(
+        "Document {document_id} is an encrypted PDF whose password isn't known since the service restarted; supply it with PUT /api/documents/{document_id}/pdf-password"
+    )]
+    PdfPasswordRequired { document_id: String },
+
     #[allow(dead_code)]
     #[error("Tool call not found: {tool_call_id}")]
     ToolCallNotFound { tool_call_id: String },
@@ -92,6 +105,9 @@ pub enum OllamaError {
         #[source]
         source: serde_json::Error,
     },
+
+    #[error("Model operation failed for {model}: {message}")]
+    ModelManagement { model: String, message: String },
 }
 
 /// Database errors
@@ -120,6 +136,9 @@ pub enum ProcessingError {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
 
+    #[error("Failed to render page {page}: {message}")]
+    PageRender { page: u32, message: String },
+
     #[error("Failed to read EPUB")]
     EpubRead(String),
 
@@ -129,6 +148,12 @@ pub enum ProcessingError {
     #[error("File too large: {size} bytes (max {max} bytes)")]
     FileTooLarge { size: u64, max: u64 },
 
+    #[error("File rejected: {reason}")]
+    FileRejected { reason: String },
+
+    #[error("Not enough disk space: {free} bytes free, {reserve} bytes must stay free")]
+    InsufficientStorage { free: u64, reserve: u64 },
+
     #[error("IO error")]
     Io(#[source] std::io::Error),
 
@@ -163,8 +188,11 @@ impl ServiceError {
         match self {
             ServiceError::DocumentNotFound { .. }
             | ServiceError::ImageNotFound { .. }
+            | ServiceError::ChunkNotFound { .. }
             | ServiceError::ToolCallNotFound { .. } => StatusCode::NOT_FOUND,
             ServiceError::InvalidRequest { .. } => StatusCode::BAD_REQUEST,
+            ServiceError::SourceExpired { .. } => StatusCode::GONE,
+            ServiceError::PdfPasswordRequired { .. } => StatusCode::CONFLICT,
             ServiceError::Ollama(OllamaError::ModelNotFound { .. }) => StatusCode::NOT_FOUND,
             ServiceError::Processing(ProcessingError::UnsupportedFormat { .. }) => {
                 StatusCode::UNSUPPORTED_MEDIA_TYPE
@@ -172,6 +200,12 @@ impl ServiceError {
             ServiceError::Processing(ProcessingError::FileTooLarge { .. }) => {
                 StatusCode::PAYLOAD_TOO_LARGE
             }
+            ServiceError::Processing(ProcessingError::FileRejected { .. }) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ServiceError::Processing(ProcessingError::InsufficientStorage { .. }) => {
+                StatusCode::INSUFFICIENT_STORAGE
+            }
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -180,20 +214,29 @@ impl ServiceError {
         match self {
             ServiceError::DocumentNotFound { .. } => "document_not_found",
             ServiceError::ImageNotFound { .. } => "image_not_found",
+            ServiceError::ChunkNotFound { .. } => "chunk_not_found",
+            ServiceError::SourceExpired { .. } => "source_expired",
+            ServiceError::PdfPasswordRequired { .. } => "pdf_password_required",
             ServiceError::ToolCallNotFound { .. } => "tool_call_not_found",
             ServiceError::Ollama(OllamaError::Connection { .. }) => "ollama_connection",
             ServiceError::Ollama(OllamaError::ModelNotFound { .. }) => "ollama_model_not_found",
             ServiceError::Ollama(OllamaError::Generation { .. }) => "ollama_generation",
             ServiceError::Ollama(OllamaError::InvalidResponse { .. }) => "ollama_invalid_response",
+            ServiceError::Ollama(OllamaError::ModelManagement { .. }) => "ollama_model_management",
             ServiceError::Database(_) => "database_error",
             ServiceError::Processing(ProcessingError::TextExtraction { .. }) => {
                 "text_extraction_error"
             }
+            ServiceError::Processing(ProcessingError::PageRender { .. }) => "page_render_error",
             ServiceError::Processing(ProcessingError::EpubRead(_)) => "epub_read_error",
             ServiceError::Processing(ProcessingError::UnsupportedFormat { .. }) => {
                 "unsupported_format"
             }
             ServiceError::Processing(ProcessingError::FileTooLarge { .. }) => "file_too_large",
+            ServiceError::Processing(ProcessingError::FileRejected { .. }) => "file_rejected",
+            ServiceError::Processing(ProcessingError::InsufficientStorage { .. }) => {
+                "insufficient_storage"
+            }
             ServiceError::Processing(ProcessingError::Io(_)) => "io_error",
             ServiceError::Processing(ProcessingError::Cancelled { .. }) => "processing_cancelled",
             ServiceError::Embedding(_) => "embedding_error",
@@ -210,6 +253,13 @@ impl ServiceError {
                 i18n.format(locale, "error-document-not-found", &[("id", document_id)])
             }
             ServiceError::Internal { .. } => i18n.get(locale, "error-internal", None),
+            // Say what was wrong with an upload rather than only that it failed
+            ServiceError::Processing(
+                e @ (ProcessingError::UnsupportedFormat { .. }
+                | ProcessingError::FileTooLarge { .. }
+                | ProcessingError::FileRejected { .. }
+                | ProcessingError::InsufficientStorage { .. }),
+            ) => e.to_string(),
             // For other errors, fall back to the technical message
             _ => self.to_string(),
         }
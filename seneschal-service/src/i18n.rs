@@ -111,6 +111,7 @@ doc-not-found = Document not found
 # Search
 search-no-results = No relevant documents found
 search-results-count = Found { $count } relevant results
+search-not-grounded = Not found in the library: nothing indexed answers "{ $query }" well enough (confidence { $confidence }). Tell the user their documents don't cover this rather than answering from general knowledge or inventing rules, and offer to search with other terms.
 
 # MCP
 mcp-connected = MCP client connected
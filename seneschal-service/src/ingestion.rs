@@ -6,9 +6,19 @@
 
 pub mod assets;
 pub mod epub;
+pub mod fvtt;
+pub mod glossary;
+pub mod grid;
 pub mod hash;
 pub mod markdown;
 pub mod pdf;
+pub mod preprocessing;
+pub mod read_aloud;
+pub mod statblocks;
+pub mod thumbnails;
+pub mod timeline;
+pub mod tokens;
+pub mod validation;
 
 use std::path::{Path, PathBuf};
 
@@ -17,13 +27,25 @@ use tracing::info;
 use uuid::Uuid;
 
 use crate::config::{EmbeddingsConfig, ImageExtractionConfig};
-use crate::db::{Chunk, DocumentImage};
+use crate::db::{Chunk, DocumentImage, GlossaryEntry};
 use crate::error::{ProcessingError, ServiceError, ServiceResult};
 use crate::tools::AccessLevel;
+use pdf::furniture::PageFurniture;
+use preprocessing::TextPreprocessing;
 
 /// Extracted document content
 pub struct ExtractedContent {
     pub sections: Vec<Section>,
+    /// Running headers, footers and page numbers stripped from PDF pages
+    pub removed_furniture: Vec<PageFurniture>,
+}
+
+/// Chunks of a processed document, with what was stripped from its text
+pub struct ProcessedDocument {
+    pub chunks: Vec<Chunk>,
+    pub removed_furniture: Vec<PageFurniture>,
+    /// Terms harvested from the document's index and glossary pages
+    pub glossary: Vec<GlossaryEntry>,
 }
 
 /// Document section
@@ -37,6 +59,7 @@ pub struct Section {
 pub struct IngestionService {
     chunk_size: usize,
     chunk_overlap: usize,
+    preprocessing: TextPreprocessing,
     data_dir: PathBuf,
     image_extraction_config: ImageExtractionConfig,
 }
@@ -50,14 +73,18 @@ impl IngestionService {
         Self {
             chunk_size: embeddings_config.chunk_size,
             chunk_overlap: embeddings_config.chunk_overlap,
+            preprocessing: TextPreprocessing::from(embeddings_config),
             data_dir,
             image_extraction_config,
         }
     }
 
-    /// Process a document with a pre-generated document ID, returning only chunks.
+    /// Process a document with a pre-generated document ID into chunks.
     ///
     /// Used for async document processing where the Document record is created first.
+    /// `pdf_options` and `pdf_password` only apply to PDFs, and
+    /// `max_archive_bytes` to EPUBs.
+    #[allow(clippy::too_many_arguments)]
     pub fn process_document_with_id(
         &self,
         path: &Path,
@@ -65,7 +92,10 @@ impl IngestionService {
         _title: &str,
         access_level: AccessLevel,
         tags: Vec<String>,
-    ) -> ServiceResult<Vec<Chunk>> {
+        pdf_options: pdf::PdfTextOptions,
+        pdf_password: Option<&str>,
+        max_archive_bytes: u64,
+    ) -> ServiceResult<ProcessedDocument> {
         let extension = path
             .extension()
             .and_then(|e| e.to_str())
@@ -74,9 +104,9 @@ impl IngestionService {
 
         info!(path = %path.display(), format = %extension, doc_id = %doc_id, "Processing document");
 
-        let content = match extension.as_str() {
-            "pdf" => self.extract_pdf_content(path)?,
-            "epub" => self.extract_epub_content(path)?,
+        let mut content = match extension.as_str() {
+            "pdf" => self.extract_pdf_content(path, pdf_options, pdf_password)?,
+            "epub" => self.extract_epub_content(path, max_archive_bytes)?,
             "md" | "markdown" => self.extract_markdown_content(path)?,
             "txt" | "text" => self.extract_text_content(path)?,
             _ => {
@@ -86,22 +116,34 @@ impl IngestionService {
             }
         };
 
-        // Create chunks
+        // Repair layout noise while line breaks are still there, then chunk
+        self.preprocessing.clean_sections(&mut content.sections);
+        let glossary = glossary::harvest_glossary(doc_id, &content.sections);
         let chunks = self.create_chunks(doc_id, &content, access_level, &tags);
 
         info!(
             doc_id = %doc_id,
             chunks = chunks.len(),
+            furniture_removed = content.removed_furniture.len(),
+            glossary_terms = glossary.len(),
             "Document processed successfully"
         );
 
-        Ok(chunks)
+        Ok(ProcessedDocument {
+            chunks,
+            removed_furniture: content.removed_furniture,
+            glossary,
+        })
     }
 
     /// Extract content from PDF.
-    fn extract_pdf_content(&self, path: &Path) -> ServiceResult<ExtractedContent> {
-        let sections = pdf::extract_pdf(path)?;
-        Ok(ExtractedContent { sections })
+    fn extract_pdf_content(
+        &self,
+        path: &Path,
+        options: pdf::PdfTextOptions,
+        password: Option<&str>,
+    ) -> ServiceResult<ExtractedContent> {
+        pdf::extract_pdf(path, options, password)
     }
 
     /// Extract images from a PDF document and save them as WebP files.
@@ -111,6 +153,7 @@ impl IngestionService {
         &self,
         path: &Path,
         document_id: &str,
+        password: Option<&str>,
     ) -> ServiceResult<Vec<DocumentImage>> {
         let images_dir = self.data_dir.join("images").join(document_id);
         pdf::extract_pdf_images(
@@ -118,6 +161,7 @@ impl IngestionService {
             document_id,
             &images_dir,
             &self.image_extraction_config,
+            password,
         )
     }
 
@@ -128,8 +172,20 @@ impl IngestionService {
         &self,
         path: &Path,
         page_numbers: &[i32],
+        password: Option<&str>,
     ) -> ServiceResult<std::collections::HashMap<i32, String>> {
-        pdf::extract_pdf_page_text(path, page_numbers)
+        pdf::extract_pdf_page_text(path, page_numbers, password)
+    }
+
+    /// Find read-aloud candidates in a PDF from its page layout.
+    ///
+    /// Returns (page number, passage) pairs.
+    pub fn extract_pdf_read_aloud(
+        &self,
+        path: &Path,
+        password: Option<&str>,
+    ) -> ServiceResult<Vec<(i32, String)>> {
+        pdf::extract_pdf_read_aloud(path, password)
     }
 
     /// Get the path where an image should be copied to in FVTT assets.
@@ -146,21 +202,34 @@ impl IngestionService {
     }
 
     /// Extract content from EPUB.
-    fn extract_epub_content(&self, path: &Path) -> ServiceResult<ExtractedContent> {
-        let sections = epub::extract_epub(path)?;
-        Ok(ExtractedContent { sections })
+    fn extract_epub_content(
+        &self,
+        path: &Path,
+        max_archive_bytes: u64,
+    ) -> ServiceResult<ExtractedContent> {
+        let sections = epub::extract_epub(path, max_archive_bytes)?;
+        Ok(ExtractedContent {
+            sections,
+            removed_furniture: Vec::new(),
+        })
     }
 
     /// Extract content from Markdown.
     fn extract_markdown_content(&self, path: &Path) -> ServiceResult<ExtractedContent> {
         let sections = markdown::extract_markdown(path)?;
-        Ok(ExtractedContent { sections })
+        Ok(ExtractedContent {
+            sections,
+            removed_furniture: Vec::new(),
+        })
     }
 
     /// Extract content from plain text.
     fn extract_text_content(&self, path: &Path) -> ServiceResult<ExtractedContent> {
         let sections = markdown::extract_text(path)?;
-        Ok(ExtractedContent { sections })
+        Ok(ExtractedContent {
+            sections,
+            removed_furniture: Vec::new(),
+        })
     }
 
     /// Create chunks from extracted content.
@@ -237,6 +306,11 @@ mod tests {
         let service = IngestionService {
             chunk_size: 10,
             chunk_overlap: 2,
+            preprocessing: TextPreprocessing {
+                normalize_unicode: false,
+                repair_hyphenation: false,
+                lowercase: false,
+            },
             data_dir: PathBuf::from("/tmp"),
             image_extraction_config: ImageExtractionConfig::default(),
         };
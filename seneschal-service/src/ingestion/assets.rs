@@ -40,6 +40,18 @@ pub fn fvtt_image_path(
     ))
 }
 
+/// Generate the FVTT-relative path for a token cut from an image.
+///
+/// Returns a path like `seneschal/{title}/tokens/page_{num}_token_{index}.webp`.
+pub fn fvtt_token_path(document_title: &str, page_number: i32, image_index: i32) -> PathBuf {
+    PathBuf::from(format!(
+        "seneschal/{}/tokens/page_{}_token_{}.webp",
+        sanitize_filename(document_title),
+        page_number,
+        image_index
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
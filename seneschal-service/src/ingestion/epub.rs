@@ -1,5 +1,6 @@
 //! EPUB document extraction.
 
+use std::io::Cursor;
 use std::path::Path;
 
 use tracing::debug;
@@ -7,11 +8,17 @@ use tracing::debug;
 use crate::error::{ProcessingError, ServiceError, ServiceResult};
 
 use super::Section;
+use super::validation::check_epub;
 
 /// Extract content from an EPUB file.
-pub fn extract_epub(path: &Path) -> ServiceResult<Vec<Section>> {
-    let mut archive =
-        epub::doc::EpubDoc::new(path).map_err(|e| ProcessingError::EpubRead(e.to_string()))?;
+///
+/// The archive is checked against `max_uncompressed_bytes` first, since files
+/// can reach ingestion without going through upload validation.
+pub fn extract_epub(path: &Path, max_uncompressed_bytes: u64) -> ServiceResult<Vec<Section>> {
+    let content = std::fs::read(path).map_err(ProcessingError::Io)?;
+    check_epub(&content, max_uncompressed_bytes)?;
+    let mut archive = epub::doc::EpubDoc::from_reader(Cursor::new(content))
+        .map_err(|e| ProcessingError::EpubRead(e.to_string()))?;
 
     let mut sections = Vec::new();
     let mut chapter_index = 0;
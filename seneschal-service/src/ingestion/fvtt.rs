@@ -0,0 +1,208 @@
+//! Foundry VTT journal entry normalization.
+//!
+//! Journal entries arrive from the FVTT module as HTML pages. They are converted
+//! to Markdown (one `#` section per page) so they flow through the regular
+//! Markdown ingestion pipeline alongside uploaded documents.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::epub::strip_html_tags;
+use crate::tools::AccessLevel;
+
+/// FVTT secret blocks, only revealed to GMs
+static SECRET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<section[^>]*class="[^"]*\bsecret\b[^"]*"[^>]*>.*?</section>"#).unwrap()
+});
+
+/// A single text page of a journal entry, as sent by the FVTT module
+#[derive(Debug, Clone, Deserialize)]
+pub struct JournalPage {
+    pub name: String,
+    /// Raw page HTML (`page.text.content` in FVTT)
+    #[serde(default)]
+    pub html: String,
+    /// Minimum role that can read the page, from its own ownership (GM only if absent)
+    #[serde(default)]
+    pub access_level: Option<u8>,
+}
+
+impl JournalPage {
+    /// Page text as indexed, with secret blocks removed
+    fn text(&self) -> String {
+        html_to_text(&strip_secrets(&self.html))
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        self.access_level
+            .map(AccessLevel::from_u8)
+            .unwrap_or(AccessLevel::GmOnly)
+    }
+}
+
+/// Access level for a journal indexed as one document: the most restrictive
+/// of the pages with text, so no page becomes readable by more users than in FVTT.
+pub fn journal_access_level(pages: &[JournalPage]) -> AccessLevel {
+    pages
+        .iter()
+        .filter(|page| !page.text().is_empty())
+        .map(JournalPage::access_level)
+        .max()
+        .unwrap_or(AccessLevel::GmOnly)
+}
+
+/// Convert a journal entry's pages into Markdown with one section per page.
+pub fn journal_to_markdown(journal_name: &str, pages: &[JournalPage]) -> String {
+    let mut output = String::new();
+
+    for page in pages {
+        let text = page.text();
+        if text.is_empty() {
+            continue;
+        }
+
+        let title = if page.name.trim().is_empty() {
+            journal_name
+        } else {
+            page.name.trim()
+        };
+        output.push_str(&format!("# {}\n\n{}\n\n", title, text));
+    }
+
+    output
+}
+
+/// Remove FVTT secret blocks from page HTML
+pub(crate) fn strip_secrets(html: &str) -> String {
+    SECRET_RE.replace_all(html, "").into_owned()
+}
+
+/// Convert journal page HTML to plain text, preserving paragraph breaks.
+pub(crate) fn html_to_text(html: &str) -> String {
+    let html = resolve_enrichers(html);
+
+    // Turn block-level boundaries into line breaks before stripping tags
+    let mut with_breaks = html;
+    for tag in [
+        "<br>",
+        "<br/>",
+        "<br />",
+        "</p>",
+        "</div>",
+        "</li>",
+        "</tr>",
+        "</h1>",
+        "</h2>",
+        "</h3>",
+        "</h4>",
+        "</h5>",
+        "</h6>",
+        "</blockquote>",
+    ] {
+        with_breaks = with_breaks.replace(tag, &format!("{}\n", tag));
+    }
+
+    with_breaks
+        .lines()
+        .map(|line| strip_html_tags(line).trim().to_string())
+        // A leading '#' would be read as a section header by the Markdown parser
+        .map(|line| line.trim_start_matches('#').trim_start().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace FVTT enricher syntax with its human-readable text.
+///
+/// - `@UUID[JournalEntry.abc]{Label}` and other `@Type[...]{Label}` links become `Label`
+/// - Links without a label keep the bracketed target
+/// - Inline rolls like `[[/r 2d6]]` become `2d6`
+fn resolve_enrichers(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(pos) = rest.find(['@', '[']) {
+        result.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if let Some(inner) = tail.strip_prefix("[[")
+            && let Some(end) = inner.find("]]")
+        {
+            let roll = inner[..end].trim();
+            let formula = roll
+                .strip_prefix("/r ")
+                .or_else(|| roll.strip_prefix("/roll "))
+                .unwrap_or(roll);
+            result.push_str(formula.trim());
+            rest = &inner[end + 2..];
+            continue;
+        }
+
+        if tail.starts_with('@')
+            && let Some(open) = tail.find('[')
+            && tail[1..open].chars().all(|c| c.is_ascii_alphabetic())
+            && open > 1
+            && let Some(close) = tail[open..].find(']')
+        {
+            let target = &tail[open + 1..open + close];
+            let after = &tail[open + close + 1..];
+            if let Some(label_body) = after.strip_prefix('{')
+                && let Some(label_end) = label_body.find('}')
+            {
+                result.push_str(&label_body[..label_end]);
+                rest = &label_body[label_end + 1..];
+            } else {
+                result.push_str(target);
+                rest = after;
+            }
+            continue;
+        }
+
+        let ch_len = tail.chars().next().map(char::len_utf8).unwrap_or(1);
+        result.push_str(&tail[..ch_len]);
+        rest = &tail[ch_len..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_to_markdown() {
+        let pages = vec![
+            JournalPage {
+                name: "Overview".to_string(),
+                html: "<h2>The Duke</h2><p>Rules from @UUID[Actor.abc123]{Castle Varn}.</p><section class=\"secret\" id=\"secret-1\"><p>He is a vampire.</p></section><p>Tax is [[/r 2d6]] Cr.</p>".to_string(),
+                access_level: Some(1),
+            },
+            JournalPage {
+                name: "Empty".to_string(),
+                html: "<p></p>".to_string(),
+                access_level: None,
+            },
+        ];
+
+        let markdown = journal_to_markdown("Varn", &pages);
+        assert_eq!(
+            markdown,
+            "# Overview\n\nThe Duke\nRules from Castle Varn.\nTax is 2d6 Cr.\n\n"
+        );
+        // The empty GM-only page is left out, so it doesn't restrict the journal
+        assert_eq!(journal_access_level(&pages), AccessLevel::Player);
+        assert_eq!(journal_access_level(&pages[1..]), AccessLevel::GmOnly);
+    }
+
+    #[test]
+    fn test_resolve_enrichers_without_label() {
+        assert_eq!(
+            resolve_enrichers("See @Compendium[world.lore.xyz] and [link]"),
+            "See world.lore.xyz and [link]"
+        );
+    }
+}
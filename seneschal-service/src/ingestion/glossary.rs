@@ -0,0 +1,268 @@
+//! Index and glossary harvesting from rulebook back matter.
+//!
+//! A book's index maps terms to the pages that cover them, which beats a
+//! semantic search when the model already knows the term. Index pages are
+//! recognized by most of their lines ending in page references; glossary
+//! pages (by bookmark or heading) contribute "Term: definition" lines.
+//!
+//! Printed page numbers rarely match PDF page numbers (covers and front
+//! matter shift them), so the offset between the two is calibrated by
+//! checking which offset puts the most index terms on their pages.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::Section;
+use crate::db::GlossaryEntry;
+
+/// A term followed by a list of page numbers and ranges ("Starports, 45, 102-104")
+static INDEX_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<term>\p{L}.{0,78}?)[\s.,:…·]+(?P<pages>\d{1,4}(?:\s*[-–]\s*\d{1,4})?(?:\s*,\s*\d{1,4}(?:\s*[-–]\s*\d{1,4})?)*)$",
+    )
+    .unwrap()
+});
+
+/// One page reference or range within an index line
+static PAGE_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d{1,4})(?:\s*[-–]\s*(\d{1,4}))?").unwrap());
+
+/// A glossary line: a short term, a colon or dash, then its definition
+static GLOSSARY_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<term>\p{L}[\p{L}\p{N} '’/()-]{0,60}?)\s*[:–—]\s+(?P<definition>\S.{2,})$")
+        .unwrap()
+});
+
+/// Pages with fewer lines are never treated as index pages
+const MIN_INDEX_LINES: usize = 8;
+
+/// Fraction of a page's lines that must be index entries...
+const INDEX_LINE_FRACTION: f64 = 0.5;
+
+/// ...or on a page whose section is titled "Index"
+const TITLED_INDEX_LINE_FRACTION: f64 = 0.3;
+
+/// Ranges longer than this are kept as their first page
+const MAX_RANGE_PAGES: i32 = 20;
+
+/// Printed-to-PDF page offsets tried during calibration
+const MAX_PAGE_OFFSET: i32 = 20;
+
+/// Index entries sampled when calibrating the page offset
+const CALIBRATION_SAMPLE: usize = 200;
+
+/// Harvest index and glossary entries from a document's sections.
+///
+/// Index pages are mapped to PDF page numbers; sections without page
+/// numbers only contribute glossary definitions.
+pub fn harvest_glossary(document_id: &str, sections: &[Section]) -> Vec<GlossaryEntry> {
+    let mut index: Vec<(String, Vec<i32>, Option<i32>)> = Vec::new();
+    let mut definitions: Vec<(String, String, Option<i32>)> = Vec::new();
+
+    for section in sections {
+        let title = section.title.as_deref().unwrap_or("").to_lowercase();
+        let lines: Vec<&str> = section
+            .content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let entries: Vec<(String, Vec<i32>)> = lines
+            .iter()
+            .filter_map(|line| parse_index_line(line))
+            .collect();
+        let fraction = if title.contains("index") {
+            TITLED_INDEX_LINE_FRACTION
+        } else {
+            INDEX_LINE_FRACTION
+        };
+        if lines.len() >= MIN_INDEX_LINES && entries.len() as f64 >= lines.len() as f64 * fraction {
+            index.extend(
+                entries
+                    .into_iter()
+                    .map(|(term, pages)| (term, pages, section.page_number)),
+            );
+            continue;
+        }
+
+        let is_glossary = title.contains("glossary")
+            || lines
+                .first()
+                .is_some_and(|line| line.eq_ignore_ascii_case("glossary"));
+        if is_glossary {
+            definitions.extend(lines.iter().filter_map(|line| {
+                let captures = GLOSSARY_LINE.captures(line)?;
+                Some((
+                    captures["term"].trim().to_string(),
+                    captures["definition"].trim().to_string(),
+                    section.page_number,
+                ))
+            }));
+        }
+    }
+
+    let offset = calibrate_page_offset(&index, sections);
+
+    // Merge repeated terms, keeping the first spelling seen
+    let mut merged: Vec<GlossaryEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (term, pages, source_page) in index {
+        let pages = pages.into_iter().map(|page| page + offset);
+        match positions.get(&term.to_lowercase()) {
+            Some(&i) => merged[i].pages.extend(pages),
+            None => {
+                positions.insert(term.to_lowercase(), merged.len());
+                merged.push(GlossaryEntry {
+                    document_id: document_id.to_string(),
+                    term,
+                    pages: pages.collect(),
+                    definition: None,
+                    source_page,
+                });
+            }
+        }
+    }
+    for (term, definition, source_page) in definitions {
+        match positions.get(&term.to_lowercase()) {
+            Some(&i) if merged[i].definition.is_none() => merged[i].definition = Some(definition),
+            Some(_) => {}
+            None => {
+                positions.insert(term.to_lowercase(), merged.len());
+                merged.push(GlossaryEntry {
+                    document_id: document_id.to_string(),
+                    term,
+                    pages: source_page.into_iter().collect(),
+                    definition: Some(definition),
+                    source_page,
+                });
+            }
+        }
+    }
+
+    for entry in &mut merged {
+        entry.pages.sort_unstable();
+        entry.pages.dedup();
+    }
+    merged
+}
+
+/// Split an index line into its term and printed page numbers
+fn parse_index_line(line: &str) -> Option<(String, Vec<i32>)> {
+    let captures = INDEX_LINE.captures(line)?;
+    let term = captures["term"]
+        .trim()
+        .trim_end_matches([',', '.'])
+        .to_string();
+    if term.is_empty() {
+        return None;
+    }
+
+    let pages = PAGE_REF
+        .captures_iter(&captures["pages"])
+        .flat_map(|page_ref| {
+            let start: i32 = page_ref[1].parse().unwrap_or(0);
+            let end = page_ref
+                .get(2)
+                .and_then(|end| end.as_str().parse::<i32>().ok())
+                .filter(|end| *end >= start && end - start <= MAX_RANGE_PAGES)
+                .unwrap_or(start);
+            start..=end
+        })
+        .filter(|page| *page > 0)
+        .collect::<Vec<_>>();
+
+    (!pages.is_empty()).then_some((term, pages))
+}
+
+/// The offset from printed to PDF page numbers that puts the most sampled
+/// terms on their first listed page (0 when nothing matches)
+fn calibrate_page_offset(index: &[(String, Vec<i32>, Option<i32>)], sections: &[Section]) -> i32 {
+    let mut pages: HashMap<i32, String> = HashMap::new();
+    for section in sections {
+        if let Some(page) = section.page_number {
+            let text = pages.entry(page).or_default();
+            text.push('\n');
+            text.push_str(&section.content.to_lowercase());
+        }
+    }
+    if pages.is_empty() {
+        return 0;
+    }
+
+    let sample: Vec<(String, i32)> = index
+        .iter()
+        .filter_map(|(term, refs, _)| Some((term.to_lowercase(), *refs.first()?)))
+        .take(CALIBRATION_SAMPLE)
+        .collect();
+
+    let (offset, hits) = (-MAX_PAGE_OFFSET..=MAX_PAGE_OFFSET)
+        .map(|offset| {
+            let hits = sample
+                .iter()
+                .filter(|(term, page)| {
+                    pages
+                        .get(&(page + offset))
+                        .is_some_and(|text| text.contains(term.as_str()))
+                })
+                .count();
+            (offset, hits)
+        })
+        .max_by(|(a_offset, a_hits), (b_offset, b_hits)| {
+            a_hits.cmp(b_hits).then(b_offset.abs().cmp(&a_offset.abs()))
+        })
+        .unwrap_or((0, 0));
+
+    if hits == 0 { 0 } else { offset }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(number: i32, title: Option<&str>, content: &str) -> Section {
+        Section {
+            title: title.map(String::from),
+            content: content.to_string(),
+            page_number: Some(number),
+        }
+    }
+
+    #[test]
+    fn test_harvest_glossary() {
+        // Printed page numbers run two behind the PDF's
+        let sections = vec![
+            page(5, None, "Starports\nA Class A starport has shipyards."),
+            page(6, None, "Jump Drives\nA jump drive needs fuel."),
+            page(7, None, "Refined fuel is available at starports."),
+            page(
+                40,
+                Some("Index"),
+                "INDEX\nJump drive, 4\nStarports 3, 5\nFuel .......... 5\nRefined fuel, 5\n\
+                 Ship design, 30-32\nWeapons, 12\nVacc suits, 14\nZero-G, 20",
+            ),
+            page(
+                41,
+                Some("Glossary"),
+                "Glossary\nJump drive: Faster-than-light drive.\nThe end of the book.",
+            ),
+        ];
+
+        let entries = harvest_glossary("doc-1", &sections);
+        let lookup = |term: &str| entries.iter().find(|e| e.term == term).unwrap();
+
+        assert_eq!(entries.len(), 8);
+        assert_eq!(lookup("Starports").pages, vec![5, 7]);
+        assert_eq!(lookup("Ship design").pages, vec![32, 33, 34]);
+        assert_eq!(lookup("Fuel").pages, vec![7]);
+        let jump = lookup("Jump drive");
+        assert_eq!(jump.pages, vec![6]);
+        assert_eq!(jump.definition.as_deref(), Some("Faster-than-light drive."));
+        assert_eq!(jump.source_page, Some(40));
+
+        // Ordinary prose isn't an index
+        assert!(harvest_glossary("doc-1", &sections[..3]).is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+//! Square grid detection for deck plans and battle maps.
+//!
+//! Grid lines show up as regularly spaced spikes in the image's edge
+//! strength, summed per column (vertical lines) and per row (horizontal
+//! lines). The spacing is the strongest repeat in each profile's
+//! autocorrelation; a square grid repeats at the same spacing on both axes.
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use image::GrayImage;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::error::ProcessingError;
+
+/// Captions that describe a gridded map
+static MAP_CAPTION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(deck ?plans?|floor ?plans?|battle ?maps?|maps?|layouts?|grid(?:ded)?|schematics?)\b")
+        .unwrap()
+});
+
+/// Grid squares smaller than this many pixels aren't looked for
+const MIN_PITCH: usize = 10;
+
+/// A grid must repeat at least this many times across the image
+const MIN_CELLS: usize = 3;
+
+/// Autocorrelation a repeat needs to count as a grid
+const MIN_CONFIDENCE: f32 = 0.25;
+
+/// Shorter repeats within this fraction of the strongest are preferred
+/// (the strongest is sometimes a multiple of the real spacing)
+const FUNDAMENTAL_FRACTION: f32 = 0.8;
+
+/// Spacings on the two axes may differ by this fraction and still be square
+const MAX_PITCH_MISMATCH: f32 = 0.04;
+
+/// A square grid found in an image, in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DetectedGrid {
+    /// Width of one grid square
+    pub pitch: f32,
+    /// Position of the first vertical and horizontal grid line
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Whole squares across and down from the first lines
+    pub columns: u32,
+    pub rows: u32,
+    /// Autocorrelation of the weaker axis at the grid spacing (0-1)
+    pub confidence: f32,
+}
+
+/// Whether a caption describes a map that may carry a grid
+pub fn is_map_caption(description: &str) -> bool {
+    MAP_CAPTION.is_match(description)
+}
+
+/// Detect the grid of an image file
+pub fn detect_grid_in_file(path: &Path) -> Result<Option<DetectedGrid>, ProcessingError> {
+    let image = image::open(path).map_err(|e| {
+        ProcessingError::Io(std::io::Error::other(format!(
+            "Failed to decode {}: {}",
+            path.display(),
+            e
+        )))
+    })?;
+    Ok(detect_grid(&image.to_luma8()))
+}
+
+/// Detect a square grid, if the image has one
+pub fn detect_grid(image: &GrayImage) -> Option<DetectedGrid> {
+    let (width, height) = image.dimensions();
+    let columns = edge_profile(image, true);
+    let rows = edge_profile(image, false);
+
+    let (pitch_x, confidence_x) = dominant_period(&columns)?;
+    let (pitch_y, confidence_y) = dominant_period(&rows)?;
+    if (pitch_x - pitch_y).abs() > pitch_x.max(pitch_y) * MAX_PITCH_MISMATCH {
+        return None;
+    }
+    let pitch = (pitch_x + pitch_y) / 2.0;
+
+    let offset_x = phase(&columns, pitch);
+    let offset_y = phase(&rows, pitch);
+    Some(DetectedGrid {
+        pitch,
+        offset_x,
+        offset_y,
+        columns: ((width as f32 - offset_x) / pitch).floor() as u32,
+        rows: ((height as f32 - offset_y) / pitch).floor() as u32,
+        confidence: confidence_x.min(confidence_y),
+    })
+}
+
+/// Summed brightness change across each column (`vertical`) or row
+fn edge_profile(image: &GrayImage, vertical: bool) -> Vec<f32> {
+    let (width, height) = image.dimensions();
+    let (len, across) = if vertical {
+        (width, height)
+    } else {
+        (height, width)
+    };
+
+    let mut profile = vec![0.0; len as usize];
+    for i in 1..len {
+        profile[i as usize] = (0..across)
+            .map(|j| {
+                let (a, b) = if vertical {
+                    (image.get_pixel(i, j)[0], image.get_pixel(i - 1, j)[0])
+                } else {
+                    (image.get_pixel(j, i)[0], image.get_pixel(j, i - 1)[0])
+                };
+                a.abs_diff(b) as f32
+            })
+            .sum();
+    }
+    profile
+}
+
+/// The spacing of a profile's strongest repeat, refined to a fraction of a
+/// pixel, with its normalized autocorrelation
+fn dominant_period(profile: &[f32]) -> Option<(f32, f32)> {
+    let n = profile.len();
+    let max_lag = n / MIN_CELLS;
+    if max_lag <= MIN_PITCH {
+        return None;
+    }
+
+    let mean = profile.iter().sum::<f32>() / n as f32;
+    let centered: Vec<f32> = profile.iter().map(|v| v - mean).collect();
+    let energy: f32 = centered.iter().map(|v| v * v).sum();
+    if energy <= f32::EPSILON {
+        return None;
+    }
+
+    // Normalized so a perfect repeat scores 1 whatever the overlap length
+    let correlation: Vec<f32> = (0..=max_lag + 1)
+        .map(|lag| {
+            let sum: f32 = centered[..n - lag]
+                .iter()
+                .zip(&centered[lag..])
+                .map(|(a, b)| a * b)
+                .sum();
+            sum / energy * n as f32 / (n - lag) as f32
+        })
+        .collect();
+
+    let peaks: Vec<usize> = (MIN_PITCH..=max_lag)
+        .filter(|&lag| {
+            correlation[lag] >= correlation[lag - 1] && correlation[lag] >= correlation[lag + 1]
+        })
+        .collect();
+    let strongest = peaks
+        .iter()
+        .map(|&lag| correlation[lag])
+        .fold(f32::MIN, f32::max);
+    if strongest < MIN_CONFIDENCE {
+        return None;
+    }
+    let lag = *peaks
+        .iter()
+        .find(|&&lag| correlation[lag] >= strongest * FUNDAMENTAL_FRACTION)?;
+
+    // Parabola through the peak and its neighbours
+    let (before, at, after) = (correlation[lag - 1], correlation[lag], correlation[lag + 1]);
+    let curvature = before - 2.0 * at + after;
+    let shift = if curvature.abs() > f32::EPSILON {
+        ((before - after) / (2.0 * curvature)).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+    Some((lag as f32 + shift, at))
+}
+
+/// The offset of the first grid line: the phase whose lines carry the most edge strength
+fn phase(profile: &[f32], pitch: f32) -> f32 {
+    let steps = pitch.floor() as usize;
+    (0..steps)
+        .map(|offset| {
+            let strength: f32 = (0..)
+                .map(|k| (offset as f32 + k as f32 * pitch).round() as usize)
+                .take_while(|&i| i < profile.len())
+                .map(|i| profile[i])
+                .sum();
+            (offset, strength)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(offset, _)| offset as f32)
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    #[test]
+    fn test_detect_grid() {
+        // Dark two-pixel lines every 25px, starting 7px in
+        let mut plan = GrayImage::from_pixel(400, 300, Luma([235]));
+        for (x, y, pixel) in plan.enumerate_pixels_mut() {
+            if (x + 25 - 7) % 25 < 2 || (y + 25 - 7) % 25 < 2 {
+                *pixel = Luma([30]);
+            }
+        }
+
+        let grid = detect_grid(&plan).unwrap();
+        assert!((grid.pitch - 25.0).abs() < 0.5, "pitch {}", grid.pitch);
+        assert!((grid.offset_x - 7.0).abs() <= 2.0);
+        assert!((grid.offset_y - 7.0).abs() <= 2.0);
+        assert_eq!((grid.columns, grid.rows), (15, 11));
+
+        // A single box isn't a grid
+        let mut room = GrayImage::from_pixel(400, 300, Luma([235]));
+        for (x, y, pixel) in room.enumerate_pixels_mut() {
+            if (100..300).contains(&x) && (y == 50 || y == 250) {
+                *pixel = Luma([30]);
+            }
+        }
+        assert!(detect_grid(&room).is_none());
+
+        assert!(is_map_caption("Deck plan of a Type-S Scout/Courier"));
+        assert!(!is_map_caption("Portrait of a Vargr corsair"));
+    }
+}
@@ -34,6 +34,32 @@ pub fn compute_content_hash(content: &[u8]) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Compute SHA-256 hash of a byte slice like [`compute_content_hash`],
+/// calling `on_progress` with the bytes hashed so far after each block.
+pub fn compute_content_hash_with_progress(
+    content: &[u8],
+    block_size: usize,
+    mut on_progress: impl FnMut(usize),
+) -> String {
+    let mut hasher = Sha256::new();
+    let mut hashed = 0;
+    for block in content.chunks(block_size.max(1)) {
+        hasher.update(block);
+        hashed += block.len();
+        on_progress(hashed);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Compute a hash identifying a chunk's text for cross-document deduplication.
+///
+/// Whitespace is collapsed before hashing so that the same paragraph extracted
+/// with different line wrapping (e.g. core book vs. SRD extract) hashes equally.
+pub fn compute_chunk_hash(content: &str) -> String {
+    let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    compute_content_hash(normalized.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +101,23 @@ mod tests {
 
         assert_eq!(file_hash, content_hash);
     }
+
+    #[test]
+    fn test_content_hash_with_progress() {
+        let mut reported = Vec::new();
+        let hash = compute_content_hash_with_progress(b"hello world", 4, |n| reported.push(n));
+        assert_eq!(hash, compute_content_hash(b"hello world"));
+        assert_eq!(reported, [4, 8, 11]);
+    }
+
+    #[test]
+    fn test_compute_chunk_hash_ignores_whitespace_layout() {
+        let wrapped = compute_chunk_hash("The ship jumps\nto the next   system.\n");
+        let flowed = compute_chunk_hash("The ship jumps to the next system.");
+        assert_eq!(wrapped, flowed);
+        assert_ne!(
+            flowed,
+            compute_chunk_hash("The ship jumps to another system.")
+        );
+    }
 }
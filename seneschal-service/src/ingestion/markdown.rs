@@ -1,5 +1,7 @@
 //! Markdown document extraction.
 
+pub mod obsidian;
+
 use std::path::Path;
 
 use crate::error::{ProcessingError, ServiceResult};
@@ -7,9 +9,13 @@ use crate::error::{ProcessingError, ServiceResult};
 use super::Section;
 
 /// Extract content from a Markdown file.
+///
+/// Frontmatter is dropped and Obsidian wiki links are rendered as their
+/// display text.
 pub fn extract_markdown(path: &Path) -> ServiceResult<Vec<Section>> {
     let content = std::fs::read_to_string(path).map_err(ProcessingError::Io)?;
-    Ok(parse_markdown_sections(&content))
+    let (_, body) = obsidian::split_frontmatter(&content);
+    Ok(parse_markdown_sections(&obsidian::render_wiki_links(body)))
 }
 
 /// Parse markdown into sections based on headers.
@@ -0,0 +1,163 @@
+//! Obsidian note conventions: YAML frontmatter and `[[wiki links]]`.
+
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+/// `[[target]]`, `[[target|alias]]` and `![[embed]]`
+static WIKI_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(!?)\[\[([^\[\]|]+)(?:\|([^\[\]]*))?\]\]").unwrap());
+
+/// Split leading YAML frontmatter (between `---` lines) from the note body.
+pub fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let text = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some(after_open) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in after_open.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return (
+                Some(&after_open[..offset]),
+                &after_open[offset + line.len()..],
+            );
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// Tags from a frontmatter `tags` (or `tag`) key, as an inline list, a block
+/// list or a comma/space separated string. Leading `#` is dropped.
+pub fn frontmatter_tags(frontmatter: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut lines = frontmatter.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !matches!(key.trim(), "tags" | "tag") {
+            continue;
+        }
+
+        let value = value.trim();
+        if value.is_empty() {
+            while let Some(item) = lines.peek().and_then(|l| l.trim_start().strip_prefix('-')) {
+                push_tags(item, &mut tags);
+                lines.next();
+            }
+        } else {
+            let inline = value
+                .strip_prefix('[')
+                .and_then(|v| v.strip_suffix(']'))
+                .unwrap_or(value);
+            push_tags(inline, &mut tags);
+        }
+    }
+    tags
+}
+
+fn push_tags(value: &str, tags: &mut Vec<String>) {
+    for tag in value.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = tag
+            .trim_matches(|c| c == '"' || c == '\'')
+            .trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+}
+
+/// Notes a body links to, in order of first appearance.
+///
+/// Heading and block anchors are dropped (`[[Regina#Starport]]` links to
+/// `Regina`), as are embeds of non-note files such as images.
+pub fn wiki_link_targets(body: &str) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for captures in WIKI_LINK_RE.captures_iter(body) {
+        let Some(target) = link_target(&captures[2]) else {
+            continue;
+        };
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    targets
+}
+
+/// Replace wiki links with the text Obsidian displays for them, so chunks
+/// read naturally. Embeds of non-note files are removed.
+pub fn render_wiki_links(body: &str) -> String {
+    WIKI_LINK_RE
+        .replace_all(body, |captures: &Captures| {
+            if let Some(alias) = captures.get(3) {
+                return alias.as_str().trim().to_string();
+            }
+            let target = captures[2].trim();
+            if !captures[1].is_empty() && link_target(target).is_none() {
+                return String::new();
+            }
+            match target.strip_prefix('#') {
+                Some(anchor) => anchor.to_string(),
+                None => target.replace('#', " > "),
+            }
+        })
+        .into_owned()
+}
+
+/// The note a link points at, or `None` for same-note anchors and non-note files
+fn link_target(raw: &str) -> Option<String> {
+    let note = raw.split(['#', '^']).next().unwrap_or_default().trim();
+    let note = note.strip_suffix(".md").unwrap_or(note);
+    let is_file = note
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()));
+    if note.is_empty() || is_file {
+        return None;
+    }
+    Some(note.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obsidian_note() {
+        let note = "---\ntitle: Regina\ntags: [subsector, \"#world\"]\naliases:\n  - Regina Highport\n---\n\
+            Capital of the [[Regina Subsector|subsector]]. See [[Captain Reyes]], \
+            [[Regina#Starport]] and [[#Law]].\n![[regina-map.png]]\n";
+
+        let (frontmatter, body) = split_frontmatter(note);
+        let frontmatter = frontmatter.unwrap();
+        assert_eq!(frontmatter_tags(frontmatter), vec!["subsector", "world"]);
+        assert_eq!(
+            frontmatter_tags("tags:\n  - npc\n  - \"#patron\"\nstatus: draft\n"),
+            vec!["npc", "patron"]
+        );
+        assert!(body.starts_with("Capital of"));
+
+        assert_eq!(
+            wiki_link_targets(body),
+            vec!["Regina Subsector", "Captain Reyes", "Regina"]
+        );
+        assert_eq!(
+            render_wiki_links(body),
+            "Capital of the subsector. See Captain Reyes, Regina > Starport and Law.\n\n"
+        );
+
+        assert_eq!(
+            split_frontmatter("# No frontmatter\n---\n"),
+            (None, "# No frontmatter\n---\n")
+        );
+    }
+}
@@ -1,10 +1,17 @@
 //! PDF document processing.
 //!
 //! This module handles PDF document processing including:
-//! - Text extraction with watermark filtering and bookmark-based sections
+//! - Text extraction in column-aware reading order, with watermark and page
+//!   furniture filtering and bookmark-based sections
 //! - Image extraction with layer compositing and transformation handling
+//! - Whole-page rendering
+//! - Read-aloud candidates from italic and indented lines
 
+pub mod furniture;
 pub mod images;
+pub mod layout;
+pub mod page_render;
+pub mod read_aloud;
 pub mod text;
 
 use pdfium_render::prelude::*;
@@ -13,7 +20,8 @@ use crate::error::ProcessingError;
 
 // Re-export commonly used items
 pub use images::extract_pdf_images;
-pub use text::{extract_pdf, extract_pdf_page_text};
+pub use read_aloud::extract_pdf_read_aloud;
+pub use text::{PdfTextOptions, extract_pdf, extract_pdf_page_text};
 
 /// Create a new Pdfium instance (dynamically linked).
 ///
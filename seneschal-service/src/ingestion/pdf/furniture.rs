@@ -0,0 +1,209 @@
+//! Page furniture detection.
+//!
+//! Running headers, footers and page numbers are extracted as body text,
+//! so every chunk of some rulebooks starts with the book title. Short text
+//! that sits at the same spot near the top or bottom edge of many pages is
+//! treated as furniture and removed from the page text before chunking.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use pdfium_render::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+
+static DIGIT_RUN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+
+/// Text whose center lies within this fraction of the page height from the
+/// top or bottom edge can be furniture
+const EDGE_BAND: f32 = 0.1;
+
+/// Positions within this fraction of the page height count as the same spot
+const POSITION_TOLERANCE: f32 = 0.02;
+
+/// Longer text is body text, even when repeated
+const MAX_FURNITURE_LEN: usize = 80;
+
+/// Furniture repeats on at least this many pages...
+const MIN_PAGES: usize = 3;
+
+/// ...and at least this fraction of them (chapter headers only span their chapter)
+const MIN_PAGE_FRACTION: f64 = 0.2;
+
+/// Which edge of the page text sits at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Edge {
+    Top,
+    Bottom,
+}
+
+/// A short run of text near the top or bottom of a page
+#[derive(Debug, Clone)]
+pub struct EdgeText {
+    pub text: String,
+    pub edge: Edge,
+    /// Distance of the text's center from its edge, as a fraction of the page height
+    pub offset: f32,
+}
+
+/// Repeated text removed from a document's pages
+#[derive(Debug, Clone, Serialize)]
+pub struct PageFurniture {
+    /// The text as it first appeared (page numbers vary between pages)
+    pub text: String,
+    pub edge: Edge,
+    pub pages: usize,
+}
+
+/// Identifies the same furniture across pages: edge, position bucket and
+/// text with digit runs masked
+type FurnitureKey = (Edge, u32, String);
+
+impl EdgeText {
+    fn key(&self) -> FurnitureKey {
+        (
+            self.edge,
+            (self.offset / POSITION_TOLERANCE).round() as u32,
+            DIGIT_RUN.replace_all(self.text.trim(), "#").into_owned(),
+        )
+    }
+}
+
+/// Short text segments near the top or bottom of a page
+pub fn edge_texts(page: &PdfPage, text: &PdfPageText) -> Vec<EdgeText> {
+    let height = page.height().value;
+    if height <= 0.0 {
+        return Vec::new();
+    }
+
+    text.segments()
+        .iter()
+        .filter_map(|segment| {
+            let bounds = segment.bounds();
+            let center = (bounds.top().value + bounds.bottom().value) / 2.0 / height;
+            let (edge, offset) = if center >= 1.0 - EDGE_BAND {
+                (Edge::Top, 1.0 - center)
+            } else if center <= EDGE_BAND {
+                (Edge::Bottom, center)
+            } else {
+                return None;
+            };
+            let text = segment.text().trim().to_string();
+            if text.is_empty() || text.chars().count() > MAX_FURNITURE_LEN {
+                return None;
+            }
+            Some(EdgeText { text, edge, offset })
+        })
+        .collect()
+}
+
+/// Find the furniture repeated across pages.
+///
+/// Takes each page's edge texts; returns the keys to strip and a report
+/// of what they matched, most widespread first.
+pub fn detect_furniture(pages: &[Vec<EdgeText>]) -> (HashSet<FurnitureKey>, Vec<PageFurniture>) {
+    let threshold = MIN_PAGES.max((pages.len() as f64 * MIN_PAGE_FRACTION).ceil() as usize);
+    if pages.len() < threshold {
+        return (HashSet::new(), Vec::new());
+    }
+
+    let mut seen: HashMap<FurnitureKey, (String, usize)> = HashMap::new();
+    for page in pages {
+        let keys: HashSet<(FurnitureKey, &str)> = page
+            .iter()
+            .map(|edge_text| (edge_text.key(), edge_text.text.as_str()))
+            .collect();
+        for (key, text) in keys {
+            seen.entry(key).or_insert_with(|| (text.to_string(), 0)).1 += 1;
+        }
+    }
+
+    let mut report: Vec<(FurnitureKey, PageFurniture)> = seen
+        .into_iter()
+        .filter(|(_, (_, pages))| *pages >= threshold)
+        .map(|(key, (text, pages))| {
+            let edge = key.0;
+            (key, PageFurniture { text, edge, pages })
+        })
+        .collect();
+    report.sort_by(|(a_key, a), (b_key, b)| b.pages.cmp(&a.pages).then(a_key.cmp(b_key)));
+
+    report.into_iter().unzip()
+}
+
+/// Remove a page's furniture lines from its text
+pub fn strip_furniture(
+    page_text: &str,
+    edge_texts: &[EdgeText],
+    furniture: &HashSet<FurnitureKey>,
+) -> String {
+    let on_page: Vec<&str> = edge_texts
+        .iter()
+        .filter(|edge_text| furniture.contains(&edge_text.key()))
+        .map(|edge_text| edge_text.text.as_str())
+        .collect();
+    if on_page.is_empty() {
+        return page_text.to_string();
+    }
+
+    page_text
+        .lines()
+        .filter(|line| {
+            // A line is furniture if nothing is left once its furniture text is removed
+            let rest = on_page
+                .iter()
+                .fold(line.to_string(), |rest, text| rest.replacen(text, "", 1));
+            !rest.trim().is_empty() || line.trim().is_empty()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(text: &str, edge: Edge, offset: f32) -> EdgeText {
+        EdgeText {
+            text: text.to_string(),
+            edge,
+            offset,
+        }
+    }
+
+    #[test]
+    fn test_detect_and_strip_furniture() {
+        let pages: Vec<Vec<EdgeText>> = (12..16)
+            .map(|page| {
+                let mut texts = vec![edge(&page.to_string(), Edge::Bottom, 0.04)];
+                if page != 15 {
+                    texts.push(edge("TRAVELLER CORE RULEBOOK", Edge::Top, 0.05));
+                }
+                if page == 13 {
+                    // Near the edge, but only on one page
+                    texts.push(edge("Starports", Edge::Top, 0.09));
+                }
+                texts
+            })
+            .collect();
+
+        let (furniture, report) = detect_furniture(&pages);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].text, "12");
+        assert_eq!(report[0].edge, Edge::Bottom);
+        assert_eq!(report[0].pages, 4);
+        assert_eq!(report[1].text, "TRAVELLER CORE RULEBOOK");
+        assert_eq!(report[1].pages, 3);
+
+        let page_text =
+            "TRAVELLER CORE RULEBOOK\nStarports\nA Class A starport has shipyards.\n\n13";
+        assert_eq!(
+            strip_furniture(page_text, &pages[1], &furniture),
+            "Starports\nA Class A starport has shipyards.\n"
+        );
+
+        // Too few pages to tell furniture from content
+        assert!(detect_furniture(&pages[..2]).0.is_empty());
+    }
+}
@@ -3,8 +3,12 @@
 //! This module extracts images from PDF documents with the following behavior:
 //! - Each image is extracted individually (no compositing)
 //! - Background images (covering 90%+ of page, appearing on multiple pages) are extracted once
+//! - Decorative images (tiny, elongated, repeated on many pages, or near-duplicates of an
+//!   image on another page) are skipped
 //! - When overlap is detected (with text, paths, or other images), a page region render
 //!   is also saved to capture the composited appearance
+//! - Dense vector artwork with no raster image (e.g. maps drawn entirely with paths)
+//!   is rendered as a page region
 //!
 //! Uses:
 //! - poppler-rs for programmatic access to PDF images with position information
@@ -14,6 +18,7 @@
 pub mod background;
 mod coordinate_fixing;
 mod extraction;
+mod filters;
 mod image_saving;
 pub mod overlap;
 pub mod region_render;
@@ -34,10 +39,12 @@ use crate::error::{ProcessingError, ServiceResult};
 use background::{ImageSignature, detect_backgrounds, is_background};
 use coordinate_fixing::fix_invalid_image_bounds;
 use extraction::extract_all_image_info;
-use image_saving::{save_group_region_render, save_individual_image};
+use filters::filter_decorative_images;
+use image_saving::{save_group_region_render, save_individual_image, save_vector_region_render};
 use overlap::{
-    ContentRegion, PdfiumImageInfo, calculate_group_region_dpi, detect_overlap_groups,
-    extract_path_regions, extract_pdfium_images, extract_text_regions,
+    ContentRegion, PdfiumImageInfo, VectorRegion, calculate_group_region_dpi,
+    detect_overlap_groups, detect_vector_regions, extract_path_regions, extract_pdfium_images,
+    extract_text_regions, extract_vector_objects,
 };
 use region_render::render_page_region;
 use transforms::extract_image_transforms_with_qpdf;
@@ -53,20 +60,23 @@ pub use types::{ImageInfo, PageBoxes, Rectangle};
 /// 1. Extract all images with poppler and match to qpdf CTMs for orientation
 /// 2. Detect background images that appear across multiple pages
 /// 3. Extract text and path bounding boxes with pdfium-render
-/// 4. For each image:
+/// 4. Drop decorative images according to the configured filters
+/// 5. For each image:
 ///    - If background: extract once, skip duplicates, no overlap check
 ///    - If non-background: extract, check overlaps, render region if needed
+/// 6. Render clusters of vector artwork that aren't covered by images
 pub fn extract_pdf_images(
     path: &Path,
     document_id: &str,
     images_dir: &Path,
     config: &ImageExtractionConfig,
+    password: Option<&str>,
 ) -> ServiceResult<Vec<DocumentImage>> {
     // Create images directory for this document
     std::fs::create_dir_all(images_dir).map_err(ProcessingError::Io)?;
 
     // Extract transformation matrices using qpdf first
-    let transforms = match extract_image_transforms_with_qpdf(path) {
+    let transforms = match extract_image_transforms_with_qpdf(path, password) {
         Ok(t) => {
             info!(
                 document_id = document_id,
@@ -88,20 +98,21 @@ pub fn extract_pdf_images(
     // Load PDF with poppler for image extraction
     let canonical_path = path.canonicalize().map_err(ProcessingError::Io)?;
     let uri = format!("file://{}", canonical_path.display());
-    let doc =
-        PopplerDocument::from_file(&uri, None).map_err(|e| ProcessingError::TextExtraction {
+    let doc = PopplerDocument::from_file(&uri, password).map_err(|e| {
+        ProcessingError::TextExtraction {
             page: 0,
             source: Box::new(std::io::Error::other(format!(
                 "Failed to load PDF with poppler: {}",
                 e
             ))),
-        })?;
+        }
+    })?;
 
     // Load PDF with pdfium for text/path extraction and region rendering
     let pdfium = super::create_pdfium()?;
     let pdfium_doc =
         pdfium
-            .load_pdf_from_file(path, None)
+            .load_pdf_from_file(path, password)
             .map_err(|e| ProcessingError::TextExtraction {
                 page: 0,
                 source: Box::new(std::io::Error::other(format!(
@@ -120,11 +131,7 @@ pub fn extract_pdf_images(
     // Phase 1: Extract all image info from all pages
     let mut all_images = extract_all_image_info(&doc, &transforms)?;
 
-    if all_images.is_empty() {
-        info!(document_id = document_id, "No images found in PDF");
-        return Ok(vec![]);
-    }
-
+    // Vector-only PDFs can still yield region renders, so keep going without images
     info!(
         document_id = document_id,
         total_images = all_images.len(),
@@ -145,6 +152,7 @@ pub fn extract_pdf_images(
     let mut page_path_regions: HashMap<usize, Vec<ContentRegion>> = HashMap::new();
     let mut page_pdfium_images: HashMap<usize, Vec<PdfiumImageInfo>> = HashMap::new();
     let mut page_boxes: HashMap<usize, PageBoxes> = HashMap::new();
+    let mut page_vector_regions: HashMap<usize, Vec<VectorRegion>> = HashMap::new();
 
     for page_num in 0..pdfium_doc.pages().len() {
         if let Ok(page) = pdfium_doc.pages().get(page_num) {
@@ -152,12 +160,21 @@ pub fn extract_pdf_images(
             let path_regions = extract_path_regions(&page);
             let pdfium_images = extract_pdfium_images(&page);
             let boxes = extract_page_boxes(&page);
+            let image_bounds: Vec<Rectangle> = pdfium_images.iter().map(|i| i.bounds).collect();
+            let vector_regions = detect_vector_regions(
+                &extract_vector_objects(&page),
+                &image_bounds,
+                page.width().value as f64,
+                page.height().value as f64,
+                config.vector_region_min_objects,
+            );
 
             trace!(
                 page = page_num + 1,
                 text_regions = text_regions.len(),
                 path_regions = path_regions.len(),
                 pdfium_images = pdfium_images.len(),
+                vector_regions = vector_regions.len(),
                 media_box = boxes
                     .media_box
                     .map(|b| format!("({:.1},{:.1})-({:.1},{:.1})", b.x1, b.y1, b.x2, b.y2)),
@@ -171,12 +188,26 @@ pub fn extract_pdf_images(
             page_path_regions.insert(page_num as usize, path_regions);
             page_pdfium_images.insert(page_num as usize, pdfium_images);
             page_boxes.insert(page_num as usize, boxes);
+            page_vector_regions.insert(page_num as usize, vector_regions);
         }
     }
 
     // Phase 3b: Fix invalid poppler coordinates using pdfium fallback
     fix_invalid_image_bounds(&mut all_images, &page_pdfium_images, &page_boxes);
 
+    // Phase 3c: Filter out decorative images (backgrounds are handled separately)
+    let filtered_images = filter_decorative_images(
+        &all_images,
+        |idx| !is_background(&all_images[idx], &background_signatures),
+        config,
+    );
+
+    info!(
+        document_id = document_id,
+        filtered = filtered_images.len(),
+        "Filtered decorative images"
+    );
+
     // Phase 4: Save all individual images first
     let mut results = Vec::new();
     let mut page_image_counts: HashMap<usize, usize> = HashMap::new();
@@ -187,6 +218,10 @@ pub fn extract_pdf_images(
     let mut saved_image_ids: HashMap<usize, String> = HashMap::new();
 
     for (image_idx, image_info) in all_images.iter().enumerate() {
+        if filtered_images.contains(&image_idx) {
+            continue;
+        }
+
         let is_bg = is_background(image_info, &background_signatures);
         let signature = ImageSignature::from_image(image_info);
 
@@ -234,7 +269,11 @@ pub fn extract_pdf_images(
     // Phase 5: Detect overlap groups and create region renders
     // Group images by page
     let mut images_by_page: HashMap<usize, Vec<usize>> = HashMap::new();
-    for (idx, info) in all_images.iter().enumerate() {
+    for (idx, info) in all_images
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !filtered_images.contains(idx))
+    {
         images_by_page
             .entry(info.page_number)
             .or_default()
@@ -296,7 +335,14 @@ pub fn extract_pdf_images(
                 "Rendering region for overlap group"
             );
 
-            match render_page_region(&pdfium, path, *page_num, &group.combined_region, region_dpi) {
+            match render_page_region(
+                &pdfium,
+                path,
+                password,
+                *page_num,
+                &group.combined_region,
+                region_dpi,
+            ) {
                 Ok(region_image) => {
                     // Find the first saved image in this group to use as source_image_id
                     let source_image_id = group
@@ -350,6 +396,76 @@ pub fn extract_pdf_images(
         }
     }
 
+    // Phase 6: Render vector artwork regions. Identical regions on several pages
+    // are borders and other page furniture rather than maps.
+    let mut signature_pages: HashMap<[i64; 5], usize> = HashMap::new();
+    for regions in page_vector_regions.values() {
+        for region in regions {
+            *signature_pages.entry(region.signature()).or_default() += 1;
+        }
+    }
+
+    let mut vector_pages: Vec<usize> = page_vector_regions.keys().copied().collect();
+    vector_pages.sort_unstable();
+    for page_num in vector_pages {
+        let page_display = (page_num + 1) as i32;
+
+        for region in &page_vector_regions[&page_num] {
+            if signature_pages[&region.signature()] >= config.background_min_pages.max(2) {
+                trace!(
+                    page = page_display,
+                    objects = region.objects,
+                    "Skipping vector region repeated across pages"
+                );
+                continue;
+            }
+
+            let region_idx = *page_group_counts.get(&page_num).unwrap_or(&0);
+            page_group_counts.insert(page_num, region_idx + 1);
+
+            debug!(
+                page = page_display,
+                region_idx = region_idx,
+                objects = region.objects,
+                region = format!(
+                    "({:.1},{:.1})-({:.1},{:.1})",
+                    region.bounds.x1, region.bounds.y1, region.bounds.x2, region.bounds.y2
+                ),
+                "Rendering vector artwork region"
+            );
+
+            let saved = render_page_region(
+                &pdfium,
+                path,
+                password,
+                page_num,
+                &region.bounds,
+                config.text_overlap_min_dpi,
+            )
+            .and_then(|region_image| {
+                save_vector_region_render(
+                    &region_image,
+                    images_dir,
+                    document_id,
+                    page_display,
+                    region_idx,
+                    now,
+                )
+            });
+            match saved {
+                Ok(region_doc_image) => results.push(region_doc_image),
+                Err(e) => {
+                    warn!(
+                        page = page_display,
+                        region_idx = region_idx,
+                        error = %e,
+                        "Failed to render vector region"
+                    );
+                }
+            }
+        }
+    }
+
     // Sort by page number then image index for consistent ordering
     results.sort_by(|a, b| {
         a.page_number
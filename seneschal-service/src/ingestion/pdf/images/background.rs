@@ -143,6 +143,7 @@ mod tests {
             background_area_threshold: 0.9,
             background_min_pages: 2,
             text_overlap_min_dpi: 300.0,
+            ..Default::default()
         };
 
         // Create images: one background covering 95% of pages 0 and 1, one normal image
@@ -169,6 +170,7 @@ mod tests {
             background_area_threshold: 0.9,
             background_min_pages: 2,
             text_overlap_min_dpi: 300.0,
+            ..Default::default()
         };
 
         // Large image on only one page
@@ -0,0 +1,135 @@
+//! Filters for decorative images such as borders, bullets and texture tiles.
+//!
+//! Applied after background detection to the remaining images: ones that are
+//! too small or too elongated on the page are dropped, as are images repeated
+//! on many pages and near-duplicates of images already seen on another page.
+//! Repeats are matched by a perceptual (difference) hash so that re-encoded
+//! or slightly rescaled copies still count.
+
+use std::collections::HashSet;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbaImage};
+
+use crate::config::ImageExtractionConfig;
+
+use super::ImageInfo;
+use super::transforms::convert_to_rgba;
+
+/// Hashes within this many differing bits are treated as the same picture
+const NEAR_DUPLICATE_DISTANCE: u32 = 4;
+
+/// Images sharing a perceptual hash
+struct DuplicateCluster {
+    hash: u64,
+    first_page: usize,
+    pages: HashSet<usize>,
+    members: Vec<usize>,
+}
+
+/// Select images to leave out of extraction.
+///
+/// Only images for which `is_candidate` returns true (by index into `images`)
+/// are considered. Returns the indices of the filtered images.
+pub fn filter_decorative_images(
+    images: &[ImageInfo],
+    is_candidate: impl Fn(usize) -> bool,
+    config: &ImageExtractionConfig,
+) -> HashSet<usize> {
+    let mut filtered = HashSet::new();
+    let check_repeats = config.suppress_near_duplicates || config.repeated_image_max_pages > 0;
+    let mut clusters: Vec<DuplicateCluster> = Vec::new();
+
+    for (idx, info) in images.iter().enumerate() {
+        if !is_candidate(idx) {
+            continue;
+        }
+        if info.area.area() < config.min_image_area || is_too_elongated(info, config) {
+            filtered.insert(idx);
+            continue;
+        }
+        if !check_repeats {
+            continue;
+        }
+
+        let hash = difference_hash(&convert_to_rgba(info));
+        match clusters
+            .iter_mut()
+            .find(|c| (c.hash ^ hash).count_ones() <= NEAR_DUPLICATE_DISTANCE)
+        {
+            Some(cluster) => {
+                cluster.pages.insert(info.page_number);
+                cluster.members.push(idx);
+            }
+            None => clusters.push(DuplicateCluster {
+                hash,
+                first_page: info.page_number,
+                pages: HashSet::from([info.page_number]),
+                members: vec![idx],
+            }),
+        }
+    }
+
+    for cluster in clusters {
+        if config.repeated_image_max_pages > 0
+            && cluster.pages.len() > config.repeated_image_max_pages
+        {
+            filtered.extend(cluster.members);
+        } else if config.suppress_near_duplicates {
+            filtered.extend(
+                cluster
+                    .members
+                    .into_iter()
+                    .filter(|&idx| images[idx].page_number != cluster.first_page),
+            );
+        }
+    }
+
+    filtered
+}
+
+/// Check the image's shape on the page against the aspect ratio limit
+fn is_too_elongated(info: &ImageInfo, config: &ImageExtractionConfig) -> bool {
+    if config.max_aspect_ratio <= 0.0 {
+        return false;
+    }
+    let (width, height) = (info.area.width(), info.area.height());
+    let short = width.min(height);
+    short <= 0.0 || width.max(height) / short > config.max_aspect_ratio
+}
+
+/// 64-bit difference hash: each bit records whether brightness increases
+/// between horizontally adjacent cells of a 9x8 grayscale thumbnail.
+fn difference_hash(image: &RgbaImage) -> u64 {
+    let thumb = DynamicImage::ImageRgba8(image.clone())
+        .resize_exact(9, 8, FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if thumb.get_pixel(x + 1, y)[0] > thumb.get_pixel(x, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difference_hash() {
+        let gradient = RgbaImage::from_fn(90, 80, |x, _| image::Rgba([(x * 2) as u8, 0, 0, 255]));
+        let rescaled = RgbaImage::from_fn(180, 160, |x, _| image::Rgba([x as u8, 0, 0, 255]));
+        let mirrored =
+            RgbaImage::from_fn(90, 80, |x, _| image::Rgba([(180 - x * 2) as u8, 0, 0, 255]));
+
+        let hash = difference_hash(&gradient);
+        assert!((hash ^ difference_hash(&rescaled)).count_ones() <= NEAR_DUPLICATE_DISTANCE);
+        assert!((hash ^ difference_hash(&mirrored)).count_ones() > NEAR_DUPLICATE_DISTANCE);
+    }
+}
@@ -140,27 +140,7 @@ pub fn save_group_region_render(
     // Name using group index to indicate this is a grouped region render
     let webp_filename = format!("page_{}_group_{}_region.webp", page_number, group_index);
     let webp_path = images_dir.join(&webp_filename);
-
-    let file = File::create(&webp_path).map_err(|e| ProcessingError::TextExtraction {
-        page: page_number as u32,
-        source: Box::new(e),
-    })?;
-
-    let encoder = WebPEncoder::new_lossless(file);
-    encoder
-        .write_image(
-            image.as_raw(),
-            width,
-            height,
-            image::ExtendedColorType::Rgba8,
-        )
-        .map_err(|e| ProcessingError::TextExtraction {
-            page: page_number as u32,
-            source: Box::new(std::io::Error::other(format!(
-                "Failed to encode region WebP: {}",
-                e
-            ))),
-        })?;
+    write_region_webp(image, &webp_path, page_number)?;
 
     debug!(
         page = page_number,
@@ -188,3 +168,78 @@ pub fn save_group_region_render(
         created_at,
     })
 }
+
+/// Save a render of a vector artwork region to disk.
+///
+/// `region_index` continues the page's overlap group numbering so that
+/// renders on the same page keep distinct indices.
+pub fn save_vector_region_render(
+    image: &image::RgbaImage,
+    images_dir: &Path,
+    document_id: &str,
+    page_number: i32,
+    region_index: usize,
+    created_at: DateTime<Utc>,
+) -> ServiceResult<DocumentImage> {
+    let width = image.width();
+    let height = image.height();
+
+    let image_id = Uuid::new_v4().to_string();
+    let webp_filename = format!("page_{}_vector_{}.webp", page_number, region_index);
+    let webp_path = images_dir.join(&webp_filename);
+    write_region_webp(image, &webp_path, page_number)?;
+
+    debug!(
+        page = page_number,
+        region_index = region_index,
+        width = width,
+        height = height,
+        "Saved vector region render"
+    );
+
+    Ok(DocumentImage {
+        id: image_id,
+        document_id: document_id.to_string(),
+        page_number,
+        image_index: region_index as i32,
+        internal_path: webp_path.to_string_lossy().to_string(),
+        mime_type: "image/webp".to_string(),
+        width: Some(width),
+        height: Some(height),
+        description: None,
+        source_pages: Some(vec![page_number]),
+        image_type: ImageType::Render,
+        source_image_id: None,
+        has_region_render: false,
+        created_at,
+    })
+}
+
+/// Encode a region render as lossless WebP
+fn write_region_webp(
+    image: &image::RgbaImage,
+    webp_path: &Path,
+    page_number: i32,
+) -> ServiceResult<()> {
+    let file = File::create(webp_path).map_err(|e| ProcessingError::TextExtraction {
+        page: page_number as u32,
+        source: Box::new(e),
+    })?;
+
+    let encoder = WebPEncoder::new_lossless(file);
+    encoder
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| ProcessingError::TextExtraction {
+            page: page_number as u32,
+            source: Box::new(std::io::Error::other(format!(
+                "Failed to encode region WebP: {}",
+                e
+            ))),
+        })?;
+    Ok(())
+}
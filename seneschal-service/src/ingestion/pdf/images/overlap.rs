@@ -2,11 +2,14 @@
 //!
 //! This module detects when images overlap with text, vector graphics (paths),
 //! or other images. Overlapping items are grouped together, and a single
-//! region render is created per overlap group.
+//! region render is created per overlap group. Dense clusters of vector
+//! drawing objects with no raster image are detected the same way so that
+//! vector-only maps can be rendered too.
 
 mod groups;
 mod regions;
 mod union_find;
+mod vector_regions;
 
 // Re-export public types and functions used by the parent images.rs module
 pub use groups::{OverlapGroup, calculate_group_region_dpi, detect_overlap_groups};
@@ -14,3 +17,4 @@ pub use regions::{
     ContentRegion, PdfiumImageInfo, extract_path_regions, extract_pdfium_images,
     extract_text_regions,
 };
+pub use vector_regions::{VectorRegion, detect_vector_regions, extract_vector_objects};
@@ -0,0 +1,209 @@
+//! Detection of artwork drawn entirely with vector paths.
+//!
+//! Maps and deck plans are often pure vector art, which poppler never reports
+//! as images. Drawing objects close to each other are clustered, and clusters
+//! with enough objects and a plausible size are rendered as page regions.
+
+use std::collections::HashMap;
+
+use pdfium_render::prelude::*;
+
+use super::super::Rectangle;
+use super::regions::{intersect_rectangles, pdf_rect_to_rectangle, rectangles_intersect};
+use super::union_find::UnionFind;
+
+/// Drawing objects within this many points of each other belong to the same artwork
+const CLUSTER_GAP: f64 = 4.0;
+
+/// Clusters narrower or shorter than this (one inch) are icons, rules or borders
+const MIN_EDGE: f64 = 72.0;
+
+/// Clusters covering more of the page than this are page decoration
+const MAX_PAGE_FRACTION: f64 = 0.9;
+
+/// Clusters mostly covered by raster images are left to overlap group renders
+const MAX_IMAGE_COVERAGE: f64 = 0.5;
+
+/// A vector drawing object on a page
+#[derive(Debug, Clone, Copy)]
+pub struct VectorObject {
+    pub bounds: Rectangle,
+    /// Number of drawing objects this stands for (a Form XObject counts its children)
+    pub weight: usize,
+}
+
+/// A cluster of vector drawing objects worth rendering as an image
+#[derive(Debug, Clone, Copy)]
+pub struct VectorRegion {
+    pub bounds: Rectangle,
+    /// Total weight of the clustered objects
+    pub objects: usize,
+}
+
+impl VectorRegion {
+    /// Bounds and object count rounded to whole points, for spotting page
+    /// furniture repeated across pages
+    pub fn signature(&self) -> [i64; 5] {
+        [
+            self.bounds.x1.round() as i64,
+            self.bounds.y1.round() as i64,
+            self.bounds.x2.round() as i64,
+            self.bounds.y2.round() as i64,
+            self.objects as i64,
+        ]
+    }
+}
+
+/// Extract path and Form XObject bounds from a page, clipped to the page.
+pub fn extract_vector_objects(page: &PdfPage) -> Vec<VectorObject> {
+    let page_bounds = Rectangle {
+        x1: 0.0,
+        y1: 0.0,
+        x2: page.width().value as f64,
+        y2: page.height().value as f64,
+    };
+
+    page.objects()
+        .iter()
+        .filter_map(|object| {
+            let weight = match &object {
+                PdfPageObject::Path(_) => 1,
+                PdfPageObject::XObjectForm(form_obj) => form_obj.len().max(1),
+                _ => return None,
+            };
+            let bounds = pdf_rect_to_rectangle(&object.bounds().ok()?.to_rect());
+            intersect_rectangles(&bounds, &page_bounds)
+                .map(|bounds| VectorObject { bounds, weight })
+        })
+        .collect()
+}
+
+/// Find clusters of dense vector artwork on a page.
+///
+/// `images` are the bounds of raster images on the page. Clusters need at
+/// least `min_objects` drawing objects; 0 disables detection. Regions are
+/// returned top to bottom.
+pub fn detect_vector_regions(
+    objects: &[VectorObject],
+    images: &[Rectangle],
+    page_width: f64,
+    page_height: f64,
+    min_objects: usize,
+) -> Vec<VectorRegion> {
+    if min_objects == 0 || objects.iter().map(|o| o.weight).sum::<usize>() < min_objects {
+        return Vec::new();
+    }
+
+    // Sweep in x order so only objects that can be near each other are compared
+    let mut order: Vec<usize> = (0..objects.len()).collect();
+    order.sort_by(|&a, &b| objects[a].bounds.x1.total_cmp(&objects[b].bounds.x1));
+
+    let mut uf = UnionFind::new(objects.len());
+    for (pos, &i) in order.iter().enumerate() {
+        let bounds = objects[i].bounds;
+        let expanded = Rectangle {
+            x1: bounds.x1 - CLUSTER_GAP,
+            y1: bounds.y1 - CLUSTER_GAP,
+            x2: bounds.x2 + CLUSTER_GAP,
+            y2: bounds.y2 + CLUSTER_GAP,
+        };
+        for &j in &order[pos + 1..] {
+            if objects[j].bounds.x1 > expanded.x2 {
+                break;
+            }
+            if rectangles_intersect(&expanded, &objects[j].bounds) {
+                uf.union(i, j);
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, VectorRegion> = HashMap::new();
+    for (i, object) in objects.iter().enumerate() {
+        clusters
+            .entry(uf.find(i))
+            .and_modify(|region| {
+                region.bounds.x1 = region.bounds.x1.min(object.bounds.x1);
+                region.bounds.y1 = region.bounds.y1.min(object.bounds.y1);
+                region.bounds.x2 = region.bounds.x2.max(object.bounds.x2);
+                region.bounds.y2 = region.bounds.y2.max(object.bounds.y2);
+                region.objects += object.weight;
+            })
+            .or_insert(VectorRegion {
+                bounds: object.bounds,
+                objects: object.weight,
+            });
+    }
+
+    let page_area = page_width * page_height;
+    let mut regions: Vec<VectorRegion> = clusters
+        .into_values()
+        .filter(|region| {
+            region.objects >= min_objects
+                && region.bounds.width() >= MIN_EDGE
+                && region.bounds.height() >= MIN_EDGE
+                && region.bounds.area() <= page_area * MAX_PAGE_FRACTION
+                && image_coverage(&region.bounds, images) < MAX_IMAGE_COVERAGE
+        })
+        .collect();
+
+    // PDF y grows upwards, so the highest top edge comes first
+    regions.sort_by(|a, b| {
+        b.bounds
+            .y2
+            .total_cmp(&a.bounds.y2)
+            .then(a.bounds.x1.total_cmp(&b.bounds.x1))
+    });
+    regions
+}
+
+/// Fraction of `bounds` covered by raster images
+fn image_coverage(bounds: &Rectangle, images: &[Rectangle]) -> f64 {
+    let covered: f64 = images
+        .iter()
+        .filter_map(|image| intersect_rectangles(bounds, image))
+        .map(|overlap| overlap.area())
+        .sum();
+    covered / bounds.area().max(f64::EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(x1: f64, y1: f64, x2: f64, y2: f64, weight: usize) -> VectorObject {
+        VectorObject {
+            bounds: Rectangle { x1, y1, x2, y2 },
+            weight,
+        }
+    }
+
+    #[test]
+    fn test_detect_vector_regions() {
+        // A 10x10 grid of touching hexes forming a 200pt map, plus a lone rule
+        let mut objects: Vec<VectorObject> = (0..100)
+            .map(|i| {
+                let x = 100.0 + (i % 10) as f64 * 20.0;
+                let y = 400.0 + (i / 10) as f64 * 20.0;
+                object(x, y, x + 21.0, y + 21.0, 1)
+            })
+            .collect();
+        objects.push(object(50.0, 50.0, 550.0, 51.0, 1));
+
+        let regions = detect_vector_regions(&objects, &[], 612.0, 792.0, 40);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].objects, 100);
+        assert_eq!(regions[0].bounds.x1, 100.0);
+        assert_eq!(regions[0].bounds.y2, 601.0);
+
+        // Too few objects, or already covered by a raster image
+        assert!(detect_vector_regions(&objects, &[], 612.0, 792.0, 200).is_empty());
+        let image = Rectangle {
+            x1: 90.0,
+            y1: 390.0,
+            x2: 330.0,
+            y2: 630.0,
+        };
+        assert!(detect_vector_regions(&objects, &[image], 612.0, 792.0, 40).is_empty());
+        assert!(detect_vector_regions(&objects, &[], 612.0, 792.0, 0).is_empty());
+    }
+}
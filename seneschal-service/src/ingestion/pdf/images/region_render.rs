@@ -17,6 +17,7 @@ use crate::error::{ProcessingError, ServiceResult};
 /// # Arguments
 /// * `pdfium` - The PDFium instance
 /// * `pdf_path` - Path to the PDF file
+/// * `password` - Password for encrypted PDFs
 /// * `page_number` - Page number (0-indexed)
 /// * `region` - The region to render in PDF points
 /// * `dpi` - Target DPI for the render
@@ -26,21 +27,21 @@ use crate::error::{ProcessingError, ServiceResult};
 pub fn render_page_region(
     pdfium: &Pdfium,
     pdf_path: &Path,
+    password: Option<&str>,
     page_number: usize,
     region: &Rectangle,
     dpi: f64,
 ) -> ServiceResult<RgbaImage> {
     // Load the PDF document
-    let document =
-        pdfium
-            .load_pdf_from_file(pdf_path, None)
-            .map_err(|e| ProcessingError::TextExtraction {
-                page: page_number as u32,
-                source: Box::new(std::io::Error::other(format!(
-                    "Failed to load PDF for region render: {}",
-                    e
-                ))),
-            })?;
+    let document = pdfium.load_pdf_from_file(pdf_path, password).map_err(|e| {
+        ProcessingError::TextExtraction {
+            page: page_number as u32,
+            source: Box::new(std::io::Error::other(format!(
+                "Failed to load PDF for region render: {}",
+                e
+            ))),
+        }
+    })?;
 
     // Get the page
     let pages = document.pages();
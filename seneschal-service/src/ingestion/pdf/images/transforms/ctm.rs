@@ -14,10 +14,15 @@ use crate::error::ProcessingError;
 /// Returns a map of page_num -> Vec<ImageTransform> for matching by dimensions.
 pub fn extract_image_transforms_with_qpdf(
     path: &Path,
+    password: Option<&str>,
 ) -> Result<HashMap<usize, Vec<ImageTransform>>, ProcessingError> {
     use qpdf::{QPdfObjectLike, QPdfObjectType, QPdfStream};
 
-    let pdf = QPdf::read(path).map_err(|e| ProcessingError::TextExtraction {
+    let pdf = match password {
+        Some(password) => QPdf::read_encrypted(path, password),
+        None => QPdf::read(path),
+    }
+    .map_err(|e| ProcessingError::TextExtraction {
         page: 0,
         source: Box::new(std::io::Error::other(format!(
             "Failed to load PDF with qpdf: {}",
@@ -297,39 +302,37 @@ fn parse_content_stream_for_all_ctms(content: &str) -> Vec<ImageTransform> {
                 // Path-ending operators - clear pending rect if not used as clip
                 pending_rect = None;
             }
-            "Do" => {
+            "Do" if i >= 1 => {
                 // Draw XObject - capture the state at this moment for each image
-                if i >= 1 {
-                    let name = tokens[i - 1].trim_start_matches('/');
-                    // Image XObjects are typically named ImN, Img, Image, etc.
-                    if name.starts_with("Im")
-                        || name.starts_with("Img")
-                        || name.starts_with("Image")
-                        || (name.starts_with('X')
-                            && name.len() > 1
-                            && name[1..]
-                                .chars()
-                                .next()
-                                .map(|c| c.is_ascii_digit())
-                                .unwrap_or(false))
-                    {
-                        let [a, b, c, d, _e, _f] = current_ctm;
-                        let expected_width = (a * a + b * b).sqrt();
-                        let expected_height = (c * c + d * d).sqrt();
-                        let computed_bounds = Some(super::compute_bounds_from_ctm(&current_ctm));
-
-                        transforms.push(ImageTransform {
-                            xobject_name: name.to_string(),
-                            matrix: current_ctm,
-                            expected_width,
-                            expected_height,
-                            computed_bounds,
-                            clip_rect: current_clip,
-                            smask_data: None,
-                            smask_width: None,
-                            smask_height: None,
-                        });
-                    }
+                let name = tokens[i - 1].trim_start_matches('/');
+                // Image XObjects are typically named ImN, Img, Image, etc.
+                if name.starts_with("Im")
+                    || name.starts_with("Img")
+                    || name.starts_with("Image")
+                    || (name.starts_with('X')
+                        && name.len() > 1
+                        && name[1..]
+                            .chars()
+                            .next()
+                            .map(|c| c.is_ascii_digit())
+                            .unwrap_or(false))
+                {
+                    let [a, b, c, d, _e, _f] = current_ctm;
+                    let expected_width = (a * a + b * b).sqrt();
+                    let expected_height = (c * c + d * d).sqrt();
+                    let computed_bounds = Some(super::compute_bounds_from_ctm(&current_ctm));
+
+                    transforms.push(ImageTransform {
+                        xobject_name: name.to_string(),
+                        matrix: current_ctm,
+                        expected_width,
+                        expected_height,
+                        computed_bounds,
+                        clip_rect: current_clip,
+                        smask_data: None,
+                        smask_width: None,
+                        smask_height: None,
+                    });
                 }
             }
             _ => {}
@@ -0,0 +1,264 @@
+//! Reading order for multi-column pages.
+//!
+//! PDFium returns page text in content-stream order, which in many
+//! two-column rulebooks interleaves lines from both columns. Text segments
+//! are placed by their coordinates instead: a vertical gutter splits the
+//! page into columns, each column is read top to bottom, and text spanning
+//! the gutter (chapter headings, full-width tables) starts a new band.
+
+use pdfium_render::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The gutter is searched for between these fractions of the page width
+const GUTTER_SEARCH: (f32, f32) = (0.3, 0.7);
+
+/// Step between gutter candidates, as a fraction of the page width
+const GUTTER_STEP: f32 = 0.01;
+
+/// Pages with fewer text segments are read as one column
+const MIN_SEGMENTS: usize = 6;
+
+/// Each column must hold at least this fraction of the page's segments...
+const MIN_COLUMN_FRACTION: f32 = 0.25;
+
+/// ...and at most this fraction may span the gutter
+const MAX_SPANNING_FRACTION: f32 = 0.2;
+
+/// How a document's pages are laid out, stored per document as `pdf_layout`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnLayout {
+    /// Detect two-column pages from text positions
+    #[default]
+    Auto,
+    /// Keep PDFium's text order
+    Single,
+    /// Read every page as two columns
+    TwoColumn,
+}
+
+impl ColumnLayout {
+    /// Parse a layout hint ("auto", "single" or "two-column")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "auto" => Some(Self::Auto),
+            "single" => Some(Self::Single),
+            "two-column" => Some(Self::TwoColumn),
+            _ => None,
+        }
+    }
+}
+
+/// A text segment and its bounds in page coordinates (y grows upward)
+#[derive(Debug, Clone)]
+pub struct TextBox {
+    pub text: String,
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl TextBox {
+    fn center_y(&self) -> f32 {
+        (self.top + self.bottom) / 2.0
+    }
+
+    fn spans(&self, x: f32) -> bool {
+        self.left < x && self.right > x
+    }
+}
+
+/// A page's text in reading order.
+///
+/// Returns the text and whether it was read as two columns.
+pub fn page_text(page: &PdfPage, text: &PdfPageText, layout: ColumnLayout) -> (String, bool) {
+    if layout == ColumnLayout::Single {
+        return (text.all(), false);
+    }
+
+    let boxes: Vec<TextBox> = text
+        .segments()
+        .iter()
+        .filter_map(|segment| {
+            let content = segment.text();
+            if content.trim().is_empty() {
+                return None;
+            }
+            let bounds = segment.bounds();
+            Some(TextBox {
+                text: content,
+                left: bounds.left().value,
+                right: bounds.right().value,
+                top: bounds.top().value,
+                bottom: bounds.bottom().value,
+            })
+        })
+        .collect();
+
+    let width = page.width().value;
+    let gutter = match layout {
+        ColumnLayout::TwoColumn => best_gutter(&boxes, width).map(|(x, _)| x),
+        _ => find_gutter(&boxes, width),
+    };
+    match gutter {
+        Some(x) => (reading_order(&boxes, x), true),
+        None => (text.all(), false),
+    }
+}
+
+/// The x position of a column gutter, if the page looks two-column
+pub fn find_gutter(boxes: &[TextBox], width: f32) -> Option<f32> {
+    if boxes.len() < MIN_SEGMENTS {
+        return None;
+    }
+    let (x, spanning) = best_gutter(boxes, width)?;
+    let total = boxes.len() as f32;
+    let left = boxes.iter().filter(|b| b.right <= x).count() as f32;
+    let right = boxes.iter().filter(|b| b.left >= x).count() as f32;
+    (spanning as f32 <= total * MAX_SPANNING_FRACTION
+        && left >= total * MIN_COLUMN_FRACTION
+        && right >= total * MIN_COLUMN_FRACTION)
+        .then_some(x)
+}
+
+/// The gutter candidate crossed by the fewest segments (nearest the middle
+/// on ties), with that count
+fn best_gutter(boxes: &[TextBox], width: f32) -> Option<(f32, usize)> {
+    if boxes.is_empty() || width <= 0.0 {
+        return None;
+    }
+    let steps = ((GUTTER_SEARCH.1 - GUTTER_SEARCH.0) / GUTTER_STEP).round() as usize;
+    (0..=steps)
+        .map(|step| width * (GUTTER_SEARCH.0 + step as f32 * GUTTER_STEP))
+        .map(|x| (x, boxes.iter().filter(|b| b.spans(x)).count()))
+        .min_by(|(a_x, a_count), (b_x, b_count)| {
+            a_count.cmp(b_count).then(
+                (a_x - width / 2.0)
+                    .abs()
+                    .total_cmp(&(b_x - width / 2.0).abs()),
+            )
+        })
+}
+
+/// Text read column by column, in bands separated by text spanning the gutter
+pub fn reading_order(boxes: &[TextBox], gutter: f32) -> String {
+    let mut sorted: Vec<&TextBox> = boxes.iter().collect();
+    sorted.sort_by(|a, b| b.top.total_cmp(&a.top));
+
+    let mut lines = Vec::new();
+    let mut left: Vec<&TextBox> = Vec::new();
+    let mut right: Vec<&TextBox> = Vec::new();
+    let mut spanning: Vec<&TextBox> = Vec::new();
+    for text_box in sorted {
+        if text_box.spans(gutter) {
+            if !left.is_empty() || !right.is_empty() {
+                lines.extend(group_lines(&mut left));
+                lines.extend(group_lines(&mut right));
+            }
+            spanning.push(text_box);
+        } else {
+            if !spanning.is_empty() {
+                lines.extend(group_lines(&mut spanning));
+            }
+            if text_box.right <= gutter {
+                left.push(text_box);
+            } else {
+                right.push(text_box);
+            }
+        }
+    }
+    lines.extend(group_lines(&mut spanning));
+    lines.extend(group_lines(&mut left));
+    lines.extend(group_lines(&mut right));
+
+    lines.join("\n")
+}
+
+/// Join boxes sharing a baseline into lines, top to bottom, draining the input
+fn group_lines(boxes: &mut Vec<&TextBox>) -> Vec<String> {
+    boxes.sort_by(|a, b| b.top.total_cmp(&a.top));
+
+    let mut lines: Vec<Vec<&TextBox>> = Vec::new();
+    for text_box in boxes.drain(..) {
+        match lines.last_mut() {
+            Some(line)
+                if (line[0].center_y() - text_box.center_y()).abs()
+                    <= (line[0].top - line[0].bottom) / 2.0 =>
+            {
+                line.push(text_box)
+            }
+            _ => lines.push(vec![text_box]),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|mut line| {
+            line.sort_by(|a, b| a.left.total_cmp(&b.left));
+            line.iter()
+                .map(|b| b.text.trim())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_box(text: &str, left: f32, right: f32, top: f32) -> TextBox {
+        TextBox {
+            text: text.to_string(),
+            left,
+            right,
+            top,
+            bottom: top - 10.0,
+        }
+    }
+
+    #[test]
+    fn test_two_column_reading_order() {
+        // Content-stream order alternates between the columns
+        let boxes = vec![
+            text_box("STARPORTS", 50.0, 550.0, 760.0),
+            text_box("A Class A starport", 50.0, 280.0, 740.0),
+            text_box("Class B starports", 320.0, 550.0, 740.0),
+            text_box("has shipyards.", 50.0, 280.0, 726.0),
+            text_box("build small craft.", 320.0, 550.0, 726.0),
+            text_box("Refined fuel", 50.0, 280.0, 712.0),
+            text_box("Class C starports", 320.0, 550.0, 712.0),
+            text_box("is available.", 50.0, 150.0, 698.0),
+            text_box("repair ships.", 320.0, 450.0, 698.0),
+            text_box("12", 290.0, 310.0, 40.0),
+        ];
+
+        let gutter = find_gutter(&boxes, 600.0).unwrap();
+        assert!(gutter > 280.0 && gutter < 320.0);
+        assert_eq!(
+            reading_order(&boxes, gutter),
+            "STARPORTS\nA Class A starport\nhas shipyards.\nRefined fuel\nis available.\n\
+             Class B starports\nbuild small craft.\nClass C starports\nrepair ships.\n12"
+        );
+
+        // Full-width lines have no gutter
+        let single: Vec<TextBox> = (0..8)
+            .map(|i| {
+                text_box(
+                    "A Class A starport has shipyards.",
+                    50.0,
+                    550.0,
+                    700.0 - i as f32 * 14.0,
+                )
+            })
+            .collect();
+        assert!(find_gutter(&single, 600.0).is_none());
+
+        assert_eq!(
+            ColumnLayout::parse("two-column"),
+            Some(ColumnLayout::TwoColumn)
+        );
+        assert_eq!(ColumnLayout::parse("three-column"), None);
+    }
+}
@@ -0,0 +1,225 @@
+//! Whole-page rendering of PDF documents.
+//!
+//! Pages are rasterized with pdfium on request (for citation previews and
+//! page handouts) and cached on disk, keyed by page, DPI and format. Regions
+//! are cropped from the cached render, which also captures vector art that
+//! image extraction misses.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{ImageEncoder, RgbaImage};
+use pdfium_render::prelude::*;
+use serde::Deserialize;
+
+use crate::error::ProcessingError;
+
+/// DPI used when none is requested
+pub const DEFAULT_RENDER_DPI: u32 = 150;
+
+/// Requested DPI is clamped to this range to bound render time and file size
+pub const MIN_RENDER_DPI: u32 = 36;
+pub const MAX_RENDER_DPI: u32 = 300;
+
+/// Encoding of a rendered page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageImageFormat {
+    #[default]
+    Webp,
+    Png,
+}
+
+impl PageImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PageImageFormat::Webp => "webp",
+            PageImageFormat::Png => "png",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            PageImageFormat::Webp => "image/webp",
+            PageImageFormat::Png => "image/png",
+        }
+    }
+}
+
+/// A rectangle on a page as fractions of the page size, from the top-left corner
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct PageRegion {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PageRegion {
+    /// Cut this region out of a rendered page
+    pub fn crop(&self, page: &RgbaImage) -> Result<RgbaImage, String> {
+        let in_unit = |v: f32| (0.0..=1.0).contains(&v);
+        if !in_unit(self.x)
+            || !in_unit(self.y)
+            || self.width <= 0.0
+            || self.height <= 0.0
+            || self.x + self.width > 1.0 + f32::EPSILON
+            || self.y + self.height > 1.0 + f32::EPSILON
+        {
+            return Err(
+                "region must lie within the page: x, y, width and height are fractions of the page size"
+                    .to_string(),
+            );
+        }
+
+        let (page_width, page_height) = (page.width() as f32, page.height() as f32);
+        let left = (self.x * page_width).floor() as u32;
+        let top = (self.y * page_height).floor() as u32;
+        let width = ((self.width * page_width).ceil() as u32)
+            .min(page.width() - left.min(page.width()))
+            .max(1);
+        let height = ((self.height * page_height).ceil() as u32)
+            .min(page.height() - top.min(page.height()))
+            .max(1);
+
+        Ok(image::imageops::crop_imm(page, left, top, width, height).to_image())
+    }
+}
+
+/// Cache location for a rendered page: `{data_dir}/page_renders/{document_id}/{page}_{dpi}.{ext}`
+pub fn page_render_path(
+    data_dir: &Path,
+    document_id: &str,
+    page_number: u32,
+    dpi: u32,
+    format: PageImageFormat,
+) -> PathBuf {
+    data_dir
+        .join("page_renders")
+        .join(document_id)
+        .join(format!("{}_{}.{}", page_number, dpi, format.extension()))
+}
+
+/// Number of pages in a PDF
+pub fn pdf_page_count(pdf_path: &Path, password: Option<&str>) -> Result<u32, ProcessingError> {
+    let pdfium = super::create_pdfium()?;
+    let document = load(&pdfium, pdf_path, password, 0)?;
+    Ok(document.pages().len() as u32)
+}
+
+/// Rasterize a page (1-indexed) at the given DPI
+pub fn render_page(
+    pdf_path: &Path,
+    page_number: u32,
+    dpi: u32,
+    password: Option<&str>,
+) -> Result<RgbaImage, ProcessingError> {
+    let pdfium = super::create_pdfium()?;
+    let document = load(&pdfium, pdf_path, password, page_number)?;
+    let page = document
+        .pages()
+        .get(page_number.saturating_sub(1) as u16)
+        .map_err(|e| render_error(page_number, format!("no such page: {}", e)))?;
+
+    let pixels_per_point = dpi as f32 / 72.0;
+    let config = PdfRenderConfig::new()
+        .set_target_width((page.width().value * pixels_per_point).ceil() as i32)
+        .set_target_height((page.height().value * pixels_per_point).ceil() as i32);
+
+    let bitmap = page
+        .render_with_config(&config)
+        .map_err(|e| render_error(page_number, e.to_string()))?;
+    Ok(bitmap.as_image().to_rgba8())
+}
+
+/// Encode a rendered image to `dest`, replacing it atomically
+pub fn write_page_image(
+    image: &RgbaImage,
+    dest: &Path,
+    format: PageImageFormat,
+) -> Result<(), ProcessingError> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(ProcessingError::Io)?;
+    }
+
+    // Write to a temporary file first so concurrent requests never read a partial image
+    let tmp_path = dest.with_extension(format!("{}.tmp", format.extension()));
+    let file = BufWriter::new(File::create(&tmp_path).map_err(ProcessingError::Io)?);
+    let result = match format {
+        PageImageFormat::Webp => WebPEncoder::new_lossless(file).write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgba8,
+        ),
+        PageImageFormat::Png => PngEncoder::new(file).write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgba8,
+        ),
+    };
+    result.map_err(|e| {
+        ProcessingError::Io(std::io::Error::other(format!(
+            "Failed to encode page image: {}",
+            e
+        )))
+    })?;
+    std::fs::rename(&tmp_path, dest).map_err(ProcessingError::Io)?;
+
+    Ok(())
+}
+
+fn load<'a>(
+    pdfium: &'a Pdfium,
+    pdf_path: &Path,
+    password: Option<&'a str>,
+    page_number: u32,
+) -> Result<PdfDocument<'a>, ProcessingError> {
+    pdfium
+        .load_pdf_from_file(pdf_path, password)
+        .map_err(|e| render_error(page_number, format!("failed to load PDF: {}", e)))
+}
+
+fn render_error(page: u32, message: String) -> ProcessingError {
+    ProcessingError::PageRender { page, message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_page_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = RgbaImage::new(60, 80);
+
+        for format in [PageImageFormat::Webp, PageImageFormat::Png] {
+            let dest = page_render_path(dir.path(), "doc", 3, 150, format);
+            assert!(dest.ends_with(format!("doc/3_150.{}", format.extension())));
+
+            write_page_image(&image, &dest, format).unwrap();
+            let written = image::open(&dest).unwrap();
+            assert_eq!((written.width(), written.height()), (60, 80));
+        }
+    }
+
+    #[test]
+    fn test_page_region_crop() {
+        let page = RgbaImage::new(200, 100);
+        let region = PageRegion {
+            x: 0.5,
+            y: 0.25,
+            width: 0.5,
+            height: 0.5,
+        };
+        let cropped = region.crop(&page).unwrap();
+        assert_eq!((cropped.width(), cropped.height()), (100, 50));
+
+        let outside = PageRegion { x: 0.75, ..region };
+        assert!(outside.crop(&page).is_err());
+    }
+}
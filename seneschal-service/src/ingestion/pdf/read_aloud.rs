@@ -0,0 +1,141 @@
+//! Read-aloud passage candidates from PDF page layout.
+//!
+//! Plain page text loses what sets boxed text apart, so pages are read
+//! again as lines with their left edge and font style (see
+//! `ingestion::read_aloud` for the detection itself).
+
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+use tracing::{info, warn};
+
+use super::layout::{TextBox, find_gutter};
+use crate::error::{ProcessingError, ServiceResult};
+use crate::ingestion::read_aloud::{LayoutLine, detect_layout_passages};
+
+/// Fraction of a segment's letters set in an italic font for it to count as italic
+const ITALIC_FRACTION: f32 = 0.6;
+
+/// Read-aloud candidates in a PDF, as (page number, passage) pairs
+pub fn extract_pdf_read_aloud(
+    path: &Path,
+    password: Option<&str>,
+) -> ServiceResult<Vec<(i32, String)>> {
+    let pdfium = super::create_pdfium()?;
+
+    let document =
+        pdfium
+            .load_pdf_from_file(path, password)
+            .map_err(|e| ProcessingError::TextExtraction {
+                page: 0,
+                source: Box::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Failed to load PDF: {:?}", e),
+                )),
+            })?;
+
+    let mut passages = Vec::new();
+    for (page_index, page) in document.pages().iter().enumerate() {
+        let page_num = page_index as i32 + 1;
+        let text = match page.text() {
+            Ok(text) => text,
+            Err(e) => {
+                warn!(page = page_num, error = ?e, "Failed to get text object for page");
+                continue;
+            }
+        };
+        for passage in detect_layout_passages(&page_lines(&page, &text)) {
+            passages.push((page_num, passage));
+        }
+    }
+
+    info!(candidates = passages.len(), "Found read-aloud candidates");
+    Ok(passages)
+}
+
+/// A page's text lines, column by column, top to bottom
+fn page_lines(page: &PdfPage, text: &PdfPageText) -> Vec<LayoutLine> {
+    let segments: Vec<(TextBox, bool)> = text
+        .segments()
+        .iter()
+        .filter_map(|segment| {
+            let content = segment.text();
+            if content.trim().is_empty() {
+                return None;
+            }
+            let bounds = segment.bounds();
+            let text_box = TextBox {
+                text: content,
+                left: bounds.left().value,
+                right: bounds.right().value,
+                top: bounds.top().value,
+                bottom: bounds.bottom().value,
+            };
+            Some((text_box, is_italic(&segment)))
+        })
+        .collect();
+
+    let boxes: Vec<TextBox> = segments.iter().map(|(b, _)| b.clone()).collect();
+    let gutter = find_gutter(&boxes, page.width().value);
+    let column = |b: &TextBox| usize::from(gutter.is_some_and(|x| b.left >= x));
+
+    let mut sorted: Vec<&(TextBox, bool)> = segments.iter().collect();
+    sorted.sort_by(|(a, _), (b, _)| column(a).cmp(&column(b)).then(b.top.total_cmp(&a.top)));
+
+    // Segments sharing a column and baseline make up a line
+    let mut lines: Vec<Vec<&(TextBox, bool)>> = Vec::new();
+    for segment in sorted {
+        let center = (segment.0.top + segment.0.bottom) / 2.0;
+        match lines.last_mut() {
+            Some(line)
+                if column(&line[0].0) == column(&segment.0)
+                    && ((line[0].0.top + line[0].0.bottom) / 2.0 - center).abs()
+                        <= (line[0].0.top - line[0].0.bottom) / 2.0 =>
+            {
+                line.push(segment)
+            }
+            _ => lines.push(vec![segment]),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|mut line| {
+            line.sort_by(|(a, _), (b, _)| a.left.total_cmp(&b.left));
+            let italic_chars: usize = line
+                .iter()
+                .filter(|(_, italic)| *italic)
+                .map(|(b, _)| b.text.len())
+                .sum();
+            let chars: usize = line.iter().map(|(b, _)| b.text.len()).sum();
+            LayoutLine {
+                text: line
+                    .iter()
+                    .map(|(b, _)| b.text.trim())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                left: line[0].0.left,
+                italic: italic_chars * 2 > chars,
+            }
+        })
+        .collect()
+}
+
+/// Whether most of a segment's letters are set in an italic font
+fn is_italic(segment: &PdfPageTextSegment) -> bool {
+    let Ok(chars) = segment.chars() else {
+        return false;
+    };
+    let (mut letters, mut italic) = (0usize, 0usize);
+    for ch in chars.iter() {
+        if !ch.unicode_char().is_some_and(char::is_alphabetic) {
+            continue;
+        }
+        letters += 1;
+        let font = ch.font_name().to_lowercase();
+        if ch.font_is_italic() || font.contains("italic") || font.contains("oblique") {
+            italic += 1;
+        }
+    }
+    letters > 0 && italic as f32 >= letters as f32 * ITALIC_FRACTION
+}
@@ -1,23 +1,42 @@
-//! PDF text extraction with watermark filtering and bookmark support.
+//! PDF text extraction with column-aware reading order, watermark and page
+//! furniture filtering, and bookmark support.
 
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 use pdfium_render::prelude::*;
 use tracing::{debug, info, warn};
 
 use crate::error::{ProcessingError, ServiceError, ServiceResult};
 
-use crate::ingestion::Section;
+use super::furniture::{self, EdgeText};
+use super::layout::{self, ColumnLayout};
+use crate::ingestion::{ExtractedContent, Section};
+
+/// Per-document options for PDF text extraction
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfTextOptions {
+    /// Remove running headers, footers and page numbers, reporting what was removed
+    pub strip_furniture: bool,
+    /// How to order text on multi-column pages
+    pub layout: ColumnLayout,
+}
 
 /// Extract text content from a PDF with watermark filtering and bookmark-based section titles.
-pub fn extract_pdf(path: &Path) -> ServiceResult<Vec<Section>> {
+///
+/// `password` opens encrypted PDFs.
+pub fn extract_pdf(
+    path: &Path,
+    options: PdfTextOptions,
+    password: Option<&str>,
+) -> ServiceResult<ExtractedContent> {
     let pdfium = super::create_pdfium()?;
 
     let document =
         pdfium
-            .load_pdf_from_file(path, None)
+            .load_pdf_from_file(path, password)
             .map_err(|e| ProcessingError::TextExtraction {
                 page: 0,
                 source: Box::new(std::io::Error::new(
@@ -30,13 +49,15 @@ pub fn extract_pdf(path: &Path) -> ServiceResult<Vec<Section>> {
     info!(pages = page_count, "Processing PDF pages");
 
     // 1. Extract bookmarks for section context
-    let bookmarks = extract_pdf_bookmarks(path);
+    let bookmarks = extract_pdf_bookmarks(path, password);
     if !bookmarks.is_empty() {
         info!(bookmark_count = bookmarks.len(), "Found PDF bookmarks");
     }
 
-    // 2. First pass: extract all page text (raw)
+    // 2. First pass: extract all page text (raw) and the text near page edges
     let mut raw_pages: Vec<(i32, String)> = Vec::new();
+    let mut page_edges: Vec<Vec<EdgeText>> = Vec::new();
+    let mut two_column_pages = 0;
     for (page_index, page) in document.pages().iter().enumerate() {
         let page_num = page_index as i32 + 1;
 
@@ -51,13 +72,41 @@ pub fn extract_pdf(path: &Path) -> ServiceResult<Vec<Section>> {
             }
         })?;
 
-        let page_text = text.all().trim().to_string();
+        let (page_text, two_column) = layout::page_text(&page, &text, options.layout);
+        let page_text = page_text.trim().to_string();
+        if two_column {
+            two_column_pages += 1;
+        }
         if !page_text.is_empty() {
+            if options.strip_furniture {
+                page_edges.push(furniture::edge_texts(&page, &text));
+            }
             raw_pages.push((page_num, page_text));
         }
     }
 
-    // 3. Detect and filter watermarks
+    if two_column_pages > 0 {
+        info!(
+            pages = two_column_pages,
+            layout = ?options.layout,
+            "Reordered two-column pages"
+        );
+    }
+
+    // 3. Detect page furniture and watermarks
+    let (furniture_keys, removed_furniture) = furniture::detect_furniture(&page_edges);
+    if !removed_furniture.is_empty() {
+        info!(
+            furniture_count = removed_furniture.len(),
+            "Detected page furniture to strip"
+        );
+    }
+    if !furniture_keys.is_empty() {
+        for ((_, text), edges) in raw_pages.iter_mut().zip(&page_edges) {
+            *text = furniture::strip_furniture(text, edges, &furniture_keys);
+        }
+    }
+
     let watermarks = detect_watermarks(&raw_pages);
     if !watermarks.is_empty() {
         info!(
@@ -109,7 +158,10 @@ pub fn extract_pdf(path: &Path) -> ServiceResult<Vec<Section>> {
         "PDF text extracted with watermark filtering and section context"
     );
 
-    Ok(sections)
+    Ok(ExtractedContent {
+        sections,
+        removed_furniture,
+    })
 }
 
 /// Extract text from specific pages of a PDF.
@@ -118,6 +170,7 @@ pub fn extract_pdf(path: &Path) -> ServiceResult<Vec<Section>> {
 pub fn extract_pdf_page_text(
     path: &Path,
     page_numbers: &[i32],
+    password: Option<&str>,
 ) -> ServiceResult<HashMap<i32, String>> {
     if page_numbers.is_empty() {
         return Ok(HashMap::new());
@@ -126,7 +179,7 @@ pub fn extract_pdf_page_text(
     let pdfium = Pdfium::default();
     let document =
         pdfium
-            .load_pdf_from_file(path, None)
+            .load_pdf_from_file(path, password)
             .map_err(|e| ProcessingError::TextExtraction {
                 page: 0,
                 source: Box::new(std::io::Error::new(
@@ -224,12 +277,26 @@ fn remove_watermarks(text: &str, watermarks: &HashSet<String>) -> String {
 /// Extract bookmark structure from PDF using qpdf.
 ///
 /// Returns a map of page numbers to section titles.
-fn extract_pdf_bookmarks(path: &Path) -> BTreeMap<i32, String> {
+fn extract_pdf_bookmarks(path: &Path, password: Option<&str>) -> BTreeMap<i32, String> {
     let mut page_sections: BTreeMap<i32, String> = BTreeMap::new();
 
-    let output = Command::new("qpdf")
-        .args(["--json", path.to_str().unwrap_or("")])
-        .output();
+    // The password goes over stdin so it never shows up in the process list
+    let mut command = Command::new("qpdf");
+    command.args(["--json", path.to_str().unwrap_or("")]);
+    let output = match password {
+        Some(password) => command
+            .arg("--password-file=-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    writeln!(stdin, "{}", password)?;
+                }
+                child.wait_with_output()
+            }),
+        None => command.output(),
+    };
 
     if let Ok(output) = output
         && output.status.success()
@@ -0,0 +1,106 @@
+//! Text preprocessing for embeddings.
+//!
+//! Raw PDFium text carries layout noise that hurts retrieval: words split
+//! across line breaks, ligatures and compatibility characters. Hyphenation
+//! repair runs on sections before chunking (chunks no longer have line
+//! breaks); character normalization runs on the text sent to the embedding
+//! model, so stored chunk text stays as extracted. Running headers and
+//! footers are stripped earlier, during PDF extraction.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use super::Section;
+use crate::config::EmbeddingsConfig;
+
+/// A word broken across a line with a hyphen (or the soft hyphen and
+/// control characters PDFium emits in its place), continuing in lowercase
+static LINE_BREAK_HYPHEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\p{L})[-\u{00AD}\u{0002}][ \t]*\r?\n[ \t]*(\p{Ll})").unwrap());
+
+/// Enabled preprocessing steps
+#[derive(Debug, Clone, Copy)]
+pub struct TextPreprocessing {
+    pub normalize_unicode: bool,
+    pub repair_hyphenation: bool,
+    pub lowercase: bool,
+}
+
+impl From<&EmbeddingsConfig> for TextPreprocessing {
+    fn from(config: &EmbeddingsConfig) -> Self {
+        Self {
+            normalize_unicode: config.normalize_unicode,
+            repair_hyphenation: config.repair_hyphenation,
+            lowercase: config.lowercase,
+        }
+    }
+}
+
+impl TextPreprocessing {
+    /// Clean section text before it is chunked
+    pub fn clean_sections(&self, sections: &mut [Section]) {
+        if self.repair_hyphenation {
+            for section in sections.iter_mut() {
+                if let Cow::Owned(repaired) =
+                    LINE_BREAK_HYPHEN.replace_all(&section.content, "$1$2")
+                {
+                    section.content = repaired;
+                }
+            }
+        }
+    }
+
+    /// The text to embed for a chunk or query
+    pub fn embedding_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        if self.normalize_unicode {
+            text = Cow::Owned(text.nfkc().collect());
+        }
+        if self.lowercase {
+            text = Cow::Owned(text.to_lowercase());
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_preprocessing() {
+        let preprocessing = TextPreprocessing {
+            normalize_unicode: true,
+            repair_hyphenation: true,
+            lowercase: true,
+        };
+        let mut sections = vec![Section {
+            title: None,
+            content: "A Class A starport has ship-\nyards and Well-\nKnown fuel.".to_string(),
+            page_number: Some(13),
+        }];
+        preprocessing.clean_sections(&mut sections);
+        // Capitalized continuations are real compound words
+        assert_eq!(
+            sections[0].content,
+            "A Class A starport has shipyards and Well-\nKnown fuel."
+        );
+
+        assert_eq!(
+            preprocessing.embedding_text("The \u{FB01}rst Imperium"),
+            "the first imperium"
+        );
+        let untouched = TextPreprocessing {
+            normalize_unicode: false,
+            lowercase: false,
+            ..preprocessing
+        };
+        assert!(matches!(
+            untouched.embedding_text("The Imperium"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+//! Read-aloud text detection.
+//!
+//! Adventures set the text a GM reads to the players apart from the rest of
+//! the page, usually as a boxed, indented or italic passage:
+//!
+//! ```text
+//! The airlock cycles open onto a dim corridor. Emergency lighting
+//! paints the bulkheads red, and somewhere ahead a hatch bangs
+//! against its frame in time with the ship's failing gravity.
+//! ```
+//!
+//! PDF passages are found from page layout (runs of italic lines, or lines
+//! indented from the page's body margin); other formats rely on blockquotes
+//! and "read aloud" cues. Detection is deliberately permissive; candidates
+//! are verified by an LLM before being stored (see
+//! `service::document_processing::read_aloud`).
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Tag added to chunks holding read-aloud text
+pub const READ_ALOUD_TAG: &str = "read-aloud";
+
+/// A line introducing read-aloud text ("Read the following aloud:")
+static CUE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(read\s+(?:or\s+paraphrase\s+)?(?:the\s+following|this)(?:\s+(?:aloud|to\s+the\s+(?:players|party)))?|read[\s-]aloud)\b[^\n]*:\s*$",
+    )
+    .unwrap()
+});
+
+/// Minimum lines in a run of italic or indented PDF lines
+const MIN_LINES: usize = 2;
+
+/// Passages shorter than this are headings, captions or callouts
+const MIN_CHARS: usize = 80;
+
+/// Line left edges are bucketed to this many points when finding margins
+const MARGIN_BUCKET: f32 = 4.0;
+
+/// A margin must start at least this fraction of a page's lines
+const MIN_MARGIN_FRACTION: f32 = 0.15;
+
+/// Indentation from a margin (in points) that sets a line apart
+const MIN_INDENT: f32 = 8.0;
+
+/// Further in than this is a table column or a second text column
+const MAX_INDENT: f32 = 60.0;
+
+/// Characters of a passage compared when finding the chunk holding it
+const MATCH_PREFIX_CHARS: usize = 60;
+
+/// A PDF text line with its left edge and whether it is set in italics
+#[derive(Debug, Clone)]
+pub struct LayoutLine {
+    pub text: String,
+    pub left: f32,
+    pub italic: bool,
+}
+
+/// Read-aloud passages on a PDF page, from its lines in reading order
+pub fn detect_layout_passages(lines: &[LayoutLine]) -> Vec<String> {
+    let margins = body_margins(lines);
+    let set_apart = |line: &LayoutLine| line.italic || is_indented(line.left, &margins);
+
+    let mut passages = Vec::new();
+    let mut run: Vec<&str> = Vec::new();
+    for line in lines {
+        if set_apart(line) && !line.text.trim().is_empty() {
+            run.push(line.text.trim());
+        } else {
+            push_run(&mut passages, &mut run, MIN_LINES);
+        }
+    }
+    push_run(&mut passages, &mut run, MIN_LINES);
+    passages
+}
+
+/// Read-aloud passages in plain or Markdown text: blockquotes, and the
+/// paragraph after a "read aloud" cue
+pub fn detect_text_passages(text: &str) -> Vec<String> {
+    let mut passages = Vec::new();
+    let mut quote: Vec<&str> = Vec::new();
+    let mut cued: Option<Vec<&str>> = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if let Some(quoted) = trimmed.strip_prefix('>') {
+            quote.push(quoted.trim());
+            continue;
+        }
+        push_run(&mut passages, &mut quote, 1);
+
+        match cued.as_mut() {
+            Some(paragraph) if !trimmed.is_empty() => paragraph.push(trimmed),
+            Some(paragraph) if !paragraph.is_empty() => {
+                push_run(&mut passages, paragraph, 1);
+                cued = None;
+            }
+            _ => {}
+        }
+        if CUE_RE.is_match(trimmed) {
+            cued = Some(Vec::new());
+        }
+    }
+    push_run(&mut passages, &mut quote, 1);
+    if let Some(mut paragraph) = cued {
+        push_run(&mut passages, &mut paragraph, 1);
+    }
+    passages
+}
+
+/// Whether a chunk's text contains (the start of) a passage, ignoring
+/// whitespace, punctuation and case
+pub fn passage_in(content: &str, passage: &str) -> bool {
+    let prefix: String = normalize(passage)
+        .chars()
+        .take(MATCH_PREFIX_CHARS)
+        .collect();
+    !prefix.is_empty() && normalize(content).contains(&prefix)
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Keep a run of lines as a passage if it is long enough, emptying the run
+fn push_run(passages: &mut Vec<String>, run: &mut Vec<&str>, min_lines: usize) {
+    let lines = std::mem::take(run);
+    if lines.len() < min_lines {
+        return;
+    }
+    let passage = join_lines(&lines);
+    if passage.len() >= MIN_CHARS {
+        passages.push(passage);
+    }
+}
+
+/// Join lines into running text, rejoining words hyphenated across lines
+fn join_lines(lines: &[&str]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        let hyphenated = text.ends_with('-')
+            && line.chars().next().is_some_and(|c| c.is_lowercase())
+            && text[..text.len() - 1]
+                .chars()
+                .last()
+                .is_some_and(|c| c.is_alphabetic());
+        if hyphenated {
+            text.pop();
+        } else if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+    }
+    text
+}
+
+/// Left edges where many upright lines start: the page's text columns.
+/// Frequent edges just inside another are indented passages, not columns.
+fn body_margins(lines: &[LayoutLine]) -> Vec<f32> {
+    let mut buckets: Vec<(i32, usize)> = Vec::new();
+    for line in lines.iter().filter(|l| !l.italic) {
+        let bucket = (line.left / MARGIN_BUCKET).round() as i32;
+        match buckets.iter_mut().find(|(b, _)| *b == bucket) {
+            Some((_, count)) => *count += 1,
+            None => buckets.push((bucket, 1)),
+        }
+    }
+    let min_count = ((lines.len() as f32 * MIN_MARGIN_FRACTION).ceil() as usize).max(3);
+    let mut frequent: Vec<f32> = buckets
+        .into_iter()
+        .filter(|(_, count)| *count >= min_count)
+        .map(|(bucket, _)| bucket as f32 * MARGIN_BUCKET)
+        .collect();
+    frequent.sort_by(f32::total_cmp);
+
+    let mut margins: Vec<f32> = Vec::new();
+    for edge in frequent {
+        if margins.last().is_none_or(|m| edge - m > MAX_INDENT) {
+            margins.push(edge);
+        }
+    }
+    margins
+}
+
+/// Whether a line starts inside a margin rather than at one
+fn is_indented(left: f32, margins: &[f32]) -> bool {
+    let nearest = margins
+        .iter()
+        .filter(|m| **m <= left + MARGIN_BUCKET)
+        .map(|m| left - m)
+        .fold(f32::INFINITY, f32::min);
+    (MIN_INDENT..=MAX_INDENT).contains(&nearest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str, left: f32, italic: bool) -> LayoutLine {
+        LayoutLine {
+            text: text.to_string(),
+            left,
+            italic,
+        }
+    }
+
+    #[test]
+    fn test_detect_layout_passages() {
+        let lines = vec![
+            line("THE DERELICT", 50.0, false),
+            line("When the Travellers board the ship, read", 50.0, false),
+            line("the following:", 50.0, false),
+            line("The airlock cycles open onto a dim corri-", 70.0, false),
+            line("dor. Emergency lighting paints the bulk-", 70.0, false),
+            line("heads red, and a hatch bangs somewhere.", 70.0, false),
+            line("The hatch leads to the engineering deck,", 50.0, false),
+            line("where the crew made their last stand.", 50.0, false),
+            line("Anyone searching the deck finds a", 50.0, false),
+            line("They found us. Whatever you do, do not", 50.0, true),
+            line("open the cargo bay, no matter what you hear.", 50.0, true),
+            line("12", 300.0, false),
+        ];
+
+        assert_eq!(
+            detect_layout_passages(&lines),
+            vec![
+                "The airlock cycles open onto a dim corridor. Emergency lighting paints \
+                 the bulkheads red, and a hatch bangs somewhere.",
+                "They found us. Whatever you do, do not open the cargo bay, no matter what you hear.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_text_passages() {
+        let text = "## Arrival\n\nRead the following aloud:\n\nThe starport is a sprawl of \
+                    landing pads and rust-streaked hangars, shimmering in the heat of \
+                    Walston's sun.\n\nThe port warden arrives shortly after.\n\n\
+                    > A tall woman in a faded uniform strides across the pad, a\n\
+                    > datapad in one hand and a sidearm on her hip.\n";
+
+        let passages = detect_text_passages(text);
+        assert_eq!(passages.len(), 2);
+        assert!(passages[0].starts_with("The starport is a sprawl"));
+        assert!(passages[1].ends_with("a sidearm on her hip."));
+
+        let chunk = "Arrival\nThe starport is a sprawl of landing pads and rust-streaked \
+                     hangars, shimmering in the heat of Walston's sun.";
+        assert!(passage_in(chunk, &passages[0]));
+        assert!(!passage_in(chunk, &passages[1]));
+    }
+}
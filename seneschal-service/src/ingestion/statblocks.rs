@@ -0,0 +1,348 @@
+//! Mongoose Traveller 2e stat block detection.
+//!
+//! Scenario and supplement PDFs present NPCs and creatures as labelled blocks:
+//!
+//! ```text
+//! Captain Marla Venn
+//! STR 7 (+0) DEX 9 (+1) END 8 (+0) INT 10 (+1) EDU 9 (+1) SOC 6 (+0)
+//! SKILLS Pilot (spacecraft) 2, Gun Combat (slug) 1, Leadership 1
+//! WEAPONS Autopistol (3D-3)
+//! ARMOUR Mesh (+2)
+//! ```
+//!
+//! Creatures use `HITS`/`SPEED`/`ATTACKS` instead of characteristics. Detection
+//! here is regex-based and deliberately permissive; candidates are validated by
+//! an LLM before being stored (see `service::stat_blocks`).
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Characteristic abbreviation followed by its value and optional DM, e.g. `DEX 9 (+1)`
+static CHARACTERISTIC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(STR|DEX|END|INT|EDU|SOC|PSI)\s*:?\s*(\d{1,2})\b(?:\s*\(\s*[+\-–]?\d\s*\))?")
+        .unwrap()
+});
+
+/// Creature hits line, e.g. `HITS 24`
+static HITS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^\s*hits\s*:?\s*(\d+)").unwrap());
+
+/// A labelled stat block line, e.g. `SKILLS Pilot 2, Recon 1`
+static LABEL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^\s*(skills|equipment|weapons?|armou?r|traits|attacks|behaviou?r|speed|hits|species|gender|age|career|animal)\b\s*:?\s*(.*)$",
+    )
+    .unwrap()
+});
+
+/// Skill with a trailing level, e.g. `Gun Combat (slug) 1`
+static SKILL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.+?)\s+(\d{1,2})$").unwrap());
+
+/// Minimum distinct characteristics on a line to treat it as an NPC stat line
+const MIN_CHARACTERISTICS: usize = 4;
+
+/// Lines searched above an anchor for the block's name
+const NAME_LOOKBACK: usize = 3;
+
+/// Maximum lines after an anchor that belong to the same block
+const MAX_BLOCK_LINES: usize = 15;
+
+/// Names longer than this are treated as prose, not a heading
+const MAX_NAME_LEN: usize = 60;
+
+/// Kind of stat block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatBlockKind {
+    Npc,
+    Creature,
+}
+
+impl StatBlockKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatBlockKind::Npc => "npc",
+            StatBlockKind::Creature => "creature",
+        }
+    }
+
+    pub fn from_str_lossy(value: &str) -> Self {
+        match value {
+            "creature" => StatBlockKind::Creature,
+            _ => StatBlockKind::Npc,
+        }
+    }
+}
+
+/// A stat block found in text, before validation
+#[derive(Debug, Clone)]
+pub struct StatBlockCandidate {
+    pub name: String,
+    pub kind: StatBlockKind,
+    /// Structured fields: characteristics, skills, weapons, etc.
+    pub data: Value,
+    /// The lines the block was parsed from
+    pub raw_text: String,
+}
+
+/// Find stat blocks in a chunk of text.
+///
+/// `fallback_name` (usually the section title) is used when no heading precedes the block.
+pub fn detect_stat_blocks(text: &str, fallback_name: Option<&str>) -> Vec<StatBlockCandidate> {
+    let lines: Vec<&str> = text.lines().collect();
+    let anchors: Vec<(usize, StatBlockKind)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| anchor_kind(line).map(|kind| (i, kind)))
+        .collect();
+
+    let mut blocks = Vec::new();
+    let mut previous_end = 0;
+
+    for (n, &(anchor, kind)) in anchors.iter().enumerate() {
+        // A creature's HITS line inside an already-parsed block isn't a new block
+        if anchor < previous_end {
+            continue;
+        }
+
+        let next_anchor = anchors.get(n + 1).map(|&(i, _)| i).unwrap_or(lines.len());
+        let start = anchor.saturating_sub(NAME_LOOKBACK).max(previous_end);
+        let name_line = (start..anchor)
+            .rev()
+            .find(|&i| is_name_line(lines[i]))
+            .filter(|&i| anchor - i <= NAME_LOOKBACK);
+
+        let end = block_end(&lines, anchor, next_anchor);
+        previous_end = end;
+
+        let name = name_line
+            .map(|i| lines[i].trim().to_string())
+            .or_else(|| fallback_name.map(|n| n.trim().to_string()))
+            .filter(|n| !n.is_empty());
+        let Some(name) = name else {
+            continue;
+        };
+
+        let block_start = name_line.unwrap_or(anchor);
+        let block_lines = &lines[block_start..end];
+        let data = parse_block(&name, kind, &block_lines[(anchor - block_start)..]);
+
+        blocks.push(StatBlockCandidate {
+            name,
+            kind,
+            data,
+            raw_text: block_lines.join("\n"),
+        });
+    }
+
+    blocks
+}
+
+/// Whether a line starts a stat block, and which kind
+fn anchor_kind(line: &str) -> Option<StatBlockKind> {
+    let distinct: std::collections::HashSet<&str> = CHARACTERISTIC_RE
+        .captures_iter(line)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .collect();
+    if distinct.len() >= MIN_CHARACTERISTICS {
+        return Some(StatBlockKind::Npc);
+    }
+    if HITS_RE.is_match(line) {
+        return Some(StatBlockKind::Creature);
+    }
+    None
+}
+
+fn is_name_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && trimmed.len() <= MAX_NAME_LEN
+        && !LABEL_RE.is_match(trimmed)
+        && anchor_kind(trimmed).is_none()
+        && !trimmed.ends_with('.')
+}
+
+/// Index one past the last line of the block starting at `anchor`
+fn block_end(lines: &[&str], anchor: usize, next_anchor: usize) -> usize {
+    let limit = next_anchor.min(anchor + MAX_BLOCK_LINES).min(lines.len());
+    let mut end = anchor + 1;
+
+    for (i, line) in lines.iter().enumerate().take(limit).skip(anchor + 1) {
+        if line.trim().is_empty() {
+            break;
+        }
+        if !LABEL_RE.is_match(line) && !is_continuation(lines[i - 1], line) {
+            break;
+        }
+        end = i + 1;
+    }
+
+    // The next block's name line belongs to it, not to this block
+    if end == next_anchor && end > anchor + 1 && is_name_line(lines[end - 1]) {
+        end -= 1;
+    }
+    end
+}
+
+/// Wrapped text from the previous field: starts lowercase or follows a comma
+fn is_continuation(previous: &str, line: &str) -> bool {
+    previous.trim_end().ends_with(',')
+        || line
+            .trim_start()
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_lowercase() || c == '(' || c.is_ascii_digit())
+}
+
+fn parse_block(name: &str, kind: StatBlockKind, lines: &[&str]) -> Value {
+    let mut characteristics = BTreeMap::new();
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for line in lines {
+        for caps in CHARACTERISTIC_RE.captures_iter(line) {
+            if let Ok(value) = caps[2].parse::<u8>() {
+                characteristics.insert(caps[1].to_string(), value);
+            }
+        }
+
+        if let Some(caps) = LABEL_RE.captures(line) {
+            fields.push((normalize_label(&caps[1]), caps[2].trim().to_string()));
+        } else if let Some((_, value)) = fields.last_mut()
+            && anchor_kind(line).is_none()
+        {
+            value.push(' ');
+            value.push_str(line.trim());
+        }
+    }
+
+    let mut data = serde_json::json!({
+        "name": name,
+        "kind": kind.as_str(),
+    });
+    if !characteristics.is_empty() {
+        data["characteristics"] = serde_json::json!(characteristics);
+    }
+
+    for (label, value) in fields {
+        if value.is_empty() {
+            continue;
+        }
+        data[label.as_str()] = match label.as_str() {
+            "skills" => Value::Array(parse_skills(&value)),
+            "weapons" | "equipment" | "traits" | "attacks" => {
+                Value::Array(split_list(&value).into_iter().map(Value::String).collect())
+            }
+            "hits" | "speed" => value
+                .split_whitespace()
+                .next()
+                .and_then(|n| n.trim_end_matches('m').parse::<u32>().ok())
+                .map(Value::from)
+                .unwrap_or(Value::String(value)),
+            _ => Value::String(value),
+        };
+    }
+
+    data
+}
+
+fn normalize_label(label: &str) -> String {
+    match label.to_lowercase().as_str() {
+        "weapon" => "weapons".to_string(),
+        "armor" => "armour".to_string(),
+        "behavior" => "behaviour".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Split a comma/semicolon list, ignoring separators inside parentheses
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' | ';' if depth == 0 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+
+    items
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_skills(value: &str) -> Vec<Value> {
+    split_list(value)
+        .into_iter()
+        .map(|skill| match SKILL_RE.captures(&skill) {
+            Some(caps) => serde_json::json!({
+                "name": caps[1].trim(),
+                "level": caps[2].parse::<u8>().unwrap_or(0),
+            }),
+            None => serde_json::json!({ "name": skill, "level": 0 }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_npc_stat_block() {
+        let text = "The captain greets the Travellers.\n\
+            Captain Marla Venn\n\
+            STR 7 (+0) DEX 9 (+1) END 8 (+0) INT 10 (+1) EDU 9 (+1) SOC 6 (+0)\n\
+            SKILLS Pilot (spacecraft) 2, Gun Combat (slug) 1,\n\
+            Leadership 1\n\
+            WEAPONS Autopistol (3D-3)\n\
+            ARMOUR Mesh (+2)\n\
+            \n\
+            She has no patience for fools.";
+
+        let blocks = detect_stat_blocks(text, None);
+        assert_eq!(blocks.len(), 1);
+
+        let block = &blocks[0];
+        assert_eq!(block.name, "Captain Marla Venn");
+        assert_eq!(block.kind, StatBlockKind::Npc);
+        assert_eq!(block.data["characteristics"]["INT"], 10);
+        assert_eq!(block.data["skills"][0]["name"], "Pilot (spacecraft)");
+        assert_eq!(block.data["skills"][2]["name"], "Leadership");
+        assert_eq!(block.data["weapons"][0], "Autopistol (3D-3)");
+        assert!(!block.raw_text.contains("greets"));
+    }
+
+    #[test]
+    fn test_detect_creature_with_fallback_name() {
+        let text = "HITS 24\nSPEED 8m\nSKILLS Melee (natural) 2, Recon 1\nATTACKS Claws (2D)\nBEHAVIOUR Carnivore, Pouncer";
+
+        let blocks = detect_stat_blocks(text, Some("Sand Stalker"));
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].name, "Sand Stalker");
+        assert_eq!(blocks[0].kind, StatBlockKind::Creature);
+        assert_eq!(blocks[0].data["hits"], 24);
+        assert_eq!(blocks[0].data["speed"], 8);
+        assert_eq!(blocks[0].data["behaviour"], "Carnivore, Pouncer");
+    }
+
+    #[test]
+    fn test_prose_is_not_a_stat_block() {
+        let text = "Roll 2D and add your INT DM. On 8+ the Traveller succeeds.";
+        assert!(detect_stat_blocks(text, Some("Rules")).is_empty());
+    }
+}
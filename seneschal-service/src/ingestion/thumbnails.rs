@@ -0,0 +1,114 @@
+//! Downscaled variants of extracted images.
+//!
+//! Extracted images are stored losslessly at full resolution, which can be
+//! several megabytes each. Browsing UIs request smaller variants, generated on
+//! first use and cached on disk next to the data directory.
+
+use std::path::{Path, PathBuf};
+
+use image::ImageEncoder;
+use image::codecs::webp::WebPEncoder;
+use serde::Deserialize;
+
+use crate::error::ProcessingError;
+
+/// Requested size variant of an image
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageSize {
+    Thumb,
+    Medium,
+    #[default]
+    Full,
+}
+
+impl ImageSize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageSize::Thumb => "thumb",
+            ImageSize::Medium => "medium",
+            ImageSize::Full => "full",
+        }
+    }
+}
+
+/// Cache location for a downscaled variant: `{data_dir}/thumbnails/{document_id}/{image_id}_{max_edge}.webp`
+///
+/// The size is part of the filename so changing the configured sizes doesn't serve stale variants.
+pub fn thumbnail_path(
+    data_dir: &Path,
+    document_id: &str,
+    image_id: &str,
+    max_edge: u32,
+) -> PathBuf {
+    data_dir
+        .join("thumbnails")
+        .join(document_id)
+        .join(format!("{}_{}.webp", image_id, max_edge))
+}
+
+/// Write a copy of `source` scaled so its longest edge is at most `max_edge`.
+///
+/// Images already within the limit are re-encoded unchanged.
+pub fn generate_thumbnail(
+    source: &Path,
+    dest: &Path,
+    max_edge: u32,
+) -> Result<(), ProcessingError> {
+    let img = image::open(source).map_err(|e| {
+        ProcessingError::Io(std::io::Error::other(format!(
+            "Failed to decode {}: {}",
+            source.display(),
+            e
+        )))
+    })?;
+
+    let scaled = if img.width() > max_edge || img.height() > max_edge {
+        img.thumbnail(max_edge, max_edge)
+    } else {
+        img
+    };
+    let rgba = scaled.to_rgba8();
+
+    let dir = dest.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir).map_err(ProcessingError::Io)?;
+
+    // Write to a uniquely named temporary file first so concurrent requests
+    // never read a partial image or write over each other's
+    let tmp = tempfile::NamedTempFile::new_in(dir).map_err(ProcessingError::Io)?;
+    WebPEncoder::new_lossless(tmp.as_file())
+        .write_image(
+            rgba.as_raw(),
+            rgba.width(),
+            rgba.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| {
+            ProcessingError::Io(std::io::Error::other(format!(
+                "Failed to encode thumbnail: {}",
+                e
+            )))
+        })?;
+    tmp.persist(dest)
+        .map_err(|e| ProcessingError::Io(e.error))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_thumbnail_preserves_aspect_ratio() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source.png");
+        image::RgbaImage::new(400, 200).save(&source).unwrap();
+
+        let dest = thumbnail_path(dir.path(), "doc", "img", 100);
+        generate_thumbnail(&source, &dest, 100).unwrap();
+
+        let thumb = image::open(&dest).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (100, 50));
+    }
+}
@@ -0,0 +1,220 @@
+//! Dated event detection for campaign timelines.
+//!
+//! Traveller sources date events on the Imperial calendar, either as a full
+//! date (`day-year`, e.g. `187-1105`) or as a year mentioned in prose ("in
+//! 1105", "1105 Imperial"). Each sentence with a date becomes one event per
+//! date it mentions.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Serialize, Serializer};
+
+/// A full Imperial date: three-digit day of the year, then the year
+static DAY_YEAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(\d{3})-(\d{3,4})\b").unwrap());
+
+/// A year introduced by a preposition, or followed by an era marker
+static PROSE_YEAR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:in|during|by|since|until|before|after|circa|around|year)\s+(-?\d{3,4})\b(?:\s+(\p{L}+))?|(?:^|\s)(-?\d{3,4})\s*(?:Imperial|Imp\.|IC)\b",
+    )
+    .unwrap()
+});
+
+/// Words after a number that make it a quantity, not a year
+const QUANTITY_WORDS: &[&str] = &[
+    "credits", "cr", "mcr", "kcr", "tons", "ton", "dt", "km", "kg", "parsecs", "people", "troops",
+    "ships", "worlds", "metres", "meters", "years", "hours", "days", "weeks",
+];
+
+/// Days in an Imperial year
+const DAYS_PER_YEAR: u16 = 365;
+
+/// Longer sentences are cut to this many characters
+const MAX_DESCRIPTION_LEN: usize = 400;
+
+/// A date on the Imperial calendar; `day` is 1-365 when known
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ImperialDate {
+    pub year: i32,
+    pub day: Option<u16>,
+}
+
+impl ImperialDate {
+    /// Parse "1105" or "187-1105"
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some((day, year)) = value.split_once('-').filter(|(day, _)| !day.is_empty()) {
+            let day: u16 = day.parse().ok()?;
+            return (1..=DAYS_PER_YEAR).contains(&day).then_some(Self {
+                year: year.parse().ok()?,
+                day: Some(day),
+            });
+        }
+        Some(Self {
+            year: value.parse().ok()?,
+            day: None,
+        })
+    }
+
+    /// Orders dates; a year without a day sorts before its first day
+    pub fn sort_key(&self) -> i64 {
+        self.year as i64 * 1000 + self.day.unwrap_or(0) as i64
+    }
+
+    /// The sort key of the last day in this date's span
+    pub fn end_sort_key(&self) -> i64 {
+        self.year as i64 * 1000 + self.day.unwrap_or(999) as i64
+    }
+
+    /// The date a number of days later (or earlier); a bare year counts
+    /// from its first day
+    pub fn add_days(&self, days: i64) -> Self {
+        let per_year = DAYS_PER_YEAR as i64;
+        let ordinal = self.year as i64 * per_year + self.day.unwrap_or(1) as i64 - 1 + days;
+        Self {
+            year: ordinal.div_euclid(per_year) as i32,
+            day: Some(ordinal.rem_euclid(per_year) as u16 + 1),
+        }
+    }
+}
+
+impl fmt::Display for ImperialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.day {
+            Some(day) => write!(f, "{:03}-{}", day, self.year),
+            None => write!(f, "{}", self.year),
+        }
+    }
+}
+
+impl Serialize for ImperialDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// An event found in text
+#[derive(Debug, Clone)]
+pub struct DatedEvent {
+    pub date: ImperialDate,
+    /// The sentence the date appeared in
+    pub description: String,
+}
+
+/// Find dated events in a chunk of text
+pub fn detect_dated_events(text: &str) -> Vec<DatedEvent> {
+    let mut events = Vec::new();
+    for sentence in sentences(text) {
+        let mut dates: Vec<ImperialDate> = DAY_YEAR
+            .captures_iter(sentence)
+            .filter_map(|captures| ImperialDate::parse(&captures[0]))
+            .collect();
+        let masked = DAY_YEAR.replace_all(sentence, "");
+        dates.extend(PROSE_YEAR.captures_iter(&masked).filter_map(|captures| {
+            let year = captures.get(1).or_else(|| captures.get(3))?;
+            if captures
+                .get(2)
+                .is_some_and(|next| QUANTITY_WORDS.contains(&next.as_str().to_lowercase().as_str()))
+            {
+                return None;
+            }
+            ImperialDate::parse(year.as_str())
+        }));
+
+        let mut seen = Vec::new();
+        for date in dates {
+            if seen.contains(&date) {
+                continue;
+            }
+            seen.push(date);
+            events.push(DatedEvent {
+                date,
+                description: truncate_description(sentence),
+            });
+        }
+    }
+    events
+}
+
+/// Split text into sentences on terminal punctuation and blank lines,
+/// with line breaks inside a sentence left as they are
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    for paragraph in text.split("\n\n") {
+        let mut start = 0;
+        let chars: Vec<(usize, char)> = paragraph.char_indices().collect();
+        for (i, &(index, c)) in chars.iter().enumerate() {
+            let ends = matches!(c, '.' | '!' | '?')
+                && chars
+                    .get(i + 1)
+                    .is_none_or(|(_, next)| next.is_whitespace());
+            if ends {
+                let end = index + c.len_utf8();
+                sentences.push(paragraph[start..end].trim());
+                start = end;
+            }
+        }
+        sentences.push(paragraph[start..].trim());
+    }
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+fn truncate_description(sentence: &str) -> String {
+    let sentence = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+    match sentence.char_indices().nth(MAX_DESCRIPTION_LEN) {
+        Some((index, _)) => format!("{}…", &sentence[..index]),
+        None => sentence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_dated_events() {
+        let text = "The Fifth Frontier War began on 187-1107 when Zhodani fleets crossed \
+                    the border. In 1105 the Regina starport was expanded. The refit cost \
+                    1200 credits. By 1110 Imperial the fleet had 300 ships.\n\n\
+                    Nothing happened here.";
+
+        let events = detect_dated_events(text);
+        let dates: Vec<String> = events.iter().map(|e| e.date.to_string()).collect();
+        assert_eq!(dates, vec!["187-1107", "1105", "1110"]);
+        assert_eq!(
+            events[1].description,
+            "In 1105 the Regina starport was expanded."
+        );
+
+        assert_eq!(
+            ImperialDate::parse("001-1105"),
+            Some(ImperialDate {
+                year: 1105,
+                day: Some(1)
+            })
+        );
+        assert_eq!(ImperialDate::parse("-2400").unwrap().day, None);
+        assert!(ImperialDate::parse("400-1105").is_none());
+        assert_eq!(
+            ImperialDate::parse("360-1105")
+                .unwrap()
+                .add_days(7)
+                .to_string(),
+            "002-1106"
+        );
+        assert_eq!(
+            ImperialDate::parse("003-1106")
+                .unwrap()
+                .add_days(-3)
+                .to_string(),
+            "365-1105"
+        );
+        assert!(
+            ImperialDate::parse("1105").unwrap().sort_key()
+                < ImperialDate::parse("001-1105").unwrap().sort_key()
+        );
+    }
+}
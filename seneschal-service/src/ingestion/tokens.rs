@@ -0,0 +1,254 @@
+//! Circular token cutouts from character art.
+//!
+//! FVTT tokens are square images with the figure inside a circle. The
+//! subject is found from the alpha channel when the art has transparency,
+//! or by its difference from the border color otherwise. The crop is a
+//! square across the subject's width, anchored at its top so heads stay in
+//! frame, then masked to a circle with an optional ring.
+
+use std::fs::File;
+use std::path::Path;
+
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{ImageEncoder, Rgba, RgbaImage};
+
+use crate::error::ProcessingError;
+
+/// Token edge length when none is given
+pub const DEFAULT_TOKEN_SIZE: u32 = 400;
+
+/// Allowed token edge lengths (FVTT renders 100px per grid square; art is
+/// usually made at 2-5x that for zoom)
+pub const TOKEN_SIZE_RANGE: (u32, u32) = (100, 1024);
+
+/// Pixels at least this opaque belong to the subject
+const ALPHA_THRESHOLD: u8 = 32;
+
+/// Art with less than this fraction of transparent pixels is treated as opaque
+const MIN_TRANSPARENT_FRACTION: f64 = 0.05;
+
+/// Summed RGB difference from the background color that marks a subject pixel
+const BACKGROUND_DISTANCE: u32 = 60;
+
+/// A subject smaller than this fraction of the image is noise; the whole image is used
+const MIN_SUBJECT_FRACTION: f64 = 0.02;
+
+/// Space around the subject, as a fraction of the crop
+const PADDING: f32 = 0.06;
+
+/// Ring width as a fraction of the token size
+const RING_WIDTH: f32 = 1.0 / 32.0;
+
+/// A rectangle in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Options for a token cutout
+#[derive(Debug, Clone, Copy)]
+pub struct TokenOptions {
+    pub size: u32,
+    /// Ring drawn around the edge of the circle
+    pub ring_color: Option<Rgba<u8>>,
+}
+
+/// Parse a ring color given as `#rrggbb`
+pub fn parse_ring_color(value: &str) -> Option<Rgba<u8>> {
+    let hex = value.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Rgba([channel(0)?, channel(2)?, channel(4)?, 255]))
+}
+
+/// The bounding box of the subject of a piece of art
+pub fn subject_bounds(image: &RgbaImage) -> Bounds {
+    let whole = Bounds {
+        x: 0,
+        y: 0,
+        width: image.width(),
+        height: image.height(),
+    };
+    let total = image.width() as f64 * image.height() as f64;
+    if total == 0.0 {
+        return whole;
+    }
+
+    let transparent = image.pixels().filter(|p| p[3] < ALPHA_THRESHOLD).count() as f64;
+    let bounds = if transparent / total >= MIN_TRANSPARENT_FRACTION {
+        bounding_box(image, |p| p[3] >= ALPHA_THRESHOLD)
+    } else {
+        let background = border_color(image);
+        bounding_box(image, |p| {
+            color_distance(p, &background) > BACKGROUND_DISTANCE
+        })
+    };
+
+    bounds
+        .filter(|b| b.width as f64 * b.height as f64 >= total * MIN_SUBJECT_FRACTION)
+        .unwrap_or(whole)
+}
+
+/// A circular token of the art's subject
+pub fn token_cutout(image: &RgbaImage, options: TokenOptions) -> RgbaImage {
+    let subject = subject_bounds(image);
+
+    // A square as wide as the subject (or as tall, for wide subjects), from its top
+    let side = subject.width.min(subject.height).max(1) as f32 / (1.0 - 2.0 * PADDING);
+    let center_x = subject.x as f32 + subject.width as f32 / 2.0;
+    let left = center_x - side / 2.0;
+    let top = if subject.height > subject.width {
+        subject.y as f32 - side * PADDING
+    } else {
+        subject.y as f32 + subject.height as f32 / 2.0 - side / 2.0
+    };
+
+    let side_px = side.round() as u32;
+    let mut square = RgbaImage::new(side_px, side_px);
+    for (x, y, pixel) in square.enumerate_pixels_mut() {
+        let source_x = left + x as f32;
+        let source_y = top + y as f32;
+        if source_x >= 0.0
+            && source_y >= 0.0
+            && let Some(source) = image.get_pixel_checked(source_x as u32, source_y as u32)
+        {
+            *pixel = *source;
+        }
+    }
+
+    let mut token =
+        image::imageops::resize(&square, options.size, options.size, FilterType::Lanczos3);
+    mask_circle(&mut token, options.ring_color);
+    token
+}
+
+/// Write a token as lossless WebP
+pub fn write_token(token: &RgbaImage, path: &Path) -> Result<(), ProcessingError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(ProcessingError::Io)?;
+    }
+    let file = File::create(path).map_err(ProcessingError::Io)?;
+    WebPEncoder::new_lossless(file)
+        .write_image(
+            token.as_raw(),
+            token.width(),
+            token.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| ProcessingError::Io(std::io::Error::other(e.to_string())))
+}
+
+/// Clear everything outside the inscribed circle, with a one-pixel soft edge
+fn mask_circle(token: &mut RgbaImage, ring_color: Option<Rgba<u8>>) {
+    let size = token.width() as f32;
+    let radius = size / 2.0;
+    let ring_width = (size * RING_WIDTH).max(1.0);
+
+    for (x, y, pixel) in token.enumerate_pixels_mut() {
+        let dx = x as f32 + 0.5 - radius;
+        let dy = y as f32 + 0.5 - radius;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        if let Some(ring) = ring_color
+            && distance >= radius - ring_width
+        {
+            *pixel = ring;
+        }
+        let coverage = (radius - distance + 0.5).clamp(0.0, 1.0);
+        pixel[3] = (pixel[3] as f32 * coverage).round() as u8;
+    }
+}
+
+/// The smallest box holding every pixel matching `is_subject`
+fn bounding_box(image: &RgbaImage, is_subject: impl Fn(&Rgba<u8>) -> bool) -> Option<Bounds> {
+    let mut min = (u32::MAX, u32::MAX);
+    let mut max = (0, 0);
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if is_subject(pixel) {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+    }
+    (min.0 <= max.0).then(|| Bounds {
+        x: min.0,
+        y: min.1,
+        width: max.0 - min.0 + 1,
+        height: max.1 - min.1 + 1,
+    })
+}
+
+/// Average color of the image's border pixels
+fn border_color(image: &RgbaImage) -> Rgba<u8> {
+    let (width, height) = image.dimensions();
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+            for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                *total += channel as u64;
+            }
+            count += 1;
+        }
+    }
+    let average = |total: u64| (total / count.max(1)) as u8;
+    Rgba([average(sum[0]), average(sum[1]), average(sum[2]), 255])
+}
+
+fn color_distance(a: &Rgba<u8>, b: &Rgba<u8>) -> u32 {
+    (0..3).map(|i| a[i].abs_diff(b[i]) as u32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_cutout() {
+        // A tall dark figure on a white page
+        let mut art = RgbaImage::from_pixel(200, 300, Rgba([255, 255, 255, 255]));
+        for x in 80..120 {
+            for y in 50..250 {
+                art.put_pixel(x, y, Rgba([40, 30, 20, 255]));
+            }
+        }
+        assert_eq!(
+            subject_bounds(&art),
+            Bounds {
+                x: 80,
+                y: 50,
+                width: 40,
+                height: 200
+            }
+        );
+
+        // The same figure with a transparent background
+        let mut cutout = art.clone();
+        for pixel in cutout.pixels_mut() {
+            if pixel[0] == 255 {
+                pixel[3] = 0;
+            }
+        }
+        assert_eq!(subject_bounds(&cutout), subject_bounds(&art));
+
+        let token = token_cutout(
+            &art,
+            TokenOptions {
+                size: 128,
+                ring_color: parse_ring_color("#c0a000"),
+            },
+        );
+        assert_eq!(token.dimensions(), (128, 128));
+        // Corners are outside the circle, the center is the figure, the edge is the ring
+        assert_eq!(token.get_pixel(0, 0)[3], 0);
+        assert_eq!(token.get_pixel(64, 64).0, [40, 30, 20, 255]);
+        assert_eq!(token.get_pixel(64, 1).0, [0xc0, 0xa0, 0x00, 255]);
+
+        assert!(parse_ring_color("c0a000").is_none());
+    }
+}
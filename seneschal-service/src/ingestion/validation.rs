@@ -0,0 +1,316 @@
+//! Upload validation.
+//!
+//! Uploads are checked by content instead of trusting the file extension:
+//! the leading bytes must match the claimed format, PDFs must open (with
+//! the uploader's password if encrypted), and EPUB archives must not expand
+//! past a size limit. This runs before a file is stored, so rejected uploads
+//! fail with a clear error instead of deep inside the processing worker.
+
+use std::io::{self, Cursor, Read};
+
+use qpdf::{QPdf, QPdfErrorCode};
+
+use crate::error::ProcessingError;
+
+/// Bytes before the `%PDF-` header that readers tolerate
+const PDF_HEADER_WINDOW: usize = 1024;
+
+/// Leading bytes checked when deciding whether a file is text
+const TEXT_SNIFF_BYTES: usize = 8192;
+
+/// Archives with more entries than this are rejected
+const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+
+/// Entries expanding more than this many times their compressed size are
+/// rejected once they are large enough to matter
+const MAX_COMPRESSION_RATIO: u64 = 100;
+const COMPRESSION_RATIO_MIN_BYTES: u64 = 1024 * 1024;
+
+/// What an upload is checked against
+#[derive(Debug, Clone)]
+pub struct UploadRules {
+    /// Lowercase file extensions accepted
+    pub allowed_types: Vec<String>,
+    pub max_archive_uncompressed_bytes: u64,
+}
+
+impl UploadRules {
+    /// Rules from a comma-separated extension list and an archive size limit
+    pub fn new(allowed_types: &str, max_archive_uncompressed_bytes: u64) -> Self {
+        Self {
+            allowed_types: allowed_types
+                .split(',')
+                .map(|t| t.trim().trim_start_matches('.').to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            max_archive_uncompressed_bytes,
+        }
+    }
+}
+
+/// File formats recognized from leading bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SniffedType {
+    Pdf,
+    Zip,
+    Text,
+    Executable,
+    Unknown,
+}
+
+impl SniffedType {
+    fn label(&self) -> &'static str {
+        match self {
+            SniffedType::Pdf => "PDF",
+            SniffedType::Zip => "ZIP archive",
+            SniffedType::Text => "text",
+            SniffedType::Executable => "executable",
+            SniffedType::Unknown => "unrecognized binary data",
+        }
+    }
+}
+
+/// Recognize a file's format from its leading bytes
+pub fn sniff(content: &[u8]) -> SniffedType {
+    let head = &content[..content.len().min(PDF_HEADER_WINDOW)];
+    if head.windows(5).any(|w| w == b"%PDF-") {
+        return SniffedType::Pdf;
+    }
+    if content.starts_with(b"PK\x03\x04") {
+        return SniffedType::Zip;
+    }
+    if content.starts_with(b"MZ")
+        || content.starts_with(b"\x7fELF")
+        || content.starts_with(&[0xcf, 0xfa, 0xed, 0xfe])
+        || content.starts_with(&[0xfe, 0xed, 0xfa, 0xcf])
+    {
+        return SniffedType::Executable;
+    }
+
+    let sample = &content[..content.len().min(TEXT_SNIFF_BYTES)];
+    let valid_utf8 = match std::str::from_utf8(sample) {
+        Ok(_) => true,
+        // A multi-byte character cut off by the sample boundary is fine
+        Err(e) => e.error_len().is_none() && sample.len() == TEXT_SNIFF_BYTES,
+    };
+    if valid_utf8 && !sample.contains(&0) {
+        SniffedType::Text
+    } else {
+        SniffedType::Unknown
+    }
+}
+
+/// What validation learned about an accepted upload
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedUpload {
+    /// The PDF only opens with a password
+    pub encrypted: bool,
+}
+
+/// Check an upload against the rules before it is stored. `pdf_password`
+/// is tried when a PDF won't open without one.
+pub fn validate_upload(
+    filename: &str,
+    content: &[u8],
+    rules: &UploadRules,
+    pdf_password: Option<&str>,
+) -> Result<ValidatedUpload, ProcessingError> {
+    let extension = std::path::Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    check_claimed_type(&extension, sniff(content), rules)?;
+
+    match extension.as_str() {
+        "pdf" => check_pdf(content, pdf_password),
+        "epub" => {
+            check_epub(content, rules.max_archive_uncompressed_bytes)?;
+            Ok(ValidatedUpload::default())
+        }
+        _ => Ok(ValidatedUpload::default()),
+    }
+}
+
+/// The extension must be allowed and agree with the sniffed content
+fn check_claimed_type(
+    extension: &str,
+    sniffed: SniffedType,
+    rules: &UploadRules,
+) -> Result<(), ProcessingError> {
+    let unsupported = || ProcessingError::UnsupportedFormat {
+        format: extension.to_string(),
+    };
+    if !rules.allowed_types.iter().any(|t| t == extension) {
+        return Err(unsupported());
+    }
+
+    match (extension, sniffed) {
+        ("pdf", SniffedType::Pdf)
+        | ("epub", SniffedType::Zip)
+        | ("md" | "markdown" | "txt" | "text", SniffedType::Text) => Ok(()),
+        ("pdf" | "epub" | "md" | "markdown" | "txt" | "text", sniffed) => Err(rejected(format!(
+            "content is {}, not a .{} file",
+            sniffed.label(),
+            extension
+        ))),
+        _ => Err(unsupported()),
+    }
+}
+
+fn rejected(reason: impl Into<String>) -> ProcessingError {
+    ProcessingError::FileRejected {
+        reason: reason.into(),
+    }
+}
+
+/// The PDF must open, with the password if it needs one, and have pages
+fn check_pdf(content: &[u8], password: Option<&str>) -> Result<ValidatedUpload, ProcessingError> {
+    let needs_password = |e: &qpdf::QPdfError| e.error_code() == QPdfErrorCode::InvalidPassword;
+    let (pdf, encrypted) = match QPdf::read_from_memory(content) {
+        Ok(pdf) => (pdf, false),
+        Err(e) if needs_password(&e) => match password {
+            Some(password) => match QPdf::read_from_memory_encrypted(content, password) {
+                Ok(pdf) => (pdf, true),
+                Err(e) if needs_password(&e) => return Err(rejected("PDF password is incorrect")),
+                Err(e) => return Err(rejected(format!("PDF is damaged: {}", e))),
+            },
+            None => {
+                return Err(rejected(
+                    "PDF is password-protected; upload it again with its password",
+                ));
+            }
+        },
+        Err(e) => return Err(rejected(format!("PDF is damaged: {}", e))),
+    };
+
+    match pdf.get_pages() {
+        Ok(pages) if !pages.is_empty() => Ok(ValidatedUpload { encrypted }),
+        Ok(_) => Err(rejected("PDF has no pages")),
+        Err(e) => Err(rejected(format!("PDF page tree is damaged: {}", e))),
+    }
+}
+
+/// The archive must be an EPUB and expand to a plausible size. Sizes come
+/// from the central directory, so nothing is decompressed here.
+pub(crate) fn check_epub(
+    content: &[u8],
+    max_uncompressed_bytes: u64,
+) -> Result<(), ProcessingError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(content))
+        .map_err(|e| rejected(format!("EPUB archive is damaged: {}", e)))?;
+    if archive.len() > MAX_ARCHIVE_ENTRIES {
+        return Err(rejected(format!(
+            "EPUB has {} entries (max {})",
+            archive.len(),
+            MAX_ARCHIVE_ENTRIES
+        )));
+    }
+    if archive.index_for_name("META-INF/container.xml").is_none() {
+        return Err(rejected(
+            "archive is not an EPUB (no META-INF/container.xml)",
+        ));
+    }
+
+    // Sizes in the archive headers can lie, so count the bytes actually inflated
+    let mut total: u64 = 0;
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| rejected(format!("EPUB archive is damaged: {}", e)))?;
+        let name = entry.name().to_string();
+        let compressed_size = entry.compressed_size();
+        let remaining = max_uncompressed_bytes - total;
+        let size = io::copy(
+            &mut entry.take(remaining.saturating_add(1)),
+            &mut io::sink(),
+        )
+        .map_err(|e| rejected(format!("EPUB entry {} is damaged: {}", name, e)))?;
+        if size >= COMPRESSION_RATIO_MIN_BYTES
+            && size / compressed_size.max(1) > MAX_COMPRESSION_RATIO
+        {
+            return Err(rejected(format!(
+                "EPUB entry {} is compressed implausibly well",
+                name
+            )));
+        }
+        total += size;
+        if total > max_uncompressed_bytes {
+            return Err(rejected(format!(
+                "EPUB expands to more than {} bytes",
+                max_uncompressed_bytes
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn epub_with(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(sniff(b"%PDF-1.7\n"), SniffedType::Pdf);
+        assert_eq!(sniff(b"\xef\xbb\xbf# Jump Drives\n"), SniffedType::Text);
+        assert_eq!(sniff(b"MZ\x90\x00"), SniffedType::Executable);
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\x00\x00"), SniffedType::Unknown);
+    }
+
+    #[test]
+    fn test_check_claimed_type() {
+        let rules = UploadRules::new("pdf, epub, .MD", 1024 * 1024);
+
+        assert!(check_claimed_type("md", SniffedType::Text, &rules).is_ok());
+        assert!(matches!(
+            check_claimed_type("txt", SniffedType::Text, &rules),
+            Err(ProcessingError::UnsupportedFormat { .. })
+        ));
+        assert!(matches!(
+            check_claimed_type("pdf", sniff(b"MZ\x90\x00"), &rules),
+            Err(ProcessingError::FileRejected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_epub() {
+        let book = epub_with(&[
+            ("mimetype", b"application/epub+zip"),
+            ("META-INF/container.xml", b"<container/>"),
+        ]);
+        assert!(check_epub(&book, 1024 * 1024).is_ok());
+
+        let not_epub = epub_with(&[("readme.txt", b"hello")]);
+        assert!(check_epub(&not_epub, 1024 * 1024).is_err());
+
+        let zeros = vec![0u8; 4 * 1024 * 1024];
+        let bomb = epub_with(&[("META-INF/container.xml", b"<container/>"), ("a", &zeros)]);
+        assert!(check_epub(&bomb, 64 * 1024 * 1024).is_err());
+        assert!(check_epub(&book, 8).is_err());
+
+        // An entry declaring a smaller size than it inflates to
+        let mut understated = epub_with(&[
+            ("META-INF/container.xml", b"<container/>"),
+            ("a", &[b'a'; 2048]),
+        ]);
+        let central = understated
+            .windows(4)
+            .rposition(|w| w == b"PK\x01\x02")
+            .unwrap();
+        understated[central + 24..central + 28].copy_from_slice(&1u32.to_le_bytes());
+        assert!(check_epub(&understated, 1024).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Live log streaming to GM clients.
+//!
+//! A tracing layer copies log events into a broadcast channel while anyone
+//! is watching, so GMs can follow ingestion and captioning from the FVTT
+//! settings dialog instead of tailing the service's logs on the host. Each
+//! watcher picks a level and module filters (see `LogFilter`); the stream
+//! ignores `RUST_LOG`, so debug events can be watched without restarting
+//! the service at a noisier level.
+
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Events buffered per watcher before the oldest are dropped
+const STREAM_CAPACITY: usize = 1024;
+
+/// Crate prefix stripped from targets, so filters can name modules directly
+const CRATE_TARGET: &str = "seneschal_service";
+
+/// Targets never streamed: the WebSocket transport logs every frame it
+/// sends, so streaming it would feed on itself
+const EXCLUDED_TARGETS: &[&str] = &["tungstenite", "tokio_tungstenite"];
+
+static STREAM: LazyLock<broadcast::Sender<LogRecord>> =
+    LazyLock::new(|| broadcast::channel(STREAM_CAPACITY).0);
+
+/// A log event as sent to watchers
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    /// Structured fields, formatted as `key=value` pairs
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub fields: String,
+}
+
+/// What one watcher wants to see
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    level: LevelFilter,
+    /// Module paths, with or without the crate prefix (e.g. `ingestion` or
+    /// `seneschal_service::ingestion::pdf`); empty means all of this crate
+    targets: Vec<String>,
+}
+
+impl LogFilter {
+    /// A filter for `level` ("error" through "trace", default "info") and
+    /// module paths; errors on an unknown level
+    pub fn new(level: Option<&str>, targets: Vec<String>) -> Result<Self, String> {
+        let level = match level.map(str::trim).filter(|l| !l.is_empty()) {
+            Some(level) => level
+                .parse::<LevelFilter>()
+                .map_err(|_| format!("Unknown log level: {}", level))?,
+            None => LevelFilter::INFO,
+        };
+        let targets = targets
+            .into_iter()
+            .map(|t| t.trim().trim_matches(':').to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        Ok(Self { level, targets })
+    }
+
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let Ok(level) = record.level.parse::<Level>() else {
+            return false;
+        };
+        if level > self.level {
+            return false;
+        }
+
+        if self.targets.is_empty() {
+            return in_module(&record.target, CRATE_TARGET);
+        }
+        let local = record
+            .target
+            .strip_prefix(CRATE_TARGET)
+            .and_then(|t| t.strip_prefix("::"));
+        self.targets.iter().any(|filter| {
+            in_module(&record.target, filter) || local.is_some_and(|t| in_module(t, filter))
+        })
+    }
+}
+
+/// Whether `target` is `module` or inside it
+fn in_module(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Start watching the log; records arrive until the receiver is dropped
+pub fn subscribe() -> broadcast::Receiver<LogRecord> {
+    STREAM.subscribe()
+}
+
+/// Whether an event should be copied to the stream: only while watched,
+/// and never from the WebSocket transport
+pub fn is_streamed(metadata: &Metadata<'_>) -> bool {
+    STREAM.receiver_count() > 0
+        && !EXCLUDED_TARGETS
+            .iter()
+            .any(|excluded| in_module(metadata.target(), excluded))
+}
+
+/// Tracing layer that copies events to the stream
+pub struct LogStreamLayer;
+
+impl<S: Subscriber> Layer<S> for LogStreamLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        // Sending only fails when nobody is watching any more
+        let _ = STREAM.send(LogRecord {
+            timestamp: Utc::now(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+/// Collects an event's message and its other fields
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.push_field(field, format_args!("{}", value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.push_field(field, format_args!("{:?}", value));
+        }
+    }
+}
+
+impl RecordVisitor {
+    fn push_field(&mut self, field: &Field, value: std::fmt::Arguments<'_>) {
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={}", field.name(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: &str, target: &str) -> LogRecord {
+        LogRecord {
+            timestamp: Utc::now(),
+            level: level.to_string(),
+            target: target.to_string(),
+            message: "Captioning image".to_string(),
+            fields: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_log_filter() {
+        let filter = LogFilter::new(None, Vec::new()).unwrap();
+        assert!(filter.matches(&record("INFO", "seneschal_service::ingestion::pdf")));
+        assert!(filter.matches(&record("WARN", "seneschal_service")));
+        assert!(!filter.matches(&record("DEBUG", "seneschal_service::ingestion")));
+        assert!(!filter.matches(&record("INFO", "hyper::proto")));
+        assert!(!filter.matches(&record("INFO", "seneschal_service_extra")));
+
+        let filter = LogFilter::new(
+            Some("debug"),
+            vec!["ingestion".to_string(), "hyper".to_string()],
+        )
+        .unwrap();
+        assert!(filter.matches(&record("DEBUG", "seneschal_service::ingestion::pdf")));
+        assert!(filter.matches(&record("INFO", "hyper::proto")));
+        assert!(!filter.matches(&record("TRACE", "seneschal_service::ingestion")));
+        assert!(!filter.matches(&record("INFO", "seneschal_service::search")));
+        assert!(!filter.matches(&record("INFO", "seneschal_service::ingestion_digest")));
+
+        assert!(LogFilter::new(Some("loud"), Vec::new()).is_err());
+    }
+}
@@ -1,20 +1,29 @@
 use std::sync::Arc;
 
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{error, info};
 
 mod api;
 mod auto_import;
+mod call_trace;
+mod cli;
 mod config;
+mod conversation_mode;
 mod db;
 mod error;
 mod i18n;
 mod ingestion;
+mod log_stream;
 mod mcp;
 mod ollama;
 mod search;
 mod service;
+mod system_profile;
+mod tls;
+mod tool_budget;
 mod tools;
+mod tts;
+mod usage;
 mod websocket;
 
 use crate::config::{RuntimeConfig, StaticConfig};
@@ -26,6 +35,17 @@ use ::config::{Config as ConfigBuilder, Environment, File};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli.command != cli::Command::Serve {
+        return cli::run(cli).await;
+    }
+
     // Initialize logging
     init_logging();
 
@@ -74,6 +94,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ => {}
     }
 
+    // Encrypted PDFs' passwords are only kept in memory
+    match auto_import::restore_pdf_passwords(&service) {
+        Ok(count) if count > 0 => info!(count, "Restored auto-import PDF passwords"),
+        Err(e) => tracing::warn!(error = %e, "Restoring auto-import PDF passwords failed"),
+        _ => {}
+    }
+
     // Build the router
     let mut app = api::router(service.clone(), &runtime_config);
 
@@ -82,7 +109,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if mcp_config.mcp.enabled {
         let mcp_path = mcp_config.mcp.path.clone();
         info!(path = %mcp_path, "MCP server enabled");
-        app = app.nest(&mcp_path, mcp::mcp_router(service.clone()));
+        let mut mcp_router = mcp::mcp_router(service.clone());
+        if runtime_config
+            .static_config
+            .server
+            .requires_gm_client_cert()
+        {
+            mcp_router =
+                mcp_router.layer(axum::middleware::from_fn(tls::require_client_certificate));
+        }
+        app = app.nest(&mcp_path, mcp_router);
     }
 
     // Start document processing worker (resumes any pending documents)
@@ -91,25 +127,66 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start image captioning worker (runs in parallel, separate from document processing)
     SeneschalService::start_captioning_worker(service.clone());
 
+    // Start ingestion digest scheduler (idle until a schedule is configured)
+    SeneschalService::start_digest_scheduler(service.clone());
+
+    // Start database maintenance scheduler (idle until a schedule is configured)
+    SeneschalService::start_maintenance_scheduler(service.clone());
+    SeneschalService::start_retention_task(service.clone());
+    SeneschalService::start_disk_monitor(service.clone());
+
     // Start auto-import worker if configured
     if let Some(auto_import_dir) = &runtime_config.static_config.storage.auto_import_dir {
         auto_import::start_auto_import_worker(service.clone(), auto_import_dir.clone());
     }
 
+    // Start Obsidian vault import worker if configured. Auto-import deletes what it
+    // imports, so a vault inside the auto-import directory is refused.
+    let storage = &runtime_config.static_config.storage;
+    if let Some(vault_dir) = &storage.obsidian_vault_dir {
+        if storage
+            .auto_import_dir
+            .as_ref()
+            .is_some_and(|dir| vault_dir.starts_with(dir) || dir.starts_with(vault_dir))
+        {
+            error!(
+                vault = %vault_dir.display(),
+                "Obsidian vault overlaps the auto-import directory, not importing it"
+            );
+        } else {
+            auto_import::start_vault_import_worker(service.clone(), vault_dir.clone());
+        }
+    }
+
     // Start the server
     let addr = format!(
         "{}:{}",
         runtime_config.static_config.server.host, runtime_config.static_config.server.port
     );
-    let listener = TcpListener::bind(&addr).await?;
-    info!("Listening on {}", addr);
-
-    axum::serve(listener, app).await?;
+    if let Some(tls_config) = &runtime_config.static_config.server.tls {
+        let addr: std::net::SocketAddr = tokio::net::lookup_host(&addr)
+            .await?
+            .next()
+            .ok_or_else(|| format!("No address for {}", addr))?;
+        info!(
+            client_ca = tls_config.client_ca_path.is_some(),
+            require_client_cert_for_gm = tls_config.require_client_cert_for_gm,
+            "Listening on {} (TLS)",
+            addr
+        );
+        tls::serve(app, addr, tls_config).await?;
+    } else {
+        let listener = TcpListener::bind(&addr).await?;
+        info!("Listening on {}", addr);
+
+        axum::serve(listener, app).await?;
+    }
 
     Ok(())
 }
 
 fn init_logging() {
+    use tracing_subscriber::filter::dynamic_filter_fn;
     use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
     let format = fmt::format()
@@ -121,8 +198,14 @@ fn init_logging() {
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("seneschal_service=info"));
 
+    // RUST_LOG only applies to the service's own output; GMs watching the
+    // log stream choose their own level
     tracing_subscriber::registry()
-        .with(fmt::layer().event_format(format))
-        .with(filter)
+        .with(fmt::layer().event_format(format).with_filter(filter))
+        .with(
+            log_stream::LogStreamLayer.with_filter(dynamic_filter_fn(|metadata, _| {
+                log_stream::is_streamed(metadata)
+            })),
+        )
         .init();
 }
@@ -23,12 +23,20 @@ use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::service::SeneschalService;
+use crate::tool_budget::TurnUsage;
+use crate::tools::compaction::TurnBudget;
+use grounding::TurnGrounding;
+use loop_detection::CallHistory;
 
+pub mod grounding;
 pub mod handlers;
+pub mod loop_detection;
+pub mod prompts;
 pub mod tool_search;
 pub mod tools;
 
 use handlers::{handle_initialize, handle_tools_list};
+use prompts::{handle_prompts_get, handle_prompts_list};
 use tools::handle_tool_call;
 
 /// Cached tool result with timestamp
@@ -42,8 +50,21 @@ pub struct McpState {
     pub service: Arc<SeneschalService>,
     /// Cache for deduplicating tool calls (key: hash of tool+args, value: cached result)
     pub tool_dedup_cache: DashMap<u64, CachedToolResult>,
+    /// Tool result tokens spent in the current turn, by session ID
+    pub turn_budgets: DashMap<String, TurnBudget>,
+    /// Tool calls and time spent in the current turn, by session ID
+    pub turn_usage: DashMap<String, TurnUsage>,
+    /// Retrieval confidence of the current turn, by session ID
+    pub turn_grounding: DashMap<String, TurnGrounding>,
+    /// Recent tool calls by session ID, for replaying repeated calls
+    pub call_histories: DashMap<String, CallHistory>,
+    /// Client names given at initialize, by session ID, for usage accounting
+    pub clients: DashMap<String, String>,
 }
 
+/// FVTT role of MCP clients: GM, since MCP has no user context
+pub const MCP_ROLE: u8 = 4;
+
 /// TTL for cached tool results (10 seconds)
 pub const TOOL_DEDUP_TTL: Duration = Duration::from_secs(10);
 
@@ -102,6 +123,11 @@ pub fn mcp_router(service: Arc<SeneschalService>) -> Router {
     let state = Arc::new(McpState {
         service,
         tool_dedup_cache: DashMap::new(),
+        turn_budgets: DashMap::new(),
+        turn_usage: DashMap::new(),
+        turn_grounding: DashMap::new(),
+        call_histories: DashMap::new(),
+        clients: DashMap::new(),
     });
 
     // Use fallback to handle the root path regardless of trailing slash
@@ -138,10 +164,33 @@ async fn mcp_fallback_handler(
                 }
             }
         }
+        Method::DELETE => mcp_delete_handler(State(state), headers),
         _ => (StatusCode::METHOD_NOT_ALLOWED, "Method not allowed").into_response(),
     }
 }
 
+/// Handle DELETE requests - the client ends its session
+///
+/// Drops the session's turn budget, usage and grounding, call history, client
+/// name, conversation mode, tool budget and stored tool result artifacts.
+fn mcp_delete_handler(State(state): State<Arc<McpState>>, headers: HeaderMap) -> Response {
+    let Some(session_id) = headers.get("mcp-session-id").and_then(|v| v.to_str().ok()) else {
+        return (StatusCode::BAD_REQUEST, "Missing mcp-session-id header").into_response();
+    };
+
+    info!(session_id = %session_id, "MCP session ended");
+    state.turn_budgets.remove(session_id);
+    state.turn_usage.remove(session_id);
+    state.turn_grounding.remove(session_id);
+    state.call_histories.remove(session_id);
+    state.clients.remove(session_id);
+    state.service.conversation_modes.end(session_id);
+    state.service.tool_budgets.end(session_id);
+    tools::artifact::delete_session_artifacts(&state, session_id);
+
+    StatusCode::NO_CONTENT.into_response()
+}
+
 /// Handle GET requests - opens SSE stream for server-initiated messages
 ///
 /// Per the Streamable HTTP spec, GET opens an SSE stream for the server
@@ -195,14 +244,28 @@ async fn mcp_post_handler(
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
 
+    // Initialize starts a new session, whose ID is returned in a header
+    let new_session_id = (request.method == "initialize").then(|| Uuid::new_v4().to_string());
+
     if let Some(ref sid) = session_id {
         debug!(session_id = %sid, "Request includes session ID");
     }
 
     let result = match request.method.as_str() {
         "initialize" => {
-            info!("MCP client initializing");
-            handle_initialize(&state).await
+            let client = request
+                .params
+                .as_ref()
+                .and_then(|p| p.pointer("/clientInfo/name"))
+                .and_then(|v| v.as_str());
+            info!(client = ?client, "MCP client initializing");
+            let new_session_id = new_session_id.as_deref().unwrap_or_default();
+            if let Some(client) = client {
+                state
+                    .clients
+                    .insert(new_session_id.to_string(), client.to_string());
+            }
+            handle_initialize(&state, new_session_id).await
         }
         "notifications/initialized" => {
             // Client acknowledgment - no response needed
@@ -217,6 +280,14 @@ async fn mcp_post_handler(
             debug!("MCP tools/call request");
             handle_tool_call(&state, request.params, session_id.as_deref()).await
         }
+        "prompts/list" => {
+            debug!("MCP prompts/list request");
+            handle_prompts_list(&state).await
+        }
+        "prompts/get" => {
+            debug!("MCP prompts/get request");
+            handle_prompts_get(&state, request.params).await
+        }
         "ping" => {
             debug!("MCP ping request");
             Ok(serde_json::json!({}))
@@ -246,14 +317,13 @@ async fn mcp_post_handler(
         },
     };
 
-    // For initialize requests, generate and include session ID
+    // For initialize requests, include the new session ID
     let mut headers = HeaderMap::new();
-    if request.method == "initialize" {
-        let session_id = Uuid::new_v4().to_string();
-        if let Ok(value) = session_id.parse() {
-            headers.insert("mcp-session-id", value);
-            debug!(session_id = %session_id, "Generated new MCP session");
-        }
+    if let Some(session_id) = new_session_id
+        && let Ok(value) = session_id.parse()
+    {
+        headers.insert("mcp-session-id", value);
+        debug!(session_id = %session_id, "Generated new MCP session");
     }
 
     (StatusCode::OK, headers, Json(response)).into_response()
@@ -0,0 +1,51 @@
+//! Retrieval confidence over a turn.
+//!
+//! A turn's confidence is the best retrieval confidence among the document
+//! searches made in it, so a weak first search followed by a better
+//! rephrasing still counts as grounded. Turns end after the same idle gap
+//! as the tool result token budget.
+
+use std::time::Instant;
+
+use crate::tools::compaction::TURN_IDLE_GAP;
+
+/// Best retrieval confidence of a session's current turn
+pub struct TurnGrounding {
+    best: f32,
+    last_search: Instant,
+}
+
+impl Default for TurnGrounding {
+    fn default() -> Self {
+        Self {
+            best: 0.0,
+            last_search: Instant::now(),
+        }
+    }
+}
+
+impl TurnGrounding {
+    /// Record a search's confidence, starting a new turn if the session has
+    /// been idle, and return the turn's confidence
+    pub fn record(&mut self, confidence: f32) -> f32 {
+        if self.last_search.elapsed() >= TURN_IDLE_GAP {
+            self.best = 0.0;
+        }
+        self.last_search = Instant::now();
+        self.best = self.best.max(confidence);
+        self.best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_keeps_best_confidence() {
+        let mut turn = TurnGrounding::default();
+        assert_eq!(turn.record(0.2), 0.2);
+        assert_eq!(turn.record(0.7), 0.7);
+        assert_eq!(turn.record(0.4), 0.7);
+    }
+}
@@ -1,35 +1,112 @@
 //! MCP message handlers.
 //!
 //! Handlers for initialize and tools/list requests.
+//! Style prompts are handled in `prompts`.
 //!
 //! NOTE: Tool definitions are now managed by the unified registry in
 //! `crate::tools::registry`. This module converts registry format to MCP format.
 
-use super::{McpError, McpState, McpToolDefinition};
+use super::prompts::server_instructions;
+use super::{MCP_ROLE, McpError, McpState, McpToolDefinition};
 use crate::tools::REGISTRY;
 
-/// Handle initialize request
-pub async fn handle_initialize(_state: &McpState) -> Result<serde_json::Value, McpError> {
+/// Most recent campaign memories listed in the server instructions
+const INSTRUCTION_MEMORIES: usize = 20;
+
+/// Most open campaign tasks listed in the server instructions
+const INSTRUCTION_TASKS: usize = 20;
+
+/// Handle initialize request, starting the session's conversation in the
+/// current default mode
+pub async fn handle_initialize(
+    state: &McpState,
+    session_id: &str,
+) -> Result<serde_json::Value, McpError> {
+    let mut instructions = server_instructions(&state.service.runtime_config.dynamic().mcp);
+
+    let mode = state.service.conversation_modes.start(session_id);
+    instructions.push_str("\n\n");
+    instructions.push_str(mode.guidance());
+
+    if let Some(guidance) = state.service.mcp_system_profile().instructions {
+        instructions.push_str("\n\n");
+        instructions.push_str(guidance);
+    }
+
+    if let Ok(Some(date)) = state.service.campaign_date(None) {
+        instructions.push_str(&format!(
+            "\n\nThe campaign date is {}. Use clock_advance as time passes in play.",
+            date
+        ));
+    }
+
+    // Established campaign facts take precedence over the documents
+    let memories = state
+        .service
+        .db
+        .list_memories(
+            state.service.mcp_world_id().as_deref(),
+            INSTRUCTION_MEMORIES,
+        )
+        .unwrap_or_default();
+    if !memories.is_empty() {
+        instructions.push_str(
+            "\n\nEstablished campaign facts (these override the documents; use memory_recall for others):\n",
+        );
+        for memory in &memories {
+            instructions.push_str(&format!("- {}\n", memory.fact));
+        }
+    }
+
+    // Reminders and prep TODOs noted in earlier conversations
+    let tasks = state
+        .service
+        .db
+        .list_tasks(
+            state.service.mcp_world_id().as_deref(),
+            false,
+            INSTRUCTION_TASKS,
+        )
+        .unwrap_or_default();
+    if !tasks.is_empty() {
+        instructions.push_str(
+            "\n\nOpen tasks from earlier conversations (bring these up with the GM; mark them done with task_complete):\n",
+        );
+        for task in &tasks {
+            instructions.push_str(&format!("- [{}] {}\n", task.id, task.description));
+        }
+    }
+
     Ok(serde_json::json!({
         "protocolVersion": "2024-11-05",
         "capabilities": {
-            "tools": { "listChanged": false }
+            "tools": { "listChanged": false },
+            "prompts": { "listChanged": false }
         },
         "serverInfo": {
             "name": "seneschal-service",
             "version": env!("CARGO_PKG_VERSION")
         },
-        "instructions": "Seneschal Program MCP server for game master assistance, document search, and Foundry VTT integration."
+        "instructions": instructions
     }))
 }
 
 /// Handle tools/list request
 ///
-/// This function retrieves tool definitions from the unified registry
-/// and converts them to the MCP format.
-pub async fn handle_tools_list(_state: &McpState) -> Result<serde_json::Value, McpError> {
+/// This function retrieves tool definitions from the unified registry that
+/// apply to the MCP world's game system, plus those FVTT clients registered
+/// for the world, and converts them to the MCP format.
+pub async fn handle_tools_list(state: &McpState) -> Result<serde_json::Value, McpError> {
     // Get MCP definitions from the unified registry
-    let registry_tools = REGISTRY.mcp_definitions();
+    let profile = state.service.mcp_system_profile();
+    let mut registry_tools = REGISTRY.mcp_definitions();
+    registry_tools.retain(|t| profile.tool_available(&t.name, t.category.as_deref().unwrap_or("")));
+    registry_tools.extend(
+        state
+            .service
+            .client_tools
+            .mcp_definitions(state.service.mcp_world_id().as_deref(), MCP_ROLE),
+    );
 
     // Convert from registry format to MCP module format
     let tools: Vec<McpToolDefinition> = registry_tools
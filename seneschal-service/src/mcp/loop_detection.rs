@@ -0,0 +1,126 @@
+//! Detection of repeated tool calls.
+//!
+//! Models sometimes call the same tool with identical arguments over and
+//! over. Within a window, an exact repeat gets the earlier result back
+//! without running the tool again, and once the same call has been repeated
+//! enough times a note is appended asking the model to change course. Only
+//! read-only, deterministic tools are replayed, and any other call clears the
+//! history since it may have changed what the reads return.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A call previously made in this session
+struct PreviousCall {
+    result: serde_json::Value,
+    repeats: u32,
+    last_seen: Instant,
+}
+
+/// Recent tool calls of one MCP session, keyed by hash of tool and arguments
+#[derive(Default)]
+pub struct CallHistory {
+    calls: HashMap<u64, PreviousCall>,
+}
+
+impl CallHistory {
+    /// Replay the result of an identical earlier call made within `window`.
+    ///
+    /// The note is appended once the call has been repeated `nudge_after`
+    /// times (0 never nudges).
+    pub fn replay(
+        &mut self,
+        key: u64,
+        tool: &str,
+        window: Duration,
+        nudge_after: u32,
+    ) -> Option<serde_json::Value> {
+        self.calls
+            .retain(|_, call| call.last_seen.elapsed() < window);
+
+        let call = self.calls.get_mut(&key)?;
+        call.repeats += 1;
+        call.last_seen = Instant::now();
+
+        let mut result = call.result.clone();
+        if nudge_after > 0
+            && call.repeats >= nudge_after
+            && let Some(content) = result.get_mut("content").and_then(|c| c.as_array_mut())
+        {
+            content.push(serde_json::json!({
+                "type": "text",
+                "text": format!(
+                    "Note: {} has been called {} times with these exact arguments and the result \
+                     has not changed. Use the result above, try different arguments or another \
+                     tool, or tell the user what is blocking you.",
+                    tool,
+                    call.repeats + 1
+                )
+            }));
+        }
+        Some(result)
+    }
+
+    /// Forget all calls, after one that may have changed what they would return
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    /// Remember a call's result for replay
+    pub fn record(&mut self, key: u64, result: &serde_json::Value) {
+        self.calls.insert(
+            key,
+            PreviousCall {
+                result: result.clone(),
+                repeats: 0,
+                last_seen: Instant::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let window = Duration::from_secs(60);
+        let result = serde_json::json!({ "content": [{ "type": "text", "text": "Regina" }] });
+        let mut history = CallHistory::default();
+
+        assert!(
+            history
+                .replay(1, "traveller_map_search", window, 2)
+                .is_none()
+        );
+        history.record(1, &result);
+
+        let first = history
+            .replay(1, "traveller_map_search", window, 2)
+            .unwrap();
+        assert_eq!(first, result);
+
+        let second = history
+            .replay(1, "traveller_map_search", window, 2)
+            .unwrap();
+        let content = second["content"].as_array().unwrap();
+        assert_eq!(content.len(), 2);
+        assert!(content[1]["text"].as_str().unwrap().contains("3 times"));
+
+        // Calls outside the window run again
+        assert!(
+            history
+                .replay(1, "traveller_map_search", Duration::ZERO, 2)
+                .is_none()
+        );
+
+        history.record(2, &result);
+        history.clear();
+        assert!(
+            history
+                .replay(2, "traveller_map_search", window, 2)
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Answer style profiles.
+//!
+//! GMs want different answers at different times: a quick ruling at the
+//! table, narration to read aloud, or prep notes. Profiles are named
+//! instructions kept in settings. The default profile is merged into the
+//! server instructions through the template's `{style}` slot, and every
+//! profile is offered as an MCP prompt so a conversation can pick its own.
+
+use crate::config::McpConfig;
+
+use super::{McpError, McpState};
+
+/// Replaced in the instructions template by the default style
+const STYLE_SLOT: &str = "{style}";
+
+/// Prompt names are the profile name with this prefix
+const PROMPT_PREFIX: &str = "style-";
+
+/// The server instructions with the default style merged in
+pub fn server_instructions(config: &McpConfig) -> String {
+    let style = config
+        .style_profiles
+        .get(&config.default_style)
+        .map(String::as_str)
+        .unwrap_or("");
+    config
+        .instructions_template
+        .replace(STYLE_SLOT, style)
+        .trim()
+        .to_string()
+}
+
+/// Handle prompts/list request
+pub async fn handle_prompts_list(state: &McpState) -> Result<serde_json::Value, McpError> {
+    let config = state.service.runtime_config.dynamic();
+    let prompts: Vec<_> = config
+        .mcp
+        .style_profiles
+        .keys()
+        .map(|name| {
+            serde_json::json!({
+                "name": format!("{}{}", PROMPT_PREFIX, name),
+                "description": format!("Answer in the '{}' style for the rest of the conversation", name)
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({ "prompts": prompts }))
+}
+
+/// Handle prompts/get request
+pub async fn handle_prompts_get(
+    state: &McpState,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, McpError> {
+    let name = params
+        .as_ref()
+        .and_then(|p| p.get("name"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing prompt name".to_string(),
+        })?;
+
+    let config = state.service.runtime_config.dynamic();
+    let instructions = name
+        .strip_prefix(PROMPT_PREFIX)
+        .and_then(|style| config.mcp.style_profiles.get(style))
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: format!("Unknown prompt: {}", name),
+        })?;
+
+    Ok(serde_json::json!({
+        "description": format!("Answer style: {}", &name[PROMPT_PREFIX.len()..]),
+        "messages": [{
+            "role": "user",
+            "content": {
+                "type": "text",
+                "text": format!(
+                    "For the rest of this conversation, follow this answer style:\n\n{}",
+                    instructions
+                )
+            }
+        }]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_instructions() {
+        let mut config: McpConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            server_instructions(&config),
+            "Seneschal Program MCP server for game master assistance, document search, and Foundry VTT integration."
+        );
+
+        config.default_style = "prep".to_string();
+        let instructions = server_instructions(&config);
+        assert!(instructions.ends_with(&config.style_profiles["prep"]));
+
+        config.instructions_template = "{style}\n\nYou assist the referee.".to_string();
+        config.default_style = "missing".to_string();
+        assert_eq!(server_instructions(&config), "You assist the referee.");
+    }
+}
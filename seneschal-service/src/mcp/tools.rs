@@ -2,17 +2,52 @@
 //!
 //! Handles execution of individual tool calls from MCP clients.
 
+pub(crate) mod artifact;
 mod document;
 mod external;
+mod handout;
+mod help;
 mod image;
+mod memory;
+mod npc;
+mod ollama;
+mod page;
+mod party;
+mod plot_hooks;
+mod read_aloud;
+mod scene;
+mod session;
+mod speech;
+mod statblock;
+mod task;
+mod timeline;
+mod token;
 mod traveller;
+mod traveller_combat;
+mod traveller_encounters;
 mod traveller_map;
+mod traveller_map_overlay;
+mod traveller_map_subsector_dossier;
+mod traveller_map_world_journal;
 mod traveller_worlds;
+mod undo;
 
-use crate::tools::{ToolLocation, classify_tool};
+use std::time::Instant;
+
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::call_trace::{current_correlation_id, record_stage};
+use crate::conversation_mode::ConversationMode;
+use crate::tool_budget::BudgetCheck;
+
+use crate::tools::compaction::compact_tool_result;
+use crate::tools::result_validation::validate_arguments;
+use crate::tools::{REGISTRY, ToolLocation, classify_tool};
+use crate::websocket::ServerMessage;
 
 use super::tool_search::TOOL_SEARCH_INDEX;
-use super::{McpError, McpState};
+use super::{MCP_ROLE, McpError, McpState};
 
 /// Handle tools/call request
 pub async fn handle_tool_call(
@@ -38,22 +73,301 @@ pub async fn handle_tool_call(
         .cloned()
         .unwrap_or(serde_json::json!({}));
 
-    // MCP clients have GM access (role=4) since MCP has no user context
-    let gm_role = 4u8;
+    // A conversation whose mode changed is told on its next call; one with
+    // tools off is refused
+    let modes = &state.service.conversation_modes;
+    let changed_mode = session_id.and_then(|id| modes.take_change(id));
+    if modes.mode(session_id) == ConversationMode::NoTools {
+        return Err(McpError {
+            code: -32000,
+            message: ConversationMode::NoTools.guidance().to_string(),
+        });
+    }
+
+    let correlation_id = Uuid::new_v4().to_string();
+    let client = session_id.and_then(|id| state.clients.get(id).map(|c| c.clone()));
+    let mut result = state
+        .service
+        .call_traces
+        .run(
+            &correlation_id,
+            name,
+            session_id,
+            client.as_deref(),
+            run_tool_call(state, name, arguments, session_id),
+        )
+        .await?;
+
+    if let Some(mode) = changed_mode
+        && let Some(content) = result.get_mut("content").and_then(|c| c.as_array_mut())
+    {
+        content.insert(
+            0,
+            serde_json::json!({
+                "type": "text",
+                "text": format!(
+                    "Conversation mode changed to {}. {}",
+                    mode.as_str(),
+                    mode.guidance()
+                )
+            }),
+        );
+    }
+
+    // Kept for inspecting what the model was given
+    let text = result
+        .get("content")
+        .and_then(|c| c.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+    state
+        .service
+        .call_traces
+        .set_model_text(&correlation_id, text);
+
+    // Lets the caller look up the call's trace
+    if let Some(meta) = result
+        .as_object_mut()
+        .map(|fields| {
+            fields
+                .entry("_meta")
+                .or_insert_with(|| serde_json::json!({}))
+        })
+        .and_then(|meta| meta.as_object_mut())
+    {
+        meta.insert(
+            "correlation_id".to_string(),
+            serde_json::json!(correlation_id),
+        );
+    }
+
+    Ok(result)
+}
+
+/// Run a tool call: dry runs, repeat replay, execution and result compaction
+async fn run_tool_call(
+    state: &McpState,
+    name: &str,
+    mut arguments: serde_json::Value,
+    session_id: Option<&str>,
+) -> Result<serde_json::Value, McpError> {
+    // Tools of other game systems aren't offered in this world
+    if let Some(tool) = REGISTRY.get_by_str(name) {
+        let profile = state.service.mcp_system_profile();
+        if !profile.tool_available(name, tool.category) {
+            return Err(McpError {
+                code: -32602,
+                message: format!(
+                    "{} isn't available: this world plays {}, not Traveller",
+                    name, profile.name
+                ),
+            });
+        }
+    }
+
+    // MCP callers act as GM, which every registered role requirement allows
+    let client_tool = state
+        .service
+        .client_tools
+        .get(state.service.mcp_world_id().as_deref(), name);
+
+    // Write tools can be checked without running them
+    if REGISTRY.supports_dry_run(name)
+        && let Some(fields) = arguments.as_object_mut()
+        && fields.remove("dry_run").and_then(|v| v.as_bool()) == Some(true)
+    {
+        return help::execute_dry_run(name, &arguments);
+    }
+
+    let gm_role = MCP_ROLE;
+
+    // Background captioning backs off while tools are being used interactively
+    state.service.mark_interactive_activity();
+
+    // Identical calls within the repeat window get the earlier result back
+    let (repeat_window, nudge_after) = {
+        let config = state.service.runtime_config.dynamic();
+        (
+            config.agentic_loop.repeat_window(),
+            config.agentic_loop.repeat_nudge_threshold,
+        )
+    };
+    let session_key = session_id.unwrap_or_default().to_string();
+    let call_key = McpState::dedup_key(session_id, name, &arguments);
+    let replayable = !repeat_window.is_zero() && REGISTRY.is_replayable(name);
+    if replayable {
+        if let Some(replayed) = state
+            .call_histories
+            .entry(session_key.clone())
+            .or_default()
+            .replay(call_key, name, repeat_window, nudge_after)
+        {
+            debug!(tool = %name, "Replaying result of repeated tool call");
+            return Ok(replayed);
+        }
+    } else if let Some(mut history) = state.call_histories.get_mut(&session_key) {
+        // Writes and rolls may change what earlier reads would return
+        history.clear();
+    }
+
+    // Long tool chains pause for the user, and stop at the turn's hard timeout
+    let budget = state.service.tool_budgets.budget(
+        session_id,
+        &state.service.runtime_config.dynamic().agentic_loop,
+    );
+    let (check, call) = {
+        let mut usage = state.turn_usage.entry(session_key.clone()).or_default();
+        let check = usage.check(&budget);
+        (check, usage.turn_calls())
+    };
+    let session = session_id.map(str::to_string);
+    if let BudgetCheck::Pause { elapsed, .. } | BudgetCheck::Stop { elapsed } = check {
+        state
+            .service
+            .ws_manager
+            .broadcast_to_gms(ServerMessage::ToolChainPaused {
+                session_id: session.clone(),
+                calls: call,
+                elapsed_secs: elapsed.as_secs(),
+                stopped: matches!(check, BudgetCheck::Stop { .. }),
+            });
+    }
+    let refusal = match check {
+        BudgetCheck::Run => None,
+        BudgetCheck::Pause { calls, elapsed } => {
+            info!(tool = %name, calls, elapsed_secs = elapsed.as_secs(), "Tool chain paused");
+            Some(format!(
+                "Paused after {} tool calls ({}s) this turn. Tell the user what you have found so far and ask whether to keep going before calling more tools.",
+                calls,
+                elapsed.as_secs()
+            ))
+        }
+        BudgetCheck::Stop { elapsed } => {
+            info!(tool = %name, elapsed_secs = elapsed.as_secs(), "Tool chain hit its hard timeout");
+            Some(format!(
+                "This turn has run for {}s, past its {}s limit. Answer with what you have; tools can be used again after the user replies.",
+                elapsed.as_secs(),
+                budget.hard_timeout_secs
+            ))
+        }
+    };
+    if let Some(refusal) = refusal {
+        return Ok(serde_json::json!({
+            "content": [{ "type": "text", "text": refusal }],
+            "isError": true
+        }));
+    }
+
+    // Tools that change the world can be held for a GM to approve
+    let require_approval = state
+        .service
+        .runtime_config
+        .dynamic()
+        .agentic_loop
+        .require_write_approval;
+    let changes_world =
+        REGISTRY.supports_dry_run(name) || client_tool.as_ref().is_some_and(|t| !t.read_only);
+    if require_approval && changes_world {
+        let started = Instant::now();
+        let approval = state
+            .service
+            .request_tool_approval(name, &arguments, session_id)
+            .await;
+        record_stage("gm_approval", started, approval.is_ok());
+        if let Err(refusal) = approval {
+            return Ok(serde_json::json!({
+                "content": [{ "type": "text", "text": refusal }],
+                "isError": true
+            }));
+        }
+    }
+
+    // Tools registered by FVTT clients are checked against their own schema
+    if let Some(client_tool) = &client_tool
+        && let Err(e) = validate_arguments(&client_tool.parameters, &arguments)
+    {
+        return Err(McpError {
+            code: -32602,
+            message: format!("Invalid arguments for {}: {}", name, e),
+        });
+    }
 
     // Classify the tool and route accordingly
     let location = classify_tool(name);
 
+    state
+        .service
+        .ws_manager
+        .broadcast_to_gms(ServerMessage::ToolCallStarted {
+            session_id: session.clone(),
+            tool: name.to_string(),
+            call,
+            correlation_id: current_correlation_id(),
+        });
+    let started = Instant::now();
     let result = match location {
         ToolLocation::Internal => {
             // Execute internal tools directly
-            execute_internal_tool(state, name, &arguments, gm_role).await?
+            execute_internal_tool(state, name, &arguments, gm_role, &session_key).await
         }
         ToolLocation::External => {
             // Route external tools through GM WebSocket connection
-            external::execute_external_tool(state, name, arguments, session_id).await?
+            external::execute_external_tool(state, name, arguments, session_id).await
         }
     };
+    record_stage("execute", started, result.is_ok());
+    state
+        .service
+        .ws_manager
+        .broadcast_to_gms(ServerMessage::ToolCallFinished {
+            session_id: session,
+            tool: name.to_string(),
+            call,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            success: result.is_ok(),
+        });
+    let mut result = result?;
+
+    // Long-running calls count as activity until they finish
+    state.service.mark_interactive_activity();
+
+    // Render results as terse text within this turn's token budget
+    let (max_tokens, turn_tokens) = {
+        let config = state.service.runtime_config.dynamic();
+        (
+            config.agentic_loop.tool_result_max_tokens,
+            config.agentic_loop.turn_result_token_budget,
+        )
+    };
+    let mut budget = state.turn_budgets.entry(session_key.clone()).or_default();
+    let allowance = budget.allowance(max_tokens, turn_tokens);
+    let started = Instant::now();
+    let tokens = compact_tool_result(name, &mut result, allowance, |text| {
+        artifact::store_artifact(state, &session_key, name, text)
+    });
+    record_stage("compact", started, true);
+    budget.spend(tokens);
+    debug!(
+        tool = %name,
+        tokens,
+        turn_tokens = budget.used(),
+        "Compacted tool result"
+    );
+    drop(budget);
+
+    if replayable {
+        state
+            .call_histories
+            .entry(session_key)
+            .or_default()
+            .record(call_key, &result);
+    }
 
     Ok(result)
 }
@@ -64,27 +378,72 @@ async fn execute_internal_tool(
     name: &str,
     arguments: &serde_json::Value,
     gm_role: u8,
+    session_key: &str,
 ) -> Result<serde_json::Value, McpError> {
     match name {
         // Document tools
-        "document_search" => document::execute_document_search(state, arguments, gm_role).await,
+        "document_search" => {
+            document::execute_document_search(state, arguments, gm_role, session_key).await
+        }
         "document_search_text" => document::execute_document_search_text(state, arguments, gm_role),
+        "chunk_similar" => document::execute_chunk_similar(state, arguments, gm_role),
         "document_get" => document::execute_document_get(state, arguments, gm_role),
-        "document_list" => document::execute_document_list(state, arguments, gm_role),
+        "document_list" => document::execute_document_list(state, arguments, gm_role).await,
         "document_find" => document::execute_document_find(state, arguments, gm_role),
+        "document_related" => document::execute_document_related(state, arguments, gm_role),
         "document_update" => document::execute_document_update(state, arguments, gm_role),
+        "document_set_access" => document::execute_document_set_access(state, arguments, gm_role),
+        "glossary_lookup" => document::execute_glossary_lookup(state, arguments, gm_role),
+        "library_data" => document::execute_library_data(state, arguments, gm_role).await,
+        "timeline_query" => timeline::execute_timeline_query(state, arguments, gm_role),
+        "timeline_add_event" => timeline::execute_timeline_add_event(state, arguments),
+        "timeline_extract" => timeline::execute_timeline_extract(state, arguments, gm_role),
+        "clock_get" => timeline::execute_clock_get(state),
+        "clock_set" => timeline::execute_clock_set(state, arguments),
+        "clock_advance" => timeline::execute_clock_advance(state, arguments),
 
         // Image tools
         "image_list" => image::execute_image_list(state, arguments, gm_role),
         "image_search" => image::execute_image_search(state, arguments, gm_role).await,
+        "image_search_similar" => {
+            image::execute_image_search_similar(state, arguments, gm_role).await
+        }
+        "image_tag" => image::execute_image_tag(state, arguments),
+        "image_token" => token::execute_image_token(state, arguments, gm_role).await,
+        "image_scene_settings" => {
+            scene::execute_image_scene_settings(state, arguments, gm_role).await
+        }
         "image_get" => image::execute_image_get(state, arguments, gm_role),
         "image_deliver" => image::execute_image_deliver(state, arguments, gm_role),
+        "image_recaption" => image::execute_image_recaption(state, arguments, gm_role),
+        "page_deliver" => page::execute_page_deliver(state, arguments, gm_role).await,
+        "handout_compose" => handout::execute_handout_compose(state, arguments, gm_role).await,
+
+        // Stat block tools
+        "statblock_search" => statblock::execute_statblock_search(state, arguments, gm_role),
+        "statblock_get" => statblock::execute_statblock_get(state, arguments, gm_role),
+        "fvtt_build_actor" => statblock::execute_fvtt_build_actor(state, arguments, gm_role),
+
+        // Read-aloud text tools
+        "read_aloud_get" => read_aloud::execute_read_aloud_get(state, arguments, gm_role),
+
+        // Speech tools
+        "speech_synthesize" => speech::execute_speech_synthesize(state, arguments, gm_role).await,
 
         // Traveller tools
         "system_schema" => traveller::execute_system_schema(arguments),
         "traveller_uwp_parse" => traveller::execute_traveller_uwp_parse(arguments),
         "traveller_jump_calc" => traveller::execute_traveller_jump_calc(arguments),
         "traveller_skill_lookup" => traveller::execute_traveller_skill_lookup(arguments),
+        "traveller_chargen" => traveller::execute_traveller_chargen(arguments),
+        "traveller_ship_design" => traveller::execute_traveller_ship_design(arguments),
+        "name_generate" => traveller::execute_name_generate(arguments),
+        "traveller_attack" => traveller_combat::execute_traveller_attack(arguments),
+        "traveller_damage" => traveller_combat::execute_traveller_damage(arguments),
+        "traveller_opposed_check" => traveller_combat::execute_traveller_opposed_check(arguments),
+        "traveller_encounter_tables" => {
+            traveller_encounters::execute_traveller_encounter_tables(state, arguments).await
+        }
 
         // Traveller Map API tools
         "traveller_map_search" => {
@@ -94,6 +453,9 @@ async fn execute_internal_tool(
             traveller_map::execute_traveller_map_jump_worlds(state, arguments).await
         }
         "traveller_map_route" => traveller_map::execute_traveller_map_route(state, arguments).await,
+        "traveller_map_plan_route" => {
+            traveller_map::execute_traveller_map_plan_route(state, arguments).await
+        }
         "traveller_map_world_data" => {
             traveller_map::execute_traveller_map_world_data(state, arguments).await
         }
@@ -118,6 +480,27 @@ async fn execute_internal_tool(
         "traveller_map_save_jump_map" => {
             traveller_map::execute_traveller_map_save_jump_map(state, arguments).await
         }
+        "traveller_map_overlay" => {
+            traveller_map_overlay::execute_traveller_map_overlay(state, arguments)
+        }
+        "traveller_map_save_overlay" => {
+            traveller_map_overlay::execute_traveller_map_save_overlay(state, arguments).await
+        }
+        "traveller_map_subsector_dossier" => {
+            traveller_map_subsector_dossier::execute_traveller_map_subsector_dossier(
+                state, arguments, gm_role,
+            )
+            .await
+        }
+        "traveller_map_world_journal" => {
+            traveller_map_world_journal::execute_traveller_map_world_journal(
+                state,
+                arguments,
+                gm_role,
+                session_key,
+            )
+            .await
+        }
 
         // Traveller Worlds tools
         "traveller_worlds_canon_url" => {
@@ -133,8 +516,44 @@ async fn execute_internal_tool(
             traveller_worlds::execute_traveller_worlds_custom_save(state, arguments).await
         }
 
-        // Tool search
-        "tool_search" => execute_tool_search(arguments),
+        // Party context
+        "party_characters" => party::execute_party_characters(state, arguments).await,
+
+        // Session tools
+        "session_summary" => session::execute_session_summary(state, arguments).await,
+        "save_note" => session::execute_save_note(state, arguments).await,
+
+        // Campaign memory tools
+        "memory_set" => memory::execute_memory_set(state, arguments).await,
+        "memory_recall" => memory::execute_memory_recall(state, arguments).await,
+
+        // Campaign task tools
+        "task_add" => task::execute_task_add(state, arguments, session_key),
+        "task_list" => task::execute_task_list(state, arguments),
+        "task_complete" => task::execute_task_complete(state, arguments),
+
+        // NPC registry tools
+        "npc_set" => npc::execute_npc_set(state, arguments),
+        "npc_relate" => npc::execute_npc_relate(state, arguments),
+        "npc_get" => npc::execute_npc_get(state, arguments, gm_role),
+        "npc_relations" => npc::execute_npc_relations(state, arguments),
+
+        // Plot hook tools
+        "plot_hooks" => plot_hooks::execute_plot_hooks(state, arguments, gm_role).await,
+
+        // Undo log tools
+        "list_recent_changes" => undo::execute_list_recent_changes(state, arguments),
+        "undo_last_change" => undo::execute_undo_last_change(state, arguments).await,
+
+        // Ollama model management tools
+        "ollama_list_models" => ollama::execute_ollama_list_models(state).await,
+        "ollama_pull_model" => ollama::execute_ollama_pull_model(state, arguments),
+        "ollama_delete_model" => ollama::execute_ollama_delete_model(state, arguments).await,
+
+        // Tool search, help and result artifacts
+        "tool_search" => execute_tool_search(state, arguments),
+        "tool_help" => help::execute_tool_help(arguments),
+        "artifact_get" => artifact::execute_artifact_get(state, arguments, session_key),
 
         _ => Err(McpError {
             code: -32601,
@@ -146,7 +565,10 @@ async fn execute_internal_tool(
 /// Execute tool_search - search for tools using natural language.
 ///
 /// Returns tool_reference blocks per the Claude tool search tool specification.
-fn execute_tool_search(arguments: &serde_json::Value) -> Result<serde_json::Value, McpError> {
+fn execute_tool_search(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
     let query = arguments
         .get("query")
         .and_then(|v| v.as_str())
@@ -165,7 +587,23 @@ fn execute_tool_search(arguments: &serde_json::Value) -> Result<serde_json::Valu
         });
     }
 
-    let results = TOOL_SEARCH_INDEX.search(query, limit);
+    // Tools registered by FVTT clients aren't in the index, and those of
+    // other game systems are left out
+    let profile = state.service.mcp_system_profile();
+    let mut results = TOOL_SEARCH_INDEX.search(query, limit);
+    results.retain(|name| {
+        REGISTRY
+            .get_by_str(name)
+            .is_none_or(|t| profile.tool_available(name, t.category))
+    });
+    let client_tools = state.service.client_tools.search(
+        state.service.mcp_world_id().as_deref(),
+        query,
+        MCP_ROLE,
+        limit,
+    );
+    results.splice(0..0, client_tools);
+    results.truncate(limit);
 
     // Return tool_reference blocks per Claude docs
     let tool_references: Vec<serde_json::Value> = results
@@ -181,6 +619,21 @@ fn execute_tool_search(arguments: &serde_json::Value) -> Result<serde_json::Valu
     Ok(serde_json::json!({ "content": tool_references }))
 }
 
+/// A tool result holding a single text block
+pub(crate) fn text_result(text: String) -> serde_json::Value {
+    serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    })
+}
+
+/// A tool result holding a value as pretty-printed JSON text
+pub(crate) fn json_result(value: &impl serde::Serialize) -> serde_json::Value {
+    text_result(serde_json::to_string_pretty(value).unwrap_or_default())
+}
+
 /// Sanitize a string for use in a filename
 fn sanitize_filename(s: &str) -> String {
     s.chars()
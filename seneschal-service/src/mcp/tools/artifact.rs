@@ -0,0 +1,208 @@
+//! Tool result artifacts.
+//!
+//! Results too large for the conversation are stored whole and replaced by
+//! their opening lines; `artifact_get` reads further slices on demand.
+//! Artifacts go away with their MCP session, or once nothing has read them
+//! for a day.
+
+use chrono::{TimeDelta, Utc};
+use tracing::{debug, warn};
+
+use super::super::{McpError, McpState};
+
+/// Artifacts unread for this long are deleted
+const ARTIFACT_IDLE_TTL: TimeDelta = TimeDelta::hours(24);
+
+/// Lines returned when no range is given
+const DEFAULT_SLICE_LINES: usize = 200;
+
+/// Store an oversized tool result for the session, returning its artifact ID.
+///
+/// Results of `artifact_get` itself are never stored again.
+pub(super) fn store_artifact(
+    state: &McpState,
+    session_id: &str,
+    tool: &str,
+    text: &str,
+) -> Option<String> {
+    if tool == "artifact_get" {
+        return None;
+    }
+
+    if let Err(e) = state
+        .service
+        .db
+        .delete_idle_artifacts(Utc::now() - ARTIFACT_IDLE_TTL)
+    {
+        warn!(error = %e, "Failed to delete idle tool artifacts");
+    }
+
+    match state
+        .service
+        .db
+        .insert_tool_artifact(session_id, tool, text)
+    {
+        Ok(id) => {
+            debug!(tool = %tool, artifact_id = %id, "Stored oversized tool result");
+            Some(id)
+        }
+        Err(e) => {
+            warn!(tool = %tool, error = %e, "Failed to store tool artifact");
+            None
+        }
+    }
+}
+
+/// Delete everything stored for an MCP session
+pub(crate) fn delete_session_artifacts(state: &McpState, session_id: &str) {
+    match state.service.db.delete_session_artifacts(session_id) {
+        Ok(deleted) if deleted > 0 => {
+            debug!(session_id = %session_id, deleted, "Deleted session tool artifacts");
+        }
+        Ok(_) => {}
+        Err(e) => warn!(session_id = %session_id, error = %e, "Failed to delete tool artifacts"),
+    }
+}
+
+pub(super) fn execute_artifact_get(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    session_id: &str,
+) -> Result<serde_json::Value, McpError> {
+    let artifact_id = arguments
+        .get("artifact_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing artifact_id".to_string(),
+        })?;
+
+    let artifact = state
+        .service
+        .db
+        .get_tool_artifact(artifact_id, session_id)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| McpError {
+            code: -32000,
+            message: format!("Artifact not found or expired: {}", artifact_id),
+        })?;
+
+    let start_line = arguments
+        .get("start_line")
+        .and_then(|v| v.as_u64())
+        .map(|n| n.max(1) as usize);
+    let end_line = arguments
+        .get("end_line")
+        .and_then(|v| v.as_u64())
+        .map(|n| n as usize);
+    let pattern = arguments
+        .get("pattern")
+        .and_then(|v| v.as_str())
+        .filter(|p| !p.trim().is_empty());
+
+    let lines: Vec<&str> = artifact.content.lines().collect();
+    let header = format!(
+        "Artifact {} from {} ({} lines, saved {})",
+        artifact.id,
+        artifact.tool,
+        lines.len(),
+        artifact.created_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    let body = match pattern {
+        Some(pattern) => matching_lines(&lines, pattern, start_line, end_line),
+        None => {
+            let start = start_line.unwrap_or(1);
+            let end = end_line.unwrap_or(start + DEFAULT_SLICE_LINES - 1);
+            slice_lines(&lines, start, end)
+        }
+    };
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": format!("{}\n{}", header, body)
+        }]
+    }))
+}
+
+/// Lines `start..=end` (1-based), numbered
+fn slice_lines(lines: &[&str], start: usize, end: usize) -> String {
+    if start > lines.len() {
+        return format!("No lines from {} (the artifact has {})", start, lines.len());
+    }
+    let end = end.clamp(start, lines.len());
+    let mut out = format!("Lines {}-{}:", start, end);
+    for (i, line) in lines[start - 1..end].iter().enumerate() {
+        out.push_str(&format!("\n{:>5}| {}", start + i, line));
+    }
+    out
+}
+
+/// Numbered lines containing the pattern (case-insensitive), optionally
+/// within a line range
+fn matching_lines(
+    lines: &[&str],
+    pattern: &str,
+    start: Option<usize>,
+    end: Option<usize>,
+) -> String {
+    let needle = pattern.to_lowercase();
+    let start = start.unwrap_or(1);
+    let end = end.unwrap_or(lines.len());
+    let matches: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(n, line)| *n >= start && *n <= end && line.to_lowercase().contains(&needle))
+        .map(|(n, line)| format!("{:>5}| {}", n, line))
+        .collect();
+
+    if matches.is_empty() {
+        return format!("No lines match \"{}\"", pattern);
+    }
+    format!(
+        "Lines matching \"{}\" ({}):\n{}",
+        pattern,
+        matches.len(),
+        matches.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_slices() {
+        let lines = ["Regina 0410", "Efate 0705", "Boughene 0303", "Roup 0807"];
+
+        assert_eq!(
+            slice_lines(&lines, 2, 3),
+            "Lines 2-3:\n    2| Efate 0705\n    3| Boughene 0303"
+        );
+        assert_eq!(
+            slice_lines(&lines, 3, 99),
+            "Lines 3-4:\n    3| Boughene 0303\n    4| Roup 0807"
+        );
+        assert_eq!(
+            slice_lines(&lines, 9, 12),
+            "No lines from 9 (the artifact has 4)"
+        );
+
+        assert_eq!(
+            matching_lines(&lines, "ro", None, None),
+            "Lines matching \"ro\" (1):\n    4| Roup 0807"
+        );
+        assert_eq!(
+            matching_lines(&lines, "0", Some(2), Some(3)),
+            "Lines matching \"0\" (2):\n    2| Efate 0705\n    3| Boughene 0303"
+        );
+        assert_eq!(
+            matching_lines(&lines, "Jewell", None, None),
+            "No lines match \"Jewell\""
+        );
+    }
+}
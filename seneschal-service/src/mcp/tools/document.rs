@@ -1,112 +1,20 @@
 //! Document-related MCP tool implementations.
 
-use crate::search::format_search_results_for_llm;
-use crate::tools::{SearchFilters, TagMatch};
+mod glossary;
+mod library_data;
+mod related;
+mod search;
+mod similar;
 
-use super::super::{McpError, McpState};
+pub(super) use glossary::execute_glossary_lookup;
+pub(super) use library_data::execute_library_data;
+pub(super) use related::execute_document_related;
+pub(super) use search::{execute_document_search, execute_document_search_text};
+pub(super) use similar::execute_chunk_similar;
 
-pub(super) async fn execute_document_search(
-    state: &McpState,
-    arguments: &serde_json::Value,
-    gm_role: u8,
-) -> Result<serde_json::Value, McpError> {
-    let query = arguments
-        .get("query")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let tags: Vec<String> = arguments
-        .get("tags")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect()
-        })
-        .unwrap_or_default();
-    let limit = arguments
-        .get("limit")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(10) as usize;
+use crate::service::SeneschalService;
 
-    let filters = if tags.is_empty() {
-        None
-    } else {
-        Some(SearchFilters {
-            tags,
-            tags_match: TagMatch::Any,
-        })
-    };
-
-    match state.service.search(query, gm_role, limit, filters).await {
-        Ok(results) => {
-            let formatted = format_search_results_for_llm(&results, &state.service.i18n, "en");
-            Ok(serde_json::json!({
-                "content": [{
-                    "type": "text",
-                    "text": formatted
-                }]
-            }))
-        }
-        Err(e) => Err(McpError {
-            code: -32000,
-            message: e.to_string(),
-        }),
-    }
-}
-
-pub(super) fn execute_document_search_text(
-    state: &McpState,
-    arguments: &serde_json::Value,
-    gm_role: u8,
-) -> Result<serde_json::Value, McpError> {
-    let query = arguments
-        .get("query")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let section = arguments.get("section").and_then(|v| v.as_str());
-    let document_id = arguments.get("document_id").and_then(|v| v.as_str());
-    let limit = arguments
-        .get("limit")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(10) as usize;
-
-    match state
-        .service
-        .db
-        .search_chunks_fts(query, section, document_id, gm_role, limit)
-    {
-        Ok(chunks) => {
-            let results: Vec<serde_json::Value> = chunks
-                .into_iter()
-                .map(|c| {
-                    serde_json::json!({
-                        "document_id": c.document_id,
-                        "page_number": c.page_number,
-                        "section_title": c.section_title,
-                        "content": c.content,
-                    })
-                })
-                .collect();
-
-            let text = if results.is_empty() {
-                format!("No matches found for '{}'", query)
-            } else {
-                serde_json::to_string_pretty(&results).unwrap_or_default()
-            };
-
-            Ok(serde_json::json!({
-                "content": [{
-                    "type": "text",
-                    "text": text
-                }]
-            }))
-        }
-        Err(e) => Err(McpError {
-            code: -32000,
-            message: e.to_string(),
-        }),
-    }
-}
+use super::super::{McpError, McpState};
 
 pub(super) fn execute_document_get(
     state: &McpState,
@@ -123,9 +31,21 @@ pub(super) fn execute_document_get(
         .and_then(|v| v.as_i64())
         .map(|p| p as i32);
 
+    let version = arguments
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32);
+
     if let Some(page) = page_number {
         // Get all chunks for the specified page
-        match state.service.db.get_chunks_by_page(doc_id, page, gm_role) {
+        let chunks = match version {
+            Some(version) => state
+                .service
+                .db
+                .get_version_page_chunks(doc_id, version, page, gm_role),
+            None => state.service.db.get_chunks_by_page(doc_id, page, gm_role),
+        };
+        match chunks {
             Ok(chunks) => {
                 if chunks.is_empty() {
                     return Err(McpError {
@@ -161,13 +81,48 @@ pub(super) fn execute_document_get(
         match state.service.db.get_document(doc_id) {
             Ok(Some(doc)) => {
                 if doc.access_level.accessible_by(gm_role) {
+                    let mut text = format!(
+                        "Document: {}\nID: {}\nTags: {:?}\nChunks: {}\nImages: {}\n",
+                        doc.title, doc.id, doc.tags, doc.chunk_count, doc.image_count
+                    );
+                    if let Some(summary) = &doc.summary {
+                        text.push_str(&format!("\nSummary: {}\n", summary));
+                    }
+                    if let Some(outline) = doc.outline.as_ref().filter(|o| !o.is_empty()) {
+                        text.push_str("\nOutline:\n");
+                        for entry in outline {
+                            text.push_str(&format!("- {}\n", entry));
+                        }
+                    }
+                    let versions = state
+                        .service
+                        .db
+                        .list_document_versions(&doc.id)
+                        .unwrap_or_default();
+                    if !versions.is_empty() {
+                        text.push_str("\nVersions:\n");
+                        for version in &versions {
+                            let changed = match &version.changed_pages {
+                                Some(pages) => format!("{} pages changed", pages.len()),
+                                None if version.page_hashes.is_none() => "processing".to_string(),
+                                None => "original".to_string(),
+                            };
+                            text.push_str(&format!(
+                                "- {} ({}, {})\n",
+                                version.version,
+                                version.created_at.format("%Y-%m-%d"),
+                                changed
+                            ));
+                        }
+                    }
+                    text.push_str(
+                        "\nUse the 'page' parameter to retrieve content from a specific page.",
+                    );
+
                     Ok(serde_json::json!({
                         "content": [{
                             "type": "text",
-                            "text": format!(
-                                "Document: {}\nID: {}\nTags: {:?}\nChunks: {}\nImages: {}\n\nUse the 'page' parameter to retrieve content from a specific page.",
-                                doc.title, doc.id, doc.tags, doc.chunk_count, doc.image_count
-                            )
+                            "text": text
                         }]
                     }))
                 } else {
@@ -189,7 +144,7 @@ pub(super) fn execute_document_get(
     }
 }
 
-pub(super) fn execute_document_list(
+pub(super) async fn execute_document_list(
     state: &McpState,
     arguments: &serde_json::Value,
     gm_role: u8,
@@ -203,45 +158,99 @@ pub(super) fn execute_document_list(
                 .collect()
         })
         .unwrap_or_default();
+    let about = arguments
+        .get("about")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
 
-    match state.service.db.list_documents(Some(gm_role)) {
-        Ok(docs) => {
-            let filtered: Vec<_> = if tags.is_empty() {
-                docs
-            } else {
-                docs.into_iter()
-                    .filter(|d| tags.iter().any(|t| d.tags.contains(t)))
-                    .collect()
-            };
+    let docs = state
+        .service
+        .db
+        .list_documents(Some(gm_role))
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+    let world_id = state.service.mcp_world_id();
+    let filtered: Vec<_> = docs
+        .into_iter()
+        .filter(|d| d.in_world(world_id.as_deref()))
+        .filter(|d| tags.is_empty() || tags.iter().any(|t| d.tags.contains(t)))
+        .collect();
 
-            let doc_list: Vec<serde_json::Value> = filtered
-                .into_iter()
-                .map(|d| {
-                    serde_json::json!({
-                        "id": d.id,
-                        "title": d.title,
-                        "tags": d.tags,
-                        "chunk_count": d.chunk_count,
-                        "image_count": d.image_count
-                    })
+    let doc_list: Vec<serde_json::Value> = if let Some(about) = about {
+        // Rank by summary similarity; documents without a summary can't be ranked
+        let embedding = state
+            .service
+            .search
+            .embed_text(about)
+            .await
+            .map_err(|e| McpError {
+                code: -32000,
+                message: e.to_string(),
+            })?;
+        let ranked = state
+            .service
+            .db
+            .search_document_summaries(
+                &embedding,
+                gm_role,
+                state.service.mcp_world_id().as_deref(),
+                usize::MAX,
+            )
+            .map_err(|e| McpError {
+                code: -32000,
+                message: e.to_string(),
+            })?;
+
+        let mut by_id: std::collections::HashMap<_, _> =
+            filtered.into_iter().map(|d| (d.id.clone(), d)).collect();
+        ranked
+            .into_iter()
+            .filter_map(|(id, similarity)| by_id.remove(&id).map(|d| (d, similarity)))
+            .take(limit)
+            .map(|(d, similarity)| {
+                serde_json::json!({
+                    "id": d.id,
+                    "title": d.title,
+                    "tags": d.tags,
+                    "summary": d.summary,
+                    "relevance": similarity,
+                    "chunk_count": d.chunk_count,
+                    "image_count": d.image_count
                 })
-                .collect();
+            })
+            .collect()
+    } else {
+        filtered
+            .into_iter()
+            .map(|d| {
+                serde_json::json!({
+                    "id": d.id,
+                    "title": d.title,
+                    "tags": d.tags,
+                    "summary": d.summary,
+                    "chunk_count": d.chunk_count,
+                    "image_count": d.image_count
+                })
+            })
+            .collect()
+    };
 
-            let text = serde_json::to_string_pretty(&serde_json::json!({ "documents": doc_list }))
-                .unwrap_or_default();
+    let text = serde_json::to_string_pretty(&serde_json::json!({ "documents": doc_list }))
+        .unwrap_or_default();
 
-            Ok(serde_json::json!({
-                "content": [{
-                    "type": "text",
-                    "text": text
-                }]
-            }))
-        }
-        Err(e) => Err(McpError {
-            code: -32000,
-            message: e.to_string(),
-        }),
-    }
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
 }
 
 pub(super) fn execute_document_find(
@@ -257,8 +266,10 @@ pub(super) fn execute_document_find(
     match state.service.db.list_documents(Some(gm_role)) {
         Ok(docs) => {
             let query_lower = title_query.to_lowercase();
+            let world_id = state.service.mcp_world_id();
             let matches: Vec<serde_json::Value> = docs
                 .into_iter()
+                .filter(|d| d.in_world(world_id.as_deref()))
                 .filter(|d| d.title.to_lowercase().contains(&query_lower))
                 .map(|d| {
                     serde_json::json!({
@@ -364,6 +375,9 @@ pub(super) fn execute_document_update(
         .update_document(doc_id, &new_title, new_access_level, new_tags.clone())
     {
         Ok(true) => {
+            if new_title != current_doc.title || new_access_level != current_doc.access_level {
+                SeneschalService::refresh_document_summary(state.service.clone(), doc_id);
+            }
             let result = serde_json::json!({
                 "success": true,
                 "document_id": doc_id,
@@ -391,3 +405,107 @@ pub(super) fn execute_document_update(
         }),
     }
 }
+
+pub(super) fn execute_document_set_access(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let doc_id = arguments
+        .get("document_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let document = match state.service.db.get_document(doc_id) {
+        Ok(Some(doc)) => doc,
+        Ok(None) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Document not found".to_string(),
+            });
+        }
+        Err(e) => {
+            return Err(McpError {
+                code: -32000,
+                message: e.to_string(),
+            });
+        }
+    };
+
+    if !document.access_level.accessible_by(gm_role) {
+        return Err(McpError {
+            code: -32000,
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let to_mcp_error = |e: crate::error::ServiceError| McpError {
+        code: -32000,
+        message: e.to_string(),
+    };
+
+    let result = if let Some(rule_id) = arguments.get("remove_rule_id").and_then(|v| v.as_str()) {
+        let removed = state
+            .service
+            .delete_document_access_rule(doc_id, rule_id)
+            .map_err(to_mcp_error)?;
+        serde_json::json!({
+            "success": removed,
+            "document_id": doc_id,
+            "removed_rule_id": rule_id
+        })
+    } else if let Some(level) = arguments.get("access_level").and_then(|v| v.as_str()) {
+        let access_level = match level {
+            "player" => crate::tools::AccessLevel::Player,
+            "trusted" => crate::tools::AccessLevel::Trusted,
+            "assistant" => crate::tools::AccessLevel::Assistant,
+            _ => crate::tools::AccessLevel::GmOnly,
+        };
+        let page = |key: &str| {
+            arguments
+                .get(key)
+                .and_then(|v| v.as_i64())
+                .map(|p| p as i32)
+        };
+        let section_pattern = arguments
+            .get("section_pattern")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let (rule, chunks, images) = state
+            .service
+            .add_document_access_rule(
+                doc_id,
+                page("start_page"),
+                page("end_page"),
+                section_pattern,
+                access_level,
+            )
+            .map_err(to_mcp_error)?;
+        serde_json::json!({
+            "success": true,
+            "rule": rule,
+            "chunks_affected": chunks,
+            "images_affected": images
+        })
+    } else {
+        let rules = state
+            .service
+            .db
+            .get_document_access_rules(doc_id)
+            .map_err(to_mcp_error)?;
+        serde_json::json!({
+            "document_id": doc_id,
+            "document_access_level": document.access_level,
+            "rules": rules
+        })
+    };
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
@@ -0,0 +1,83 @@
+//! Glossary lookup MCP tool implementation.
+
+use std::collections::HashMap;
+
+use super::super::super::{McpError, McpState};
+
+pub(in super::super) fn execute_glossary_lookup(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let term = arguments
+        .get("term")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing term".to_string(),
+        })?;
+    let doc_id = arguments.get("document_id").and_then(|v| v.as_str());
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+
+    let entries = state
+        .service
+        .db
+        .lookup_glossary(
+            term,
+            doc_id,
+            gm_role,
+            state.service.mcp_world_id().as_deref(),
+            limit,
+        )
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let mut titles: HashMap<String, Option<String>> = HashMap::new();
+    let results: Vec<_> = entries
+        .into_iter()
+        .map(|entry| {
+            let title = titles
+                .entry(entry.document_id.clone())
+                .or_insert_with(|| {
+                    state
+                        .service
+                        .db
+                        .get_document(&entry.document_id)
+                        .ok()
+                        .flatten()
+                        .map(|doc| doc.title)
+                })
+                .clone();
+            serde_json::json!({
+                "term": entry.term,
+                "document_id": entry.document_id,
+                "document_title": title,
+                "pages": entry.pages,
+                "definition": entry.definition
+            })
+        })
+        .collect();
+
+    let text = if results.is_empty() {
+        format!(
+            "No index or glossary entries match \"{}\". Try document_search instead.",
+            term
+        )
+    } else {
+        serde_json::to_string_pretty(&serde_json::json!({ "entries": results })).unwrap_or_default()
+    };
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
@@ -0,0 +1,67 @@
+//! Library Data MCP tool implementation.
+
+use crate::service::{DEFAULT_MIN_CONFIDENCE, no_data_readout};
+use crate::tools::AccessLevel;
+
+use super::super::super::{McpError, McpState};
+
+pub(in super::super) async fn execute_library_data(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let topic = arguments
+        .get("topic")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing topic".to_string(),
+        })?;
+    // In-character lookups only see what the players could, unless asked
+    let access_level = arguments
+        .get("access_level")
+        .and_then(|v| v.as_str())
+        .map(|s| match s {
+            "trusted" => AccessLevel::Trusted,
+            "assistant" => AccessLevel::Assistant,
+            "gm_only" => AccessLevel::GmOnly,
+            _ => AccessLevel::Player,
+        })
+        .unwrap_or(AccessLevel::Player);
+    let min_confidence = arguments
+        .get("min_confidence")
+        .and_then(|v| v.as_f64())
+        .map(|c| c.clamp(0.0, 1.0) as f32)
+        .unwrap_or(DEFAULT_MIN_CONFIDENCE);
+
+    let entry = state
+        .service
+        .library_data(topic, (access_level as u8).min(gm_role), min_confidence)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let result = match entry {
+        Some(entry) => serde_json::json!({
+            "found": true,
+            "readout": entry.render(),
+            "entry": entry
+        }),
+        None => serde_json::json!({
+            "found": false,
+            "readout": no_data_readout(topic)
+        }),
+    };
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
@@ -0,0 +1,37 @@
+//! Related-document MCP tool implementation.
+
+use super::super::super::{McpError, McpState};
+
+pub(in super::super) fn execute_document_related(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let doc_id = arguments
+        .get("document_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let limit = arguments.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as usize;
+
+    match state.service.related_documents(
+        doc_id,
+        gm_role,
+        state.service.mcp_world_id().as_deref(),
+        limit,
+    ) {
+        Ok(related) => {
+            let text = serde_json::to_string_pretty(&serde_json::json!({ "documents": related }))
+                .unwrap_or_default();
+            Ok(serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }]
+            }))
+        }
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,158 @@
+//! Semantic and full-text document search MCP tool implementations.
+
+use crate::search::{
+    DEFAULT_SNIPPET_CHARS, RetrievalConfidence, format_search_results_for_llm,
+    format_search_snippets_for_llm,
+};
+use crate::tools::{SearchFilters, TagMatch};
+
+use super::super::super::{McpError, McpState};
+
+pub(in super::super) async fn execute_document_search(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+    session_key: &str,
+) -> Result<serde_json::Value, McpError> {
+    let query = arguments
+        .get("query")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let tags: Vec<String> = arguments
+        .get("tags")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+
+    let world_id = state.service.mcp_world_id();
+    let filters = if tags.is_empty() && world_id.is_none() {
+        None
+    } else {
+        Some(SearchFilters {
+            tags,
+            tags_match: TagMatch::Any,
+            world_id,
+            ..Default::default()
+        })
+    };
+
+    let snippets = arguments
+        .get("snippets")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    match state.service.search(query, gm_role, limit, filters).await {
+        Ok(results) => {
+            // Weak retrievals get a not-found answer so the model doesn't
+            // improvise rules from them
+            let retrieval = RetrievalConfidence::measure(query, &results);
+            let turn_confidence = state
+                .turn_grounding
+                .entry(session_key.to_string())
+                .or_default()
+                .record(retrieval.confidence);
+            let min_confidence = state
+                .service
+                .runtime_config
+                .dynamic()
+                .embeddings
+                .min_answer_confidence;
+
+            let formatted = if retrieval.confidence < min_confidence {
+                state.service.i18n.format(
+                    "en",
+                    "search-not-grounded",
+                    &[
+                        ("query", query),
+                        ("confidence", &format!("{:.2}", retrieval.confidence)),
+                    ],
+                )
+            } else if snippets {
+                let snippets = state
+                    .service
+                    .search
+                    .snippets(query, &results, DEFAULT_SNIPPET_CHARS, false)
+                    .await;
+                format_search_snippets_for_llm(&results, &snippets, &state.service.i18n, "en")
+            } else {
+                format_search_results_for_llm(&results, &state.service.i18n, "en")
+            };
+            Ok(serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": formatted
+                }],
+                "_meta": {
+                    "retrieval": retrieval,
+                    "turn_confidence": turn_confidence,
+                }
+            }))
+        }
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e.to_string(),
+        }),
+    }
+}
+
+pub(in super::super) fn execute_document_search_text(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let query = arguments
+        .get("query")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let section = arguments.get("section").and_then(|v| v.as_str());
+    let document_id = arguments.get("document_id").and_then(|v| v.as_str());
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+
+    match state
+        .service
+        .db
+        .search_chunks_fts(query, section, document_id, gm_role, limit)
+    {
+        Ok(chunks) => {
+            let results: Vec<serde_json::Value> = chunks
+                .into_iter()
+                .map(|c| {
+                    serde_json::json!({
+                        "document_id": c.document_id,
+                        "page_number": c.page_number,
+                        "section_title": c.section_title,
+                        "content": c.content,
+                    })
+                })
+                .collect();
+
+            let text = if results.is_empty() {
+                format!("No matches found for '{}'", query)
+            } else {
+                serde_json::to_string_pretty(&results).unwrap_or_default()
+            };
+
+            Ok(serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }]
+            }))
+        }
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e.to_string(),
+        }),
+    }
+}
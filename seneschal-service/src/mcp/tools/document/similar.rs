@@ -0,0 +1,43 @@
+//! Similar-chunk MCP tool implementation.
+
+use crate::search::format_search_results_for_llm;
+
+use super::super::super::{McpError, McpState};
+
+pub(in super::super) fn execute_chunk_similar(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let chunk_id = arguments
+        .get("chunk_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing chunk_id".to_string(),
+        })?;
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+
+    let world_id = state.service.mcp_world_id();
+    match state
+        .service
+        .search_chunks_like(chunk_id, gm_role, limit, world_id.as_deref())
+    {
+        Ok(results) => {
+            let formatted = format_search_results_for_llm(&results, &state.service.i18n, "en");
+            Ok(serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": formatted
+                }]
+            }))
+        }
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e.to_string(),
+        }),
+    }
+}
@@ -37,7 +37,7 @@ pub(super) async fn execute_external_tool(
 
     match state
         .service
-        .execute_external_tool_mcp(name, arguments, timeout)
+        .execute_external_tool_for_session(name, arguments, timeout, session_id)
         .await
     {
         Ok(result) => {
@@ -0,0 +1,86 @@
+//! Player handout MCP tool implementation.
+
+use serde::de::DeserializeOwned;
+
+use crate::service::{HandoutRequest, ImageDelivery};
+
+use super::super::{McpError, McpState};
+
+/// Parse an optional enum argument, defaulting when absent
+fn option_argument<T: DeserializeOwned + Default>(
+    arguments: &serde_json::Value,
+    name: &str,
+) -> Result<T, McpError> {
+    match arguments.get(name) {
+        Some(value) => serde_json::from_value(value.clone()).map_err(|e| McpError {
+            code: -32602,
+            message: format!("Invalid {}: {}", name, e),
+        }),
+        None => Ok(T::default()),
+    }
+}
+
+pub(super) async fn execute_handout_compose(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let text = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let request = HandoutRequest {
+        title: text("title"),
+        markdown: text("markdown"),
+        template: option_argument(arguments, "template")?,
+        format: option_argument(arguments, "format")?,
+        image_ids: arguments
+            .get("image_ids")
+            .and_then(|v| v.as_array())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let handout = state
+        .service
+        .compose_handout(&request, gm_role)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let download_url = format!("/api/handouts/{}", handout.file_name);
+    let result = match &handout.delivery {
+        ImageDelivery::Direct { fvtt_path } => serde_json::json!({
+            "success": true,
+            "mode": "direct",
+            "fvtt_path": fvtt_path,
+            "size_bytes": handout.size_bytes,
+            "message": format!("Handout '{}' delivered to FVTT assets at {}", handout.title, fvtt_path)
+        }),
+        ImageDelivery::Shuttle { suggested_path } => serde_json::json!({
+            "success": false,
+            "mode": "shuttle",
+            "download_url": download_url,
+            "suggested_path": suggested_path,
+            "size_bytes": handout.size_bytes,
+            "message": "Direct delivery not available. Use the FVTT module to fetch the handout and save it to assets."
+        }),
+    };
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
@@ -0,0 +1,255 @@
+//! Tool usage help and dry runs of write tools.
+
+use serde_json::Value;
+
+use crate::tools::registry::{ToolMetadata, ToolName};
+use crate::tools::{REGISTRY, ToolLocation};
+
+use super::super::McpError;
+
+/// Execute tool_help - describe a tool's parameters with an example call
+pub(super) fn execute_tool_help(arguments: &Value) -> Result<Value, McpError> {
+    let name = arguments.get("tool").and_then(|v| v.as_str()).unwrap_or("");
+    let tool = REGISTRY
+        .get_by_str(name)
+        .filter(|t| t.mcp_enabled)
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: format!("Unknown tool '{}'. Use tool_search to find tools.", name),
+        })?;
+
+    let schema = tool.input_schema();
+    let required = required_params(&schema);
+
+    let mut text = format!("{}\n\n{}\n", tool.name, tool.description);
+    if let Some(suffix) = tool.mcp_suffix {
+        text.push_str(&format!("{}\n", suffix));
+    }
+    text.push_str(&format!(
+        "\nCategory: {}\nRuns on: {}\n",
+        tool.category,
+        match tool.location {
+            ToolLocation::Internal => "Seneschal backend",
+            ToolLocation::External => "FVTT GM client",
+        }
+    ));
+    if tool.name.is_write() {
+        text.push_str("Changes FVTT world data.\n");
+    }
+    if tool.name.supports_dry_run() {
+        text.push_str(
+            "Pass dry_run: true to check the arguments and see the planned effect without executing.\n",
+        );
+    }
+
+    text.push_str("\nParameters:\n");
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    match properties {
+        Some(properties) if !properties.is_empty() => {
+            // Required parameters first
+            let mut names: Vec<&String> = properties.keys().collect();
+            names.sort_by_key(|n| !required.contains(&n.as_str()));
+            for param in names {
+                text.push_str(&describe_param(
+                    param,
+                    &properties[param],
+                    required.contains(&param.as_str()),
+                ));
+            }
+        }
+        _ => text.push_str("(none)\n"),
+    }
+
+    text.push_str(&format!(
+        "\nExample:\n{}",
+        serde_json::to_string_pretty(&example_value(&schema)).unwrap_or_default()
+    ));
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
+
+/// Check a write tool's arguments and report what it would do, without executing
+pub(super) fn execute_dry_run(name: &str, arguments: &Value) -> Result<Value, McpError> {
+    let tool = REGISTRY.get_by_str(name).ok_or_else(|| McpError {
+        code: -32602,
+        message: format!("Unknown tool: {}", name),
+    })?;
+
+    let mut problems = Vec::new();
+    if let Err(e) = REGISTRY.validate_arguments(name, arguments) {
+        problems.push(e);
+    }
+    problems.extend(
+        unknown_arguments(&tool.input_schema(), arguments)
+            .into_iter()
+            .map(|field| format!("arguments has unknown field '{}'", field)),
+    );
+
+    let result = serde_json::json!({
+        "dry_run": true,
+        "tool": name,
+        "valid": problems.is_empty(),
+        "problems": problems,
+        "planned_effect": planned_effect(tool, arguments),
+    });
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
+
+fn required_params(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|f| f.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn describe_param(name: &str, spec: &Value, required: bool) -> String {
+    let mut line = format!(
+        "- {} ({}, {})",
+        name,
+        type_label(spec),
+        if required { "required" } else { "optional" }
+    );
+    if let Some(description) = spec.get("description").and_then(|d| d.as_str()) {
+        line.push_str(&format!(": {}", description));
+    }
+    if let Some(allowed) = spec.get("enum").and_then(|e| e.as_array()) {
+        let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+        line.push_str(&format!(" Allowed: {}.", allowed.join(", ")));
+    }
+    line.push('\n');
+    line
+}
+
+fn type_label(spec: &Value) -> String {
+    let name = spec.get("type").and_then(|t| t.as_str()).unwrap_or("any");
+    match (name, spec.get("items")) {
+        ("array", Some(items)) => format!("array of {}", type_label(items)),
+        _ => name.to_string(),
+    }
+}
+
+/// A placeholder value for a schema, filling in only required object fields
+fn example_value(schema: &Value) -> Value {
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .and_then(|e| e.first())
+    {
+        return first.clone();
+    }
+    match schema.get("type").and_then(|t| t.as_str()) {
+        Some("object") => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let fields = required_params(schema)
+                .into_iter()
+                .map(|field| {
+                    let value = properties
+                        .and_then(|p| p.get(field))
+                        .map_or(Value::Null, example_value);
+                    (field.to_string(), value)
+                })
+                .collect();
+            Value::Object(fields)
+        }
+        Some("array") => Value::Array(schema.get("items").map(example_value).into_iter().collect()),
+        Some("integer") => serde_json::json!(1),
+        Some("number") => serde_json::json!(1.0),
+        Some("boolean") => Value::Bool(true),
+        Some("string") => Value::String("...".to_string()),
+        _ => Value::Null,
+    }
+}
+
+/// Top-level arguments the schema doesn't declare, often hallucinated parameters
+fn unknown_arguments(schema: &Value, arguments: &Value) -> Vec<String> {
+    let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+    arguments
+        .as_object()
+        .map(|fields| {
+            fields
+                .keys()
+                .filter(|field| !properties.contains_key(*field))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn planned_effect(tool: &ToolMetadata, arguments: &Value) -> String {
+    let summary = tool
+        .description
+        .split_once(". ")
+        .map_or(tool.description, |(first, _)| first)
+        .trim_end_matches('.');
+    let mut effect = format!("Would call {}: {}.", tool.name, summary);
+
+    let targets: Vec<String> = arguments
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(field, value)| field.ends_with("id") && value.is_string())
+        .map(|(field, value)| format!("{}={}", field, value.as_str().unwrap_or_default()))
+        .collect();
+    if !targets.is_empty() {
+        effect.push_str(&format!(" Target: {}.", targets.join(", ")));
+    }
+
+    if tool.name.is_write() {
+        effect.push_str(" Changes FVTT world data through the GM client.");
+        if tool.name.to_string().starts_with("delete_") {
+            if tool.name == ToolName::DeleteFolder || arguments.get("pack_id").is_some() {
+                effect.push_str(" Deletion cannot be undone.");
+            } else {
+                effect.push_str(" Can be reverted with undo_last_change.");
+            }
+        }
+    } else {
+        effect.push_str(" Writes a file into the FVTT assets directory.");
+    }
+    effect
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_and_unknown_arguments() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "type": {"type": "string", "enum": ["npc", "character"]},
+                "items": {"type": "array", "items": {"type": "integer"}},
+                "folder": {"type": "string"}
+            },
+            "required": ["name", "type", "items"]
+        });
+
+        assert_eq!(
+            example_value(&schema),
+            serde_json::json!({"name": "...", "type": "npc", "items": [1]})
+        );
+        assert_eq!(
+            unknown_arguments(
+                &schema,
+                &serde_json::json!({"name": "Vargr", "actor_type": "npc"})
+            ),
+            ["actor_type"]
+        );
+    }
+}
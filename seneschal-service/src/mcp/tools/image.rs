@@ -1,19 +1,37 @@
 //! Image-related MCP tool implementations.
 
-use crate::config::AssetsAccess;
+use crate::db::DocumentImageWithAccess;
 use crate::ingestion::IngestionService;
+use crate::service::{CaptionPreset, ImageDelivery};
+use crate::tools::{SearchFilters, TagMatch};
 
 use super::super::{McpError, McpState};
 
+/// Whether an image's document belongs to the MCP world (or is shared)
+pub(super) fn image_in_mcp_world(state: &McpState, img: &DocumentImageWithAccess) -> bool {
+    let world_id = state.service.mcp_world_id();
+    state
+        .service
+        .db
+        .get_document(&img.image.document_id)
+        .ok()
+        .flatten()
+        .is_some_and(|doc| doc.in_world(world_id.as_deref()))
+}
+
 pub(super) fn execute_image_list(
     state: &McpState,
     arguments: &serde_json::Value,
     gm_role: u8,
 ) -> Result<serde_json::Value, McpError> {
-    let doc_id = arguments
-        .get("document_id")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
+    let doc_id = arguments.get("document_id").and_then(|v| v.as_str());
+    let filters = parse_filters(state, arguments);
+    if doc_id.is_none() && filters.as_ref().is_none_or(|f| f.tags.is_empty()) {
+        return Err(McpError {
+            code: -32602,
+            message: "Give a document_id or tags".to_string(),
+        });
+    }
     let start_page = arguments
         .get("start_page")
         .and_then(|v| v.as_i64())
@@ -27,17 +45,22 @@ pub(super) fn execute_image_list(
         .and_then(|v| v.as_u64())
         .unwrap_or(20) as usize;
 
-    match state
-        .service
-        .db
-        .list_document_images(gm_role, Some(doc_id), start_page, end_page, limit)
-    {
+    match state.service.db.list_document_images(
+        gm_role,
+        doc_id,
+        start_page,
+        end_page,
+        filters.as_ref(),
+        limit,
+    ) {
         Ok(images) => {
             let image_list: Vec<_> = images
                 .into_iter()
                 .map(|img| {
                     serde_json::json!({
                         "id": img.image.id,
+                        "document_id": img.image.document_id,
+                        "document_title": img.document_title,
                         "page_number": img.image.page_number,
                         "image_index": img.image.image_index,
                         "width": img.image.width,
@@ -90,7 +113,12 @@ pub(super) async fn execute_image_search(
             message: format!("Failed to generate embedding: {}", e),
         })?;
 
-    match state.service.db.search_images(&embedding, gm_role, limit) {
+    let filters = parse_filters(state, arguments);
+    match state
+        .service
+        .db
+        .search_images(&embedding, gm_role, limit, filters.as_ref())
+    {
         Ok(results) => {
             let filtered: Vec<_> = results
                 .into_iter()
@@ -125,6 +153,129 @@ pub(super) async fn execute_image_search(
     }
 }
 
+pub(super) fn execute_image_tag(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let image_ids = string_list(arguments.get("image_ids"));
+    if image_ids.is_empty() {
+        return Err(McpError {
+            code: -32602,
+            message: "Missing image_ids".to_string(),
+        });
+    }
+    let add = string_list(arguments.get("add"));
+    let remove = string_list(arguments.get("remove"));
+    if add.is_empty() && remove.is_empty() {
+        return Err(McpError {
+            code: -32602,
+            message: "Give tags to add or remove".to_string(),
+        });
+    }
+
+    let db = &state.service.db;
+    let to_mcp_error = |e: crate::error::ServiceError| McpError {
+        code: -32000,
+        message: e.to_string(),
+    };
+    let found = if add.is_empty() {
+        image_ids.clone()
+    } else {
+        db.add_image_tags(&image_ids, &add).map_err(to_mcp_error)?
+    };
+    for image_id in &image_ids {
+        for tag in &remove {
+            db.remove_image_tag(image_id, tag).map_err(to_mcp_error)?;
+        }
+    }
+
+    let images: Vec<_> = image_ids
+        .iter()
+        .map(|id| {
+            if !found.contains(id) {
+                return Ok(serde_json::json!({ "id": id, "error": "Image not found" }));
+            }
+            let tags = db.get_image_tags(id).map_err(to_mcp_error)?;
+            Ok(serde_json::json!({
+                "id": id,
+                "tags": tags.tags,
+                "inherited": tags.inherited
+            }))
+        })
+        .collect::<Result<_, McpError>>()?;
+
+    let text =
+        serde_json::to_string_pretty(&serde_json::json!({ "images": images })).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
+
+pub(super) async fn execute_image_search_similar(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+
+    let result = if let Some(image_id) = arguments.get("image_id").and_then(|v| v.as_str()) {
+        state.service.search_images_like(image_id, gm_role, limit)
+    } else if let Some(path) = arguments.get("fvtt_path").and_then(|v| v.as_str()) {
+        state
+            .service
+            .search_images_by_fvtt_path(path, gm_role, limit)
+            .await
+    } else {
+        return Err(McpError {
+            code: -32602,
+            message: "Provide either fvtt_path or image_id".to_string(),
+        });
+    };
+
+    match result {
+        Ok(similar) => {
+            let images: Vec<_> = similar
+                .images
+                .into_iter()
+                .map(|(img, score)| {
+                    serde_json::json!({
+                        "id": img.image.id,
+                        "document_id": img.image.document_id,
+                        "document_title": img.document_title,
+                        "page_number": img.image.page_number,
+                        "image_index": img.image.image_index,
+                        "description": img.image.description,
+                        "similarity": score
+                    })
+                })
+                .collect();
+
+            let text = serde_json::to_string_pretty(&serde_json::json!({
+                "example_description": similar.description,
+                "images": images
+            }))
+            .unwrap_or_default();
+
+            Ok(serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }]
+            }))
+        }
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e.to_string(),
+        }),
+    }
+}
+
 pub(super) fn execute_image_get(
     state: &McpState,
     arguments: &serde_json::Value,
@@ -136,7 +287,7 @@ pub(super) fn execute_image_get(
         .unwrap_or("");
 
     match state.service.db.get_document_image(image_id) {
-        Ok(Some(img)) => {
+        Ok(Some(img)) if image_in_mcp_world(state, &img) => {
             if img.access_level.accessible_by(gm_role) {
                 let result = serde_json::json!({
                     "id": img.image.id,
@@ -164,7 +315,7 @@ pub(super) fn execute_image_get(
                 })
             }
         }
-        Ok(None) => Err(McpError {
+        Ok(_) => Err(McpError {
             code: -32000,
             message: "Image not found".to_string(),
         }),
@@ -191,7 +342,7 @@ pub(super) fn execute_image_deliver(
 
     // Get the image
     let img = match state.service.db.get_document_image(image_id) {
-        Ok(Some(img)) => {
+        Ok(Some(img)) if image_in_mcp_world(state, &img) => {
             if !img.access_level.accessible_by(gm_role) {
                 return Err(McpError {
                     code: -32000,
@@ -200,7 +351,7 @@ pub(super) fn execute_image_deliver(
             }
             img
         }
-        Ok(None) => {
+        Ok(_) => {
             return Err(McpError {
                 code: -32000,
                 message: "Image not found".to_string(),
@@ -225,63 +376,109 @@ pub(super) fn execute_image_deliver(
         .to_string()
     });
 
-    // The FVTT path is what FVTT uses to reference the file
-    let fvtt_path = format!("assets/{}", relative_path);
-
-    // Check assets access mode
-    match state
+    let delivery = state
         .service
-        .runtime_config
-        .static_config
-        .fvtt
-        .check_assets_access()
-    {
-        AssetsAccess::Direct(assets_dir) => {
-            // Create target directory
-            let full_path = assets_dir.join(&relative_path);
-            if let Some(parent) = full_path.parent()
-                && let Err(e) = std::fs::create_dir_all(parent)
-            {
-                return Err(McpError {
-                    code: -32000,
-                    message: format!("Failed to create directory: {}", e),
-                });
-            }
+        .deliver_image(&img, &relative_path)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: format!("Failed to deliver image: {}", e),
+        })?;
 
-            // Copy file
-            if let Err(e) = std::fs::copy(&img.image.internal_path, &full_path) {
-                return Err(McpError {
-                    code: -32000,
-                    message: format!("Failed to copy image: {}", e),
-                });
-            }
+    let result = match delivery {
+        ImageDelivery::Direct { fvtt_path } => serde_json::json!({
+            "success": true,
+            "mode": "direct",
+            "fvtt_path": fvtt_path,
+            "message": format!("Image delivered to FVTT assets at {}", fvtt_path)
+        }),
+        ImageDelivery::Shuttle { suggested_path } => serde_json::json!({
+            "success": false,
+            "mode": "shuttle",
+            "image_id": image_id,
+            "suggested_path": suggested_path,
+            "message": "Direct delivery not available. Use the FVTT module to fetch and deliver this image."
+        }),
+    };
 
-            let result = serde_json::json!({
-                "success": true,
-                "mode": "direct",
-                "fvtt_path": fvtt_path,
-                "message": format!("Image delivered to FVTT assets at {}", fvtt_path)
-            });
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
 
-            let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
 
-            Ok(serde_json::json!({
-                "content": [{
-                    "type": "text",
-                    "text": text
-                }]
-            }))
+pub(super) fn execute_image_recaption(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let doc_id = arguments
+        .get("document_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    match state.service.db.get_document(doc_id) {
+        Ok(Some(doc)) if doc.access_level.accessible_by(gm_role) => {}
+        Ok(_) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Document not found".to_string(),
+            });
         }
-        AssetsAccess::Shuttle => {
-            let result = serde_json::json!({
-                "success": false,
-                "mode": "shuttle",
-                "image_id": image_id,
-                "suggested_path": fvtt_path,
-                "message": "Direct delivery not available. Use the FVTT module to fetch and deliver this image."
+        Err(e) => {
+            return Err(McpError {
+                code: -32000,
+                message: e.to_string(),
             });
+        }
+    }
+
+    let image_ids: Vec<String> = arguments
+        .get("image_ids")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let start_page = arguments
+        .get("start_page")
+        .and_then(|v| v.as_i64())
+        .map(|p| p as i32);
+    let end_page = arguments
+        .get("end_page")
+        .and_then(|v| v.as_i64())
+        .map(|p| p as i32);
+    let vision_model = arguments
+        .get("vision_model")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    let preset = arguments
+        .get("preset")
+        .and_then(|v| v.as_str())
+        .map(CaptionPreset::from_str_lossy)
+        .unwrap_or_default();
 
-            let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    match state.service.recaption_images(
+        doc_id,
+        &image_ids,
+        start_page,
+        end_page,
+        vision_model,
+        preset,
+    ) {
+        Ok(count) => {
+            let text = serde_json::to_string_pretty(&serde_json::json!({
+                "success": true,
+                "document_id": doc_id,
+                "queued_count": count,
+                "preset": preset.as_str()
+            }))
+            .unwrap_or_default();
 
             Ok(serde_json::json!({
                 "content": [{
@@ -290,5 +487,44 @@ pub(super) fn execute_image_deliver(
                 }]
             }))
         }
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e.to_string(),
+        }),
     }
 }
+
+/// The `tags`, `tags_match` and `exclude_used` arguments and the MCP world
+/// as a filter, if any is set
+fn parse_filters(state: &McpState, arguments: &serde_json::Value) -> Option<SearchFilters> {
+    let tags = string_list(arguments.get("tags"));
+    let world_id = state.service.mcp_world_id();
+    let exclude_used = arguments
+        .get("exclude_used")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    (!tags.is_empty() || world_id.is_some() || exclude_used).then(|| SearchFilters {
+        tags,
+        tags_match: match arguments.get("tags_match").and_then(|v| v.as_str()) {
+            Some("all") => TagMatch::All,
+            _ => TagMatch::Any,
+        },
+        world_id,
+        exclude_used,
+    })
+}
+
+/// Non-empty trimmed strings from a JSON array argument
+fn string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,86 @@
+//! Campaign memory tool implementations.
+
+use super::super::{McpError, McpState};
+use super::text_result;
+
+pub(super) async fn execute_memory_set(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let fact = arguments.get("fact").and_then(|v| v.as_str()).unwrap_or("");
+    let memory_id = arguments
+        .get("memory_id")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty());
+
+    let result = match memory_id {
+        Some(id) => state.service.update_memory(id, fact).await,
+        None => {
+            state
+                .service
+                .add_memory(fact, state.service.mcp_world_id())
+                .await
+        }
+    };
+    let memory = result.map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?;
+
+    Ok(text_result(format!(
+        "Remembered (memory {}): {}",
+        memory.id, memory.fact
+    )))
+}
+
+pub(super) async fn execute_memory_recall(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let query = arguments
+        .get("query")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if query.trim().is_empty() {
+        return Err(McpError {
+            code: -32602,
+            message: "Query parameter is required".to_string(),
+        });
+    }
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+
+    let memories = state
+        .service
+        .recall_memories(query, limit)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    if memories.is_empty() {
+        return Ok(text_result(
+            "No campaign facts have been remembered yet.".to_string(),
+        ));
+    }
+
+    let results: Vec<_> = memories
+        .into_iter()
+        .map(|(memory, similarity)| {
+            serde_json::json!({
+                "memory_id": memory.id,
+                "fact": memory.fact,
+                "relevance": similarity,
+                "updated_at": memory.updated_at.format("%Y-%m-%d").to_string()
+            })
+        })
+        .collect();
+
+    Ok(text_result(
+        serde_json::to_string_pretty(&serde_json::json!({ "memories": results }))
+            .unwrap_or_default(),
+    ))
+}
@@ -0,0 +1,187 @@
+//! NPC registry tool implementations.
+
+use crate::service::NpcUpdate;
+
+use super::super::{McpError, McpState};
+use super::text_result;
+
+fn string_argument(arguments: &serde_json::Value, name: &str) -> Option<String> {
+    arguments
+        .get(name)
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+fn required_argument(arguments: &serde_json::Value, name: &str) -> Result<String, McpError> {
+    string_argument(arguments, name)
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: format!("Missing {}", name),
+        })
+}
+
+fn service_error(e: impl std::fmt::Display) -> McpError {
+    McpError {
+        code: -32000,
+        message: e.to_string(),
+    }
+}
+
+pub(super) fn execute_npc_set(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let update = NpcUpdate {
+        npc_id: string_argument(arguments, "npc_id"),
+        name: string_argument(arguments, "name"),
+        description: string_argument(arguments, "description"),
+        stat_block_id: string_argument(arguments, "stat_block_id"),
+        actor_uuid: string_argument(arguments, "actor_uuid"),
+    };
+
+    let npc = state
+        .service
+        .set_npc(state.service.mcp_world_id(), update)
+        .map_err(service_error)?;
+
+    Ok(text_result(format!(
+        "Saved NPC {} (npc {})",
+        npc.name, npc.id
+    )))
+}
+
+pub(super) fn execute_npc_relate(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let source = required_argument(arguments, "source")?;
+    let relation = required_argument(arguments, "relation")?;
+    let target = required_argument(arguments, "target")?;
+    let world_id = state.service.mcp_world_id();
+
+    if arguments
+        .get("remove")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        let removed = state
+            .service
+            .unrelate_npcs(world_id.as_deref(), &source, &relation, &target)
+            .map_err(service_error)?;
+        return Ok(text_result(if removed {
+            format!("Removed: {} {} {}", source, relation, target)
+        } else {
+            format!("No such relationship: {} {} {}", source, relation, target)
+        }));
+    }
+
+    let link = state
+        .service
+        .relate_npcs(
+            world_id,
+            &source,
+            &relation,
+            &target,
+            string_argument(arguments, "notes"),
+        )
+        .map_err(service_error)?;
+
+    Ok(text_result(format!(
+        "Recorded: {} {} {}",
+        link.source, link.relation, link.target
+    )))
+}
+
+pub(super) fn execute_npc_get(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let key = required_argument(arguments, "npc")?;
+    let Some(npc) = state
+        .service
+        .resolve_npc(state.service.mcp_world_id().as_deref(), &key)
+        .map_err(service_error)?
+    else {
+        return Ok(text_result(format!(
+            "No NPC named '{}' is registered; use npc_set to add them.",
+            key
+        )));
+    };
+
+    let stat_block = match &npc.stat_block_id {
+        Some(id) => state
+            .service
+            .db
+            .get_stat_block(id, gm_role, state.service.mcp_world_id().as_deref())
+            .map_err(service_error)?,
+        None => None,
+    };
+    let links = state.service.npc_links(&npc).map_err(service_error)?;
+
+    let result = serde_json::json!({
+        "npc_id": npc.id,
+        "name": npc.name,
+        "description": npc.description,
+        "actor_uuid": npc.actor_uuid,
+        "stat_block": stat_block.map(|block| serde_json::json!({
+            "id": block.id,
+            "name": block.name,
+            "data": block.data
+        })),
+        "relationships": links
+            .iter()
+            .map(|link| format!("{} {} {}", link.source, link.relation, link.target))
+            .collect::<Vec<_>>(),
+        "updated_at": npc.updated_at.format("%Y-%m-%d").to_string()
+    });
+
+    Ok(text_result(
+        serde_json::to_string_pretty(&result).unwrap_or_default(),
+    ))
+}
+
+pub(super) fn execute_npc_relations(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let world_id = state.service.mcp_world_id();
+    let links = match string_argument(arguments, "npc").filter(|n| !n.trim().is_empty()) {
+        Some(key) => {
+            let npc = state
+                .service
+                .resolve_npc(world_id.as_deref(), &key)
+                .map_err(service_error)?
+                .ok_or_else(|| McpError {
+                    code: -32000,
+                    message: format!("NPC not found: {}", key),
+                })?;
+            state.service.npc_links(&npc).map_err(service_error)?
+        }
+        None => state
+            .service
+            .npc_graph(world_id.as_deref())
+            .map_err(service_error)?
+            .links(),
+    };
+
+    let filter = string_argument(arguments, "relation").map(|r| r.trim().to_lowercase());
+    let links: Vec<_> = links
+        .into_iter()
+        .filter(|link| {
+            filter
+                .as_deref()
+                .is_none_or(|filter| link.relation.contains(filter))
+        })
+        .collect();
+
+    if links.is_empty() {
+        return Ok(text_result("No matching NPC relationships.".to_string()));
+    }
+
+    Ok(text_result(
+        serde_json::to_string_pretty(&serde_json::json!({ "relationships": links }))
+            .unwrap_or_default(),
+    ))
+}
@@ -0,0 +1,81 @@
+//! Ollama model management tool implementations.
+
+use super::super::{McpError, McpState};
+use super::json_result;
+
+fn model_argument(arguments: &serde_json::Value) -> Result<&str, McpError> {
+    arguments
+        .get("model")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing model".to_string(),
+        })
+}
+
+pub(super) async fn execute_ollama_list_models(
+    state: &McpState,
+) -> Result<serde_json::Value, McpError> {
+    let models = state
+        .service
+        .list_local_models()
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let config = state.service.runtime_config.dynamic();
+    Ok(json_result(&serde_json::json!({
+        "models": models,
+        "configured": {
+            "chat": config.ollama.default_model,
+            "vision": config.ollama.vision_model,
+            "embeddings": config.embeddings.model,
+        },
+        "routes": state.service.model_routes(),
+        "pulls": state.service.model_pull_status(),
+    })))
+}
+
+pub(super) fn execute_ollama_pull_model(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let model = model_argument(arguments)?;
+    let started = state.service.start_model_pull(model);
+
+    let message = if started {
+        format!("Started pulling {}", model)
+    } else {
+        format!("{} is already being pulled", model)
+    };
+    Ok(json_result(&serde_json::json!({
+        "model": model,
+        "started": started,
+        "message": message,
+    })))
+}
+
+pub(super) async fn execute_ollama_delete_model(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let model = model_argument(arguments)?;
+
+    state
+        .service
+        .delete_model(model)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(json_result(&serde_json::json!({
+        "success": true,
+        "model": model,
+    })))
+}
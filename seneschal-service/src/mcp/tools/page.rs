@@ -0,0 +1,132 @@
+//! Page rendering MCP tool implementation.
+
+use crate::config::AssetsAccess;
+use crate::ingestion::IngestionService;
+use crate::ingestion::pdf::page_render::{
+    DEFAULT_RENDER_DPI, PageImageFormat, PageRegion, write_page_image,
+};
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_page_deliver(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let document_id = arguments
+        .get("document_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let page = arguments
+        .get("page")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "page is required".to_string(),
+        })? as u32;
+    let dpi = arguments
+        .get("dpi")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+        .unwrap_or(DEFAULT_RENDER_DPI);
+    let region = match arguments.get("region") {
+        Some(value) if !value.is_null() => Some(
+            serde_json::from_value::<PageRegion>(value.clone()).map_err(|e| McpError {
+                code: -32602,
+                message: format!("Invalid region: {}", e),
+            })?,
+        ),
+        _ => None,
+    };
+    let target_path = arguments
+        .get("target_path")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let document = match state.service.db.get_document(&document_id) {
+        Ok(Some(doc))
+            if doc.access_level.accessible_by(gm_role)
+                && doc.in_world(state.service.mcp_world_id().as_deref()) =>
+        {
+            doc
+        }
+        Ok(_) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Document not found".to_string(),
+            });
+        }
+        Err(e) => {
+            return Err(McpError {
+                code: -32000,
+                message: e.to_string(),
+            });
+        }
+    };
+
+    let relative_path = target_path.unwrap_or_else(|| {
+        let label = if region.is_some() { "region" } else { "render" };
+        IngestionService::fvtt_image_path(&document.title, page as i32, Some(label))
+            .to_string_lossy()
+            .to_string()
+    });
+    let fvtt_path = format!("assets/{}", relative_path);
+
+    let result = match state
+        .service
+        .runtime_config
+        .static_config
+        .fvtt
+        .check_assets_access()
+    {
+        AssetsAccess::Direct(assets_dir) => {
+            let full_path = assets_dir.join(&relative_path);
+
+            // Rendering loads the PDF and rasterizes the page, so keep it off the async runtime
+            let service = state.service.clone();
+            let doc_id = document_id.clone();
+            tokio::task::spawn_blocking(move || {
+                let image = service.render_page_image(&doc_id, page, dpi, region, gm_role)?;
+                write_page_image(&image, &full_path, PageImageFormat::Webp)?;
+                Ok::<_, crate::error::ServiceError>((image.width(), image.height()))
+            })
+            .await
+            .map_err(|e| McpError {
+                code: -32000,
+                message: e.to_string(),
+            })?
+            .map(|(width, height)| {
+                serde_json::json!({
+                    "success": true,
+                    "mode": "direct",
+                    "fvtt_path": fvtt_path,
+                    "width": width,
+                    "height": height,
+                    "message": format!("Page {} delivered to FVTT assets at {}", page, fvtt_path)
+                })
+            })
+            .map_err(|e| McpError {
+                code: -32000,
+                message: e.to_string(),
+            })?
+        }
+        AssetsAccess::Shuttle => serde_json::json!({
+            "success": false,
+            "mode": "shuttle",
+            "document_id": document_id,
+            "page": page,
+            "render_url": format!("/api/documents/{}/pages/{}/render?dpi={}", document_id, page, dpi),
+            "suggested_path": fvtt_path,
+            "message": "Direct delivery not available. Use the FVTT module to fetch the rendered page and save it to assets; regions are only cropped in direct mode."
+        }),
+    };
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
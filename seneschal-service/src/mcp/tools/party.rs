@@ -0,0 +1,28 @@
+//! Party context tool implementations.
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_party_characters(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let include_owned = arguments
+        .get("include_owned")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let players = state.service.party_character_summaries(include_owned).await;
+
+    let text = if players.is_empty() {
+        "No players are currently connected to Foundry VTT.".to_string()
+    } else {
+        serde_json::to_string_pretty(&players).unwrap_or_default()
+    };
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
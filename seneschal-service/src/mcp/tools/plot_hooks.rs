@@ -0,0 +1,65 @@
+//! Rumor and plot hook MCP tool implementation.
+
+use crate::service::{DEFAULT_HOOK_TAG, MAX_PLOT_HOOKS};
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_plot_hooks(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let location = arguments
+        .get("location")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let count = arguments
+        .get("count")
+        .and_then(|v| v.as_u64())
+        .map(|c| (c as usize).min(MAX_PLOT_HOOKS))
+        .unwrap_or(3);
+    let tag = arguments
+        .get("tag")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(DEFAULT_HOOK_TAG);
+    let seed = arguments.get("seed").and_then(|v| v.as_u64());
+    let service_error = |e: crate::error::ServiceError| McpError {
+        code: -32000,
+        message: e.to_string(),
+    };
+
+    let mut cleared = 0;
+    if arguments
+        .get("reset")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        cleared = state.service.reset_plot_hooks().map_err(service_error)?;
+    }
+
+    let hooks = state
+        .service
+        .plot_hooks(location, count, tag, seed, gm_role)
+        .await
+        .map_err(service_error)?;
+
+    let mut result = serde_json::to_value(&hooks).unwrap_or_default();
+    if cleared > 0 {
+        result["reset"] = serde_json::json!(cleared);
+    }
+    if hooks.hooks.is_empty() {
+        result["message"] = serde_json::json!(format!(
+            "No unused adventure passages tagged '{}' mention {}",
+            tag, hooks.location
+        ));
+    }
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
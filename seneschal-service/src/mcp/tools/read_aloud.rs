@@ -0,0 +1,64 @@
+//! Read-aloud text tool implementations.
+
+use super::super::{McpError, McpState};
+
+pub(super) fn execute_read_aloud_get(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let scene = arguments
+        .get("scene")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let doc_id = arguments.get("document_id").and_then(|v| v.as_str());
+    let page = arguments
+        .get("page")
+        .and_then(|v| v.as_i64())
+        .map(|p| p as i32);
+    if scene.is_none() && doc_id.is_none() && page.is_none() {
+        return Err(McpError {
+            code: -32602,
+            message: "Give a scene, document_id or page".to_string(),
+        });
+    }
+    let limit = arguments.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as usize;
+
+    let world_id = state.service.mcp_world_id();
+    let passages = state
+        .service
+        .db
+        .find_read_aloud(doc_id, page, scene, gm_role, world_id.as_deref(), limit)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let text = if passages.is_empty() {
+        "No read-aloud text found.".to_string()
+    } else {
+        let results: Vec<_> = passages
+            .into_iter()
+            .map(|passage| {
+                serde_json::json!({
+                    "id": passage.id,
+                    "document_id": passage.document_id,
+                    "page_number": passage.page_number,
+                    "section": passage.section_title,
+                    "text": passage.text,
+                    "validated": passage.validated
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&serde_json::json!({ "passages": results }))
+            .unwrap_or_default()
+    };
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
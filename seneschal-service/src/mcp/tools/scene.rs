@@ -0,0 +1,97 @@
+//! Map scene settings tool implementation.
+
+use crate::service::ImageDelivery;
+
+use super::super::{McpError, McpState};
+use super::image::image_in_mcp_world;
+
+pub(super) async fn execute_image_scene_settings(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let image_id = arguments
+        .get("image_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing image_id".to_string(),
+        })?
+        .to_string();
+    let grid_distance = arguments
+        .get("grid_distance")
+        .and_then(|v| v.as_f64())
+        .map(|d| d as f32);
+    let grid_units = arguments
+        .get("grid_units")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    match state.service.db.get_document_image(&image_id) {
+        Ok(Some(img)) if !image_in_mcp_world(state, &img) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Image not found".to_string(),
+            });
+        }
+        Ok(Some(img)) if img.access_level.accessible_by(gm_role) => {}
+        Ok(Some(_)) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Access denied".to_string(),
+            });
+        }
+        Ok(None) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Image not found".to_string(),
+            });
+        }
+        Err(e) => {
+            return Err(McpError {
+                code: -32000,
+                message: e.to_string(),
+            });
+        }
+    }
+
+    // Grid detection decodes the whole map
+    let service = state.service.clone();
+    let map = tokio::task::spawn_blocking(move || {
+        service.map_scene(&image_id, grid_distance, grid_units.as_deref())
+    })
+    .await
+    .map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?
+    .map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?;
+
+    let message = match &map.delivery {
+        ImageDelivery::Direct { .. } => {
+            "Map delivered to FVTT assets. Pass the scene settings to create_scene, then set the background offset on the scene.".to_string()
+        }
+        ImageDelivery::Shuttle { suggested_path } => format!(
+            "Direct delivery not available. Use the FVTT module to fetch the image and upload it to {} before creating the scene.",
+            suggested_path
+        ),
+    };
+    let result = serde_json::json!({
+        "image_id": map.grid.image_id,
+        "grid": map.grid,
+        "scene": map.scene,
+        "delivery": map.delivery,
+        "message": message,
+    });
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
@@ -0,0 +1,124 @@
+//! Session recap and note tool implementations.
+
+use super::super::{McpError, McpState};
+use crate::service::SessionSummaryOptions;
+use crate::tools::AccessLevel;
+
+pub(super) async fn execute_session_summary(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let title = arguments
+        .get("title")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing title".to_string(),
+        })?;
+
+    let access_level = parse_access_level(arguments);
+
+    let options = SessionSummaryOptions {
+        title: title.trim().to_string(),
+        transcript: arguments
+            .get("transcript")
+            .and_then(|v| v.as_str())
+            .filter(|t| !t.trim().is_empty())
+            .map(str::to_string),
+        include_chat_log: arguments
+            .get("include_chat_log")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true),
+        chat_since: arguments
+            .get("since")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        access_level,
+        write_journal: arguments
+            .get("write_journal")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        journal_folder: arguments
+            .get("folder")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    };
+
+    let summary = state
+        .service
+        .generate_session_summary(options)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&summary).unwrap_or_default()
+        }]
+    }))
+}
+
+pub(super) async fn execute_save_note(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let title = arguments.get("title").and_then(|v| v.as_str());
+    let content = arguments
+        .get("content")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing content".to_string(),
+        })?;
+
+    let tags: Vec<String> = arguments
+        .get("tags")
+        .and_then(|v| v.as_array())
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|t| t.as_str())
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let document = state
+        .service
+        .save_note(title, content, tags, parse_access_level(arguments))
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": format!(
+                "Saved note '{}' as document {} (tags: {}). It will be searchable once processing finishes.",
+                document.title,
+                document.id,
+                document.tags.join(", ")
+            )
+        }]
+    }))
+}
+
+/// The `access_level` argument, defaulting to GM only
+fn parse_access_level(arguments: &serde_json::Value) -> AccessLevel {
+    arguments
+        .get("access_level")
+        .and_then(|v| v.as_str())
+        .map(|s| match s {
+            "player" => AccessLevel::Player,
+            "trusted" => AccessLevel::Trusted,
+            "assistant" => AccessLevel::Assistant,
+            _ => AccessLevel::GmOnly,
+        })
+        .unwrap_or(AccessLevel::GmOnly)
+}
@@ -0,0 +1,67 @@
+//! Text-to-speech MCP tool implementation.
+
+use crate::service::{ImageDelivery, SpeechSource};
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_speech_synthesize(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let argument = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    let source = match (argument("text"), argument("passage_id")) {
+        (Some(text), None) => SpeechSource::Text(text),
+        (None, Some(passage_id)) => SpeechSource::ReadAloud(passage_id),
+        _ => {
+            return Err(McpError {
+                code: -32602,
+                message: "Give either text or passage_id".to_string(),
+            });
+        }
+    };
+
+    let world_id = state.service.mcp_world_id();
+    let clip = state
+        .service
+        .synthesize_speech(&source, gm_role, world_id.as_deref())
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let download_url = format!("/api/speech/{}", clip.file_name);
+    let result = match &clip.delivery {
+        ImageDelivery::Direct { fvtt_path } => serde_json::json!({
+            "success": true,
+            "mode": "direct",
+            "fvtt_path": fvtt_path,
+            "size_bytes": clip.size_bytes,
+            "message": format!("Speech delivered to FVTT assets at {}", fvtt_path)
+        }),
+        ImageDelivery::Shuttle { suggested_path } => serde_json::json!({
+            "success": false,
+            "mode": "shuttle",
+            "download_url": download_url,
+            "suggested_path": suggested_path,
+            "size_bytes": clip.size_bytes,
+            "message": "Direct delivery not available. Use the FVTT module to fetch the clip and save it to assets."
+        }),
+    };
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
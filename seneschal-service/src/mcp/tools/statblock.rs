@@ -0,0 +1,167 @@
+//! Stat block and actor payload tool implementations.
+
+use super::super::{McpError, McpState};
+use crate::tools::fvtt_actor::build_actor_payload;
+
+pub(super) fn execute_statblock_search(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let query = arguments
+        .get("query")
+        .and_then(|v| v.as_str())
+        .filter(|q| !q.is_empty());
+    let kind = arguments.get("kind").and_then(|v| v.as_str());
+    let doc_id = arguments.get("document_id").and_then(|v| v.as_str());
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(20) as usize;
+
+    let blocks = state
+        .service
+        .db
+        .search_stat_blocks(
+            query,
+            kind,
+            doc_id,
+            gm_role,
+            state.service.mcp_world_id().as_deref(),
+            limit,
+        )
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let results: Vec<_> = blocks
+        .into_iter()
+        .map(|block| {
+            serde_json::json!({
+                "id": block.id,
+                "name": block.name,
+                "kind": block.kind,
+                "document_id": block.document_id,
+                "page_number": block.page_number,
+                "validated": block.validated
+            })
+        })
+        .collect();
+
+    let text = serde_json::to_string_pretty(&serde_json::json!({ "stat_blocks": results }))
+        .unwrap_or_default();
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
+
+pub(super) fn execute_statblock_get(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let id = arguments
+        .get("statblock_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing statblock_id".to_string(),
+        })?;
+
+    let block = state
+        .service
+        .db
+        .get_stat_block(id, gm_role, state.service.mcp_world_id().as_deref())
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| McpError {
+            code: -32000,
+            message: format!("Stat block not found: {}", id),
+        })?;
+
+    // Extraction is best-effort, so a block may not map to a complete actor
+    let fvtt_actor = match build_actor_payload(&block.data, None) {
+        Ok(payload) => serde_json::to_value(payload).unwrap_or_default(),
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+
+    let text = serde_json::to_string_pretty(&serde_json::json!({
+        "id": block.id,
+        "name": block.name,
+        "kind": block.kind,
+        "document_id": block.document_id,
+        "page_number": block.page_number,
+        "validated": block.validated,
+        "data": block.data,
+        "raw_text": block.raw_text,
+        "fvtt_actor": fvtt_actor
+    }))
+    .unwrap_or_default();
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
+
+pub(super) fn execute_fvtt_build_actor(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let stat_block = match (
+        arguments.get("statblock_id").and_then(|v| v.as_str()),
+        arguments.get("stat_block").filter(|v| v.is_object()),
+    ) {
+        (Some(id), _) => {
+            state
+                .service
+                .db
+                .get_stat_block(id, gm_role, state.service.mcp_world_id().as_deref())
+                .map_err(|e| McpError {
+                    code: -32000,
+                    message: e.to_string(),
+                })?
+                .ok_or_else(|| McpError {
+                    code: -32000,
+                    message: format!("Stat block not found: {}", id),
+                })?
+                .data
+        }
+        (None, Some(stat_block)) => stat_block.clone(),
+        (None, None) => {
+            return Err(McpError {
+                code: -32602,
+                message: "Provide either statblock_id or stat_block".to_string(),
+            });
+        }
+    };
+
+    let actor_type = arguments.get("actor_type").and_then(|v| v.as_str());
+    let mut payload = build_actor_payload(&stat_block, actor_type).map_err(|message| McpError {
+        code: -32602,
+        message,
+    })?;
+
+    for key in ["img", "folder", "pack_id"] {
+        if let Some(value) = arguments.get(key).and_then(|v| v.as_str()) {
+            payload.create_actor[key] = serde_json::Value::from(value);
+        }
+    }
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&payload).unwrap_or_default()
+        }]
+    }))
+}
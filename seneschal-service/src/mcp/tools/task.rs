@@ -0,0 +1,108 @@
+//! Campaign task tool implementations.
+
+use super::super::{McpError, McpState};
+use super::text_result;
+
+pub(super) fn execute_task_add(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    session_key: &str,
+) -> Result<serde_json::Value, McpError> {
+    let description = arguments
+        .get("description")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if description.trim().is_empty() {
+        return Err(McpError {
+            code: -32602,
+            message: "Description parameter is required".to_string(),
+        });
+    }
+
+    let task = state
+        .service
+        .add_task(
+            description,
+            state.service.mcp_world_id(),
+            Some(session_key).filter(|key| !key.is_empty()),
+        )
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(text_result(format!(
+        "Noted (task {}): {}",
+        task.id, task.description
+    )))
+}
+
+pub(super) fn execute_task_list(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let include_completed = arguments
+        .get("include_completed")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(50) as usize;
+
+    let tasks = state
+        .service
+        .db
+        .list_tasks(
+            state.service.mcp_world_id().as_deref(),
+            include_completed,
+            limit,
+        )
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    if tasks.is_empty() {
+        return Ok(text_result("There are no open tasks.".to_string()));
+    }
+
+    let results: Vec<_> = tasks
+        .into_iter()
+        .map(|task| {
+            serde_json::json!({
+                "task_id": task.id,
+                "description": task.description,
+                "added": task.created_at.format("%Y-%m-%d").to_string(),
+                "completed": task.completed_at.map(|t| t.format("%Y-%m-%d").to_string())
+            })
+        })
+        .collect();
+
+    Ok(text_result(
+        serde_json::to_string_pretty(&serde_json::json!({ "tasks": results })).unwrap_or_default(),
+    ))
+}
+
+pub(super) fn execute_task_complete(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let task_id = arguments
+        .get("task_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "task_id parameter is required".to_string(),
+        })?;
+
+    let task = state.service.complete_task(task_id).map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?;
+
+    Ok(text_result(format!(
+        "Done (task {}): {}",
+        task.id, task.description
+    )))
+}
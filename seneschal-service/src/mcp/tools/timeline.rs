@@ -0,0 +1,228 @@
+//! Campaign timeline tool implementations.
+
+use crate::ingestion::timeline::ImperialDate;
+use crate::service::ClockAdvance;
+use crate::tools::AccessLevel;
+
+use super::super::{McpError, McpState};
+use super::text_result;
+
+fn date_argument(
+    arguments: &serde_json::Value,
+    name: &str,
+) -> Result<Option<ImperialDate>, McpError> {
+    arguments
+        .get(name)
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| {
+            ImperialDate::parse(v).ok_or_else(|| McpError {
+                code: -32602,
+                message: format!(
+                    "Invalid {} date '{}': use a year ('1105') or day-year ('187-1105')",
+                    name, v
+                ),
+            })
+        })
+        .transpose()
+}
+
+pub(super) fn execute_timeline_query(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let from = date_argument(arguments, "from")?;
+    let to = date_argument(arguments, "to")?;
+    let query = arguments
+        .get("query")
+        .and_then(|v| v.as_str())
+        .filter(|q| !q.is_empty());
+    let doc_id = arguments.get("document_id").and_then(|v| v.as_str());
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(50) as usize;
+
+    let events = state
+        .service
+        .db
+        .query_timeline(
+            from,
+            to,
+            query,
+            doc_id,
+            gm_role,
+            state.service.mcp_world_id().as_deref(),
+            limit,
+        )
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    if events.is_empty() {
+        return Ok(text_result(
+            "No timeline events match. Try a wider date range, or timeline_extract on the relevant documents."
+                .to_string(),
+        ));
+    }
+
+    let results: Vec<_> = events
+        .into_iter()
+        .map(|event| {
+            serde_json::json!({
+                "id": event.id,
+                "date": event.date,
+                "description": event.description,
+                "location": event.location,
+                "source": event.source,
+                "document_id": event.document_id,
+                "page_number": event.page_number
+            })
+        })
+        .collect();
+
+    Ok(text_result(
+        serde_json::to_string_pretty(&serde_json::json!({ "events": results })).unwrap_or_default(),
+    ))
+}
+
+pub(super) fn execute_timeline_add_event(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let date = date_argument(arguments, "date")?.ok_or_else(|| McpError {
+        code: -32602,
+        message: "Missing date".to_string(),
+    })?;
+    let description = arguments
+        .get("description")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let location = arguments
+        .get("location")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let access_level = arguments
+        .get("access_level")
+        .and_then(|v| serde_json::from_value::<AccessLevel>(v.clone()).ok())
+        .unwrap_or_default();
+
+    let event = state
+        .service
+        .add_timeline_event(date, description, location, access_level, None)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(text_result(format!(
+        "Added timeline event {} on {}",
+        event.id, event.date
+    )))
+}
+
+pub(super) fn execute_timeline_extract(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let doc_id = arguments
+        .get("document_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing document_id".to_string(),
+        })?;
+
+    let accessible = state
+        .service
+        .db
+        .get_document(doc_id)
+        .ok()
+        .flatten()
+        .is_some_and(|doc| doc.access_level.accessible_by(gm_role));
+    if !accessible {
+        return Err(McpError {
+            code: -32000,
+            message: format!("Document not found: {}", doc_id),
+        });
+    }
+
+    let count = state
+        .service
+        .extract_document_timeline(doc_id)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(text_result(format!(
+        "Extracted {} dated events from document {}",
+        count, doc_id
+    )))
+}
+
+pub(super) fn execute_clock_get(state: &McpState) -> Result<serde_json::Value, McpError> {
+    let date = state.service.campaign_date(None).map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?;
+
+    Ok(text_result(match date {
+        Some(date) => format!("The campaign date is {}", date),
+        None => {
+            "The campaign date has not been set; ask the GM for it and use clock_set.".to_string()
+        }
+    }))
+}
+
+pub(super) fn execute_clock_set(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let date = date_argument(arguments, "date")?.ok_or_else(|| McpError {
+        code: -32602,
+        message: "Missing date".to_string(),
+    })?;
+
+    let date = state
+        .service
+        .set_campaign_date(None, date)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(text_result(format!("The campaign date is now {}", date)))
+}
+
+pub(super) fn execute_clock_advance(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let count = |name: &str| arguments.get(name).and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+    let advance = ClockAdvance {
+        days: count("days"),
+        weeks: count("weeks"),
+        jumps: count("jumps"),
+        seed: arguments.get("seed").and_then(|v| v.as_u64()),
+        note: arguments
+            .get("note")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+    };
+
+    let result = state
+        .service
+        .advance_campaign_date(None, &advance)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    Ok(text_result(
+        serde_json::to_string_pretty(&result).unwrap_or_default(),
+    ))
+}
@@ -0,0 +1,111 @@
+//! Token cutout tool implementation.
+
+use crate::error::ServiceError;
+use crate::service::ImageDelivery;
+
+use super::super::{McpError, McpState};
+use super::image::image_in_mcp_world;
+
+pub(super) async fn execute_image_token(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let image_id = arguments
+        .get("image_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError {
+            code: -32602,
+            message: "Missing image_id".to_string(),
+        })?
+        .to_string();
+    let size = arguments
+        .get("size")
+        .and_then(|v| v.as_u64())
+        .map(|s| s as u32);
+    let ring_color = arguments
+        .get("ring_color")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let deliver = arguments
+        .get("deliver")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    match state.service.db.get_document_image(&image_id) {
+        Ok(Some(img)) if !image_in_mcp_world(state, &img) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Image not found".to_string(),
+            });
+        }
+        Ok(Some(img)) if img.access_level.accessible_by(gm_role) => {}
+        Ok(Some(_)) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Access denied".to_string(),
+            });
+        }
+        Ok(None) => {
+            return Err(McpError {
+                code: -32000,
+                message: "Image not found".to_string(),
+            });
+        }
+        Err(e) => {
+            return Err(McpError {
+                code: -32000,
+                message: e.to_string(),
+            });
+        }
+    }
+
+    // Decoding and resampling the art is CPU-bound
+    let service = state.service.clone();
+    let (token, delivery) = tokio::task::spawn_blocking(move || {
+        let token = service.create_token_image(&image_id, size, ring_color.as_deref())?;
+        let delivery = deliver
+            .then(|| service.deliver_token_image(&token))
+            .transpose()?;
+        Ok::<_, ServiceError>((token, delivery))
+    })
+    .await
+    .map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?
+    .map_err(|e| McpError {
+        code: -32000,
+        message: e.to_string(),
+    })?;
+
+    let mut result = serde_json::json!({
+        "token_image_id": token.image.id,
+        "source_image_id": token.image.source_image_id,
+        "size": token.image.width,
+    });
+    match delivery {
+        Some(ImageDelivery::Direct { fvtt_path }) => {
+            result["fvtt_path"] = serde_json::Value::from(fvtt_path.clone());
+            result["message"] = serde_json::Value::from(format!(
+                "Token delivered to FVTT assets at {}. Use it as a token texture.",
+                fvtt_path
+            ));
+        }
+        Some(ImageDelivery::Shuttle { suggested_path }) => {
+            result["suggested_path"] = serde_json::Value::from(suggested_path);
+            result["message"] = serde_json::Value::from(
+                "Direct delivery not available. Use the FVTT module to fetch and deliver the token image.",
+            );
+        }
+        None => {}
+    }
+
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
@@ -1,6 +1,10 @@
 //! Traveller RPG-related MCP tool implementations.
 
 use crate::tools::TravellerTool;
+use crate::tools::fvtt_actor::build_actor_payload;
+use crate::tools::names::{NameRequest, generate_names};
+use crate::tools::traveller_chargen::{self, ChargenRequest};
+use crate::tools::traveller_ship::{self, ShipSpec};
 
 use super::super::McpError;
 
@@ -93,6 +97,81 @@ pub(super) fn execute_traveller_skill_lookup(
     }
 }
 
+pub(super) fn execute_traveller_chargen(
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let request: ChargenRequest =
+        serde_json::from_value(arguments.clone()).map_err(|e| McpError {
+            code: -32602,
+            message: format!("Invalid chargen arguments: {}", e),
+        })?;
+    let state = traveller_chargen::generate(&request).map_err(|e| McpError {
+        code: -32000,
+        message: e,
+    })?;
+
+    let stat_block = state.stat_block();
+    let mut result = serde_json::json!({
+        "character": state,
+        "stat_block": stat_block,
+        "note": "Career-specific events, mishaps and life events are summarized; use document_search for the rulebook text when narrating."
+    });
+    // The actor is ready once the character has left every career
+    if state.next.iter().any(|n| n.starts_with("finish"))
+        && let Ok(payload) = build_actor_payload(&stat_block, Some("traveller"))
+    {
+        result["create_actor"] = payload.create_actor;
+        result["actor_warnings"] = serde_json::json!(payload.warnings);
+    }
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&result).unwrap_or_default()
+        }]
+    }))
+}
+
+pub(super) fn execute_traveller_ship_design(
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let spec: ShipSpec = serde_json::from_value(arguments.clone()).map_err(|e| McpError {
+        code: -32602,
+        message: format!("Invalid ship design arguments: {}", e),
+    })?;
+    let design = traveller_ship::design(&spec).map_err(|e| McpError {
+        code: -32000,
+        message: e,
+    })?;
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&design).unwrap_or_default()
+        }]
+    }))
+}
+
+pub(super) fn execute_name_generate(
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let request: NameRequest = serde_json::from_value(arguments.clone()).map_err(|e| McpError {
+        code: -32602,
+        message: format!("Invalid name_generate arguments: {}", e),
+    })?;
+    let names = generate_names(&request).map_err(|e| McpError {
+        code: -32000,
+        message: e,
+    })?;
+
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&names).unwrap_or_default()
+        }]
+    }))
+}
+
 pub(super) fn execute_system_schema(
     _arguments: &serde_json::Value,
 ) -> Result<serde_json::Value, McpError> {
@@ -0,0 +1,46 @@
+//! Traveller combat math MCP tool implementations.
+
+use serde::de::DeserializeOwned;
+
+use crate::tools::traveller_combat::{
+    AttackRequest, DamageRequest, OpposedRequest, resolve_attack, resolve_damage, resolve_opposed,
+};
+
+use super::super::McpError;
+use super::json_result;
+
+fn parse<T: DeserializeOwned>(arguments: &serde_json::Value) -> Result<T, McpError> {
+    serde_json::from_value(arguments.clone()).map_err(|e| McpError {
+        code: -32602,
+        message: format!("Invalid arguments: {}", e),
+    })
+}
+
+pub(super) fn execute_traveller_attack(
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let request: AttackRequest = parse(arguments)?;
+    let outcome = resolve_attack(&request).map_err(|e| McpError {
+        code: -32000,
+        message: e,
+    })?;
+    Ok(json_result(&outcome))
+}
+
+pub(super) fn execute_traveller_damage(
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let request: DamageRequest = parse(arguments)?;
+    let outcome = resolve_damage(&request).map_err(|e| McpError {
+        code: -32000,
+        message: e,
+    })?;
+    Ok(json_result(&outcome))
+}
+
+pub(super) fn execute_traveller_opposed_check(
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let request: OpposedRequest = parse(arguments)?;
+    Ok(json_result(&resolve_opposed(&request)))
+}
@@ -0,0 +1,83 @@
+//! Animal encounter table MCP tool implementation.
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_traveller_encounter_tables(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let string_arg = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let service_error = |e: crate::error::ServiceError| McpError {
+        code: -32000,
+        message: e.to_string(),
+    };
+
+    let mut world = string_arg("world").map(|s| s.to_string());
+    let uwp = match (string_arg("uwp"), string_arg("sector"), string_arg("hex")) {
+        (Some(uwp), _, _) => uwp.to_string(),
+        (None, Some(sector), Some(hex)) => {
+            let data = state
+                .service
+                .traveller_map_client
+                .world_data(sector, hex)
+                .await
+                .map_err(|e| McpError {
+                    code: -32000,
+                    message: e.to_string(),
+                })?;
+            if world.is_none() {
+                world = data.name;
+            }
+            data.uwp.ok_or_else(|| McpError {
+                code: -32000,
+                message: format!("No UWP listed for {} {}", sector, hex),
+            })?
+        }
+        _ => {
+            return Err(McpError {
+                code: -32602,
+                message: "Give the world's uwp, or a sector and hex to look it up".to_string(),
+            });
+        }
+    };
+    let world = world.ok_or_else(|| McpError {
+        code: -32602,
+        message: "Missing required parameter: world".to_string(),
+    })?;
+    let regenerate = arguments
+        .get("regenerate")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let seed = arguments.get("seed").and_then(|v| v.as_u64());
+
+    let (mut tables, cached) = state
+        .service
+        .encounter_tables(&world, &uwp, seed, regenerate)
+        .map_err(service_error)?;
+
+    if let Some(terrain) = string_arg("terrain") {
+        let wanted = terrain.to_lowercase().replace([' ', '-'], "_");
+        tables.tables.retain(|table| {
+            serde_json::to_value(table.terrain).is_ok_and(|name| name == wanted.as_str())
+        });
+        if tables.tables.is_empty() && tables.note.is_none() {
+            tables.note = Some(format!("{} has no {} terrain", tables.world, terrain));
+        }
+    }
+
+    let mut result = serde_json::to_value(&tables).unwrap_or_default();
+    result["cached"] = serde_json::json!(cached);
+    let text = serde_json::to_string_pretty(&result).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    }))
+}
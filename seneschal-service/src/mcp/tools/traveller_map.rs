@@ -2,7 +2,7 @@
 
 use crate::config::AssetsAccess;
 use crate::tools::TravellerMapTool;
-use crate::tools::traveller_map::{JumpMapOptions, PosterOptions};
+use crate::tools::traveller_map::{JumpMapOptions, PosterOptions, jump_map_scene};
 
 use super::super::{McpError, McpState};
 use super::sanitize_filename;
@@ -113,6 +113,66 @@ pub(super) async fn execute_traveller_map_route(
     }
 }
 
+pub(super) async fn execute_traveller_map_plan_route(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let start = arguments
+        .get("start")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let end = arguments.get("end").and_then(|v| v.as_str()).unwrap_or("");
+    let jump = arguments.get("jump").and_then(|v| v.as_u64()).unwrap_or(2) as u8;
+    let Some(tonnage) = arguments.get("tonnage").and_then(|v| v.as_u64()) else {
+        return Err(McpError {
+            code: -32602,
+            message: "Missing required parameter: tonnage".to_string(),
+        });
+    };
+    let thrust = arguments
+        .get("thrust")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1) as u8;
+    let fuel_capacity = arguments.get("fuel_capacity").and_then(|v| v.as_f64());
+    let wild = arguments
+        .get("wild")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let imperium_only = arguments
+        .get("imperium_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let no_red_zones = arguments
+        .get("no_red_zones")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let tool = TravellerMapTool::PlanRoute {
+        start: start.to_string(),
+        end: end.to_string(),
+        jump,
+        tonnage: tonnage as u32,
+        thrust,
+        fuel_capacity,
+        wild,
+        imperium_only,
+        no_red_zones,
+    };
+
+    match tool.execute(&state.service.traveller_map_client).await {
+        Ok(result) => Ok(serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": serde_json::to_string_pretty(&result).unwrap_or_default()
+            }]
+        })),
+        Err(e) => Err(McpError {
+            code: -32000,
+            message: e,
+        }),
+    }
+}
+
 pub(super) async fn execute_traveller_map_world_data(
     state: &McpState,
     arguments: &serde_json::Value,
@@ -424,6 +484,12 @@ pub(super) async fn execute_traveller_map_save_jump_map(
         .and_then(|v| v.as_u64())
         .map(|s| s as u32);
     let target_path = arguments.get("target_path").and_then(|v| v.as_str());
+    let create_scene = arguments
+        .get("create_scene")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let scene_name = arguments.get("scene_name").and_then(|v| v.as_str());
+    let scene_folder = arguments.get("scene_folder").and_then(|v| v.as_str());
 
     // Download the image
     let options = JumpMapOptions {
@@ -481,7 +547,7 @@ pub(super) async fn execute_traveller_map_save_jump_map(
                 });
             }
 
-            let result = serde_json::json!({
+            let mut result = serde_json::json!({
                 "success": true,
                 "mode": "direct",
                 "fvtt_path": fvtt_path,
@@ -489,6 +555,29 @@ pub(super) async fn execute_traveller_map_save_jump_map(
                 "message": format!("Jump map saved to {}", fvtt_path)
             });
 
+            // Scene data sized to the image with one hex per parsec
+            let name = scene_name
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("{} {} Jump-{}", sector, hex, jump));
+            match jump_map_scene(
+                &name,
+                &fvtt_path,
+                &bytes,
+                hex,
+                jump,
+                scale,
+                scene_folder.map(|s| s.to_string()),
+            ) {
+                Ok(scene) => {
+                    let scene = serde_json::to_value(scene).unwrap_or_default();
+                    if create_scene {
+                        result["scene_result"] = create_jump_map_scene(state, &scene).await;
+                    }
+                    result["scene"] = scene;
+                }
+                Err(e) => result["scene_error"] = serde_json::Value::from(e),
+            }
+
             let text = serde_json::to_string_pretty(&result).unwrap_or_default();
 
             Ok(serde_json::json!({
@@ -517,3 +606,22 @@ pub(super) async fn execute_traveller_map_save_jump_map(
         }
     }
 }
+
+/// Create an FVTT scene via the GM client, returning its result or an error object.
+async fn create_jump_map_scene(state: &McpState, scene: &serde_json::Value) -> serde_json::Value {
+    let timeout = state
+        .service
+        .runtime_config
+        .dynamic()
+        .agentic_loop
+        .external_tool_timeout();
+
+    match state
+        .service
+        .execute_external_tool_mcp("create_scene", scene.clone(), timeout)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => serde_json::json!({ "error": e }),
+    }
+}
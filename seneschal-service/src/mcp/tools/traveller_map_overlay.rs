@@ -0,0 +1,144 @@
+//! Traveller Map campaign overlay MCP tool implementations.
+
+use crate::config::AssetsAccess;
+use crate::tools::traveller_map::{MapOverlay, PosterOptions};
+
+use super::super::{McpError, McpState};
+use super::{json_result, sanitize_filename};
+
+/// Parse and validate the overlay described by a tool call's arguments
+fn parse_overlay(arguments: &serde_json::Value) -> Result<MapOverlay, McpError> {
+    let overlay: MapOverlay = serde_json::from_value(arguments.clone()).map_err(|e| McpError {
+        code: -32602,
+        message: format!("Invalid overlay: {}", e),
+    })?;
+    overlay.validate().map_err(|message| McpError {
+        code: -32602,
+        message,
+    })?;
+    Ok(overlay)
+}
+
+fn poster_options(arguments: &serde_json::Value) -> PosterOptions {
+    PosterOptions {
+        subsector: arguments
+            .get("subsector")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        style: arguments
+            .get("style")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        scale: arguments
+            .get("scale")
+            .and_then(|v| v.as_u64())
+            .map(|s| s as u32),
+        ..Default::default()
+    }
+}
+
+pub(super) fn execute_traveller_map_overlay(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let overlay = parse_overlay(arguments)?;
+    let options = poster_options(arguments);
+    let client = &state.service.traveller_map_client;
+
+    let result = serde_json::json!({
+        "map_url": client.overlay_map_url(&overlay, options.style.as_deref()),
+        "poster": {
+            "method": "POST",
+            "url": client.overlay_poster_url(&options),
+            "form": {
+                "data": format!("Sector data of {} (traveller_map_sector_data)", overlay.sector),
+                "metadata": overlay.metadata_xml(),
+            },
+        },
+        "message": "The map URL marks the party; routes and territories are drawn on posters rendered from the sector data with this metadata merged into the sector's own. Use traveller_map_save_overlay to render one into FVTT assets."
+    });
+    Ok(json_result(&result))
+}
+
+pub(super) async fn execute_traveller_map_save_overlay(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let overlay = parse_overlay(arguments)?;
+    let options = poster_options(arguments);
+    let target_path = arguments.get("target_path").and_then(|v| v.as_str());
+
+    let (bytes, extension) = state
+        .service
+        .traveller_map_client
+        .download_overlay_poster(&overlay, &options)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let filename = match &options.subsector {
+        Some(ss) => format!(
+            "traveller-map/{}-{}-overlay.{}",
+            sanitize_filename(&overlay.sector),
+            sanitize_filename(ss),
+            extension
+        ),
+        None => format!(
+            "traveller-map/{}-overlay.{}",
+            sanitize_filename(&overlay.sector),
+            extension
+        ),
+    };
+    let relative_path = target_path.map(|s| s.to_string()).unwrap_or(filename);
+
+    // The FVTT path is what FVTT uses to reference the file
+    let fvtt_path = format!("assets/{}", relative_path);
+
+    let result = match state
+        .service
+        .runtime_config
+        .static_config
+        .fvtt
+        .check_assets_access()
+    {
+        AssetsAccess::Direct(assets_dir) => {
+            let full_path = assets_dir.join(&relative_path);
+            if let Some(parent) = full_path.parent()
+                && let Err(e) = std::fs::create_dir_all(parent)
+            {
+                return Err(McpError {
+                    code: -32000,
+                    message: format!("Failed to create directory: {}", e),
+                });
+            }
+
+            if let Err(e) = std::fs::write(&full_path, &bytes) {
+                return Err(McpError {
+                    code: -32000,
+                    message: format!("Failed to write image: {}", e),
+                });
+            }
+
+            serde_json::json!({
+                "success": true,
+                "mode": "direct",
+                "fvtt_path": fvtt_path,
+                "size_bytes": bytes.len(),
+                "map_url": state
+                    .service
+                    .traveller_map_client
+                    .overlay_map_url(&overlay, options.style.as_deref()),
+                "message": format!("Overlay poster saved to {}", fvtt_path)
+            })
+        }
+        AssetsAccess::Shuttle => serde_json::json!({
+            "success": false,
+            "mode": "shuttle",
+            "suggested_path": fvtt_path,
+            "message": "Direct asset writing not available. FVTT assets directory not configured or not writable."
+        }),
+    };
+    Ok(json_result(&result))
+}
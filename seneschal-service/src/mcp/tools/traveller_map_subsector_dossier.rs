@@ -0,0 +1,70 @@
+//! Subsector dossier MCP tool implementation.
+
+use crate::service::{DEFAULT_HOOK_TAG, MAX_DOSSIER_MENTIONS};
+use crate::tools::traveller_map::parse_sector_worlds;
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_traveller_map_subsector_dossier(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+) -> Result<serde_json::Value, McpError> {
+    let required = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| McpError {
+                code: -32602,
+                message: format!("Missing required parameter: {}", name),
+            })
+    };
+    let sector = required("sector")?;
+    let subsector = required("subsector")?;
+    let max_mentions = arguments
+        .get("max_mentions")
+        .and_then(|v| v.as_u64())
+        .map(|m| (m as usize).min(MAX_DOSSIER_MENTIONS))
+        .unwrap_or(3);
+    let tag = arguments
+        .get("tag")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(DEFAULT_HOOK_TAG);
+
+    let data = state
+        .service
+        .traveller_map_client
+        .sector_data(sector, Some(subsector))
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+    let worlds = parse_sector_worlds(&data);
+
+    let dossier = state
+        .service
+        .subsector_dossier(sector, subsector, &worlds, max_mentions, tag, gm_role)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let json = serde_json::to_string_pretty(&dossier).unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [
+            {
+                "type": "text",
+                "text": dossier.render()
+            },
+            {
+                "type": "text",
+                "text": json
+            }
+        ]
+    }))
+}
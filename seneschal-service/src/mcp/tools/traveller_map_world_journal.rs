@@ -0,0 +1,141 @@
+//! World journal MCP tool implementation.
+
+use crate::service::{
+    DEFAULT_HOOK_TAG, ImageDelivery, MAX_DOSSIER_MENTIONS, MAX_JOURNAL_IMAGES, WorldJournalOptions,
+};
+
+use super::super::{McpError, McpState};
+
+pub(super) async fn execute_traveller_map_world_journal(
+    state: &McpState,
+    arguments: &serde_json::Value,
+    gm_role: u8,
+    session_key: &str,
+) -> Result<serde_json::Value, McpError> {
+    let text = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let flag = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true)
+    };
+    let required = |name: &str| {
+        text(name).ok_or_else(|| McpError {
+            code: -32602,
+            message: format!("Missing required parameter: {}", name),
+        })
+    };
+    let sector = required("sector")?;
+    let hex = required("hex")?;
+    let create = flag("create");
+    let options = WorldJournalOptions {
+        max_mentions: arguments
+            .get("max_mentions")
+            .and_then(|v| v.as_u64())
+            .map(|m| (m as usize).min(MAX_DOSSIER_MENTIONS))
+            .unwrap_or(3),
+        hook_tag: text("tag").unwrap_or(DEFAULT_HOOK_TAG).to_string(),
+        generate: flag("generate"),
+        images: arguments
+            .get("images")
+            .and_then(|v| v.as_u64())
+            .map(|n| (n as usize).min(MAX_JOURNAL_IMAGES))
+            .unwrap_or(2),
+        deliver_images: create,
+    };
+    let failed = |message: String| McpError {
+        code: -32000,
+        message,
+    };
+
+    let world = state
+        .service
+        .traveller_map_client
+        .world_data(sector, hex)
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut journal = state
+        .service
+        .world_journal(&world, &options, gm_role)
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+    if let Some(name) = text("name") {
+        journal.name = name.to_string();
+    }
+
+    if !create {
+        let json = serde_json::to_string_pretty(&journal).unwrap_or_default();
+        return Ok(serde_json::json!({
+            "content": [{ "type": "text", "text": json }]
+        }));
+    }
+
+    let timeout = state
+        .service
+        .runtime_config
+        .dynamic()
+        .agentic_loop
+        .external_tool_timeout();
+    let response = state
+        .service
+        .execute_external_tool_for_session(
+            "create_journal",
+            journal.create_journal_args(text("folder")),
+            timeout,
+            Some(session_key).filter(|s| !s.is_empty()),
+        )
+        .await
+        .map_err(failed)?;
+    if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+        return Err(failed(format!("Failed to create journal: {}", error)));
+    }
+
+    let mut summary = format!(
+        "Created journal '{}' with {} pages from {} library passages{}.",
+        journal.name,
+        journal.pages.len(),
+        journal.mentions,
+        if journal.generated {
+            ""
+        } else {
+            " (without generated prose)"
+        }
+    );
+    let uploads: Vec<(&str, &str)> = journal
+        .images
+        .iter()
+        .filter_map(|image| match &image.delivery {
+            Some(ImageDelivery::Shuttle { suggested_path }) => {
+                Some((image.image_id.as_str(), suggested_path.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+    if !uploads.is_empty() {
+        summary.push_str(
+            "\nDirect delivery not available. Use the FVTT module to fetch these images and upload them to the paths their pages use:",
+        );
+        for (id, path) in uploads {
+            summary.push_str(&format!("\n- {} -> {}", id, path));
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "journal_id": response.get("id"),
+        "journal": journal,
+    }))
+    .unwrap_or_default();
+    Ok(serde_json::json!({
+        "content": [
+            { "type": "text", "text": summary },
+            { "type": "text", "text": json }
+        ]
+    }))
+}
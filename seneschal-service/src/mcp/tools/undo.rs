@@ -0,0 +1,88 @@
+//! Undo log tool implementations.
+
+use super::super::{McpError, McpState};
+use super::text_result;
+use crate::db::FvttChange;
+
+/// A change as listed to the model, without the document snapshots
+fn describe_change(change: &FvttChange) -> serde_json::Value {
+    serde_json::json!({
+        "change_id": change.id,
+        "tool": change.tool,
+        "operation": change.operation,
+        "document_type": change.document_type,
+        "document_id": change.document_id,
+        "document_name": change.document_name,
+        "made_at": change.created_at.to_rfc3339(),
+        "undone_at": change.undone_at.map(|at| at.to_rfc3339()),
+    })
+}
+
+pub(super) fn execute_list_recent_changes(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let limit = arguments
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
+    let include_undone = arguments
+        .get("include_undone")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let changes = state
+        .service
+        .list_fvtt_changes(include_undone, limit)
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    if changes.is_empty() {
+        return Ok(text_result(
+            "No changes to FVTT world documents have been recorded.".to_string(),
+        ));
+    }
+
+    let changes: Vec<_> = changes.iter().map(describe_change).collect();
+    Ok(text_result(
+        serde_json::to_string_pretty(&serde_json::json!({ "changes": changes }))
+            .unwrap_or_default(),
+    ))
+}
+
+pub(super) async fn execute_undo_last_change(
+    state: &McpState,
+    arguments: &serde_json::Value,
+) -> Result<serde_json::Value, McpError> {
+    let change_id = arguments
+        .get("change_id")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty());
+
+    let change = state
+        .service
+        .undo_fvtt_change(change_id)
+        .await
+        .map_err(|e| McpError {
+            code: -32000,
+            message: e.to_string(),
+        })?;
+
+    let reverted = match change.operation.as_str() {
+        "create" => "Deleted the created",
+        "delete" => "Recreated the deleted",
+        _ => "Restored the earlier version of the",
+    };
+    Ok(text_result(format!(
+        "{} {} {} (change {}).",
+        reverted,
+        change.document_type.replace('_', " "),
+        change
+            .document_name
+            .as_deref()
+            .unwrap_or(&change.document_id),
+        change.id
+    )))
+}
@@ -1,15 +1,21 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::warn;
 
 use crate::config::OllamaConfig;
 use crate::error::{OllamaError, ServiceError, ServiceResult};
+use crate::usage::{ModelUsageTracker, TokenCounts};
+
+/// Upper bound on a model pull; multi-gigabyte downloads outlast the request timeout
+const MODEL_PULL_TIMEOUT_SECS: u64 = 6 * 60 * 60;
 
 /// Ollama API client
 pub struct OllamaClient {
     client: Client,
     config: OllamaConfig,
+    usage: Arc<ModelUsageTracker>,
 }
 
 impl OllamaClient {
@@ -25,7 +31,17 @@ impl OllamaClient {
                 })
             })?;
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            usage: Arc::new(ModelUsageTracker::default()),
+        })
+    }
+
+    /// Record usage in a tracker shared with other clients
+    pub fn with_usage_tracker(mut self, usage: Arc<ModelUsageTracker>) -> Self {
+        self.usage = usage;
+        self
     }
 
     /// Check if Ollama is available
@@ -43,35 +59,7 @@ impl OllamaClient {
 
     /// List available models
     pub async fn list_models(&self) -> ServiceResult<Vec<ModelInfo>> {
-        let url = format!("{}/api/tags", self.config.base_url);
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| OllamaError::Connection {
-                url: url.clone(),
-                source: e,
-            })?;
-
-        if !response.status().is_success() {
-            return Err(ServiceError::Ollama(OllamaError::Generation {
-                status: response.status().as_u16(),
-                message: "Failed to list models".to_string(),
-            }));
-        }
-
-        let tags: TagsResponse =
-            response
-                .json()
-                .await
-                .map_err(|e| OllamaError::InvalidResponse {
-                    source: serde_json::Error::io(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        e.to_string(),
-                    )),
-                })?;
+        let tags = self.tags().await?;
 
         let mut models = Vec::new();
 
@@ -123,12 +111,161 @@ impl OllamaClient {
         Ok(models)
     }
 
+    /// List models installed on the Ollama host with size and modification date
+    pub async fn list_local_models(&self) -> ServiceResult<Vec<LocalModel>> {
+        let tags = self.tags().await?;
+        Ok(tags
+            .models
+            .into_iter()
+            .map(|m| LocalModel {
+                name: m.name,
+                size: m.size,
+                modified_at: m.modified_at,
+                digest: m.digest,
+                parameter_size: m.details.parameter_size,
+                quantization: m.details.quantization_level,
+            })
+            .collect())
+    }
+
+    /// Pull a model from the Ollama library, reporting each progress update.
+    ///
+    /// Returns once the pull has finished; large models can take a long time.
+    pub async fn pull_model(
+        &self,
+        model: &str,
+        mut on_progress: impl FnMut(PullProgress),
+    ) -> ServiceResult<()> {
+        let url = format!("{}/api/pull", self.config.base_url);
+
+        let mut response = self
+            .client
+            .post(&url)
+            .timeout(Duration::from_secs(MODEL_PULL_TIMEOUT_SECS))
+            .json(&serde_json::json!({ "model": model, "stream": true }))
+            .send()
+            .await
+            .map_err(|e| OllamaError::Connection {
+                url: url.clone(),
+                source: e,
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ServiceError::Ollama(OllamaError::ModelManagement {
+                model: model.to_string(),
+                message: response.text().await.unwrap_or_default(),
+            }));
+        }
+
+        // The body is newline-delimited JSON, one progress update per line
+        let mut buffer = Vec::new();
+        loop {
+            let chunk = response
+                .chunk()
+                .await
+                .map_err(|e| OllamaError::Connection {
+                    url: url.clone(),
+                    source: e,
+                })?;
+            let done = chunk.is_none();
+            if let Some(chunk) = chunk {
+                buffer.extend_from_slice(&chunk);
+            }
+
+            while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                handle_pull_line(model, &line, &mut on_progress)?;
+            }
+            if done {
+                handle_pull_line(model, &buffer, &mut on_progress)?;
+                return Ok(());
+            }
+        }
+    }
+
+    /// Delete a model from the Ollama host
+    pub async fn delete_model(&self, model: &str) -> ServiceResult<()> {
+        let url = format!("{}/api/delete", self.config.base_url);
+
+        let response = self
+            .client
+            .delete(&url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(|e| OllamaError::Connection {
+                url: url.clone(),
+                source: e,
+            })?;
+
+        match response.status().as_u16() {
+            200..=299 => Ok(()),
+            404 => Err(ServiceError::Ollama(OllamaError::ModelNotFound {
+                model: model.to_string(),
+            })),
+            _ => Err(ServiceError::Ollama(OllamaError::ModelManagement {
+                model: model.to_string(),
+                message: response.text().await.unwrap_or_default(),
+            })),
+        }
+    }
+
+    async fn tags(&self) -> ServiceResult<TagsResponse> {
+        let url = format!("{}/api/tags", self.config.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| OllamaError::Connection {
+                url: url.clone(),
+                source: e,
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ServiceError::Ollama(OllamaError::Generation {
+                status: response.status().as_u16(),
+                message: "Failed to list models".to_string(),
+            }));
+        }
+
+        Ok(response
+            .json()
+            .await
+            .map_err(|e| OllamaError::InvalidResponse {
+                source: serde_json::Error::io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    e.to_string(),
+                )),
+            })?)
+    }
+
     /// Generate a non-streaming response (for simple tasks like image captioning)
+    ///
+    /// `feature` names what the request is for in usage accounting.
     pub async fn generate_simple(
         &self,
         model: &str,
+        feature: &str,
         messages: Vec<ChatMessage>,
     ) -> ServiceResult<String> {
+        let started = Instant::now();
+        let result = self.chat(model, messages).await;
+        let tokens = result
+            .as_ref()
+            .map(|(_, tokens)| *tokens)
+            .unwrap_or_default();
+        self.usage
+            .record(model, feature, started, result.is_ok(), tokens);
+        result.map(|(content, _)| content)
+    }
+
+    async fn chat(
+        &self,
+        model: &str,
+        messages: Vec<ChatMessage>,
+    ) -> ServiceResult<(String, TokenCounts)> {
         let url = format!("{}/api/chat", self.config.base_url);
 
         let request = OllamaChatRequest {
@@ -178,7 +315,45 @@ impl OllamaClient {
                     )),
                 })?;
 
-        Ok(chat_response.message.content)
+        let tokens = TokenCounts {
+            prompt: chat_response.prompt_eval_count,
+            completion: chat_response.eval_count,
+        };
+        Ok((chat_response.message.content, tokens))
+    }
+}
+
+/// Parse one line of a pull stream, failing if Ollama reported an error
+fn handle_pull_line(
+    model: &str,
+    line: &[u8],
+    on_progress: &mut impl FnMut(PullProgress),
+) -> ServiceResult<()> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(());
+    }
+
+    let progress: PullProgress =
+        serde_json::from_str(line).map_err(|e| OllamaError::InvalidResponse { source: e })?;
+    if let Some(error) = progress.error {
+        return Err(ServiceError::Ollama(OllamaError::ModelManagement {
+            model: model.to_string(),
+            message: error,
+        }));
+    }
+    on_progress(progress);
+    Ok(())
+}
+
+/// Extract the JSON object from a model response.
+///
+/// Models asked for "only JSON" still sometimes wrap it in prose or code fences.
+pub fn extract_json_object(response: &str) -> &str {
+    match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if end > start => &response[start..=end],
+        _ => response,
     }
 }
 
@@ -193,6 +368,15 @@ pub struct ChatMessage {
 }
 
 impl ChatMessage {
+    /// Create a plain text user message
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.into(),
+            images: None,
+        }
+    }
+
     /// Create a user message with an image for vision models
     pub fn user_with_image(content: impl Into<String>, image_base64: String) -> Self {
         Self {
@@ -212,6 +396,31 @@ pub struct ModelInfo {
     pub quantization: Option<String>,
 }
 
+/// A model installed on the Ollama host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModel {
+    pub name: String,
+    /// Size on disk in bytes
+    pub size: u64,
+    pub modified_at: String,
+    pub digest: String,
+    pub parameter_size: Option<String>,
+    pub quantization: Option<String>,
+}
+
+/// One progress update from a model pull
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullProgress {
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub completed: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
 // Internal Ollama API types
 
 #[derive(Debug, Serialize)]
@@ -232,6 +441,10 @@ struct OllamaOptions {
 #[derive(Debug, Deserialize)]
 struct OllamaChatResponse {
     message: OllamaMessage,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -248,6 +461,14 @@ struct TagsResponse {
 #[derive(Debug, Deserialize)]
 struct TagModel {
     name: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    modified_at: String,
+    #[serde(default)]
+    digest: String,
+    #[serde(default)]
+    details: ModelDetails,
 }
 
 #[derive(Debug, Deserialize)]
@@ -265,3 +486,39 @@ struct ModelDetails {
     #[serde(default)]
     quantization_level: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_pull_line() {
+        let mut updates = Vec::new();
+        let mut record = |p: PullProgress| updates.push((p.status, p.completed, p.total));
+
+        handle_pull_line("m", b"{\"status\":\"pulling manifest\"}\n", &mut record).unwrap();
+        handle_pull_line(
+            "m",
+            br#"{"status":"pulling abc","digest":"sha256:abc","total":100,"completed":40}"#,
+            &mut record,
+        )
+        .unwrap();
+        handle_pull_line("m", b"  \n", &mut record).unwrap();
+
+        let err = handle_pull_line(
+            "m",
+            br#"{"error":"pull model manifest: file does not exist"}"#,
+            &mut record,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("file does not exist"));
+
+        assert_eq!(
+            updates,
+            vec![
+                ("pulling manifest".to_string(), None, None),
+                ("pulling abc".to_string(), Some(40), Some(100)),
+            ]
+        );
+    }
+}
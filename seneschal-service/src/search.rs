@@ -1,22 +1,33 @@
+mod confidence;
+mod expansion;
+mod highlight;
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 use crate::config::EmbeddingsConfig;
 use crate::db::{Chunk, Database};
 use crate::error::{EmbeddingError, OllamaError, ProcessingError, ServiceError, ServiceResult};
 use crate::i18n::I18n;
+use crate::ingestion::preprocessing::TextPreprocessing;
 use crate::tools::{SearchFilters, TagMatch};
+use crate::usage::{EMBEDDING_FEATURE, ModelUsageTracker, TokenCounts};
 use tokio_util::sync::CancellationToken;
 
+pub use confidence::RetrievalConfidence;
+pub use highlight::{DEFAULT_SNIPPET_CHARS, Snippet};
+
 /// Search service for RAG functionality using Ollama embeddings
 pub struct SearchService {
     db: Arc<Database>,
     client: Client,
     ollama_url: String,
     embedding_model: String,
+    preprocessing: TextPreprocessing,
+    usage: Arc<ModelUsageTracker>,
 }
 
 impl SearchService {
@@ -25,6 +36,7 @@ impl SearchService {
         db: Arc<Database>,
         config: &EmbeddingsConfig,
         ollama_base_url: &str,
+        usage: Arc<ModelUsageTracker>,
     ) -> ServiceResult<Self> {
         info!(model = %config.model, "Initializing embedding service using Ollama");
 
@@ -42,6 +54,8 @@ impl SearchService {
             client,
             ollama_url: ollama_base_url.to_string(),
             embedding_model: config.model.clone(),
+            preprocessing: TextPreprocessing::from(config),
+            usage,
         };
 
         // Try a test embedding to verify the model is available
@@ -56,7 +70,24 @@ impl SearchService {
     }
 
     /// Generate embedding for text using Ollama
+    ///
+    /// The text is normalized as configured first, so queries and chunks are
+    /// embedded alike.
     pub async fn embed_text(&self, text: &str) -> ServiceResult<Vec<f32>> {
+        let text = self.preprocessing.embedding_text(text);
+        let started = Instant::now();
+        let result = self.request_embedding(&text).await;
+        self.usage.record(
+            &self.embedding_model,
+            EMBEDDING_FEATURE,
+            started,
+            result.is_ok(),
+            TokenCounts::default(),
+        );
+        result
+    }
+
+    async fn request_embedding(&self, text: &str) -> ServiceResult<Vec<f32>> {
         let url = format!("{}/api/embeddings", self.ollama_url);
 
         let request = OllamaEmbeddingRequest {
@@ -111,6 +142,7 @@ impl SearchService {
         user_role: u8,
         limit: usize,
         filters: Option<SearchFilters>,
+        priority_boost: f32,
     ) -> ServiceResult<Vec<SearchResult>> {
         debug!(query = %query, user_role = user_role, limit = limit, "Searching documents");
 
@@ -118,9 +150,9 @@ impl SearchService {
         let query_embedding = self.embed_text(query).await?;
 
         // Extract filter parameters
-        let (tags, tag_match_all) = filters
-            .map(|f| (Some(f.tags), f.tags_match == TagMatch::All))
-            .unwrap_or((None, false));
+        let (tags, tag_match_all, world_id) = filters
+            .map(|f| (Some(f.tags), f.tags_match == TagMatch::All, f.world_id))
+            .unwrap_or((None, false, None));
 
         // Search database
         let results = self.db.search_chunks(
@@ -129,13 +161,19 @@ impl SearchService {
             limit,
             tags.as_deref(),
             tag_match_all,
+            world_id.as_deref(),
+            priority_boost,
         )?;
 
         debug!(results = results.len(), "Search completed");
 
         Ok(results
             .into_iter()
-            .map(|(chunk, similarity)| SearchResult { chunk, similarity })
+            .map(|(chunk, similarity)| SearchResult {
+                chunk,
+                similarity,
+                errata: None,
+            })
             .collect())
     }
 
@@ -159,10 +197,15 @@ impl SearchService {
         let total = chunks.len();
         info!(total = total, "Starting embedding generation");
 
-        // Generate embeddings for all chunks
+        // Generate embeddings for all chunks, reusing embeddings of identical chunks
+        let mut reused = 0;
         for (i, chunk) in chunks.iter().enumerate() {
-            let embedding = self.embed_text(&chunk.content).await?;
-            self.db.insert_embedding(&chunk.id, &embedding)?;
+            if self.db.copy_duplicate_embedding(&chunk.id)? {
+                reused += 1;
+            } else {
+                let embedding = self.embed_text(&chunk.content).await?;
+                self.db.insert_embedding(&chunk.id, &embedding)?;
+            }
 
             let progress = i + 1;
 
@@ -180,7 +223,11 @@ impl SearchService {
             }
         }
 
-        info!(chunks = total, "Embedding generation complete");
+        info!(
+            chunks = total,
+            reused = reused,
+            "Embedding generation complete"
+        );
 
         Ok(())
     }
@@ -203,7 +250,8 @@ impl SearchService {
         let total = chunks.len();
         info!(total = total, "Starting embedding generation (cancellable)");
 
-        // Generate embeddings for all chunks
+        // Generate embeddings for all chunks, reusing embeddings of identical chunks
+        let mut reused = 0;
         for (i, chunk) in chunks.iter().enumerate() {
             // Check for cancellation before each embedding
             if cancel_token.is_cancelled() {
@@ -217,8 +265,12 @@ impl SearchService {
                 }));
             }
 
-            let embedding = self.embed_text(&chunk.content).await?;
-            self.db.insert_embedding(&chunk.id, &embedding)?;
+            if self.db.copy_duplicate_embedding(&chunk.id)? {
+                reused += 1;
+            } else {
+                let embedding = self.embed_text(&chunk.content).await?;
+                self.db.insert_embedding(&chunk.id, &embedding)?;
+            }
 
             let progress = i + 1;
 
@@ -236,7 +288,11 @@ impl SearchService {
             }
         }
 
-        info!(chunks = total, "Embedding generation complete");
+        info!(
+            chunks = total,
+            reused = reused,
+            "Embedding generation complete"
+        );
 
         Ok(())
     }
@@ -260,11 +316,95 @@ struct OllamaEmbeddingResponse {
 pub struct SearchResult {
     pub chunk: Chunk,
     pub similarity: f32,
+    /// How the chunk relates to published errata, if at all
+    pub errata: Option<ErrataStatus>,
+}
+
+/// A search result's relation to published errata
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ErrataStatus {
+    /// The chunk's text has been corrected by errata
+    Superseded {
+        errata_document_id: String,
+        errata_title: String,
+        errata_start_page: Option<i32>,
+        errata_end_page: Option<i32>,
+        issued: Option<String>,
+        note: Option<String>,
+    },
+    /// The chunk is itself a correction to another document
+    Correction {
+        target_document_id: String,
+        target_title: String,
+        issued: Option<String>,
+    },
+}
+
+impl ErrataStatus {
+    /// A line telling the model which text to trust
+    fn disclaimer(&self) -> String {
+        match self {
+            Self::Superseded {
+                errata_document_id,
+                errata_title,
+                errata_start_page,
+                errata_end_page,
+                issued,
+                note,
+            } => {
+                let pages = match (errata_start_page, errata_end_page) {
+                    (Some(start), Some(end)) if start != end => {
+                        format!(", pages {}-{}", start, end)
+                    }
+                    (Some(page), _) | (None, Some(page)) => format!(", page {}", page),
+                    (None, None) => String::new(),
+                };
+                let mut line = format!(
+                    "Errata: this text is superseded by \"{}\"{} (document {}{}). \
+                    Prefer the errata wording; read it with document_get if it isn't among these results.",
+                    errata_title,
+                    issued_suffix(issued),
+                    errata_document_id,
+                    pages
+                );
+                if let Some(note) = note {
+                    line.push_str(&format!(" Note: {}", note));
+                }
+                line
+            }
+            Self::Correction {
+                target_title,
+                issued,
+                ..
+            } => format!(
+                "Errata: this text corrects \"{}\"{} and takes precedence over it.",
+                target_title,
+                issued_suffix(issued)
+            ),
+        }
+    }
+}
+
+fn issued_suffix(issued: &Option<String>) -> String {
+    issued
+        .as_ref()
+        .map(|date| format!(", issued {}", date))
+        .unwrap_or_default()
 }
 
 impl SearchResult {
     /// Format for LLM context
     pub fn format_for_context(&self) -> String {
+        self.format_with_content(&self.chunk.content)
+    }
+
+    /// Format for LLM context, showing only a snippet of the chunk
+    pub fn format_snippet_for_context(&self, snippet: &Snippet) -> String {
+        self.format_with_content(&snippet.marked(self.chunk.content.len()))
+    }
+
+    fn format_with_content(&self, content: &str) -> String {
         let mut parts = Vec::new();
 
         if let Some(ref title) = self.chunk.section_title {
@@ -275,8 +415,12 @@ impl SearchResult {
             parts.push(format!("Page: {}", page));
         }
 
+        parts.push(format!("Chunk ID: {}", self.chunk.id));
         parts.push(format!("Relevance: {:.2}", self.similarity));
-        parts.push(format!("Content:\n{}", self.chunk.content));
+        if let Some(errata) = &self.errata {
+            parts.push(errata.disclaimer());
+        }
+        parts.push(format!("Content:\n{}", content));
 
         parts.join("\n")
     }
@@ -287,6 +431,30 @@ pub fn format_search_results_for_llm(
     results: &[SearchResult],
     i18n: &I18n,
     locale: &str,
+) -> String {
+    format_results(results, i18n, locale, SearchResult::format_for_context)
+}
+
+/// Format search results for LLM consumption as snippets, one per result,
+/// with query terms marked
+pub fn format_search_snippets_for_llm(
+    results: &[SearchResult],
+    snippets: &[Snippet],
+    i18n: &I18n,
+    locale: &str,
+) -> String {
+    let mut snippets = snippets.iter();
+    format_results(results, i18n, locale, |result| match snippets.next() {
+        Some(snippet) => result.format_snippet_for_context(snippet),
+        None => result.format_for_context(),
+    })
+}
+
+fn format_results(
+    results: &[SearchResult],
+    i18n: &I18n,
+    locale: &str,
+    mut format: impl FnMut(&SearchResult) -> String,
 ) -> String {
     if results.is_empty() {
         return i18n.get(locale, "search-no-results", None);
@@ -301,7 +469,7 @@ pub fn format_search_results_for_llm(
 
     for (i, result) in results.iter().enumerate() {
         output.push_str(&format!("--- Result {} ---\n", i + 1));
-        output.push_str(&result.format_for_context());
+        output.push_str(&format(result));
         output.push_str("\n\n");
     }
 
@@ -0,0 +1,80 @@
+//! Confidence that search results can ground an answer.
+//!
+//! A retrieval's confidence is its best score scaled by how much of the
+//! query the top results cover, so a chunk that sounds close but never
+//! mentions what was asked about still counts as weak.
+
+use serde::Serialize;
+
+use super::SearchResult;
+use super::highlight::query_terms;
+
+/// Results whose text counts toward coverage
+const COVERAGE_RESULTS: usize = 5;
+
+/// How well a search's results answer its query
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RetrievalConfidence {
+    /// Score of the best result
+    pub top_score: f32,
+    /// Share of the query's terms found in the top results
+    pub coverage: f32,
+    /// Top score scaled by coverage, from 0 to 1
+    pub confidence: f32,
+}
+
+impl RetrievalConfidence {
+    /// Measure the results of a search for `query`
+    pub fn measure(query: &str, results: &[SearchResult]) -> Self {
+        let top_score = results.iter().map(|r| r.similarity).fold(0.0, f32::max);
+        let contents: Vec<&str> = results
+            .iter()
+            .take(COVERAGE_RESULTS)
+            .map(|r| r.chunk.content.as_str())
+            .collect();
+        Self::from_parts(top_score, coverage(query, &contents))
+    }
+
+    fn from_parts(top_score: f32, coverage: f32) -> Self {
+        Self {
+            top_score,
+            coverage,
+            confidence: (top_score * (0.5 + 0.5 * coverage)).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Share of the query's terms that appear in any of the contents; all of
+/// them when the query has none worth matching
+fn coverage(query: &str, contents: &[&str]) -> f32 {
+    let terms = query_terms(query);
+    if terms.is_empty() {
+        return if contents.is_empty() { 0.0 } else { 1.0 };
+    }
+    let contents: Vec<String> = contents.iter().map(|c| c.to_lowercase()).collect();
+    let found = terms
+        .iter()
+        .filter(|term| contents.iter().any(|c| c.contains(term.as_str())))
+        .count();
+    found as f32 / terms.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_scales_confidence() {
+        let contents = ["Jump drives need fuel equal to 10% of hull per parsec."];
+        assert_eq!(coverage("jump fuel per parsec", &contents), 1.0);
+        assert_eq!(coverage("jump psionics vargr", &contents), 1.0 / 3.0);
+        assert_eq!(coverage("how is the", &contents), 1.0);
+        assert_eq!(coverage("jump", &[]), 0.0);
+
+        let full = RetrievalConfidence::from_parts(0.8, 1.0);
+        let half = RetrievalConfidence::from_parts(0.8, 0.5);
+        assert!((full.confidence - 0.8).abs() < 1e-6);
+        assert!((half.confidence - 0.6).abs() < 1e-6);
+        assert_eq!(RetrievalConfidence::measure("jump", &[]).confidence, 0.0);
+    }
+}
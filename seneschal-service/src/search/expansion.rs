@@ -0,0 +1,258 @@
+//! Query expansion and spelling tolerance for rules lookups.
+//!
+//! Questions abbreviate book titles and game terms ("THB", "UWP") and
+//! misspell the rest. With expansion on, a query is also searched with its
+//! abbreviations spelled out and with words a letter or two away from a
+//! glossary term corrected to that term, and the retrievals are merged
+//! before ranking. The original query is always searched, so a bad rewrite
+//! only adds candidates.
+
+use std::collections::{HashMap, HashSet};
+
+use tracing::debug;
+
+use super::{SearchResult, SearchService};
+use crate::error::ServiceResult;
+use crate::tools::SearchFilters;
+
+/// Abbreviations common in rules questions, matched case-insensitively
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("thb", "Traveller's Handbook"),
+    ("crb", "Core Rulebook"),
+    ("csc", "Central Supply Catalogue"),
+    ("jtas", "Journal of the Travellers' Aid Society"),
+    ("tas", "Travellers' Aid Society"),
+    ("mgt", "Mongoose Traveller"),
+    ("uwp", "Universal World Profile"),
+    ("tl", "Tech Level"),
+    ("dm", "dice modifier"),
+    ("dton", "displacement ton"),
+    ("dtons", "displacement tons"),
+    ("j-drive", "jump drive"),
+    ("m-drive", "manoeuvre drive"),
+    ("edu", "Education"),
+    ("soc", "Social Standing"),
+    ("dex", "Dexterity"),
+    ("phb", "Player's Handbook"),
+    ("dmg", "Dungeon Master's Guide"),
+    ("srd", "System Reference Document"),
+];
+
+/// Shortest word spelling correction is tried on
+const MIN_CORRECTED_LEN: usize = 5;
+
+/// Edits allowed between a word and the term it's corrected to
+fn max_edits(len: usize) -> usize {
+    if len >= 8 { 2 } else { 1 }
+}
+
+/// Rewrite each word of the query `rewrite` has a replacement for, keeping
+/// surrounding punctuation. `None` if no word changed.
+fn rewrite_words(query: &str, rewrite: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut changed = false;
+    let words: Vec<String> = query
+        .split_whitespace()
+        .map(|token| {
+            let core = token.trim_matches(|c: char| !c.is_alphanumeric());
+            let Some(replacement) = (!core.is_empty()).then(|| rewrite(core)).flatten() else {
+                return token.to_string();
+            };
+            changed = true;
+            let start = token.find(core).unwrap_or(0);
+            format!(
+                "{}{}{}",
+                &token[..start],
+                replacement,
+                &token[start + core.len()..]
+            )
+        })
+        .collect();
+    changed.then(|| words.join(" "))
+}
+
+/// The query with known abbreviations spelled out
+fn expand_abbreviations(query: &str) -> Option<String> {
+    rewrite_words(query, |word| {
+        ABBREVIATIONS
+            .iter()
+            .find(|(abbreviation, _)| abbreviation.eq_ignore_ascii_case(word))
+            .map(|(_, expansion)| expansion.to_string())
+    })
+}
+
+/// Lowercase words of glossary terms, for spelling correction
+fn vocabulary(terms: &[String]) -> HashSet<String> {
+    terms
+        .iter()
+        .flat_map(|term| term.split(|c: char| !c.is_alphabetic()))
+        .filter(|word| word.chars().count() >= MIN_CORRECTED_LEN - 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Optimal string alignment distance between two words
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// The closest vocabulary word within the allowed edits, sharing the
+/// word's first letter
+fn closest_term(word: &str, vocabulary: &HashSet<String>) -> Option<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let allowed = max_edits(chars.len());
+    vocabulary
+        .iter()
+        .filter(|term| term.chars().next() == chars.first().copied())
+        .filter(|term| term.chars().count().abs_diff(chars.len()) <= allowed)
+        .filter_map(|term| {
+            let distance = edit_distance(&chars, &term.chars().collect::<Vec<_>>());
+            (distance <= allowed).then_some((distance, term))
+        })
+        .min()
+        .map(|(_, term)| term.clone())
+}
+
+/// The query with near-miss spellings of vocabulary words corrected
+fn correct_spelling(query: &str, vocabulary: &HashSet<String>) -> Option<String> {
+    rewrite_words(query, |word| {
+        let lower = word.to_lowercase();
+        if lower.chars().count() < MIN_CORRECTED_LEN
+            || !lower.chars().all(char::is_alphabetic)
+            || vocabulary.contains(&lower)
+        {
+            return None;
+        }
+        closest_term(&lower, vocabulary)
+    })
+}
+
+/// Queries to search: the original, then with abbreviations expanded, then
+/// with spelling corrected as well
+fn query_variants(query: &str, vocabulary: &HashSet<String>) -> Vec<String> {
+    let mut variants = vec![query.to_string()];
+    let expanded = expand_abbreviations(query);
+    let base = expanded.as_deref().unwrap_or(query);
+    let corrected = correct_spelling(base, vocabulary);
+    for variant in [expanded.clone(), corrected].into_iter().flatten() {
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+    variants
+}
+
+/// Merge retrievals, keeping each chunk's best score, best first
+fn merge_results(lists: Vec<Vec<SearchResult>>, limit: usize) -> Vec<SearchResult> {
+    let mut best: HashMap<String, SearchResult> = HashMap::new();
+    for result in lists.into_iter().flatten() {
+        match best.get(&result.chunk.id) {
+            Some(existing) if existing.similarity >= result.similarity => {}
+            _ => {
+                best.insert(result.chunk.id.clone(), result);
+            }
+        }
+    }
+    let mut merged: Vec<SearchResult> = best.into_values().collect();
+    merged.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    merged.truncate(limit);
+    merged
+}
+
+impl SearchService {
+    /// Search with the query and its expansions, merging the results
+    pub async fn search_expanded(
+        &self,
+        query: &str,
+        user_role: u8,
+        limit: usize,
+        filters: Option<SearchFilters>,
+        priority_boost: f32,
+    ) -> ServiceResult<Vec<SearchResult>> {
+        let world_id = filters.as_ref().and_then(|f| f.world_id.as_deref());
+        let terms = self.db.glossary_terms(user_role, world_id)?;
+        let variants = query_variants(query, &vocabulary(&terms));
+        if variants.len() > 1 {
+            debug!(variants = ?variants, "Expanded search query");
+        }
+
+        let mut lists = Vec::with_capacity(variants.len());
+        for variant in &variants {
+            lists.push(
+                self.search(variant, user_role, limit, filters.clone(), priority_boost)
+                    .await?,
+            );
+        }
+        Ok(merge_results(lists, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glossary() -> HashSet<String> {
+        vocabulary(&[
+            "Jump Drive".to_string(),
+            "Manoeuvre Drive".to_string(),
+            "Vilani".to_string(),
+            "Zhodani Consulate".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_expand_abbreviations() {
+        assert_eq!(
+            expand_abbreviations("Where in the THB are (UWP) codes?").as_deref(),
+            Some("Where in the Traveller's Handbook are (Universal World Profile) codes?")
+        );
+        assert_eq!(expand_abbreviations("thbs and throws"), None);
+    }
+
+    #[test]
+    fn test_correct_spelling() {
+        let vocabulary = glossary();
+        assert_eq!(
+            correct_spelling("Zhodanni psionics", &vocabulary).as_deref(),
+            Some("zhodani psionics")
+        );
+        assert_eq!(
+            correct_spelling("Maneuver drive", &vocabulary).as_deref(),
+            Some("manoeuvre drive")
+        );
+        assert_eq!(correct_spelling("Vilani jump", &vocabulary), None);
+        assert_eq!(correct_spelling("Vilnius", &vocabulary), None);
+    }
+
+    #[test]
+    fn test_query_variants() {
+        let vocabulary = glossary();
+        assert_eq!(
+            query_variants("TL of the Vilanni", &vocabulary),
+            [
+                "TL of the Vilanni",
+                "Tech Level of the Vilanni",
+                "Tech Level of the vilani",
+            ]
+        );
+        assert_eq!(query_variants("jump drive", &vocabulary), ["jump drive"]);
+    }
+}
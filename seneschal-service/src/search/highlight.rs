@@ -0,0 +1,360 @@
+//! Snippet windows and match highlighting for search results.
+//!
+//! A result's snippet is a window of the chunk centered on the sentence
+//! that best matches the query, with the query's terms marked by byte
+//! offset. The sentence is the one with the most query terms; when no term
+//! appears (a purely semantic match), it can instead be the sentence whose
+//! embedding is closest to the query's.
+
+use serde::Serialize;
+use tracing::warn;
+
+use super::{SearchResult, SearchService};
+use crate::db::cosine_similarity;
+
+/// Default snippet length, in bytes
+pub const DEFAULT_SNIPPET_CHARS: usize = 300;
+
+/// Sentences per result embedded for semantic highlighting
+const MAX_SEMANTIC_SENTENCES: usize = 12;
+
+/// Words too common to highlight
+const STOPWORDS: &[&str] = &[
+    "about", "and", "are", "can", "does", "for", "from", "has", "have", "how", "into", "its",
+    "not", "that", "the", "their", "them", "then", "there", "they", "this", "was", "were", "what",
+    "when", "where", "which", "who", "why", "will", "with", "you", "your",
+];
+
+/// How a snippet's sentence was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetBasis {
+    /// Most query terms
+    Terms,
+    /// Closest embedding to the query
+    Semantic,
+    /// Nothing matched; the chunk's opening
+    Lead,
+}
+
+/// A window of a search result's text
+#[derive(Debug, Clone, Serialize)]
+pub struct Snippet {
+    pub text: String,
+    /// Byte offset of the window in the chunk's content
+    pub offset: usize,
+    /// Byte ranges of query terms in `text`
+    pub matches: Vec<(usize, usize)>,
+    /// Byte range of the best-matching sentence in `text`
+    pub sentence: (usize, usize),
+    pub basis: SnippetBasis,
+}
+
+impl Snippet {
+    /// The snippet with its matches wrapped in `**`, elided where it was cut
+    /// from the chunk
+    pub fn marked(&self, content_len: usize) -> String {
+        let mut out = String::new();
+        if self.offset > 0 {
+            out.push('…');
+        }
+        let mut last = 0;
+        for &(start, end) in &self.matches {
+            out.push_str(&self.text[last..start]);
+            out.push_str("**");
+            out.push_str(&self.text[start..end]);
+            out.push_str("**");
+            last = end;
+        }
+        out.push_str(&self.text[last..]);
+        if self.offset + self.text.len() < content_len {
+            out.push('…');
+        }
+        out
+    }
+}
+
+/// The query's words worth highlighting, lowercased
+pub(super) fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for (start, end) in word_spans(query) {
+        let term = query[start..end].to_lowercase();
+        let keep = term.chars().any(|c| c.is_ascii_digit())
+            || (term.chars().count() >= 3 && !STOPWORDS.contains(&term.as_str()));
+        if keep && !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Byte ranges of the alphanumeric runs in the text
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+/// Byte ranges of words matching a term, allowing short suffixes so
+/// "drive" matches "drives"
+pub fn term_matches(text: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    word_spans(text)
+        .into_iter()
+        .filter(|&(start, end)| {
+            let word = text[start..end].to_lowercase();
+            terms
+                .iter()
+                .any(|term| word.starts_with(term.as_str()) && word.len() <= term.len() + 3)
+        })
+        .collect()
+}
+
+/// Byte ranges of the text's sentences, trimmed
+fn sentence_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut push = |start: usize, end: usize| {
+        let sentence = &text[start..end];
+        let trimmed = sentence.trim_start();
+        let s = start + (sentence.len() - trimmed.len());
+        let e = s + trimmed.trim_end().len();
+        if e > s {
+            spans.push((s, e));
+        }
+    };
+    for (i, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            let end = i + c.len_utf8();
+            push(start, end);
+            start = end;
+        }
+    }
+    push(start, text.len());
+    spans
+}
+
+/// A window of at most `max_chars` bytes around a sentence, cut at spaces
+fn window(text: &str, sentence: (usize, usize), max_chars: usize) -> (usize, usize) {
+    let (s, e) = sentence;
+    if e - s >= max_chars {
+        let end = text.floor_char_boundary(s + max_chars);
+        let end = text[s..end]
+            .rfind(char::is_whitespace)
+            .map_or(end, |i| s + i);
+        return (s, end);
+    }
+
+    let spare = max_chars - (e - s);
+    let mut start = s.saturating_sub(spare / 2);
+    let mut end = (e + spare - (s - start)).min(text.len());
+    start = start.saturating_sub(max_chars - (end - start));
+    start = text.ceil_char_boundary(start);
+    end = text.floor_char_boundary(end);
+
+    if start > 0
+        && let Some(i) = text[start..s].find(char::is_whitespace)
+    {
+        start += i;
+    }
+    if end < text.len()
+        && let Some(i) = text[e..end].rfind(char::is_whitespace)
+    {
+        end = e + i;
+    }
+    let trimmed = text[start..end].trim_start();
+    (end - trimmed.len(), end)
+}
+
+/// The sentence with the most term matches, if any matched
+fn best_sentence(sentences: &[(usize, usize)], matches: &[(usize, usize)]) -> Option<usize> {
+    let count = |&(s, e): &(usize, usize)| {
+        matches
+            .iter()
+            .filter(|&&(ms, me)| ms >= s && me <= e)
+            .count()
+    };
+    let best = sentences
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, sentence)| count(sentence))?;
+    (count(best.1) > 0).then_some(best.0)
+}
+
+/// Build a snippet around a sentence
+fn build_snippet(
+    content: &str,
+    sentence: (usize, usize),
+    matches: &[(usize, usize)],
+    max_chars: usize,
+    basis: SnippetBasis,
+) -> Snippet {
+    let (start, end) = window(content, sentence, max_chars);
+    Snippet {
+        text: content[start..end].to_string(),
+        offset: start,
+        matches: matches
+            .iter()
+            .filter(|&&(ms, me)| ms >= start && me <= end)
+            .map(|&(ms, me)| (ms - start, me - start))
+            .collect(),
+        sentence: (
+            sentence.0.clamp(start, end) - start,
+            sentence.1.clamp(start, end) - start,
+        ),
+        basis,
+    }
+}
+
+/// A snippet centered on the sentence with the most query terms, or on the
+/// opening sentence if none appear
+pub fn term_snippet(content: &str, terms: &[String], max_chars: usize) -> Snippet {
+    let matches = term_matches(content, terms);
+    let sentences = sentence_spans(content);
+    match best_sentence(&sentences, &matches) {
+        Some(i) => build_snippet(
+            content,
+            sentences[i],
+            &matches,
+            max_chars,
+            SnippetBasis::Terms,
+        ),
+        None => {
+            let lead = sentences.first().copied().unwrap_or((0, 0));
+            build_snippet(content, lead, &matches, max_chars, SnippetBasis::Lead)
+        }
+    }
+}
+
+impl SearchService {
+    /// Snippets for search results, in order. With `semantic`, results no
+    /// query term appears in are centered on the sentence closest to the
+    /// query by embedding, at the cost of embedding their sentences.
+    pub async fn snippets(
+        &self,
+        query: &str,
+        results: &[SearchResult],
+        max_chars: usize,
+        semantic: bool,
+    ) -> Vec<Snippet> {
+        let terms = query_terms(query);
+        let mut query_embedding = None;
+        let mut snippets = Vec::with_capacity(results.len());
+
+        for result in results {
+            let content = &result.chunk.content;
+            let snippet = term_snippet(content, &terms, max_chars);
+            if !semantic || snippet.basis != SnippetBasis::Lead {
+                snippets.push(snippet);
+                continue;
+            }
+
+            if query_embedding.is_none() {
+                match self.embed_text(query).await {
+                    Ok(embedding) => query_embedding = Some(embedding),
+                    Err(e) => {
+                        warn!(error = %e, "Failed to embed query for semantic highlighting");
+                        snippets.push(snippet);
+                        continue;
+                    }
+                }
+            }
+            let target = query_embedding.as_deref().unwrap_or_default();
+
+            let mut best: Option<((usize, usize), f32)> = None;
+            for sentence in sentence_spans(content)
+                .into_iter()
+                .take(MAX_SEMANTIC_SENTENCES)
+            {
+                match self.embed_text(&content[sentence.0..sentence.1]).await {
+                    Ok(embedding) => {
+                        let similarity = cosine_similarity(target, &embedding);
+                        if best.is_none_or(|(_, s)| similarity > s) {
+                            best = Some((sentence, similarity));
+                        }
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "Failed to embed sentence for semantic highlighting");
+                        break;
+                    }
+                }
+            }
+
+            snippets.push(match best {
+                Some((sentence, _)) => {
+                    build_snippet(content, sentence, &[], max_chars, SnippetBasis::Semantic)
+                }
+                None => snippet,
+            });
+        }
+
+        snippets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_terms() {
+        assert_eq!(
+            query_terms("How do Jump drives work with J-2?"),
+            vec!["jump", "drives", "work", "2"]
+        );
+    }
+
+    #[test]
+    fn test_term_snippet_centers_on_best_sentence() {
+        let content = "Starships carry cargo. A jump drive needs fuel. \
+            Each jump consumes 10% of hull tonnage per parsec for jump drives. Crew need rest.";
+        let terms = query_terms("jump drive fuel");
+        let snippet = term_snippet(content, &terms, 80);
+
+        assert_eq!(snippet.basis, SnippetBasis::Terms);
+        assert!(snippet.text.len() <= 80);
+        let (s, e) = snippet.sentence;
+        assert_eq!(&snippet.text[s..e], "A jump drive needs fuel.");
+        for &(ms, me) in &snippet.matches {
+            let word = snippet.text[ms..me].to_lowercase();
+            assert!(word.starts_with("jump") || word.starts_with("drive") || word == "fuel");
+        }
+        assert_eq!(
+            &content[snippet.offset..snippet.offset + snippet.text.len()],
+            snippet.text
+        );
+    }
+
+    #[test]
+    fn test_term_snippet_falls_back_to_lead() {
+        let content = "The Imperium spans eleven thousand worlds. Its capital is Capital.";
+        let snippet = term_snippet(content, &query_terms("zhodani psionics"), 300);
+        assert_eq!(snippet.basis, SnippetBasis::Lead);
+        assert_eq!(snippet.text, content);
+        assert!(snippet.matches.is_empty());
+        assert_eq!(snippet.marked(content.len()), content);
+    }
+
+    #[test]
+    fn test_marked() {
+        let content = "Pirates raid the jump point. Patrols are rare.";
+        let snippet = term_snippet(content, &query_terms("pirates"), 30);
+        assert_eq!(
+            snippet.marked(content.len()),
+            "**Pirates** raid the jump point.…"
+        );
+    }
+}
@@ -4,34 +4,126 @@
 //! all service functionality. The implementation is split across submodules
 //! for better organization:
 //!
+//! - `character_context`: Condensed sheets for connected players' characters
+//! - `diagnostics`: End-to-end self-test of Ollama, ingestion and integrations
+//! - `disk_space`: Free space monitoring, and holding back uploads and image extraction
 //! - `document_processing`: Document upload, chunking, embedding, captioning
+//! - `encounter_tables`: Animal encounter tables generated from a world's UWP and cached per campaign
+//! - `errata`: Errata links, and flagging and ranking of corrected search results
 //! - `external_tools`: MCP external tool execution via WebSocket
+//! - `image_operations`: Image delivery to FVTT and batch image operations
+//! - `image_similarity`: Image search by example image
+//! - `ingestion_digest`: Scheduled digest of newly ingested documents
+//! - `journal_import`: Foundry VTT journal entry sync
+//! - `memories`: Canonical campaign facts recalled by similarity
+//! - `model_management`: Ollama model listing, background pulls, and deletion
+//! - `model_routing`: Per-task model lists with health-aware failover
+//! - `maintenance`: Integrity checks, orphan cleanup and vacuuming
+//! - `notes`: Chat answers saved as indexed note documents
+//! - `related_documents`: Related documents by centroid similarity, links and tags
+//! - `retention`: Per-class TTLs for source files, uncaptioned images, caches and audit logs
+//! - `schedule`: Cron-style schedules for background tasks
+//! - `session_summary`: Session recaps from transcripts and the FVTT chat log
+//! - `speech`: Spoken answers and read-aloud text from a TTS server
+//! - `subsector_dossier`: Subsector world summaries cross-referenced with the library
+//! - `system_profiles`: The game system each FVTT world runs
+//! - `tasks`: Prep TODOs and reminders brought up in later conversations
+//! - `timeline`: Dated campaign events extracted from documents or added by the GM
+//! - `token_images`: Circular token cutouts derived from character art
+//! - `world_journal`: Multi-page FVTT journals for Traveller worlds
 
+mod character_context;
+mod chunk_inspector;
+mod clock;
+mod diagnostics;
+mod disk_space;
 mod document_processing;
+mod encounter_tables;
+mod errata;
+mod evaluation;
 mod external_tools;
+mod fvtt_changes;
+mod handouts;
+mod image_operations;
+mod image_similarity;
+mod ingestion_digest;
+mod journal_import;
+mod library_data;
+mod maintenance;
+mod map_scenes;
+mod memories;
+mod model_management;
+mod model_routing;
+mod notes;
+mod npcs;
+mod plot_hooks;
+mod related_documents;
+mod retention;
+mod schedule;
+mod session_summary;
+mod shared_answers;
+mod similar_chunks;
+mod speech;
+mod subsector_dossier;
+mod system_profiles;
+mod tasks;
+mod timeline;
+mod token_images;
+mod tool_approval;
+mod world_journal;
 
-use std::sync::Arc;
+pub use chunk_inspector::ChunkPage;
+pub use clock::ClockAdvance;
+pub use diagnostics::DiagnosticsReport;
+pub use disk_space::{DiskSpaceLevel, DiskUsage};
+pub use document_processing::CaptionPreset;
+pub use handouts::{HandoutFormat, HandoutRequest, HandoutTemplate};
+pub use image_operations::{ImageBatchReport, ImageDelivery};
+pub use library_data::{DEFAULT_MIN_CONFIDENCE, no_data_readout};
+pub use maintenance::MaintenanceReport;
+pub use model_routing::{ModelRoute, ModelTask};
+pub use npcs::{NpcLink, NpcUpdate};
+pub use plot_hooks::{DEFAULT_HOOK_TAG, MAX_PLOT_HOOKS};
+pub use related_documents::RelatedDocument;
+pub use retention::RetentionReport;
+pub use session_summary::SessionSummaryOptions;
+pub use speech::SpeechSource;
+pub use subsector_dossier::MAX_DOSSIER_MENTIONS;
+pub use tool_approval::{ApprovalDecision, PendingApprovals};
+pub use world_journal::{MAX_JOURNAL_IMAGES, WorldJournalOptions};
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use dashmap::DashMap;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
-use crate::config::RuntimeConfig;
+use crate::auto_import::AutoImportRun;
+use crate::call_trace::CallTraceStore;
+use crate::config::{RuntimeConfig, TravellerMapConfig};
+use crate::conversation_mode::ConversationModes;
 use crate::db::Database;
 use crate::error::ServiceResult;
 use crate::i18n::I18n;
 use crate::ingestion::IngestionService;
 use crate::ollama::OllamaClient;
 use crate::search::{SearchResult, SearchService};
+use crate::tool_budget::ToolBudgets;
+use crate::tools::client_tools::ClientToolRegistry;
+use crate::tools::traveller_map::CacheSettings;
 use crate::tools::{SearchFilters, TravellerMapClient, TravellerWorldsClient};
-use crate::websocket::WebSocketManager;
+use crate::usage::ModelUsageTracker;
+use crate::websocket::{ModelPullUpdate, WebSocketManager};
 
 /// Main service coordinator
 pub struct SeneschalService {
     pub runtime_config: Arc<RuntimeConfig>,
     pub db: Arc<Database>,
     pub ollama: Arc<OllamaClient>,
+    /// Client for vision models (a separate host if `captioning.vision_base_url` is set)
+    pub vision_ollama: Arc<OllamaClient>,
     pub search: Arc<SearchService>,
     pub ingestion: Arc<IngestionService>,
     pub i18n: Arc<I18n>,
@@ -42,9 +134,41 @@ pub struct SeneschalService {
     pub traveller_worlds_client: TravellerWorldsClient,
     /// Senders for MCP tool results, keyed by request_id ("mcp:{uuid}")
     pub(crate) mcp_tool_result_senders: Arc<DashMap<String, oneshot::Sender<serde_json::Value>>>,
+    /// Tool approval requests waiting for a GM's answer
+    pub(crate) pending_approvals: Arc<PendingApprovals>,
     /// Cancellation tokens for documents currently being processed.
     /// Key: document_id, Value: CancellationToken
     pub(crate) processing_cancellation_tokens: Arc<DashMap<String, CancellationToken>>,
+    /// Condensed character sheets, keyed by actor_id
+    pub(crate) character_summary_cache:
+        Arc<DashMap<String, character_context::CachedCharacterSummary>>,
+    /// Documents currently being captioned, keyed by vision model
+    pub(crate) active_captioning: Arc<DashMap<String, String>>,
+    /// Time of the last MCP tool call, used to pause captioning during interactive use
+    pub(crate) last_interactive_activity: Arc<Mutex<Option<Instant>>>,
+    /// Requests per Ollama model (chat, vision and embeddings) since startup
+    pub model_usage: Arc<ModelUsageTracker>,
+    /// Recent failures per model, for failing over between a task's models
+    pub(crate) model_health: Arc<model_routing::ModelHealth>,
+    /// Stage timings of recent MCP tool calls, by correlation ID
+    pub call_traces: Arc<CallTraceStore>,
+    /// Retrieval modes of MCP conversations
+    pub conversation_modes: Arc<ConversationModes>,
+    /// Tool-use budget overrides of MCP conversations
+    pub tool_budgets: Arc<ToolBudgets>,
+    /// External tools registered by FVTT clients, by world
+    pub client_tools: Arc<ClientToolRegistry>,
+    /// Most recent file handled by the auto-import worker
+    pub(crate) last_auto_import: Arc<Mutex<Option<AutoImportRun>>>,
+    /// Most recent database maintenance run
+    pub(crate) last_maintenance: Arc<Mutex<Option<MaintenanceReport>>>,
+    /// Most recent retention run
+    pub(crate) last_retention: Arc<Mutex<Option<RetentionReport>>>,
+    /// Latest progress of each model pull, keyed by model name
+    pub(crate) model_pulls: Arc<DashMap<String, ModelPullUpdate>>,
+    /// Passwords of encrypted PDFs, keyed by document_id. Only held in
+    /// memory; the document just records that it was encrypted.
+    pub(crate) pdf_passwords: Arc<DashMap<String, String>>,
 }
 
 impl SeneschalService {
@@ -57,7 +181,10 @@ impl SeneschalService {
         let dynamic = runtime_config.dynamic();
 
         // Initialize Ollama client
-        let ollama = Arc::new(OllamaClient::new(dynamic.ollama.clone())?);
+        let model_usage = Arc::new(ModelUsageTracker::with_log(db.clone()));
+        let ollama = Arc::new(
+            OllamaClient::new(dynamic.ollama.clone())?.with_usage_tracker(model_usage.clone()),
+        );
 
         // Check Ollama availability
         if ollama.health_check().await? {
@@ -66,9 +193,25 @@ impl SeneschalService {
             warn!(url = %dynamic.ollama.base_url, "Ollama is not available");
         }
 
+        // Vision models may live on a dedicated host so captioning doesn't compete with other requests
+        let vision_ollama = if dynamic.captioning.vision_base_url.is_empty() {
+            ollama.clone()
+        } else {
+            let mut vision_config = dynamic.ollama.clone();
+            vision_config.base_url = dynamic.captioning.vision_base_url.clone();
+            info!(url = %vision_config.base_url, "Using dedicated Ollama host for vision models");
+            Arc::new(OllamaClient::new(vision_config)?.with_usage_tracker(model_usage.clone()))
+        };
+
         // Initialize search service
         let search = Arc::new(
-            SearchService::new(db.clone(), &dynamic.embeddings, &dynamic.ollama.base_url).await?,
+            SearchService::new(
+                db.clone(),
+                &dynamic.embeddings,
+                &dynamic.ollama.base_url,
+                model_usage.clone(),
+            )
+            .await?,
         );
 
         // Initialize ingestion service
@@ -88,9 +231,18 @@ impl SeneschalService {
         let traveller_map_client = TravellerMapClient::new(
             &dynamic.traveller_map.base_url,
             dynamic.traveller_map.timeout_secs,
+        )
+        .with_cache(
+            runtime_config
+                .static_config
+                .storage
+                .data_dir
+                .join("traveller_map_cache"),
+            traveller_map_cache_settings(&dynamic.traveller_map),
         );
         info!(
             url = %dynamic.traveller_map.base_url,
+            offline = dynamic.traveller_map.offline,
             "Traveller Map API client initialized"
         );
 
@@ -109,6 +261,7 @@ impl SeneschalService {
             runtime_config,
             db,
             ollama,
+            vision_ollama,
             search,
             ingestion,
             i18n,
@@ -116,7 +269,22 @@ impl SeneschalService {
             traveller_map_client,
             traveller_worlds_client,
             mcp_tool_result_senders: Arc::new(DashMap::new()),
+            pending_approvals: Arc::new(PendingApprovals::default()),
             processing_cancellation_tokens: Arc::new(DashMap::new()),
+            character_summary_cache: Arc::new(DashMap::new()),
+            active_captioning: Arc::new(DashMap::new()),
+            last_interactive_activity: Arc::new(Mutex::new(None)),
+            model_usage,
+            model_health: Arc::new(model_routing::ModelHealth::default()),
+            call_traces: Arc::new(CallTraceStore::default()),
+            conversation_modes: Arc::new(ConversationModes::default()),
+            tool_budgets: Arc::new(ToolBudgets::default()),
+            client_tools: Arc::new(ClientToolRegistry::default()),
+            last_auto_import: Arc::new(Mutex::new(None)),
+            last_maintenance: Arc::new(Mutex::new(None)),
+            last_retention: Arc::new(Mutex::new(None)),
+            model_pulls: Arc::new(DashMap::new()),
+            pdf_passwords: Arc::new(DashMap::new()),
         })
     }
 
@@ -131,6 +299,38 @@ impl SeneschalService {
         // Reload config from DB
         self.runtime_config.reload_from_db(&self.db)?;
 
+        // Cache settings apply immediately so offline mode can be toggled mid-session
+        self.traveller_map_client
+            .update_cache_settings(traveller_map_cache_settings(
+                &self.runtime_config.dynamic().traveller_map,
+            ));
+
+        Ok(())
+    }
+
+    /// Update a world's settings overrides; the MCP world's apply immediately
+    pub async fn update_world_settings(
+        &self,
+        world_id: &str,
+        updates: std::collections::HashMap<String, serde_json::Value>,
+    ) -> ServiceResult<()> {
+        self.db.set_world_settings(world_id, updates)?;
+        self.runtime_config.reload_from_db(&self.db)?;
+        Ok(())
+    }
+
+    /// The FVTT world MCP clients work in, if configured
+    pub fn mcp_world_id(&self) -> Option<String> {
+        let world_id = self.runtime_config.dynamic().mcp.world_id.clone();
+        (!world_id.is_empty()).then_some(world_id)
+    }
+
+    /// Place a document created for the campaign (a note, journal or recap)
+    /// in the MCP world, so it isn't shared with other worlds
+    pub(crate) fn assign_mcp_world(&self, document_id: &str) -> ServiceResult<()> {
+        if let Some(world_id) = self.mcp_world_id() {
+            self.db.set_document_world(document_id, Some(&world_id))?;
+        }
         Ok(())
     }
 
@@ -142,6 +342,23 @@ impl SeneschalService {
         limit: usize,
         filters: Option<SearchFilters>,
     ) -> ServiceResult<Vec<SearchResult>> {
-        self.search.search(query, user_role, limit, filters).await
+        let embeddings = self.runtime_config.dynamic().embeddings.clone();
+        let results = if embeddings.query_expansion {
+            self.search
+                .search_expanded(query, user_role, limit, filters, embeddings.priority_boost)
+                .await?
+        } else {
+            self.search
+                .search(query, user_role, limit, filters, embeddings.priority_boost)
+                .await?
+        };
+        self.apply_errata(results)
+    }
+}
+
+fn traveller_map_cache_settings(config: &TravellerMapConfig) -> CacheSettings {
+    CacheSettings {
+        ttl: std::time::Duration::from_secs(config.cache_ttl_secs),
+        offline: config.offline,
     }
 }
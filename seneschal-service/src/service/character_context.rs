@@ -0,0 +1,209 @@
+//! Character context for connected players.
+//!
+//! Players' FVTT clients report which actors they own when they authenticate.
+//! Sheet data is fetched through the external `fvtt_read` tool on a GM
+//! connection, condensed to the fields that matter for rules questions, and
+//! cached briefly so repeated questions don't round-trip to FVTT each time.
+
+use std::time::{Duration, Instant};
+
+use serde_json::{Map, Value};
+use tracing::debug;
+
+use crate::service::SeneschalService;
+
+/// How long a condensed character sheet stays cached
+const CHARACTER_SUMMARY_TTL: Duration = Duration::from_secs(300);
+
+/// Strings longer than this (biographies, HTML notes) are dropped from summaries
+const MAX_SUMMARY_STRING_LEN: usize = 120;
+
+/// Nesting depth of `system` data kept in summaries
+const MAX_SUMMARY_DEPTH: usize = 3;
+
+/// A condensed character sheet with the time it was fetched
+pub struct CachedCharacterSummary {
+    pub summary: Value,
+    pub fetched_at: Instant,
+}
+
+impl SeneschalService {
+    /// Get condensed sheet summaries for every connected player's characters.
+    ///
+    /// Each player's assigned character is listed first, followed by other owned actors.
+    pub async fn party_character_summaries(&self, include_owned: bool) -> Vec<Value> {
+        let timeout = self
+            .runtime_config
+            .dynamic()
+            .agentic_loop
+            .external_tool_timeout();
+
+        let mut players = Vec::new();
+        for player in self.ws_manager.connected_players() {
+            let mut actor_ids: Vec<String> = player.character_id.iter().cloned().collect();
+            if include_owned {
+                for id in &player.owned_actor_ids {
+                    if !actor_ids.contains(id) {
+                        actor_ids.push(id.clone());
+                    }
+                }
+            }
+
+            let mut characters = Vec::new();
+            for actor_id in actor_ids {
+                match self.character_summary(&actor_id, timeout).await {
+                    Ok(summary) => characters.push(summary),
+                    Err(e) => characters.push(serde_json::json!({
+                        "id": actor_id,
+                        "error": e,
+                    })),
+                }
+            }
+
+            players.push(serde_json::json!({
+                "user_id": player.user_id,
+                "user_name": player.user_name,
+                "character_id": player.character_id,
+                "characters": characters,
+            }));
+        }
+
+        players
+    }
+
+    /// Get a condensed summary of an actor's sheet, using the cache when fresh.
+    async fn character_summary(&self, actor_id: &str, timeout: Duration) -> Result<Value, String> {
+        if let Some(cached) = self.character_summary_cache.get(actor_id)
+            && cached.fetched_at.elapsed() < CHARACTER_SUMMARY_TTL
+        {
+            return Ok(cached.summary.clone());
+        }
+
+        debug!(actor_id = %actor_id, "Fetching character sheet for summary");
+        let actor = self
+            .execute_external_tool_mcp(
+                "fvtt_read",
+                serde_json::json!({ "document_type": "actor", "document_id": actor_id }),
+                timeout,
+            )
+            .await?;
+
+        if actor.is_null() {
+            return Err("Actor not found".to_string());
+        }
+        if let Some(error) = actor.get("error").and_then(|e| e.as_str()) {
+            return Err(error.to_string());
+        }
+
+        let mut summary = condense_actor(&actor);
+        summary["id"] = Value::String(actor_id.to_string());
+        // Actors of players who left would otherwise stay cached forever
+        self.character_summary_cache
+            .retain(|_, cached| cached.fetched_at.elapsed() < CHARACTER_SUMMARY_TTL);
+        self.character_summary_cache.insert(
+            actor_id.to_string(),
+            CachedCharacterSummary {
+                summary: summary.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+
+        Ok(summary)
+    }
+}
+
+/// Reduce an actor's `toObject()` data to name, type, short system fields, and item names.
+fn condense_actor(actor: &Value) -> Value {
+    let items: Vec<String> = actor
+        .get("items")
+        .and_then(|i| i.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let name = item.get("name")?.as_str()?;
+                    Some(match item.get("type").and_then(|t| t.as_str()) {
+                        Some(kind) => format!("{} ({})", name, kind),
+                        None => name.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    serde_json::json!({
+        "name": actor.get("name").cloned().unwrap_or(Value::Null),
+        "type": actor.get("type").cloned().unwrap_or(Value::Null),
+        "system": actor
+            .get("system")
+            .map(|s| condense_value(s, 0))
+            .unwrap_or(Value::Null),
+        "items": items,
+    })
+}
+
+/// Recursively drop long strings, empty containers, and deeply nested data.
+fn condense_value(value: &Value, depth: usize) -> Value {
+    match value {
+        Value::String(s) if s.len() > MAX_SUMMARY_STRING_LEN => Value::Null,
+        Value::Object(map) if depth < MAX_SUMMARY_DEPTH => {
+            let condensed: Map<String, Value> = map
+                .iter()
+                .map(|(k, v)| (k.clone(), condense_value(v, depth + 1)))
+                .filter(|(_, v)| !is_empty_value(v))
+                .collect();
+            Value::Object(condensed)
+        }
+        Value::Array(items) if depth < MAX_SUMMARY_DEPTH => Value::Array(
+            items
+                .iter()
+                .map(|v| condense_value(v, depth + 1))
+                .filter(|v| !is_empty_value(v))
+                .collect(),
+        ),
+        Value::Object(_) | Value::Array(_) => Value::Null,
+        other => other.clone(),
+    }
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condense_actor() {
+        let actor = serde_json::json!({
+            "name": "Vargas",
+            "type": "traveller",
+            "system": {
+                "characteristics": { "str": { "value": 7, "dm": 0 } },
+                "biography": "x".repeat(500),
+                "notes": "",
+            },
+            "items": [
+                { "name": "Pilot", "type": "skill" },
+                { "name": "Autopistol" },
+            ],
+        });
+
+        let summary = condense_actor(&actor);
+        assert_eq!(summary["name"], "Vargas");
+        assert_eq!(summary["system"]["characteristics"]["str"]["value"], 7);
+        assert!(summary["system"].get("biography").is_none());
+        assert!(summary["system"].get("notes").is_none());
+        assert_eq!(
+            summary["items"],
+            serde_json::json!(["Pilot (skill)", "Autopistol"])
+        );
+    }
+}
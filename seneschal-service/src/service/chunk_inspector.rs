@@ -0,0 +1,145 @@
+//! Chunk inspection for debugging retrieval.
+//!
+//! Pages through a document's chunks as they were indexed, with each
+//! chunk's nearest neighbors by embedding and where a query matches it in
+//! the full-text index, to show why a search preferred one book's text over
+//! another's.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+
+/// Most chunks in one page
+const MAX_INSPECT_CHUNKS: usize = 50;
+
+/// Most neighbors per chunk
+const MAX_INSPECT_NEIGHBORS: usize = 20;
+
+/// A chunk close to an inspected chunk in embedding space
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkNeighbor {
+    pub chunk_id: String,
+    pub document_id: String,
+    pub document_title: String,
+    pub page_number: Option<i32>,
+    pub similarity: f32,
+}
+
+/// A chunk as indexed
+#[derive(Debug, Clone, Serialize)]
+pub struct InspectedChunk {
+    pub chunk_id: String,
+    pub chunk_index: i32,
+    pub page_number: Option<i32>,
+    pub section_title: Option<String>,
+    pub access_level: AccessLevel,
+    pub content: String,
+    pub embedded: bool,
+    /// Byte ranges in `content` matching the query in the full-text index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fts_matches: Option<Vec<(usize, usize)>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub neighbors: Vec<ChunkNeighbor>,
+}
+
+/// A page of a document's chunks
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkPage {
+    pub document_id: String,
+    pub total: usize,
+    pub offset: usize,
+    pub chunks: Vec<InspectedChunk>,
+}
+
+impl SeneschalService {
+    /// Page through a document's chunks, with FTS matches for `query` and
+    /// up to `neighbors` nearest chunks from the whole library for each
+    pub fn inspect_document_chunks(
+        &self,
+        document_id: &str,
+        offset: usize,
+        limit: usize,
+        query: Option<&str>,
+        neighbors: usize,
+    ) -> ServiceResult<ChunkPage> {
+        if self.db.get_document(document_id)?.is_none() {
+            return Err(ServiceError::DocumentNotFound {
+                document_id: document_id.to_string(),
+            });
+        }
+        let limit = limit.clamp(1, MAX_INSPECT_CHUNKS);
+        let neighbors = neighbors.min(MAX_INSPECT_NEIGHBORS);
+
+        let total = self.db.get_chunk_count(document_id)?;
+        let rows = self
+            .db
+            .get_document_chunk_page(document_id, offset, limit)?;
+        let mut matches = match query.map(str::trim).filter(|q| !q.is_empty()) {
+            Some(query) => Some(self.db.highlight_document_chunks(document_id, query)?),
+            None => None,
+        };
+
+        let mut titles: HashMap<String, String> = HashMap::new();
+        let mut chunks = Vec::with_capacity(rows.len());
+        for (chunk, embedded) in rows {
+            let mut nearest = Vec::new();
+            if neighbors > 0
+                && embedded
+                && let Some(embedding) = self.db.get_chunk_embedding(&chunk.id)?
+            {
+                let similar = self.db.search_chunks(
+                    &embedding,
+                    AccessLevel::GmOnly as u8,
+                    neighbors + 1,
+                    None,
+                    false,
+                    None,
+                    0.0,
+                )?;
+                for (other, similarity) in similar.into_iter().filter(|(c, _)| c.id != chunk.id) {
+                    if !titles.contains_key(&other.document_id) {
+                        let title = self
+                            .db
+                            .get_document(&other.document_id)?
+                            .map(|doc| doc.title)
+                            .unwrap_or_else(|| other.document_id.clone());
+                        titles.insert(other.document_id.clone(), title);
+                    }
+                    nearest.push(ChunkNeighbor {
+                        document_title: titles[&other.document_id].clone(),
+                        chunk_id: other.id,
+                        document_id: other.document_id,
+                        page_number: other.page_number,
+                        similarity,
+                    });
+                }
+                nearest.truncate(neighbors);
+            }
+
+            chunks.push(InspectedChunk {
+                fts_matches: matches
+                    .as_mut()
+                    .map(|m| m.remove(&chunk.id).unwrap_or_default()),
+                chunk_id: chunk.id,
+                chunk_index: chunk.chunk_index,
+                page_number: chunk.page_number,
+                section_title: chunk.section_title,
+                access_level: chunk.access_level,
+                content: chunk.content,
+                embedded,
+                neighbors: nearest,
+            });
+        }
+
+        Ok(ChunkPage {
+            document_id: document_id.to_string(),
+            total,
+            offset,
+            chunks,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+//! Campaign clock.
+//!
+//! Each world keeps its current Imperial date so a long campaign stays
+//! chronologically consistent. The LLM advances it as travel and downtime
+//! pass, session recaps are stamped with it, and the FVTT module shows it.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use tracing::info;
+
+use crate::db::TimelineEvent;
+use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::timeline::ImperialDate;
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+
+/// A jump takes this many hours plus 6D
+const JUMP_BASE_HOURS: u32 = 148;
+
+/// Time to add to the clock
+#[derive(Debug, Clone, Default)]
+pub struct ClockAdvance {
+    pub days: u32,
+    pub weeks: u32,
+    /// Jumps, each rolled as 148 + 6D hours
+    pub jumps: u32,
+    /// Seed for the jump duration rolls
+    pub seed: Option<u64>,
+    /// Recorded on the timeline at the new date
+    pub note: Option<String>,
+}
+
+/// How the clock moved
+#[derive(Debug, Clone, Serialize)]
+pub struct ClockAdvanceResult {
+    pub from: ImperialDate,
+    pub to: ImperialDate,
+    pub days: i64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub jump_hours: Vec<u32>,
+    pub seed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<TimelineEvent>,
+}
+
+/// Roll the duration of each jump in hours
+pub fn roll_jump_hours(seed: u64, jumps: u32) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..jumps)
+        .map(|_| JUMP_BASE_HOURS + (0..6).map(|_| rng.gen_range(1..=6u32)).sum::<u32>())
+        .collect()
+}
+
+impl SeneschalService {
+    /// The clock's world: the one given, else the MCP world
+    fn clock_world(&self, world_id: Option<&str>) -> String {
+        world_id
+            .map(str::to_string)
+            .or_else(|| self.mcp_world_id())
+            .unwrap_or_default()
+    }
+
+    /// A world's current in-game date, if its clock has been set. A world
+    /// without its own clock reads the one kept when no MCP world is
+    /// configured.
+    pub fn campaign_date(&self, world_id: Option<&str>) -> ServiceResult<Option<ImperialDate>> {
+        let world = self.clock_world(world_id);
+        match self.db.get_campaign_date(&world)? {
+            None if !world.is_empty() => self.db.get_campaign_date(""),
+            date => Ok(date),
+        }
+    }
+
+    /// Set a world's in-game date; a bare year starts on its first day
+    pub fn set_campaign_date(
+        &self,
+        world_id: Option<&str>,
+        date: ImperialDate,
+    ) -> ServiceResult<ImperialDate> {
+        let date = ImperialDate {
+            day: Some(date.day.unwrap_or(1)),
+            ..date
+        };
+        let world = self.clock_world(world_id);
+        self.db.set_campaign_date(&world, date)?;
+        info!(world_id = %world, date = %date, "Campaign date set");
+        Ok(date)
+    }
+
+    /// Move a world's clock forward
+    pub fn advance_campaign_date(
+        &self,
+        world_id: Option<&str>,
+        advance: &ClockAdvance,
+    ) -> ServiceResult<ClockAdvanceResult> {
+        let world = self.clock_world(world_id);
+        let from =
+            self.db
+                .get_campaign_date(&world)?
+                .ok_or_else(|| ServiceError::InvalidRequest {
+                    message: "The campaign date has not been set".to_string(),
+                })?;
+
+        let seed = advance.seed.unwrap_or_else(rand::random);
+        let jump_hours = roll_jump_hours(seed, advance.jumps);
+        let total_jump_hours: u32 = jump_hours.iter().sum();
+        let days = advance.days as i64
+            + advance.weeks as i64 * 7
+            + (total_jump_hours as f64 / 24.0).round() as i64;
+        let to = from.add_days(days);
+        self.db.set_campaign_date(&world, to)?;
+
+        let event = match advance.note.as_deref().filter(|n| !n.trim().is_empty()) {
+            Some(note) => {
+                Some(self.add_timeline_event(to, note, None, AccessLevel::default(), None)?)
+            }
+            None => None,
+        };
+
+        info!(world_id = %world, from = %from, to = %to, "Campaign date advanced");
+        Ok(ClockAdvanceResult {
+            from,
+            to,
+            days,
+            jump_hours,
+            seed,
+            event,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_jump_hours() {
+        let hours = roll_jump_hours(1105, 3);
+        assert_eq!(hours.len(), 3);
+        assert!(hours.iter().all(|h| (154..=184).contains(h)));
+        assert_eq!(hours, roll_jump_hours(1105, 3));
+    }
+}
@@ -0,0 +1,376 @@
+//! End-to-end self-test.
+//!
+//! Most support problems are environmental: Ollama missing a model, PDFium
+//! not installed, no route to Traveller Map, an assets directory FVTT can
+//! see but the service can't write. Diagnostics exercise each dependency
+//! the way real work does and report every check, so one failure doesn't
+//! hide the next. Nothing is stored: the sample PDF is ingested into a
+//! throwaway document that never reaches the database.
+
+use std::io::{Cursor, Write};
+use std::time::Instant;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::info;
+
+use crate::config::AssetsAccess;
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::pdf::PdfTextOptions;
+use crate::ollama::ChatMessage;
+use crate::service::{ModelTask, SeneschalService};
+use crate::tools::AccessLevel;
+
+/// Text printed in the sample PDF, and expected back from ingestion
+const SAMPLE_TEXT: &str =
+    "Seneschal diagnostics: the Free Trader Beowulf is docked at Regina Highport.";
+
+/// Result of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// Not applicable to this install (e.g. Traveller Map in offline mode)
+    Skipped,
+}
+
+/// One diagnostic check
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub duration_ms: u64,
+    /// What was found, or why the check failed or was skipped
+    pub detail: String,
+}
+
+impl DiagnosticCheck {
+    fn finished(name: &'static str, started: Instant, result: ServiceResult<String>) -> Self {
+        let (status, detail) = match result {
+            Ok(detail) => (CheckStatus::Passed, detail),
+            Err(e) => (CheckStatus::Failed, e.to_string()),
+        };
+        Self {
+            name,
+            status,
+            duration_ms: started.elapsed().as_millis() as u64,
+            detail,
+        }
+    }
+
+    fn skipped(name: &'static str, reason: &str) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skipped,
+            duration_ms: 0,
+            detail: reason.to_string(),
+        }
+    }
+}
+
+/// Outcome of a self-test
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsReport {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Whether no check failed
+    pub passed: bool,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+/// A one-page PDF showing `text` in Helvetica.
+///
+/// `text` must not contain parentheses or backslashes.
+pub(crate) fn sample_pdf(text: &str) -> Vec<u8> {
+    let content = format!("BT /F1 14 Tf 72 720 Td ({}) Tj ET", text);
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+         /Resources << /Font << /F1 4 0 R >> >> /Contents 5 0 R >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+impl SeneschalService {
+    /// Run every diagnostic check, in order
+    pub async fn run_diagnostics(&self) -> DiagnosticsReport {
+        let started_at = Utc::now();
+        let started = Instant::now();
+        info!("Running diagnostics");
+
+        let mut checks = Vec::new();
+
+        let check = Instant::now();
+        let result = self.check_chat().await;
+        checks.push(DiagnosticCheck::finished("ollama_chat", check, result));
+
+        let check = Instant::now();
+        let result = self.check_embedding().await;
+        checks.push(DiagnosticCheck::finished("ollama_embedding", check, result));
+
+        checks.push(if self.primary_model(ModelTask::Captioning).is_none() {
+            DiagnosticCheck::skipped("ollama_vision", "No vision model configured")
+        } else {
+            let check = Instant::now();
+            let result = self.check_vision().await;
+            DiagnosticCheck::finished("ollama_vision", check, result)
+        });
+
+        let check = Instant::now();
+        let result = self.check_pdf_ingestion().await;
+        checks.push(DiagnosticCheck::finished("pdf_ingestion", check, result));
+
+        let check = Instant::now();
+        let result = self.check_search().await;
+        checks.push(DiagnosticCheck::finished("search", check, result));
+
+        checks.push(if self.runtime_config.dynamic().traveller_map.offline {
+            DiagnosticCheck::skipped("traveller_map", "Traveller Map is in offline mode")
+        } else {
+            let check = Instant::now();
+            let result = self.check_traveller_map().await;
+            DiagnosticCheck::finished("traveller_map", check, result)
+        });
+
+        checks.push(match self.runtime_config.static_config.fvtt.assets_path {
+            None => DiagnosticCheck::skipped(
+                "fvtt_assets",
+                "No FVTT assets path configured; files are delivered through the FVTT module",
+            ),
+            Some(_) => {
+                let check = Instant::now();
+                let result = self.check_fvtt_assets();
+                DiagnosticCheck::finished("fvtt_assets", check, result)
+            }
+        });
+
+        let passed = checks.iter().all(|c| c.status != CheckStatus::Failed);
+        info!(passed, "Diagnostics finished");
+        DiagnosticsReport {
+            started_at,
+            duration_ms: started.elapsed().as_millis() as u64,
+            passed,
+            checks,
+        }
+    }
+
+    async fn check_chat(&self) -> ServiceResult<String> {
+        let model = self.primary_model(ModelTask::Chat).unwrap_or_default();
+        let reply = self
+            .generate_routed(
+                ModelTask::Chat,
+                None,
+                vec![ChatMessage::user("Reply with the single word OK.")],
+            )
+            .await?;
+        Ok(format!("{} replied: {}", model, reply.trim()))
+    }
+
+    async fn check_embedding(&self) -> ServiceResult<String> {
+        let embedding = self.search.embed_text(SAMPLE_TEXT).await?;
+        if embedding.is_empty() {
+            return Err(ServiceError::Internal {
+                message: "Embedding model returned an empty vector".to_string(),
+            });
+        }
+        Ok(format!(
+            "{} returned {} dimensions",
+            self.runtime_config.dynamic().embeddings.model,
+            embedding.len()
+        ))
+    }
+
+    async fn check_vision(&self) -> ServiceResult<String> {
+        let model = self
+            .primary_model(ModelTask::Captioning)
+            .unwrap_or_default();
+        let image = image::RgbImage::from_pixel(64, 64, image::Rgb([200, 30, 30]));
+        let mut png = Cursor::new(Vec::new());
+        image
+            .write_to(&mut png, image::ImageFormat::Png)
+            .map_err(|e| ServiceError::Internal {
+                message: format!("Failed to encode test image: {}", e),
+            })?;
+        let image_base64 = base64::engine::general_purpose::STANDARD.encode(png.into_inner());
+
+        let reply = self
+            .generate_routed(
+                ModelTask::Captioning,
+                None,
+                vec![ChatMessage::user_with_image(
+                    "What color is this image? Answer in one word.",
+                    image_base64,
+                )],
+            )
+            .await?;
+        Ok(format!(
+            "{} described a red square as: {}",
+            model,
+            reply.trim()
+        ))
+    }
+
+    async fn check_pdf_ingestion(&self) -> ServiceResult<String> {
+        let mut file = tempfile::Builder::new()
+            .prefix("seneschal-diagnostics-")
+            .suffix(".pdf")
+            .tempfile()
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+        file.write_all(&sample_pdf(SAMPLE_TEXT))
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+
+        let ingestion = self.ingestion.clone();
+        let max_archive_bytes = self
+            .runtime_config
+            .dynamic()
+            .limits
+            .max_archive_uncompressed_bytes;
+        let processed = tokio::task::spawn_blocking(move || {
+            let doc_id = format!("diagnostics-{}", uuid::Uuid::new_v4());
+            ingestion.process_document_with_id(
+                file.path(),
+                &doc_id,
+                "Diagnostics",
+                AccessLevel::GmOnly,
+                Vec::new(),
+                PdfTextOptions::default(),
+                None,
+                max_archive_bytes,
+            )
+        })
+        .await
+        .map_err(|e| ServiceError::Internal {
+            message: format!("PDF ingestion task failed: {}", e),
+        })??;
+
+        let text: String = processed
+            .chunks
+            .iter()
+            .map(|c| c.content.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !text.contains("Free Trader Beowulf") {
+            return Err(ServiceError::Internal {
+                message: format!("Sample text not found in extracted chunks: {:?}", text),
+            });
+        }
+        Ok(format!(
+            "Extracted {} chunk(s) from a sample PDF",
+            processed.chunks.len()
+        ))
+    }
+
+    async fn check_search(&self) -> ServiceResult<String> {
+        let results = self
+            .search
+            .search("starport", AccessLevel::GmOnly as u8, 3, None, 0.0)
+            .await?;
+        Ok(format!("Search returned {} result(s)", results.len()))
+    }
+
+    async fn check_traveller_map(&self) -> ServiceResult<String> {
+        let config = self.runtime_config.dynamic().traveller_map.clone();
+        let url = format!("{}/api/search", config.base_url.trim_end_matches('/'));
+        let response = reqwest::Client::new()
+            .get(&url)
+            .query(&[("q", "Regina")])
+            .timeout(std::time::Duration::from_secs(config.timeout_secs))
+            .send()
+            .await
+            .map_err(|e| ServiceError::Internal {
+                message: format!("{} unreachable: {}", config.base_url, e),
+            })?;
+        if !response.status().is_success() {
+            return Err(ServiceError::Internal {
+                message: format!("{} returned {}", url, response.status()),
+            });
+        }
+        Ok(format!("{} reachable", config.base_url))
+    }
+
+    fn check_fvtt_assets(&self) -> ServiceResult<String> {
+        let fvtt = &self.runtime_config.static_config.fvtt;
+        let AssetsAccess::Direct(assets_dir) = fvtt.check_assets_access() else {
+            return Err(ServiceError::Config {
+                message: "FVTT assets directory is not writable".to_string(),
+            });
+        };
+
+        let probe = assets_dir
+            .join("seneschal")
+            .join(format!(".diagnostics-{}", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&probe, b"ok")
+            .and_then(|_| std::fs::remove_file(&probe))
+            .map_err(|e| ServiceError::Config {
+                message: format!("Cannot write to {}: {}", assets_dir.display(), e),
+            })?;
+        Ok(format!("{} is writable", assets_dir.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_pdf_xref() {
+        let pdf = sample_pdf("Hello Regina");
+        let text = String::from_utf8(pdf.clone()).unwrap();
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("(Hello Regina) Tj"));
+
+        // Every xref entry points at the start of its object
+        let xref = text.find("xref\n").unwrap();
+        let entries: Vec<usize> = text[xref..]
+            .lines()
+            .skip(3)
+            .take(5)
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        for (index, offset) in entries.iter().enumerate() {
+            assert!(text[*offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+
+        let startxref: usize = text
+            .lines()
+            .skip_while(|l| *l != "startxref")
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(startxref, xref);
+    }
+}
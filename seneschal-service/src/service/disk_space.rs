@@ -0,0 +1,276 @@
+//! Disk space monitoring and ingestion backpressure.
+//!
+//! Everything the service stores lives under `storage.data_dir`, and a
+//! full disk corrupts more than it refuses. A background task checks the
+//! filesystem's free space every minute and tells GMs (over the WebSocket
+//! and in the log) when it crosses the `disk` thresholds. Below
+//! `disk.min_free_mb` new uploads are refused and image extraction, the
+//! step that writes the most, waits until space is freed.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::DiskConfig;
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+use crate::websocket::ServerMessage;
+
+/// How often the monitor checks free space
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often paused image extraction checks whether space was freed
+const EXTRACTION_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const MB: u64 = 1024 * 1024;
+
+/// How close the data directory's filesystem is to full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskSpaceLevel {
+    Ok,
+    /// Below `disk.warn_free_mb`
+    Low,
+    /// Below `disk.min_free_mb`: uploads and image extraction are held back
+    Critical,
+}
+
+impl DiskSpaceLevel {
+    fn for_free_bytes(free_bytes: u64, config: &DiskConfig) -> Self {
+        let below = |threshold_mb: u64| threshold_mb > 0 && free_bytes < threshold_mb * MB;
+        if below(config.min_free_mb) {
+            DiskSpaceLevel::Critical
+        } else if below(config.warn_free_mb) {
+            DiskSpaceLevel::Low
+        } else {
+            DiskSpaceLevel::Ok
+        }
+    }
+}
+
+/// Size and free space of a filesystem
+#[derive(Debug, Clone, Copy)]
+struct FreeSpace {
+    total_bytes: u64,
+    /// Space available to the service (excluding blocks reserved for root)
+    free_bytes: u64,
+}
+
+fn free_space(path: &Path) -> std::io::Result<FreeSpace> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is NUL-terminated and `stat` is only read after
+    // statvfs reports that it filled it in
+    let stat = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        stat.assume_init()
+    };
+    let block_size = stat.f_frsize as u64;
+    Ok(FreeSpace {
+        total_bytes: stat.f_blocks as u64 * block_size,
+        free_bytes: stat.f_bavail as u64 * block_size,
+    })
+}
+
+/// Total size of the files under `dir`
+fn dir_size(dir: &Path) -> u64 {
+    super::maintenance::files_under(dir)
+        .iter()
+        .filter_map(|file| std::fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Free space, and what the service's data takes up
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskUsage {
+    pub level: DiskSpaceLevel,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    /// Database, including its WAL
+    pub database_bytes: u64,
+    /// Uploaded source files
+    pub documents_bytes: u64,
+    /// Images extracted from documents
+    pub images_bytes: u64,
+    /// Cached downscaled image variants
+    pub thumbnails_bytes: u64,
+}
+
+impl SeneschalService {
+    /// Start the background task that warns GMs about low disk space
+    pub fn start_disk_monitor(service: Arc<SeneschalService>) {
+        tokio::spawn(async move {
+            info!("Disk space monitor started");
+            let mut last_level = DiskSpaceLevel::Ok;
+            loop {
+                match service.disk_space() {
+                    Ok((level, space)) if level != last_level => {
+                        service.announce_disk_space(level, space);
+                        last_level = level;
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!(error = %e, "Failed to check disk space"),
+                }
+                tokio::time::sleep(DISK_CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    fn disk_space(&self) -> ServiceResult<(DiskSpaceLevel, FreeSpace)> {
+        let data_dir = &self.runtime_config.static_config.storage.data_dir;
+        let space =
+            free_space(data_dir).map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+        let config = &self.runtime_config.dynamic().disk;
+        Ok((
+            DiskSpaceLevel::for_free_bytes(space.free_bytes, config),
+            space,
+        ))
+    }
+
+    fn announce_disk_space(&self, level: DiskSpaceLevel, space: FreeSpace) {
+        let free_mb = space.free_bytes / MB;
+        match level {
+            DiskSpaceLevel::Critical => warn!(
+                free_mb,
+                "Disk space critically low; uploads are refused and image extraction is paused"
+            ),
+            DiskSpaceLevel::Low => warn!(free_mb, "Disk space running low"),
+            DiskSpaceLevel::Ok => info!(free_mb, "Disk space recovered"),
+        }
+        self.ws_manager
+            .broadcast_to_gms(ServerMessage::DiskSpaceStatus {
+                level,
+                free_bytes: space.free_bytes,
+                total_bytes: space.total_bytes,
+            });
+    }
+
+    /// Refuse to store `incoming_bytes` more if that would leave less than
+    /// `disk.min_free_mb` free
+    pub(crate) fn ensure_disk_space(&self, incoming_bytes: u64) -> ServiceResult<()> {
+        let reserve = self.runtime_config.dynamic().disk.min_free_mb * MB;
+        if reserve == 0 {
+            return Ok(());
+        }
+        let (_, space) = self.disk_space()?;
+        if space.free_bytes.saturating_sub(incoming_bytes) < reserve {
+            return Err(ServiceError::Processing(
+                ProcessingError::InsufficientStorage {
+                    free: space.free_bytes,
+                    reserve,
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Wait until free space is above `disk.min_free_mb`. Returns false if
+    /// the document was cancelled while waiting.
+    pub(crate) async fn wait_for_disk_space(
+        &self,
+        doc_id: &str,
+        cancel_token: &CancellationToken,
+    ) -> bool {
+        let mut announced = false;
+        loop {
+            match self.disk_space() {
+                Ok((DiskSpaceLevel::Critical, _)) => {}
+                Ok(_) => return true,
+                Err(e) => {
+                    warn!(doc_id = %doc_id, error = %e, "Failed to check disk space");
+                    return true;
+                }
+            }
+            if !announced {
+                warn!(doc_id = %doc_id, "Image extraction paused until disk space is freed");
+                if let Err(e) =
+                    self.db
+                        .update_document_progress(doc_id, "waiting_for_disk_space", 0, 1)
+                {
+                    warn!(doc_id = %doc_id, phase = "waiting_for_disk_space", error = %e, "Failed to update progress");
+                }
+                self.broadcast_document_progress(
+                    doc_id,
+                    "processing",
+                    Some("waiting_for_disk_space"),
+                    None,
+                    None,
+                    None,
+                );
+                announced = true;
+            }
+            tokio::select! {
+                _ = cancel_token.cancelled() => return false,
+                _ = tokio::time::sleep(EXTRACTION_RECHECK_INTERVAL) => {}
+            }
+        }
+    }
+
+    /// Free space and the size of each kind of stored data.
+    ///
+    /// Walks the data directory; call it off the async runtime.
+    pub fn disk_usage(&self) -> ServiceResult<DiskUsage> {
+        let data_dir = &self.runtime_config.static_config.storage.data_dir;
+        let (level, space) = self.disk_space()?;
+        let database_bytes = ["seneschal.db", "seneschal.db-wal"]
+            .iter()
+            .filter_map(|name| std::fs::metadata(data_dir.join(name)).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+
+        Ok(DiskUsage {
+            level,
+            total_bytes: space.total_bytes,
+            free_bytes: space.free_bytes,
+            database_bytes,
+            documents_bytes: dir_size(&data_dir.join("documents")),
+            images_bytes: dir_size(&data_dir.join("images")),
+            thumbnails_bytes: dir_size(&data_dir.join("thumbnails")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_space_level() {
+        let config = DiskConfig {
+            min_free_mb: 1024,
+            warn_free_mb: 5 * 1024,
+        };
+        assert_eq!(
+            DiskSpaceLevel::for_free_bytes(100 * MB, &config),
+            DiskSpaceLevel::Critical
+        );
+        assert_eq!(
+            DiskSpaceLevel::for_free_bytes(2048 * MB, &config),
+            DiskSpaceLevel::Low
+        );
+        assert_eq!(
+            DiskSpaceLevel::for_free_bytes(10 * 1024 * MB, &config),
+            DiskSpaceLevel::Ok
+        );
+
+        // 0 disables a threshold
+        let config = DiskConfig {
+            min_free_mb: 0,
+            warn_free_mb: 0,
+        };
+        assert_eq!(
+            DiskSpaceLevel::for_free_bytes(0, &config),
+            DiskSpaceLevel::Ok
+        );
+    }
+}
@@ -2,8 +2,13 @@
 //!
 //! This module coordinates document lifecycle operations:
 //! - Upload and hash backfill
+//! - New versions, re-ingesting only changed pages
 //! - Background processing workers
-//! - Image captioning
+//! - Image captioning and re-captioning
+//! - NPC/creature stat block extraction
+//! - Read-aloud text extraction
+//! - Document summaries and outlines
+//! - Page rendering
 //! - Progress broadcasting
 //! - Cancellation management
 //! - CRUD operations
@@ -11,7 +16,15 @@
 mod cancellation;
 mod captioning;
 mod crud;
+mod page_render;
 mod processing;
 mod progress;
+mod read_aloud;
+mod recaption;
+mod stat_blocks;
+mod summaries;
 mod upload;
+mod versions;
 mod workers;
+
+pub use captioning::CaptionPreset;
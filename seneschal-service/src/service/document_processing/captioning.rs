@@ -1,11 +1,67 @@
 //! Image captioning functionality.
 
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
 use base64::Engine;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use crate::db::{CaptioningStatus, Document};
+use crate::db::{CaptioningStatus, Document, DocumentImage};
 use crate::error::{ServiceError, ServiceResult};
-use crate::service::SeneschalService;
+use crate::ingestion::grid::{detect_grid_in_file, is_map_caption};
+use crate::service::{ModelTask, SeneschalService};
+
+/// Style of description requested from the vision model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionPreset {
+    /// One or two sentences naming the subject
+    Brief,
+    /// Subject, composition and visible text (the default)
+    #[default]
+    Detailed,
+    /// Prioritizes transcribing text, labels and legends (maps, deck plans, handouts)
+    Transcription,
+}
+
+impl CaptionPreset {
+    /// Parse a preset name, falling back to `Detailed` for unknown values
+    pub fn from_str_lossy(value: &str) -> Self {
+        match value {
+            "brief" => CaptionPreset::Brief,
+            "transcription" => CaptionPreset::Transcription,
+            _ => CaptionPreset::Detailed,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptionPreset::Brief => "brief",
+            CaptionPreset::Detailed => "detailed",
+            CaptionPreset::Transcription => "transcription",
+        }
+    }
+
+    fn instructions(&self) -> &'static str {
+        match self {
+            CaptionPreset::Brief => {
+                "Describe what the image depicts in one or two sentences, \
+                naming the main subject (character, creature, location, item, map, etc.)."
+            }
+            CaptionPreset::Detailed => {
+                "Focus on what the image depicts (characters, creatures, locations, items, maps, etc.) \
+                and any text visible in the image. Be concise but descriptive."
+            }
+            CaptionPreset::Transcription => {
+                "Transcribe all text visible in the image, including labels, legends, room names \
+                and numbers, then briefly describe what the image depicts."
+            }
+        }
+    }
+}
 
 impl SeneschalService {
     /// Caption images for a single document (called by the captioning worker)
@@ -16,56 +72,44 @@ impl SeneschalService {
         // Register cancellation token for this document
         let cancel_token = self.register_processing_token(doc_id);
 
-        let file_path = match &document.file_path {
-            Some(p) => std::path::PathBuf::from(p),
-            None => {
-                error!(doc_id = %doc_id, "Document has no file path for captioning");
+        let file_path = match self.readable_source(document) {
+            Ok(path) => path,
+            Err(e) => {
+                let message = e.to_string();
+                error!(doc_id = %doc_id, "{}", message);
                 if let Err(e) = self.db.update_captioning_status(
                     doc_id,
                     CaptioningStatus::Failed,
-                    Some("Document has no file path"),
+                    Some(&message),
                 ) {
                     warn!(doc_id = %doc_id, error = %e, "Failed to update captioning status to failed");
                 }
-                self.broadcast_captioning_progress(
-                    doc_id,
-                    "failed",
-                    None,
-                    None,
-                    Some("Document has no file path"),
-                );
+                self.broadcast_captioning_progress(doc_id, "failed", None, None, Some(&message));
                 self.unregister_processing_token(doc_id);
                 return;
             }
         };
 
-        // Extract vision model from metadata, falling back to runtime config
-        let vision_model = match document
+        let preset = document
             .metadata
             .as_ref()
-            .and_then(|m| m.get("vision_model"))
+            .and_then(|m| m.get("caption_preset"))
             .and_then(|v| v.as_str())
-        {
-            Some(model) => model.to_string(),
-            None => {
-                // Fall back to runtime config
-                let config = self.runtime_config.dynamic();
-                let model = &config.ollama.vision_model;
-                if model.is_empty() {
-                    info!(doc_id = %doc_id, "No vision model configured, skipping captioning");
-                    // Mark as completed (no captioning needed) instead of failed
-                    if let Err(e) =
-                        self.db
-                            .update_captioning_status(doc_id, CaptioningStatus::Completed, None)
-                    {
-                        warn!(doc_id = %doc_id, error = %e, "Failed to update captioning status");
-                    }
-                    self.broadcast_captioning_progress(doc_id, "completed", None, None, None);
-                    self.unregister_processing_token(doc_id);
-                    return;
-                }
-                model.clone()
+            .map(CaptionPreset::from_str_lossy)
+            .unwrap_or_default();
+
+        let Some(vision_model) = self.captioning_model(document) else {
+            info!(doc_id = %doc_id, "No vision model configured, skipping captioning");
+            // Mark as completed (no captioning needed) instead of failed
+            if let Err(e) =
+                self.db
+                    .update_captioning_status(doc_id, CaptioningStatus::Completed, None)
+            {
+                warn!(doc_id = %doc_id, error = %e, "Failed to update captioning status");
             }
+            self.broadcast_captioning_progress(doc_id, "completed", None, None, None);
+            self.unregister_processing_token(doc_id);
+            return;
         };
 
         // Get images that need captioning
@@ -158,7 +202,12 @@ impl SeneschalService {
             .collect();
 
         let page_list: Vec<i32> = unique_pages.into_iter().collect();
-        let page_texts = match self.ingestion.extract_pdf_page_text(&file_path, &page_list) {
+        let pdf_password = self.pdf_password(doc_id);
+        let page_texts = match self.ingestion.extract_pdf_page_text(
+            &file_path,
+            &page_list,
+            pdf_password.as_deref(),
+        ) {
             Ok(texts) => {
                 debug!(
                     doc_id = %doc_id,
@@ -173,20 +222,41 @@ impl SeneschalService {
                     error = %e,
                     "Failed to extract page text, captioning without context"
                 );
-                std::collections::HashMap::new()
+                HashMap::new()
             }
         };
 
-        // Caption each image
-        for (i, image) in images_to_caption.iter().enumerate() {
-            // Check for cancellation before each image
+        // Caption images, several at a time if configured
+        let concurrency = self.runtime_config.dynamic().captioning.concurrency.max(1);
+        let mut captioning = futures::stream::iter(images_to_caption)
+            .map(|image: DocumentImage| {
+                let page_texts = &page_texts;
+                let vision_model = &vision_model;
+                let cancel_token = &cancel_token;
+                async move {
+                    self.wait_for_interactive_idle(cancel_token).await;
+                    if cancel_token.is_cancelled() {
+                        return;
+                    }
+                    self.caption_and_embed_image(
+                        &image,
+                        vision_model,
+                        preset,
+                        &document.title,
+                        page_texts,
+                    )
+                    .await;
+                }
+            })
+            .buffer_unordered(concurrency);
+
+        let mut current_progress = already_captioned;
+        while captioning.next().await.is_some() {
             if cancel_token.is_cancelled() {
-                info!(doc_id = %doc_id, progress = i, "Image captioning cancelled");
-                self.unregister_processing_token(doc_id);
-                return;
+                continue;
             }
 
-            let current_progress = already_captioned + i + 1;
+            current_progress += 1;
             if let Err(e) =
                 self.db
                     .update_captioning_progress(doc_id, current_progress, total_images)
@@ -200,86 +270,12 @@ impl SeneschalService {
                 Some(total_images),
                 None,
             );
+        }
 
-            debug!(
-                doc_id = %doc_id,
-                image_id = %image.id,
-                progress = current_progress,
-                total = total_images,
-                "Captioning image"
-            );
-
-            // Build page context for this image
-            let mut source_pages = image
-                .source_pages
-                .clone()
-                .unwrap_or_else(|| vec![image.page_number]);
-            source_pages.sort();
-            let context: String = source_pages
-                .iter()
-                .filter_map(|p| {
-                    page_texts
-                        .get(p)
-                        .map(|t| format!("--- Page {} ---\n{}", p, t))
-                })
-                .collect::<Vec<_>>()
-                .join("\n\n");
-            let page_context = if context.is_empty() {
-                None
-            } else {
-                Some(context.as_str())
-            };
-
-            let image_path = std::path::Path::new(&image.internal_path);
-            match self
-                .caption_image(image_path, &vision_model, &document.title, page_context)
-                .await
-            {
-                Ok(Some(description)) => {
-                    if let Err(e) = self.db.update_image_description(&image.id, &description) {
-                        warn!(
-                            image_id = %image.id,
-                            error = %e,
-                            "Failed to update image description"
-                        );
-                    } else {
-                        // Generate and store embedding for the description
-                        match self.search.embed_text(&description).await {
-                            Ok(embedding) => {
-                                if let Err(e) =
-                                    self.db.insert_image_embedding(&image.id, &embedding)
-                                {
-                                    warn!(
-                                        image_id = %image.id,
-                                        error = %e,
-                                        "Failed to store image embedding"
-                                    );
-                                }
-                            }
-                            Err(e) => {
-                                warn!(
-                                    image_id = %image.id,
-                                    error = %e,
-                                    "Failed to generate image embedding"
-                                );
-                            }
-                        }
-                        debug!(
-                            image_id = %image.id,
-                            description_len = description.len(),
-                            "Image captioned successfully"
-                        );
-                    }
-                }
-                Ok(None) => {}
-                Err(e) => {
-                    warn!(
-                        image_id = %image.id,
-                        error = %e,
-                        "Failed to caption image"
-                    );
-                }
-            }
+        if cancel_token.is_cancelled() {
+            info!(doc_id = %doc_id, progress = current_progress, "Image captioning cancelled");
+            self.unregister_processing_token(doc_id);
+            return;
         }
 
         // Mark captioning as complete
@@ -300,11 +296,173 @@ impl SeneschalService {
         info!(doc_id = %doc_id, "Image captioning complete");
     }
 
-    /// Caption an image using the specified vision model
+    /// Vision model for a document: its upload-time choice, else the routed captioning model
+    pub(crate) fn captioning_model(&self, document: &Document) -> Option<String> {
+        document
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("vision_model"))
+            .and_then(|v| v.as_str())
+            .map(|m| m.to_string())
+            .or_else(|| self.primary_model(ModelTask::Captioning))
+    }
+
+    /// Record an interactive request so background captioning yields the model host
+    pub fn mark_interactive_activity(&self) {
+        *self.last_interactive_activity.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Wait until no interactive request has been seen for `captioning.interactive_pause_secs`
+    async fn wait_for_interactive_idle(&self, cancel_token: &CancellationToken) {
+        loop {
+            let pause = Duration::from_secs(
+                self.runtime_config
+                    .dynamic()
+                    .captioning
+                    .interactive_pause_secs,
+            );
+            let idle_for = self
+                .last_interactive_activity
+                .lock()
+                .unwrap()
+                .map(|last| last.elapsed());
+
+            let remaining = match idle_for {
+                Some(idle) if idle < pause => pause - idle,
+                _ => return,
+            };
+
+            debug!(
+                wait_secs = remaining.as_secs(),
+                "Captioning paused for interactive use"
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(remaining) => {}
+                _ = cancel_token.cancelled() => return,
+            }
+        }
+    }
+
+    /// Caption a single image and store its description and embedding
+    async fn caption_and_embed_image(
+        &self,
+        image: &DocumentImage,
+        vision_model: &str,
+        preset: CaptionPreset,
+        document_title: &str,
+        page_texts: &HashMap<i32, String>,
+    ) {
+        debug!(image_id = %image.id, "Captioning image");
+
+        // Build page context for this image
+        let mut source_pages = image
+            .source_pages
+            .clone()
+            .unwrap_or_else(|| vec![image.page_number]);
+        source_pages.sort();
+        let context: String = source_pages
+            .iter()
+            .filter_map(|p| {
+                page_texts
+                    .get(p)
+                    .map(|t| format!("--- Page {} ---\n{}", p, t))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let page_context = if context.is_empty() {
+            None
+        } else {
+            Some(context.as_str())
+        };
+
+        let image_path = std::path::Path::new(&image.internal_path);
+        match self
+            .caption_image(
+                image_path,
+                vision_model,
+                preset,
+                document_title,
+                page_context,
+            )
+            .await
+        {
+            Ok(Some(description)) => {
+                if let Err(e) = self.db.update_image_description(&image.id, &description) {
+                    warn!(
+                        image_id = %image.id,
+                        error = %e,
+                        "Failed to update image description"
+                    );
+                    return;
+                }
+
+                // Generate and store embedding for the description
+                match self.search.embed_text(&description).await {
+                    Ok(embedding) => {
+                        if let Err(e) = self.db.insert_image_embedding(&image.id, &embedding) {
+                            warn!(
+                                image_id = %image.id,
+                                error = %e,
+                                "Failed to store image embedding"
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        warn!(
+                            image_id = %image.id,
+                            error = %e,
+                            "Failed to generate image embedding"
+                        );
+                    }
+                }
+                if is_map_caption(&description) {
+                    self.detect_map_grid(image).await;
+                }
+                debug!(
+                    image_id = %image.id,
+                    description_len = description.len(),
+                    "Image captioned successfully"
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    image_id = %image.id,
+                    error = %e,
+                    "Failed to caption image"
+                );
+            }
+        }
+    }
+
+    /// Detect and store the grid of an image captioned as a map. Failures
+    /// only mean the scene settings tool detects it again on demand.
+    async fn detect_map_grid(&self, image: &DocumentImage) {
+        let path = std::path::PathBuf::from(&image.internal_path);
+        match tokio::task::spawn_blocking(move || detect_grid_in_file(&path)).await {
+            Ok(Ok(Some(detected))) => {
+                if let Err(e) = self.store_image_grid(&image.id, detected) {
+                    warn!(image_id = %image.id, error = %e, "Failed to store map grid");
+                }
+            }
+            Ok(Ok(None)) => {
+                debug!(image_id = %image.id, "No grid found on map image");
+            }
+            Ok(Err(e)) => {
+                warn!(image_id = %image.id, error = %e, "Failed to detect map grid");
+            }
+            Err(e) => {
+                warn!(image_id = %image.id, error = %e, "Map grid detection panicked");
+            }
+        }
+    }
+
+    /// Caption an image using the specified vision model and prompt preset
     pub async fn caption_image(
         &self,
         image_path: &std::path::Path,
         vision_model: &str,
+        preset: CaptionPreset,
         document_title: &str,
         page_context: Option<&str>,
     ) -> ServiceResult<Option<String>> {
@@ -315,11 +473,10 @@ impl SeneschalService {
 
         // Build prompt with document title and optional page context
         let base_prompt = format!(
-            "Describe this image from the tabletop RPG document \"{}\". \
-            Focus on what the image depicts (characters, creatures, locations, items, maps, etc.) \
-            and any text visible in the image. Be concise but descriptive. \
+            "Describe this image from the tabletop RPG document \"{}\". {} \
             This description will be used to help game masters find relevant images.",
-            document_title
+            document_title,
+            preset.instructions()
         );
 
         let prompt = if let Some(context) = page_context {
@@ -339,8 +496,7 @@ impl SeneschalService {
         let message = crate::ollama::ChatMessage::user_with_image(&prompt, image_base64);
 
         let description = self
-            .ollama
-            .generate_simple(vision_model, vec![message])
+            .generate_routed(ModelTask::Captioning, Some(vision_model), vec![message])
             .await?;
 
         Ok(Some(description))
@@ -1,9 +1,12 @@
 //! Document and image CRUD operations.
 
-use tracing::{info, warn};
+use std::path::PathBuf;
 
-use crate::db::{Document, ProcessingStatus};
+use tracing::{debug, info, warn};
+
+use crate::db::{Document, DocumentAccessRule, DocumentImage, ProcessingStatus};
 use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::thumbnails::{ImageSize, generate_thumbnail, thumbnail_path};
 use crate::service::SeneschalService;
 use crate::tools::AccessLevel;
 
@@ -20,6 +23,7 @@ impl SeneschalService {
         if was_processing {
             info!(doc_id = %document_id, "Cancelled in-progress processing for deleted document");
         }
+        self.pdf_passwords.remove(document_id);
 
         self.db.delete_document(document_id)
     }
@@ -32,8 +36,82 @@ impl SeneschalService {
         access_level: AccessLevel,
         tags: Vec<String>,
     ) -> ServiceResult<bool> {
-        self.db
-            .update_document(document_id, title, access_level, tags)
+        let updated = self
+            .db
+            .update_document(document_id, title, access_level, tags)?;
+        if updated {
+            // Chunks copy the document's level, so propagate the change (keeping rule overrides)
+            self.db.apply_document_access_rules(document_id)?;
+        }
+        Ok(updated)
+    }
+
+    /// Override the access level for a page range and/or section of a document.
+    ///
+    /// Returns the stored rule with the number of chunks and images it now covers.
+    pub fn add_document_access_rule(
+        &self,
+        document_id: &str,
+        start_page: Option<i32>,
+        end_page: Option<i32>,
+        section_pattern: Option<String>,
+        access_level: AccessLevel,
+    ) -> ServiceResult<(DocumentAccessRule, usize, usize)> {
+        let section_pattern = section_pattern
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+        if start_page.is_none() && end_page.is_none() && section_pattern.is_none() {
+            return Err(ServiceError::InvalidRequest {
+                message: "An access rule needs a page range or section pattern".to_string(),
+            });
+        }
+        if let (Some(start), Some(end)) = (start_page, end_page)
+            && start > end
+        {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Invalid page range: {} > {}", start, end),
+            });
+        }
+        if self.db.get_document(document_id)?.is_none() {
+            return Err(ServiceError::DocumentNotFound {
+                document_id: document_id.to_string(),
+            });
+        }
+
+        let rule = DocumentAccessRule {
+            id: uuid::Uuid::new_v4().to_string(),
+            document_id: document_id.to_string(),
+            start_page,
+            end_page,
+            section_pattern,
+            access_level,
+            created_at: chrono::Utc::now(),
+        };
+        self.db.insert_document_access_rule(&rule)?;
+        self.db.apply_document_access_rules(document_id)?;
+
+        let (chunks, images) = self.db.count_access_rule_matches(&rule)?;
+        info!(
+            document_id = %document_id,
+            rule_id = %rule.id,
+            chunks = chunks,
+            images = images,
+            "Added document access rule"
+        );
+        Ok((rule, chunks, images))
+    }
+
+    /// Remove an access rule and recompute the document's access levels
+    pub fn delete_document_access_rule(
+        &self,
+        document_id: &str,
+        rule_id: &str,
+    ) -> ServiceResult<bool> {
+        let deleted = self.db.delete_document_access_rule(document_id, rule_id)?;
+        if deleted {
+            self.db.apply_document_access_rules(document_id)?;
+        }
+        Ok(deleted)
     }
 
     /// Get images for a document
@@ -67,6 +145,20 @@ impl SeneschalService {
             .join(document_id);
         let _ = std::fs::remove_dir(&images_dir); // Ignore error if not empty or doesn't exist
 
+        // Cached size variants are keyed by document, so drop them all at once
+        let thumbnails_dir = self
+            .runtime_config
+            .static_config
+            .storage
+            .data_dir
+            .join("thumbnails")
+            .join(document_id);
+        if thumbnails_dir.exists()
+            && let Err(e) = std::fs::remove_dir_all(&thumbnails_dir)
+        {
+            warn!(path = %thumbnails_dir.display(), error = %e, "Failed to delete thumbnails");
+        }
+
         info!(document_id = %document_id, count = count, "Deleted document images");
         Ok(count)
     }
@@ -82,6 +174,8 @@ impl SeneschalService {
                 warn!(path = %path, error = %e, "Failed to delete image file");
             }
 
+            self.remove_image_thumbnails(&document_id, image_id);
+
             info!(image_id = %image_id, document_id = %document_id, "Deleted image");
             Ok(true)
         } else {
@@ -89,6 +183,73 @@ impl SeneschalService {
         }
     }
 
+    /// Get the file to serve for an image at the requested size, generating it if needed.
+    ///
+    /// Falls back to the original if the variant can't be generated.
+    pub fn image_variant_path(
+        &self,
+        image: &DocumentImage,
+        size: ImageSize,
+    ) -> ServiceResult<PathBuf> {
+        let original = PathBuf::from(&image.internal_path);
+        let config = self.runtime_config.dynamic();
+        let max_edge = match size {
+            ImageSize::Full => return Ok(original),
+            ImageSize::Thumb => config.image_extraction.thumbnail_size,
+            ImageSize::Medium => config.image_extraction.medium_size,
+        };
+
+        // Original already fits, no need for a copy
+        if image.width.is_some_and(|w| w <= max_edge) && image.height.is_some_and(|h| h <= max_edge)
+        {
+            return Ok(original);
+        }
+
+        let variant = thumbnail_path(
+            &self.runtime_config.static_config.storage.data_dir,
+            &image.document_id,
+            &image.id,
+            max_edge,
+        );
+        if variant.exists() {
+            return Ok(variant);
+        }
+
+        match generate_thumbnail(&original, &variant, max_edge) {
+            Ok(()) => {
+                debug!(image_id = %image.id, size = size.as_str(), "Generated image variant");
+                Ok(variant)
+            }
+            Err(e) => {
+                warn!(image_id = %image.id, error = %e, "Failed to generate image variant, serving original");
+                Ok(original)
+            }
+        }
+    }
+
+    /// Remove cached size variants of a single image
+    fn remove_image_thumbnails(&self, document_id: &str, image_id: &str) {
+        let thumbnails_dir = self
+            .runtime_config
+            .static_config
+            .storage
+            .data_dir
+            .join("thumbnails")
+            .join(document_id);
+        let Ok(entries) = std::fs::read_dir(&thumbnails_dir) else {
+            return;
+        };
+
+        let prefix = format!("{}_", image_id);
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&prefix)
+                && let Err(e) = std::fs::remove_file(entry.path())
+            {
+                warn!(path = %entry.path().display(), error = %e, "Failed to delete thumbnail");
+            }
+        }
+    }
+
     /// Re-extract images from a document
     pub fn reextract_document_images(
         &self,
@@ -104,18 +265,10 @@ impl SeneschalService {
                 })?;
 
         // Get the file path
-        let file_path =
-            document
-                .file_path
-                .as_ref()
-                .ok_or_else(|| ServiceError::InvalidRequest {
-                    message:
-                        "Document has no associated file. Re-upload the document to extract images."
-                            .to_string(),
-                })?;
+        let doc_path = self.readable_source(&document)?;
 
         // Check if it's a PDF
-        let extension = std::path::Path::new(file_path)
+        let extension = doc_path
             .extension()
             .and_then(|e| e.to_str())
             .map(|e| e.to_lowercase())
@@ -127,7 +280,6 @@ impl SeneschalService {
             });
         }
 
-        let doc_path = std::path::Path::new(file_path);
         if !doc_path.exists() {
             return Err(ServiceError::InvalidRequest {
                 message:
@@ -139,12 +291,17 @@ impl SeneschalService {
         // Delete existing images first
         self.delete_document_images(document_id)?;
 
-        // Update metadata with vision model if provided
-        if vision_model.is_some() {
-            let metadata = serde_json::json!({ "vision_model": vision_model });
+        // Update metadata with vision model if provided, keeping the rest
+        // (the encrypted flag in particular)
+        if let Some(vision_model) = vision_model {
+            let mut metadata = match document.metadata {
+                Some(serde_json::Value::Object(map)) => map,
+                _ => serde_json::Map::new(),
+            };
+            metadata.insert("vision_model".to_string(), serde_json::json!(vision_model));
             let _ = self
                 .db
-                .update_document_metadata(document_id, Some(metadata));
+                .update_document_metadata(document_id, Some(serde_json::Value::Object(metadata)));
         }
 
         // Queue for processing by setting status back to "processing"
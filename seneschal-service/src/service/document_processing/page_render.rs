@@ -0,0 +1,113 @@
+//! Rendering of PDF pages and page regions for previews and handouts.
+
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+use tracing::debug;
+
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::pdf::page_render::{
+    MAX_RENDER_DPI, MIN_RENDER_DPI, PageImageFormat, PageRegion, page_render_path, pdf_page_count,
+    render_page, write_page_image,
+};
+use crate::service::SeneschalService;
+
+impl SeneschalService {
+    /// Get the file for a rendered page of a PDF document, rendering it if needed.
+    ///
+    /// Pages are 1-indexed; `dpi` is clamped to the supported range. Documents
+    /// above `max_access_level` are reported as not found, before any cached
+    /// render is looked at. This is blocking work and should be run off the
+    /// async runtime.
+    pub fn rendered_page_path(
+        &self,
+        document_id: &str,
+        page_number: u32,
+        dpi: u32,
+        format: PageImageFormat,
+        max_access_level: u8,
+    ) -> ServiceResult<PathBuf> {
+        let document = self
+            .db
+            .get_document(document_id)?
+            .filter(|doc| doc.access_level.accessible_by(max_access_level))
+            .ok_or_else(|| ServiceError::DocumentNotFound {
+                document_id: document_id.to_string(),
+            })?;
+
+        let dpi = dpi.clamp(MIN_RENDER_DPI, MAX_RENDER_DPI);
+        let cached = page_render_path(
+            &self.runtime_config.static_config.storage.data_dir,
+            &document.id,
+            page_number,
+            dpi,
+            format,
+        );
+        if cached.exists() {
+            return Ok(cached);
+        }
+
+        let pdf_path = self.readable_source(&document)?;
+        if !is_pdf(&pdf_path) {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Document {} is not a PDF", document.id),
+            });
+        }
+        let password = self.pdf_password(&document.id);
+        let page_count = pdf_page_count(&pdf_path, password.as_deref())?;
+        if page_number == 0 || page_number > page_count {
+            return Err(ServiceError::InvalidRequest {
+                message: format!(
+                    "Page {} is out of range; the document has {} pages",
+                    page_number, page_count
+                ),
+            });
+        }
+
+        let image = render_page(&pdf_path, page_number, dpi, password.as_deref())?;
+        write_page_image(&image, &cached, format)?;
+        debug!(doc_id = %document_id, page = page_number, dpi, "Rendered document page");
+
+        Ok(cached)
+    }
+
+    /// Render a page, or a region of it, as an image.
+    ///
+    /// Uses the cached WebP render of the whole page when there is one. This is
+    /// blocking work and should be run off the async runtime.
+    pub fn render_page_image(
+        &self,
+        document_id: &str,
+        page_number: u32,
+        dpi: u32,
+        region: Option<PageRegion>,
+        max_access_level: u8,
+    ) -> ServiceResult<RgbaImage> {
+        let path = self.rendered_page_path(
+            document_id,
+            page_number,
+            dpi,
+            PageImageFormat::Webp,
+            max_access_level,
+        )?;
+        let page = image::open(&path)
+            .map_err(|e| ProcessingError::PageRender {
+                page: page_number,
+                message: format!("failed to read cached render: {}", e),
+            })?
+            .to_rgba8();
+
+        match region {
+            Some(region) => region
+                .crop(&page)
+                .map_err(|message| ServiceError::InvalidRequest { message }),
+            None => Ok(page),
+        }
+    }
+}
+
+fn is_pdf(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+}
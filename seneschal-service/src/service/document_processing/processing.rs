@@ -5,8 +5,11 @@ use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
 use crate::db::{Document, ProcessingStatus};
-use crate::error::ServiceError;
 use crate::error::format_error_chain_ref;
+use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::pdf::PdfTextOptions;
+use crate::ingestion::pdf::furniture::PageFurniture;
+use crate::ingestion::pdf::layout::ColumnLayout;
 use crate::service::SeneschalService;
 use crate::websocket::DocumentProgressUpdate;
 
@@ -20,14 +23,15 @@ impl SeneschalService {
         // Register cancellation token for this document
         let cancel_token = self.register_processing_token(doc_id);
 
-        let file_path = match &document.file_path {
-            Some(p) => std::path::PathBuf::from(p),
-            None => {
-                error!(doc_id = %doc_id, "Document has no file path");
+        let file_path = match self.readable_source(document) {
+            Ok(path) => path,
+            Err(e) => {
+                let message = e.to_string();
+                error!(doc_id = %doc_id, "{}", message);
                 if let Err(e) = self.db.update_document_processing_status(
                     doc_id,
                     ProcessingStatus::Failed,
-                    Some("Document has no file path"),
+                    Some(&message),
                 ) {
                     warn!(doc_id = %doc_id, error = %e, "Failed to update status to failed");
                 }
@@ -37,7 +41,7 @@ impl SeneschalService {
                     None,
                     None,
                     None,
-                    Some("Document has no file path"),
+                    Some(&message),
                 );
                 self.unregister_processing_token(doc_id);
                 return;
@@ -50,22 +54,7 @@ impl SeneschalService {
             .unwrap_or("document")
             .to_string();
 
-        // Extract vision model from metadata, falling back to runtime config
-        let vision_model = document
-            .metadata
-            .as_ref()
-            .and_then(|m| m.get("vision_model"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .or_else(|| {
-                let config = self.runtime_config.dynamic();
-                let model = &config.ollama.vision_model;
-                if model.is_empty() {
-                    None
-                } else {
-                    Some(model.clone())
-                }
-            });
+        let vision_model = self.captioning_model(document);
 
         info!(doc_id = %doc_id, "Resuming/starting document processing");
 
@@ -80,8 +69,17 @@ impl SeneschalService {
             debug!(doc_id = %doc_id, error = %e, "Failed to get chunk count");
             0
         });
-        if existing_chunk_count == 0 {
-            info!(doc_id = %doc_id, "Extracting text and creating chunks");
+        let pending_version = self
+            .db
+            .get_pending_document_version(doc_id)
+            .unwrap_or_else(|e| {
+                debug!(doc_id = %doc_id, error = %e, "Failed to get pending document version");
+                None
+            });
+        // Whether chunks were (re)built, so the data derived from them needs rebuilding
+        let mut rechunked = existing_chunk_count == 0;
+        if existing_chunk_count == 0 || pending_version.is_some() {
+            info!(doc_id = %doc_id, version = ?pending_version, "Extracting text and creating chunks");
             if let Err(e) = self.db.update_document_progress(doc_id, "chunking", 0, 1) {
                 warn!(doc_id = %doc_id, phase = "chunking", error = %e, "Failed to update progress");
             }
@@ -94,14 +92,39 @@ impl SeneschalService {
                 None,
             );
 
-            let chunks = match self.ingestion.process_document_with_id(
+            // Per-document settings, falling back to runtime config
+            let metadata = document.metadata.as_ref();
+            let pdf_options = PdfTextOptions {
+                strip_furniture: metadata
+                    .and_then(|m| m.get("strip_page_furniture"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or_else(|| {
+                        self.runtime_config
+                            .dynamic()
+                            .embeddings
+                            .strip_headers_footers
+                    }),
+                layout: metadata
+                    .and_then(|m| m.get("pdf_layout"))
+                    .and_then(|v| v.as_str())
+                    .and_then(ColumnLayout::parse)
+                    .unwrap_or_default(),
+            };
+
+            let processed = match self.ingestion.process_document_with_id(
                 &file_path,
                 doc_id,
                 title,
                 document.access_level,
                 document.tags.clone(),
+                pdf_options,
+                self.pdf_password(doc_id).as_deref(),
+                self.runtime_config
+                    .dynamic()
+                    .limits
+                    .max_archive_uncompressed_bytes,
             ) {
-                Ok(chunks) => chunks,
+                Ok(processed) => processed,
                 Err(e) => {
                     error!(doc_id = %doc_id, error = %e, "Document text extraction failed");
                     if let Err(update_err) = self.db.update_document_processing_status(
@@ -129,14 +152,65 @@ impl SeneschalService {
                 }
             };
 
-            // Save chunks
-            for chunk in &chunks {
-                if let Err(e) = self.db.insert_chunk(chunk) {
-                    warn!(chunk_id = %chunk.id, error = %e, "Failed to save chunk");
+            // Record what was stripped so it can be reviewed
+            if let Err(e) = self.record_removed_furniture(doc_id, &processed.removed_furniture) {
+                warn!(doc_id = %doc_id, error = %e, "Failed to record removed page furniture");
+            }
+
+            // Save chunks (only those of changed pages for a new version)
+            let chunks = processed.chunks;
+            match pending_version {
+                Some(version) => match self.store_version_chunks(doc_id, version, &chunks) {
+                    Ok(changed_pages) => rechunked |= !changed_pages.is_empty(),
+                    Err(e) => {
+                        error!(doc_id = %doc_id, version, error = %e, "Failed to apply document version");
+                        let error_msg = format!("Failed to apply version {}: {}", version, e);
+                        if let Err(update_err) = self.db.update_document_processing_status(
+                            doc_id,
+                            ProcessingStatus::Failed,
+                            Some(&error_msg),
+                        ) {
+                            warn!(
+                                doc_id = %doc_id,
+                                original_error = %e,
+                                update_error = %update_err,
+                                "Failed to mark document as failed"
+                            );
+                        }
+                        self.broadcast_document_progress(
+                            doc_id,
+                            "failed",
+                            None,
+                            None,
+                            None,
+                            Some(&error_msg),
+                        );
+                        self.unregister_processing_token(doc_id);
+                        return;
+                    }
+                },
+                None => {
+                    for chunk in &chunks {
+                        if let Err(e) = self.db.insert_chunk(chunk) {
+                            warn!(chunk_id = %chunk.id, error = %e, "Failed to save chunk");
+                        }
+                    }
                 }
             }
+            // Apply page/section access overrides before chunks become searchable
+            if let Err(e) = self.db.apply_document_access_rules(doc_id) {
+                warn!(doc_id = %doc_id, error = %e, "Failed to apply access rules to chunks");
+            }
 
             info!(doc_id = %doc_id, chunks = chunks.len(), "Chunks created");
+
+            // Glossary entries are checked against the chunks on their source page
+            if let Err(e) = self
+                .db
+                .replace_document_glossary(doc_id, &processed.glossary)
+            {
+                warn!(doc_id = %doc_id, error = %e, "Failed to save glossary entries");
+            }
         } else {
             info!(doc_id = %doc_id, chunks = existing_chunk_count, "Chunks already exist, skipping text extraction");
         }
@@ -297,6 +371,60 @@ impl SeneschalService {
             info!(doc_id = %doc_id, "All chunks already have embeddings");
         }
 
+        // Refresh the centroid used to find related documents
+        if let Err(e) = self.db.update_document_centroid(doc_id) {
+            warn!(doc_id = %doc_id, error = %e, "Failed to update document centroid");
+        }
+
+        // Step 2b: Extract stat blocks and read-aloud text (the chunk cascade clears
+        // them when re-chunking, and a new version may have changed pages holding them)
+        if self.check_cancellation(doc_id, &cancel_token).is_err() {
+            info!(doc_id = %doc_id, "Document processing cancelled before stat block extraction");
+            self.unregister_processing_token(doc_id);
+            return;
+        }
+
+        match self.db.get_stat_block_count(doc_id) {
+            Ok(count) if count == 0 || rechunked => {
+                if let Err(e) = self.extract_document_stat_blocks(doc_id).await {
+                    warn!(doc_id = %doc_id, error = %format_error_chain_ref(&e), "Failed to extract stat blocks");
+                }
+            }
+            Ok(count) => {
+                info!(doc_id = %doc_id, stat_blocks = count, "Stat blocks already exist, skipping extraction");
+            }
+            Err(e) => {
+                debug!(doc_id = %doc_id, error = %e, "Failed to get stat block count");
+            }
+        }
+        if (rechunked
+            || self
+                .db
+                .get_read_aloud_count(doc_id)
+                .is_ok_and(|count| count == 0))
+            && let Err(e) = self.extract_document_read_aloud(doc_id, &file_path).await
+        {
+            warn!(doc_id = %doc_id, error = %format_error_chain_ref(&e), "Failed to extract read-aloud text");
+        }
+
+        // Step 2c: Summarize the document (again if it was just re-chunked)
+        if self.check_cancellation(doc_id, &cancel_token).is_err() {
+            info!(doc_id = %doc_id, "Document processing cancelled before summarization");
+            self.unregister_processing_token(doc_id);
+            return;
+        }
+
+        if rechunked || document.summary.is_none() {
+            if let Err(e) = self
+                .summarize_document(doc_id, &document.title, document.access_level)
+                .await
+            {
+                warn!(doc_id = %doc_id, error = %format_error_chain_ref(&e), "Failed to summarize document");
+            }
+        } else {
+            info!(doc_id = %doc_id, "Summary already exists, skipping summarization");
+        }
+
         // Step 3: Extract images from PDFs if not already done
         if self.check_cancellation(doc_id, &cancel_token).is_err() {
             info!(doc_id = %doc_id, "Document processing cancelled before image extraction");
@@ -318,6 +446,12 @@ impl SeneschalService {
             });
             let mut image_count = existing_images.len();
 
+            if image_count == 0 && !self.wait_for_disk_space(doc_id, &cancel_token).await {
+                info!(doc_id = %doc_id, "Document processing cancelled while waiting for disk space");
+                self.unregister_processing_token(doc_id);
+                return;
+            }
+
             if image_count == 0 {
                 info!(doc_id = %doc_id, "Extracting images from PDF");
                 if let Err(e) = self
@@ -334,7 +468,11 @@ impl SeneschalService {
                     Some(1),
                     None,
                 );
-                match self.ingestion.extract_pdf_images(&file_path, doc_id) {
+                match self.ingestion.extract_pdf_images(
+                    &file_path,
+                    doc_id,
+                    self.pdf_password(doc_id).as_deref(),
+                ) {
                     Ok(images) => {
                         image_count = images.len();
                         for image in &images {
@@ -381,6 +519,11 @@ impl SeneschalService {
             }
         }
 
+        // Extracted images start at the document's level; apply page/section overrides
+        if let Err(e) = self.db.apply_document_access_rules(doc_id) {
+            warn!(doc_id = %doc_id, error = %e, "Failed to apply access rules");
+        }
+
         // Update document with final counts and status
         let total_chunks = self.db.get_chunk_count(doc_id).unwrap_or_else(|e| {
             debug!(doc_id = %doc_id, error = %e, "Failed to get final chunk count");
@@ -419,4 +562,33 @@ impl SeneschalService {
             "Document processing complete"
         );
     }
+
+    /// Record the page furniture stripped from a document in its metadata.
+    ///
+    /// Reads the metadata fresh, since importers add their own keys right after upload.
+    fn record_removed_furniture(
+        &self,
+        doc_id: &str,
+        removed: &[PageFurniture],
+    ) -> ServiceResult<()> {
+        let Some(document) = self.db.get_document(doc_id)? else {
+            return Ok(());
+        };
+        let mut metadata = document.metadata.unwrap_or_else(|| serde_json::json!({}));
+        let Some(map) = metadata.as_object_mut() else {
+            return Ok(());
+        };
+        if removed.is_empty() {
+            if map.remove("page_furniture_removed").is_none() {
+                return Ok(());
+            }
+        } else {
+            map.insert(
+                "page_furniture_removed".to_string(),
+                serde_json::json!(removed),
+            );
+        }
+        self.db.update_document_metadata(doc_id, Some(metadata))?;
+        Ok(())
+    }
 }
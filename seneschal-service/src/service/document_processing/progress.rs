@@ -1,7 +1,7 @@
 //! WebSocket progress broadcast helpers.
 
 use crate::service::SeneschalService;
-use crate::websocket::{CaptioningProgressUpdate, DocumentProgressUpdate};
+use crate::websocket::{CaptioningProgressUpdate, DocumentProgressUpdate, UploadProgressUpdate};
 
 impl SeneschalService {
     /// Broadcast captioning progress via WebSocket
@@ -23,6 +23,27 @@ impl SeneschalService {
             });
     }
 
+    /// Broadcast the server-side progress of an HTTP upload via WebSocket
+    pub(crate) fn broadcast_upload_progress(
+        &self,
+        upload_token: &str,
+        stage: &str,
+        bytes: Option<u64>,
+        total: Option<u64>,
+        document_id: Option<&str>,
+        error: Option<&str>,
+    ) {
+        self.ws_manager
+            .broadcast_upload_update(UploadProgressUpdate {
+                upload_token: upload_token.to_string(),
+                stage: stage.to_string(),
+                bytes,
+                total,
+                document_id: document_id.map(String::from),
+                error: error.map(String::from),
+            });
+    }
+
     /// Broadcast document processing progress via WebSocket
     pub(crate) fn broadcast_document_progress(
         &self,
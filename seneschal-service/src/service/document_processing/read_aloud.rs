@@ -0,0 +1,121 @@
+//! Read-aloud text extraction.
+//!
+//! Candidates come from page layout for PDFs and from blockquotes and cues
+//! in other formats (see `ingestion::read_aloud`), and are confirmed by the
+//! default model, which weeds out sidebars, quotes and example dialogue.
+//! Each passage is kept verbatim and its chunk is tagged `read-aloud`.
+
+use std::path::Path;
+
+use chrono::Utc;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::db::{Chunk, ReadAloudPassage};
+use crate::error::{OllamaError, ServiceResult};
+use crate::ingestion::read_aloud::{detect_text_passages, passage_in};
+use crate::ollama::{ChatMessage, extract_json_object};
+use crate::service::{ModelTask, SeneschalService};
+
+/// LLM verdict on a read-aloud candidate
+#[derive(Debug, Deserialize)]
+struct ReadAloudValidation {
+    is_read_aloud: bool,
+}
+
+impl SeneschalService {
+    /// Detect and store read-aloud passages from a document, replacing any
+    /// existing ones.
+    ///
+    /// Returns the number of passages stored.
+    pub(crate) async fn extract_document_read_aloud(
+        &self,
+        document_id: &str,
+        file_path: &Path,
+    ) -> ServiceResult<usize> {
+        let chunks = self.db.get_document_chunks(document_id)?;
+        let is_pdf = file_path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("pdf"));
+
+        // (chunk, passage) pairs; PDF passages are matched back to their chunk
+        let candidates: Vec<(&Chunk, String)> = if is_pdf {
+            self.ingestion
+                .extract_pdf_read_aloud(file_path, self.pdf_password(document_id).as_deref())?
+                .into_iter()
+                .filter_map(|(page, passage)| {
+                    let chunk = chunks
+                        .iter()
+                        .find(|c| c.page_number == Some(page) && passage_in(&c.content, &passage));
+                    if chunk.is_none() {
+                        debug!(page, "No chunk holds read-aloud candidate");
+                    }
+                    chunk.map(|c| (c, passage))
+                })
+                .collect()
+        } else {
+            chunks
+                .iter()
+                .flat_map(|c| {
+                    detect_text_passages(&c.content)
+                        .into_iter()
+                        .map(move |p| (c, p))
+                })
+                .collect()
+        };
+
+        let mut passages = Vec::new();
+        for (chunk, text) in candidates {
+            let validated = match self.validate_read_aloud(&text).await {
+                Ok(true) => true,
+                Ok(false) => {
+                    debug!(page = ?chunk.page_number, "LLM rejected read-aloud candidate");
+                    continue;
+                }
+                // Keep the candidate unvalidated rather than losing it to a model outage
+                Err(e) => {
+                    warn!(page = ?chunk.page_number, error = %e, "Read-aloud validation failed");
+                    false
+                }
+            };
+
+            passages.push(ReadAloudPassage {
+                id: Uuid::new_v4().to_string(),
+                document_id: document_id.to_string(),
+                chunk_id: chunk.id.clone(),
+                page_number: chunk.page_number,
+                section_title: chunk.section_title.clone(),
+                text,
+                validated,
+                created_at: Utc::now(),
+            });
+        }
+
+        self.db
+            .replace_document_read_aloud(document_id, &passages)?;
+        info!(document_id = %document_id, passages = passages.len(), "Read-aloud text extracted");
+        Ok(passages.len())
+    }
+
+    /// Ask the model whether a candidate is text meant to be read to the players
+    async fn validate_read_aloud(&self, text: &str) -> ServiceResult<bool> {
+        let prompt = format!(
+            "The following passage was set apart (boxed, indented or italic) in a tabletop \
+            RPG adventure. Decide whether it is read-aloud text: narration the GM reads \
+            verbatim to the players to describe a scene, rather than a sidebar, rules note, \
+            quote, handout or example of play.\n\n{}\n\n\
+            Respond with only JSON: {{\"is_read_aloud\": true|false}}",
+            text
+        );
+
+        let response = self
+            .generate_routed(ModelTask::Chat, None, vec![ChatMessage::user(prompt)])
+            .await?;
+
+        let validation: ReadAloudValidation = serde_json::from_str(extract_json_object(&response))
+            .map_err(|e| OllamaError::InvalidResponse { source: e })?;
+
+        Ok(validation.is_read_aloud)
+    }
+}
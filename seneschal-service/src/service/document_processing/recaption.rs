@@ -0,0 +1,75 @@
+//! Re-captioning of already extracted images.
+
+use tracing::info;
+
+use super::captioning::CaptionPreset;
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+
+impl SeneschalService {
+    /// Queue images for re-captioning with a chosen vision model and prompt preset.
+    ///
+    /// Selects `image_ids` if given, otherwise every image in the page range (the whole
+    /// document when both bounds are None). Existing descriptions and embeddings are
+    /// cleared and the captioning worker regenerates them. Returns the number of images queued.
+    pub fn recaption_images(
+        &self,
+        document_id: &str,
+        image_ids: &[String],
+        start_page: Option<i32>,
+        end_page: Option<i32>,
+        vision_model: Option<String>,
+        preset: CaptionPreset,
+    ) -> ServiceResult<usize> {
+        let document =
+            self.db
+                .get_document(document_id)?
+                .ok_or_else(|| ServiceError::DocumentNotFound {
+                    document_id: document_id.to_string(),
+                })?;
+
+        let vision_model = vision_model
+            .filter(|m| !m.is_empty())
+            .or_else(|| self.captioning_model(&document))
+            .ok_or_else(|| ServiceError::Config {
+                message: "No vision model given and none configured".to_string(),
+            })?;
+
+        // Stop an in-flight captioning run so it doesn't mark the document complete
+        // before the newly cleared images are picked up
+        if self
+            .active_captioning
+            .iter()
+            .any(|entry| entry.value() == document_id)
+        {
+            self.cancel_document_processing(document_id);
+        }
+
+        let count =
+            self.db
+                .clear_image_descriptions(document_id, image_ids, start_page, end_page)?;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        // The worker reads the model and preset from metadata, keeping other keys intact
+        let mut metadata = document
+            .metadata
+            .filter(|m| m.is_object())
+            .unwrap_or_else(|| serde_json::json!({}));
+        metadata["vision_model"] = serde_json::Value::String(vision_model.clone());
+        metadata["caption_preset"] = serde_json::Value::String(preset.as_str().to_string());
+        self.db
+            .update_document_metadata(document_id, Some(metadata))?;
+        self.db.set_captioning_pending(document_id)?;
+
+        info!(
+            document_id = %document_id,
+            images = count,
+            model = %vision_model,
+            preset = preset.as_str(),
+            "Queued images for re-captioning"
+        );
+        Ok(count)
+    }
+}
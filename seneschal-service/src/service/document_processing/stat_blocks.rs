@@ -0,0 +1,116 @@
+//! NPC/creature stat block extraction.
+//!
+//! Candidates are found by the game system's parser (for Traveller, the
+//! regexes in `ingestion::statblocks`) and then confirmed by the default
+//! model, which weeds out rules examples and tables that merely mention
+//! characteristics. Systems without a parser get no stat blocks.
+
+use chrono::Utc;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::db::StatBlock;
+use crate::error::{OllamaError, ServiceResult};
+use crate::ingestion::statblocks::StatBlockCandidate;
+use crate::ollama::{ChatMessage, extract_json_object};
+use crate::service::{ModelTask, SeneschalService};
+use crate::system_profile::SystemProfile;
+
+/// LLM verdict on a stat block candidate
+#[derive(Debug, Deserialize)]
+struct StatBlockValidation {
+    is_stat_block: bool,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+impl SeneschalService {
+    /// Detect and store stat blocks from a document's chunks, replacing any existing ones.
+    ///
+    /// Returns the number of stat blocks stored.
+    pub(crate) async fn extract_document_stat_blocks(
+        &self,
+        document_id: &str,
+    ) -> ServiceResult<usize> {
+        // Each game system lays stat blocks out its own way, if it has a parser
+        let profile = self.document_system_profile(document_id);
+        let Some(detect_stat_blocks) = profile.stat_blocks else {
+            debug!(document_id = %document_id, system = %profile.id, "No stat block parser for game system");
+            return Ok(0);
+        };
+        let chunks = self.db.get_document_chunks(document_id)?;
+
+        let mut stat_blocks = Vec::new();
+        for chunk in &chunks {
+            for candidate in detect_stat_blocks(&chunk.content, chunk.section_title.as_deref()) {
+                let (validated, name) = match self.validate_stat_block(&candidate, profile).await {
+                    Ok(Some(name)) => (true, name),
+                    Ok(None) => {
+                        debug!(name = %candidate.name, "LLM rejected stat block candidate");
+                        continue;
+                    }
+                    // Keep the candidate unvalidated rather than losing it to a model outage
+                    Err(e) => {
+                        warn!(name = %candidate.name, error = %e, "Stat block validation failed");
+                        (false, candidate.name.clone())
+                    }
+                };
+
+                let mut data = candidate.data;
+                data["name"] = serde_json::Value::String(name.clone());
+                stat_blocks.push(StatBlock {
+                    id: Uuid::new_v4().to_string(),
+                    document_id: document_id.to_string(),
+                    chunk_id: chunk.id.clone(),
+                    name,
+                    kind: candidate.kind,
+                    page_number: chunk.page_number,
+                    data,
+                    raw_text: candidate.raw_text,
+                    validated,
+                    created_at: Utc::now(),
+                });
+            }
+        }
+
+        self.db
+            .replace_document_stat_blocks(document_id, &stat_blocks)?;
+        info!(document_id = %document_id, stat_blocks = stat_blocks.len(), "Stat blocks extracted");
+        Ok(stat_blocks.len())
+    }
+
+    /// Ask the model whether a candidate is a real stat block.
+    ///
+    /// Returns the (possibly corrected) name if it is, None if it isn't.
+    async fn validate_stat_block(
+        &self,
+        candidate: &StatBlockCandidate,
+        profile: &SystemProfile,
+    ) -> ServiceResult<Option<String>> {
+        let prompt = format!(
+            "The following text was extracted from a {} PDF. \
+            Decide whether it is a stat block for a single NPC or creature (not a rules \
+            example, table, or pre-generated character list), and give the character's \
+            or creature's proper name.\n\n\
+            Suggested name: {}\n\n{}\n\n\
+            Respond with only JSON: {{\"is_stat_block\": true|false, \"name\": \"...\"}}",
+            profile.name, candidate.name, candidate.raw_text
+        );
+
+        let response = self
+            .generate_routed(ModelTask::Chat, None, vec![ChatMessage::user(prompt)])
+            .await?;
+
+        let validation: StatBlockValidation = serde_json::from_str(extract_json_object(&response))
+            .map_err(|e| OllamaError::InvalidResponse { source: e })?;
+
+        Ok(validation.is_stat_block.then(|| {
+            validation
+                .name
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| candidate.name.clone())
+        }))
+    }
+}
@@ -0,0 +1,234 @@
+//! Per-document summaries and outlines.
+//!
+//! After chunking, the default model writes a short summary and a
+//! table-of-contents outline from the document's section headings and a
+//! sample of its text. The summary is embedded so documents can be ranked by
+//! topic without searching every chunk.
+//!
+//! Summaries are served to anyone who can see the document, so they are
+//! written only from chunks at or below the document's own access level;
+//! sections that page/section rules restrict further are left out. When
+//! those levels change the summary is cleared and written again.
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::db::Chunk;
+use crate::error::{OllamaError, ServiceResult, format_error_chain_ref};
+use crate::ollama::{ChatMessage, extract_json_object};
+use crate::service::{ModelTask, SeneschalService};
+use crate::tools::AccessLevel;
+
+/// Section headings beyond this are left out of the prompt
+const MAX_HEADINGS: usize = 200;
+
+/// Chunks sampled evenly through the document for the prompt
+const SAMPLE_CHUNKS: usize = 8;
+
+/// Characters taken from each sampled chunk
+const SAMPLE_CHARS_PER_CHUNK: usize = 1500;
+
+/// Summary and outline as produced by the model
+#[derive(Debug, Deserialize)]
+struct DocumentAbstract {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    outline: Vec<String>,
+}
+
+impl SeneschalService {
+    /// Clear a document's summary and write it again in the background, after
+    /// its title or the access levels it was written under changed
+    pub fn refresh_document_summary(service: Arc<SeneschalService>, document_id: &str) {
+        if let Err(e) = service.db.clear_document_summary(document_id) {
+            warn!(doc_id = %document_id, error = %e, "Failed to clear stale document summary");
+            return;
+        }
+        let document_id = document_id.to_string();
+        tokio::spawn(async move {
+            let document = match service.db.get_document(&document_id) {
+                Ok(Some(document)) => document,
+                Ok(None) => return,
+                Err(e) => {
+                    warn!(doc_id = %document_id, error = %e, "Failed to load document for summary");
+                    return;
+                }
+            };
+            if let Err(e) = service
+                .summarize_document(&document_id, &document.title, document.access_level)
+                .await
+            {
+                warn!(doc_id = %document_id, error = %format_error_chain_ref(&e), "Failed to summarize document");
+            }
+        });
+    }
+
+    /// Generate and store a summary, outline and summary embedding for a document.
+    pub(crate) async fn summarize_document(
+        &self,
+        document_id: &str,
+        title: &str,
+        access_level: AccessLevel,
+    ) -> ServiceResult<()> {
+        let chunks = visible_chunks(self.db.get_document_chunks(document_id)?, access_level);
+        if chunks.is_empty() {
+            self.db.clear_document_summary(document_id)?;
+            return Ok(());
+        }
+
+        let headings = section_headings(&chunks);
+        let prompt = format!(
+            "Below are the section headings and excerpts of \"{}\", a document in a {} \
+            GM's reference library.\n\n\
+            ## Section headings\n{}\n\n\
+            ## Excerpts\n{}\n\n\
+            Write a 2-4 sentence summary of what the document covers and who would use it, \
+            and an outline of its major parts (at most 20 entries, in order).\n\
+            Respond with only JSON in this shape: \
+            {{\"summary\": \"...\", \"outline\": [\"...\"]}}",
+            title,
+            self.document_system_profile(document_id).name,
+            if headings.is_empty() {
+                "(none)".to_string()
+            } else {
+                headings.join("\n")
+            },
+            sample_text(&chunks)
+        );
+
+        let response = self
+            .generate_routed(
+                ModelTask::Summarization,
+                None,
+                vec![ChatMessage::user(prompt)],
+            )
+            .await?;
+        let parsed: DocumentAbstract = serde_json::from_str(extract_json_object(&response))
+            .map_err(|e| OllamaError::InvalidResponse { source: e })?;
+
+        let summary = parsed.summary.trim();
+        if summary.is_empty() {
+            debug!(doc_id = %document_id, "Model returned an empty document summary");
+            return Ok(());
+        }
+        let outline: Vec<String> = parsed
+            .outline
+            .into_iter()
+            .map(|entry| entry.trim().to_string())
+            .filter(|entry| !entry.is_empty())
+            .collect();
+
+        let embedding = self
+            .search
+            .embed_text(&format!("{}\n{}", title, summary))
+            .await?;
+        self.db
+            .update_document_summary(document_id, summary, &outline, &embedding)?;
+
+        info!(doc_id = %document_id, outline = outline.len(), "Document summary generated");
+        Ok(())
+    }
+}
+
+/// Chunks anyone who can see the document can also see
+fn visible_chunks(chunks: Vec<Chunk>, access_level: AccessLevel) -> Vec<Chunk> {
+    chunks
+        .into_iter()
+        .filter(|chunk| chunk.access_level.accessible_by(access_level as u8))
+        .collect()
+}
+
+/// Distinct section headings in document order
+fn section_headings(chunks: &[Chunk]) -> Vec<&str> {
+    let mut headings: Vec<&str> = Vec::new();
+    for title in chunks.iter().filter_map(|c| c.section_title.as_deref()) {
+        if !headings.contains(&title) {
+            headings.push(title);
+            if headings.len() == MAX_HEADINGS {
+                break;
+            }
+        }
+    }
+    headings
+}
+
+/// The start of chunks spread evenly through the document
+fn sample_text(chunks: &[Chunk]) -> String {
+    let step = chunks.len().div_ceil(SAMPLE_CHUNKS).max(1);
+    chunks
+        .iter()
+        .step_by(step)
+        .map(|chunk| {
+            let excerpt: String = chunk.content.chars().take(SAMPLE_CHARS_PER_CHUNK).collect();
+            match chunk.page_number {
+                Some(page) => format!("[page {}]\n{}", page, excerpt),
+                None => excerpt,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(index: i32, section: &str) -> Chunk {
+        Chunk {
+            id: index.to_string(),
+            document_id: "doc".to_string(),
+            content: format!("Text of chunk {}", index),
+            chunk_index: index,
+            page_number: Some(index + 1),
+            section_title: Some(section.to_string()),
+            access_level: AccessLevel::GmOnly,
+            tags: vec![],
+            metadata: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_prompt_material() {
+        let chunks: Vec<Chunk> = (0..20)
+            .map(|i| chunk(i, if i < 10 { "Careers" } else { "Equipment" }))
+            .collect();
+
+        assert_eq!(section_headings(&chunks), vec!["Careers", "Equipment"]);
+
+        let sample = sample_text(&chunks);
+        assert_eq!(sample.matches("[page ").count(), 7);
+        assert!(sample.starts_with("[page 1]\nText of chunk 0"));
+        assert!(sample.contains("[page 19]\nText of chunk 18"));
+    }
+
+    #[test]
+    fn test_gm_only_sections_left_out() {
+        let mut chunks: Vec<Chunk> = (0..6)
+            .map(|i| {
+                chunk(
+                    i,
+                    if i < 4 {
+                        "Player Guide"
+                    } else {
+                        "Referee Secrets"
+                    },
+                )
+            })
+            .collect();
+        for chunk in &mut chunks[..4] {
+            chunk.access_level = AccessLevel::Player;
+        }
+
+        let visible = visible_chunks(chunks.clone(), AccessLevel::Player);
+        assert_eq!(visible.len(), 4);
+        assert_eq!(section_headings(&visible), vec!["Player Guide"]);
+        assert!(!sample_text(&visible).contains("chunk 4"));
+
+        // A GM-only document's summary is only served to GMs
+        assert_eq!(visible_chunks(chunks, AccessLevel::GmOnly).len(), 6);
+    }
+}
@@ -1,19 +1,31 @@
-//! Document upload and hash backfill functionality.
+//! Document upload, re-ingest and hash backfill functionality.
+
+use std::path::{Path, PathBuf};
 
 use tracing::{debug, info, warn};
 
 use crate::db::{CaptioningStatus, Document, ProcessingStatus};
 use crate::error::{ServiceError, ServiceResult};
-use crate::ingestion::hash::compute_content_hash;
+use crate::ingestion::hash::{compute_content_hash, compute_content_hash_with_progress};
+use crate::ingestion::pdf::layout::ColumnLayout;
+use crate::ingestion::pdf::page_render::pdf_page_count;
+use crate::ingestion::validation::{UploadRules, ValidatedUpload, validate_upload};
 use crate::service::SeneschalService;
 use crate::tools::AccessLevel;
 
+/// Uploads at least this large report hashing progress
+const HASH_PROGRESS_MIN_BYTES: usize = 64 * 1024 * 1024;
+
+/// Bytes hashed between hashing progress updates
+const HASH_PROGRESS_BLOCK_BYTES: usize = 16 * 1024 * 1024;
+
 impl SeneschalService {
     /// Upload a document and enqueue it for processing
     ///
     /// This method saves the file and creates a document record with "processing"
     /// status. The document processing worker will pick it up and process it.
     /// Clients should poll the document status for completion.
+    #[allow(clippy::too_many_arguments)]
     pub async fn upload_document(
         &self,
         content: &[u8],
@@ -22,20 +34,73 @@ impl SeneschalService {
         access_level: AccessLevel,
         tags: Vec<String>,
         vision_model: Option<String>,
+        strip_page_furniture: Option<bool>,
+        pdf_layout: Option<ColumnLayout>,
     ) -> ServiceResult<Document> {
-        // Check file size
-        let max_size = self.runtime_config.dynamic().limits.max_document_size_bytes;
-        if content.len() as u64 > max_size {
-            return Err(ServiceError::Processing(
-                crate::error::ProcessingError::FileTooLarge {
-                    size: content.len() as u64,
-                    max: max_size,
-                },
-            ));
-        }
+        self.upload_document_with_progress(
+            content,
+            filename,
+            title,
+            access_level,
+            tags,
+            vision_model,
+            strip_page_furniture,
+            pdf_layout,
+            None,
+            None,
+        )
+        .await
+    }
 
-        // Compute content hash for duplicate detection
-        let file_hash = compute_content_hash(content);
+    /// Upload a document like [`Self::upload_document`], broadcasting its
+    /// hashing, validated and queued stages under the client's upload token.
+    /// `pdf_password` opens an encrypted PDF.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upload_document_with_progress(
+        &self,
+        content: &[u8],
+        filename: &str,
+        title: &str,
+        access_level: AccessLevel,
+        tags: Vec<String>,
+        vision_model: Option<String>,
+        strip_page_furniture: Option<bool>,
+        pdf_layout: Option<ColumnLayout>,
+        pdf_password: Option<&str>,
+        upload_token: Option<&str>,
+    ) -> ServiceResult<Document> {
+        self.check_document_size(content)?;
+        self.ensure_disk_space(content.len() as u64)?;
+        let validated = self.validate_document_file(filename, content, pdf_password)?;
+
+        // Compute content hash for duplicate detection, reporting progress
+        // on files large enough for it to take noticeable time
+        let total = content.len() as u64;
+        let file_hash = match upload_token {
+            Some(token) if content.len() >= HASH_PROGRESS_MIN_BYTES => {
+                compute_content_hash_with_progress(content, HASH_PROGRESS_BLOCK_BYTES, |hashed| {
+                    self.broadcast_upload_progress(
+                        token,
+                        "hashing",
+                        Some(hashed as u64),
+                        Some(total),
+                        None,
+                        None,
+                    );
+                })
+            }
+            _ => compute_content_hash(content),
+        };
+        if let Some(token) = upload_token {
+            self.broadcast_upload_progress(
+                token,
+                "validated",
+                Some(total),
+                Some(total),
+                None,
+                None,
+            );
+        }
 
         // Generate document ID
         let doc_id = uuid::Uuid::new_v4().to_string();
@@ -54,8 +119,21 @@ impl SeneschalService {
         std::fs::write(&permanent_path, content)
             .map_err(|e| ServiceError::Processing(crate::error::ProcessingError::Io(e)))?;
 
-        // Store vision model in metadata if provided
-        let metadata = vision_model.map(|vm| serde_json::json!({ "vision_model": vm }));
+        // Store per-document processing options in metadata if provided
+        let mut options = serde_json::Map::new();
+        if let Some(vm) = vision_model {
+            options.insert("vision_model".to_string(), serde_json::json!(vm));
+        }
+        if let Some(strip) = strip_page_furniture {
+            options.insert("strip_page_furniture".to_string(), serde_json::json!(strip));
+        }
+        if let Some(layout) = pdf_layout {
+            options.insert("pdf_layout".to_string(), serde_json::json!(layout));
+        }
+        if validated.encrypted {
+            options.insert("encrypted".to_string(), serde_json::json!(true));
+        }
+        let metadata = (!options.is_empty()).then_some(serde_json::Value::Object(options));
 
         // Create document record with "processing" status
         let now = chrono::Utc::now();
@@ -78,12 +156,29 @@ impl SeneschalService {
             captioning_error: None,
             captioning_progress: None,
             captioning_total: None,
+            summary: None,
+            outline: None,
+            world_id: None,
+            priority: 0,
             created_at: now,
             updated_at: now,
         };
 
         // Save document to database (enqueue for processing)
+        if validated.encrypted {
+            self.remember_pdf_password(&doc_id, pdf_password);
+        }
         self.db.insert_document(&document)?;
+        if let Some(token) = upload_token {
+            self.broadcast_upload_progress(
+                token,
+                "queued",
+                Some(total),
+                Some(total),
+                Some(&document.id),
+                None,
+            );
+        }
 
         info!(
             doc_id = %doc_id,
@@ -94,6 +189,148 @@ impl SeneschalService {
         Ok(document)
     }
 
+    /// Replace a document's source file and queue it for processing again.
+    ///
+    /// The document keeps its ID, so references to it stay valid. Its chunks
+    /// (and the stat blocks cut from them) are cleared and rebuilt by the
+    /// processing worker.
+    pub fn reingest_document(
+        &self,
+        document_id: &str,
+        content: &[u8],
+        tags: Vec<String>,
+        metadata: Option<serde_json::Value>,
+    ) -> ServiceResult<()> {
+        self.check_document_size(content)?;
+        self.ensure_disk_space(content.len() as u64)?;
+
+        let document =
+            self.db
+                .get_document(document_id)?
+                .ok_or_else(|| ServiceError::DocumentNotFound {
+                    document_id: document_id.to_string(),
+                })?;
+        let file_path = document.source_file()?;
+
+        if self.cancel_document_processing(document_id) {
+            info!(doc_id = %document_id, "Cancelled in-progress processing for re-ingest");
+        }
+
+        std::fs::write(file_path, content)
+            .map_err(|e| ServiceError::Processing(crate::error::ProcessingError::Io(e)))?;
+        self.db
+            .update_document_hash(document_id, &compute_content_hash(content))?;
+        self.db
+            .update_document(document_id, &document.title, document.access_level, tags)?;
+        self.db.update_document_metadata(document_id, metadata)?;
+        self.db.delete_document_chunks(document_id)?;
+        self.db.update_document_processing_status(
+            document_id,
+            ProcessingStatus::Processing,
+            None,
+        )?;
+        self.db
+            .update_document_progress(document_id, "queued", 0, 0)?;
+
+        info!(doc_id = %document_id, title = %document.title, "Document re-queued for processing");
+        Ok(())
+    }
+
+    /// Queue a document for processing again from its stored source file,
+    /// e.g. after changing the embedding model or chunking settings
+    pub fn reindex_document(&self, document_id: &str) -> ServiceResult<()> {
+        let document =
+            self.db
+                .get_document(document_id)?
+                .ok_or_else(|| ServiceError::DocumentNotFound {
+                    document_id: document_id.to_string(),
+                })?;
+        let content = std::fs::read(self.readable_source(&document)?)
+            .map_err(|e| ServiceError::Processing(crate::error::ProcessingError::Io(e)))?;
+        self.reingest_document(document_id, &content, document.tags, document.metadata)
+    }
+
+    pub(super) fn check_document_size(&self, content: &[u8]) -> ServiceResult<()> {
+        let max_size = self.runtime_config.dynamic().limits.max_document_size_bytes;
+        if content.len() as u64 > max_size {
+            return Err(ServiceError::Processing(
+                crate::error::ProcessingError::FileTooLarge {
+                    size: content.len() as u64,
+                    max: max_size,
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check an uploaded file's content against the allowed document types
+    /// and archive limits before it is stored
+    pub fn validate_document_file(
+        &self,
+        filename: &str,
+        content: &[u8],
+        pdf_password: Option<&str>,
+    ) -> ServiceResult<ValidatedUpload> {
+        let limits = &self.runtime_config.dynamic().limits;
+        let rules = UploadRules::new(
+            &limits.allowed_document_types,
+            limits.max_archive_uncompressed_bytes,
+        );
+        Ok(validate_upload(filename, content, &rules, pdf_password)?)
+    }
+
+    /// Keep an encrypted PDF's password for processing and page rendering.
+    /// It is never written to disk: after a restart, auto-imported documents
+    /// get theirs back from the import config, and others need it supplied
+    /// again with [`Self::supply_pdf_password`].
+    pub(crate) fn remember_pdf_password(&self, document_id: &str, password: Option<&str>) {
+        if let Some(password) = password {
+            self.pdf_passwords
+                .insert(document_id.to_string(), password.to_string());
+        }
+    }
+
+    /// Password to open a document's PDF with, if it is encrypted
+    pub(crate) fn pdf_password(&self, document_id: &str) -> Option<String> {
+        self.pdf_passwords
+            .get(document_id)
+            .map(|password| password.clone())
+    }
+
+    /// Path of a document's source file once it can be read: it hasn't
+    /// expired, and the password of an encrypted PDF is known
+    pub(crate) fn readable_source(&self, document: &Document) -> ServiceResult<PathBuf> {
+        let path = PathBuf::from(document.source_file()?);
+        if document.is_encrypted() && !self.pdf_passwords.contains_key(&document.id) {
+            return Err(ServiceError::PdfPasswordRequired {
+                document_id: document.id.clone(),
+            });
+        }
+        Ok(path)
+    }
+
+    /// Supply the password of an encrypted PDF again, e.g. after a restart.
+    ///
+    /// The password is checked against the source file. This is blocking
+    /// work and should be run off the async runtime.
+    pub fn supply_pdf_password(&self, document_id: &str, password: &str) -> ServiceResult<()> {
+        let document =
+            self.db
+                .get_document(document_id)?
+                .ok_or_else(|| ServiceError::DocumentNotFound {
+                    document_id: document_id.to_string(),
+                })?;
+        if !document.is_encrypted() {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Document {} is not an encrypted PDF", document_id),
+            });
+        }
+        pdf_page_count(Path::new(document.source_file()?), Some(password))?;
+        self.remember_pdf_password(document_id, Some(password));
+        info!(doc_id = %document_id, "PDF password supplied");
+        Ok(())
+    }
+
     /// Backfill file_hash for existing documents that don't have one.
     ///
     /// This runs once on startup to populate hashes for documents uploaded
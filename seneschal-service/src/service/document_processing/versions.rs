@@ -0,0 +1,302 @@
+//! Document versions and diff-aware re-ingestion.
+//!
+//! A new version's text is chunked as usual, then hashed page by page and
+//! compared with the pages of the indexed chunks. Only changed pages are
+//! replaced (and so re-embedded); the chunks they replace are superseded
+//! rather than deleted, so earlier versions stay readable.
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::db::{Chunk, Document, DocumentVersion, PageHash, ProcessingStatus};
+use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::hash::{compute_chunk_hash, compute_content_hash};
+use crate::service::SeneschalService;
+
+/// Changes needed to bring the indexed chunks up to a new version
+struct PageDiff {
+    page_hashes: Vec<PageHash>,
+    changed_pages: Vec<Option<i32>>,
+    /// Extracted chunks on changed pages
+    new_chunks: Vec<Chunk>,
+    /// Indexed chunks on unchanged pages, with their index in the new version
+    renumbered: Vec<(String, i32)>,
+}
+
+impl SeneschalService {
+    /// Upload a new version of a document's source file and queue it for processing.
+    ///
+    /// The first time a document gets a new version, its current file is
+    /// recorded as version 1.
+    pub fn upload_document_version(
+        &self,
+        document_id: &str,
+        content: &[u8],
+        filename: &str,
+        pdf_password: Option<&str>,
+    ) -> ServiceResult<DocumentVersion> {
+        self.check_document_size(content)?;
+        let validated = self.validate_document_file(filename, content, pdf_password)?;
+
+        let document =
+            self.db
+                .get_document(document_id)?
+                .ok_or_else(|| ServiceError::DocumentNotFound {
+                    document_id: document_id.to_string(),
+                })?;
+        if document.processing_status == ProcessingStatus::Processing {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Document {} is still being processed", document_id),
+            });
+        }
+        let file_hash = compute_content_hash(content);
+        if document.file_hash.as_deref() == Some(file_hash.as_str()) {
+            return Err(ServiceError::InvalidRequest {
+                message: "The file is identical to the current version".to_string(),
+            });
+        }
+
+        let mut versions = self.db.list_document_versions(document_id)?;
+        if versions.is_empty() {
+            let original = DocumentVersion {
+                document_id: document_id.to_string(),
+                version: 1,
+                file_path: document.source_file()?.to_string(),
+                file_hash: document.file_hash.clone().unwrap_or_default(),
+                page_hashes: Some(page_hashes(&self.db.get_document_chunks(document_id)?)),
+                changed_pages: None,
+                created_at: document.created_at,
+            };
+            self.db.insert_document_version(&original)?;
+            versions.push(original);
+        }
+        let number = versions.last().map_or(1, |v| v.version) + 1;
+
+        let docs_dir = self
+            .runtime_config
+            .static_config
+            .storage
+            .data_dir
+            .join("documents");
+        std::fs::create_dir_all(&docs_dir)
+            .map_err(|e| ServiceError::Processing(crate::error::ProcessingError::Io(e)))?;
+        let path = docs_dir.join(format!("{}_v{}_{}", document_id, number, filename));
+        std::fs::write(&path, content)
+            .map_err(|e| ServiceError::Processing(crate::error::ProcessingError::Io(e)))?;
+
+        let version = DocumentVersion {
+            document_id: document_id.to_string(),
+            version: number,
+            file_path: path.to_string_lossy().to_string(),
+            file_hash,
+            page_hashes: None,
+            changed_pages: None,
+            created_at: Utc::now(),
+        };
+        self.db.insert_document_version(&version)?;
+        self.db
+            .update_document_file(document_id, &version.file_path, &version.file_hash)?;
+        self.record_pdf_encryption(&document, validated.encrypted, pdf_password)?;
+        self.db.update_document_processing_status(
+            document_id,
+            ProcessingStatus::Processing,
+            None,
+        )?;
+        self.db
+            .update_document_progress(document_id, "queued", 0, 0)?;
+
+        info!(
+            doc_id = %document_id,
+            version = number,
+            "Document version uploaded and queued for processing"
+        );
+        Ok(version)
+    }
+
+    /// Update a document's encrypted flag for a new source file, keeping the
+    /// password of an encrypted one
+    fn record_pdf_encryption(
+        &self,
+        document: &Document,
+        encrypted: bool,
+        password: Option<&str>,
+    ) -> ServiceResult<()> {
+        let was_encrypted = document.is_encrypted();
+        if encrypted {
+            self.remember_pdf_password(&document.id, password);
+        } else {
+            self.pdf_passwords.remove(&document.id);
+        }
+        if encrypted == was_encrypted {
+            return Ok(());
+        }
+
+        let mut metadata = match &document.metadata {
+            Some(serde_json::Value::Object(map)) => map.clone(),
+            _ => serde_json::Map::new(),
+        };
+        if encrypted {
+            metadata.insert("encrypted".to_string(), serde_json::json!(true));
+        } else {
+            metadata.remove("encrypted");
+        }
+        let metadata = (!metadata.is_empty()).then_some(serde_json::Value::Object(metadata));
+        self.db.update_document_metadata(&document.id, metadata)?;
+        Ok(())
+    }
+
+    /// Replace the indexed chunks of pages that changed in a new version with
+    /// the version's extracted chunks. Returns the changed pages.
+    pub(super) fn store_version_chunks(
+        &self,
+        document_id: &str,
+        version: u32,
+        extracted: &[Chunk],
+    ) -> ServiceResult<Vec<Option<i32>>> {
+        let indexed = self.db.get_document_chunks(document_id)?;
+        let diff = diff_pages(&indexed, extracted);
+        self.db.apply_document_version(
+            document_id,
+            version,
+            &diff.page_hashes,
+            &diff.changed_pages,
+            &diff.new_chunks,
+            &diff.renumbered,
+        )?;
+
+        info!(
+            doc_id = %document_id,
+            version,
+            changed_pages = diff.changed_pages.len(),
+            new_chunks = diff.new_chunks.len(),
+            "Document version applied"
+        );
+        Ok(diff.changed_pages)
+    }
+}
+
+/// Chunks grouped by page, in chunk order
+fn chunks_by_page(chunks: &[Chunk]) -> BTreeMap<Option<i32>, Vec<&Chunk>> {
+    let mut pages: BTreeMap<Option<i32>, Vec<&Chunk>> = BTreeMap::new();
+    for chunk in chunks {
+        pages.entry(chunk.page_number).or_default().push(chunk);
+    }
+    for page in pages.values_mut() {
+        page.sort_by_key(|c| c.chunk_index);
+    }
+    pages
+}
+
+fn page_hash(chunks: &[&Chunk]) -> String {
+    let text: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+    compute_chunk_hash(&text.join("\n"))
+}
+
+/// Hash of each page's chunk text, in page order
+fn page_hashes(chunks: &[Chunk]) -> Vec<PageHash> {
+    chunks_by_page(chunks)
+        .into_iter()
+        .map(|(page, chunks)| PageHash {
+            page,
+            hash: page_hash(&chunks),
+        })
+        .collect()
+}
+
+/// Compare indexed chunks with those extracted from a new version, page by page
+fn diff_pages(indexed: &[Chunk], extracted: &[Chunk]) -> PageDiff {
+    let old_pages = chunks_by_page(indexed);
+    let new_pages = chunks_by_page(extracted);
+
+    let mut diff = PageDiff {
+        page_hashes: Vec::with_capacity(new_pages.len()),
+        changed_pages: Vec::new(),
+        new_chunks: Vec::new(),
+        renumbered: Vec::new(),
+    };
+    for (page, new_chunks) in &new_pages {
+        let hash = page_hash(new_chunks);
+        let unchanged = old_pages.get(page).filter(|old_chunks| {
+            old_chunks.len() == new_chunks.len() && page_hash(old_chunks) == hash
+        });
+        match unchanged {
+            Some(old_chunks) => diff.renumbered.extend(
+                old_chunks
+                    .iter()
+                    .zip(new_chunks)
+                    .filter(|(old, new)| old.chunk_index != new.chunk_index)
+                    .map(|(old, new)| (old.id.clone(), new.chunk_index)),
+            ),
+            None => {
+                diff.changed_pages.push(*page);
+                diff.new_chunks
+                    .extend(new_chunks.iter().map(|&chunk| chunk.clone()));
+            }
+        }
+        diff.page_hashes.push(PageHash { page: *page, hash });
+    }
+    // Pages the new version no longer has
+    diff.changed_pages.extend(
+        old_pages
+            .keys()
+            .filter(|page| !new_pages.contains_key(page))
+            .copied(),
+    );
+    diff.changed_pages.sort();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::AccessLevel;
+
+    fn chunk(id: &str, chunk_index: i32, page: i32, content: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            document_id: "doc".to_string(),
+            content: content.to_string(),
+            chunk_index,
+            page_number: Some(page),
+            section_title: None,
+            access_level: AccessLevel::Player,
+            tags: vec![],
+            metadata: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_diff_pages() {
+        let indexed = [
+            chunk("a", 0, 1, "Jump drives"),
+            chunk("b", 1, 2, "Fuel costs Cr500 per ton"),
+            chunk("c", 2, 3, "Errata"),
+            chunk("d", 3, 4, "Starports"),
+            chunk("e", 4, 4, "Highports"),
+        ];
+        // Page 1 is only rewrapped, page 2 corrected and expanded, page 3 dropped
+        let extracted = [
+            chunk("w", 0, 1, "Jump\ndrives"),
+            chunk("x", 1, 2, "Fuel costs Cr100 per ton"),
+            chunk("y", 2, 2, "Refined fuel"),
+            chunk("z", 3, 2, "Unrefined fuel"),
+            chunk("u", 4, 4, "Starports"),
+            chunk("v", 5, 4, "Highports"),
+        ];
+
+        let diff = diff_pages(&indexed, &extracted);
+        assert_eq!(diff.changed_pages, vec![Some(2), Some(3)]);
+        let new_ids: Vec<&str> = diff.new_chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(new_ids, ["x", "y", "z"]);
+        assert_eq!(
+            diff.renumbered,
+            vec![("d".to_string(), 4), ("e".to_string(), 5)]
+        );
+        let pages: Vec<Option<i32>> = diff.page_hashes.iter().map(|h| h.page).collect();
+        assert_eq!(pages, [Some(1), Some(2), Some(4)]);
+        assert_eq!(page_hashes(&indexed)[0], diff.page_hashes[0]);
+    }
+}
@@ -2,10 +2,25 @@
 
 use std::sync::Arc;
 
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+
 use tracing::{error, info};
 
 use crate::service::SeneschalService;
 
+/// Frees a vision model's captioning queue when dropped, even if captioning panics
+struct CaptioningSlot {
+    active: Arc<DashMap<String, String>>,
+    model: String,
+}
+
+impl Drop for CaptioningSlot {
+    fn drop(&mut self) {
+        self.active.remove(&self.model);
+    }
+}
+
 impl SeneschalService {
     /// Start the document processing worker
     /// This should be called once on server startup
@@ -33,19 +48,37 @@ impl SeneschalService {
     }
 
     /// Start the image captioning worker
-    /// This runs as a separate background task to caption document images without blocking document processing
+    /// This runs as a separate background task to caption document images without blocking document processing.
+    /// Each vision model has its own queue: documents using different models are captioned in parallel,
+    /// documents sharing a model wait their turn.
     pub fn start_captioning_worker(service: Arc<SeneschalService>) {
         tokio::spawn(async move {
             info!("Image captioning worker started");
             loop {
                 // Check for documents pending captioning
-                match service.db.get_next_pending_captioning_document() {
-                    Ok(Some(doc)) => {
-                        info!(doc_id = %doc.id, title = %doc.title, "Captioning images for document");
-                        service.caption_document_images(&doc).await;
-                    }
-                    Ok(None) => {
-                        // No pending captioning, sleep before checking again
+                match service.db.get_pending_captioning_documents() {
+                    Ok(docs) => {
+                        for doc in docs {
+                            let model = service.captioning_model(&doc).unwrap_or_default();
+                            let Entry::Vacant(slot) =
+                                service.active_captioning.entry(model.clone())
+                            else {
+                                // This model's queue is busy (possibly with this document)
+                                continue;
+                            };
+                            slot.insert(doc.id.clone());
+
+                            info!(doc_id = %doc.id, title = %doc.title, model = %model, "Captioning images for document");
+                            let claimed = CaptioningSlot {
+                                active: service.active_captioning.clone(),
+                                model,
+                            };
+                            let service = service.clone();
+                            tokio::spawn(async move {
+                                service.caption_document_images(&doc).await;
+                                drop(claimed);
+                            });
+                        }
                         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                     }
                     Err(e) => {
@@ -0,0 +1,56 @@
+//! Animal encounter tables per world.
+//!
+//! Tables are generated from the world's UWP the first time they're asked
+//! for and saved per campaign, so the same world keeps the same animals
+//! from session to session. They're regenerated when the UWP they were
+//! built for changes, or on request.
+
+use tracing::{info, warn};
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+use crate::tools::traveller::decode_uwp;
+use crate::tools::traveller_encounters::{WorldEncounterTables, generate_encounter_tables};
+
+impl SeneschalService {
+    /// A world's encounter tables, and whether they came from the cache
+    pub fn encounter_tables(
+        &self,
+        world: &str,
+        uwp: &str,
+        seed: Option<u64>,
+        regenerate: bool,
+    ) -> ServiceResult<(WorldEncounterTables, bool)> {
+        let world = world.trim();
+        if world.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "A world name is required".to_string(),
+            });
+        }
+        let parsed = decode_uwp(uwp).map_err(|message| ServiceError::InvalidRequest { message })?;
+        let campaign = self.mcp_world_id().unwrap_or_default();
+        let world_key = world.to_lowercase();
+
+        if !regenerate
+            && seed.is_none()
+            && let Some((cached_uwp, json)) = self.db.get_encounter_tables(&campaign, &world_key)?
+            && cached_uwp == parsed.raw
+        {
+            match serde_json::from_str(&json) {
+                Ok(tables) => return Ok((tables, true)),
+                Err(e) => {
+                    warn!(world = %world, error = %e, "Discarding unreadable encounter tables")
+                }
+            }
+        }
+
+        let tables = generate_encounter_tables(world, &parsed, seed);
+        let json = serde_json::to_string(&tables).map_err(|e| ServiceError::Internal {
+            message: format!("Failed to serialize encounter tables: {}", e),
+        })?;
+        self.db
+            .save_encounter_tables(&campaign, &world_key, &parsed.raw, &json)?;
+        info!(world = %world, uwp = %parsed.raw, tables = tables.tables.len(), "Encounter tables generated");
+        Ok((tables, false))
+    }
+}
@@ -0,0 +1,230 @@
+//! Errata links between documents.
+//!
+//! Search results from corrected pages are flagged with the errata that
+//! supersedes them and ranked below uncorrected text, while results from the
+//! errata itself are ranked above it, so the model reads the corrected rule
+//! first and is told not to quote the original.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::db::Errata;
+use crate::error::{ServiceError, ServiceResult};
+use crate::search::{ErrataStatus, SearchResult};
+use crate::service::SeneschalService;
+
+/// Ranking adjustment for errata text (added) and the text it supersedes (subtracted)
+const ERRATA_RANK_ADJUSTMENT: f32 = 0.05;
+
+impl SeneschalService {
+    /// Mark a document, or a page range of one, as errata for a page range of another
+    pub fn add_errata(
+        &self,
+        errata_document_id: &str,
+        errata_pages: (Option<i32>, Option<i32>),
+        target_document_id: &str,
+        target_pages: (Option<i32>, Option<i32>),
+        issued: Option<String>,
+        note: Option<String>,
+    ) -> ServiceResult<Errata> {
+        if errata_document_id == target_document_id {
+            return Err(ServiceError::InvalidRequest {
+                message: "A document can't be errata for itself".to_string(),
+            });
+        }
+        for (start, end) in [errata_pages, target_pages] {
+            if let (Some(start), Some(end)) = (start, end)
+                && start > end
+            {
+                return Err(ServiceError::InvalidRequest {
+                    message: format!("Invalid page range: {} > {}", start, end),
+                });
+            }
+        }
+        for document_id in [errata_document_id, target_document_id] {
+            if self.db.get_document(document_id)?.is_none() {
+                return Err(ServiceError::DocumentNotFound {
+                    document_id: document_id.to_string(),
+                });
+            }
+        }
+
+        let trimmed = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let errata = Errata {
+            id: Uuid::new_v4().to_string(),
+            errata_document_id: errata_document_id.to_string(),
+            errata_start_page: errata_pages.0,
+            errata_end_page: errata_pages.1,
+            target_document_id: target_document_id.to_string(),
+            target_start_page: target_pages.0,
+            target_end_page: target_pages.1,
+            issued: trimmed(issued),
+            note: trimmed(note),
+            created_at: Utc::now(),
+        };
+        self.db.insert_errata(&errata)?;
+
+        info!(
+            errata_id = %errata.id,
+            errata_document_id = %errata.errata_document_id,
+            target_document_id = %errata.target_document_id,
+            "Errata added"
+        );
+        Ok(errata)
+    }
+
+    /// Flag search results affected by errata and rank corrections above
+    /// the text they supersede
+    pub(crate) fn apply_errata(
+        &self,
+        results: Vec<SearchResult>,
+    ) -> ServiceResult<Vec<SearchResult>> {
+        let errata = self.db.list_errata()?;
+        if errata.is_empty() {
+            return Ok(results);
+        }
+
+        let mut titles = HashMap::new();
+        for e in &errata {
+            for document_id in [&e.errata_document_id, &e.target_document_id] {
+                if !titles.contains_key(document_id)
+                    && let Some(document) = self.db.get_document(document_id)?
+                {
+                    titles.insert(document_id.clone(), document.title);
+                }
+            }
+        }
+
+        Ok(rank_with_errata(results, &errata, &titles))
+    }
+}
+
+/// Annotate results with their errata status and re-rank them
+fn rank_with_errata(
+    results: Vec<SearchResult>,
+    errata: &[Errata],
+    titles: &HashMap<String, String>,
+) -> Vec<SearchResult> {
+    let title = |document_id: &str| {
+        titles
+            .get(document_id)
+            .cloned()
+            .unwrap_or_else(|| document_id.to_string())
+    };
+
+    let mut ranked: Vec<(f32, SearchResult)> = results
+        .into_iter()
+        .map(|mut result| {
+            let (document_id, page) = (&result.chunk.document_id, result.chunk.page_number);
+            // Newest errata wins when several cover the same text
+            let superseding = errata
+                .iter()
+                .rev()
+                .find(|e| e.supersedes(document_id, page));
+            let correcting = errata
+                .iter()
+                .rev()
+                .find(|e| e.corrects_with(document_id, page));
+
+            let mut score = result.similarity;
+            if let Some(e) = superseding {
+                score -= ERRATA_RANK_ADJUSTMENT;
+                result.errata = Some(ErrataStatus::Superseded {
+                    errata_document_id: e.errata_document_id.clone(),
+                    errata_title: title(&e.errata_document_id),
+                    errata_start_page: e.errata_start_page,
+                    errata_end_page: e.errata_end_page,
+                    issued: e.issued.clone(),
+                    note: e.note.clone(),
+                });
+            } else if let Some(e) = correcting {
+                score += ERRATA_RANK_ADJUSTMENT;
+                result.errata = Some(ErrataStatus::Correction {
+                    target_document_id: e.target_document_id.clone(),
+                    target_title: title(&e.target_document_id),
+                    issued: e.issued.clone(),
+                });
+            }
+            (score, result)
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Chunk;
+    use crate::tools::AccessLevel;
+
+    fn result(id: &str, document_id: &str, page: i32, similarity: f32) -> SearchResult {
+        SearchResult {
+            chunk: Chunk {
+                id: id.to_string(),
+                document_id: document_id.to_string(),
+                content: String::new(),
+                chunk_index: 0,
+                page_number: Some(page),
+                section_title: None,
+                access_level: AccessLevel::Player,
+                tags: vec![],
+                metadata: None,
+                created_at: Utc::now(),
+            },
+            similarity,
+            errata: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_with_errata() {
+        let errata = Errata {
+            id: "e".to_string(),
+            errata_document_id: "fixes".to_string(),
+            errata_start_page: Some(3),
+            errata_end_page: Some(3),
+            target_document_id: "core".to_string(),
+            target_start_page: Some(140),
+            target_end_page: Some(160),
+            issued: Some("2023-05".to_string()),
+            note: None,
+            created_at: Utc::now(),
+        };
+        let titles = HashMap::from([("fixes".to_string(), "Core Errata".to_string())]);
+        let results = vec![
+            result("old-rule", "core", 148, 0.80),
+            result("fix", "fixes", 3, 0.77),
+            result("other", "core", 12, 0.78),
+            result("intro", "fixes", 1, 0.79),
+        ];
+
+        let ranked = rank_with_errata(results, &[errata], &titles);
+        let order: Vec<&str> = ranked.iter().map(|r| r.chunk.id.as_str()).collect();
+        assert_eq!(order, ["fix", "intro", "other", "old-rule"]);
+
+        assert!(matches!(
+            ranked[0].errata,
+            Some(ErrataStatus::Correction { .. })
+        ));
+        assert_eq!(ranked[1].errata, None);
+        let old = &ranked[3];
+        assert!(
+            matches!(&old.errata, Some(ErrataStatus::Superseded { errata_title, .. }) if errata_title == "Core Errata")
+        );
+        assert!(
+            old.format_for_context()
+                .contains("superseded by \"Core Errata\", issued 2023-05")
+        );
+        // Relevance is reported unchanged
+        assert_eq!(old.similarity, 0.80);
+    }
+}
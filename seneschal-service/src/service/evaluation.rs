@@ -0,0 +1,283 @@
+//! Retrieval and answer evaluation against a set of known questions.
+//!
+//! Each question names the pages its answer is on. A run searches for every
+//! question with the current embedding model and chunking, and scores how
+//! often (and how high) an expected page is retrieved. With answer
+//! generation on, the default model also answers from the retrieved text,
+//! and the answer is scored by how much of it is found in that text.
+//!
+//! Grounding and answer recall are word-overlap measures: cheap, repeatable
+//! and good for comparing configurations, not judgements of correctness.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::db::{EvalQuestion, EvalResult, EvalRun, EvalSettings};
+use crate::error::{ServiceError, ServiceResult};
+use crate::ollama::ChatMessage;
+use crate::search::SearchResult;
+use crate::service::{ModelTask, SeneschalService};
+use crate::tools::AccessLevel;
+
+/// Results searched per question when no top_k is given
+const DEFAULT_TOP_K: usize = 10;
+
+/// Words this short are ignored when comparing texts
+const MIN_WORD_LEN: usize = 4;
+
+impl SeneschalService {
+    /// Add an evaluation question
+    pub fn add_eval_question(
+        &self,
+        question: &str,
+        expected_answer: Option<String>,
+        document_id: Option<String>,
+        expected_pages: Vec<i32>,
+    ) -> ServiceResult<EvalQuestion> {
+        let question = question.trim();
+        if question.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Question is required".to_string(),
+            });
+        }
+        if let Some(document_id) = &document_id
+            && self.db.get_document(document_id)?.is_none()
+        {
+            return Err(ServiceError::DocumentNotFound {
+                document_id: document_id.clone(),
+            });
+        }
+
+        let question = EvalQuestion {
+            id: Uuid::new_v4().to_string(),
+            question: question.to_string(),
+            expected_answer: expected_answer.filter(|a| !a.trim().is_empty()),
+            document_id,
+            expected_pages,
+            created_at: Utc::now(),
+        };
+        self.db.insert_eval_question(&question)?;
+        Ok(question)
+    }
+
+    /// Run every evaluation question against the current configuration and
+    /// store the scores
+    pub async fn run_evaluation(
+        &self,
+        top_k: Option<usize>,
+        generate_answers: bool,
+    ) -> ServiceResult<EvalRun> {
+        let questions = self.db.list_eval_questions()?;
+        if questions.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "No evaluation questions defined".to_string(),
+            });
+        }
+
+        let config = self.runtime_config.dynamic();
+        let settings = EvalSettings {
+            embedding_model: config.embeddings.model.clone(),
+            chunk_size: config.embeddings.chunk_size,
+            chunk_overlap: config.embeddings.chunk_overlap,
+            top_k: top_k.unwrap_or(DEFAULT_TOP_K).max(1),
+            answer_model: generate_answers
+                .then(|| self.primary_model(ModelTask::Chat))
+                .flatten(),
+        };
+
+        let mut results = Vec::with_capacity(questions.len());
+        for question in &questions {
+            results.push(self.evaluate_question(question, &settings).await);
+        }
+
+        let count = results.len() as f32;
+        let hits = results.iter().filter(|r| r.rank.is_some()).count() as f32;
+        let reciprocal_ranks: f32 = results
+            .iter()
+            .filter_map(|r| r.rank)
+            .map(|rank| 1.0 / rank as f32)
+            .sum();
+        let grounding: Vec<f32> = results.iter().filter_map(|r| r.grounding).collect();
+
+        let run = EvalRun {
+            id: Uuid::new_v4().to_string(),
+            settings,
+            question_count: results.len(),
+            hit_rate: hits / count,
+            mean_reciprocal_rank: reciprocal_ranks / count,
+            grounding_score: (!grounding.is_empty())
+                .then(|| grounding.iter().sum::<f32>() / grounding.len() as f32),
+            results,
+            created_at: Utc::now(),
+        };
+        self.db.insert_eval_run(&run)?;
+
+        info!(
+            run_id = %run.id,
+            questions = run.question_count,
+            hit_rate = run.hit_rate,
+            mrr = run.mean_reciprocal_rank,
+            grounding = ?run.grounding_score,
+            "Evaluation run complete"
+        );
+        Ok(run)
+    }
+
+    async fn evaluate_question(
+        &self,
+        question: &EvalQuestion,
+        settings: &EvalSettings,
+    ) -> EvalResult {
+        let mut result = EvalResult {
+            question_id: question.id.clone(),
+            question: question.question.clone(),
+            rank: None,
+            retrieved: Vec::new(),
+            answer: None,
+            grounding: None,
+            answer_recall: None,
+            error: None,
+        };
+
+        let retrieved = match self
+            .search
+            .search(
+                &question.question,
+                AccessLevel::GmOnly as u8,
+                settings.top_k,
+                None,
+                self.runtime_config.dynamic().embeddings.priority_boost,
+            )
+            .await
+        {
+            Ok(retrieved) => retrieved,
+            Err(e) => {
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+        result.rank = first_hit_rank(question, &retrieved);
+        result.retrieved = retrieved
+            .iter()
+            .map(|r| match r.chunk.page_number {
+                Some(page) => format!("{}:{}", r.chunk.document_id, page),
+                None => r.chunk.document_id.clone(),
+            })
+            .collect();
+
+        let Some(model) = &settings.answer_model else {
+            return result;
+        };
+        let context: String = retrieved
+            .iter()
+            .map(|r| r.chunk.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let prompt = format!(
+            "Answer the question using only the reference text below. \
+            Be brief. If the text doesn't contain the answer, say so.\n\n\
+            Reference text:\n{}\n\nQuestion: {}",
+            context, question.question
+        );
+        match self
+            .generate_routed(
+                ModelTask::Chat,
+                Some(model),
+                vec![ChatMessage::user(prompt)],
+            )
+            .await
+        {
+            Ok(answer) => {
+                result.grounding = Some(word_coverage(&answer, &context));
+                result.answer_recall = question
+                    .expected_answer
+                    .as_deref()
+                    .map(|expected| word_coverage(expected, &answer));
+                result.answer = Some(answer);
+            }
+            Err(e) => result.error = Some(e.to_string()),
+        }
+        result
+    }
+}
+
+/// 1-based rank of the first result from the question's expected document and pages
+fn first_hit_rank(question: &EvalQuestion, retrieved: &[SearchResult]) -> Option<usize> {
+    retrieved
+        .iter()
+        .position(|r| {
+            let document_matches = question
+                .document_id
+                .as_ref()
+                .is_none_or(|id| *id == r.chunk.document_id);
+            let page_matches = question.expected_pages.is_empty()
+                || r.chunk
+                    .page_number
+                    .is_some_and(|page| question.expected_pages.contains(&page));
+            document_matches && page_matches
+        })
+        .map(|index| index + 1)
+}
+
+/// Fraction of `text`'s words that also appear in `reference`
+fn word_coverage(text: &str, reference: &str) -> f32 {
+    let reference: HashSet<String> = words(reference).collect();
+    let words: HashSet<String> = words(text).collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+    words.iter().filter(|w| reference.contains(*w)).count() as f32 / words.len() as f32
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= MIN_WORD_LEN)
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Chunk;
+
+    fn result(document_id: &str, page_number: i32) -> SearchResult {
+        SearchResult {
+            chunk: Chunk {
+                id: format!("{}-{}", document_id, page_number),
+                document_id: document_id.to_string(),
+                content: String::new(),
+                chunk_index: 0,
+                page_number: Some(page_number),
+                section_title: None,
+                access_level: AccessLevel::GmOnly,
+                tags: vec![],
+                metadata: None,
+                created_at: Utc::now(),
+            },
+            similarity: 0.5,
+            errata: None,
+        }
+    }
+
+    #[test]
+    fn test_evaluation_scoring() {
+        let question = EvalQuestion {
+            id: "q".to_string(),
+            question: "How far can a jump-2 drive travel?".to_string(),
+            expected_answer: None,
+            document_id: Some("core".to_string()),
+            expected_pages: vec![148, 149],
+            created_at: Utc::now(),
+        };
+        let retrieved = [result("core", 12), result("srd", 148), result("core", 149)];
+        assert_eq!(first_hit_rank(&question, &retrieved), Some(3));
+        assert_eq!(first_hit_rank(&question, &retrieved[..2]), None);
+
+        let context = "A jump drive moves a ship one parsec per jump number.";
+        assert_eq!(word_coverage("Two parsecs per jump", context), 0.5);
+        assert_eq!(word_coverage("", context), 0.0);
+    }
+}
@@ -1,11 +1,13 @@
 //! External tool execution via WebSocket for MCP requests.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tracing::{debug, warn};
 use uuid::Uuid;
 
-use crate::websocket::ServerMessage;
+use crate::call_trace::{current_correlation_id, record_stage};
+use crate::tools::REGISTRY;
+use crate::websocket::{GmRoute, ServerMessage};
 
 use super::SeneschalService;
 
@@ -21,14 +23,49 @@ impl SeneschalService {
         tool: &str,
         args: serde_json::Value,
         timeout: Duration,
+    ) -> Result<serde_json::Value, String> {
+        self.execute_external_tool_for_session(tool, args, timeout, None)
+            .await
+    }
+
+    /// Execute an external tool on behalf of an MCP session.
+    ///
+    /// Calls from the same session keep going to the same GM connection while
+    /// it stays connected and eligible.
+    pub async fn execute_external_tool_for_session(
+        &self,
+        tool: &str,
+        args: serde_json::Value,
+        timeout: Duration,
+        mcp_session_id: Option<&str>,
     ) -> Result<serde_json::Value, String> {
         use tokio::sync::oneshot;
 
-        // Find an available GM connection
-        let session_id = self
-            .ws_manager
-            .get_any_gm_connection()
-            .ok_or_else(|| "No GM connection available to execute FVTT tools".to_string())?;
+        let (world_id, restrict_write_tools) = {
+            let config = self.runtime_config.dynamic();
+            (config.mcp.world_id.clone(), config.mcp.restrict_write_tools)
+        };
+        let world_id = Some(world_id.as_str()).filter(|w| !w.is_empty());
+        // Tools registered by clients say whether they write
+        let is_write = match self.client_tools.get(world_id, tool) {
+            Some(client_tool) => !client_tool.read_only,
+            None => REGISTRY.is_write(tool),
+        };
+        let route = GmRoute {
+            world_id,
+            affinity_key: mcp_session_id,
+            require_write_access: restrict_write_tools && is_write,
+        };
+        let session_id = self.ws_manager.route_gm_connection(&route).ok_or_else(|| {
+            if route.require_write_access {
+                format!(
+                    "No GM connection that allows write tools is available to execute '{}'",
+                    tool
+                )
+            } else {
+                "No GM connection available to execute FVTT tools".to_string()
+            }
+        })?;
 
         // Generate unique request ID for this MCP tool call
         let request_id = format!("mcp:{}", Uuid::new_v4());
@@ -54,13 +91,40 @@ impl SeneschalService {
                 id: tool_call_id.clone(),
                 tool: tool.to_string(),
                 args,
+                correlation_id: current_correlation_id(),
             },
         );
 
         // Wait for result with timeout
-        match tokio::time::timeout(timeout, rx).await {
-            Ok(Ok(result)) => {
+        let started = Instant::now();
+        let outcome = tokio::time::timeout(timeout, rx).await;
+        record_stage(
+            &format!("gm_client {}", tool),
+            started,
+            matches!(outcome, Ok(Ok(_))),
+        );
+        match outcome {
+            Ok(Ok(mut result)) => {
                 debug!(request_id = %request_id, "MCP tool result received");
+                self.record_fvtt_change(tool, mcp_session_id, &mut result);
+                if let Err(problem) = REGISTRY.validate_result(tool, &result) {
+                    warn!(request_id = %request_id, tool = %tool, problem = %problem, "Malformed external tool result");
+                    self.ws_manager.send_to(
+                        &session_id,
+                        ServerMessage::Error {
+                            code: "invalid_tool_result".to_string(),
+                            message: format!("Result of '{}' was rejected: {}", tool, problem),
+                            recoverable: true,
+                        },
+                    );
+                    // Reported like an FVTT-side failure so the model can retry or move on
+                    return Ok(serde_json::json!({
+                        "error": format!(
+                            "Tool '{}' returned a malformed result ({}); try the call again or use another tool",
+                            tool, problem
+                        )
+                    }));
+                }
                 Ok(result)
             }
             Ok(Err(_)) => {
@@ -0,0 +1,155 @@
+//! Undo log for changes tools make to FVTT world documents.
+//!
+//! The FVTT client attaches a `_change` to the result of each tool that
+//! created, updated or deleted a world document, with the document's data
+//! before and after. The backend strips it from the result and keeps it, so
+//! an over-eager edit can be reverted later: the GM client is asked to delete
+//! what was created, restore what was updated and recreate what was deleted.
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::db::FvttChange;
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+
+/// Operations the FVTT client knows how to undo
+const UNDOABLE_OPERATIONS: &[&str] = &["create", "update", "delete"];
+
+impl SeneschalService {
+    /// Take the change attached to an external tool result and log it
+    pub(crate) fn record_fvtt_change(
+        &self,
+        tool: &str,
+        mcp_session_id: Option<&str>,
+        result: &mut serde_json::Value,
+    ) {
+        let Some(change) = result.as_object_mut().and_then(|r| r.remove("_change")) else {
+            return;
+        };
+        let field = |name: &str| {
+            change
+                .get(name)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let (Some(document_type), Some(document_id), Some(operation)) = (
+            field("document_type"),
+            field("document_id"),
+            field("operation").filter(|op| UNDOABLE_OPERATIONS.contains(&op.as_str())),
+        ) else {
+            warn!(tool = %tool, "Ignoring malformed change in tool result");
+            return;
+        };
+        let snapshot = |name: &str| change.get(name).filter(|v| v.is_object()).cloned();
+
+        let change = FvttChange {
+            id: uuid::Uuid::new_v4().to_string(),
+            world_id: self.mcp_world_id().unwrap_or_default(),
+            tool: tool.to_string(),
+            document_type,
+            document_id,
+            document_name: field("document_name"),
+            operation,
+            before: snapshot("before"),
+            after: snapshot("after"),
+            session_id: mcp_session_id.map(str::to_string),
+            created_at: Utc::now(),
+            undone_at: None,
+        };
+        if let Err(e) = self.db.insert_fvtt_change(&change) {
+            warn!(error = %e, tool = %tool, "Failed to record FVTT change");
+        }
+    }
+
+    /// Recent changes to the MCP world, most recent first
+    pub fn list_fvtt_changes(
+        &self,
+        include_undone: bool,
+        limit: usize,
+    ) -> ServiceResult<Vec<FvttChange>> {
+        self.db.list_fvtt_changes(
+            &self.mcp_world_id().unwrap_or_default(),
+            include_undone,
+            limit,
+        )
+    }
+
+    /// Revert a change, or the most recent one still in effect
+    ///
+    /// Only the latest change to a document can be undone, so an older
+    /// snapshot never overwrites later edits.
+    pub async fn undo_fvtt_change(&self, change_id: Option<&str>) -> ServiceResult<FvttChange> {
+        let world_id = self.mcp_world_id().unwrap_or_default();
+        let change = match change_id {
+            Some(id) => self
+                .db
+                .get_fvtt_change(id)?
+                .filter(|change| change.world_id == world_id),
+            None => self
+                .db
+                .list_fvtt_changes(&world_id, false, 1)?
+                .into_iter()
+                .next(),
+        }
+        .ok_or_else(|| ServiceError::InvalidRequest {
+            message: match change_id {
+                Some(id) => format!("Change not found: {}", id),
+                None => "There are no changes to undo".to_string(),
+            },
+        })?;
+
+        if change.undone_at.is_some() {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Change {} was already undone", change.id),
+            });
+        }
+        if let Some(latest) = self.db.latest_fvtt_change_for_document(
+            &world_id,
+            &change.document_type,
+            &change.document_id,
+        )? && latest.id != change.id
+        {
+            return Err(ServiceError::InvalidRequest {
+                message: format!(
+                    "{} {} was changed again since; undo change {} first",
+                    change.document_type, change.document_id, latest.id
+                ),
+            });
+        }
+
+        let timeout = self
+            .runtime_config
+            .dynamic()
+            .agentic_loop
+            .external_tool_timeout();
+        let args = serde_json::json!({
+            "document_type": change.document_type,
+            "document_id": change.document_id,
+            "operation": change.operation,
+            "before": change.before,
+        });
+        let response = self
+            .execute_external_tool_mcp("fvtt_undo_change", args, timeout)
+            .await
+            .map_err(|message| ServiceError::InvalidRequest { message })?;
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Failed to undo change: {}", error),
+            });
+        }
+
+        self.db.mark_fvtt_change_undone(&change.id)?;
+        info!(
+            change_id = %change.id,
+            document_type = %change.document_type,
+            document_id = %change.document_id,
+            "FVTT change undone"
+        );
+        self.db
+            .get_fvtt_change(&change.id)?
+            .ok_or_else(|| ServiceError::InvalidRequest {
+                message: format!("Change not found: {}", change.id),
+            })
+    }
+}
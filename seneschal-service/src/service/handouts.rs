@@ -0,0 +1,177 @@
+//! Player handouts.
+//!
+//! GM-written or LLM-generated markdown, with optional corpus images, is
+//! laid out in a handout template (patron briefing, news feed, library
+//! data), rendered to a PDF or PNG with headless Chrome, and delivered to
+//! the FVTT assets directory.
+
+mod render;
+mod templates;
+
+use std::path::PathBuf;
+
+use base64::Engine;
+use serde::Serialize;
+use tracing::info;
+
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::assets::sanitize_filename;
+use crate::service::{ImageDelivery, SeneschalService};
+
+pub use render::HandoutFormat;
+pub use templates::HandoutTemplate;
+use templates::{HandoutImage, handout_html, referenced_images};
+
+/// Most corpus images in one handout
+const MAX_HANDOUT_IMAGES: usize = 12;
+
+/// A handout to compose
+#[derive(Debug, Clone)]
+pub struct HandoutRequest {
+    pub title: String,
+    pub markdown: String,
+    pub template: HandoutTemplate,
+    pub format: HandoutFormat,
+    /// Images to add beyond those referenced inline as `image:<id>`
+    pub image_ids: Vec<String>,
+}
+
+/// A composed handout
+#[derive(Debug, Clone, Serialize)]
+pub struct Handout {
+    pub file_name: String,
+    pub title: String,
+    pub template: HandoutTemplate,
+    pub format: HandoutFormat,
+    pub size_bytes: usize,
+    #[serde(flatten)]
+    pub delivery: ImageDelivery,
+}
+
+impl SeneschalService {
+    fn handouts_dir(&self) -> PathBuf {
+        self.runtime_config
+            .static_config
+            .storage
+            .data_dir
+            .join("handouts")
+    }
+
+    /// The stored copy of a handout, by the file name it was composed with
+    pub fn handout_path(&self, file_name: &str) -> ServiceResult<PathBuf> {
+        let path = self.handouts_dir().join(file_name);
+        if file_name.contains(['/', '\\']) || file_name.starts_with('.') || !path.is_file() {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Handout not found: {}", file_name),
+            });
+        }
+        Ok(path)
+    }
+
+    /// Embed the requested corpus images the caller may see
+    fn handout_images(
+        &self,
+        request: &HandoutRequest,
+        max_access_level: u8,
+    ) -> ServiceResult<Vec<HandoutImage>> {
+        let mut ids = referenced_images(&request.markdown);
+        for id in &request.image_ids {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        if ids.len() > MAX_HANDOUT_IMAGES {
+            return Err(ServiceError::InvalidRequest {
+                message: format!(
+                    "A handout can include at most {} images",
+                    MAX_HANDOUT_IMAGES
+                ),
+            });
+        }
+
+        let mut images = Vec::with_capacity(ids.len());
+        for id in ids {
+            let image = self
+                .db
+                .get_document_image(&id)?
+                .filter(|image| image.access_level.accessible_by(max_access_level))
+                .ok_or_else(|| ServiceError::ImageNotFound {
+                    image_id: id.clone(),
+                })?;
+            let data = std::fs::read(&image.image.internal_path)
+                .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+            images.push(HandoutImage {
+                id,
+                data_uri: format!(
+                    "data:{};base64,{}",
+                    image.image.mime_type,
+                    base64::engine::general_purpose::STANDARD.encode(&data)
+                ),
+                caption: image.image.description,
+            });
+        }
+        Ok(images)
+    }
+
+    /// Render a handout and deliver it to FVTT
+    pub async fn compose_handout(
+        &self,
+        request: &HandoutRequest,
+        max_access_level: u8,
+    ) -> ServiceResult<Handout> {
+        let title = request.title.trim();
+        if title.is_empty() || request.markdown.trim().is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "A handout needs a title and content".to_string(),
+            });
+        }
+
+        let images = self.handout_images(request, max_access_level)?;
+        let dateline = self
+            .campaign_date(None)?
+            .map(|date| format!("Imperial date {}", date));
+        let html = handout_html(
+            title,
+            &request.markdown,
+            request.template,
+            dateline.as_deref(),
+            &images,
+        )
+        .map_err(|message| ServiceError::InvalidRequest { message })?;
+
+        let chrome_path = self
+            .runtime_config
+            .dynamic()
+            .traveller_worlds
+            .chrome_path
+            .clone();
+        let data = render::render_html(&html, request.format, chrome_path.as_deref())
+            .await
+            .map_err(|message| ServiceError::Internal { message })?;
+
+        let file_name = format!(
+            "{}-{}.{}",
+            sanitize_filename(title).to_lowercase(),
+            &uuid::Uuid::new_v4().simple().to_string()[..8],
+            request.format.extension()
+        );
+        let dir = self.handouts_dir();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+        let stored = dir.join(&file_name);
+        std::fs::write(&stored, &data)
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+
+        let delivery = self.deliver_file(&stored, &format!("seneschal/handouts/{}", file_name))?;
+        info!(file_name = %file_name, template = ?request.template, "Handout composed");
+
+        Ok(Handout {
+            file_name,
+            title: title.to_string(),
+            template: request.template,
+            format: request.format,
+            size_bytes: data.len(),
+            delivery,
+        })
+    }
+}
@@ -0,0 +1,106 @@
+//! Handout rendering with headless Chrome/Chromium.
+
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::page::{CaptureScreenshotFormat, PrintToPdfParams};
+use chromiumoxide::page::ScreenshotParams;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// Handout file formats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HandoutFormat {
+    #[default]
+    Pdf,
+    Png,
+}
+
+impl HandoutFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Pdf => "pdf",
+            Self::Png => "png",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Pdf => "application/pdf",
+            Self::Png => "image/png",
+        }
+    }
+}
+
+/// Render a page of HTML to a PDF, or a full-page PNG sized like a sheet of
+/// A4 paper
+pub async fn render_html(
+    html: &str,
+    format: HandoutFormat,
+    chrome_path: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let mut builder = BrowserConfig::builder()
+        .window_size(794, 1123)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-sandbox")
+        .arg("--disable-dev-shm-usage");
+    if let Some(path) = chrome_path {
+        builder = builder.chrome_executable(path);
+    }
+    let config = builder
+        .build()
+        .map_err(|e| format!("Failed to launch browser: {}", e))?;
+
+    let (mut browser, mut handler) = Browser::launch(config)
+        .await
+        .map_err(|e| format!("Failed to launch browser: {}", e))?;
+    let handle = tokio::spawn(async move {
+        while let Some(h) = handler.next().await {
+            if h.is_err() {
+                break;
+            }
+        }
+    });
+
+    let result = render_page(&browser, html, format).await;
+
+    let _ = browser.close().await;
+    handle.abort();
+
+    result
+}
+
+async fn render_page(
+    browser: &Browser,
+    html: &str,
+    format: HandoutFormat,
+) -> Result<Vec<u8>, String> {
+    let page = browser
+        .new_page("about:blank")
+        .await
+        .map_err(|e| format!("Failed to open page: {}", e))?;
+    page.set_content(html)
+        .await
+        .map_err(|e| format!("Failed to load handout: {}", e))?;
+
+    match format {
+        HandoutFormat::Pdf => page
+            .pdf(
+                PrintToPdfParams::builder()
+                    .print_background(true)
+                    .prefer_css_page_size(true)
+                    .build(),
+            )
+            .await
+            .map_err(|e| format!("Failed to print PDF: {}", e)),
+        HandoutFormat::Png => page
+            .screenshot(
+                ScreenshotParams::builder()
+                    .format(CaptureScreenshotFormat::Png)
+                    .full_page(true)
+                    .build(),
+            )
+            .await
+            .map_err(|e| format!("Failed to capture image: {}", e)),
+    }
+}
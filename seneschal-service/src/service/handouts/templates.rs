@@ -0,0 +1,228 @@
+//! Handout HTML templates.
+//!
+//! Markdown is converted to HTML and wrapped in a page styled for the
+//! handout type. Corpus images are referenced in the markdown as
+//! `![caption](image:<image_id>)` and embedded as data URIs, since the
+//! headless browser rendering the page has no access to the image store.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::super::session_summary::escape_html;
+
+/// `](image:<id>)` in markdown
+static IMAGE_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\]\(image:([A-Za-z0-9_-]+)\)").unwrap());
+
+/// Image references after conversion; markdown drops unknown URL schemes, so
+/// references are rewritten to fragments first
+static IMAGE_SRC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r##"src="#seneschal-image-([A-Za-z0-9_-]+)""##).unwrap());
+
+const BASE_CSS: &str = r#"
+@page { size: A4; margin: 18mm; }
+body { font-family: "Helvetica Neue", Arial, sans-serif; font-size: 11pt; line-height: 1.45; color: #1a1a1a; margin: 0; }
+header { margin-bottom: 1.2em; }
+h1 { font-size: 22pt; margin: 0.2em 0; }
+h2 { font-size: 14pt; margin-top: 1.2em; }
+img { max-width: 100%; }
+figure { margin: 1em 0; text-align: center; page-break-inside: avoid; }
+figcaption { font-size: 9pt; font-style: italic; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0; }
+th, td { border: 1px solid #999; padding: 0.3em 0.5em; text-align: left; }
+.banner { font-size: 9pt; letter-spacing: 0.3em; text-transform: uppercase; }
+.dateline { font-size: 9pt; }
+"#;
+
+const PATRON_BRIEFING_CSS: &str = r#"
+body { font-family: "Courier New", monospace; }
+header { border-top: 4px solid #8b0000; border-bottom: 1px solid #8b0000; padding: 0.5em 0; }
+.banner { color: #8b0000; font-weight: bold; }
+h1 { text-transform: uppercase; }
+"#;
+
+const NEWS_FEED_CSS: &str = r#"
+body { font-family: Georgia, "Times New Roman", serif; }
+header { text-align: center; border-bottom: 3px double #1a1a1a; }
+.banner { font-size: 12pt; font-weight: bold; }
+h1 { font-size: 26pt; }
+main { column-count: 2; column-gap: 2em; }
+main h2 { column-span: all; }
+"#;
+
+const LIBRARY_DATA_CSS: &str = r#"
+html, body { background: #0b1a0b; }
+body { font-family: "Courier New", monospace; color: #7cfc7c; padding: 1em; }
+header { border: 1px solid #7cfc7c; padding: 0.5em 1em; }
+.banner { color: #b6ffb6; }
+h1, h2 { color: #b6ffb6; }
+th, td { border-color: #3c8c3c; }
+"#;
+
+/// Handout styles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandoutTemplate {
+    #[default]
+    Plain,
+    /// A patron's job offer or mission brief
+    PatronBriefing,
+    /// A news feed bulletin
+    NewsFeed,
+    /// An in-universe computer library entry
+    LibraryData,
+}
+
+impl HandoutTemplate {
+    fn banner(&self) -> Option<&'static str> {
+        match self {
+            Self::Plain => None,
+            Self::PatronBriefing => Some("Patron Briefing — Confidential"),
+            Self::NewsFeed => Some("Traveller News Service"),
+            Self::LibraryData => Some("Library Data"),
+        }
+    }
+
+    fn css(&self) -> &'static str {
+        match self {
+            Self::Plain => "",
+            Self::PatronBriefing => PATRON_BRIEFING_CSS,
+            Self::NewsFeed => NEWS_FEED_CSS,
+            Self::LibraryData => LIBRARY_DATA_CSS,
+        }
+    }
+}
+
+/// A corpus image to embed in a handout
+#[derive(Debug, Clone)]
+pub struct HandoutImage {
+    pub id: String,
+    pub data_uri: String,
+    pub caption: Option<String>,
+}
+
+/// IDs of the images the markdown references inline, in order
+pub fn referenced_images(markdown: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for caps in IMAGE_REF_RE.captures_iter(markdown) {
+        if !ids.iter().any(|id| id == &caps[1]) {
+            ids.push(caps[1].to_string());
+        }
+    }
+    ids
+}
+
+/// Build the handout page. Images the markdown doesn't reference inline are
+/// added as captioned figures at the end.
+pub fn handout_html(
+    title: &str,
+    markdown: &str,
+    template: HandoutTemplate,
+    dateline: Option<&str>,
+    images: &[HandoutImage],
+) -> Result<String, String> {
+    let inline = referenced_images(markdown);
+    let markdown = IMAGE_REF_RE.replace_all(markdown, "](#seneschal-image-$1)");
+    let body = markdown::to_html_with_options(&markdown, &markdown::Options::gfm())
+        .map_err(|e| e.to_string())?;
+
+    let by_id: HashMap<&str, &HandoutImage> = images
+        .iter()
+        .map(|image| (image.id.as_str(), image))
+        .collect();
+    let mut body = IMAGE_SRC_RE
+        .replace_all(&body, |caps: &regex::Captures| match by_id.get(&caps[1]) {
+            Some(image) => format!("src=\"{}\"", image.data_uri),
+            None => "src=\"\"".to_string(),
+        })
+        .into_owned();
+
+    for image in images.iter().filter(|image| !inline.contains(&image.id)) {
+        body.push_str(&format!("<figure><img src=\"{}\">", image.data_uri));
+        if let Some(caption) = &image.caption {
+            body.push_str(&format!(
+                "<figcaption>{}</figcaption>",
+                escape_html(caption)
+            ));
+        }
+        body.push_str("</figure>");
+    }
+
+    let mut header = String::new();
+    if let Some(banner) = template.banner() {
+        header.push_str(&format!("<div class=\"banner\">{}</div>", banner));
+    }
+    header.push_str(&format!("<h1>{}</h1>", escape_html(title)));
+    if let Some(dateline) = dateline {
+        header.push_str(&format!(
+            "<div class=\"dateline\">{}</div>",
+            escape_html(dateline)
+        ));
+    }
+
+    Ok(format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title><style>{}{}</style></head><body><header>{}</header><main>{}</main></body></html>",
+        escape_html(title),
+        BASE_CSS,
+        template.css(),
+        header,
+        body
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(id: &str) -> HandoutImage {
+        HandoutImage {
+            id: id.to_string(),
+            data_uri: format!("data:image/png;base64,{}", id),
+            caption: Some("Starport <A>".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_referenced_images() {
+        let markdown = "![Map](image:img-1) text ![Again](image:img-1) ![Ship](image:img_2)";
+        assert_eq!(referenced_images(markdown), vec!["img-1", "img_2"]);
+    }
+
+    #[test]
+    fn test_handout_html_embeds_images() {
+        let html = handout_html(
+            "Job <Offer>",
+            "Meet at the **starport**.\n\n![Map](image:a)",
+            HandoutTemplate::PatronBriefing,
+            Some("Imperial date 123-1105"),
+            &[image("a"), image("b")],
+        )
+        .unwrap();
+
+        assert!(html.contains("<h1>Job &lt;Offer&gt;</h1>"));
+        assert!(html.contains("Patron Briefing"));
+        assert!(html.contains("<strong>starport</strong>"));
+        assert!(html.contains("src=\"data:image/png;base64,a\""));
+        // b isn't referenced inline, so it's appended as a figure
+        assert!(html.contains("<figure><img src=\"data:image/png;base64,b\">"));
+        assert!(html.contains("<figcaption>Starport &lt;A&gt;</figcaption>"));
+        assert_eq!(html.matches("base64,a").count(), 1);
+    }
+
+    #[test]
+    fn test_unknown_image_is_dropped() {
+        let html = handout_html(
+            "Notice",
+            "![Gone](image:missing)",
+            HandoutTemplate::Plain,
+            None,
+            &[],
+        )
+        .unwrap();
+        assert!(html.contains("src=\"\""));
+        assert!(!html.contains("class=\"banner\""));
+    }
+}
@@ -0,0 +1,345 @@
+//! Image delivery and batch image operations.
+//!
+//! Extraction can produce hundreds of images per rulebook, too many to
+//! manage one at a time. Batch operations apply one change to a set of
+//! images and report which images succeeded and which failed, instead
+//! of stopping at the first failure.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::config::AssetsAccess;
+use crate::db::DocumentImageWithAccess;
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::IngestionService;
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+
+/// Most images one batch request may name
+const MAX_BATCH_IMAGES: usize = 1000;
+
+/// Where an image was delivered for FVTT
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum ImageDelivery {
+    /// Copied into the FVTT assets directory
+    Direct { fvtt_path: String },
+    /// The client must fetch the image and upload it to this path
+    Shuttle { suggested_path: String },
+}
+
+/// An image a batch operation couldn't apply to
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageBatchFailure {
+    pub image_id: String,
+    pub error: String,
+}
+
+/// An image delivered by a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageBatchDelivery {
+    pub image_id: String,
+    #[serde(flatten)]
+    pub delivery: ImageDelivery,
+}
+
+/// Outcome of a batch image operation
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImageBatchReport {
+    pub requested: usize,
+    pub succeeded: usize,
+    pub failed: Vec<ImageBatchFailure>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub delivered: Vec<ImageBatchDelivery>,
+}
+
+impl ImageBatchReport {
+    fn new(requested: usize) -> Self {
+        Self {
+            requested,
+            ..Default::default()
+        }
+    }
+
+    /// Record every requested image not in `updated` as not found
+    fn from_updated(image_ids: &[String], updated: &[String]) -> Self {
+        let mut report = Self::new(image_ids.len());
+        report.succeeded = updated.len();
+        report.failed = image_ids
+            .iter()
+            .filter(|id| !updated.contains(id))
+            .map(|id| ImageBatchFailure {
+                image_id: id.clone(),
+                error: "Image not found".to_string(),
+            })
+            .collect();
+        report
+    }
+
+    fn fail(&mut self, image_id: &str, error: impl ToString) {
+        self.failed.push(ImageBatchFailure {
+            image_id: image_id.to_string(),
+            error: error.to_string(),
+        });
+    }
+}
+
+impl SeneschalService {
+    /// Deliver an image to the FVTT assets directory, at a path relative to it
+    ///
+    /// The delivery is recorded for the MCP world, so searches can leave out
+    /// art the campaign has already used.
+    pub fn deliver_image(
+        &self,
+        image: &DocumentImageWithAccess,
+        relative_path: &str,
+    ) -> ServiceResult<ImageDelivery> {
+        let delivery = self.deliver_file(Path::new(&image.image.internal_path), relative_path)?;
+        let fvtt_path = match &delivery {
+            ImageDelivery::Direct { fvtt_path } => fvtt_path,
+            ImageDelivery::Shuttle { suggested_path } => suggested_path,
+        };
+        if let Err(e) = self.db.record_image_delivery(
+            &self.mcp_world_id().unwrap_or_default(),
+            fvtt_path,
+            &image.image.id,
+        ) {
+            warn!(error = %e, image_id = %image.image.id, "Failed to record image delivery");
+        }
+        Ok(delivery)
+    }
+
+    /// Record the delivered images a world document uses, as reported by the
+    /// FVTT module. Paths that weren't delivered by Seneschal are ignored.
+    pub fn record_asset_usage(
+        &self,
+        world_id: Option<String>,
+        document_type: &str,
+        document_id: &str,
+        document_name: Option<&str>,
+        paths: &[String],
+    ) -> ServiceResult<()> {
+        let world_id = world_id.or_else(|| self.mcp_world_id()).unwrap_or_default();
+        let placed = self.db.set_image_placements(
+            &world_id,
+            document_type,
+            document_id,
+            document_name,
+            paths,
+        )?;
+        if placed > 0 {
+            debug!(
+                document_type,
+                document_id, placed, "Recorded delivered image usage"
+            );
+        }
+        Ok(())
+    }
+
+    /// Copy a file into the FVTT assets directory, at a path relative to it
+    pub(crate) fn deliver_file(
+        &self,
+        source: &Path,
+        relative_path: &str,
+    ) -> ServiceResult<ImageDelivery> {
+        // The FVTT path is what FVTT uses to reference the file (prepend assets/)
+        let fvtt_path = format!("assets/{}", relative_path);
+
+        match self.runtime_config.static_config.fvtt.check_assets_access() {
+            AssetsAccess::Direct(assets_dir) => {
+                let full_path = assets_dir.join(relative_path);
+                if let Some(parent) = full_path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+                }
+                std::fs::copy(source, &full_path)
+                    .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+
+                Ok(ImageDelivery::Direct { fvtt_path })
+            }
+            AssetsAccess::Shuttle => Ok(ImageDelivery::Shuttle {
+                suggested_path: fvtt_path,
+            }),
+        }
+    }
+
+    /// Override the access level of a set of images (`None` inherits the document's)
+    pub fn batch_set_image_access_level(
+        &self,
+        image_ids: &[String],
+        access_level: Option<AccessLevel>,
+    ) -> ServiceResult<ImageBatchReport> {
+        check_batch_size(image_ids)?;
+        let updated = self.db.set_images_access_level(image_ids, access_level)?;
+        info!(count = updated.len(), access_level = ?access_level, "Set image access levels");
+        Ok(ImageBatchReport::from_updated(image_ids, &updated))
+    }
+
+    /// Add tags to a set of images
+    pub fn batch_add_image_tags(
+        &self,
+        image_ids: &[String],
+        tags: &[String],
+    ) -> ServiceResult<ImageBatchReport> {
+        check_batch_size(image_ids)?;
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if tags.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "No tags given".to_string(),
+            });
+        }
+
+        let tagged = self.db.add_image_tags(image_ids, &tags)?;
+        info!(count = tagged.len(), tags = ?tags, "Tagged images");
+        Ok(ImageBatchReport::from_updated(image_ids, &tagged))
+    }
+
+    /// Delete a set of images and their files
+    pub fn batch_delete_images(&self, image_ids: &[String]) -> ServiceResult<ImageBatchReport> {
+        check_batch_size(image_ids)?;
+        let mut report = ImageBatchReport::new(image_ids.len());
+        for image_id in image_ids {
+            match self.delete_image(image_id) {
+                Ok(true) => report.succeeded += 1,
+                Ok(false) => report.fail(image_id, "Image not found"),
+                Err(e) => report.fail(image_id, e),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Deliver a set of images to the FVTT assets directory.
+    ///
+    /// Images go under `target_folder` (relative to the assets directory) when
+    /// given, or each to its default path; names repeated within the batch get
+    /// a numeric suffix.
+    pub fn batch_deliver_images(
+        &self,
+        image_ids: &[String],
+        target_folder: Option<&str>,
+    ) -> ServiceResult<ImageBatchReport> {
+        check_batch_size(image_ids)?;
+        if target_folder.is_some_and(|folder| {
+            Path::new(folder)
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        }) {
+            return Err(ServiceError::InvalidRequest {
+                message: "Target folder must stay inside the assets directory".to_string(),
+            });
+        }
+
+        let mut report = ImageBatchReport::new(image_ids.len());
+        let mut used_paths = HashSet::new();
+
+        for image_id in image_ids {
+            let image = match self.db.get_document_image(image_id) {
+                Ok(Some(image)) => image,
+                Ok(None) => {
+                    report.fail(image_id, "Image not found");
+                    continue;
+                }
+                Err(e) => {
+                    report.fail(image_id, e);
+                    continue;
+                }
+            };
+
+            let default_path = IngestionService::fvtt_image_path(
+                &image.document_title,
+                image.image.page_number,
+                image.image.description.as_deref(),
+            );
+            let path = match (target_folder, default_path.file_name()) {
+                (Some(folder), Some(file_name)) => {
+                    Path::new(folder.trim_matches('/')).join(file_name)
+                }
+                _ => default_path,
+            };
+            let path = unique_path(path, &mut used_paths);
+
+            match self.deliver_image(&image, &path.to_string_lossy()) {
+                Ok(delivery) => {
+                    report.succeeded += 1;
+                    report.delivered.push(ImageBatchDelivery {
+                        image_id: image_id.clone(),
+                        delivery,
+                    });
+                }
+                Err(e) => report.fail(image_id, e),
+            }
+        }
+
+        info!(
+            requested = report.requested,
+            delivered = report.succeeded,
+            "Delivered images"
+        );
+        Ok(report)
+    }
+}
+
+fn check_batch_size(image_ids: &[String]) -> ServiceResult<()> {
+    if image_ids.is_empty() || image_ids.len() > MAX_BATCH_IMAGES {
+        return Err(ServiceError::InvalidRequest {
+            message: format!(
+                "A batch must name between 1 and {} images",
+                MAX_BATCH_IMAGES
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// `path`, or `path` with `_2`, `_3`... before its extension if already used
+fn unique_path(path: PathBuf, used: &mut HashSet<PathBuf>) -> PathBuf {
+    if used.insert(path.clone()) {
+        return path;
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{}_{}{}", stem, n, extension)))
+        .find(|candidate| used.insert(candidate.clone()))
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_path() {
+        let mut used = HashSet::new();
+        let path = PathBuf::from("seneschal/Core/page_12.webp");
+
+        assert_eq!(unique_path(path.clone(), &mut used), path);
+        assert_eq!(
+            unique_path(path.clone(), &mut used),
+            PathBuf::from("seneschal/Core/page_12_2.webp")
+        );
+        assert_eq!(
+            unique_path(path, &mut used),
+            PathBuf::from("seneschal/Core/page_12_3.webp")
+        );
+
+        let report =
+            ImageBatchReport::from_updated(&["a".to_string(), "b".to_string()], &["b".to_string()]);
+        assert_eq!(report.succeeded, 1);
+        assert_eq!(report.failed[0].image_id, "a");
+    }
+}
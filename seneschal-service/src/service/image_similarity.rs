@@ -0,0 +1,184 @@
+//! Image similarity search by example.
+//!
+//! Indexed images are searchable through embeddings of their vision captions.
+//! To find images like an example, the example is captioned with the same
+//! vision model, the caption is embedded, and the result is compared against
+//! `document_image_embeddings`. Examples can be uploaded directly, referenced
+//! by FVTT path (fetched through the connected GM client), or be an image that
+//! is already indexed.
+
+use base64::Engine;
+use tracing::debug;
+
+use crate::db::DocumentImageWithAccess;
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::{ModelTask, SeneschalService};
+
+/// Prompt for describing an example image. Kept close to the captioning prompt
+/// so descriptions land near indexed captions in embedding space.
+const EXAMPLE_IMAGE_PROMPT: &str = "Describe this image from a tabletop RPG. \
+    Focus on what the image depicts (characters, creatures, locations, items, maps, etc.), \
+    its art style, and any text visible in the image. Be concise but descriptive.";
+
+/// Result of a search by example image
+pub struct SimilarImages {
+    /// Vision model description of the example (None when searching from an indexed image)
+    pub description: Option<String>,
+    pub images: Vec<(DocumentImageWithAccess, f32)>,
+}
+
+impl SeneschalService {
+    /// Find indexed images similar to uploaded image data.
+    pub async fn search_images_by_example(
+        &self,
+        image_data: &[u8],
+        user_role: u8,
+        limit: usize,
+    ) -> ServiceResult<SimilarImages> {
+        let vision_model = self.require_vision_model()?;
+        let description = self
+            .describe_example_image(image_data, &vision_model)
+            .await?;
+        let embedding = self.search.embed_text(&description).await?;
+        let images = self.db.search_images(&embedding, user_role, limit, None)?;
+
+        Ok(SimilarImages {
+            description: Some(description),
+            images,
+        })
+    }
+
+    /// Find indexed images similar to an image in FVTT's data directory.
+    ///
+    /// Descriptions are cached per path, so repeated searches skip the vision model.
+    pub async fn search_images_by_fvtt_path(
+        &self,
+        image_path: &str,
+        user_role: u8,
+        limit: usize,
+    ) -> ServiceResult<SimilarImages> {
+        let vision_model = self.require_vision_model()?;
+
+        let (description, embedding) = match self
+            .db
+            .get_fvtt_image_description(image_path, &vision_model)?
+        {
+            Some((description, Some(embedding))) => {
+                debug!(image_path = %image_path, "Using cached FVTT image description");
+                (description, embedding)
+            }
+            _ => {
+                let image_data = self.fetch_fvtt_image(image_path).await?;
+                let description = self
+                    .describe_example_image(&image_data, &vision_model)
+                    .await?;
+                let embedding = self.search.embed_text(&description).await?;
+                self.db.upsert_fvtt_image_description(
+                    image_path,
+                    &vision_model,
+                    &description,
+                    &embedding,
+                )?;
+                (description, embedding)
+            }
+        };
+
+        let images = self.db.search_images(&embedding, user_role, limit, None)?;
+        Ok(SimilarImages {
+            description: Some(description),
+            images,
+        })
+    }
+
+    /// Find indexed images similar to another indexed image, excluding the image itself.
+    pub fn search_images_like(
+        &self,
+        image_id: &str,
+        user_role: u8,
+        limit: usize,
+    ) -> ServiceResult<SimilarImages> {
+        let source = self
+            .db
+            .get_document_image(image_id)?
+            .filter(|img| img.access_level.accessible_by(user_role))
+            .ok_or_else(|| ServiceError::ImageNotFound {
+                image_id: image_id.to_string(),
+            })?;
+
+        let embedding =
+            self.db
+                .get_image_embedding(image_id)?
+                .ok_or_else(|| ServiceError::InvalidRequest {
+                    message: format!("Image {} has not been captioned yet", image_id),
+                })?;
+
+        let mut images = self
+            .db
+            .search_images(&embedding, user_role, limit + 1, None)?;
+        images.retain(|(img, _)| img.image.id != source.image.id);
+        images.truncate(limit);
+
+        Ok(SimilarImages {
+            description: None,
+            images,
+        })
+    }
+
+    fn require_vision_model(&self) -> ServiceResult<String> {
+        self.primary_model(ModelTask::Captioning)
+            .ok_or_else(|| ServiceError::Config {
+                message: "No vision model configured; set ollama.vision_model to search by image"
+                    .to_string(),
+            })
+    }
+
+    async fn describe_example_image(
+        &self,
+        image_data: &[u8],
+        vision_model: &str,
+    ) -> ServiceResult<String> {
+        let image_base64 = base64::engine::general_purpose::STANDARD.encode(image_data);
+        let message =
+            crate::ollama::ChatMessage::user_with_image(EXAMPLE_IMAGE_PROMPT, image_base64);
+
+        self.generate_routed(ModelTask::Captioning, Some(vision_model), vec![message])
+            .await
+    }
+
+    /// Fetch image bytes from FVTT through the external `image_describe` tool.
+    async fn fetch_fvtt_image(&self, image_path: &str) -> ServiceResult<Vec<u8>> {
+        let timeout = self
+            .runtime_config
+            .dynamic()
+            .agentic_loop
+            .external_tool_timeout();
+
+        let response = self
+            .execute_external_tool_mcp(
+                "image_describe",
+                serde_json::json!({ "image_path": image_path }),
+                timeout,
+            )
+            .await
+            .map_err(|message| ServiceError::InvalidRequest { message })?;
+
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Failed to fetch {}: {}", image_path, error),
+            });
+        }
+
+        let encoded = response
+            .get("image_data")
+            .and_then(|d| d.as_str())
+            .ok_or_else(|| ServiceError::InvalidRequest {
+                message: format!("FVTT returned no image data for {}", image_path),
+            })?;
+
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| ServiceError::InvalidRequest {
+                message: format!("Invalid image data for {}: {}", image_path, e),
+            })
+    }
+}
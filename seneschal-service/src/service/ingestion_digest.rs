@@ -0,0 +1,342 @@
+//! Scheduled digest of newly ingested documents.
+//!
+//! On the configured cron schedule, documents that finished processing since
+//! the previous digest are listed with their page counts and a few key topics
+//! picked out by the default model. The digest is written to an FVTT journal
+//! through the connected GM client so co-GMs learn what is newly searchable.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::db::{Document, ProcessingStatus};
+use crate::error::{OllamaError, ServiceError, ServiceResult};
+use crate::ollama::{ChatMessage, extract_json_object};
+use crate::service::{ModelTask, SeneschalService};
+use crate::system_profile::SystemProfile;
+
+use super::schedule::Schedule;
+use super::session_summary::escape_html;
+
+/// How often the scheduler checks whether the schedule is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The first digest covers documents ingested this many days before it runs
+const FIRST_DIGEST_LOOKBACK_DAYS: i64 = 7;
+
+/// Documents beyond this are counted but not listed
+const MAX_DIGEST_DOCUMENTS: usize = 25;
+
+/// Text from the start of each document sent to the model to pick out topics
+const TOPIC_EXCERPT_CHARS: usize = 4000;
+
+/// A newly searchable document
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestEntry {
+    pub document_id: String,
+    pub title: String,
+    /// Highest page number seen in the document's chunks (none for unpaged formats)
+    pub pages: Option<i32>,
+    pub image_count: usize,
+    pub topics: Vec<String>,
+}
+
+/// A posted digest
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestionDigest {
+    pub since: DateTime<Utc>,
+    pub documents: Vec<DigestEntry>,
+    /// New documents left out of the listing
+    pub omitted: usize,
+    pub journal_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TopicsResponse {
+    #[serde(default)]
+    topics: Vec<String>,
+}
+
+impl SeneschalService {
+    /// Start the background task that posts the ingestion digest on schedule.
+    ///
+    /// The schedule is read from the dynamic config on every check, so changes
+    /// apply without a restart; an empty schedule disables the digest.
+    pub fn start_digest_scheduler(service: Arc<SeneschalService>) {
+        tokio::spawn(async move {
+            info!("Ingestion digest scheduler started");
+            let mut last_fired_minute: Option<i64> = None;
+            let mut reported_invalid: Option<String> = None;
+
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+
+                let expression = service.runtime_config.dynamic().digest.schedule.clone();
+                if expression.is_empty() {
+                    continue;
+                }
+                let schedule = match Schedule::parse(&expression) {
+                    Ok(schedule) => schedule,
+                    Err(e) => {
+                        if reported_invalid.as_deref() != Some(expression.as_str()) {
+                            warn!(schedule = %expression, error = %e, "Invalid digest schedule");
+                            reported_invalid = Some(expression);
+                        }
+                        continue;
+                    }
+                };
+
+                let now = Local::now();
+                let minute = now.timestamp() / 60;
+                if !schedule.matches(&now) || last_fired_minute == Some(minute) {
+                    continue;
+                }
+                last_fired_minute = Some(minute);
+
+                match service.post_ingestion_digest().await {
+                    Ok(Some(digest)) => info!(
+                        documents = digest.documents.len() + digest.omitted,
+                        journal_id = ?digest.journal_id,
+                        "Posted ingestion digest"
+                    ),
+                    Ok(None) => debug!("No newly ingested documents for the digest"),
+                    Err(e) => warn!(error = %e, "Failed to post ingestion digest"),
+                }
+            }
+        });
+    }
+
+    /// Write a digest of documents ingested since the previous one to an FVTT journal.
+    ///
+    /// Returns `None` when there is nothing new. Documents still processing
+    /// are left for the next digest.
+    pub async fn post_ingestion_digest(&self) -> ServiceResult<Option<IngestionDigest>> {
+        let now = Utc::now();
+        let since = self
+            .db
+            .get_last_digest_covered_until()?
+            .unwrap_or_else(|| now - chrono::Duration::days(FIRST_DIGEST_LOOKBACK_DAYS));
+
+        let documents: Vec<Document> = self
+            .db
+            .list_documents(None)?
+            .into_iter()
+            .filter(|doc| doc.created_at > since)
+            .collect();
+        let covered_until = documents
+            .iter()
+            .filter(|doc| doc.processing_status == ProcessingStatus::Processing)
+            .map(|doc| doc.created_at)
+            .min()
+            .unwrap_or(now);
+
+        let mut new_documents: Vec<Document> = documents
+            .into_iter()
+            .filter(|doc| {
+                doc.processing_status == ProcessingStatus::Completed
+                    && doc.created_at < covered_until
+            })
+            .collect();
+        if new_documents.is_empty() {
+            return Ok(None);
+        }
+        new_documents.sort_by_key(|doc| doc.created_at);
+
+        let document_count = new_documents.len();
+        let omitted = document_count.saturating_sub(MAX_DIGEST_DOCUMENTS);
+        let mut entries = Vec::new();
+        for document in new_documents.into_iter().take(MAX_DIGEST_DOCUMENTS) {
+            entries.push(self.digest_entry(document).await?);
+        }
+
+        let title = format!("New in the Library: {}", Local::now().format("%Y-%m-%d"));
+        let journal_id = self
+            .write_digest_journal(&title, &digest_to_html(since, &entries, omitted))
+            .await
+            .map_err(|message| ServiceError::InvalidRequest { message })?;
+
+        self.db
+            .insert_ingestion_digest(covered_until, document_count, journal_id.as_deref())?;
+
+        Ok(Some(IngestionDigest {
+            since,
+            documents: entries,
+            omitted,
+            journal_id,
+        }))
+    }
+
+    async fn digest_entry(&self, document: Document) -> ServiceResult<DigestEntry> {
+        let chunks = self.db.get_document_chunks(&document.id)?;
+        let pages = chunks.iter().filter_map(|c| c.page_number).max();
+
+        let mut excerpt = String::new();
+        for chunk in &chunks {
+            if excerpt.len() >= TOPIC_EXCERPT_CHARS {
+                break;
+            }
+            excerpt.push_str(&chunk.content);
+            excerpt.push('\n');
+        }
+
+        // A digest without topics is still worth posting
+        let profile = self.document_system_profile(&document.id);
+        let topics = match self
+            .document_topics(&document.title, &excerpt, profile)
+            .await
+        {
+            Ok(topics) => topics,
+            Err(e) => {
+                warn!(doc_id = %document.id, error = %e, "Failed to pick digest topics");
+                Vec::new()
+            }
+        };
+
+        Ok(DigestEntry {
+            document_id: document.id,
+            title: document.title,
+            pages,
+            image_count: document.image_count,
+            topics,
+        })
+    }
+
+    async fn document_topics(
+        &self,
+        title: &str,
+        excerpt: &str,
+        profile: &SystemProfile,
+    ) -> ServiceResult<Vec<String>> {
+        if excerpt.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let excerpt: String = excerpt.chars().take(TOPIC_EXCERPT_CHARS).collect();
+        let prompt = format!(
+            "The following is the start of \"{}\", a {} document just added \
+            to a GM's reference library. List 3 to 6 key topics it covers (places, factions, \
+            rules, adventures, equipment), each a few words long.\n\n{}\n\n\
+            Respond with only JSON in this shape: {{\"topics\": [\"...\"]}}",
+            title, profile.name, excerpt
+        );
+
+        let response = self
+            .generate_routed(
+                ModelTask::Summarization,
+                None,
+                vec![ChatMessage::user(prompt)],
+            )
+            .await?;
+
+        let parsed: TopicsResponse = serde_json::from_str(extract_json_object(&response))
+            .map_err(|e| OllamaError::InvalidResponse { source: e })?;
+        Ok(parsed.topics)
+    }
+
+    /// Create the digest journal, returning its ID.
+    async fn write_digest_journal(
+        &self,
+        title: &str,
+        content: &str,
+    ) -> Result<Option<String>, String> {
+        let timeout = self
+            .runtime_config
+            .dynamic()
+            .agentic_loop
+            .external_tool_timeout();
+
+        let mut args = serde_json::json!({
+            "name": title,
+            "content": content,
+        });
+        let folder = self.runtime_config.dynamic().digest.journal_folder.clone();
+        if !folder.is_empty() {
+            args["folder"] = serde_json::Value::from(folder);
+        }
+
+        let response = self
+            .execute_external_tool_mcp("create_journal", args, timeout)
+            .await?;
+
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            return Err(error.to_string());
+        }
+        Ok(response
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string))
+    }
+}
+
+fn digest_to_html(since: DateTime<Utc>, entries: &[DigestEntry], omitted: usize) -> String {
+    let mut html = format!(
+        "<p>{} documents finished processing since {} and can now be searched.</p><ul>",
+        entries.len() + omitted,
+        since.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+
+    for entry in entries {
+        let mut details = Vec::new();
+        if let Some(pages) = entry.pages {
+            details.push(format!("{} pages", pages));
+        }
+        if entry.image_count > 0 {
+            details.push(format!("{} images", entry.image_count));
+        }
+
+        html.push_str(&format!(
+            "<li><strong>{}</strong>",
+            escape_html(&entry.title)
+        ));
+        if !details.is_empty() {
+            html.push_str(&format!(" ({})", details.join(", ")));
+        }
+        if !entry.topics.is_empty() {
+            let topics: Vec<String> = entry.topics.iter().map(|t| escape_html(t)).collect();
+            html.push_str(&format!("<br>Topics: {}", topics.join(", ")));
+        }
+        html.push_str("</li>");
+    }
+    html.push_str("</ul>");
+
+    if omitted > 0 {
+        html.push_str(&format!("<p>...and {} more.</p>", omitted));
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_to_html() {
+        let entries = vec![
+            DigestEntry {
+                document_id: "a".to_string(),
+                title: "Deepnight <Revelation>".to_string(),
+                pages: Some(212),
+                image_count: 40,
+                topics: vec!["Deepnight Endeavour".to_string(), "Jump drives".to_string()],
+            },
+            DigestEntry {
+                document_id: "b".to_string(),
+                title: "Session notes".to_string(),
+                pages: None,
+                image_count: 0,
+                topics: vec![],
+            },
+        ];
+
+        let html = digest_to_html(Utc::now(), &entries, 3);
+        assert!(html.starts_with("<p>5 documents finished processing"));
+        assert!(html.contains(
+            "<li><strong>Deepnight &lt;Revelation&gt;</strong> (212 pages, 40 images)<br>Topics: Deepnight Endeavour, Jump drives</li>"
+        ));
+        assert!(html.contains("<li><strong>Session notes</strong></li>"));
+        assert!(html.ends_with("<p>...and 3 more.</p>"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Foundry VTT journal entry import.
+//!
+//! The FVTT module ships journal entries over WebSocket. Each entry is stored
+//! as a Markdown document tagged with `source: fvtt` in its metadata and then
+//! processed by the regular document worker, so in-world lore is searchable
+//! alongside uploaded PDFs. Re-sending an entry replaces its content only when
+//! something actually changed.
+
+use tracing::info;
+
+use crate::db::ProcessingStatus;
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::fvtt::{JournalPage, journal_access_level, journal_to_markdown};
+use crate::ingestion::hash::compute_content_hash;
+use crate::service::SeneschalService;
+
+/// Tag applied to every document imported from a journal entry
+const FVTT_JOURNAL_TAG: &str = "fvtt-journal";
+
+/// Outcome of syncing a single journal entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalSyncStatus {
+    /// Content was new or changed and has been queued for processing
+    Queued,
+    /// Content matches what is already indexed
+    Unchanged,
+    /// The entry has no text content; any previously indexed copy was removed
+    Empty,
+}
+
+impl JournalSyncStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalSyncStatus::Queued => "queued",
+            JournalSyncStatus::Unchanged => "unchanged",
+            JournalSyncStatus::Empty => "empty",
+        }
+    }
+}
+
+impl SeneschalService {
+    /// Import or re-sync a journal entry, returning the backing document ID (if any).
+    ///
+    /// New entries are placed in the world of the GM client that sent them,
+    /// or shared when it didn't say which world it is in. The document gets the
+    /// most restrictive access level of its pages.
+    pub async fn sync_fvtt_journal(
+        &self,
+        journal_id: &str,
+        name: &str,
+        pages: &[JournalPage],
+        world_id: Option<&str>,
+    ) -> ServiceResult<(JournalSyncStatus, Option<String>)> {
+        let markdown = journal_to_markdown(name, pages);
+        let access_level = journal_access_level(pages);
+        let existing = self.db.get_document_by_fvtt_journal_id(journal_id)?;
+
+        if markdown.trim().is_empty() {
+            if let Some(doc) = existing {
+                self.delete_document(&doc.id)?;
+                info!(journal_id = %journal_id, doc_id = %doc.id, "Removed emptied FVTT journal document");
+            }
+            return Ok((JournalSyncStatus::Empty, None));
+        }
+
+        let content_hash = compute_content_hash(markdown.as_bytes());
+
+        let Some(doc) = existing else {
+            let filename = format!("fvtt_journal_{}.md", journal_id);
+            let document = self
+                .upload_document(
+                    markdown.as_bytes(),
+                    &filename,
+                    name,
+                    access_level,
+                    vec![FVTT_JOURNAL_TAG.to_string()],
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+            self.db.update_document_metadata(
+                &document.id,
+                Some(serde_json::json!({
+                    "source": "fvtt",
+                    "fvtt_journal_id": journal_id,
+                })),
+            )?;
+            if world_id.is_some() {
+                self.db.set_document_world(&document.id, world_id)?;
+            }
+            info!(journal_id = %journal_id, doc_id = %document.id, "Imported FVTT journal entry");
+            return Ok((JournalSyncStatus::Queued, Some(document.id)));
+        };
+
+        if doc.file_hash.as_deref() == Some(content_hash.as_str())
+            && doc.title == name
+            && doc.access_level == access_level
+        {
+            return Ok((JournalSyncStatus::Unchanged, Some(doc.id)));
+        }
+
+        // Content, title or visibility changed: replace the file and re-chunk.
+        // Chunks copy the document's access level, so a visibility change needs this too.
+        self.cancel_document_processing(&doc.id);
+
+        let file_path = doc.source_file()?;
+        std::fs::write(file_path, markdown.as_bytes())
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+
+        self.db
+            .update_document(&doc.id, name, access_level, doc.tags.clone())?;
+        self.db.update_document_hash(&doc.id, &content_hash)?;
+        self.db.delete_document_chunks(&doc.id)?;
+        self.db.update_document_progress(&doc.id, "queued", 0, 1)?;
+        self.db
+            .update_document_processing_status(&doc.id, ProcessingStatus::Processing, None)?;
+
+        info!(journal_id = %journal_id, doc_id = %doc.id, "Re-queued changed FVTT journal entry");
+        Ok((JournalSyncStatus::Queued, Some(doc.id)))
+    }
+
+    /// Remove the document backing a deleted journal entry.
+    pub fn remove_fvtt_journal(&self, journal_id: &str) -> ServiceResult<bool> {
+        match self.db.get_document_by_fvtt_journal_id(journal_id)? {
+            Some(doc) => {
+                info!(journal_id = %journal_id, doc_id = %doc.id, "Removing FVTT journal document");
+                self.delete_document(&doc.id)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! In-universe Library Data lookups.
+//!
+//! Answers are assembled from retrieved chunks only, never generated, so an
+//! in-character lookup can't invent lore. When nothing retrieved is relevant
+//! enough, the lookup reports that no data is available rather than
+//! offering the closest miss.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::error::ServiceResult;
+use crate::search::ErrataStatus;
+use crate::service::SeneschalService;
+use crate::tools::{AccessLevel, SearchFilters};
+
+/// Relevance below which a chunk isn't used
+pub const DEFAULT_MIN_CONFIDENCE: f32 = 0.6;
+
+/// Chunks retrieved per lookup
+const CANDIDATE_CHUNKS: usize = 8;
+
+/// Chunks an entry is drawn from
+const MAX_SOURCE_CHUNKS: usize = 3;
+
+/// Longest excerpt, in characters
+const MAX_EXCERPT_CHARS: usize = 900;
+
+/// Where part of an entry came from
+#[derive(Debug, Clone, Serialize)]
+pub struct LibrarySource {
+    pub document_title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    pub chunk_id: String,
+}
+
+/// A Library Data entry
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryEntry {
+    pub entry: String,
+    pub classification: &'static str,
+    pub excerpt: String,
+    pub sources: Vec<LibrarySource>,
+    pub confidence: f32,
+}
+
+impl LibraryEntry {
+    /// The entry as an in-universe terminal readout
+    pub fn render(&self) -> String {
+        let sources = self
+            .sources
+            .iter()
+            .map(|source| match source.page {
+                Some(page) => format!("{} p.{}", source.document_title, page),
+                None => source.document_title.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        format!(
+            "LIBRARY DATA\nENTRY: {}\nCLASSIFICATION: {}\n\n{}\n\n[Sources: {}]",
+            self.entry, self.classification, self.excerpt, sources
+        )
+    }
+}
+
+/// The readout when nothing relevant was retrieved
+pub fn no_data_readout(topic: &str) -> String {
+    format!(
+        "LIBRARY DATA\nENTRY: {}\n\nNO DATA AVAILABLE",
+        topic.trim().to_uppercase()
+    )
+}
+
+/// In-universe classification for the most restricted source
+pub fn classification(level: AccessLevel) -> &'static str {
+    match level {
+        AccessLevel::Player => "PUBLIC",
+        AccessLevel::Trusted => "RESTRICTED",
+        AccessLevel::Assistant => "CONFIDENTIAL",
+        AccessLevel::GmOnly => "CLASSIFIED",
+    }
+}
+
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            let sentence = text[start..=i].trim();
+            if sentence.len() > 1 {
+                sentences.push(sentence);
+            }
+            start = i + c.len_utf8();
+        }
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        sentences.push(rest);
+    }
+    sentences
+}
+
+/// Sentences from the chunks, best chunk first, that mention the topic; the
+/// best chunk's opening sentences if none do
+pub(crate) fn excerpt(topic: &str, contents: &[&str], max_chars: usize) -> String {
+    let terms: Vec<String> = topic
+        .split_whitespace()
+        .map(|t| {
+            t.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|t| t.len() >= 3)
+        .collect();
+    let mentions = |sentence: &str| {
+        let lower = sentence.to_lowercase();
+        terms.iter().any(|term| lower.contains(term.as_str()))
+    };
+
+    let mut picked: Vec<&str> = contents
+        .iter()
+        .flat_map(|content| sentences(content))
+        .filter(|sentence| mentions(sentence))
+        .collect();
+    if picked.is_empty()
+        && let Some(best) = contents.first()
+    {
+        picked = sentences(best);
+    }
+
+    let mut excerpt = String::new();
+    for sentence in picked {
+        if excerpt.contains(sentence) {
+            continue;
+        }
+        if !excerpt.is_empty() && excerpt.len() + sentence.len() + 1 > max_chars {
+            break;
+        }
+        if !excerpt.is_empty() {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(sentence);
+    }
+    excerpt
+}
+
+impl SeneschalService {
+    /// Look a topic up in the library, from chunks readable at the given
+    /// access level. `None` when nothing retrieved is relevant enough.
+    pub async fn library_data(
+        &self,
+        topic: &str,
+        access_level: u8,
+        min_confidence: f32,
+    ) -> ServiceResult<Option<LibraryEntry>> {
+        let filters = self.mcp_world_id().map(|world_id| SearchFilters {
+            world_id: Some(world_id),
+            ..Default::default()
+        });
+        let results = self
+            .search(topic, access_level, CANDIDATE_CHUNKS, filters)
+            .await?;
+
+        let used: Vec<_> = results
+            .into_iter()
+            .filter(|r| r.similarity >= min_confidence)
+            .filter(|r| !matches!(r.errata, Some(ErrataStatus::Superseded { .. })))
+            .take(MAX_SOURCE_CHUNKS)
+            .collect();
+        let Some(confidence) = used.first().map(|r| r.similarity) else {
+            return Ok(None);
+        };
+
+        let mut titles: HashMap<String, String> = HashMap::new();
+        let mut sources = Vec::with_capacity(used.len());
+        for result in &used {
+            let chunk = &result.chunk;
+            if !titles.contains_key(&chunk.document_id) {
+                let title = self
+                    .db
+                    .get_document(&chunk.document_id)?
+                    .map(|doc| doc.title)
+                    .unwrap_or_else(|| chunk.document_id.clone());
+                titles.insert(chunk.document_id.clone(), title);
+            }
+            sources.push(LibrarySource {
+                document_title: titles[&chunk.document_id].clone(),
+                page: chunk.page_number,
+                chunk_id: chunk.id.clone(),
+            });
+        }
+
+        let contents: Vec<&str> = used.iter().map(|r| r.chunk.content.as_str()).collect();
+        let level = used
+            .iter()
+            .map(|r| r.chunk.access_level)
+            .max_by_key(|level| *level as u8)
+            .unwrap_or(AccessLevel::Player);
+
+        Ok(Some(LibraryEntry {
+            entry: topic.trim().to_uppercase(),
+            classification: classification(level),
+            excerpt: excerpt(topic, &contents, MAX_EXCERPT_CHARS),
+            sources,
+            confidence,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excerpt_prefers_sentences_on_topic() {
+        let contents = [
+            "The Travellers' Aid Society maintains hostels at class A starports. Dues are steep.",
+            "Membership in the Society is by invitation. The Imperium is vast.",
+        ];
+        assert_eq!(
+            excerpt("Travellers' Aid Society", &contents, 900),
+            "The Travellers' Aid Society maintains hostels at class A starports. Membership in the Society is by invitation."
+        );
+    }
+
+    #[test]
+    fn test_excerpt_falls_back_and_truncates() {
+        let contents = ["First sentence here. Second sentence here. Third one."];
+        assert_eq!(
+            excerpt("Zhodani", &contents, 45),
+            "First sentence here. Second sentence here."
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let entry = LibraryEntry {
+            entry: "REGINA".to_string(),
+            classification: classification(AccessLevel::Player),
+            excerpt: "Regina is the subsector capital.".to_string(),
+            sources: vec![LibrarySource {
+                document_title: "Spinward Marches".to_string(),
+                page: Some(12),
+                chunk_id: "c1".to_string(),
+            }],
+            confidence: 0.8,
+        };
+        let text = entry.render();
+        assert!(text.starts_with("LIBRARY DATA\nENTRY: REGINA\nCLASSIFICATION: PUBLIC"));
+        assert!(text.ends_with("[Sources: Spinward Marches p.12]"));
+        assert!(no_data_readout("regina").ends_with("NO DATA AVAILABLE"));
+    }
+}
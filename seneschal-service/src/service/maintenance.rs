@@ -0,0 +1,290 @@
+//! Database maintenance.
+//!
+//! Long-running installs gather garbage: rows left behind by interrupted
+//! ingestions, image and source files whose rows were deleted, image rows
+//! whose files went missing, and free pages after large deletions. A run
+//! checks integrity first and only cleans up a sound database. VACUUM
+//! rewrites the whole file and blocks writers, so it waits until MCP tools
+//! haven't been used for a while and no document is processing.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::db::ProcessingStatus;
+use crate::error::ServiceResult;
+use crate::service::SeneschalService;
+
+use super::schedule::Schedule;
+
+/// How often the scheduler checks whether the schedule is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Unreferenced files younger than this may belong to an upload or
+/// ingestion that hasn't stored its rows yet
+const ORPHAN_FILE_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Outcome of a maintenance run
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceReport {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Problems reported by the integrity check; cleanup is skipped unless empty
+    pub integrity_errors: Vec<String>,
+    /// Rows removed because their parent row was gone
+    pub orphaned_rows: usize,
+    /// Image rows removed because their file was missing
+    pub missing_image_files: usize,
+    /// Stored files removed because no row referred to them
+    pub orphaned_files: usize,
+    pub vacuumed: bool,
+    /// Why VACUUM didn't run, if it didn't
+    pub vacuum_skipped: Option<String>,
+    /// Bytes freed on disk by removed files and the smaller database file
+    pub bytes_reclaimed: u64,
+}
+
+impl SeneschalService {
+    /// Start the background task that runs maintenance on schedule.
+    ///
+    /// The schedule is read from the dynamic config on every check, so changes
+    /// apply without a restart; an empty schedule disables it.
+    pub fn start_maintenance_scheduler(service: Arc<SeneschalService>) {
+        tokio::spawn(async move {
+            info!("Maintenance scheduler started");
+            let mut last_fired_minute: Option<i64> = None;
+            let mut reported_invalid: Option<String> = None;
+
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+
+                let expression = service
+                    .runtime_config
+                    .dynamic()
+                    .maintenance
+                    .schedule
+                    .clone();
+                if expression.is_empty() {
+                    continue;
+                }
+                let schedule = match Schedule::parse(&expression) {
+                    Ok(schedule) => schedule,
+                    Err(e) => {
+                        if reported_invalid.as_deref() != Some(expression.as_str()) {
+                            warn!(schedule = %expression, error = %e, "Invalid maintenance schedule");
+                            reported_invalid = Some(expression);
+                        }
+                        continue;
+                    }
+                };
+
+                let now = Local::now();
+                let minute = now.timestamp() / 60;
+                if !schedule.matches(&now) || last_fired_minute == Some(minute) {
+                    continue;
+                }
+                last_fired_minute = Some(minute);
+
+                let worker = service.clone();
+                match tokio::task::spawn_blocking(move || worker.run_maintenance(true)).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => warn!(error = %e, "Database maintenance failed"),
+                    Err(e) => warn!(error = %e, "Database maintenance task panicked"),
+                }
+            }
+        });
+    }
+
+    /// Check integrity, remove orphaned rows and files, and VACUUM when
+    /// `vacuum` is set and the service is idle.
+    ///
+    /// Blocks on file and database I/O; call it off the async runtime.
+    pub fn run_maintenance(&self, vacuum: bool) -> ServiceResult<MaintenanceReport> {
+        let started = Instant::now();
+        let size_before = self.db.database_size()?;
+
+        let mut report = MaintenanceReport {
+            started_at: Utc::now(),
+            duration_ms: 0,
+            integrity_errors: self.db.integrity_check()?,
+            orphaned_rows: 0,
+            missing_image_files: 0,
+            orphaned_files: 0,
+            vacuumed: false,
+            vacuum_skipped: None,
+            bytes_reclaimed: 0,
+        };
+
+        if report.integrity_errors.is_empty() {
+            report.orphaned_rows = self.db.prune_orphaned_rows()?;
+
+            let missing: Vec<String> = self
+                .db
+                .settled_image_paths()?
+                .into_iter()
+                .filter(|(_, path)| !Path::new(path).exists())
+                .map(|(id, _)| id)
+                .collect();
+            report.missing_image_files = self.db.delete_images_by_id(&missing)?;
+
+            let (files, bytes) = self.remove_orphaned_files()?;
+            report.orphaned_files = files;
+            report.bytes_reclaimed += bytes;
+
+            report.vacuum_skipped = if vacuum {
+                self.vacuum_blocker()?
+            } else {
+                Some("not requested".to_string())
+            };
+            if report.vacuum_skipped.is_none() {
+                self.db.vacuum()?;
+                report.vacuumed = true;
+            }
+        } else {
+            warn!(
+                errors = ?report.integrity_errors,
+                "Database integrity check failed; skipping cleanup"
+            );
+            report.vacuum_skipped = Some("integrity check failed".to_string());
+        }
+
+        report.bytes_reclaimed += size_before.saturating_sub(self.db.database_size()?);
+        report.duration_ms = started.elapsed().as_millis() as u64;
+
+        info!(
+            orphaned_rows = report.orphaned_rows,
+            missing_image_files = report.missing_image_files,
+            orphaned_files = report.orphaned_files,
+            vacuumed = report.vacuumed,
+            bytes_reclaimed = report.bytes_reclaimed,
+            "Database maintenance finished"
+        );
+        *self.last_maintenance.lock().unwrap() = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Why VACUUM shouldn't run right now, if anything
+    fn vacuum_blocker(&self) -> ServiceResult<Option<String>> {
+        let idle = Duration::from_secs(self.runtime_config.dynamic().maintenance.vacuum_idle_secs);
+        let recently_used = self
+            .last_interactive_activity
+            .lock()
+            .unwrap()
+            .is_some_and(|last| last.elapsed() < idle);
+        if recently_used {
+            return Ok(Some("MCP tools were used recently".to_string()));
+        }
+
+        let processing = self
+            .db
+            .list_documents(None)?
+            .iter()
+            .any(|doc| doc.processing_status == ProcessingStatus::Processing);
+        if processing {
+            return Ok(Some("documents are processing".to_string()));
+        }
+
+        Ok(None)
+    }
+
+    /// Delete stored images and source files no row refers to, and cached
+    /// thumbnails of deleted documents. Returns the files removed and their size.
+    fn remove_orphaned_files(&self) -> ServiceResult<(usize, u64)> {
+        let data_dir = &self.runtime_config.static_config.storage.data_dir;
+        // Compare canonical paths so a differently spelled data_dir can't
+        // make every file look unreferenced
+        let referenced: HashSet<PathBuf> = self
+            .db
+            .referenced_file_paths()?
+            .into_iter()
+            .filter_map(|path| std::fs::canonicalize(path).ok())
+            .collect();
+
+        let mut removed = 0;
+        let mut bytes = 0;
+        for dir in ["images", "documents"] {
+            for file in files_under(&data_dir.join(dir)) {
+                let Ok(canonical) = std::fs::canonicalize(&file) else {
+                    continue;
+                };
+                let Ok(metadata) = std::fs::metadata(&file) else {
+                    continue;
+                };
+                let recent = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_none_or(|age| age < ORPHAN_FILE_MIN_AGE);
+                if referenced.contains(&canonical) || recent {
+                    continue;
+                }
+                match std::fs::remove_file(&file) {
+                    Ok(()) => {
+                        removed += 1;
+                        bytes += metadata.len();
+                    }
+                    Err(e) => {
+                        warn!(path = %file.display(), error = %e, "Failed to delete orphaned file")
+                    }
+                }
+            }
+        }
+
+        // Thumbnails are cached per document
+        let document_ids: HashSet<String> = self
+            .db
+            .list_documents(None)?
+            .into_iter()
+            .map(|doc| doc.id)
+            .collect();
+        if let Ok(entries) = std::fs::read_dir(data_dir.join("thumbnails")) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_dir() || document_ids.contains(&*entry.file_name().to_string_lossy()) {
+                    continue;
+                }
+                let files = files_under(&path);
+                let size: u64 = files
+                    .iter()
+                    .filter_map(|file| std::fs::metadata(file).ok())
+                    .map(|metadata| metadata.len())
+                    .sum();
+                match std::fs::remove_dir_all(&path) {
+                    Ok(()) => {
+                        removed += files.len();
+                        bytes += size;
+                    }
+                    Err(e) => {
+                        warn!(path = %path.display(), error = %e, "Failed to delete orphaned thumbnails")
+                    }
+                }
+            }
+        }
+
+        Ok((removed, bytes))
+    }
+}
+
+/// Every file below a directory (none if it doesn't exist)
+pub(super) fn files_under(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => pending.push(entry.path()),
+                Ok(kind) if kind.is_file() => files.push(entry.path()),
+                _ => {}
+            }
+        }
+    }
+    files
+}
@@ -0,0 +1,152 @@
+//! FVTT scene settings for gridded map images.
+//!
+//! Deck plans are drawn at whatever resolution the book used, so the scene
+//! is scaled to FVTT's standard grid size rather than the image's own grid
+//! pitch, and the background is shifted so the first grid line sits on the
+//! scene's edge (as FVTT's own grid configuration tool does).
+
+use serde::Serialize;
+use tracing::info;
+
+use crate::db::{DocumentImage, ImageGrid};
+use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::IngestionService;
+use crate::ingestion::grid::{DetectedGrid, detect_grid_in_file};
+use crate::service::{ImageDelivery, SeneschalService};
+
+/// Pixels per grid square in the scene
+const SCENE_GRID_SIZE: u32 = 100;
+
+/// Scene padding around the map, as a fraction of the scene size
+const SCENE_PADDING: f32 = 0.1;
+
+/// One deck plan square in Traveller is 1.5 meters
+const DEFAULT_GRID_DISTANCE: f32 = 1.5;
+const DEFAULT_GRID_UNITS: &str = "m";
+
+/// Settings for an FVTT scene showing a map image
+#[derive(Debug, Clone, Serialize)]
+pub struct SceneSettings {
+    pub name: String,
+    /// FVTT path of the background image
+    pub image_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub padding: f32,
+    pub grid_type: &'static str,
+    pub grid_size: u32,
+    pub grid_distance: f32,
+    pub grid_units: String,
+    /// Background shift that puts the first grid line on the scene's edge
+    pub background_offset_x: i32,
+    pub background_offset_y: i32,
+}
+
+/// A map image delivered for FVTT with the scene to show it in
+#[derive(Debug, Clone, Serialize)]
+pub struct MapScene {
+    pub grid: ImageGrid,
+    pub scene: SceneSettings,
+    pub delivery: ImageDelivery,
+}
+
+impl SeneschalService {
+    /// Detect and store the grid of a map image.
+    ///
+    /// This is blocking work and should be run off the async runtime.
+    pub fn detect_image_grid(&self, image: &DocumentImage) -> ServiceResult<Option<ImageGrid>> {
+        let detected = detect_grid_in_file(std::path::Path::new(&image.internal_path))?;
+        detected
+            .map(|detected| self.store_image_grid(&image.id, detected))
+            .transpose()
+    }
+
+    /// Store a detected grid for an image
+    pub fn store_image_grid(
+        &self,
+        image_id: &str,
+        detected: DetectedGrid,
+    ) -> ServiceResult<ImageGrid> {
+        let grid = ImageGrid {
+            image_id: image_id.to_string(),
+            pitch: detected.pitch,
+            offset_x: detected.offset_x,
+            offset_y: detected.offset_y,
+            columns: detected.columns,
+            rows: detected.rows,
+            confidence: detected.confidence,
+        };
+        self.db.set_image_grid(&grid)?;
+        info!(
+            image_id,
+            pitch = grid.pitch,
+            columns = grid.columns,
+            rows = grid.rows,
+            "Detected map grid"
+        );
+        Ok(grid)
+    }
+
+    /// Deliver a map image and work out the FVTT scene for it.
+    ///
+    /// The grid is detected first if it hasn't been. This is blocking work
+    /// and should be run off the async runtime.
+    pub fn map_scene(
+        &self,
+        image_id: &str,
+        grid_distance: Option<f32>,
+        grid_units: Option<&str>,
+    ) -> ServiceResult<MapScene> {
+        let image =
+            self.db
+                .get_document_image(image_id)?
+                .ok_or_else(|| ServiceError::ImageNotFound {
+                    image_id: image_id.to_string(),
+                })?;
+        let grid = match self.db.get_image_grid(image_id)? {
+            Some(grid) => grid,
+            None => self.detect_image_grid(&image.image)?.ok_or_else(|| {
+                ServiceError::InvalidRequest {
+                    message: "No square grid found in the image".to_string(),
+                }
+            })?,
+        };
+
+        let path = IngestionService::fvtt_image_path(
+            &image.document_title,
+            image.image.page_number,
+            image.image.description.as_deref(),
+        );
+        let delivery = self.deliver_image(&image, &path.to_string_lossy())?;
+        let image_path = match &delivery {
+            ImageDelivery::Direct { fvtt_path } => fvtt_path.clone(),
+            ImageDelivery::Shuttle { suggested_path } => suggested_path.clone(),
+        };
+
+        let scale = SCENE_GRID_SIZE as f32 / grid.pitch;
+        let scaled = |pixels: Option<u32>, squares: u32| {
+            pixels
+                .map(|p| (p as f32 * scale).round() as u32)
+                .unwrap_or(squares * SCENE_GRID_SIZE)
+        };
+        let scene = SceneSettings {
+            name: format!("{} p{}", image.document_title, image.image.page_number),
+            image_path,
+            width: scaled(image.image.width, grid.columns),
+            height: scaled(image.image.height, grid.rows),
+            padding: SCENE_PADDING,
+            grid_type: "square",
+            grid_size: SCENE_GRID_SIZE,
+            grid_distance: grid_distance.unwrap_or(DEFAULT_GRID_DISTANCE),
+            grid_units: grid_units.unwrap_or(DEFAULT_GRID_UNITS).to_string(),
+            background_offset_x: -(grid.offset_x * scale).round() as i32,
+            background_offset_y: -(grid.offset_y * scale).round() as i32,
+        };
+
+        Ok(MapScene {
+            grid,
+            scene,
+            delivery,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! Campaign memory.
+//!
+//! Canonical facts the GM settles during play ("Baron Sonnim is dead") are
+//! kept apart from the document library, so they can override what the
+//! rulebooks say. Each fact is embedded for recall by similarity, and the
+//! most recent are listed in the MCP server instructions.
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::db::CampaignMemory;
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+
+fn clean_fact(fact: &str) -> ServiceResult<&str> {
+    let fact = fact.trim();
+    if fact.is_empty() {
+        return Err(ServiceError::InvalidRequest {
+            message: "Memory fact is empty".to_string(),
+        });
+    }
+    Ok(fact)
+}
+
+impl SeneschalService {
+    /// Remember a fact about the campaign
+    pub async fn add_memory(
+        &self,
+        fact: &str,
+        world_id: Option<String>,
+    ) -> ServiceResult<CampaignMemory> {
+        let fact = clean_fact(fact)?;
+        let embedding = self.search.embed_text(fact).await?;
+
+        let now = Utc::now();
+        let memory = CampaignMemory {
+            id: uuid::Uuid::new_v4().to_string(),
+            fact: fact.to_string(),
+            world_id: world_id.filter(|w| !w.is_empty()),
+            created_at: now,
+            updated_at: now,
+        };
+        self.db.insert_memory(&memory, &embedding)?;
+        info!(memory_id = %memory.id, "Campaign memory added");
+        Ok(memory)
+    }
+
+    /// Replace a remembered fact, e.g. when it stops being true
+    pub async fn update_memory(&self, id: &str, fact: &str) -> ServiceResult<CampaignMemory> {
+        let fact = clean_fact(fact)?;
+        let embedding = self.search.embed_text(fact).await?;
+
+        if !self.db.update_memory(id, fact, &embedding)? {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Memory not found: {}", id),
+            });
+        }
+        info!(memory_id = %id, "Campaign memory updated");
+        self.db
+            .get_memory(id)?
+            .ok_or_else(|| ServiceError::InvalidRequest {
+                message: format!("Memory not found: {}", id),
+            })
+    }
+
+    /// Memories most relevant to a query, from the MCP world and shared ones
+    pub async fn recall_memories(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> ServiceResult<Vec<(CampaignMemory, f32)>> {
+        let embedding = self.search.embed_text(query).await?;
+        self.db
+            .search_memories(&embedding, self.mcp_world_id().as_deref(), limit)
+    }
+}
@@ -0,0 +1,143 @@
+//! Ollama model management.
+//!
+//! Pulls can take many minutes, so they run in the background: progress is
+//! broadcast to GM WebSocket connections and the latest update for each model
+//! is kept so MCP clients, which can't receive broadcasts, can poll it.
+
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::ollama::LocalModel;
+use crate::service::SeneschalService;
+use crate::websocket::ModelPullUpdate;
+
+/// Minimum time between broadcasts while bytes are downloading
+const PULL_BROADCAST_INTERVAL: Duration = Duration::from_millis(500);
+
+impl SeneschalService {
+    /// List models installed on the Ollama host
+    pub async fn list_local_models(&self) -> ServiceResult<Vec<LocalModel>> {
+        self.ollama.list_local_models().await
+    }
+
+    /// Start pulling a model in the background.
+    ///
+    /// Returns false if a pull of the same model is already running.
+    pub fn start_model_pull(&self, model: &str) -> bool {
+        if self
+            .model_pulls
+            .get(model)
+            .is_some_and(|update| !update.done)
+        {
+            return false;
+        }
+
+        let model = model.to_string();
+        let ollama = self.ollama.clone();
+        let ws_manager = self.ws_manager.clone();
+        let model_pulls = self.model_pulls.clone();
+
+        let publish = move |update: ModelPullUpdate| {
+            model_pulls.insert(update.model.clone(), update.clone());
+            ws_manager.broadcast_model_pull_update(update);
+        };
+        publish(ModelPullUpdate {
+            model: model.clone(),
+            status: "starting".to_string(),
+            completed: None,
+            total: None,
+            done: false,
+            error: None,
+        });
+
+        info!(model = %model, "Starting model pull");
+        tokio::spawn(async move {
+            let mut last_status = String::new();
+            let mut last_broadcast = Instant::now();
+
+            let result = ollama
+                .pull_model(&model, |progress| {
+                    // Byte counts arrive many times a second; status changes always go out
+                    if progress.status == last_status
+                        && last_broadcast.elapsed() < PULL_BROADCAST_INTERVAL
+                    {
+                        return;
+                    }
+                    last_status = progress.status.clone();
+                    last_broadcast = Instant::now();
+                    publish(ModelPullUpdate {
+                        model: model.clone(),
+                        status: progress.status,
+                        completed: progress.completed,
+                        total: progress.total,
+                        done: false,
+                        error: None,
+                    });
+                })
+                .await;
+
+            let (status, error) = match result {
+                Ok(()) => {
+                    info!(model = %model, "Model pull completed");
+                    ("success".to_string(), None)
+                }
+                Err(e) => {
+                    warn!(model = %model, error = %e, "Model pull failed");
+                    ("failed".to_string(), Some(e.to_string()))
+                }
+            };
+            publish(ModelPullUpdate {
+                model: model.clone(),
+                status,
+                completed: None,
+                total: None,
+                done: true,
+                error,
+            });
+        });
+
+        true
+    }
+
+    /// Latest progress of pulls started since startup, by model
+    pub fn model_pull_status(&self) -> Vec<ModelPullUpdate> {
+        let mut pulls: Vec<ModelPullUpdate> = self
+            .model_pulls
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        pulls.sort_by(|a, b| a.model.cmp(&b.model));
+        pulls
+    }
+
+    /// Delete a model from the Ollama host.
+    ///
+    /// Models configured for chat, vision or embeddings can't be deleted.
+    pub async fn delete_model(&self, model: &str) -> ServiceResult<()> {
+        let in_use = {
+            let config = self.runtime_config.dynamic();
+            [
+                &config.ollama.default_model,
+                &config.ollama.vision_model,
+                &config.embeddings.model,
+            ]
+            .iter()
+            .any(|configured| configured.as_str() == model)
+        };
+        if in_use {
+            return Err(ServiceError::InvalidRequest {
+                message: format!(
+                    "{} is configured for use; change the model settings before deleting it",
+                    model
+                ),
+            });
+        }
+
+        self.ollama.delete_model(model).await?;
+        self.model_pulls.remove(model);
+        info!(model = %model, "Deleted model");
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! Model routing by task type.
+//!
+//! Each kind of LLM request (chat, summarization, captioning, query
+//! expansion, title generation) has its own ordered list of models in the
+//! `model_routing` config. A request tries the task's models in order and
+//! fails over to the next one when a model errors. Failed models are
+//! remembered for `failover_cooldown_secs` and tried last meanwhile, so a
+//! model that is down or missing doesn't slow every request.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::DynamicConfig;
+use crate::error::{ServiceError, ServiceResult};
+use crate::ollama::ChatMessage;
+use crate::service::SeneschalService;
+
+/// A kind of LLM request, routed to its own models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTask {
+    Chat,
+    Summarization,
+    Captioning,
+    QueryExpansion,
+    TitleGeneration,
+}
+
+impl ModelTask {
+    pub const ALL: [ModelTask; 5] = [
+        ModelTask::Chat,
+        ModelTask::Summarization,
+        ModelTask::Captioning,
+        ModelTask::QueryExpansion,
+        ModelTask::TitleGeneration,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModelTask::Chat => "chat",
+            ModelTask::Summarization => "summarization",
+            ModelTask::Captioning => "captioning",
+            ModelTask::QueryExpansion => "query_expansion",
+            ModelTask::TitleGeneration => "title_generation",
+        }
+    }
+
+    /// The task's models in the order they are tried
+    pub fn models(self, config: &DynamicConfig) -> Vec<String> {
+        let routing = &config.model_routing;
+        let configured = match self {
+            ModelTask::Chat => &routing.chat,
+            ModelTask::Summarization => &routing.summarization,
+            ModelTask::Captioning => &routing.captioning,
+            ModelTask::QueryExpansion => &routing.query_expansion,
+            ModelTask::TitleGeneration => &routing.title_generation,
+        };
+        let models = parse_model_list(configured);
+        if !models.is_empty() {
+            return models;
+        }
+
+        match self {
+            ModelTask::Chat => parse_model_list(&config.ollama.default_model),
+            ModelTask::Captioning => parse_model_list(&config.ollama.vision_model),
+            _ => ModelTask::Chat.models(config),
+        }
+    }
+}
+
+/// Model names from a comma-separated list, without blanks or repeats
+fn parse_model_list(list: &str) -> Vec<String> {
+    let mut models: Vec<String> = Vec::new();
+    for model in list.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+        if !models.iter().any(|m| m == model) {
+            models.push(model.to_string());
+        }
+    }
+    models
+}
+
+/// When each recently failed model last failed
+#[derive(Debug, Default)]
+pub struct ModelHealth {
+    failures: DashMap<String, Instant>,
+}
+
+impl ModelHealth {
+    pub fn record(&self, model: &str, success: bool) {
+        if success {
+            self.failures.remove(model);
+        } else {
+            self.failures.insert(model.to_string(), Instant::now());
+        }
+    }
+
+    /// Whether a model failed within the cooldown
+    pub fn is_cooling_down(&self, model: &str, cooldown: Duration) -> bool {
+        self.failures
+            .get(model)
+            .is_some_and(|failed| failed.elapsed() < cooldown)
+    }
+
+    /// Models in the order to try them: healthy ones first, each group in
+    /// configured order
+    pub fn order(&self, models: Vec<String>, cooldown: Duration) -> Vec<String> {
+        let (healthy, cooling): (Vec<_>, Vec<_>) = models
+            .into_iter()
+            .partition(|m| !self.is_cooling_down(m, cooldown));
+        healthy.into_iter().chain(cooling).collect()
+    }
+}
+
+/// A task's models, as reported to admins
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelRoute {
+    pub task: ModelTask,
+    pub models: Vec<String>,
+    /// Models that recently failed and are tried last
+    pub cooling_down: Vec<String>,
+}
+
+impl SeneschalService {
+    fn failover_cooldown(&self) -> Duration {
+        Duration::from_secs(
+            self.runtime_config
+                .dynamic()
+                .model_routing
+                .failover_cooldown_secs,
+        )
+    }
+
+    /// The task's models in the order they would be tried now
+    fn routed_models(&self, task: ModelTask) -> Vec<String> {
+        let models = task.models(&self.runtime_config.dynamic());
+        self.model_health.order(models, self.failover_cooldown())
+    }
+
+    /// The model the next request for a task goes to, if any is configured
+    pub fn primary_model(&self, task: ModelTask) -> Option<String> {
+        self.routed_models(task).into_iter().next()
+    }
+
+    /// Every task's models and which of them are cooling down
+    pub fn model_routes(&self) -> Vec<ModelRoute> {
+        let config = self.runtime_config.dynamic();
+        let cooldown = self.failover_cooldown();
+        ModelTask::ALL
+            .into_iter()
+            .map(|task| {
+                let models = task.models(&config);
+                let cooling_down = models
+                    .iter()
+                    .filter(|m| self.model_health.is_cooling_down(m, cooldown))
+                    .cloned()
+                    .collect();
+                ModelRoute {
+                    task,
+                    models,
+                    cooling_down,
+                }
+            })
+            .collect()
+    }
+
+    /// Send a request for a task, failing over through its models.
+    ///
+    /// `preferred` (e.g. a document's chosen vision model) is tried first,
+    /// then the task's routed models. Returns the last error if all fail.
+    pub(crate) async fn generate_routed(
+        &self,
+        task: ModelTask,
+        preferred: Option<&str>,
+        messages: Vec<ChatMessage>,
+    ) -> ServiceResult<String> {
+        let mut models: Vec<String> = preferred.map(String::from).into_iter().collect();
+        for model in self.routed_models(task) {
+            if !models.contains(&model) {
+                models.push(model);
+            }
+        }
+
+        let client = match task {
+            ModelTask::Captioning => &self.vision_ollama,
+            _ => &self.ollama,
+        };
+        let mut last_error = None;
+        for model in models {
+            match client
+                .generate_simple(&model, task.as_str(), messages.clone())
+                .await
+            {
+                Ok(response) => {
+                    self.model_health.record(&model, true);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    warn!(task = ?task, model = %model, error = %e, "Model request failed");
+                    self.model_health.record(&model, false);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| ServiceError::Config {
+            message: format!("No model configured for {:?} requests", task),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DynamicConfig;
+
+    fn config() -> DynamicConfig {
+        serde_json::from_value(serde_json::json!({
+            "ollama": { "default_model": "qwen3:14b", "vision_model": "" },
+            "model_routing": { "summarization": "llama3.2:3b, qwen3:14b,llama3.2:3b" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_task_models() {
+        let config = config();
+        assert_eq!(ModelTask::Chat.models(&config), vec!["qwen3:14b"]);
+        assert_eq!(
+            ModelTask::Summarization.models(&config),
+            vec!["llama3.2:3b", "qwen3:14b"]
+        );
+        assert_eq!(
+            ModelTask::TitleGeneration.models(&config),
+            vec!["qwen3:14b"]
+        );
+        assert!(ModelTask::Captioning.models(&config).is_empty());
+    }
+
+    #[test]
+    fn test_health_order() {
+        let health = ModelHealth::default();
+        let models = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let cooldown = Duration::from_secs(60);
+
+        health.record("a", false);
+        assert_eq!(health.order(models.clone(), cooldown), vec!["b", "c", "a"]);
+        assert_eq!(
+            health.order(models.clone(), Duration::ZERO),
+            vec!["a", "b", "c"]
+        );
+
+        health.record("a", true);
+        assert_eq!(health.order(models, cooldown), vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Notes promoted from chat answers.
+//!
+//! Generated content worth keeping (house rules, NPC write-ups, rulings)
+//! only lives in the MCP client's conversation. Saving it as a note stores
+//! it as an indexed Markdown document, tagged as a note and marked with
+//! `source: chat`, so later searches find it like any other document.
+
+use tracing::{info, warn};
+
+use crate::db::Document;
+use crate::error::{ServiceError, ServiceResult};
+use crate::ollama::ChatMessage;
+use crate::service::{ModelTask, SeneschalService};
+use crate::tools::AccessLevel;
+
+/// Tag applied to every saved note
+const NOTE_TAG: &str = "note";
+
+/// Characters of a note shown to the model when generating its title
+const TITLE_SOURCE_CHARS: usize = 2000;
+
+impl SeneschalService {
+    /// Store chat content as an indexed note document.
+    ///
+    /// Without a title, the note's heading is used, or one is generated.
+    pub async fn save_note(
+        &self,
+        title: Option<&str>,
+        content: &str,
+        mut tags: Vec<String>,
+        access_level: AccessLevel,
+    ) -> ServiceResult<Document> {
+        let content = content.trim();
+        if content.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Note content is empty".to_string(),
+            });
+        }
+
+        let title = match title.map(str::trim).filter(|t| !t.is_empty()) {
+            Some(title) => title.to_string(),
+            None => match note_heading(content) {
+                Some(heading) => heading.to_string(),
+                None => self.generate_note_title(content).await,
+            },
+        };
+        let title = title.as_str();
+
+        if !tags.iter().any(|tag| tag == NOTE_TAG) {
+            tags.insert(0, NOTE_TAG.to_string());
+        }
+
+        let filename = format!("note_{}.md", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+        let document = self
+            .upload_document(
+                note_markdown(title, content).as_bytes(),
+                &filename,
+                title,
+                access_level,
+                tags,
+                None,
+                None,
+                None,
+            )
+            .await?;
+        self.db.update_document_metadata(
+            &document.id,
+            Some(serde_json::json!({ "source": "chat" })),
+        )?;
+        self.assign_mcp_world(&document.id)?;
+
+        info!(doc_id = %document.id, title = %title, "Chat note saved");
+        Ok(document)
+    }
+
+    /// A short title for a note, falling back to its date if no model answers
+    async fn generate_note_title(&self, content: &str) -> String {
+        let source: String = content.chars().take(TITLE_SOURCE_CHARS).collect();
+        let prompt = format!(
+            "Write a short title (at most 8 words) for this note from a {} \
+            GM's campaign. Respond with only the title.\n\n{}",
+            self.mcp_system_profile().name,
+            source
+        );
+        match self
+            .generate_routed(
+                ModelTask::TitleGeneration,
+                None,
+                vec![ChatMessage::user(prompt)],
+            )
+            .await
+        {
+            Ok(response) => {
+                let title = response.lines().next().unwrap_or("").trim();
+                let title = title.trim_matches(['"', '\'', '*', '#', ' ']);
+                if !title.is_empty() {
+                    return title.to_string();
+                }
+            }
+            Err(e) => warn!(error = %e, "Note title generation failed"),
+        }
+        format!("Note {}", chrono::Utc::now().format("%Y-%m-%d %H:%M"))
+    }
+}
+
+/// The text of a note's leading `# ` heading
+fn note_heading(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("# "))
+        .map(str::trim)
+        .filter(|heading| !heading.is_empty())
+}
+
+/// The note as Markdown, headed by its title unless it already has a heading
+fn note_markdown(title: &str, content: &str) -> String {
+    if content.starts_with("# ") {
+        format!("{}\n", content)
+    } else {
+        format!("# {}\n\n{}\n", title, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_markdown() {
+        assert_eq!(
+            note_markdown("Jump Rules", "Misjumps happen on a 2."),
+            "# Jump Rules\n\nMisjumps happen on a 2.\n"
+        );
+        assert_eq!(
+            note_markdown("Jump Rules", "# Misjumps\n\nOn a 2."),
+            "# Misjumps\n\nOn a 2.\n"
+        );
+        assert_eq!(note_heading("# Misjumps\n\nOn a 2."), Some("Misjumps"));
+        assert_eq!(note_heading("Misjumps happen on a 2."), None);
+    }
+}
@@ -0,0 +1,368 @@
+//! NPC registry.
+//!
+//! Recurring NPCs are kept apart from the documents, with links to the stat
+//! block or FVTT actor they came from and a graph of their relationships
+//! ("rival of", "employed by"), so the LLM can keep them consistent from
+//! session to session.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use serde::Serialize;
+use tracing::info;
+
+use crate::db::{Npc, NpcRelation};
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+
+/// Fields to create or update an NPC with; unset fields are left alone
+#[derive(Debug, Clone, Default)]
+pub struct NpcUpdate {
+    /// NPC to update; otherwise matched by name, or created
+    pub npc_id: Option<String>,
+    /// Defaults to the stat block's name
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub stat_block_id: Option<String>,
+    pub actor_uuid: Option<String>,
+}
+
+/// A relationship with both NPCs named
+#[derive(Debug, Clone, Serialize)]
+pub struct NpcLink {
+    pub relation_id: String,
+    pub source_id: String,
+    pub source: String,
+    pub relation: String,
+    pub target_id: String,
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Every NPC in a world and the relationships between them
+#[derive(Debug, Clone, Serialize)]
+pub struct NpcGraph {
+    pub nodes: Vec<Npc>,
+    pub edges: Vec<NpcRelation>,
+}
+
+impl NpcGraph {
+    /// The edges with both NPCs named
+    pub fn links(&self) -> Vec<NpcLink> {
+        let names: HashMap<&str, &str> = self
+            .nodes
+            .iter()
+            .map(|npc| (npc.id.as_str(), npc.name.as_str()))
+            .collect();
+        let name = |id: &str| names.get(id).copied().unwrap_or_default().to_string();
+        self.edges
+            .iter()
+            .map(|edge| NpcLink {
+                relation_id: edge.id.clone(),
+                source_id: edge.source_id.clone(),
+                source: name(&edge.source_id),
+                relation: edge.relation.clone(),
+                target_id: edge.target_id.clone(),
+                target: name(&edge.target_id),
+                notes: edge.notes.clone(),
+            })
+            .collect()
+    }
+
+    /// Render the graph in Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = String::from("digraph npcs {\n");
+        for npc in &self.nodes {
+            dot.push_str(&format!(
+                "  {} [label={}];\n",
+                quote(&npc.id),
+                quote(&npc.name)
+            ));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "  {} -> {} [label={}];\n",
+                quote(&edge.source_id),
+                quote(&edge.target_id),
+                quote(&edge.relation)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Lower-case a relationship and collapse its whitespace, so "Rival  of"
+/// and "rival of" are the same edge
+pub fn normalize_relation(relation: &str) -> String {
+    relation
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn clean(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+impl SeneschalService {
+    /// An NPC visible from a world, by ID or name
+    pub fn resolve_npc(&self, world_id: Option<&str>, key: &str) -> ServiceResult<Option<Npc>> {
+        let key = key.trim();
+        match self.db.get_npc(key)? {
+            Some(npc) => Ok(Some(npc)),
+            None => self.db.find_npc(key, world_id),
+        }
+    }
+
+    /// Create an NPC, or update the one with the given ID or name
+    pub fn set_npc(&self, world_id: Option<String>, update: NpcUpdate) -> ServiceResult<Npc> {
+        let world_id = world_id.filter(|w| !w.is_empty());
+        let stat_block_id = clean(update.stat_block_id);
+        let stat_block = match &stat_block_id {
+            Some(id) => Some(
+                self.db
+                    .get_stat_block(id, AccessLevel::GmOnly as u8, world_id.as_deref())?
+                    .ok_or_else(|| ServiceError::InvalidRequest {
+                        message: format!("Stat block not found: {}", id),
+                    })?,
+            ),
+            None => None,
+        };
+        let name = clean(update.name).or_else(|| stat_block.map(|block| block.name));
+
+        let existing = match (clean(update.npc_id), &name) {
+            (Some(id), _) => {
+                Some(
+                    self.db
+                        .get_npc(&id)?
+                        .ok_or_else(|| ServiceError::InvalidRequest {
+                            message: format!("NPC not found: {}", id),
+                        })?,
+                )
+            }
+            (None, Some(name)) => self.db.find_npc(name, world_id.as_deref())?,
+            (None, None) => {
+                return Err(ServiceError::InvalidRequest {
+                    message: "An NPC needs a name or a stat block".to_string(),
+                });
+            }
+        };
+
+        let description = clean(update.description);
+        let actor_uuid = clean(update.actor_uuid);
+        match existing {
+            Some(npc) => {
+                let npc = Npc {
+                    name: name.unwrap_or(npc.name),
+                    description: description.or(npc.description),
+                    stat_block_id: stat_block_id.or(npc.stat_block_id),
+                    actor_uuid: actor_uuid.or(npc.actor_uuid),
+                    updated_at: Utc::now(),
+                    ..npc
+                };
+                self.db.update_npc(&npc)?;
+                info!(npc_id = %npc.id, name = %npc.name, "NPC updated");
+                Ok(npc)
+            }
+            None => {
+                let now = Utc::now();
+                let npc = Npc {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: name.unwrap_or_default(),
+                    world_id,
+                    description,
+                    stat_block_id,
+                    actor_uuid,
+                    created_at: now,
+                    updated_at: now,
+                };
+                self.db.insert_npc(&npc)?;
+                info!(npc_id = %npc.id, name = %npc.name, "NPC added");
+                Ok(npc)
+            }
+        }
+    }
+
+    /// Record that one NPC relates to another, adding either NPC by name if
+    /// it isn't registered yet
+    pub fn relate_npcs(
+        &self,
+        world_id: Option<String>,
+        source: &str,
+        relation: &str,
+        target: &str,
+        notes: Option<String>,
+    ) -> ServiceResult<NpcLink> {
+        let relation = normalize_relation(relation);
+        if relation.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Relationship is empty".to_string(),
+            });
+        }
+
+        let mut ends = Vec::with_capacity(2);
+        for key in [source, target] {
+            let npc = match self.resolve_npc(world_id.as_deref(), key)? {
+                Some(npc) => npc,
+                None => self.set_npc(
+                    world_id.clone(),
+                    NpcUpdate {
+                        name: Some(key.to_string()),
+                        ..Default::default()
+                    },
+                )?,
+            };
+            ends.push(npc);
+        }
+        let (source, target) = (&ends[0], &ends[1]);
+        if source.id == target.id {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("{} cannot be related to themselves", source.name),
+            });
+        }
+
+        let saved = self.db.upsert_npc_relation(&NpcRelation {
+            id: uuid::Uuid::new_v4().to_string(),
+            source_id: source.id.clone(),
+            target_id: target.id.clone(),
+            relation,
+            notes: clean(notes),
+            created_at: Utc::now(),
+        })?;
+        info!(source = %source.name, relation = %saved.relation, target = %target.name, "NPC relationship recorded");
+
+        Ok(NpcLink {
+            relation_id: saved.id,
+            source_id: source.id.clone(),
+            source: source.name.clone(),
+            relation: saved.relation,
+            target_id: target.id.clone(),
+            target: target.name.clone(),
+            notes: saved.notes,
+        })
+    }
+
+    /// Remove a relationship between two registered NPCs, returning whether
+    /// it existed
+    pub fn unrelate_npcs(
+        &self,
+        world_id: Option<&str>,
+        source: &str,
+        relation: &str,
+        target: &str,
+    ) -> ServiceResult<bool> {
+        let (Some(source), Some(target)) = (
+            self.resolve_npc(world_id, source)?,
+            self.resolve_npc(world_id, target)?,
+        ) else {
+            return Ok(false);
+        };
+        let relation = normalize_relation(relation);
+        match self
+            .db
+            .list_npc_relations(&source.id)?
+            .into_iter()
+            .find(|r| {
+                r.source_id == source.id && r.target_id == target.id && r.relation == relation
+            }) {
+            Some(existing) => {
+                info!(source = %source.name, relation = %relation, target = %target.name, "NPC relationship removed");
+                self.db.delete_npc_relation(&existing.id)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// An NPC's relationships in either direction, with both ends named
+    pub fn npc_links(&self, npc: &Npc) -> ServiceResult<Vec<NpcLink>> {
+        let mut names: HashMap<String, String> =
+            HashMap::from([(npc.id.clone(), npc.name.clone())]);
+        let mut links = Vec::new();
+        for relation in self.db.list_npc_relations(&npc.id)? {
+            for id in [&relation.source_id, &relation.target_id] {
+                if !names.contains_key(id)
+                    && let Some(other) = self.db.get_npc(id)?
+                {
+                    names.insert(other.id, other.name);
+                }
+            }
+            let name = |id: &str| names.get(id).cloned().unwrap_or_default();
+            links.push(NpcLink {
+                source: name(&relation.source_id),
+                target: name(&relation.target_id),
+                relation_id: relation.id,
+                source_id: relation.source_id,
+                relation: relation.relation,
+                target_id: relation.target_id,
+                notes: relation.notes,
+            });
+        }
+        Ok(links)
+    }
+
+    /// The NPCs visible from a world and their relationships
+    pub fn npc_graph(&self, world_id: Option<&str>) -> ServiceResult<NpcGraph> {
+        Ok(NpcGraph {
+            nodes: self.db.list_npcs(world_id)?,
+            edges: self.db.list_world_npc_relations(world_id)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npc(id: &str, name: &str) -> Npc {
+        let now = Utc::now();
+        Npc {
+            id: id.to_string(),
+            name: name.to_string(),
+            world_id: None,
+            description: None,
+            stat_block_id: None,
+            actor_uuid: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_normalize_relation() {
+        assert_eq!(normalize_relation("  Rival   OF "), "rival of");
+        assert_eq!(normalize_relation("employed by"), "employed by");
+        assert_eq!(normalize_relation("   "), "");
+    }
+
+    #[test]
+    fn test_graph_to_dot() {
+        let graph = NpcGraph {
+            nodes: vec![npc("a", "Baron \"Red\" Sonnim"), npc("b", "Anders Casarii")],
+            edges: vec![NpcRelation {
+                id: "r".to_string(),
+                source_id: "b".to_string(),
+                target_id: "a".to_string(),
+                relation: "employed by".to_string(),
+                notes: None,
+                created_at: Utc::now(),
+            }],
+        };
+
+        let links = graph.links();
+        assert_eq!(links[0].source, "Anders Casarii");
+        assert_eq!(links[0].target, "Baron \"Red\" Sonnim");
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph npcs {\n"));
+        assert!(dot.contains(r#""a" [label="Baron \"Red\" Sonnim"];"#));
+        assert!(dot.contains(r#""b" -> "a" [label="employed by"];"#));
+        assert!(dot.ends_with("}\n"));
+    }
+}
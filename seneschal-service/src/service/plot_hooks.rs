@@ -0,0 +1,176 @@
+//! Rumors and plot hooks drawn from indexed adventures.
+//!
+//! Passages are sampled from GM-only adventure documents relevant to a
+//! world or subsector, for the LLM to retell as rumors. Each passage is
+//! surfaced once per campaign world, so the table doesn't keep hearing the
+//! same rumor.
+
+use std::collections::HashSet;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::Serialize;
+use tracing::info;
+
+use super::library_data::excerpt;
+use crate::error::{ServiceError, ServiceResult};
+use crate::search::ErrataStatus;
+use crate::service::SeneschalService;
+use crate::tools::{SearchFilters, TagMatch};
+
+/// Most hooks drawn in one call
+pub const MAX_PLOT_HOOKS: usize = 10;
+
+/// Tag marking adventure documents
+pub const DEFAULT_HOOK_TAG: &str = "adventure";
+
+/// Candidate passages retrieved per hook wanted
+const CANDIDATES_PER_HOOK: usize = 4;
+
+/// Longest hook excerpt, in characters
+const MAX_HOOK_CHARS: usize = 600;
+
+/// A passage to retell as a rumor or hook
+#[derive(Debug, Clone, Serialize)]
+pub struct PlotHook {
+    pub chunk_id: String,
+    pub document_id: String,
+    pub document_title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_title: Option<String>,
+    pub excerpt: String,
+}
+
+/// Hooks drawn for a location
+#[derive(Debug, Clone, Serialize)]
+pub struct PlotHooks {
+    pub location: String,
+    pub seed: u64,
+    pub hooks: Vec<PlotHook>,
+    /// Relevant passages not yet surfaced, beyond those drawn
+    pub remaining: usize,
+}
+
+/// Draw `count` items at random, keeping their order
+fn sample<T>(pool: Vec<T>, count: usize, seed: u64) -> Vec<T> {
+    if pool.len() <= count {
+        return pool;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut picked = rand::seq::index::sample(&mut rng, pool.len(), count).into_vec();
+    picked.sort_unstable();
+    let picked: HashSet<usize> = picked.into_iter().collect();
+    pool.into_iter()
+        .enumerate()
+        .filter(|(i, _)| picked.contains(i))
+        .map(|(_, item)| item)
+        .collect()
+}
+
+impl SeneschalService {
+    /// The campaign hooks are tracked for: the MCP world, or '' without one
+    fn hook_world(&self) -> String {
+        self.mcp_world_id().unwrap_or_default()
+    }
+
+    /// Draw passages about a world or subsector from adventures tagged
+    /// `tag` that haven't been surfaced in this campaign yet, and mark them
+    /// surfaced. Hooks come from GM-only material, so the caller's role
+    /// decides how much of it is reachable.
+    pub async fn plot_hooks(
+        &self,
+        location: &str,
+        count: usize,
+        tag: &str,
+        seed: Option<u64>,
+        user_role: u8,
+    ) -> ServiceResult<PlotHooks> {
+        let location = location.trim();
+        if location.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "A world or subsector is required".to_string(),
+            });
+        }
+        let count = count.clamp(1, MAX_PLOT_HOOKS);
+        let seed = seed.unwrap_or_else(rand::random);
+        let world = self.hook_world();
+
+        let filters = SearchFilters {
+            tags: vec![tag.to_string()],
+            tags_match: TagMatch::Any,
+            world_id: self.mcp_world_id(),
+            ..Default::default()
+        };
+        let results = self
+            .search(
+                location,
+                user_role,
+                count * CANDIDATES_PER_HOOK,
+                Some(filters),
+            )
+            .await?;
+
+        let surfaced = self.db.surfaced_plot_hooks(&world)?;
+        let pool: Vec<_> = results
+            .into_iter()
+            .filter(|r| !surfaced.contains(&r.chunk.id))
+            .filter(|r| !matches!(r.errata, Some(ErrataStatus::Superseded { .. })))
+            .map(|r| r.chunk)
+            .collect();
+        let remaining = pool.len().saturating_sub(count);
+
+        let mut hooks = Vec::new();
+        for chunk in sample(pool, count, seed) {
+            let document_title = self
+                .db
+                .get_document(&chunk.document_id)?
+                .map(|doc| doc.title)
+                .unwrap_or_else(|| chunk.document_id.clone());
+            hooks.push(PlotHook {
+                excerpt: excerpt(location, &[chunk.content.as_str()], MAX_HOOK_CHARS),
+                chunk_id: chunk.id,
+                document_id: chunk.document_id,
+                document_title,
+                page: chunk.page_number,
+                section_title: chunk.section_title,
+            });
+        }
+
+        let ids: Vec<String> = hooks.iter().map(|hook| hook.chunk_id.clone()).collect();
+        self.db.mark_plot_hooks_surfaced(&world, &ids)?;
+        info!(location = %location, hooks = hooks.len(), "Plot hooks surfaced");
+
+        Ok(PlotHooks {
+            location: location.to_string(),
+            seed,
+            hooks,
+            remaining,
+        })
+    }
+
+    /// Let this campaign's surfaced hooks be drawn again
+    pub fn reset_plot_hooks(&self) -> ServiceResult<usize> {
+        self.db.clear_surfaced_plot_hooks(&self.hook_world())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_keeps_order_and_is_seeded() {
+        let pool: Vec<u32> = (0..20).collect();
+        let first = sample(pool.clone(), 5, 42);
+        assert_eq!(first.len(), 5);
+        assert!(first.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(first, sample(pool, 5, 42));
+    }
+
+    #[test]
+    fn test_sample_small_pool() {
+        assert_eq!(sample(vec![1, 2], 5, 7), vec![1, 2]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Related documents.
+//!
+//! Documents are related by content (cosine similarity of their embedding
+//! centroids), by explicit references (resolved wiki links from an Obsidian
+//! vault, in either direction) and by shared tags. The scores are combined
+//! so a linked session note ranks above a merely similar rulebook.
+
+use serde::Serialize;
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+
+/// Added to the score for each direction of an explicit link
+const LINK_WEIGHT: f32 = 0.3;
+
+/// Added to the score, scaled by the fraction of tags the documents share
+const TAG_WEIGHT: f32 = 0.2;
+
+/// A document related to another
+#[derive(Debug, Clone, Serialize)]
+pub struct RelatedDocument {
+    pub document_id: String,
+    pub title: String,
+    pub score: f32,
+    /// Cosine similarity of the documents' embedding centroids
+    pub similarity: Option<f32>,
+    /// The source document links to this one
+    pub links_to: bool,
+    /// This document links to the source document
+    pub linked_from: bool,
+    pub shared_tags: Vec<String>,
+}
+
+impl SeneschalService {
+    /// The documents visible from a world most related to one, best first.
+    pub fn related_documents(
+        &self,
+        document_id: &str,
+        user_role: u8,
+        world_id: Option<&str>,
+        limit: usize,
+    ) -> ServiceResult<Vec<RelatedDocument>> {
+        let document = self
+            .db
+            .get_document(document_id)?
+            .filter(|doc| doc.access_level.accessible_by(user_role) && doc.in_world(world_id))
+            .ok_or_else(|| ServiceError::DocumentNotFound {
+                document_id: document_id.to_string(),
+            })?;
+
+        // Documents embedded before centroids existed get theirs on first use
+        for id in self.db.get_documents_without_centroid()? {
+            self.db.update_document_centroid(&id)?;
+        }
+        let similarities = self
+            .db
+            .get_centroid_similarities(document_id, user_role, world_id)?;
+        let outgoing = linked_document_ids(document.metadata.as_ref());
+
+        let mut related: Vec<RelatedDocument> = self
+            .db
+            .list_documents(Some(user_role))?
+            .into_iter()
+            .filter(|candidate| candidate.id != document.id && candidate.in_world(world_id))
+            .filter_map(|candidate| {
+                let similarity = similarities.get(&candidate.id).copied();
+                let links_to = outgoing.contains(&candidate.id.as_str());
+                let linked_from = linked_document_ids(candidate.metadata.as_ref())
+                    .contains(&document.id.as_str());
+                let shared_tags: Vec<String> = candidate
+                    .tags
+                    .iter()
+                    .filter(|tag| document.tags.contains(tag))
+                    .cloned()
+                    .collect();
+                if similarity.is_none() && !links_to && !linked_from && shared_tags.is_empty() {
+                    return None;
+                }
+
+                let tag_union = document.tags.len() + candidate.tags.len() - shared_tags.len();
+                let tag_overlap = if tag_union == 0 {
+                    0.0
+                } else {
+                    shared_tags.len() as f32 / tag_union as f32
+                };
+                let links = u8::from(links_to) + u8::from(linked_from);
+                let score = similarity.unwrap_or(0.0)
+                    + LINK_WEIGHT * f32::from(links)
+                    + TAG_WEIGHT * tag_overlap;
+
+                Some(RelatedDocument {
+                    document_id: candidate.id,
+                    title: candidate.title,
+                    score,
+                    similarity,
+                    links_to,
+                    linked_from,
+                    shared_tags,
+                })
+            })
+            .collect();
+
+        related.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        related.truncate(limit);
+        Ok(related)
+    }
+}
+
+/// IDs of the documents a document's resolved wiki links point at
+fn linked_document_ids(metadata: Option<&serde_json::Value>) -> Vec<&str> {
+    metadata
+        .and_then(|m| m.get("wiki_links"))
+        .and_then(|links| links.as_array())
+        .map(|links| {
+            links
+                .iter()
+                .filter_map(|link| link.get("document_id")?.as_str())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linked_document_ids() {
+        let metadata = serde_json::json!({
+            "vault_path": "NPCs/Captain Reyes.md",
+            "wiki_links": [
+                {"target": "Regina", "document_id": "regina"},
+                {"target": "Efate", "document_id": null},
+                {"target": "Free Trader Beowulf", "document_id": "beowulf"}
+            ]
+        });
+
+        assert_eq!(
+            linked_document_ids(Some(&metadata)),
+            vec!["regina", "beowulf"]
+        );
+        assert!(linked_document_ids(Some(&serde_json::json!({}))).is_empty());
+        assert!(linked_document_ids(None).is_empty());
+    }
+}
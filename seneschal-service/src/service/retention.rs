@@ -0,0 +1,156 @@
+//! Data retention policies.
+//!
+//! Each kind of data that piles up in the data directory has its own TTL in
+//! the `retention` config (0 keeps it forever): uploaded source files that
+//! nothing reads again (see `Database::expired_source_files`), extracted
+//! images that never got a caption, cached vision descriptions of FVTT
+//! images, and audit logs (FVTT world changes and model usage). A background
+//! task enforces them hourly; TTL changes apply on the next run.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::error::ServiceResult;
+use crate::service::SeneschalService;
+
+/// How often retention policies are enforced
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// What a retention run removed
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionReport {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Source files deleted from processed documents
+    pub source_files: usize,
+    pub uncaptioned_images: usize,
+    pub image_descriptions: usize,
+    /// FVTT change records and model usage entries
+    pub audit_entries: usize,
+    /// Bytes freed by deleted files
+    pub bytes_reclaimed: u64,
+}
+
+/// The time before which data kept for `days` has expired; None keeps it forever
+fn cutoff(now: DateTime<Utc>, days: u64) -> Option<DateTime<Utc>> {
+    if days == 0 {
+        return None;
+    }
+    Some(now - chrono::Duration::days(days.min(365 * 1000) as i64))
+}
+
+/// Delete a file, returning its size if it was deleted
+fn remove_file(path: &str) -> Option<u64> {
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    match std::fs::remove_file(path) {
+        Ok(()) => Some(size),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Some(0),
+        Err(e) => {
+            warn!(path = %path, error = %e, "Failed to delete expired file");
+            None
+        }
+    }
+}
+
+impl SeneschalService {
+    /// Start the background task that enforces retention policies
+    pub fn start_retention_task(service: Arc<SeneschalService>) {
+        tokio::spawn(async move {
+            info!("Retention task started");
+            loop {
+                tokio::time::sleep(RETENTION_INTERVAL).await;
+
+                let worker = service.clone();
+                match tokio::task::spawn_blocking(move || worker.run_retention()).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => warn!(error = %e, "Retention run failed"),
+                    Err(e) => warn!(error = %e, "Retention task panicked"),
+                }
+            }
+        });
+    }
+
+    /// Delete everything older than its retention policy allows.
+    ///
+    /// Blocks on file and database I/O; call it off the async runtime.
+    pub fn run_retention(&self) -> ServiceResult<RetentionReport> {
+        let started = Instant::now();
+        let now = Utc::now();
+        let policy = self.runtime_config.dynamic().retention.clone();
+
+        let mut report = RetentionReport {
+            started_at: now,
+            duration_ms: 0,
+            source_files: 0,
+            uncaptioned_images: 0,
+            image_descriptions: 0,
+            audit_entries: 0,
+            bytes_reclaimed: 0,
+        };
+
+        if let Some(cutoff) = cutoff(now, policy.source_files_days) {
+            for (document_id, path) in self.db.expired_source_files(cutoff)? {
+                if let Some(size) = remove_file(&path) {
+                    self.db.clear_document_file(&document_id)?;
+                    report.source_files += 1;
+                    report.bytes_reclaimed += size;
+                }
+            }
+        }
+
+        if let Some(cutoff) = cutoff(now, policy.uncaptioned_images_days) {
+            for (image_id, path) in self.db.expired_uncaptioned_images(cutoff)? {
+                let size = std::fs::metadata(&path).map_or(0, |m| m.len());
+                if self.delete_image(&image_id)? {
+                    report.uncaptioned_images += 1;
+                    report.bytes_reclaimed += size;
+                }
+            }
+        }
+
+        if let Some(cutoff) = cutoff(now, policy.image_description_cache_days) {
+            report.image_descriptions = self.db.delete_image_descriptions_before(cutoff)?;
+        }
+
+        if let Some(cutoff) = cutoff(now, policy.audit_log_days) {
+            report.audit_entries = self.db.delete_audit_entries_before(cutoff)?;
+        }
+
+        report.duration_ms = started.elapsed().as_millis() as u64;
+        if report.source_files
+            + report.uncaptioned_images
+            + report.image_descriptions
+            + report.audit_entries
+            > 0
+        {
+            info!(
+                source_files = report.source_files,
+                uncaptioned_images = report.uncaptioned_images,
+                image_descriptions = report.image_descriptions,
+                audit_entries = report.audit_entries,
+                bytes_reclaimed = report.bytes_reclaimed,
+                "Expired data removed"
+            );
+        }
+        *self.last_retention.lock().unwrap() = Some(report.clone());
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff() {
+        let now = Utc::now();
+        assert_eq!(cutoff(now, 0), None);
+        assert_eq!(cutoff(now, 30), Some(now - chrono::Duration::days(30)));
+        // Absurd TTLs keep data for a very long time instead of overflowing
+        assert!(cutoff(now, u64::MAX).is_some());
+    }
+}
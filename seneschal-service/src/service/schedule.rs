@@ -0,0 +1,141 @@
+//! Cron-style schedules.
+//!
+//! Supports the five standard fields (minute, hour, day of month, month, day
+//! of week) with `*`, single values, ranges (`1-5`), steps (`*/15`, `0-30/10`)
+//! and comma-separated lists. Day of week runs 0-6 from Sunday; 7 is also
+//! Sunday. As in cron, when both day fields are restricted either may match.
+
+use chrono::{Datelike, Timelike};
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl Schedule {
+    /// Parse a five-field cron expression
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                fields.len()
+            ));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7, "day of week")?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_field(day_of_month, 1, 31, "day of month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    /// Whether the schedule fires in the minute containing `time`
+    pub fn matches<T: Datelike + Timelike>(&self, time: &T) -> bool {
+        let has = |set: u64, value: u32| set & (1 << value) != 0;
+
+        let day_of_month = has(self.days_of_month, time.day());
+        let day_of_week = has(self.days_of_week, time.weekday().num_days_from_sunday());
+        let day = match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+
+        day && has(self.minutes, time.minute())
+            && has(self.hours, time.hour())
+            && has(self.months, time.month())
+    }
+}
+
+/// Parse one field into a bit set of the values it allows
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid {} field '{}'", name, field);
+    let number = |s: &str| -> Result<u32, String> {
+        let value: u32 = s.parse().map_err(|_| invalid())?;
+        if value < min || value > max {
+            return Err(format!(
+                "{} value {} is outside {}-{}",
+                name, value, min, max
+            ));
+        }
+        Ok(value)
+    };
+
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (number(start)?, number(end)?)
+        } else {
+            let value = number(range)?;
+            // "5/15" means every 15 from 5
+            (value, if part.contains('/') { max } else { value })
+        };
+        if start > end {
+            return Err(invalid());
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_schedule() {
+        // Mondays and Thursdays at 09:30
+        let schedule = Schedule::parse("30 9 * * 1,4").unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        assert!(schedule.matches(&monday.and_hms_opt(9, 30, 0).unwrap()));
+        assert!(!schedule.matches(&monday.and_hms_opt(9, 31, 0).unwrap()));
+        assert!(!schedule.matches(&monday.succ_opt().unwrap().and_hms_opt(9, 30, 0).unwrap()));
+
+        // Every 15 minutes during working hours
+        let schedule = Schedule::parse("*/15 9-17 * * *").unwrap();
+        assert!(schedule.matches(&monday.and_hms_opt(17, 45, 0).unwrap()));
+        assert!(!schedule.matches(&monday.and_hms_opt(18, 0, 0).unwrap()));
+
+        // Either day field matches when both are restricted; 7 is Sunday
+        let schedule = Schedule::parse("0 0 1 * 7").unwrap();
+        let sunday = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert!(schedule.matches(&sunday.and_hms_opt(0, 0, 0).unwrap()));
+        let first = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        assert!(schedule.matches(&first.and_hms_opt(0, 0, 0).unwrap()));
+
+        assert!(Schedule::parse("0 9 * *").is_err());
+        assert!(Schedule::parse("60 * * * *").is_err());
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+    }
+}
@@ -0,0 +1,427 @@
+//! Session recap generation.
+//!
+//! The server keeps no conversation history, so the MCP client supplies the
+//! transcript (or its own notes) and the FVTT chat log, including dice rolls,
+//! is fetched from the connected GM client. The default model condenses both
+//! into a structured recap, which is stored as an indexed Markdown document so
+//! later sessions can search it, and optionally written to an FVTT journal.
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::error::{OllamaError, ServiceError, ServiceResult};
+use crate::ollama::{ChatMessage, extract_json_object};
+use crate::service::{ModelTask, SeneschalService};
+use crate::tools::AccessLevel;
+
+/// Tag applied to every stored session summary
+const SESSION_SUMMARY_TAG: &str = "session-summary";
+
+/// Maximum chat messages fetched from FVTT
+const CHAT_LOG_LIMIT: u64 = 500;
+
+/// Source text sent to the model is trimmed to its most recent part beyond this
+const MAX_SOURCE_CHARS: usize = 60_000;
+
+/// What to summarize and where to put the result
+#[derive(Debug, Clone)]
+pub struct SessionSummaryOptions {
+    pub title: String,
+    /// Conversation transcript or GM notes from the MCP client
+    pub transcript: Option<String>,
+    pub include_chat_log: bool,
+    /// Only chat messages after this ISO 8601 timestamp
+    pub chat_since: Option<String>,
+    pub access_level: AccessLevel,
+    pub write_journal: bool,
+    pub journal_folder: Option<String>,
+}
+
+/// An NPC the party interacted with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpcMet {
+    pub name: String,
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Structured recap as produced by the model
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionRecap {
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub scenes: Vec<String>,
+    #[serde(default)]
+    pub npcs: Vec<NpcMet>,
+    #[serde(default)]
+    pub loot: Vec<String>,
+    #[serde(default)]
+    pub open_threads: Vec<String>,
+    /// In-game date from the campaign clock when the recap was made
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub imperial_date: Option<String>,
+}
+
+/// Generated recap and where it was stored
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub title: String,
+    #[serde(flatten)]
+    pub recap: SessionRecap,
+    pub chat_messages: usize,
+    pub document_id: String,
+    pub journal_id: Option<String>,
+    /// Set when the journal was requested but couldn't be written
+    pub journal_error: Option<String>,
+}
+
+impl SeneschalService {
+    /// Generate a session recap, store it as a document, and optionally write an FVTT journal.
+    pub async fn generate_session_summary(
+        &self,
+        options: SessionSummaryOptions,
+    ) -> ServiceResult<SessionSummary> {
+        let (chat_log, chat_messages) = if options.include_chat_log {
+            match self.fetch_chat_log(options.chat_since.as_deref()).await {
+                Ok(log) => log,
+                // A transcript alone is still worth summarizing
+                Err(e) if options.transcript.is_some() => {
+                    warn!(error = %e, "Chat log unavailable; summarizing transcript only");
+                    (String::new(), 0)
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            (String::new(), 0)
+        };
+
+        let transcript = options.transcript.as_deref().unwrap_or("").trim();
+        if transcript.is_empty() && chat_log.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Nothing to summarize: no transcript given and the chat log is empty"
+                    .to_string(),
+            });
+        }
+
+        let mut recap = self.summarize_session(transcript, &chat_log).await?;
+        recap.imperial_date = self.campaign_date(None)?.map(|date| date.to_string());
+        let markdown = recap_to_markdown(&options.title, &recap);
+
+        let filename = format!(
+            "session_summary_{}.md",
+            chrono::Utc::now().format("%Y%m%d_%H%M%S")
+        );
+        let document = self
+            .upload_document(
+                markdown.as_bytes(),
+                &filename,
+                &options.title,
+                options.access_level,
+                vec![SESSION_SUMMARY_TAG.to_string()],
+                None,
+                None,
+                None,
+            )
+            .await?;
+        self.db.update_document_metadata(
+            &document.id,
+            Some(serde_json::json!({ "source": "session_summary" })),
+        )?;
+        self.assign_mcp_world(&document.id)?;
+
+        let (journal_id, journal_error) = if options.write_journal {
+            match self
+                .write_summary_journal(&options.title, &recap, options.journal_folder)
+                .await
+            {
+                Ok(id) => (id, None),
+                Err(e) => (None, Some(e)),
+            }
+        } else {
+            (None, None)
+        };
+
+        info!(
+            doc_id = %document.id,
+            chat_messages = chat_messages,
+            journal = journal_id.is_some(),
+            "Session summary generated"
+        );
+
+        Ok(SessionSummary {
+            title: options.title,
+            recap,
+            chat_messages,
+            document_id: document.id,
+            journal_id,
+            journal_error,
+        })
+    }
+
+    /// Fetch the FVTT chat log as one line per message, with the message count.
+    async fn fetch_chat_log(&self, since: Option<&str>) -> ServiceResult<(String, usize)> {
+        let timeout = self
+            .runtime_config
+            .dynamic()
+            .agentic_loop
+            .external_tool_timeout();
+
+        let mut args = serde_json::json!({ "limit": CHAT_LOG_LIMIT });
+        if let Some(since) = since {
+            args["since"] = serde_json::Value::from(since);
+        }
+
+        let response = self
+            .execute_external_tool_mcp("chat_log", args, timeout)
+            .await
+            .map_err(|message| ServiceError::InvalidRequest { message })?;
+
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Failed to read chat log: {}", error),
+            });
+        }
+
+        let messages = response
+            .get("messages")
+            .and_then(|m| m.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let lines: Vec<String> = messages.iter().map(format_chat_message).collect();
+        Ok((lines.join("\n"), messages.len()))
+    }
+
+    async fn summarize_session(
+        &self,
+        transcript: &str,
+        chat_log: &str,
+    ) -> ServiceResult<SessionRecap> {
+        let mut source = String::new();
+        if !transcript.is_empty() {
+            source.push_str("## Transcript\n");
+            source.push_str(transcript);
+            source.push_str("\n\n");
+        }
+        if !chat_log.is_empty() {
+            source.push_str("## Chat log and dice rolls\n");
+            source.push_str(chat_log);
+        }
+
+        let prompt = format!(
+            "You are recording notes for a {} session. \
+            From the session material below, write a recap for the GM.\n\n\
+            {}\n\n\
+            Respond with only JSON in this shape:\n\
+            {{\"summary\": \"2-4 sentence overview\", \
+            \"scenes\": [\"one entry per scene or location, in order\"], \
+            \"npcs\": [{{\"name\": \"...\", \"notes\": \"who they are and how the party interacted\"}}], \
+            \"loot\": [\"items, credits or information gained\"], \
+            \"open_threads\": [\"unresolved hooks, promises and mysteries\"]}}",
+            self.mcp_system_profile().name,
+            tail_chars(&source, MAX_SOURCE_CHARS)
+        );
+
+        let response = self
+            .generate_routed(
+                ModelTask::Summarization,
+                None,
+                vec![ChatMessage::user(prompt)],
+            )
+            .await?;
+
+        serde_json::from_str(extract_json_object(&response))
+            .map_err(|e| OllamaError::InvalidResponse { source: e }.into())
+    }
+
+    /// Create an FVTT journal for the recap, returning its ID.
+    async fn write_summary_journal(
+        &self,
+        title: &str,
+        recap: &SessionRecap,
+        folder: Option<String>,
+    ) -> Result<Option<String>, String> {
+        let timeout = self
+            .runtime_config
+            .dynamic()
+            .agentic_loop
+            .external_tool_timeout();
+
+        let mut args = serde_json::json!({
+            "name": title,
+            "content": recap_to_html(recap),
+        });
+        if let Some(folder) = folder {
+            args["folder"] = serde_json::Value::from(folder);
+        }
+
+        let response = self
+            .execute_external_tool_mcp("create_journal", args, timeout)
+            .await?;
+
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            return Err(error.to_string());
+        }
+        Ok(response
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string))
+    }
+}
+
+fn format_chat_message(message: &serde_json::Value) -> String {
+    let field = |name: &str| message.get(name).and_then(|v| v.as_str()).unwrap_or("");
+
+    let mut line = format!("[{}] {}: ", field("timestamp"), field("speaker"));
+    if !field("flavor").is_empty() {
+        line.push_str(&format!("({}) ", field("flavor")));
+    }
+    line.push_str(field("content"));
+
+    if let Some(rolls) = message.get("rolls").and_then(|r| r.as_array()) {
+        let rolls: Vec<String> = rolls
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} = {}",
+                    r.get("formula").and_then(|f| f.as_str()).unwrap_or("?"),
+                    r.get("total").map(|t| t.to_string()).unwrap_or_default()
+                )
+            })
+            .collect();
+        line.push_str(&format!(" [rolled {}]", rolls.join(", ")));
+    }
+    line
+}
+
+/// The last `max` characters of `text`, on a char boundary
+fn tail_chars(text: &str, max: usize) -> &str {
+    let count = text.chars().count();
+    if count <= max {
+        return text;
+    }
+    let start = text
+        .char_indices()
+        .nth(count - max)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    &text[start..]
+}
+
+fn recap_to_markdown(title: &str, recap: &SessionRecap) -> String {
+    let mut md = format!("# {}\n\n", title);
+    if let Some(date) = &recap.imperial_date {
+        md.push_str(&format!("*Imperial date: {}*\n\n", date));
+    }
+    md.push_str(&format!("{}\n", recap.summary.trim()));
+
+    let mut section = |heading: &str, items: Vec<String>| {
+        if !items.is_empty() {
+            md.push_str(&format!("\n## {}\n\n", heading));
+            for item in items {
+                md.push_str(&format!("- {}\n", item));
+            }
+        }
+    };
+    section("Scenes", recap.scenes.clone());
+    section(
+        "NPCs Met",
+        recap
+            .npcs
+            .iter()
+            .map(|npc| {
+                if npc.notes.is_empty() {
+                    npc.name.clone()
+                } else {
+                    format!("**{}**: {}", npc.name, npc.notes)
+                }
+            })
+            .collect(),
+    );
+    section("Loot", recap.loot.clone());
+    section("Open Threads", recap.open_threads.clone());
+
+    md
+}
+
+fn recap_to_html(recap: &SessionRecap) -> String {
+    let mut html = String::new();
+    if let Some(date) = &recap.imperial_date {
+        html.push_str(&format!(
+            "<p><em>Imperial date: {}</em></p>",
+            escape_html(date)
+        ));
+    }
+    html.push_str(&format!("<p>{}</p>", escape_html(recap.summary.trim())));
+
+    let mut section = |heading: &str, items: Vec<String>| {
+        if !items.is_empty() {
+            html.push_str(&format!("<h2>{}</h2><ul>", heading));
+            for item in items {
+                html.push_str(&format!("<li>{}</li>", item));
+            }
+            html.push_str("</ul>");
+        }
+    };
+    let escaped = |items: &[String]| items.iter().map(|i| escape_html(i)).collect();
+    section("Scenes", escaped(&recap.scenes));
+    section(
+        "NPCs Met",
+        recap
+            .npcs
+            .iter()
+            .map(|npc| {
+                if npc.notes.is_empty() {
+                    escape_html(&npc.name)
+                } else {
+                    format!(
+                        "<strong>{}</strong>: {}",
+                        escape_html(&npc.name),
+                        escape_html(&npc.notes)
+                    )
+                }
+            })
+            .collect(),
+    );
+    section("Loot", escaped(&recap.loot));
+    section("Open Threads", escaped(&recap.open_threads));
+
+    html
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recap_rendering() {
+        let recap = SessionRecap {
+            summary: "The crew took a job on Regina.".to_string(),
+            scenes: vec!["Startown bar".to_string()],
+            npcs: vec![NpcMet {
+                name: "Anders Casarii".to_string(),
+                notes: "Patron <offered> Cr5000".to_string(),
+            }],
+            loot: vec![],
+            open_threads: vec!["Who is following them?".to_string()],
+            imperial_date: Some("187-1105".to_string()),
+        };
+
+        let md = recap_to_markdown("Session 3", &recap);
+        assert!(md.starts_with("# Session 3\n\n*Imperial date: 187-1105*\n"));
+        assert!(md.contains("## NPCs Met\n\n- **Anders Casarii**: Patron <offered> Cr5000"));
+        assert!(!md.contains("## Loot"));
+
+        let html = recap_to_html(&recap);
+        assert!(html.contains("Patron &lt;offered&gt; Cr5000"));
+        assert!(!html.contains("<h2>Loot</h2>"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Answers the GM shares with players.
+//!
+//! Server messages normally go to one connection. A GM can share an answer
+//! (a chat message, usually one an assistant wrote) with the players, like a
+//! handout: the service strips what players shouldn't see and broadcasts it
+//! to connected player sessions allowed to read it. An answer citing
+//! documents is only shown to players with access to all of them.
+
+use tracing::info;
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::fvtt::{html_to_text, strip_secrets};
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+use crate::websocket::ServerMessage;
+
+/// Prefixes of lines addressed to the GM
+const GM_LINE_PREFIXES: &[&str] = &["gm:", "gm note:", "gm only:", "[gm]"];
+
+/// An answer as players get to see it: secret blocks and GM notes removed,
+/// markup reduced to plain text
+pub(crate) fn sanitize_shared_answer(content: &str) -> String {
+    html_to_text(&strip_secrets(content))
+        .lines()
+        .filter(|line| {
+            let lower = line.trim_start().to_lowercase();
+            !GM_LINE_PREFIXES
+                .iter()
+                .any(|prefix| lower.starts_with(prefix))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The outcome of sharing an answer
+#[derive(Debug, Clone)]
+pub struct SharedAnswer {
+    pub share_id: String,
+    /// Lowest role that was sent the answer
+    pub access_level: AccessLevel,
+    /// Player connections the answer was sent to
+    pub recipients: usize,
+}
+
+impl SeneschalService {
+    /// Broadcast a sanitized answer to connected players allowed to see it
+    ///
+    /// The answer goes to players at `access_level` and above (all players by
+    /// default), raised to the access level of each cited document. Only
+    /// players in the sharing GM's world receive it.
+    pub fn share_answer_with_players(
+        &self,
+        content: &str,
+        title: Option<String>,
+        access_level: Option<AccessLevel>,
+        document_ids: &[String],
+        world_id: Option<&str>,
+    ) -> ServiceResult<SharedAnswer> {
+        let mut level = access_level.unwrap_or(AccessLevel::Player);
+        for document_id in document_ids {
+            let document = self.db.get_document(document_id)?.ok_or_else(|| {
+                ServiceError::DocumentNotFound {
+                    document_id: document_id.clone(),
+                }
+            })?;
+            level = level.max(document.access_level);
+        }
+        if level == AccessLevel::GmOnly {
+            return Err(ServiceError::InvalidRequest {
+                message: "The answer is only visible to GMs, so there is no one to share it with"
+                    .to_string(),
+            });
+        }
+
+        let text = sanitize_shared_answer(content);
+        if text.trim().is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Nothing is left to share once GM-only content is removed".to_string(),
+            });
+        }
+
+        let share_id = uuid::Uuid::new_v4().to_string();
+        let recipients = self.ws_manager.broadcast_to_players(
+            level as u8,
+            world_id,
+            ServerMessage::SharedAnswer {
+                share_id: share_id.clone(),
+                title,
+                content: text,
+            },
+        );
+        info!(share_id = %share_id, access_level = ?level, recipients, "Shared answer with players");
+
+        Ok(SharedAnswer {
+            share_id,
+            access_level: level,
+            recipients,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_shared_answer() {
+        let content = r#"<p>The <strong>Highndry</strong> is on Walston.</p><section class="secret" id="s1"><p>It is rigged to explode.</p></section><p>GM: roll 2D for the patrol.</p><p>Docking costs Cr100 &amp; up.</p>"#;
+        assert_eq!(
+            sanitize_shared_answer(content),
+            "The Highndry is on Walston.\nDocking costs Cr100 & up."
+        );
+        assert_eq!(sanitize_shared_answer("[GM] only for you"), "");
+    }
+}
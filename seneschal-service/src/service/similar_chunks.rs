@@ -0,0 +1,74 @@
+//! "More like this" for document chunks.
+//!
+//! A chunk's stored embedding is used as the query, so no embedding model
+//! call is needed. Chunks from the same page are left out: they are the
+//! text around the rule being read rather than related rules and errata
+//! elsewhere.
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::search::SearchResult;
+use crate::service::SeneschalService;
+
+impl SeneschalService {
+    /// Find chunks similar to an indexed chunk, from other pages and documents
+    /// (limited to those visible from `world_id`, when given)
+    pub fn search_chunks_like(
+        &self,
+        chunk_id: &str,
+        user_role: u8,
+        limit: usize,
+        world_id: Option<&str>,
+    ) -> ServiceResult<Vec<SearchResult>> {
+        let source = self
+            .db
+            .get_chunk(chunk_id)?
+            .filter(|chunk| chunk.access_level.accessible_by(user_role))
+            .ok_or_else(|| ServiceError::ChunkNotFound {
+                chunk_id: chunk_id.to_string(),
+            })?;
+
+        let embedding =
+            self.db
+                .get_chunk_embedding(chunk_id)?
+                .ok_or_else(|| ServiceError::InvalidRequest {
+                    message: format!("Chunk {} has not been embedded yet", chunk_id),
+                })?;
+
+        // Fetch enough to make up for the source page's own chunks
+        let same_page = match source.page_number {
+            Some(page) => self
+                .db
+                .get_chunks_by_page(&source.document_id, page, user_role)?
+                .len(),
+            None => 1,
+        };
+
+        let results = self
+            .db
+            .search_chunks(
+                &embedding,
+                user_role,
+                limit + same_page,
+                None,
+                false,
+                world_id,
+                0.0,
+            )?
+            .into_iter()
+            .filter(|(chunk, _)| {
+                chunk.id != source.id
+                    && !(chunk.document_id == source.document_id
+                        && source.page_number.is_some()
+                        && chunk.page_number == source.page_number)
+            })
+            .take(limit)
+            .map(|(chunk, similarity)| SearchResult {
+                chunk,
+                similarity,
+                errata: None,
+            })
+            .collect();
+
+        self.apply_errata(results)
+    }
+}
@@ -0,0 +1,154 @@
+//! Spoken answers and read-aloud text.
+//!
+//! A chat answer or a stored read-aloud passage is reduced to plain text,
+//! synthesized by the configured TTS server (see `crate::tts`), stored under
+//! the data directory and delivered to the FVTT assets directory, so a GM
+//! can play it to the table.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tracing::info;
+
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::fvtt::html_to_text;
+use crate::service::{ImageDelivery, SeneschalService};
+
+/// Longest text synthesized in one clip; longer text is cut at a sentence end
+const MAX_SPEECH_CHARS: usize = 4000;
+
+/// What to speak
+#[derive(Debug, Clone)]
+pub enum SpeechSource {
+    /// Answer text (HTML or Markdown)
+    Text(String),
+    /// A stored read-aloud passage, by ID
+    ReadAloud(String),
+}
+
+/// A synthesized clip
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechClip {
+    pub file_name: String,
+    /// Characters of text spoken
+    pub text_chars: usize,
+    pub size_bytes: usize,
+    #[serde(flatten)]
+    pub delivery: ImageDelivery,
+}
+
+/// Text as it should be spoken: markup removed, whitespace collapsed, and
+/// cut at a sentence end if too long
+pub(crate) fn speech_text(content: &str) -> String {
+    let mut plain = String::new();
+    for word in html_to_text(content).split_whitespace() {
+        let word = word.trim_matches(['*', '_', '#', '`']);
+        if word.is_empty() {
+            continue;
+        }
+        // Inline tags leave a space before punctuation ("Walston ." from "<em>Walston</em>.")
+        if !plain.is_empty() && !word.starts_with(['.', ',', ';', ':', '!', '?', ')']) {
+            plain.push(' ');
+        }
+        plain.push_str(word);
+    }
+    if plain.chars().count() <= MAX_SPEECH_CHARS {
+        return plain;
+    }
+
+    let cut: String = plain.chars().take(MAX_SPEECH_CHARS).collect();
+    match cut.rfind(['.', '!', '?']) {
+        Some(end) => cut[..=end].to_string(),
+        None => cut,
+    }
+}
+
+impl SeneschalService {
+    fn speech_dir(&self) -> PathBuf {
+        self.runtime_config
+            .static_config
+            .storage
+            .data_dir
+            .join("speech")
+    }
+
+    /// The stored copy of a clip, by its file name
+    pub fn speech_path(&self, file_name: &str) -> ServiceResult<PathBuf> {
+        let path = self.speech_dir().join(file_name);
+        if file_name.contains(['/', '\\']) || file_name.starts_with('.') || !path.is_file() {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("Speech clip not found: {}", file_name),
+            });
+        }
+        Ok(path)
+    }
+
+    /// Synthesize speech for an answer or read-aloud passage and deliver it to FVTT
+    pub async fn synthesize_speech(
+        &self,
+        source: &SpeechSource,
+        max_access_level: u8,
+        world_id: Option<&str>,
+    ) -> ServiceResult<SpeechClip> {
+        let content = match source {
+            SpeechSource::Text(text) => text.clone(),
+            SpeechSource::ReadAloud(passage_id) => {
+                self.db
+                    .get_read_aloud(passage_id, max_access_level, world_id)?
+                    .ok_or_else(|| ServiceError::InvalidRequest {
+                        message: format!("Read-aloud passage not found: {}", passage_id),
+                    })?
+                    .text
+            }
+        };
+        let text = speech_text(&content);
+        if text.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "There is no text to speak".to_string(),
+            });
+        }
+
+        let config = self.runtime_config.dynamic().tts.clone();
+        let audio = crate::tts::synthesize(&config, &text).await?;
+
+        let file_name = format!("speech-{}.wav", uuid::Uuid::new_v4().simple());
+        let dir = self.speech_dir();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+        let stored = dir.join(&file_name);
+        std::fs::write(&stored, &audio)
+            .map_err(|e| ServiceError::Processing(ProcessingError::Io(e)))?;
+
+        let delivery = self.deliver_file(&stored, &format!("seneschal/speech/{}", file_name))?;
+        info!(file_name = %file_name, chars = text.len(), "Speech synthesized");
+
+        Ok(SpeechClip {
+            file_name,
+            text_chars: text.chars().count(),
+            size_bytes: audio.len(),
+            delivery,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_text() {
+        assert_eq!(
+            speech_text("<p>The <strong>Highndry</strong>\n is   on <em>Walston</em>.</p>"),
+            "The Highndry is on Walston."
+        );
+        assert_eq!(
+            speech_text("## Arrival\n\nThe port is **hot** &amp; dusty."),
+            "Arrival The port is hot & dusty."
+        );
+
+        let long = "Jump space is dark. ".repeat(MAX_SPEECH_CHARS / 10);
+        let spoken = speech_text(&long);
+        assert!(spoken.len() <= MAX_SPEECH_CHARS);
+        assert!(spoken.ends_with("dark."));
+    }
+}
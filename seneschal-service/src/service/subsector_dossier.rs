@@ -0,0 +1,351 @@
+//! Subsector dossiers combining Traveller Map data with the library.
+//!
+//! For each world in a subsector the dossier decodes its UWP into a short
+//! summary and looks up the indexed passages that name it. Passages from
+//! adventure documents are listed as hooks, the rest as background, and
+//! Amber and Red zones are gathered up front. Unlike `plot_hooks`, building
+//! a dossier doesn't mark anything surfaced.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::library_data::excerpt;
+use crate::error::{ServiceError, ServiceResult};
+use crate::search::ErrataStatus;
+use crate::service::SeneschalService;
+use crate::tools::SearchFilters;
+use crate::tools::traveller::decode_uwp;
+use crate::tools::traveller_map::SectorWorld;
+
+/// Most library passages listed per world
+pub const MAX_DOSSIER_MENTIONS: usize = 10;
+
+/// Candidate passages retrieved per mention wanted; most won't name the world
+const CANDIDATES_PER_MENTION: usize = 3;
+
+/// Longest passage excerpt, in characters
+const MAX_MENTION_CHARS: usize = 400;
+
+/// A library passage naming a world
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldMention {
+    pub chunk_id: String,
+    pub document_id: String,
+    pub document_title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    pub excerpt: String,
+}
+
+/// One world's entry in a dossier
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldDossier {
+    pub hex: String,
+    pub name: String,
+    pub uwp: String,
+    /// The UWP decoded, or why it couldn't be
+    pub summary: String,
+    pub zone: TravelZone,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub bases: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub remarks: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub allegiance: String,
+    /// Passages from adventure documents
+    pub hooks: Vec<WorldMention>,
+    /// Passages from everything else
+    pub background: Vec<WorldMention>,
+}
+
+/// Travel zone assigned by the Travellers' Aid Society
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TravelZone {
+    Green,
+    Amber,
+    Red,
+}
+
+impl TravelZone {
+    fn from_code(code: &str) -> Self {
+        match code.trim() {
+            "A" | "a" => TravelZone::Amber,
+            "R" | "r" => TravelZone::Red,
+            _ => TravelZone::Green,
+        }
+    }
+}
+
+/// A subsector's worlds with what the library says about them
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsectorDossier {
+    pub sector: String,
+    pub subsector: String,
+    pub worlds: Vec<WorldDossier>,
+    /// Names of Amber zone worlds
+    pub amber_zones: Vec<String>,
+    /// Names of Red zone worlds
+    pub red_zones: Vec<String>,
+    /// Adventure hooks found across all worlds
+    pub hook_count: usize,
+}
+
+/// One line describing a decoded UWP
+pub(super) fn uwp_summary(uwp: &str) -> String {
+    let parsed = match decode_uwp(uwp) {
+        Ok(parsed) => parsed,
+        Err(e) => return e,
+    };
+    let starport = parsed
+        .starport_quality
+        .split(" - ")
+        .next()
+        .unwrap_or_default();
+    format!(
+        "Starport {} ({}), {}, {} atmosphere, {} water; population {}, {}, law level {}, TL {}",
+        parsed.starport,
+        starport,
+        parsed.size_km,
+        parsed.atmosphere_type,
+        parsed.hydrographics_percent,
+        parsed.population_range.to_lowercase(),
+        parsed.government_type,
+        parsed.law_level,
+        parsed.tech_level
+    )
+}
+
+/// Whether `content` names `name` as a whole word, ignoring case
+fn mentions_name(content: &str, name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return false;
+    }
+    let content = content.to_lowercase();
+    content.match_indices(&name).any(|(at, _)| {
+        let before = content[..at].chars().next_back();
+        let after = content[at + name.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+impl SubsectorDossier {
+    /// The dossier as text for the GM
+    pub fn render(&self) -> String {
+        let mut text = format!("# {} subsector, {}\n", self.subsector, self.sector);
+        if !self.amber_zones.is_empty() {
+            let _ = writeln!(text, "Amber zones: {}", self.amber_zones.join(", "));
+        }
+        if !self.red_zones.is_empty() {
+            let _ = writeln!(text, "Red zones: {}", self.red_zones.join(", "));
+        }
+
+        for world in &self.worlds {
+            let zone = match world.zone {
+                TravelZone::Green => "",
+                TravelZone::Amber => " [AMBER]",
+                TravelZone::Red => " [RED]",
+            };
+            let _ = writeln!(
+                text,
+                "\n## {} ({}) {}{}",
+                world.name, world.hex, world.uwp, zone
+            );
+            let _ = writeln!(text, "{}", world.summary);
+            if !world.remarks.is_empty() {
+                let _ = writeln!(text, "Trade codes: {}", world.remarks);
+            }
+            if !world.bases.is_empty() {
+                let _ = writeln!(text, "Bases: {}", world.bases);
+            }
+            for (heading, mentions) in [("Hooks", &world.hooks), ("Background", &world.background)]
+            {
+                if mentions.is_empty() {
+                    continue;
+                }
+                let _ = writeln!(text, "{}:", heading);
+                for mention in mentions {
+                    let page = mention
+                        .page
+                        .map(|p| format!(", p. {}", p))
+                        .unwrap_or_default();
+                    let _ = writeln!(
+                        text,
+                        "- {} ({}{})",
+                        mention.excerpt, mention.document_title, page
+                    );
+                }
+            }
+        }
+        text
+    }
+}
+
+/// Title, and whether it's an adventure, of documents already looked up
+pub(super) type MentionDocuments = HashMap<String, (String, bool)>;
+
+impl SeneschalService {
+    /// Library passages naming a world: those from documents tagged
+    /// `hook_tag` as hooks, and the rest as background
+    pub(super) async fn world_mentions(
+        &self,
+        name: &str,
+        max_mentions: usize,
+        hook_tag: &str,
+        user_role: u8,
+        documents: &mut MentionDocuments,
+    ) -> ServiceResult<(Vec<WorldMention>, Vec<WorldMention>)> {
+        let mut hooks = Vec::new();
+        let mut background = Vec::new();
+        if max_mentions == 0 || name.is_empty() {
+            return Ok((hooks, background));
+        }
+
+        let filters = SearchFilters {
+            world_id: self.mcp_world_id(),
+            ..Default::default()
+        };
+        let results = self
+            .search(
+                name,
+                user_role,
+                max_mentions * CANDIDATES_PER_MENTION,
+                Some(filters),
+            )
+            .await?;
+
+        for result in results {
+            if hooks.len() + background.len() >= max_mentions {
+                break;
+            }
+            if matches!(result.errata, Some(ErrataStatus::Superseded { .. }))
+                || !mentions_name(&result.chunk.content, name)
+            {
+                continue;
+            }
+            let chunk = result.chunk;
+            let (document_title, is_hook) = match documents.get(&chunk.document_id) {
+                Some(document) => document.clone(),
+                None => {
+                    let document = match self.db.get_document(&chunk.document_id)? {
+                        Some(doc) => (
+                            doc.title,
+                            doc.tags.iter().any(|t| t.eq_ignore_ascii_case(hook_tag)),
+                        ),
+                        None => (chunk.document_id.clone(), false),
+                    };
+                    documents.insert(chunk.document_id.clone(), document.clone());
+                    document
+                }
+            };
+            let mention = WorldMention {
+                excerpt: excerpt(name, &[chunk.content.as_str()], MAX_MENTION_CHARS),
+                chunk_id: chunk.id,
+                document_id: chunk.document_id,
+                document_title,
+                page: chunk.page_number,
+            };
+            if is_hook {
+                hooks.push(mention);
+            } else {
+                background.push(mention);
+            }
+        }
+        Ok((hooks, background))
+    }
+
+    /// Build a dossier for the worlds of a subsector, looking up passages
+    /// that name each world. Documents tagged `hook_tag` supply the hooks.
+    pub async fn subsector_dossier(
+        &self,
+        sector: &str,
+        subsector: &str,
+        worlds: &[SectorWorld],
+        max_mentions: usize,
+        hook_tag: &str,
+        user_role: u8,
+    ) -> ServiceResult<SubsectorDossier> {
+        if worlds.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: format!("No worlds found in {} subsector {}", sector, subsector),
+            });
+        }
+        let max_mentions = max_mentions.min(MAX_DOSSIER_MENTIONS);
+        let mut documents = HashMap::new();
+
+        let mut entries = Vec::with_capacity(worlds.len());
+        for world in worlds {
+            let (hooks, background) = self
+                .world_mentions(
+                    &world.name,
+                    max_mentions,
+                    hook_tag,
+                    user_role,
+                    &mut documents,
+                )
+                .await?;
+
+            entries.push(WorldDossier {
+                hex: world.hex.clone(),
+                name: world.name.clone(),
+                uwp: world.uwp.clone(),
+                summary: uwp_summary(&world.uwp),
+                zone: TravelZone::from_code(&world.zone),
+                bases: world.bases.clone(),
+                remarks: world.remarks.clone(),
+                allegiance: world.allegiance.clone(),
+                hooks,
+                background,
+            });
+        }
+
+        let zoned = |zone: TravelZone| -> Vec<String> {
+            entries
+                .iter()
+                .filter(|w| w.zone == zone)
+                .map(|w| w.name.clone())
+                .collect()
+        };
+        Ok(SubsectorDossier {
+            sector: sector.to_string(),
+            subsector: subsector.to_string(),
+            amber_zones: zoned(TravelZone::Amber),
+            red_zones: zoned(TravelZone::Red),
+            hook_count: entries.iter().map(|w| w.hooks.len()).sum(),
+            worlds: entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_name() {
+        assert!(mentions_name("The Duke of Regina awaits.", "Regina"));
+        assert!(mentions_name("REGINA's downport", "regina"));
+        assert!(!mentions_name("Reginald is a noble.", "Regina"));
+        assert!(!mentions_name("anything", " "));
+    }
+
+    #[test]
+    fn test_uwp_summary() {
+        assert_eq!(
+            uwp_summary("A788899-C"),
+            "Starport A (Excellent), 11,200 km, Dense atmosphere, 80% water; \
+             population hundreds of millions, Impersonal Bureaucracy, law level 9, TL 12"
+        );
+        assert!(uwp_summary("A78").starts_with("Invalid UWP"));
+    }
+
+    #[test]
+    fn test_travel_zone() {
+        assert_eq!(TravelZone::from_code("A"), TravelZone::Amber);
+        assert_eq!(TravelZone::from_code("R"), TravelZone::Red);
+        assert_eq!(TravelZone::from_code(""), TravelZone::Green);
+    }
+}
@@ -0,0 +1,50 @@
+//! The game system each FVTT world runs.
+
+use tracing::{info, warn};
+
+use crate::error::ServiceResult;
+use crate::service::SeneschalService;
+use crate::system_profile::SystemProfile;
+
+impl SeneschalService {
+    /// Record the game system a world's GM client reported
+    pub fn set_world_system(&self, world_id: &str, system_id: &str) -> ServiceResult<()> {
+        let system_id = system_id.trim();
+        if world_id.is_empty() || system_id.is_empty() {
+            return Ok(());
+        }
+        if self.db.get_world_system(world_id)?.as_deref() != Some(system_id) {
+            info!(world_id = %world_id, system_id = %system_id, "World game system recorded");
+            self.db.set_world_system(world_id, system_id)?;
+        }
+        Ok(())
+    }
+
+    /// The profile of a world's game system
+    pub fn system_profile(&self, world_id: Option<&str>) -> &'static SystemProfile {
+        let system_id = match world_id.filter(|w| !w.is_empty()) {
+            Some(world_id) => self.db.get_world_system(world_id).unwrap_or_else(|e| {
+                warn!(world_id = %world_id, error = %e, "Failed to look up world game system");
+                None
+            }),
+            None => None,
+        };
+        SystemProfile::for_system(system_id.as_deref())
+    }
+
+    /// The profile of the MCP world's game system
+    pub fn mcp_system_profile(&self) -> &'static SystemProfile {
+        self.system_profile(self.mcp_world_id().as_deref())
+    }
+
+    /// The profile a document was written for: its world's, or the MCP
+    /// world's for documents shared by every world
+    pub(crate) fn document_system_profile(&self, document_id: &str) -> &'static SystemProfile {
+        match self.db.get_document(document_id) {
+            Ok(Some(document)) if document.world_id.is_some() => {
+                self.system_profile(document.world_id.as_deref())
+            }
+            _ => self.mcp_system_profile(),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Campaign tasks.
+//!
+//! Prep TODOs and reminders ("remind me next session to stat up the Aslan
+//! patrol") noted during planning chats. Open tasks are listed in the MCP
+//! server instructions, so they come up at the start of the next
+//! conversation in the campaign, until they are marked done.
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::db::CampaignTask;
+use crate::error::{ServiceError, ServiceResult};
+use crate::service::SeneschalService;
+
+impl SeneschalService {
+    /// Note a task to bring up in later conversations
+    pub fn add_task(
+        &self,
+        description: &str,
+        world_id: Option<String>,
+        session_id: Option<&str>,
+    ) -> ServiceResult<CampaignTask> {
+        let description = description.trim();
+        if description.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Task description is empty".to_string(),
+            });
+        }
+
+        let task = CampaignTask {
+            id: uuid::Uuid::new_v4().to_string(),
+            description: description.to_string(),
+            world_id: world_id.filter(|w| !w.is_empty()),
+            session_id: session_id.map(str::to_string),
+            created_at: Utc::now(),
+            completed_at: None,
+        };
+        self.db.insert_task(&task)?;
+        info!(task_id = %task.id, "Campaign task added");
+        Ok(task)
+    }
+
+    /// Mark a task done so it stops coming up
+    pub fn complete_task(&self, id: &str) -> ServiceResult<CampaignTask> {
+        let task = self
+            .db
+            .get_task(id)?
+            .ok_or_else(|| ServiceError::InvalidRequest {
+                message: format!("Task not found: {}", id),
+            })?;
+        if task.completed_at.is_some() {
+            return Ok(task);
+        }
+
+        self.db.complete_task(id)?;
+        info!(task_id = %id, "Campaign task completed");
+        self.db
+            .get_task(id)?
+            .ok_or_else(|| ServiceError::InvalidRequest {
+                message: format!("Task not found: {}", id),
+            })
+    }
+}
@@ -0,0 +1,93 @@
+//! Campaign timeline.
+//!
+//! Events are extracted on request from the documents a GM picks (a
+//! scenario's background chronology, a setting history) rather than from
+//! every upload, and the GM can add events by hand. Re-extracting a
+//! document replaces its extracted events but keeps manual ones.
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::db::{TimelineEvent, TimelineSource};
+use crate::error::{ServiceError, ServiceResult};
+use crate::ingestion::timeline::{ImperialDate, detect_dated_events};
+use crate::service::SeneschalService;
+use crate::tools::AccessLevel;
+
+impl SeneschalService {
+    /// Extract dated events from a processed document, returning how many were found
+    pub fn extract_document_timeline(&self, document_id: &str) -> ServiceResult<usize> {
+        if self.db.get_document(document_id)?.is_none() {
+            return Err(ServiceError::DocumentNotFound {
+                document_id: document_id.to_string(),
+            });
+        }
+
+        let now = Utc::now();
+        let events: Vec<TimelineEvent> = self
+            .db
+            .get_document_chunks(document_id)?
+            .into_iter()
+            .flat_map(|chunk| {
+                detect_dated_events(&chunk.content)
+                    .into_iter()
+                    .map(move |event| TimelineEvent {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        date: event.date,
+                        description: event.description,
+                        location: None,
+                        source: TimelineSource::Extracted,
+                        document_id: Some(chunk.document_id.clone()),
+                        chunk_id: Some(chunk.id.clone()),
+                        page_number: chunk.page_number,
+                        access_level: chunk.access_level,
+                        created_at: now,
+                    })
+            })
+            .collect();
+
+        self.db.replace_document_timeline(document_id, &events)?;
+        info!(document_id = %document_id, events = events.len(), "Timeline events extracted");
+        Ok(events.len())
+    }
+
+    /// Add an event by hand
+    pub fn add_timeline_event(
+        &self,
+        date: ImperialDate,
+        description: &str,
+        location: Option<String>,
+        access_level: AccessLevel,
+        document_id: Option<String>,
+    ) -> ServiceResult<TimelineEvent> {
+        let description = description.trim();
+        if description.is_empty() {
+            return Err(ServiceError::InvalidRequest {
+                message: "Timeline event description is empty".to_string(),
+            });
+        }
+        if let Some(document_id) = &document_id
+            && self.db.get_document(document_id)?.is_none()
+        {
+            return Err(ServiceError::DocumentNotFound {
+                document_id: document_id.clone(),
+            });
+        }
+
+        let event = TimelineEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            date,
+            description: description.to_string(),
+            location: location.filter(|l| !l.trim().is_empty()),
+            source: TimelineSource::Manual,
+            document_id,
+            chunk_id: None,
+            page_number: None,
+            access_level,
+            created_at: Utc::now(),
+        };
+        self.db.insert_timeline_event(&event)?;
+        info!(event_id = %event.id, date = %event.date, "Timeline event added");
+        Ok(event)
+    }
+}
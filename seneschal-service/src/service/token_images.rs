@@ -0,0 +1,135 @@
+//! Token cutouts derived from extracted character art.
+//!
+//! A token is stored as its own document image (type `token`, pointing at
+//! its source image), so it can be listed, tagged and delivered like any
+//! other image.
+
+use chrono::Utc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::db::{DocumentImage, DocumentImageWithAccess, ImageType};
+use crate::error::{ProcessingError, ServiceError, ServiceResult};
+use crate::ingestion::assets::fvtt_token_path;
+use crate::ingestion::tokens::{
+    DEFAULT_TOKEN_SIZE, TOKEN_SIZE_RANGE, TokenOptions, parse_ring_color, token_cutout, write_token,
+};
+use crate::service::{ImageDelivery, SeneschalService};
+
+impl SeneschalService {
+    /// Cut a circular token out of an image and store it as a derived image.
+    ///
+    /// `ring_color` is `#rrggbb`. This is blocking work and should be run off
+    /// the async runtime.
+    pub fn create_token_image(
+        &self,
+        image_id: &str,
+        size: Option<u32>,
+        ring_color: Option<&str>,
+    ) -> ServiceResult<DocumentImageWithAccess> {
+        let size = size.unwrap_or(DEFAULT_TOKEN_SIZE);
+        if !(TOKEN_SIZE_RANGE.0..=TOKEN_SIZE_RANGE.1).contains(&size) {
+            return Err(ServiceError::InvalidRequest {
+                message: format!(
+                    "Token size must be between {} and {} pixels",
+                    TOKEN_SIZE_RANGE.0, TOKEN_SIZE_RANGE.1
+                ),
+            });
+        }
+        let ring_color = ring_color
+            .map(|color| {
+                parse_ring_color(color).ok_or_else(|| ServiceError::InvalidRequest {
+                    message: format!("Invalid ring color '{}', expected #rrggbb", color),
+                })
+            })
+            .transpose()?;
+
+        let source =
+            self.db
+                .get_document_image(image_id)?
+                .ok_or_else(|| ServiceError::ImageNotFound {
+                    image_id: image_id.to_string(),
+                })?;
+        let art = image::open(&source.image.internal_path)
+            .map_err(|e| {
+                ProcessingError::Io(std::io::Error::other(format!(
+                    "Failed to decode {}: {}",
+                    source.image.internal_path, e
+                )))
+            })?
+            .to_rgba8();
+
+        let token = token_cutout(&art, TokenOptions { size, ring_color });
+
+        let document_id = &source.image.document_id;
+        let page_number = source.image.page_number;
+        let image_index = self
+            .db
+            .next_image_index(document_id, page_number, ImageType::Token)?;
+        let path = self
+            .runtime_config
+            .static_config
+            .storage
+            .data_dir
+            .join("images")
+            .join(document_id)
+            .join(format!("page_{}_token_{}.webp", page_number, image_index));
+        write_token(&token, &path)?;
+
+        let token_image = DocumentImage {
+            id: Uuid::new_v4().to_string(),
+            document_id: document_id.clone(),
+            page_number,
+            image_index,
+            internal_path: path.to_string_lossy().to_string(),
+            mime_type: "image/webp".to_string(),
+            width: Some(size),
+            height: Some(size),
+            description: Some(match &source.image.description {
+                Some(description) => format!("Token: {}", description),
+                None => "Token".to_string(),
+            }),
+            source_pages: source.image.source_pages.clone(),
+            image_type: ImageType::Token,
+            source_image_id: Some(source.image.id.clone()),
+            has_region_render: false,
+            created_at: Utc::now(),
+        };
+        self.db.insert_document_image(&token_image)?;
+
+        // Keep an access override on the source (e.g. a spoiler portrait)
+        let document_level = self.db.get_document(document_id)?.map(|d| d.access_level);
+        if document_level != Some(source.access_level) {
+            self.db.set_images_access_level(
+                std::slice::from_ref(&token_image.id),
+                Some(source.access_level),
+            )?;
+        }
+
+        info!(
+            image_id = %token_image.id,
+            source_image_id = %source.image.id,
+            size,
+            "Created token image"
+        );
+
+        Ok(DocumentImageWithAccess {
+            image: token_image,
+            document_title: source.document_title,
+            access_level: source.access_level,
+        })
+    }
+
+    /// Deliver a token image to its FVTT tokens folder
+    pub fn deliver_token_image(
+        &self,
+        token: &DocumentImageWithAccess,
+    ) -> ServiceResult<ImageDelivery> {
+        let path = fvtt_token_path(
+            &token.document_title,
+            token.image.page_number,
+            token.image.image_index,
+        );
+        self.deliver_image(token, &path.to_string_lossy())
+    }
+}
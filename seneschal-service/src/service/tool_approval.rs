@@ -0,0 +1,262 @@
+//! GM approval of tool calls that change the world.
+//!
+//! With approval required, a write tool call is held while a GM connection
+//! is asked to approve it; the call runs once approved, and is refused when
+//! denied, not answered in time, or the GM's connection closes first.
+
+use std::time::Duration;
+
+use dashmap::DashMap;
+use tokio::sync::oneshot;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::call_trace::current_correlation_id;
+use crate::websocket::{GmRoute, ServerMessage};
+
+use super::SeneschalService;
+
+/// A GM's answer to an approval request
+#[derive(Debug)]
+pub struct ApprovalDecision {
+    pub approved: bool,
+    pub reason: Option<String>,
+}
+
+/// How an approval request ended
+#[derive(Debug, PartialEq)]
+enum ApprovalOutcome {
+    Approved,
+    Denied(Option<String>),
+    Disconnected,
+    TimedOut,
+}
+
+/// An approval request waiting for a GM's answer
+struct PendingApproval {
+    /// Connection the request was sent to
+    gm_session_id: String,
+    sender: oneshot::Sender<ApprovalDecision>,
+}
+
+/// Approval requests waiting for a GM's answer, keyed by request_id
+#[derive(Default)]
+pub struct PendingApprovals {
+    requests: DashMap<String, PendingApproval>,
+}
+
+impl PendingApprovals {
+    /// Open a request sent to a GM connection
+    fn open(&self, gm_session_id: &str) -> (String, oneshot::Receiver<ApprovalDecision>) {
+        let request_id = Uuid::new_v4().to_string();
+        let (sender, receiver) = oneshot::channel();
+        self.requests.insert(
+            request_id.clone(),
+            PendingApproval {
+                gm_session_id: gm_session_id.to_string(),
+                sender,
+            },
+        );
+        (request_id, receiver)
+    }
+
+    /// Wait for the answer to a request, closing it when it ends
+    async fn wait(
+        &self,
+        request_id: &str,
+        receiver: oneshot::Receiver<ApprovalDecision>,
+        timeout: Duration,
+    ) -> ApprovalOutcome {
+        let outcome = tokio::time::timeout(timeout, receiver).await;
+        self.requests.remove(request_id);
+        match outcome {
+            Ok(Ok(ApprovalDecision { approved: true, .. })) => ApprovalOutcome::Approved,
+            Ok(Ok(ApprovalDecision { reason, .. })) => ApprovalOutcome::Denied(reason),
+            Ok(Err(_)) => ApprovalOutcome::Disconnected,
+            Err(_) => ApprovalOutcome::TimedOut,
+        }
+    }
+
+    /// Deliver an answer; false if no such request is waiting
+    fn resolve(&self, request_id: &str, decision: ApprovalDecision) -> bool {
+        match self.requests.remove(request_id) {
+            Some((_, pending)) => {
+                if pending.sender.send(decision).is_err() {
+                    debug!(request_id = %request_id, "Approval arrived after the request ended");
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fail the requests sent to a GM connection that closed
+    pub fn cancel_for_session(&self, gm_session_id: &str) {
+        self.requests
+            .retain(|_, pending| pending.gm_session_id != gm_session_id);
+    }
+
+    /// Number of requests waiting
+    fn waiting(&self) -> usize {
+        self.requests.len()
+    }
+}
+
+impl SeneschalService {
+    /// Ask a GM to approve a tool call, waiting for the answer.
+    ///
+    /// Returns why the call may not run when it is denied, times out or no
+    /// GM is connected to ask.
+    pub async fn request_tool_approval(
+        &self,
+        tool: &str,
+        args: &serde_json::Value,
+        mcp_session_id: Option<&str>,
+    ) -> Result<(), String> {
+        let (world_id, timeout) = {
+            let config = self.runtime_config.dynamic();
+            (
+                config.mcp.world_id.clone(),
+                config.agentic_loop.approval_timeout(),
+            )
+        };
+        let route = GmRoute {
+            world_id: Some(world_id.as_str()).filter(|w| !w.is_empty()),
+            affinity_key: mcp_session_id,
+            require_write_access: false,
+        };
+        let session_id = self.ws_manager.route_gm_connection(&route).ok_or_else(|| {
+            format!(
+                "'{}' needs GM approval, but no GM is connected to approve it",
+                tool
+            )
+        })?;
+
+        let (request_id, receiver) = self.pending_approvals.open(&session_id);
+        self.ws_manager
+            .broadcast_to_gms(ServerMessage::ToolCallAwaitingApproval {
+                session_id: mcp_session_id.map(str::to_string),
+                tool: tool.to_string(),
+                queue_position: self.pending_approvals.waiting(),
+            });
+
+        debug!(request_id = %request_id, tool = %tool, session_id = %session_id, "Requesting GM approval");
+        self.ws_manager.send_to(
+            &session_id,
+            ServerMessage::ChatApprovalRequired {
+                request_id: request_id.clone(),
+                tool: tool.to_string(),
+                args: args.clone(),
+                timeout_secs: timeout.as_secs(),
+                correlation_id: current_correlation_id(),
+            },
+        );
+
+        match self
+            .pending_approvals
+            .wait(&request_id, receiver, timeout)
+            .await
+        {
+            ApprovalOutcome::Approved => {
+                info!(request_id = %request_id, tool = %tool, "Tool call approved");
+                Ok(())
+            }
+            ApprovalOutcome::Denied(reason) => {
+                info!(request_id = %request_id, tool = %tool, "Tool call denied");
+                Err(match reason.filter(|r| !r.trim().is_empty()) {
+                    Some(reason) => format!("The GM denied '{}': {}", tool, reason.trim()),
+                    None => format!("The GM denied '{}'", tool),
+                })
+            }
+            ApprovalOutcome::Disconnected => Err(format!(
+                "The GM client disconnected before approving '{}'",
+                tool
+            )),
+            ApprovalOutcome::TimedOut => {
+                warn!(request_id = %request_id, tool = %tool, "Tool approval timed out");
+                Err(format!(
+                    "'{}' was not approved within {} seconds",
+                    tool,
+                    timeout.as_secs()
+                ))
+            }
+        }
+    }
+
+    /// Deliver a GM's answer to a pending approval request
+    pub fn handle_tool_approval(&self, request_id: &str, decision: ApprovalDecision) {
+        if !self.pending_approvals.resolve(request_id, decision) {
+            warn!(request_id = %request_id, "No pending approval request");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn decision(approved: bool, reason: Option<&str>) -> ApprovalDecision {
+        ApprovalDecision {
+            approved,
+            reason: reason.map(String::from),
+        }
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_approve_and_deny() {
+        let approvals = PendingApprovals::default();
+
+        let (request_id, receiver) = approvals.open("gm");
+        assert!(approvals.resolve(&request_id, decision(true, None)));
+        assert_eq!(
+            run(approvals.wait(&request_id, receiver, TIMEOUT)),
+            ApprovalOutcome::Approved
+        );
+
+        let (request_id, receiver) = approvals.open("gm");
+        assert!(approvals.resolve(&request_id, decision(false, Some("not yet"))));
+        assert_eq!(
+            run(approvals.wait(&request_id, receiver, TIMEOUT)),
+            ApprovalOutcome::Denied(Some("not yet".to_string()))
+        );
+        assert_eq!(approvals.waiting(), 0);
+        assert!(!approvals.resolve(&request_id, decision(true, None)));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let approvals = PendingApprovals::default();
+        let (request_id, receiver) = approvals.open("gm");
+        assert_eq!(
+            run(approvals.wait(&request_id, receiver, Duration::from_millis(10))),
+            ApprovalOutcome::TimedOut
+        );
+        assert_eq!(approvals.waiting(), 0);
+    }
+
+    #[test]
+    fn test_gm_disconnect_fails_its_requests() {
+        let approvals = PendingApprovals::default();
+        let (request_id, receiver) = approvals.open("gm-a");
+        let (other_id, _other) = approvals.open("gm-b");
+
+        approvals.cancel_for_session("gm-a");
+        assert_eq!(
+            run(approvals.wait(&request_id, receiver, TIMEOUT)),
+            ApprovalOutcome::Disconnected
+        );
+        // Requests sent to other GMs are still waiting
+        assert_eq!(approvals.waiting(), 1);
+        assert!(approvals.resolve(&other_id, decision(true, None)));
+    }
+}
@@ -0,0 +1,563 @@
+//! FVTT journals for Traveller worlds.
+//!
+//! A world journal has an overview, starport, notable locations and patrons
+//! page. The facts come from the Traveller Map, the passages naming the world
+//! from the library, and the prose from the chat model, which is told to
+//! stay consistent with both. Library images that fit the world are
+//! delivered for FVTT and added as image pages. When no model answers, the
+//! journal is built from the data and passages alone.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::session_summary::escape_html;
+use super::subsector_dossier::{MentionDocuments, WorldMention, uwp_summary};
+use crate::db::DocumentImageWithAccess;
+use crate::error::{OllamaError, ServiceError, ServiceResult};
+use crate::ollama::{ChatMessage, extract_json_object};
+use crate::service::{ImageDelivery, ModelTask, SeneschalService};
+use crate::tools::SearchFilters;
+use crate::tools::traveller::decode_uwp;
+use crate::tools::traveller_map::WorldData;
+
+/// Most library images added to a journal
+pub const MAX_JOURNAL_IMAGES: usize = 4;
+
+/// FVTT assets folder journal images are delivered under
+const JOURNAL_IMAGE_FOLDER: &str = "seneschal/worlds";
+
+/// What to put in a world journal
+#[derive(Debug, Clone)]
+pub struct WorldJournalOptions {
+    /// Library passages to look up
+    pub max_mentions: usize,
+    /// Tag marking adventure documents, whose passages become patron hooks
+    pub hook_tag: String,
+    /// Whether to write prose with the chat model
+    pub generate: bool,
+    /// Library images to add
+    pub images: usize,
+    /// Whether to deliver the images for FVTT; without it they're only listed
+    pub deliver_images: bool,
+}
+
+/// A journal page
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum JournalPageDraft {
+    Text { name: String, html: String },
+    Image { name: String, src: String },
+}
+
+/// A library image chosen for the journal
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalImage {
+    pub image_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery: Option<ImageDelivery>,
+}
+
+/// A world journal ready to create in FVTT
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldJournal {
+    pub name: String,
+    pub pages: Vec<JournalPageDraft>,
+    pub images: Vec<JournalImage>,
+    /// Whether the prose was written by the model
+    pub generated: bool,
+    /// Library passages used
+    pub mentions: usize,
+}
+
+/// A notable location as written by the model
+#[derive(Debug, Clone, Default, Deserialize)]
+struct GeneratedLocation {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// A patron as written by the model
+#[derive(Debug, Clone, Default, Deserialize)]
+struct GeneratedPatron {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    request: String,
+    #[serde(default)]
+    complication: String,
+}
+
+/// The model's prose for a world
+#[derive(Debug, Clone, Default, Deserialize)]
+struct GeneratedWorld {
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    starport: String,
+    #[serde(default)]
+    locations: Vec<GeneratedLocation>,
+    #[serde(default)]
+    patrons: Vec<GeneratedPatron>,
+}
+
+/// Text as HTML paragraphs, one per blank-line separated block
+fn paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| format!("<p>{}</p>", escape_html(p)))
+        .collect()
+}
+
+/// HTML list of library passages
+fn mention_list(heading: &str, mentions: &[WorldMention]) -> String {
+    if mentions.is_empty() {
+        return String::new();
+    }
+    let items: String = mentions
+        .iter()
+        .map(|m| {
+            let page = m.page.map(|p| format!(", p. {}", p)).unwrap_or_default();
+            format!(
+                "<li>{} <em>({}{})</em></li>",
+                escape_html(&m.excerpt),
+                escape_html(&m.document_title),
+                page
+            )
+        })
+        .collect();
+    format!("<h2>{}</h2><ul>{}</ul>", escape_html(heading), items)
+}
+
+/// The world's Traveller Map data as an HTML list
+fn fact_list(world: &WorldData, name: &str) -> String {
+    let mut facts = Vec::new();
+    if let (Some(sector), Some(hex)) = (&world.sector, &world.hex) {
+        facts.push(("Location", format!("{} {}", sector, hex)));
+    }
+    if let Some(uwp) = &world.uwp {
+        facts.push(("UWP", format!("{} ({})", uwp, uwp_summary(uwp))));
+    }
+    let optional = [
+        ("Trade codes", &world.remarks),
+        ("Bases", &world.bases),
+        ("Travel zone", &world.zone),
+        ("Allegiance", &world.allegiance),
+        ("PBG", &world.pbg),
+        ("Stellar", &world.stellar),
+    ];
+    for (label, value) in optional {
+        if let Some(value) = value.as_deref().map(str::trim)
+            && !value.is_empty()
+            && value != "-"
+        {
+            facts.push((label, value.to_string()));
+        }
+    }
+    let items: String = facts
+        .iter()
+        .map(|(label, value)| {
+            format!(
+                "<li><strong>{}:</strong> {}</li>",
+                label,
+                escape_html(value)
+            )
+        })
+        .collect();
+    format!("<h2>{}</h2><ul>{}</ul>", escape_html(name), items)
+}
+
+/// Build the journal's text pages
+fn text_pages(
+    world: &WorldData,
+    name: &str,
+    generated: &GeneratedWorld,
+    hooks: &[WorldMention],
+    background: &[WorldMention],
+) -> Vec<JournalPageDraft> {
+    let overview = format!(
+        "{}{}{}",
+        fact_list(world, name),
+        paragraphs(&generated.overview),
+        mention_list("From the library", background)
+    );
+
+    let starport = match world.uwp.as_deref().map(decode_uwp) {
+        Some(Ok(uwp)) => format!(
+            "<p><strong>Class {}:</strong> {}</p>",
+            uwp.starport,
+            escape_html(&uwp.starport_quality)
+        ),
+        _ => String::new(),
+    } + &paragraphs(&generated.starport);
+
+    let locations: String = generated
+        .locations
+        .iter()
+        .filter(|l| !l.name.trim().is_empty())
+        .map(|l| {
+            format!(
+                "<h2>{}</h2>{}",
+                escape_html(l.name.trim()),
+                paragraphs(&l.description)
+            )
+        })
+        .collect();
+
+    let mut patrons: String = generated
+        .patrons
+        .iter()
+        .filter(|p| !p.name.trim().is_empty())
+        .map(|p| {
+            let complication = if p.complication.trim().is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "<p><em>Complication:</em> {}</p>",
+                    escape_html(p.complication.trim())
+                )
+            };
+            format!(
+                "<h2>{}</h2>{}{}",
+                escape_html(p.name.trim()),
+                paragraphs(&p.request),
+                complication
+            )
+        })
+        .collect();
+    patrons.push_str(&mention_list("Hooks from the library", hooks));
+
+    [
+        ("Overview", overview),
+        ("Starport", starport),
+        ("Notable Locations", locations),
+        ("Patrons", patrons),
+    ]
+    .into_iter()
+    .filter(|(_, html)| !html.is_empty())
+    .map(|(page, html)| JournalPageDraft::Text {
+        name: page.to_string(),
+        html,
+    })
+    .collect()
+}
+
+impl WorldJournal {
+    /// Arguments for the create_journal tool
+    pub fn create_journal_args(&self, folder: Option<&str>) -> serde_json::Value {
+        let pages: Vec<serde_json::Value> = self
+            .pages
+            .iter()
+            .map(|page| match page {
+                JournalPageDraft::Text { name, html } => serde_json::json!({
+                    "name": name,
+                    "type": "text",
+                    "text": { "content": html }
+                }),
+                JournalPageDraft::Image { name, src } => serde_json::json!({
+                    "name": name,
+                    "type": "image",
+                    "src": src
+                }),
+            })
+            .collect();
+        let mut args = serde_json::json!({ "name": self.name, "pages": pages });
+        if let Some(folder) = folder {
+            args["folder"] = serde_json::json!(folder);
+        }
+        args
+    }
+}
+
+impl SeneschalService {
+    /// Build a journal for a world from its Traveller Map data, the library
+    /// passages that name it, and the model's prose
+    pub async fn world_journal(
+        &self,
+        world: &WorldData,
+        options: &WorldJournalOptions,
+        user_role: u8,
+    ) -> ServiceResult<WorldJournal> {
+        let name = world
+            .name
+            .clone()
+            .filter(|n| !n.trim().is_empty())
+            .ok_or_else(|| ServiceError::InvalidRequest {
+                message: "The world has no name to build a journal for".to_string(),
+            })?;
+
+        let mut documents = MentionDocuments::new();
+        let (hooks, background) = self
+            .world_mentions(
+                &name,
+                options.max_mentions,
+                &options.hook_tag,
+                user_role,
+                &mut documents,
+            )
+            .await?;
+
+        let generated = if options.generate {
+            match self
+                .generate_world_prose(world, &name, &hooks, &background)
+                .await
+            {
+                Ok(generated) => Some(generated),
+                Err(e) => {
+                    warn!(world = %name, error = %e, "World journal prose generation failed");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut pages = text_pages(
+            world,
+            &name,
+            generated.as_ref().unwrap_or(&GeneratedWorld::default()),
+            &hooks,
+            &background,
+        );
+        let images = self
+            .journal_images(world, &name, options, user_role)
+            .await?;
+        for (index, image) in images.iter().enumerate() {
+            let src = match &image.delivery {
+                Some(ImageDelivery::Direct { fvtt_path }) => fvtt_path,
+                Some(ImageDelivery::Shuttle { suggested_path }) => suggested_path,
+                None => continue,
+            };
+            pages.push(JournalPageDraft::Image {
+                name: format!("Image {}", index + 1),
+                src: src.clone(),
+            });
+        }
+
+        Ok(WorldJournal {
+            name,
+            pages,
+            images,
+            generated: generated.is_some(),
+            mentions: hooks.len() + background.len(),
+        })
+    }
+
+    /// Ask the chat model for the journal's prose
+    async fn generate_world_prose(
+        &self,
+        world: &WorldData,
+        name: &str,
+        hooks: &[WorldMention],
+        background: &[WorldMention],
+    ) -> ServiceResult<GeneratedWorld> {
+        let mut source = format!(
+            "World: {}\nLocation: {} {}\nUWP: {}\nTrade codes: {}\nBases: {}\nTravel zone: {}\nAllegiance: {}\n",
+            name,
+            world.sector.as_deref().unwrap_or("unknown"),
+            world.hex.as_deref().unwrap_or(""),
+            world
+                .uwp
+                .as_deref()
+                .map(|uwp| format!("{} ({})", uwp, uwp_summary(uwp)))
+                .unwrap_or_else(|| "unknown".to_string()),
+            world.remarks.as_deref().unwrap_or("none"),
+            world.bases.as_deref().unwrap_or("none"),
+            world.zone.as_deref().unwrap_or("green"),
+            world.allegiance.as_deref().unwrap_or("unknown"),
+        );
+        for (heading, mentions) in [("Library passages", background), ("Adventure hooks", hooks)] {
+            if mentions.is_empty() {
+                continue;
+            }
+            source.push_str(&format!("\n## {}\n", heading));
+            for mention in mentions {
+                source.push_str(&format!("- {}\n", mention.excerpt));
+            }
+        }
+
+        let prompt = format!(
+            "You are preparing a GM's journal for a world in a {} campaign. Using the world \
+            data and passages below, and never contradicting them, write:\n\
+            - an overview of the world for the GM (2-3 paragraphs)\n\
+            - a description of its starport and what travellers find there (1-2 paragraphs)\n\
+            - 3 to 5 notable locations\n\
+            - 3 patrons with a job for the travellers and a complication\n\n\
+            {}\n\
+            Respond with only JSON in this shape:\n\
+            {{\"overview\": \"...\", \"starport\": \"...\", \
+            \"locations\": [{{\"name\": \"...\", \"description\": \"...\"}}], \
+            \"patrons\": [{{\"name\": \"...\", \"request\": \"...\", \"complication\": \"...\"}}]}}",
+            self.mcp_system_profile().name,
+            source
+        );
+
+        let response = self
+            .generate_routed(ModelTask::Chat, None, vec![ChatMessage::user(prompt)])
+            .await?;
+        serde_json::from_str(extract_json_object(&response))
+            .map_err(|e| OllamaError::InvalidResponse { source: e }.into())
+    }
+
+    /// Library images of the world or its starport, delivered for FVTT when
+    /// asked to
+    async fn journal_images(
+        &self,
+        world: &WorldData,
+        name: &str,
+        options: &WorldJournalOptions,
+        user_role: u8,
+    ) -> ServiceResult<Vec<JournalImage>> {
+        let wanted = options.images.min(MAX_JOURNAL_IMAGES);
+        if wanted == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut landscape = format!("{} planet surface", name);
+        if let Some(Ok(uwp)) = world.uwp.as_deref().map(decode_uwp) {
+            landscape.push_str(&format!(
+                ", {} atmosphere, {} water",
+                uwp.atmosphere_type, uwp.hydrographics_percent
+            ));
+        }
+        let queries = [landscape, format!("{} starport", name)];
+        let filters = SearchFilters {
+            world_id: self.mcp_world_id(),
+            ..Default::default()
+        };
+
+        let mut chosen = Vec::new();
+        for query in &queries {
+            let embedding = self.search.embed_text(query).await?;
+            let results = self
+                .db
+                .search_images(&embedding, user_role, wanted, Some(&filters))?;
+            for (image, _) in results {
+                if chosen.len() >= wanted {
+                    break;
+                }
+                if !chosen
+                    .iter()
+                    .any(|c: &DocumentImageWithAccess| c.image.id == image.image.id)
+                {
+                    chosen.push(image);
+                }
+            }
+        }
+
+        let folder = Path::new(JOURNAL_IMAGE_FOLDER).join(slug(name));
+        let mut images = Vec::with_capacity(chosen.len());
+        for image in chosen {
+            let delivery = if options.deliver_images {
+                let file_name = Path::new(&image.image.internal_path)
+                    .file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_else(|| format!("{}.webp", image.image.id));
+                match self.deliver_image(&image, &folder.join(file_name).to_string_lossy()) {
+                    Ok(delivery) => Some(delivery),
+                    Err(e) => {
+                        debug!(image_id = %image.image.id, error = %e, "Journal image not delivered");
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+            images.push(JournalImage {
+                image_id: image.image.id,
+                description: image.image.description,
+                delivery,
+            });
+        }
+        Ok(images)
+    }
+}
+
+/// A world name as a folder name
+fn slug(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> WorldData {
+        serde_json::from_value(serde_json::json!({
+            "Name": "Regina",
+            "Sector": "Spinward Marches",
+            "Hex": "1910",
+            "UWP": "A788899-C",
+            "Remarks": "Ri Pa Ph An Cp",
+            "Bases": "NS",
+            "Zone": "",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("  Regina "), "regina");
+        assert_eq!(slug("Efate's Moon (B)"), "efate-s-moon-b");
+    }
+
+    #[test]
+    fn test_pages_without_generation() {
+        let pages = text_pages(&world(), "Regina", &GeneratedWorld::default(), &[], &[]);
+        let names: Vec<&str> = pages
+            .iter()
+            .map(|p| match p {
+                JournalPageDraft::Text { name, .. } | JournalPageDraft::Image { name, .. } => {
+                    name.as_str()
+                }
+            })
+            .collect();
+        assert_eq!(names, vec!["Overview", "Starport"]);
+        let JournalPageDraft::Text { html, .. } = &pages[0] else {
+            panic!("overview should be a text page");
+        };
+        assert!(html.contains("<strong>Trade codes:</strong> Ri Pa Ph An Cp"));
+        assert!(!html.contains("Travel zone"));
+    }
+
+    #[test]
+    fn test_create_journal_args() {
+        let generated = GeneratedWorld {
+            overview: "A rich world.\n\nThe <capital> sprawls.".to_string(),
+            patrons: vec![GeneratedPatron {
+                name: "Duke Norris".to_string(),
+                request: "Find the courier.".to_string(),
+                complication: String::new(),
+            }],
+            ..Default::default()
+        };
+        let journal = WorldJournal {
+            name: "Regina".to_string(),
+            pages: text_pages(&world(), "Regina", &generated, &[], &[]),
+            images: Vec::new(),
+            generated: true,
+            mentions: 0,
+        };
+        let args = journal.create_journal_args(Some("Worlds"));
+        assert_eq!(args["folder"], "Worlds");
+        assert_eq!(args["pages"].as_array().unwrap().len(), 3);
+        assert_eq!(args["pages"][2]["name"], "Patrons");
+        let overview = args["pages"][0]["text"]["content"].as_str().unwrap();
+        assert!(overview.contains("<p>The &lt;capital&gt; sprawls.</p>"));
+    }
+}
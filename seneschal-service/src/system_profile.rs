@@ -0,0 +1,126 @@
+//! Game system profiles.
+//!
+//! Each FVTT world runs one game system. The GM client reports the world's
+//! system (FVTT's `game.system.id`) when it connects, and the profile for it
+//! decides what the service assumes about the game: the name used in model
+//! prompts, guidance added to the MCP server instructions, whether the
+//! Traveller tools are offered, and how stat blocks are found in documents.
+//! Worlds whose system hasn't been reported yet are treated as Mongoose
+//! Traveller, as the service was before profiles existed.
+
+use crate::ingestion::statblocks::{StatBlockCandidate, detect_stat_blocks};
+
+/// Finds stat block candidates in a chunk of text; the second argument is a
+/// name to fall back on, usually the section title
+pub type StatBlockParser = fn(&str, Option<&str>) -> Vec<StatBlockCandidate>;
+
+/// Tool categories that only apply to Traveller games
+const TRAVELLER_CATEGORIES: &[&str] = &["traveller", "traveller_map", "traveller_worlds"];
+
+/// Tools outside those categories that assume the mgt2e data model
+const TRAVELLER_TOOLS: &[&str] = &["fvtt_build_actor"];
+
+/// What the service assumes about a game system
+#[derive(Debug)]
+pub struct SystemProfile {
+    /// FVTT system ID
+    pub id: &'static str,
+    /// Name of the game, as used in model prompts
+    pub name: &'static str,
+    /// Guidance for the model added to the MCP server instructions
+    pub instructions: Option<&'static str>,
+    /// Whether the Traveller tools apply
+    pub traveller: bool,
+    /// Stat block detection for the system's documents, if it has any
+    pub stat_blocks: Option<StatBlockParser>,
+}
+
+/// Mongoose Traveller 2nd edition
+pub static MGT2E: SystemProfile = SystemProfile {
+    id: "mgt2e",
+    name: "Mongoose Traveller 2e",
+    instructions: Some(
+        "This world plays Mongoose Traveller 2e. Use the traveller_* tools for UWPs, Traveller Map data, world generation and combat rolls.",
+    ),
+    traveller: true,
+    stat_blocks: Some(detect_stat_blocks),
+};
+
+/// Dungeons & Dragons 5th edition
+pub static DND5E: SystemProfile = SystemProfile {
+    id: "dnd5e",
+    name: "Dungeons & Dragons 5e",
+    instructions: Some(
+        "This world plays Dungeons & Dragons 5e. Traveller tools aren't available; use system_schema before creating actors or items.",
+    ),
+    traveller: false,
+    stat_blocks: None,
+};
+
+/// Pathfinder 2nd edition
+pub static PF2E: SystemProfile = SystemProfile {
+    id: "pf2e",
+    name: "Pathfinder 2e",
+    instructions: Some(
+        "This world plays Pathfinder 2e. Traveller tools aren't available; use system_schema before creating actors or items.",
+    ),
+    traveller: false,
+    stat_blocks: None,
+};
+
+/// Any other system
+pub static GENERIC: SystemProfile = SystemProfile {
+    id: "generic",
+    name: "tabletop roleplaying game",
+    instructions: Some(
+        "Traveller tools aren't available in this world's game system; use system_schema before creating actors or items.",
+    ),
+    traveller: false,
+    stat_blocks: None,
+};
+
+/// Profiles of the systems the service knows
+static PROFILES: &[&SystemProfile] = &[&MGT2E, &DND5E, &PF2E];
+
+impl SystemProfile {
+    /// The profile of an FVTT system ID. Unknown systems get the generic
+    /// profile; worlds without a reported system get Traveller's.
+    pub fn for_system(system_id: Option<&str>) -> &'static SystemProfile {
+        let Some(system_id) = system_id.map(str::trim).filter(|s| !s.is_empty()) else {
+            return &MGT2E;
+        };
+        PROFILES
+            .iter()
+            .copied()
+            .find(|p| p.id.eq_ignore_ascii_case(system_id))
+            .unwrap_or(&GENERIC)
+    }
+
+    /// Whether a tool, by name and category, is offered in this system
+    pub fn tool_available(&self, name: &str, category: &str) -> bool {
+        self.traveller
+            || !(TRAVELLER_CATEGORIES.contains(&category) || TRAVELLER_TOOLS.contains(&name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_system() {
+        assert_eq!(SystemProfile::for_system(None).id, "mgt2e");
+        assert_eq!(SystemProfile::for_system(Some(" ")).id, "mgt2e");
+        assert_eq!(SystemProfile::for_system(Some("PF2E")).id, "pf2e");
+        assert_eq!(SystemProfile::for_system(Some("wfrp4e")).id, "generic");
+    }
+
+    #[test]
+    fn test_tool_availability() {
+        assert!(MGT2E.tool_available("traveller_map_search", "traveller_map"));
+        assert!(!DND5E.tool_available("traveller_map_search", "traveller_map"));
+        assert!(!DND5E.tool_available("fvtt_build_actor", "statblock"));
+        assert!(DND5E.tool_available("document_search", "document"));
+        assert!(!GENERIC.tool_available("traveller_uwp_parse", "traveller"));
+    }
+}
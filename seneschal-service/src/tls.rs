@@ -0,0 +1,164 @@
+//! HTTPS serving with optional client certificate (mTLS) verification.
+//!
+//! When a client CA is configured, clients may present a certificate signed
+//! by it. Each request records whether its connection did, so GM endpoints
+//! can require a certificate while player-facing endpoints stay reachable
+//! without one.
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    extract::Request,
+    http::StatusCode,
+    middleware::{AddExtension, Next},
+    response::{IntoResponse, Response},
+};
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use rustls::RootCertStore;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use tokio::net::TcpStream;
+use tower::Layer;
+
+use crate::config::TlsConfig;
+
+/// Whether the request's connection presented a verified client certificate
+#[derive(Debug, Clone, Copy)]
+pub struct ClientCertificate {
+    pub verified: bool,
+}
+
+/// Serve the app over TLS until the listener fails
+pub async fn serve(app: Router, addr: SocketAddr, tls: &TlsConfig) -> io::Result<()> {
+    let config = server_config(tls)?;
+    let acceptor = ClientCertAcceptor {
+        inner: RustlsAcceptor::new(RustlsConfig::from_config(Arc::new(config))),
+    };
+
+    axum_server::bind(addr)
+        .acceptor(acceptor)
+        .serve(app.into_make_service())
+        .await
+}
+
+/// Reject requests from connections without a verified client certificate
+pub async fn require_client_certificate(request: Request, next: Next) -> Response {
+    let verified = request
+        .extensions()
+        .get::<ClientCertificate>()
+        .is_some_and(|cert| cert.verified);
+    if !verified {
+        return (StatusCode::FORBIDDEN, "Client certificate required").into_response();
+    }
+    next.run(request).await
+}
+
+fn server_config(tls: &TlsConfig) -> io::Result<rustls::ServerConfig> {
+    if tls.require_client_cert_for_gm && tls.client_ca_path.is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "require_client_cert_for_gm needs client_ca_path",
+        ));
+    }
+
+    let certs = CertificateDer::pem_file_iter(&tls.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| pem_error(&tls.cert_path, e))?;
+    let key =
+        PrivateKeyDer::from_pem_file(&tls.key_path).map_err(|e| pem_error(&tls.key_path, e))?;
+
+    // Pin the provider so a second one enabled elsewhere in the tree can't make the default ambiguous
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?;
+
+    let builder = match &tls.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in CertificateDer::pem_file_iter(ca_path).map_err(|e| pem_error(ca_path, e))? {
+                roots
+                    .add(cert.map_err(|e| pem_error(ca_path, e))?)
+                    .map_err(io::Error::other)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()
+                .map_err(io::Error::other)?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(io::Error::other)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+fn pem_error(path: &Path, error: rustls::pki_types::pem::Error) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), error),
+    )
+}
+
+/// Completes the TLS handshake, then tags the connection's requests with
+/// whether a client certificate was presented (the verifier has already
+/// rejected any it couldn't verify).
+#[derive(Clone)]
+struct ClientCertAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl<S> Accept<TcpStream, S> for ClientCertAcceptor
+where
+    S: Send + 'static,
+{
+    type Stream = <RustlsAcceptor as Accept<TcpStream, S>>::Stream;
+    type Service = AddExtension<S, ClientCertificate>;
+    type Future = Pin<Box<dyn Future<Output = io::Result<(Self::Stream, Self::Service)>> + Send>>;
+
+    fn accept(&self, stream: TcpStream, service: S) -> Self::Future {
+        let handshake = self.inner.accept(stream, service);
+        Box::pin(async move {
+            let (stream, service) = handshake.await?;
+            let verified = stream.get_ref().1.peer_certificates().is_some();
+            Ok((
+                stream,
+                Extension(ClientCertificate { verified }).layer(service),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_server_config_errors() {
+        let mut tls = TlsConfig {
+            cert_path: PathBuf::from("/nonexistent/cert.pem"),
+            key_path: PathBuf::from("/nonexistent/key.pem"),
+            client_ca_path: None,
+            require_client_cert_for_gm: true,
+        };
+        let error = server_config(&tls).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        tls.require_client_cert_for_gm = false;
+        let error = server_config(&tls).unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/cert.pem"));
+    }
+}
@@ -0,0 +1,339 @@
+//! Tool-use budgets of MCP conversations.
+//!
+//! A turn (tool calls with no gap of `TURN_IDLE_GAP` between them) pauses
+//! after `agentic_loop.tool_call_pause_threshold` calls or
+//! `time_pause_threshold_secs`: the next call isn't run, and the model is
+//! told to check in with the user before carrying on. Past
+//! `hard_timeout_secs` (0, the default, for none) no more calls run that
+//! turn; refused calls don't keep the turn going, so the next call after an
+//! idle gap starts a new one. GM clients can raise or
+//! lower these for one conversation, or for new ones, over the WebSocket,
+//! up to the `max_*` ceilings the admin sets: prep sessions need long tool
+//! chains, while questions at the table should stay quick.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AgenticLoopConfig;
+use crate::tools::compaction::TURN_IDLE_GAP;
+
+/// Limits a conversation asks for; unset limits use the configured ones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolBudgetOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_pause_threshold: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_pause_threshold_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard_timeout_secs: Option<u64>,
+}
+
+/// The limits a conversation's turns run under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ToolBudget {
+    pub tool_call_pause_threshold: u32,
+    pub time_pause_threshold_secs: u64,
+    pub hard_timeout_secs: u64,
+}
+
+impl ToolBudget {
+    /// The configured limits with a conversation's overrides applied, each
+    /// capped at its ceiling. A conversation can't turn the hard timeout off:
+    /// an override of 0 leaves the configured one.
+    pub fn resolve(config: &AgenticLoopConfig, overrides: ToolBudgetOverride) -> Self {
+        Self {
+            tool_call_pause_threshold: overrides
+                .tool_call_pause_threshold
+                .unwrap_or(config.tool_call_pause_threshold)
+                .clamp(1, config.max_tool_call_pause_threshold.max(1)),
+            time_pause_threshold_secs: overrides
+                .time_pause_threshold_secs
+                .unwrap_or(config.time_pause_threshold_secs)
+                .min(config.max_time_pause_threshold_secs),
+            hard_timeout_secs: overrides
+                .hard_timeout_secs
+                .filter(|&secs| secs > 0)
+                .unwrap_or(config.hard_timeout_secs)
+                .min(config.max_hard_timeout_secs),
+        }
+    }
+}
+
+/// What a turn's next tool call may do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BudgetCheck {
+    Run,
+    /// Check in with the user first; the counters start over afterwards
+    Pause {
+        calls: u32,
+        elapsed: Duration,
+    },
+    /// The turn is out of time
+    Stop {
+        elapsed: Duration,
+    },
+}
+
+/// Tool calls made in a conversation's current turn
+#[derive(Debug, Clone, Copy)]
+pub struct TurnUsage {
+    turn_started: Instant,
+    /// Start of the calls counted toward the next pause
+    cycle_started: Instant,
+    calls: u32,
+    /// Calls run since the turn started
+    turn_calls: u32,
+    last_call: Instant,
+}
+
+impl Default for TurnUsage {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            turn_started: now,
+            cycle_started: now,
+            calls: 0,
+            turn_calls: 0,
+            last_call: now,
+        }
+    }
+}
+
+impl TurnUsage {
+    /// Account for a tool call about to be made, starting a new turn if no
+    /// call has run for `TURN_IDLE_GAP`
+    pub fn check(&mut self, budget: &ToolBudget) -> BudgetCheck {
+        self.check_at(budget, Instant::now())
+    }
+
+    fn check_at(&mut self, budget: &ToolBudget, now: Instant) -> BudgetCheck {
+        if now.duration_since(self.last_call) >= TURN_IDLE_GAP {
+            *self = Self {
+                turn_started: now,
+                cycle_started: now,
+                calls: 0,
+                turn_calls: 0,
+                last_call: now,
+            };
+        }
+
+        let elapsed = now.duration_since(self.turn_started);
+        if budget.hard_timeout_secs > 0 && elapsed >= Duration::from_secs(budget.hard_timeout_secs)
+        {
+            return BudgetCheck::Stop { elapsed };
+        }
+        let cycle = now.duration_since(self.cycle_started);
+        if self.calls >= budget.tool_call_pause_threshold
+            || cycle >= Duration::from_secs(budget.time_pause_threshold_secs)
+        {
+            let calls = self.calls;
+            self.calls = 0;
+            self.cycle_started = now;
+            return BudgetCheck::Pause { calls, elapsed };
+        }
+        self.calls += 1;
+        self.turn_calls += 1;
+        self.last_call = now;
+        BudgetCheck::Run
+    }
+
+    /// Calls run since the turn started
+    pub fn turn_calls(&self) -> u32 {
+        self.turn_calls
+    }
+}
+
+/// A conversation's budget
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionToolBudget {
+    pub session_id: String,
+    pub budget: ToolBudget,
+}
+
+/// Budget overrides of open conversations, and those new ones start with
+#[derive(Debug, Default)]
+pub struct ToolBudgets {
+    default: Mutex<ToolBudgetOverride>,
+    overrides: DashMap<String, ToolBudgetOverride>,
+}
+
+impl ToolBudgets {
+    /// Overrides new conversations start with
+    pub fn default_override(&self) -> ToolBudgetOverride {
+        *self.default.lock().unwrap()
+    }
+
+    /// Overrides of a conversation
+    pub fn overrides(&self, session_id: Option<&str>) -> ToolBudgetOverride {
+        session_id
+            .and_then(|id| self.overrides.get(id).map(|o| *o))
+            .unwrap_or_else(|| self.default_override())
+    }
+
+    /// The limits a conversation runs under
+    pub fn budget(&self, session_id: Option<&str>, config: &AgenticLoopConfig) -> ToolBudget {
+        ToolBudget::resolve(config, self.overrides(session_id))
+    }
+
+    /// Set one conversation's overrides, or without a session those of new
+    /// conversations and open ones not set individually
+    pub fn set(&self, session_id: Option<&str>, overrides: ToolBudgetOverride) {
+        match session_id {
+            Some(id) => {
+                self.overrides.insert(id.to_string(), overrides);
+            }
+            None => *self.default.lock().unwrap() = overrides,
+        }
+    }
+
+    /// Forget an ended conversation
+    pub fn end(&self, session_id: &str) {
+        self.overrides.remove(session_id);
+    }
+
+    /// Budgets of conversations with their own overrides
+    pub fn sessions(&self, config: &AgenticLoopConfig) -> Vec<SessionToolBudget> {
+        let mut sessions: Vec<SessionToolBudget> = self
+            .overrides
+            .iter()
+            .map(|entry| SessionToolBudget {
+                session_id: entry.key().clone(),
+                budget: ToolBudget::resolve(config, *entry.value()),
+            })
+            .collect();
+        sessions.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(calls: u32, pause_secs: u64, hard_secs: u64) -> ToolBudget {
+        ToolBudget {
+            tool_call_pause_threshold: calls,
+            time_pause_threshold_secs: pause_secs,
+            hard_timeout_secs: hard_secs,
+        }
+    }
+
+    #[test]
+    fn test_resolve_caps_overrides() {
+        let mut config: AgenticLoopConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        config.tool_call_pause_threshold = 10;
+        config.max_tool_call_pause_threshold = 40;
+        config.max_hard_timeout_secs = 900;
+
+        let resolved = ToolBudget::resolve(&config, ToolBudgetOverride::default());
+        assert_eq!(resolved.tool_call_pause_threshold, 10);
+        assert_eq!(resolved.hard_timeout_secs, config.hard_timeout_secs);
+
+        let resolved = ToolBudget::resolve(
+            &config,
+            ToolBudgetOverride {
+                tool_call_pause_threshold: Some(100),
+                time_pause_threshold_secs: Some(120),
+                hard_timeout_secs: Some(3600),
+            },
+        );
+        assert_eq!(resolved, budget(40, 120, 900));
+
+        // The hard timeout is off unless configured, and stays on once it is
+        config.hard_timeout_secs = 300;
+        let resolved = ToolBudget::resolve(
+            &config,
+            ToolBudgetOverride {
+                hard_timeout_secs: Some(0),
+                ..Default::default()
+            },
+        );
+        assert_eq!(resolved.hard_timeout_secs, 300);
+    }
+
+    #[test]
+    fn test_no_hard_timeout_by_default() {
+        let config: AgenticLoopConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        let budget = ToolBudget::resolve(&config, ToolBudgetOverride::default());
+        let start = Instant::now();
+        let mut usage = TurnUsage::default();
+        for secs in (0..3600).step_by(30) {
+            assert_eq!(
+                usage.check_at(&budget, start + Duration::from_secs(secs)),
+                BudgetCheck::Run
+            );
+        }
+    }
+
+    #[test]
+    fn test_pause_after_calls_then_continue() {
+        let budget = budget(2, u64::MAX, 300);
+        let start = Instant::now();
+        let mut usage = TurnUsage::default();
+        assert_eq!(usage.check_at(&budget, start), BudgetCheck::Run);
+        assert_eq!(usage.check_at(&budget, start), BudgetCheck::Run);
+        assert!(matches!(
+            usage.check_at(&budget, start),
+            BudgetCheck::Pause { calls: 2, .. }
+        ));
+        assert_eq!(usage.check_at(&budget, start), BudgetCheck::Run);
+        assert_eq!(usage.turn_calls(), 3);
+    }
+
+    #[test]
+    fn test_hard_timeout_and_new_turn() {
+        let budget = budget(u32::MAX, u64::MAX, 100);
+        let start = Instant::now();
+        let mut usage = TurnUsage::default();
+        assert_eq!(usage.check_at(&budget, start), BudgetCheck::Run);
+        for secs in [30, 60, 90] {
+            usage.check_at(&budget, start + Duration::from_secs(secs));
+        }
+        assert!(matches!(
+            usage.check_at(&budget, start + Duration::from_secs(110)),
+            BudgetCheck::Stop { .. }
+        ));
+
+        // Refused calls don't keep the turn going: an idle gap since the
+        // last call that ran starts a new one
+        let retry = start + Duration::from_secs(90) + TURN_IDLE_GAP - Duration::from_secs(1);
+        assert!(matches!(
+            usage.check_at(&budget, retry),
+            BudgetCheck::Stop { .. }
+        ));
+        let later = start + Duration::from_secs(90) + TURN_IDLE_GAP;
+        assert_eq!(usage.check_at(&budget, later), BudgetCheck::Run);
+        assert_eq!(usage.turn_calls(), 1);
+    }
+
+    #[test]
+    fn test_session_overrides() {
+        let config: AgenticLoopConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        let budgets = ToolBudgets::default();
+        let prep = ToolBudgetOverride {
+            tool_call_pause_threshold: Some(40),
+            ..Default::default()
+        };
+        budgets.set(Some("prep"), prep);
+        assert_eq!(budgets.overrides(Some("prep")), prep);
+        assert_eq!(
+            budgets.overrides(Some("table")),
+            ToolBudgetOverride::default()
+        );
+        assert_eq!(
+            budgets
+                .budget(Some("prep"), &config)
+                .tool_call_pause_threshold,
+            40
+        );
+        let sessions = budgets.sessions(&config);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, "prep");
+
+        budgets.end("prep");
+        assert!(budgets.sessions(&config).is_empty());
+    }
+}
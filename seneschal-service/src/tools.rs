@@ -4,15 +4,27 @@
 //! - Access level definitions
 //! - Tool classification (internal vs external)
 //! - Unified tool registry for MCP
+//! - External tools registered by FVTT clients
+//! - Validation of external tool results against their schemas
+//! - Compaction of tool results into terse text
 //! - Submodules for tool definitions and game-specific tools
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub mod client_tools;
+pub mod compaction;
+pub mod fvtt_actor;
+pub mod names;
 pub mod registry;
+pub mod result_validation;
 pub mod tool_defs;
 pub mod traveller;
+pub mod traveller_chargen;
+pub mod traveller_combat;
+pub mod traveller_encounters;
 pub mod traveller_map;
+pub mod traveller_ship;
 pub mod traveller_worlds;
 
 pub use registry::REGISTRY;
@@ -68,6 +80,12 @@ pub struct SearchFilters {
     pub tags: Vec<String>,
     #[serde(default)]
     pub tags_match: TagMatch,
+    /// FVTT world to search; documents without a world are shared by all
+    #[serde(default)]
+    pub world_id: Option<String>,
+    /// Leave out images already delivered to the world (image searches only)
+    #[serde(default)]
+    pub exclude_used: bool,
 }
 
 /// Classify whether a tool is internal (backend-only) or external (requires client)
@@ -0,0 +1,285 @@
+//! External tools registered by FVTT clients.
+//!
+//! The built-in external tools are defined in `tool_defs`, so a new one
+//! needs a service release. A GM client can instead register its own when it
+//! connects: system-specific modules describe tools that the FVTT module
+//! runs for them. Registrations are kept per world, each one replacing the
+//! world's previous set, and are offered to MCP clients of that world next
+//! to the registry's tools. Calls to them are routed to a GM client like any
+//! other external tool.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+use super::registry::{McpToolDefinition, ToolName};
+
+/// Longest tool name accepted
+const MAX_NAME_LEN: usize = 64;
+
+/// Most tools one world can register
+pub const MAX_CLIENT_TOOLS: usize = 64;
+
+/// An external tool described by an FVTT client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientToolDefinition {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the tool's parameters
+    #[serde(default = "empty_parameters")]
+    pub parameters: serde_json::Value,
+    /// Lowest FVTT role that may call the tool
+    #[serde(default = "default_required_role")]
+    pub required_role: u8,
+    /// Whether the tool only reads world data; tools that may change it are
+    /// routed only to GM clients allowed to write
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+fn empty_parameters() -> serde_json::Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+fn default_required_role() -> u8 {
+    4
+}
+
+/// A definition that wasn't registered, and why
+#[derive(Debug, Clone, Serialize)]
+pub struct RejectedClientTool {
+    pub name: String,
+    pub reason: String,
+}
+
+impl ClientToolDefinition {
+    /// Why the definition can't be registered, if it can't
+    fn problem(&self) -> Option<String> {
+        if self.name.is_empty() || self.name.len() > MAX_NAME_LEN {
+            return Some(format!("name must be 1-{} characters", MAX_NAME_LEN));
+        }
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Some("name may only use lowercase letters, digits and '_'".to_string());
+        }
+        if ToolName::from_str(&self.name).is_ok() {
+            return Some("name is taken by a built-in tool".to_string());
+        }
+        if self.description.trim().is_empty() {
+            return Some("description is empty".to_string());
+        }
+        if self.parameters.get("type").and_then(|t| t.as_str()) != Some("object") {
+            return Some("parameters must be a JSON Schema of type \"object\"".to_string());
+        }
+        if !(1..=4).contains(&self.required_role) {
+            return Some("required_role must be 1-4".to_string());
+        }
+        None
+    }
+
+    /// Whether a user of the role may call the tool
+    pub fn callable_by(&self, role: u8) -> bool {
+        role >= self.required_role
+    }
+
+    fn mcp_definition(&self) -> McpToolDefinition {
+        McpToolDefinition {
+            name: self.name.clone(),
+            description: format!(
+                "{} Requires GM WebSocket connection.",
+                self.description.trim()
+            ),
+            input_schema: self.parameters.clone(),
+            defer_loading: Some(true),
+            category: Some("fvtt_client".to_string()),
+        }
+    }
+}
+
+/// Tools registered by FVTT clients, by world
+#[derive(Debug, Default)]
+pub struct ClientToolRegistry {
+    worlds: DashMap<String, HashMap<String, ClientToolDefinition>>,
+}
+
+impl ClientToolRegistry {
+    /// Replace a world's tools with those given. Returns the names
+    /// registered and the definitions refused.
+    pub fn register(
+        &self,
+        world_id: &str,
+        tools: Vec<ClientToolDefinition>,
+    ) -> (Vec<String>, Vec<RejectedClientTool>) {
+        let mut accepted = HashMap::new();
+        let mut rejected = Vec::new();
+        for tool in tools {
+            let problem = tool.problem().or_else(|| {
+                if accepted.contains_key(&tool.name) {
+                    Some("registered twice".to_string())
+                } else if accepted.len() >= MAX_CLIENT_TOOLS {
+                    Some(format!("at most {} tools per world", MAX_CLIENT_TOOLS))
+                } else {
+                    None
+                }
+            });
+            match problem {
+                Some(reason) => rejected.push(RejectedClientTool {
+                    name: tool.name,
+                    reason,
+                }),
+                None => {
+                    accepted.insert(tool.name.clone(), tool);
+                }
+            }
+        }
+
+        let mut names: Vec<String> = accepted.keys().cloned().collect();
+        names.sort();
+        if accepted.is_empty() {
+            self.worlds.remove(world_id);
+        } else {
+            self.worlds.insert(world_id.to_string(), accepted);
+        }
+        (names, rejected)
+    }
+
+    /// A world's tool by name
+    pub fn get(&self, world_id: Option<&str>, name: &str) -> Option<ClientToolDefinition> {
+        self.worlds.get(world_id?)?.get(name).cloned()
+    }
+
+    /// A world's tools that a role may call, as MCP definitions
+    pub fn mcp_definitions(&self, world_id: Option<&str>, role: u8) -> Vec<McpToolDefinition> {
+        let Some(tools) = world_id.and_then(|w| self.worlds.get(w)) else {
+            return Vec::new();
+        };
+        let mut definitions: Vec<McpToolDefinition> = tools
+            .values()
+            .filter(|t| t.callable_by(role))
+            .map(ClientToolDefinition::mcp_definition)
+            .collect();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        definitions
+    }
+
+    /// Names of a world's tools that a role may call whose name or
+    /// description share a word with the query, best matches first
+    pub fn search(
+        &self,
+        world_id: Option<&str>,
+        query: &str,
+        role: u8,
+        limit: usize,
+    ) -> Vec<String> {
+        let terms: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| t.len() > 2)
+            .map(str::to_lowercase)
+            .collect();
+        let Some(tools) = world_id.and_then(|w| self.worlds.get(w)) else {
+            return Vec::new();
+        };
+        let mut scored: Vec<(usize, &String)> = tools
+            .values()
+            .filter(|tool| tool.callable_by(role))
+            .filter_map(|tool| {
+                let text =
+                    format!("{} {}", tool.name.replace('_', " "), tool.description).to_lowercase();
+                let score = terms.iter().filter(|t| text.contains(t.as_str())).count();
+                (score > 0).then_some((score, &tool.name))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, description: &str) -> ClientToolDefinition {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "description": description,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_register_rejects_invalid_tools() {
+        let registry = ClientToolRegistry::default();
+        let (registered, rejected) = registry.register(
+            "world",
+            vec![
+                tool("pf2e_spell_lookup", "Look up a spell in the compendium"),
+                tool("fvtt_read", "Shadows a built-in"),
+                tool("Bad Name", "Spaces and capitals"),
+                tool("pf2e_spell_lookup", "Again"),
+                tool("no_description", " "),
+            ],
+        );
+        assert_eq!(registered, vec!["pf2e_spell_lookup".to_string()]);
+        let reasons: Vec<&str> = rejected.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "fvtt_read",
+                "Bad Name",
+                "pf2e_spell_lookup",
+                "no_description"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registration_is_per_world_and_replaces() {
+        let registry = ClientToolRegistry::default();
+        registry.register("a", vec![tool("spell_lookup", "Find a spell")]);
+        registry.register("b", vec![tool("feat_lookup", "Find a feat")]);
+        assert!(registry.get(Some("a"), "spell_lookup").is_some());
+        assert!(registry.get(Some("a"), "feat_lookup").is_none());
+        assert!(registry.get(None, "spell_lookup").is_none());
+
+        registry.register("a", vec![tool("condition_apply", "Apply a condition")]);
+        assert!(registry.get(Some("a"), "spell_lookup").is_none());
+        assert_eq!(registry.mcp_definitions(Some("a"), 4).len(), 1);
+    }
+
+    #[test]
+    fn test_required_role_and_search() {
+        let registry = ClientToolRegistry::default();
+        let mut secret = tool("trap_reveal", "Reveal a hidden trap");
+        secret.required_role = 4;
+        let mut open = tool("spell_lookup", "Find a spell by name");
+        open.required_role = 1;
+        registry.register("w", vec![secret, open]);
+
+        let names: Vec<String> = registry
+            .mcp_definitions(Some("w"), 1)
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(names, vec!["spell_lookup".to_string()]);
+        assert_eq!(
+            registry.search(Some("w"), "find a spell", 4, 5),
+            vec!["spell_lookup".to_string()]
+        );
+        assert!(registry.search(Some("w"), "reveal trap", 1, 5).is_empty());
+        assert!(
+            !registry
+                .get(Some("w"), "trap_reveal")
+                .unwrap()
+                .callable_by(3)
+        );
+    }
+}
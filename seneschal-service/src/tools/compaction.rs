@@ -0,0 +1,318 @@
+//! Tool result compaction.
+//!
+//! Tool results are rendered into terse text before they reach the model:
+//! JSON payloads go through a per-tool formatter (or a generic indented
+//! renderer), oversized text is truncated with a hint naming the call that
+//! retrieves more (`artifact_get`, when the full text could be stored as an
+//! artifact), and the tokens spent on results are tracked per turn so later
+//! results in a long tool chain get a smaller share.
+
+mod formatters;
+
+use std::time::{Duration, Instant};
+
+/// A turn ends once a session has made no tool calls for this long
+pub const TURN_IDLE_GAP: Duration = Duration::from_secs(60);
+
+/// Results are never truncated below this many tokens, even once the turn's
+/// budget is spent, so the model still sees enough to decide what to call next
+const MIN_RESULT_TOKENS: usize = 256;
+
+/// Rough token estimate for text (about four characters per token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Tokens spent on tool results during the current turn of a session
+#[derive(Debug, Clone)]
+pub struct TurnBudget {
+    used: usize,
+    last_call: Instant,
+}
+
+impl Default for TurnBudget {
+    fn default() -> Self {
+        Self {
+            used: 0,
+            last_call: Instant::now(),
+        }
+    }
+}
+
+impl TurnBudget {
+    /// Token allowance for the next result, starting a new turn if the
+    /// session has been idle
+    pub fn allowance(&mut self, max_result_tokens: usize, turn_tokens: usize) -> usize {
+        if self.last_call.elapsed() >= TURN_IDLE_GAP {
+            self.used = 0;
+        }
+        self.last_call = Instant::now();
+
+        let remaining = turn_tokens.saturating_sub(self.used);
+        max_result_tokens.min(remaining).max(MIN_RESULT_TOKENS)
+    }
+
+    /// Record tokens spent on a result
+    pub fn spend(&mut self, tokens: usize) {
+        self.used += tokens;
+    }
+
+    /// Tokens spent so far this turn
+    pub fn used(&self) -> usize {
+        self.used
+    }
+}
+
+/// Compact the text content of an MCP tool result in place.
+///
+/// Text that doesn't fit is handed to `store_artifact`, which returns an
+/// artifact ID if it kept the full text for later slicing. Returns the
+/// estimated tokens of the compacted text. Non-text content (images) is
+/// left untouched.
+pub fn compact_tool_result(
+    tool: &str,
+    result: &mut serde_json::Value,
+    max_tokens: usize,
+    mut store_artifact: impl FnMut(&str) -> Option<String>,
+) -> usize {
+    let Some(content) = result.get_mut("content").and_then(|c| c.as_array_mut()) else {
+        return 0;
+    };
+
+    let mut tokens = 0;
+    for item in content {
+        if item.get("type").and_then(|t| t.as_str()) != Some("text") {
+            continue;
+        }
+        let Some(text) = item.get("text").and_then(|t| t.as_str()) else {
+            continue;
+        };
+
+        let rendered = render_text(tool, text);
+        let allowance = max_tokens.saturating_sub(tokens);
+        let compacted = if estimate_tokens(&rendered) <= allowance {
+            rendered
+        } else {
+            let hint = match store_artifact(&rendered) {
+                Some(id) => format!(
+                    "Full result ({} lines) saved as artifact {}; call artifact_get with its artifact_id and a line range or pattern to read more",
+                    rendered.lines().count(),
+                    id
+                ),
+                None => more_hint(tool),
+            };
+            truncate(&rendered, allowance, &hint)
+        };
+        tokens += estimate_tokens(&compacted);
+        item["text"] = serde_json::Value::String(compacted);
+    }
+    tokens
+}
+
+/// Render a result's text, formatting it if it is a JSON payload
+fn render_text(tool: &str, text: &str) -> String {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        return text.to_string();
+    }
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) => formatters::format(tool, &value).unwrap_or_else(|| render_value(&value)),
+        Err(_) => text.to_string(),
+    }
+}
+
+/// Render any JSON value as indented `key: value` lines, dropping nulls and
+/// empty values
+fn render_value(value: &serde_json::Value) -> String {
+    let mut out = String::new();
+    render_into(&mut out, value, 0);
+    out.trim_end().to_string()
+}
+
+fn render_into(out: &mut String, value: &serde_json::Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                if is_empty(value) {
+                    continue;
+                }
+                match scalar(value) {
+                    Some(s) if !s.contains('\n') => out.push_str(&format!("{pad}{key}: {s}\n")),
+                    _ => {
+                        out.push_str(&format!("{pad}{key}:\n"));
+                        render_into(out, value, indent + 1);
+                    }
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            // Short arrays of scalars fit on one line
+            if items
+                .iter()
+                .all(|v| scalar(v).is_some_and(|s| !s.contains('\n')))
+            {
+                let joined: Vec<String> = items.iter().filter_map(scalar).collect();
+                out.push_str(&format!("{pad}{}\n", joined.join(", ")));
+                return;
+            }
+            for item in items {
+                if is_empty(item) {
+                    continue;
+                }
+                match scalar(item) {
+                    Some(s) => out.push_str(&format!("{pad}- {}\n", s.replace('\n', " "))),
+                    None => {
+                        out.push_str(&format!("{pad}-\n"));
+                        render_into(out, item, indent + 1);
+                    }
+                }
+            }
+        }
+        _ => {
+            if let Some(s) = scalar(value) {
+                for line in s.lines() {
+                    out.push_str(&format!("{pad}{line}\n"));
+                }
+            }
+        }
+    }
+}
+
+/// Render a scalar value without JSON quoting
+fn scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn is_empty(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::String(s) => s.is_empty(),
+        serde_json::Value::Array(a) => a.is_empty(),
+        serde_json::Value::Object(o) => o.is_empty(),
+        _ => false,
+    }
+}
+
+/// Cut text to roughly `max_tokens`, ending on a line boundary where possible,
+/// and append a hint on how to retrieve the rest
+fn truncate(text: &str, max_tokens: usize, hint: &str) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+
+    let max_chars = max_tokens * 4;
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let head = &text[..cut];
+    let head = match head.rfind('\n') {
+        Some(i) if i > cut / 2 => &head[..i],
+        _ => head,
+    };
+
+    let omitted = estimate_tokens(&text[head.len()..]);
+    format!(
+        "{}\n\n[Truncated: ~{} more tokens omitted. {}]",
+        head.trim_end(),
+        omitted,
+        hint
+    )
+}
+
+/// Which call retrieves more of a truncated result
+fn more_hint(tool: &str) -> String {
+    match tool {
+        "document_search" | "document_search_text" => {
+            "Call document_get with a document_id and page for full page text, or narrow the query"
+                .to_string()
+        }
+        "document_get" => {
+            "Call document_get with a specific page for that page's full text".to_string()
+        }
+        "document_list" | "document_find" => {
+            "Call document_find with a title or tags to narrow the list".to_string()
+        }
+        "image_list" | "image_search" | "image_search_similar" => {
+            "Call image_get with an image id for details, or lower the limit".to_string()
+        }
+        "statblock_search" => "Call statblock_get with a stat block id for details".to_string(),
+        "artifact_get" => "Call artifact_get with a narrower line range or a pattern".to_string(),
+        "traveller_map_sector_data" => {
+            "Call traveller_map_sector_data with a subsector for a smaller slice".to_string()
+        }
+        _ => format!(
+            "Call {} again with narrower arguments (a lower limit or more specific query) for more",
+            tool
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_tool_result() {
+        let payload = serde_json::json!({
+            "documents": [
+                {"id": "core", "title": "Core Rulebook", "tags": ["rules"], "chunk_count": 900, "image_count": 12}
+            ]
+        });
+        let mut result = serde_json::json!({
+            "content": [
+                {"type": "text", "text": serde_json::to_string_pretty(&payload).unwrap()},
+                {"type": "image", "data": "abc", "mimeType": "image/png"}
+            ]
+        });
+        compact_tool_result("document_list", &mut result, 1000, |_| None);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("core | Core Rulebook | rules | 900 | 12"));
+        assert!(!text.contains('{'));
+        assert_eq!(result["content"][1]["data"], "abc");
+
+        // Unknown tools fall back to the generic renderer
+        let rendered = render_text("other", r#"{"name": "Regina", "zone": null, "tl": 12}"#);
+        assert_eq!(rendered, "name: Regina\ntl: 12");
+
+        // Plain text is only truncated
+        let long = "line\n".repeat(2000);
+        let mut result = serde_json::json!({"content": [{"type": "text", "text": long}]});
+        let tokens = compact_tool_result("document_search_text", &mut result, 300, |_| None);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(tokens <= 350);
+        assert!(text.ends_with("or narrow the query]"));
+
+        // Stored results point at their artifact instead
+        let mut stored = String::new();
+        let mut result = serde_json::json!({"content": [{"type": "text", "text": long}]});
+        compact_tool_result("traveller_map_sector_data", &mut result, 300, |text| {
+            stored = text.to_string();
+            Some("art_1".to_string())
+        });
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert_eq!(stored.lines().count(), 2000);
+        assert!(text.contains("(2000 lines) saved as artifact art_1"));
+    }
+
+    #[test]
+    fn test_turn_budget() {
+        let mut budget = TurnBudget::default();
+        assert_eq!(budget.allowance(4000, 10000), 4000);
+        budget.spend(8000);
+        assert_eq!(budget.allowance(4000, 10000), 2000);
+        budget.spend(2000);
+        assert_eq!(budget.allowance(4000, 10000), MIN_RESULT_TOKENS);
+
+        budget.last_call = Instant::now() - TURN_IDLE_GAP;
+        assert_eq!(budget.allowance(4000, 10000), 4000);
+        assert_eq!(budget.used(), 0);
+    }
+}
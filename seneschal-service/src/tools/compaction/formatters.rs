@@ -0,0 +1,85 @@
+//! Per-tool formatters for tool result payloads.
+//!
+//! List-style results become one line per row with a header naming the
+//! columns; long text fields follow their row. A formatter returns `None`
+//! when the payload isn't the shape it expects, and the generic renderer is
+//! used instead.
+
+use serde_json::Value;
+
+use super::scalar;
+
+/// Format a tool's JSON payload, if the tool has a dedicated formatter
+pub(super) fn format(tool: &str, value: &Value) -> Option<String> {
+    match tool {
+        "document_list" => table(
+            value.get("documents")?,
+            &["id", "title", "tags", "chunk_count", "image_count"],
+            Some("summary"),
+        ),
+        "document_search_text" => table(
+            value,
+            &["document_id", "page_number", "section_title"],
+            Some("content"),
+        ),
+        "image_list" | "image_search" | "image_search_similar" => table(
+            value.get("images")?,
+            &["id", "page_number", "width", "height"],
+            Some("description"),
+        ),
+        "statblock_search" => table(
+            value.get("stat_blocks")?,
+            &[
+                "id",
+                "name",
+                "kind",
+                "document_id",
+                "page_number",
+                "validated",
+            ],
+            None,
+        ),
+        _ => None,
+    }
+}
+
+/// Render an array of objects as `a | b | c` rows, with an optional long
+/// text field on the lines after each row
+fn table(rows: &Value, columns: &[&str], body: Option<&str>) -> Option<String> {
+    let rows = rows.as_array()?;
+    if rows.iter().any(|row| !row.is_object()) {
+        return None;
+    }
+
+    let mut out = columns.join(" | ");
+    if let Some(body) = body {
+        out.push_str(&format!(" (then {})", body));
+    }
+    out.push('\n');
+
+    for row in rows {
+        let cells: Vec<String> = columns.iter().map(|c| cell(row.get(*c))).collect();
+        out.push_str(&cells.join(" | "));
+        out.push('\n');
+        if let Some(text) = body.and_then(|b| row.get(b)).and_then(scalar)
+            && !text.is_empty()
+        {
+            out.push_str(text.trim());
+            out.push_str("\n\n");
+        }
+    }
+
+    Some(out.trim_end().to_string())
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(scalar)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(value) => scalar(value).unwrap_or_else(|| "-".to_string()),
+        None => "-".to_string(),
+    }
+}
@@ -0,0 +1,360 @@
+//! Mapping of structured stat blocks to Foundry VTT mgt2e actor payloads.
+//!
+//! Stat blocks come from document extraction (`ingestion::statblocks`) or are
+//! written by the LLM from generator output. Rather than having the LLM guess
+//! the mgt2e data model, `build_actor_payload` normalizes the stat block,
+//! checks it against the bundled schema description below, and returns
+//! arguments ready to pass to the external `create_actor` tool.
+
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+/// Characteristic keys in mgt2e actor data with their stat block abbreviations
+const CHARACTERISTICS: [(&str, &str); 7] = [
+    ("strength", "STR"),
+    ("dexterity", "DEX"),
+    ("endurance", "END"),
+    ("intellect", "INT"),
+    ("education", "EDU"),
+    ("social", "SOC"),
+    ("psionic", "PSI"),
+];
+
+/// Schema description for a supported actor type
+struct ActorTypeSchema {
+    name: &'static str,
+    /// Characteristics that must be present
+    required_characteristics: &'static [&'static str],
+    /// Whether the actor tracks hits directly rather than deriving them from characteristics
+    uses_hits: bool,
+}
+
+/// Actor types the builder can produce, mirroring `MGT2E_ENHANCEMENTS` in the FVTT module
+const ACTOR_TYPES: [ActorTypeSchema; 3] = [
+    ActorTypeSchema {
+        name: "traveller",
+        required_characteristics: &[
+            "strength",
+            "dexterity",
+            "endurance",
+            "intellect",
+            "education",
+            "social",
+        ],
+        uses_hits: false,
+    },
+    ActorTypeSchema {
+        name: "npc",
+        required_characteristics: &[
+            "strength",
+            "dexterity",
+            "endurance",
+            "intellect",
+            "education",
+            "social",
+        ],
+        uses_hits: false,
+    },
+    ActorTypeSchema {
+        name: "creature",
+        required_characteristics: &[],
+        uses_hits: true,
+    },
+];
+
+/// Create-ready actor payload with any problems found while mapping
+#[derive(Debug, Serialize)]
+pub struct ActorPayload {
+    /// Arguments for the external `create_actor` tool
+    pub create_actor: Value,
+    /// Fields that were dropped or defaulted
+    pub warnings: Vec<String>,
+}
+
+/// Build `create_actor` arguments from a structured stat block.
+///
+/// The stat block may use abbreviations (`STR`) or full names (`strength`) for
+/// characteristics, and lists (`[{"name","level"}]` or `["Pilot 2"]`) or maps
+/// (`{"Pilot": 2}`) for skills. `actor_type` overrides the stat block's `kind`.
+/// Returns an error listing missing required fields.
+pub fn build_actor_payload(
+    stat_block: &Value,
+    actor_type: Option<&str>,
+) -> Result<ActorPayload, String> {
+    let mut warnings = Vec::new();
+    let mut missing = Vec::new();
+
+    let name = stat_block
+        .get("name")
+        .and_then(|n| n.as_str())
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    if name.is_none() {
+        missing.push("name".to_string());
+    }
+
+    let type_name = actor_type
+        .or_else(|| stat_block.get("kind").and_then(|k| k.as_str()))
+        .unwrap_or("npc");
+    let schema = ACTOR_TYPES
+        .iter()
+        .find(|t| t.name == type_name)
+        .ok_or_else(|| {
+            format!(
+                "Unsupported actor type '{}'; expected one of: {}",
+                type_name,
+                ACTOR_TYPES
+                    .iter()
+                    .map(|t| t.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+
+    let mut data = Map::new();
+
+    let characteristics = parse_characteristics(stat_block.get("characteristics"));
+    for required in schema.required_characteristics {
+        if !characteristics.contains_key(*required) {
+            missing.push(format!("characteristics.{}", required));
+        }
+    }
+    if !characteristics.is_empty() {
+        data.insert(
+            "characteristics".to_string(),
+            Value::Object(
+                characteristics
+                    .into_iter()
+                    .map(|(key, value)| (key, json!({ "value": value })))
+                    .collect(),
+            ),
+        );
+    }
+
+    match stat_block.get("hits").and_then(|h| h.as_u64()) {
+        Some(hits) => {
+            data.insert("hits".to_string(), json!({ "value": hits, "max": hits }));
+        }
+        None if schema.uses_hits => missing.push("hits".to_string()),
+        None => {}
+    }
+    if let Some(speed) = stat_block.get("speed").and_then(|s| s.as_u64()) {
+        data.insert("speed".to_string(), Value::from(speed));
+    }
+    if let Some(behaviour) = stat_block.get("behaviour").and_then(|b| b.as_str()) {
+        data.insert("behaviour".to_string(), Value::from(behaviour));
+    }
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "Stat block is missing required fields for a {} actor: {}",
+            schema.name,
+            missing.join(", ")
+        ));
+    }
+
+    let mut items = parse_skills(stat_block.get("skills"), &mut warnings);
+    for weapon in string_list(stat_block.get("weapons")) {
+        let (name, damage) = split_parenthetical(&weapon);
+        let mut system = Map::new();
+        if let Some(damage) = damage {
+            system.insert("damage".to_string(), Value::from(damage));
+        }
+        items.push(json!({ "name": name, "type": "weapon", "system": system }));
+    }
+    for armour in string_list(stat_block.get("armour")) {
+        let (name, protection) = split_parenthetical(&armour);
+        let mut system = Map::new();
+        match protection.map(|p| p.trim_start_matches('+').parse::<i64>()) {
+            Some(Ok(protection)) => {
+                system.insert("protection".to_string(), Value::from(protection));
+            }
+            Some(Err(_)) => {
+                warnings.push(format!("Could not read protection for armour '{}'", armour))
+            }
+            None => {}
+        }
+        items.push(json!({ "name": name, "type": "armour", "system": system }));
+    }
+    for equipment in string_list(stat_block.get("equipment")) {
+        items.push(json!({ "name": equipment, "type": "equipment", "system": {} }));
+    }
+    // Creature attacks become natural weapons
+    for attack in string_list(stat_block.get("attacks")) {
+        let (name, damage) = split_parenthetical(&attack);
+        let mut system = Map::new();
+        if let Some(damage) = damage {
+            system.insert("damage".to_string(), Value::from(damage));
+        }
+        items.push(json!({ "name": name, "type": "weapon", "system": system }));
+    }
+    if !items.is_empty() {
+        data.insert("items".to_string(), Value::Array(items));
+    }
+
+    let known = [
+        "name",
+        "kind",
+        "characteristics",
+        "skills",
+        "weapons",
+        "armour",
+        "equipment",
+        "attacks",
+        "hits",
+        "speed",
+        "behaviour",
+    ];
+    if let Some(object) = stat_block.as_object() {
+        for key in object.keys().filter(|k| !known.contains(&k.as_str())) {
+            warnings.push(format!("Ignored field '{}'", key));
+        }
+    }
+
+    Ok(ActorPayload {
+        create_actor: json!({
+            "name": name,
+            "actor_type": schema.name,
+            "data": data,
+        }),
+        warnings,
+    })
+}
+
+/// Characteristics keyed by mgt2e name, accepting abbreviations or full names
+fn parse_characteristics(value: Option<&Value>) -> Map<String, Value> {
+    let mut result = Map::new();
+    let Some(object) = value.and_then(|v| v.as_object()) else {
+        return result;
+    };
+
+    for (key, abbr) in CHARACTERISTICS {
+        let found = object
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key) || k.eq_ignore_ascii_case(abbr))
+            .map(|(_, v)| v);
+        // Accept either a bare number or an already-shaped {"value": n}
+        if let Some(n) = found.and_then(|v| v.as_u64().or_else(|| v.get("value")?.as_u64())) {
+            result.insert(key.to_string(), Value::from(n));
+        }
+    }
+    result
+}
+
+fn parse_skills(value: Option<&Value>, warnings: &mut Vec<String>) -> Vec<Value> {
+    let entries: Vec<(String, Option<i64>)> = match value {
+        Some(Value::Array(skills)) => skills
+            .iter()
+            .filter_map(|skill| match skill {
+                Value::String(s) => Some(split_level(s)),
+                Value::Object(o) => o.get("name").and_then(|n| n.as_str()).map(|name| {
+                    (
+                        name.to_string(),
+                        o.get("level")
+                            .or_else(|| o.get("value"))
+                            .and_then(|l| l.as_i64()),
+                    )
+                }),
+                _ => None,
+            })
+            .collect(),
+        Some(Value::Object(skills)) => skills
+            .iter()
+            .map(|(name, level)| (name.clone(), level.as_i64()))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    entries
+        .into_iter()
+        .map(|(name, level)| {
+            let level = level.unwrap_or_else(|| {
+                warnings.push(format!("Skill '{}' has no level; using 0", name));
+                0
+            });
+            let (name, speciality) = split_parenthetical(&name);
+            let mut system = Map::new();
+            system.insert("value".to_string(), Value::from(level));
+            if let Some(speciality) = speciality {
+                system.insert("speciality".to_string(), Value::from(speciality));
+            }
+            json!({ "name": name, "type": "skill", "system": system })
+        })
+        .collect()
+}
+
+/// Split `Pilot (spacecraft) 2` into the name and level
+fn split_level(skill: &str) -> (String, Option<i64>) {
+    let skill = skill.trim();
+    match skill.rsplit_once(' ') {
+        Some((name, level)) if level.parse::<i64>().is_ok() => {
+            (name.trim().to_string(), level.parse().ok())
+        }
+        _ => (skill.to_string(), None),
+    }
+}
+
+/// Split `Autopistol (3D-3)` into `Autopistol` and `3D-3`
+fn split_parenthetical(text: &str) -> (String, Option<String>) {
+    let text = text.trim();
+    match (text.find('('), text.rfind(')')) {
+        (Some(open), Some(close)) if close > open => (
+            text[..open].trim().to_string(),
+            Some(text[open + 1..close].trim().to_string()),
+        ),
+        _ => (text.to_string(), None),
+    }
+}
+
+/// A string or list of strings
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) if !s.is_empty() => vec![s.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|i| i.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_npc_payload() {
+        let stat_block = json!({
+            "name": "Captain Marla Venn",
+            "kind": "npc",
+            "characteristics": { "STR": 7, "DEX": 9, "END": 8, "INT": 10, "EDU": 9, "SOC": 6 },
+            "skills": [{ "name": "Pilot (spacecraft)", "level": 2 }, "Leadership 1"],
+            "weapons": ["Autopistol (3D-3)"],
+            "armour": "Mesh (+2)"
+        });
+
+        let payload = build_actor_payload(&stat_block, None).unwrap();
+        let args = &payload.create_actor;
+        assert_eq!(args["actor_type"], "npc");
+        assert_eq!(args["data"]["characteristics"]["intellect"]["value"], 10);
+
+        let items = args["data"]["items"].as_array().unwrap();
+        assert_eq!(items[0]["name"], "Pilot");
+        assert_eq!(items[0]["system"]["speciality"], "spacecraft");
+        assert_eq!(items[1]["system"]["value"], 1);
+        assert_eq!(items[2]["system"]["damage"], "3D-3");
+        assert_eq!(items[3]["system"]["protection"], 2);
+        assert!(payload.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_missing_required_fields() {
+        let stat_block = json!({ "name": "Thug", "characteristics": { "strength": 9 } });
+        let err = build_actor_payload(&stat_block, None).unwrap_err();
+        assert!(err.contains("characteristics.dexterity"));
+
+        let creature = json!({ "name": "Sand Stalker", "kind": "creature" });
+        let err = build_actor_payload(&creature, None).unwrap_err();
+        assert!(err.contains("hits"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Culture-flavored name generation.
+//!
+//! Names are built from weighted syllable tables rather than asked of the
+//! LLM, so they stay consistent within a culture and a seed always gives
+//! the same names.
+
+mod cultures;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use cultures::{CULTURES, Culture, SHIP_ADJECTIVES, SHIP_NOUNS};
+
+/// Most names generated in one call
+pub const MAX_NAMES: usize = 50;
+
+/// Attempts at a word before accepting one with awkward letter runs
+const WORD_ATTEMPTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameKind {
+    #[default]
+    Person,
+    Ship,
+    Corporation,
+    World,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NameRequest {
+    #[serde(default)]
+    pub kind: NameKind,
+    /// vilani, solomani, aslan or vargr
+    #[serde(default = "default_culture")]
+    pub culture: String,
+    #[serde(default = "default_count")]
+    pub count: usize,
+    pub seed: Option<u64>,
+}
+
+fn default_culture() -> String {
+    "solomani".to_string()
+}
+
+fn default_count() -> usize {
+    5
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedNames {
+    pub seed: u64,
+    pub culture: &'static str,
+    pub kind: NameKind,
+    pub names: Vec<String>,
+}
+
+fn pick<T: Copy>(rng: &mut StdRng, table: &[(T, u32)]) -> T {
+    let total: u32 = table.iter().map(|(_, weight)| weight).sum();
+    let mut roll = rng.gen_range(0..total);
+    for (entry, weight) in table {
+        if roll < *weight {
+            return *entry;
+        }
+        roll -= weight;
+    }
+    table[table.len() - 1].0
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The same letter three times running reads badly in any culture
+fn awkward(word: &str) -> bool {
+    word.as_bytes()
+        .windows(3)
+        .any(|w| w[0] == w[1] && w[1] == w[2])
+}
+
+fn word(rng: &mut StdRng, culture: &Culture) -> String {
+    let mut candidate = String::new();
+    for _ in 0..WORD_ATTEMPTS {
+        candidate.clear();
+        for _ in 0..pick(rng, culture.syllables) {
+            candidate.push_str(pick(rng, culture.onsets));
+            candidate.push_str(pick(rng, culture.vowels));
+            candidate.push_str(pick(rng, culture.codas));
+        }
+        if !awkward(&candidate) {
+            break;
+        }
+    }
+    capitalize(&candidate)
+}
+
+fn name(rng: &mut StdRng, culture: &Culture, kind: NameKind) -> String {
+    match kind {
+        NameKind::Person if culture.family_names => {
+            format!("{} {}", word(rng, culture), word(rng, culture))
+        }
+        NameKind::Person | NameKind::World => word(rng, culture),
+        NameKind::Corporation => {
+            format!(
+                "{} {}",
+                word(rng, culture),
+                pick(rng, culture.corporate_suffixes)
+            )
+        }
+        // Solomani crews favor descriptive names; others name ships like people
+        NameKind::Ship if culture.name == "solomani" && rng.gen_bool(0.5) => {
+            format!("{} {}", pick(rng, SHIP_ADJECTIVES), pick(rng, SHIP_NOUNS))
+        }
+        NameKind::Ship => format!("{}{}", pick(rng, culture.ship_prefixes), word(rng, culture)),
+    }
+}
+
+/// Generate names for a culture
+pub fn generate_names(request: &NameRequest) -> Result<GeneratedNames, String> {
+    let wanted = request.culture.trim().to_lowercase();
+    let culture = CULTURES.iter().find(|c| c.name == wanted).ok_or_else(|| {
+        format!(
+            "Unknown culture '{}'; choose from {}",
+            request.culture,
+            CULTURES
+                .iter()
+                .map(|c| c.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    let seed = request.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let names = (0..request.count.clamp(1, MAX_NAMES))
+        .map(|_| name(&mut rng, culture, request.kind))
+        .collect();
+
+    Ok(GeneratedNames {
+        seed,
+        culture: culture.name,
+        kind: request.kind,
+        names,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(kind: NameKind, culture: &str) -> NameRequest {
+        NameRequest {
+            kind,
+            culture: culture.to_string(),
+            count: 10,
+            seed: Some(1105),
+        }
+    }
+
+    #[test]
+    fn test_generate_names_is_reproducible() {
+        for culture in ["vilani", "solomani", "aslan", "vargr"] {
+            let first = generate_names(&request(NameKind::Person, culture)).unwrap();
+            let second = generate_names(&request(NameKind::Person, culture)).unwrap();
+            assert_eq!(first.names, second.names);
+            assert_eq!(first.names.len(), 10);
+            assert!(
+                first
+                    .names
+                    .iter()
+                    .all(|n| n.chars().next().unwrap().is_uppercase())
+            );
+        }
+
+        let vilani = generate_names(&request(NameKind::Person, "Vilani")).unwrap();
+        assert!(vilani.names.iter().all(|n| n.contains(' ')));
+        let corporations = generate_names(&request(NameKind::Corporation, "vargr")).unwrap();
+        assert!(corporations.names.iter().all(|n| n.contains(' ')));
+    }
+
+    #[test]
+    fn test_unknown_culture() {
+        assert!(
+            generate_names(&request(NameKind::World, "droyne"))
+                .unwrap_err()
+                .contains("vilani, solomani, aslan, vargr")
+        );
+    }
+
+    #[test]
+    fn test_awkward() {
+        assert!(awkward("Shiiishu"));
+        assert!(!awkward("Shiishu"));
+    }
+}
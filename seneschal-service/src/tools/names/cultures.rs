@@ -0,0 +1,265 @@
+//! Weighted syllable tables for each culture.
+//!
+//! Each entry is `(text, weight)`. An empty onset or coda means the
+//! syllable has none.
+
+/// Syllable tables and naming habits for one culture
+#[derive(Debug)]
+pub struct Culture {
+    pub name: &'static str,
+    pub onsets: &'static [(&'static str, u32)],
+    pub vowels: &'static [(&'static str, u32)],
+    pub codas: &'static [(&'static str, u32)],
+    /// Syllables in a single name
+    pub syllables: &'static [(usize, u32)],
+    /// People have a family name as well as a given name
+    pub family_names: bool,
+    /// Corporate and ship name words
+    pub corporate_suffixes: &'static [(&'static str, u32)],
+    pub ship_prefixes: &'static [(&'static str, u32)],
+}
+
+pub const VILANI: Culture = Culture {
+    name: "vilani",
+    onsets: &[
+        ("", 4),
+        ("k", 6),
+        ("sh", 6),
+        ("g", 4),
+        ("l", 4),
+        ("m", 4),
+        ("n", 3),
+        ("d", 3),
+        ("r", 3),
+        ("z", 2),
+        ("b", 2),
+        ("kh", 2),
+        ("s", 2),
+    ],
+    vowels: &[
+        ("a", 10),
+        ("i", 8),
+        ("u", 6),
+        ("aa", 3),
+        ("ii", 3),
+        ("uu", 2),
+        ("e", 2),
+    ],
+    codas: &[
+        ("", 12),
+        ("r", 3),
+        ("n", 3),
+        ("m", 2),
+        ("sh", 2),
+        ("k", 1),
+        ("g", 1),
+    ],
+    syllables: &[(2, 3), (3, 5), (4, 3)],
+    family_names: true,
+    corporate_suffixes: &[
+        ("Sharurshid", 3),
+        ("Naasirka", 3),
+        ("Makhidkarun", 2),
+        ("Consolidated", 2),
+        ("Holdings", 2),
+    ],
+    ship_prefixes: &[("", 6), ("Ziru ", 1), ("Shiishu ", 1)],
+};
+
+pub const SOLOMANI: Culture = Culture {
+    name: "solomani",
+    onsets: &[
+        ("", 2),
+        ("b", 3),
+        ("c", 3),
+        ("d", 3),
+        ("f", 2),
+        ("g", 2),
+        ("h", 3),
+        ("j", 2),
+        ("k", 2),
+        ("l", 4),
+        ("m", 4),
+        ("n", 3),
+        ("p", 2),
+        ("r", 4),
+        ("s", 4),
+        ("t", 4),
+        ("v", 2),
+        ("w", 2),
+        ("br", 1),
+        ("ch", 2),
+        ("st", 1),
+        ("th", 1),
+    ],
+    vowels: &[
+        ("a", 10),
+        ("e", 9),
+        ("i", 6),
+        ("o", 7),
+        ("u", 3),
+        ("ea", 1),
+        ("ie", 1),
+    ],
+    codas: &[
+        ("", 8),
+        ("n", 4),
+        ("r", 3),
+        ("l", 3),
+        ("s", 2),
+        ("t", 2),
+        ("ck", 1),
+        ("nd", 1),
+        ("rt", 1),
+        ("son", 1),
+        ("ton", 1),
+        ("ley", 1),
+    ],
+    syllables: &[(2, 6), (3, 3)],
+    family_names: true,
+    corporate_suffixes: &[
+        ("Industries", 3),
+        ("Lines", 3),
+        ("Shipyards", 2),
+        ("Mining", 2),
+        ("Holdings", 2),
+        ("LIC", 3),
+        ("Consolidated", 1),
+        ("Transport", 2),
+    ],
+    ship_prefixes: &[
+        ("", 4),
+        ("Free Trader ", 2),
+        ("Far Trader ", 1),
+        ("Star ", 1),
+        ("Lady ", 1),
+    ],
+};
+
+pub const ASLAN: Culture = Culture {
+    name: "aslan",
+    onsets: &[
+        ("", 6),
+        ("h", 4),
+        ("ht", 4),
+        ("kh", 4),
+        ("hk", 2),
+        ("w", 3),
+        ("y", 3),
+        ("s", 2),
+        ("f", 2),
+        ("t", 2),
+        ("r", 1),
+        ("st", 1),
+    ],
+    vowels: &[
+        ("ea", 6),
+        ("ao", 5),
+        ("ai", 5),
+        ("oi", 3),
+        ("a", 6),
+        ("e", 3),
+        ("o", 3),
+        ("ua", 2),
+        ("iy", 1),
+    ],
+    codas: &[
+        ("", 10),
+        ("h", 3),
+        ("rl", 2),
+        ("w", 2),
+        ("kh", 2),
+        ("r", 2),
+        ("l", 1),
+    ],
+    syllables: &[(2, 2), (3, 5), (4, 3), (5, 1)],
+    family_names: false,
+    corporate_suffixes: &[("Clan Holdings", 3), ("Ihatei Combine", 1), ("Traders", 2)],
+    ship_prefixes: &[("", 1)],
+};
+
+pub const VARGR: Culture = Culture {
+    name: "vargr",
+    onsets: &[
+        ("", 2),
+        ("g", 4),
+        ("gv", 3),
+        ("gh", 2),
+        ("k", 4),
+        ("kn", 2),
+        ("r", 3),
+        ("rr", 2),
+        ("d", 2),
+        ("dh", 1),
+        ("ts", 1),
+        ("th", 2),
+        ("v", 2),
+        ("l", 2),
+        ("n", 2),
+    ],
+    vowels: &[
+        ("a", 6),
+        ("ae", 5),
+        ("ue", 4),
+        ("o", 5),
+        ("u", 5),
+        ("ou", 3),
+        ("e", 3),
+        ("i", 2),
+    ],
+    codas: &[
+        ("", 6),
+        ("rr", 3),
+        ("g", 3),
+        ("dz", 3),
+        ("ng", 2),
+        ("kh", 2),
+        ("ks", 2),
+        ("n", 2),
+        ("z", 2),
+        ("ts", 1),
+    ],
+    syllables: &[(1, 3), (2, 6), (3, 2)],
+    family_names: false,
+    corporate_suffixes: &[
+        ("Corsairs", 1),
+        ("Pack", 3),
+        ("Raiders", 1),
+        ("Traders", 3),
+        ("Combine", 2),
+    ],
+    ship_prefixes: &[("", 1)],
+};
+
+pub const CULTURES: [Culture; 4] = [VILANI, SOLOMANI, ASLAN, VARGR];
+
+/// Words for Solomani-style descriptive ship names
+pub const SHIP_ADJECTIVES: &[(&str, u32)] = &[
+    ("Bright", 2),
+    ("Distant", 2),
+    ("Fortunate", 2),
+    ("Golden", 2),
+    ("Iron", 2),
+    ("Lucky", 3),
+    ("Patient", 1),
+    ("Quiet", 2),
+    ("Restless", 2),
+    ("Silver", 2),
+    ("Stubborn", 1),
+    ("Wandering", 2),
+];
+
+pub const SHIP_NOUNS: &[(&str, u32)] = &[
+    ("Dawn", 2),
+    ("Drifter", 2),
+    ("Fortune", 2),
+    ("Hope", 2),
+    ("Horizon", 2),
+    ("Lady", 2),
+    ("Promise", 1),
+    ("Rover", 2),
+    ("Star", 3),
+    ("Venture", 2),
+    ("Voyager", 2),
+    ("Wind", 2),
+];
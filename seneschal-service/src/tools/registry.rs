@@ -28,30 +28,70 @@ pub enum ToolName {
     // ==========================================
     DocumentSearch,
     DocumentSearchText,
+    ChunkSimilar,
     DocumentGet,
     DocumentList,
     DocumentFind,
+    DocumentRelated,
     DocumentUpdate,
+    DocumentSetAccess,
+    GlossaryLookup,
+    LibraryData,
 
     // ==========================================
     // Image tools (Internal)
     // ==========================================
     ImageList,
     ImageSearch,
+    ImageSearchSimilar,
+    ImageTag,
+    ImageToken,
+    ImageSceneSettings,
     ImageGet,
     ImageDeliver,
+    ImageRecaption,
+    PageDeliver,
+
+    // ==========================================
+    // Stat block tools (Internal)
+    // ==========================================
+    StatblockSearch,
+    StatblockGet,
+    FvttBuildActor,
+
+    // ==========================================
+    // Read-aloud text tools (Internal)
+    // ==========================================
+    ReadAloudGet,
 
     // ==========================================
     // Page rendering tools (Internal)
     // ==========================================
     RenderPageRegion,
 
+    // ==========================================
+    // Handout tools (Internal)
+    // ==========================================
+    HandoutCompose,
+
+    // ==========================================
+    // Speech tools (Internal)
+    // ==========================================
+    SpeechSynthesize,
+
     // ==========================================
     // Traveller tools (Internal)
     // ==========================================
     TravellerUwpParse,
     TravellerJumpCalc,
     TravellerSkillLookup,
+    TravellerChargen,
+    TravellerShipDesign,
+    TravellerAttack,
+    TravellerDamage,
+    TravellerOpposedCheck,
+    TravellerEncounterTables,
+    NameGenerate,
 
     // ==========================================
     // Traveller Map API tools (Internal)
@@ -59,6 +99,7 @@ pub enum ToolName {
     TravellerMapSearch,
     TravellerMapJumpWorlds,
     TravellerMapRoute,
+    TravellerMapPlanRoute,
     TravellerMapWorldData,
     TravellerMapSectorData,
     TravellerMapCoordinates,
@@ -67,6 +108,10 @@ pub enum ToolName {
     TravellerMapJumpMapUrl,
     TravellerMapSavePoster,
     TravellerMapSaveJumpMap,
+    TravellerMapOverlay,
+    TravellerMapSaveOverlay,
+    TravellerMapSubsectorDossier,
+    TravellerMapWorldJournal,
 
     // ==========================================
     // Traveller Worlds tools (Internal - headless browser)
@@ -88,6 +133,7 @@ pub enum ToolName {
     FvttWrite,
     FvttQuery,
     DiceRoll,
+    ChatLog,
 
     // ==========================================
     // Asset tools (External)
@@ -186,10 +232,210 @@ pub enum ToolName {
     ImportFromCompendium,
     ExportToCompendium,
 
+    // ==========================================
+    // Party context (Internal - reads via GM connection)
+    // ==========================================
+    PartyCharacters,
+
+    // ==========================================
+    // Session tools (Internal - chat log via GM connection)
+    // ==========================================
+    SessionSummary,
+    SaveNote,
+
+    // ==========================================
+    // Campaign timeline tools (Internal)
+    // ==========================================
+    TimelineQuery,
+    TimelineAddEvent,
+    TimelineExtract,
+    ClockGet,
+    ClockSet,
+    ClockAdvance,
+
+    // ==========================================
+    // Campaign memory tools (Internal)
+    // ==========================================
+    MemorySet,
+    MemoryRecall,
+
+    // ==========================================
+    // Campaign task tools (Internal)
+    // ==========================================
+    TaskAdd,
+    TaskList,
+    TaskComplete,
+
+    // ==========================================
+    // NPC registry tools (Internal)
+    // ==========================================
+    NpcSet,
+    NpcRelate,
+    NpcGet,
+    NpcRelations,
+
+    // ==========================================
+    // Plot hook tools (Internal)
+    // ==========================================
+    PlotHooks,
+
+    // ==========================================
+    // Undo log tools (Internal, undoing via GM connection)
+    // ==========================================
+    ListRecentChanges,
+    UndoLastChange,
+    FvttUndoChange,
+
+    // ==========================================
+    // Ollama model management tools (Internal)
+    // ==========================================
+    OllamaListModels,
+    OllamaPullModel,
+    OllamaDeleteModel,
+
     // ==========================================
     // MCP-specific Tools (Internal)
     // ==========================================
     ToolSearch,
+    ToolHelp,
+    ArtifactGet,
+}
+
+impl ToolName {
+    /// Whether the tool changes FVTT world data
+    pub fn is_write(self) -> bool {
+        matches!(
+            self,
+            ToolName::FvttWrite
+                | ToolName::CreateFolder
+                | ToolName::UpdateFolder
+                | ToolName::DeleteFolder
+                | ToolName::CreateScene
+                | ToolName::UpdateScene
+                | ToolName::DeleteScene
+                | ToolName::CreateActor
+                | ToolName::UpdateActor
+                | ToolName::DeleteActor
+                | ToolName::AddActorItem
+                | ToolName::UpdateActorItem
+                | ToolName::DeleteActorItem
+                | ToolName::CreateItem
+                | ToolName::UpdateItem
+                | ToolName::DeleteItem
+                | ToolName::CreateJournal
+                | ToolName::UpdateJournal
+                | ToolName::DeleteJournal
+                | ToolName::AddJournalPage
+                | ToolName::UpdateJournalPage
+                | ToolName::DeleteJournalPage
+                | ToolName::ReorderJournalPages
+                | ToolName::CreateRollableTable
+                | ToolName::UpdateRollableTable
+                | ToolName::DeleteRollableTable
+                | ToolName::UpdateOwnership
+                | ToolName::ImportFromCompendium
+                | ToolName::ExportToCompendium
+                | ToolName::UndoLastChange
+                | ToolName::FvttUndoChange
+                | ToolName::TravellerMapWorldJournal
+        )
+    }
+
+    /// Whether the tool only reads and returns the same result for the same
+    /// arguments, so a repeated call may get the earlier result back. Tools
+    /// that roll dice, ask a model or record anything are never replayed.
+    pub fn is_replayable(self) -> bool {
+        matches!(
+            self,
+            ToolName::DocumentSearch
+                | ToolName::DocumentSearchText
+                | ToolName::ChunkSimilar
+                | ToolName::DocumentGet
+                | ToolName::DocumentList
+                | ToolName::DocumentFind
+                | ToolName::DocumentRelated
+                | ToolName::GlossaryLookup
+                | ToolName::LibraryData
+                | ToolName::ImageList
+                | ToolName::ImageSearch
+                | ToolName::ImageSearchSimilar
+                | ToolName::ImageGet
+                | ToolName::StatblockSearch
+                | ToolName::StatblockGet
+                | ToolName::ReadAloudGet
+                | ToolName::TravellerUwpParse
+                | ToolName::TravellerJumpCalc
+                | ToolName::TravellerSkillLookup
+                | ToolName::TravellerShipDesign
+                | ToolName::TravellerMapSearch
+                | ToolName::TravellerMapJumpWorlds
+                | ToolName::TravellerMapRoute
+                | ToolName::TravellerMapPlanRoute
+                | ToolName::TravellerMapWorldData
+                | ToolName::TravellerMapSectorData
+                | ToolName::TravellerMapCoordinates
+                | ToolName::TravellerMapListSectors
+                | ToolName::TravellerMapPosterUrl
+                | ToolName::TravellerMapJumpMapUrl
+                | ToolName::TravellerWorldsCanonUrl
+                | ToolName::TravellerWorldsCustomUrl
+                | ToolName::SystemSchema
+                | ToolName::FvttRead
+                | ToolName::FvttQuery
+                | ToolName::FvttAssetsBrowse
+                | ToolName::ListFolders
+                | ToolName::GetScene
+                | ToolName::ListScenes
+                | ToolName::GetActor
+                | ToolName::GetActors
+                | ToolName::ListActors
+                | ToolName::GetActorItem
+                | ToolName::ListActorItems
+                | ToolName::GetItem
+                | ToolName::GetItems
+                | ToolName::ListItems
+                | ToolName::GetJournal
+                | ToolName::GetJournals
+                | ToolName::ListJournals
+                | ToolName::GetJournalPage
+                | ToolName::GetJournalPages
+                | ToolName::ListJournalPages
+                | ToolName::GetRollableTable
+                | ToolName::ListRollableTables
+                | ToolName::ListUsers
+                | ToolName::ListCompendiumPacks
+                | ToolName::BrowseCompendiumPack
+                | ToolName::SearchCompendiumPacks
+                | ToolName::PartyCharacters
+                | ToolName::TimelineQuery
+                | ToolName::ClockGet
+                | ToolName::MemoryRecall
+                | ToolName::TaskList
+                | ToolName::NpcGet
+                | ToolName::NpcRelations
+                | ToolName::ListRecentChanges
+                | ToolName::OllamaListModels
+                | ToolName::ToolSearch
+                | ToolName::ToolHelp
+                | ToolName::ArtifactGet
+        )
+    }
+
+    /// Whether the tool accepts `dry_run` to validate arguments and report
+    /// the planned effect without executing
+    pub fn supports_dry_run(self) -> bool {
+        self.is_write()
+            || matches!(
+                self,
+                ToolName::ImageDeliver
+                    | ToolName::PageDeliver
+                    | ToolName::TravellerMapSavePoster
+                    | ToolName::TravellerMapSaveJumpMap
+                    | ToolName::TravellerMapSaveOverlay
+                    | ToolName::TravellerWorldsCanonSave
+                    | ToolName::TravellerWorldsCustomSave
+            )
+    }
 }
 
 /// Metadata for a tool definition.
@@ -223,6 +469,10 @@ pub struct ToolMetadata {
     /// 3 = low priority (specialized tools like traveller_map_*)
     pub priority: u8,
 
+    /// JSON Schema that successful results of external tools must match.
+    /// Results carrying an `error` field are passed through unchecked.
+    pub result_schema: Option<fn() -> serde_json::Value>,
+
     /// JSON Schema for tool parameters (called lazily to avoid static initialization issues)
     pub parameters: fn() -> serde_json::Value,
 }
@@ -233,6 +483,24 @@ impl ToolMetadata {
     pub fn name_str(&self) -> String {
         self.name.to_string()
     }
+
+    /// Parameter schema as advertised to clients, including `dry_run` for
+    /// tools that support it
+    pub fn input_schema(&self) -> serde_json::Value {
+        let mut schema = (self.parameters)();
+        if self.name.supports_dry_run()
+            && let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut())
+        {
+            properties.insert(
+                "dry_run".to_string(),
+                serde_json::json!({
+                    "type": "boolean",
+                    "description": "Validate the arguments and report what the call would do, without executing it"
+                }),
+            );
+        }
+        schema
+    }
 }
 
 /// Central registry of all tools.
@@ -269,7 +537,7 @@ impl ToolRegistry {
                 McpToolDefinition {
                     name: t.name.to_string(),
                     description,
-                    input_schema: (t.parameters)(),
+                    input_schema: t.input_schema(),
                     defer_loading,
                     category: Some(t.category.to_string()),
                 }
@@ -289,6 +557,56 @@ impl ToolRegistry {
             .unwrap_or(ToolLocation::External)
     }
 
+    /// Whether a tool, by its string name, changes FVTT world data.
+    ///
+    /// Unknown tools are treated as writes for safety.
+    pub fn is_write(&self, name: &str) -> bool {
+        ToolName::from_str(name).map_or(true, ToolName::is_write)
+    }
+
+    /// Whether a tool, by its string name, may have its result replayed.
+    ///
+    /// Unknown tools are never replayed.
+    pub fn is_replayable(&self, name: &str) -> bool {
+        ToolName::from_str(name).is_ok_and(ToolName::is_replayable)
+    }
+
+    /// Whether a tool, by its string name, accepts `dry_run`
+    pub fn supports_dry_run(&self, name: &str) -> bool {
+        ToolName::from_str(name).is_ok_and(ToolName::supports_dry_run)
+    }
+
+    /// Check a tool's arguments against its parameter schema.
+    ///
+    /// Returns a description of the first mismatch found.
+    pub fn validate_arguments(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+    ) -> Result<(), String> {
+        let Some(tool) = self.get_by_str(name) else {
+            return Err(format!("Unknown tool: {}", name));
+        };
+        super::result_validation::validate_arguments(&tool.input_schema(), arguments)
+    }
+
+    /// Check an external tool's result against its result schema, if it has one.
+    ///
+    /// Returns a description of the first mismatch found.
+    pub fn validate_result(&self, name: &str, result: &serde_json::Value) -> Result<(), String> {
+        let Some(schema) = ToolName::from_str(name)
+            .ok()
+            .and_then(|n| self.tools.get(&n))
+            .and_then(|t| t.result_schema)
+        else {
+            return Ok(());
+        };
+        if result.get("error").is_some_and(|e| !e.is_null()) {
+            return Ok(());
+        }
+        super::result_validation::validate(&schema(), result)
+    }
+
     /// Get metadata by enum variant
     #[allow(dead_code)]
     pub fn get(&self, name: ToolName) -> Option<&ToolMetadata> {
@@ -296,7 +614,6 @@ impl ToolRegistry {
     }
 
     /// Get metadata by string name
-    #[allow(dead_code)]
     pub fn get_by_str(&self, name: &str) -> Option<&ToolMetadata> {
         ToolName::from_str(name)
             .ok()
@@ -0,0 +1,129 @@
+//! Validation of external tool results and tool arguments.
+//!
+//! Supports the subset of JSON Schema used by tool schemas: `type`
+//! (a name or list of names), `properties`, `required`, `items` and `enum`.
+//! Properties not listed in `properties` are allowed.
+
+use serde_json::Value;
+
+/// Check `value` against `schema`, describing the first mismatch found
+pub fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    validate_at(schema, value, "result")
+}
+
+/// Check tool call `arguments` against a parameter schema
+pub fn validate_arguments(schema: &Value, arguments: &Value) -> Result<(), String> {
+    validate_at(schema, arguments, "arguments")
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    if let Some(expected) = schema.get("type") {
+        let names: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(|n| n.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !names.is_empty() && !names.iter().any(|name| has_type(value, name)) {
+            return Err(format!(
+                "{} should be {} but is {}",
+                path,
+                names.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array())
+        && !allowed.contains(value)
+    {
+        return Err(format!("{} has unexpected value {}", path, value));
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for field in required.iter().filter_map(|f| f.as_str()) {
+                if !fields.contains_key(field) {
+                    return Err(format!("{} is missing required field '{}'", path, field));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (field, field_schema) in properties {
+                if let Some(field_value) = fields.get(field) {
+                    validate_at(field_schema, field_value, &format!("{}.{}", path, field))?;
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        name => type_name(value) == name,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::REGISTRY;
+
+    #[test]
+    fn test_validate() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["total", "dice"],
+            "properties": {
+                "total": { "type": "integer" },
+                "dice": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "faces": { "type": "integer" } }
+                    }
+                }
+            }
+        });
+
+        let roll = serde_json::json!({ "formula": "2d6", "total": 7, "dice": [{ "faces": 6 }] });
+        assert!(validate(&schema, &roll).is_ok());
+
+        let missing = serde_json::json!({ "total": 7 });
+        assert_eq!(
+            validate(&schema, &missing).unwrap_err(),
+            "result is missing required field 'dice'"
+        );
+
+        let wrong = serde_json::json!({ "total": 7, "dice": [{ "faces": "six" }] });
+        assert_eq!(
+            validate(&schema, &wrong).unwrap_err(),
+            "result.dice[0].faces should be integer but is string"
+        );
+
+        // Error results are reported as-is rather than validated
+        let error = serde_json::json!({ "error": "Invalid formula" });
+        assert!(REGISTRY.validate_result("dice_roll", &error).is_ok());
+        assert!(REGISTRY.validate_result("dice_roll", &missing).is_err());
+    }
+}
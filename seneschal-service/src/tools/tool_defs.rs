@@ -6,12 +6,29 @@
 mod document;
 mod fvtt_crud;
 mod fvtt_system;
+mod handout;
 mod image;
 mod mcp;
+mod memory;
+mod npc;
+mod ollama;
+mod party;
+mod plot_hooks;
+mod read_aloud;
 mod rendering;
+mod session;
+mod speech;
+mod statblock;
+mod subsector_dossier;
+mod task;
+mod timeline;
 mod traveller;
+mod traveller_combat;
+mod traveller_encounters;
 mod traveller_map;
 mod traveller_worlds;
+mod undo;
+mod world_journal;
 
 use std::collections::HashMap;
 
@@ -21,11 +38,28 @@ use super::registry::{ToolMetadata, ToolName};
 pub fn register_all_tools(registry: &mut HashMap<ToolName, ToolMetadata>) {
     document::register(registry);
     image::register(registry);
+    statblock::register(registry);
+    read_aloud::register(registry);
     rendering::register(registry);
+    handout::register(registry);
+    speech::register(registry);
     traveller::register(registry);
+    traveller_combat::register(registry);
+    traveller_encounters::register(registry);
     traveller_map::register(registry);
+    subsector_dossier::register(registry);
+    world_journal::register(registry);
     traveller_worlds::register(registry);
     fvtt_system::register(registry);
     fvtt_crud::register(registry);
+    party::register(registry);
+    session::register(registry);
+    timeline::register(registry);
+    memory::register(registry);
+    task::register(registry);
+    npc::register(registry);
+    plot_hooks::register(registry);
+    undo::register(registry);
+    ollama::register(registry);
     mcp::register(registry);
 }
@@ -11,10 +11,15 @@ pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
     let tools = [
         document_search(),
         document_search_text(),
+        chunk_similar(),
         document_get(),
         document_list(),
         document_find(),
+        document_related(),
         document_update(),
+        document_set_access(),
+        glossary_lookup(),
+        library_data(),
     ];
     for tool in tools {
         registry.insert(tool.name, tool);
@@ -30,6 +35,7 @@ fn document_search() -> ToolMetadata {
         mcp_suffix: None,
         category: "document",
         priority: 1, // High priority - core RAG functionality
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -46,6 +52,10 @@ fn document_search() -> ToolMetadata {
                     "limit": {
                         "type": "integer",
                         "description": "Maximum number of results (default 10)"
+                    },
+                    "snippets": {
+                        "type": "boolean",
+                        "description": "Return short snippets with query terms in **bold** instead of whole chunks"
                     }
                 },
                 "required": ["query"]
@@ -63,6 +73,7 @@ fn document_search_text() -> ToolMetadata {
         mcp_suffix: None,
         category: "document",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -90,6 +101,35 @@ fn document_search_text() -> ToolMetadata {
     }
 }
 
+fn chunk_similar() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ChunkSimilar,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Find text chunks similar to a chunk from a previous document_search result (by its Chunk ID), from other pages and other books. Use it to surface related rules, variants and errata for a rule being read.",
+        mcp_suffix: None,
+        category: "document",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "chunk_id": {
+                        "type": "string",
+                        "description": "The chunk ID"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of results (default 10)"
+                    }
+                },
+                "required": ["chunk_id"]
+            })
+        },
+    }
+}
+
 fn document_get() -> ToolMetadata {
     ToolMetadata {
         name: ToolName::DocumentGet,
@@ -99,6 +139,7 @@ fn document_get() -> ToolMetadata {
         mcp_suffix: None,
         category: "document",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -110,6 +151,10 @@ fn document_get() -> ToolMetadata {
                     "page": {
                         "type": "integer",
                         "description": "Page number to retrieve. If specified, returns the full text content of that page. If omitted, returns document metadata only."
+                    },
+                    "version": {
+                        "type": "integer",
+                        "description": "Read the page as it was in an earlier version of the document (versions are listed in the document metadata). Defaults to the current version."
                     }
                 },
                 "required": ["document_id"]
@@ -123,10 +168,11 @@ fn document_list() -> ToolMetadata {
         name: ToolName::DocumentList,
         location: ToolLocation::Internal,
         mcp_enabled: true,
-        description: "List all available documents (rulebooks, scenarios) with their IDs and titles.",
+        description: "List all available documents (rulebooks, scenarios) with their IDs, titles and short summaries. Use 'about' to find which documents cover a topic (e.g. 'mercenary tickets') without a deep search.",
         mcp_suffix: None,
         category: "document",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -135,6 +181,14 @@ fn document_list() -> ToolMetadata {
                         "type": "array",
                         "items": { "type": "string" },
                         "description": "Optional tags to filter documents"
+                    },
+                    "about": {
+                        "type": "string",
+                        "description": "Optional topic; returns the documents whose summaries best match it, most relevant first"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of documents when 'about' is given (default 10)"
                     }
                 }
             })
@@ -151,6 +205,7 @@ fn document_find() -> ToolMetadata {
         mcp_suffix: None,
         category: "document",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -166,6 +221,35 @@ fn document_find() -> ToolMetadata {
     }
 }
 
+fn document_related() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::DocumentRelated,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Find documents related to a document: similar content, wiki links in either direction, and shared tags. Use to suggest adjacent source material (e.g. the supplement that expands a rulebook chapter, or session notes about an adventure).",
+        mcp_suffix: None,
+        category: "document",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "document_id": {
+                        "type": "string",
+                        "description": "The document ID (get from document_list or document_find)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of related documents (default 5)"
+                    }
+                },
+                "required": ["document_id"]
+            })
+        },
+    }
+}
+
 fn document_update() -> ToolMetadata {
     ToolMetadata {
         name: ToolName::DocumentUpdate,
@@ -175,6 +259,7 @@ fn document_update() -> ToolMetadata {
         mcp_suffix: None,
         category: "document",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -203,3 +288,116 @@ fn document_update() -> ToolMetadata {
         },
     }
 }
+
+fn document_set_access() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::DocumentSetAccess,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Override the access level for part of a document, by page range and/or section title. Use this to hide a scenario's GM-only sections (e.g., 'Referee Information', pages 40-52) while the rest stays visible to players. Later rules take precedence over earlier ones. Pass remove_rule_id to delete a rule; call with only document_id to list existing rules.",
+        mcp_suffix: None,
+        category: "document",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "document_id": {
+                        "type": "string",
+                        "description": "The unique identifier of the document"
+                    },
+                    "start_page": {
+                        "type": "integer",
+                        "description": "First page of the range (inclusive)"
+                    },
+                    "end_page": {
+                        "type": "integer",
+                        "description": "Last page of the range (inclusive)"
+                    },
+                    "section_pattern": {
+                        "type": "string",
+                        "description": "Case-insensitive text matched against section titles"
+                    },
+                    "access_level": {
+                        "type": "string",
+                        "enum": ["player", "trusted", "assistant", "gm_only"],
+                        "description": "Who can access the matching pages/sections"
+                    },
+                    "remove_rule_id": {
+                        "type": "string",
+                        "description": "ID of an existing rule to remove instead of adding one"
+                    }
+                },
+                "required": ["document_id"]
+            })
+        },
+    }
+}
+
+fn glossary_lookup() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::GlossaryLookup,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Look up a term in the indexes and glossaries of rulebooks. Returns the pages that cover it (read them with document_get) and any glossary definition. Faster and more precise than document_search when you know the term (e.g. 'jump drive', 'Vacc Suit').",
+        mcp_suffix: None,
+        category: "document",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "term": {
+                        "type": "string",
+                        "description": "The term to look up; exact matches come first, then partial matches"
+                    },
+                    "document_id": {
+                        "type": "string",
+                        "description": "Only look in this document's index"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum entries (default 10)"
+                    }
+                },
+                "required": ["term"]
+            })
+        },
+    }
+}
+
+fn library_data() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::LibraryData,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Answer an in-character Library Data lookup (the ship's computer or a starport terminal) with an entry name, classification and excerpt quoted from the documents. Read the excerpt out as-is; if it reports NO DATA AVAILABLE, the library has nothing on the topic, so don't make an answer up.",
+        mcp_suffix: None,
+        category: "document",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "topic": {
+                        "type": "string",
+                        "description": "What the character looks up (e.g. 'Regina', 'Travellers' Aid Society')"
+                    },
+                    "access_level": {
+                        "type": "string",
+                        "enum": ["player", "trusted", "assistant", "gm_only"],
+                        "description": "Most restricted content the entry may draw on (default player)"
+                    },
+                    "min_confidence": {
+                        "type": "number",
+                        "description": "Relevance (0-1) a passage needs to be used (default 0.6)"
+                    }
+                },
+                "required": ["topic"]
+            })
+        },
+    }
+}
@@ -31,6 +31,7 @@ fn add_actor_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -75,6 +76,7 @@ fn get_actor_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -107,6 +109,7 @@ fn update_actor_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -151,6 +154,7 @@ fn delete_actor_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -183,6 +187,7 @@ fn list_actor_items() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -31,6 +31,7 @@ fn list_compendium_packs() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -59,6 +60,7 @@ fn browse_compendium_pack() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -99,6 +101,7 @@ fn search_compendium_packs() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -132,6 +135,7 @@ fn import_from_compendium() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -173,6 +177,7 @@ fn export_to_compendium() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -32,6 +32,7 @@ fn create_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -76,6 +77,7 @@ fn get_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -104,6 +106,7 @@ fn get_items() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -133,6 +136,7 @@ fn update_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -177,6 +181,7 @@ fn delete_item() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -205,6 +210,7 @@ fn list_items() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 1, // High priority - second most common FVTT query
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
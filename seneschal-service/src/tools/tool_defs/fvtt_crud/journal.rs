@@ -32,6 +32,7 @@ fn create_journal() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -79,6 +80,7 @@ fn get_journal() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -107,6 +109,7 @@ fn get_journals() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -136,6 +139,7 @@ fn update_journal() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -183,6 +187,7 @@ fn delete_journal() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -211,6 +216,7 @@ fn list_journals() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -31,6 +31,19 @@ fn create_scene() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: Some(|| {
+            serde_json::json!({
+                "type": "object",
+                "required": ["success", "id"],
+                "properties": {
+                    "success": { "type": "boolean" },
+                    "id": { "type": "string" },
+                    "name": { "type": "string" },
+                    "width": { "type": "number" },
+                    "height": { "type": "number" }
+                }
+            })
+        }),
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -55,6 +68,19 @@ fn create_scene() -> ToolMetadata {
                         "type": "integer",
                         "description": "Grid size in pixels (default: 100)"
                     },
+                    "grid_type": {
+                        "type": "string",
+                        "enum": ["square", "gridless", "hex_odd_q", "hex_even_q", "hex_odd_r", "hex_even_r"],
+                        "description": "Grid type (default: square). The _q types are hex columns, the _r types hex rows."
+                    },
+                    "grid_distance": {
+                        "type": "number",
+                        "description": "Distance covered by one grid space"
+                    },
+                    "grid_units": {
+                        "type": "string",
+                        "description": "Units for grid distance (e.g., 'm', 'pc')"
+                    },
                     "folder": {
                         "type": "string",
                         "description": "Folder name or ID to place the scene in"
@@ -79,6 +105,7 @@ fn get_scene() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -107,6 +134,7 @@ fn update_scene() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -163,6 +191,13 @@ fn delete_scene() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: Some(|| {
+            serde_json::json!({
+                "type": "object",
+                "required": ["success"],
+                "properties": { "success": { "type": "boolean" } }
+            })
+        }),
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -191,6 +226,19 @@ fn list_scenes() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_crud",
         priority: 2,
+        result_schema: Some(|| {
+            serde_json::json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["id", "name"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "name": { "type": "string" }
+                    }
+                }
+            })
+        }),
         parameters: || {
             serde_json::json!({
                 "type": "object",
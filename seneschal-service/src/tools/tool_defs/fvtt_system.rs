@@ -18,6 +18,7 @@ pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
         fvtt_write(),
         fvtt_query(),
         dice_roll(),
+        chat_log(),
         // Asset tools
         fvtt_assets_browse(),
         image_describe(),
@@ -44,6 +45,7 @@ fn system_schema() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -68,6 +70,7 @@ fn fvtt_read() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -97,6 +100,7 @@ fn fvtt_write() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -131,6 +135,7 @@ fn fvtt_query() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -164,6 +169,26 @@ fn dice_roll() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 3, // Low priority - specialized tool
+        result_schema: Some(|| {
+            serde_json::json!({
+                "type": "object",
+                "required": ["formula", "total"],
+                "properties": {
+                    "formula": { "type": "string" },
+                    "total": { "type": "number" },
+                    "dice": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "faces": { "type": "integer" },
+                                "results": { "type": "array", "items": { "type": "number" } }
+                            }
+                        }
+                    }
+                }
+            })
+        }),
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -183,6 +208,54 @@ fn dice_roll() -> ToolMetadata {
     }
 }
 
+fn chat_log() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ChatLog,
+        location: ToolLocation::External,
+        mcp_enabled: true,
+        description: "Read recent FVTT chat messages with speakers and dice roll results, oldest first.",
+        mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
+        category: "fvtt_system",
+        priority: 3,
+        result_schema: Some(|| {
+            serde_json::json!({
+                "type": "object",
+                "required": ["messages"],
+                "properties": {
+                    "messages": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["timestamp", "content"],
+                            "properties": {
+                                "timestamp": { "type": "string" },
+                                "speaker": { "type": ["string", "null"] },
+                                "content": { "type": "string" },
+                                "rolls": { "type": "array" }
+                            }
+                        }
+                    }
+                }
+            })
+        }),
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "since": {
+                        "type": "string",
+                        "description": "ISO 8601 timestamp; only messages after this are returned"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of messages, keeping the most recent (default 200)"
+                    }
+                }
+            })
+        },
+    }
+}
+
 fn fvtt_assets_browse() -> ToolMetadata {
     ToolMetadata {
         name: ToolName::FvttAssetsBrowse,
@@ -192,6 +265,7 @@ fn fvtt_assets_browse() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -229,6 +303,7 @@ fn image_describe() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -261,6 +336,7 @@ fn list_folders() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -294,6 +370,7 @@ fn create_folder() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -335,6 +412,7 @@ fn update_folder() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -375,6 +453,7 @@ fn delete_folder() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -407,6 +486,7 @@ fn list_users() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -430,6 +510,7 @@ fn update_ownership() -> ToolMetadata {
         mcp_suffix: Some(EXTERNAL_MCP_SUFFIX),
         category: "fvtt_system",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
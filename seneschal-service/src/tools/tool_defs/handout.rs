@@ -0,0 +1,56 @@
+//! Player handout tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    registry.insert(handout_compose().name, handout_compose());
+}
+
+fn handout_compose() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::HandoutCompose,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Render a player handout from markdown as a styled PDF or PNG and deliver it to FVTT assets. Templates: patron_briefing (a patron's job offer), news_feed (a news bulletin), library_data (a computer library entry) or plain. Embed corpus images inline with ![caption](image:<image_id>), or list them in image_ids to add them at the end. The handout is stamped with the campaign date when the clock is set.",
+        mcp_suffix: None,
+        category: "rendering",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "title": {
+                        "type": "string",
+                        "description": "Handout title"
+                    },
+                    "markdown": {
+                        "type": "string",
+                        "description": "Handout content in markdown (tables supported)"
+                    },
+                    "template": {
+                        "type": "string",
+                        "enum": ["plain", "patron_briefing", "news_feed", "library_data"],
+                        "description": "Handout style (default plain)"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["pdf", "png"],
+                        "description": "Output format (default pdf)"
+                    },
+                    "image_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Corpus images to add after the content (from image_search)"
+                    }
+                },
+                "required": ["title", "markdown"]
+            })
+        },
+    }
+}
@@ -8,7 +8,18 @@ use crate::tools::{
 };
 
 pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
-    let tools = [image_list(), image_search(), image_get(), image_deliver()];
+    let tools = [
+        image_list(),
+        image_search(),
+        image_search_similar(),
+        image_tag(),
+        image_token(),
+        image_scene_settings(),
+        image_get(),
+        image_deliver(),
+        image_recaption(),
+        page_deliver(),
+    ];
     for tool in tools {
         registry.insert(tool.name, tool);
     }
@@ -19,17 +30,32 @@ fn image_list() -> ToolMetadata {
         name: ToolName::ImageList,
         location: ToolLocation::Internal,
         mcp_enabled: true,
-        description: "List images from a document. Use document_find first to get the document ID, then use this to browse images from specific pages or page ranges.",
+        description: "List images from a document, or across documents by tag. Use document_find first to get the document ID, then use this to browse images from specific pages or page ranges. Give tags (e.g. 'deckplan') to browse a curated set.",
         mcp_suffix: None,
         category: "image",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
                 "properties": {
                     "document_id": {
                         "type": "string",
-                        "description": "The document ID"
+                        "description": "The document ID (required unless tags are given)"
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Optional: only images with these tags (images without their own tags use their document's)"
+                    },
+                    "tags_match": {
+                        "type": "string",
+                        "enum": ["any", "all"],
+                        "description": "Whether images need any (default) or all of the tags"
+                    },
+                    "exclude_used": {
+                        "type": "boolean",
+                        "description": "Leave out images already delivered to FVTT for this campaign, to avoid suggesting art the players have seen (default false)"
                     },
                     "start_page": {
                         "type": "integer",
@@ -43,8 +69,7 @@ fn image_list() -> ToolMetadata {
                         "type": "integer",
                         "description": "Maximum images to return (default 20)"
                     }
-                },
-                "required": ["document_id"]
+                }
             })
         },
     }
@@ -59,6 +84,7 @@ fn image_search() -> ToolMetadata {
         mcp_suffix: None,
         category: "image",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -71,6 +97,20 @@ fn image_search() -> ToolMetadata {
                         "type": "string",
                         "description": "Optional: limit search to a specific document"
                     },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Optional: only images with these tags (images without their own tags use their document's)"
+                    },
+                    "tags_match": {
+                        "type": "string",
+                        "enum": ["any", "all"],
+                        "description": "Whether images need any (default) or all of the tags"
+                    },
+                    "exclude_used": {
+                        "type": "boolean",
+                        "description": "Leave out images already delivered to FVTT for this campaign, to avoid suggesting art the players have seen (default false)"
+                    },
                     "limit": {
                         "type": "integer",
                         "description": "Maximum results (default 10)"
@@ -82,6 +122,144 @@ fn image_search() -> ToolMetadata {
     }
 }
 
+fn image_search_similar() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ImageSearchSimilar,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Find indexed document images that look like an example image. Give either an FVTT image path (e.g., a token or tile) to find its original art in the rulebooks, or the ID of an indexed image to find related artwork.",
+        mcp_suffix: None,
+        category: "image",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "fvtt_path": {
+                        "type": "string",
+                        "description": "FVTT path to the example image (e.g., 'assets/tokens/guard.webp')"
+                    },
+                    "image_id": {
+                        "type": "string",
+                        "description": "ID of an indexed image to use as the example"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum results (default 10)"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn image_tag() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ImageTag,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Add or remove tags on images to curate browsable sets (e.g. tag deck plans 'deckplan', then list them with image_list). Images without tags of their own inherit their document's tags; giving an image its own tags replaces the inherited ones.",
+        mcp_suffix: None,
+        category: "image",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "image_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "IDs of the images to tag"
+                    },
+                    "add": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Tags to add"
+                    },
+                    "remove": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Tags to remove"
+                    }
+                },
+                "required": ["image_ids"]
+            })
+        },
+    }
+}
+
+fn image_token() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ImageToken,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Make a circular, token-ready cutout from character or creature art. The subject is found from transparency or the background, cropped from the top so the head stays in frame, and stored as a new image linked to the original. Delivered to the FVTT assets tree by default.",
+        mcp_suffix: None,
+        category: "image",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "image_id": {
+                        "type": "string",
+                        "description": "ID of the character art image"
+                    },
+                    "size": {
+                        "type": "integer",
+                        "description": "Token edge length in pixels, 100-1024 (default 400)"
+                    },
+                    "ring_color": {
+                        "type": "string",
+                        "description": "Optional ring color as #rrggbb"
+                    },
+                    "deliver": {
+                        "type": "boolean",
+                        "description": "Copy the token to the FVTT assets directory (default true)"
+                    }
+                },
+                "required": ["image_id"]
+            })
+        },
+    }
+}
+
+fn image_scene_settings() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ImageSceneSettings,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Prepare a deck plan or battle map image as an FVTT scene. Detects the square grid printed on the map, delivers the image to the FVTT assets tree, and returns scene settings (width, height, padding, grid size and distance, background offset) scaled so one map square is one FVTT grid square. Pass the settings to create_scene.",
+        mcp_suffix: None,
+        category: "image",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "image_id": {
+                        "type": "string",
+                        "description": "ID of the map image"
+                    },
+                    "grid_distance": {
+                        "type": "number",
+                        "description": "Distance one grid square represents (default 1.5)"
+                    },
+                    "grid_units": {
+                        "type": "string",
+                        "description": "Units of grid_distance (default 'm')"
+                    }
+                },
+                "required": ["image_id"]
+            })
+        },
+    }
+}
+
 fn image_get() -> ToolMetadata {
     ToolMetadata {
         name: ToolName::ImageGet,
@@ -91,6 +269,7 @@ fn image_get() -> ToolMetadata {
         mcp_suffix: None,
         category: "image",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -115,6 +294,7 @@ fn image_deliver() -> ToolMetadata {
         mcp_suffix: None,
         category: "image",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -133,3 +313,98 @@ fn image_deliver() -> ToolMetadata {
         },
     }
 }
+
+fn image_recaption() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ImageRecaption,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Regenerate vision model descriptions for document images: specific images, a page range, or the whole document. Use 'transcription' for maps and deck plans whose labels matter, 'brief' for short captions. Captioning runs in the background.",
+        mcp_suffix: None,
+        category: "image",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "document_id": {
+                        "type": "string",
+                        "description": "The document whose images to re-caption"
+                    },
+                    "image_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Optional: specific images to re-caption"
+                    },
+                    "start_page": {
+                        "type": "integer",
+                        "description": "Optional: first page of the range (inclusive)"
+                    },
+                    "end_page": {
+                        "type": "integer",
+                        "description": "Optional: last page of the range (inclusive)"
+                    },
+                    "vision_model": {
+                        "type": "string",
+                        "description": "Optional: vision model to use (defaults to the document's or configured model)"
+                    },
+                    "preset": {
+                        "type": "string",
+                        "enum": ["brief", "detailed", "transcription"],
+                        "description": "Description style (default 'detailed')"
+                    }
+                },
+                "required": ["document_id"]
+            })
+        },
+    }
+}
+
+fn page_deliver() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::PageDeliver,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Render a PDF page, or a rectangle on it, and copy the image to the Foundry VTT assets directory. Use this when image_list doesn't have what you need: tables, vector-drawn maps and diagrams, or a whole page as a handout. Returns the full FVTT path (starting with 'assets/') to use in documents.",
+        mcp_suffix: None,
+        category: "image",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "document_id": {
+                        "type": "string",
+                        "description": "The document ID (must be a PDF)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page number (1-indexed)"
+                    },
+                    "region": {
+                        "type": "object",
+                        "description": "Optional: part of the page to keep, as fractions of the page size measured from the top-left corner (e.g., the bottom half is {x: 0, y: 0.5, width: 1, height: 0.5})",
+                        "properties": {
+                            "x": { "type": "number" },
+                            "y": { "type": "number" },
+                            "width": { "type": "number" },
+                            "height": { "type": "number" }
+                        },
+                        "required": ["x", "y", "width", "height"]
+                    },
+                    "dpi": {
+                        "type": "integer",
+                        "description": "Optional: render resolution, 36-300 (default 150). Use higher values for small regions."
+                    },
+                    "target_path": {
+                        "type": "string",
+                        "description": "Optional: path relative to the assets directory, e.g., 'seneschal/handouts/ship_plan.webp'. Do NOT include 'assets/' prefix. Default: auto-generated as 'seneschal/{doc_title}/page_{N}_render.webp'"
+                    }
+                },
+                "required": ["document_id", "page"]
+            })
+        },
+    }
+}
@@ -1,7 +1,7 @@
 //! MCP-specific tool definitions.
 //!
 //! These tools are only exposed via MCP and provide meta-functionality
-//! for tool discovery and search.
+//! for tool discovery, tool usage help and reading stored tool results.
 
 use std::collections::HashMap;
 
@@ -11,7 +11,7 @@ use crate::tools::{
 };
 
 pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
-    let tools = [tool_search()];
+    let tools = [tool_search(), tool_help(), artifact_get()];
     for tool in tools {
         registry.insert(tool.name, tool);
     }
@@ -26,6 +26,7 @@ fn tool_search() -> ToolMetadata {
         mcp_suffix: None,
         category: "mcp",
         priority: 0, // Never defer - always available for discovery
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -44,3 +45,65 @@ fn tool_search() -> ToolMetadata {
         },
     }
 }
+
+fn tool_help() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ToolHelp,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Show a tool's parameters, required arguments, allowed values and an example call. Write tools also accept dry_run: true to check arguments and report the planned effect without executing.",
+        mcp_suffix: None,
+        category: "mcp",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "tool": {
+                        "type": "string",
+                        "description": "Name of the tool to describe (e.g., 'create_actor')"
+                    }
+                },
+                "required": ["tool"]
+            })
+        },
+    }
+}
+
+fn artifact_get() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ArtifactGet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Read part of a large tool result that was saved as an artifact. Truncated results name their artifact_id; request a line range or lines matching a pattern instead of repeating the original call.",
+        mcp_suffix: None,
+        category: "mcp",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "artifact_id": {
+                        "type": "string",
+                        "description": "Artifact ID from a truncated tool result"
+                    },
+                    "start_line": {
+                        "type": "integer",
+                        "description": "First line to return, 1-based (default 1)"
+                    },
+                    "end_line": {
+                        "type": "integer",
+                        "description": "Last line to return (default start_line + 199)"
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Only return lines containing this text (case-insensitive), within start_line/end_line if given"
+                    }
+                },
+                "required": ["artifact_id"]
+            })
+        },
+    }
+}
@@ -0,0 +1,73 @@
+//! Campaign memory tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [memory_set(), memory_recall()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn memory_set() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::MemorySet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Remember a canonical campaign fact settled in play (e.g. 'The party owns the Highndry', 'Baron Sonnim is dead'). Remembered facts override the documents in later sessions. Give memory_id to replace a fact that is no longer true instead of adding a contradicting one. Only store facts the GM has confirmed.",
+        mcp_suffix: None,
+        category: "memory",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "fact": {
+                        "type": "string",
+                        "description": "The fact, as one self-contained sentence"
+                    },
+                    "memory_id": {
+                        "type": "string",
+                        "description": "ID of a remembered fact to replace (from memory_recall)"
+                    }
+                },
+                "required": ["fact"]
+            })
+        },
+    }
+}
+
+fn memory_recall() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::MemoryRecall,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Recall remembered campaign facts related to a topic, most relevant first. Check this before answering about NPCs, ships, factions or places the party has dealt with, since remembered facts override the documents.",
+        mcp_suffix: None,
+        category: "memory",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Topic to recall facts about (e.g. 'Baron Sonnim', 'our ship')"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum facts (default 10)"
+                    }
+                },
+                "required": ["query"]
+            })
+        },
+    }
+}
@@ -0,0 +1,149 @@
+//! NPC registry tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [npc_set(), npc_relate(), npc_get(), npc_relations()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn npc_set() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::NpcSet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Add a recurring NPC to the campaign's NPC registry, or update one already there (matched by npc_id or name). Link the NPC to an extracted stat block or an FVTT actor when there is one. Only the fields given are changed.",
+        mcp_suffix: None,
+        category: "npc",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "The NPC's name (defaults to the stat block's name)"
+                    },
+                    "npc_id": {
+                        "type": "string",
+                        "description": "ID of the NPC to update, e.g. to rename them"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "Who the NPC is: role, appearance, manner, what the party knows of them"
+                    },
+                    "stat_block_id": {
+                        "type": "string",
+                        "description": "Stat block the NPC uses (from statblock_search)"
+                    },
+                    "actor_uuid": {
+                        "type": "string",
+                        "description": "UUID of the FVTT actor for the NPC"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn npc_relate() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::NpcRelate,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Record a relationship from one NPC to another, read as '<source> <relation> <target>' (e.g. source 'Anders Casarii', relation 'employed by', target 'Baron Sonnim'). NPCs not yet in the registry are added by name. Set remove to true when a relationship ends.",
+        mcp_suffix: None,
+        category: "npc",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "source": {
+                        "type": "string",
+                        "description": "Name or ID of the NPC the relationship is from"
+                    },
+                    "relation": {
+                        "type": "string",
+                        "description": "The relationship (e.g. 'rival of', 'employed by', 'sister of')"
+                    },
+                    "target": {
+                        "type": "string",
+                        "description": "Name or ID of the NPC the relationship is to"
+                    },
+                    "notes": {
+                        "type": "string",
+                        "description": "Details of the relationship"
+                    },
+                    "remove": {
+                        "type": "boolean",
+                        "description": "Remove the relationship instead of recording it"
+                    }
+                },
+                "required": ["source", "relation", "target"]
+            })
+        },
+    }
+}
+
+fn npc_get() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::NpcGet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Look up a registered NPC by name or ID: their description, stat block, FVTT actor and relationships. Check this before portraying a recurring NPC so they stay consistent with earlier sessions.",
+        mcp_suffix: None,
+        category: "npc",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "npc": {
+                        "type": "string",
+                        "description": "The NPC's name or ID"
+                    }
+                },
+                "required": ["npc"]
+            })
+        },
+    }
+}
+
+fn npc_relations() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::NpcRelations,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "List relationships in the NPC registry: those of one NPC when npc is given, otherwise every relationship in the campaign. Filter by relation to answer questions like 'who employs whom'.",
+        mcp_suffix: None,
+        category: "npc",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "npc": {
+                        "type": "string",
+                        "description": "Name or ID of an NPC"
+                    },
+                    "relation": {
+                        "type": "string",
+                        "description": "Only relationships containing this text (e.g. 'rival')"
+                    }
+                }
+            })
+        },
+    }
+}
@@ -0,0 +1,88 @@
+//! Ollama model management tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [
+        ollama_list_models(),
+        ollama_pull_model(),
+        ollama_delete_model(),
+    ];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn ollama_list_models() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::OllamaListModels,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "List models installed on the Ollama host with size and modification date, the models currently configured for chat, vision and embeddings, and the progress of any model pulls.",
+        mcp_suffix: None,
+        category: "ollama",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {}
+            })
+        },
+    }
+}
+
+fn ollama_pull_model() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::OllamaPullModel,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Start downloading a model from the Ollama library (e.g. 'llama3.2:3b'). The pull runs in the background; check progress with ollama_list_models.",
+        mcp_suffix: None,
+        category: "ollama",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "model": {
+                        "type": "string",
+                        "description": "Model name with optional tag"
+                    }
+                },
+                "required": ["model"]
+            })
+        },
+    }
+}
+
+fn ollama_delete_model() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::OllamaDeleteModel,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Delete a model from the Ollama host to free disk space. Refuses to delete a model that is currently configured for chat, vision or embeddings.",
+        mcp_suffix: None,
+        category: "ollama",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "model": {
+                        "type": "string",
+                        "description": "Exact installed model name, including tag"
+                    }
+                },
+                "required": ["model"]
+            })
+        },
+    }
+}
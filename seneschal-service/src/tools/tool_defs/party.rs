@@ -0,0 +1,39 @@
+//! Party context tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [party_characters()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn party_characters() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::PartyCharacters,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "List players currently connected to Foundry VTT with a condensed sheet (characteristics, skills, items) for each player's character. Use this to answer questions like 'can my character make this check'.",
+        mcp_suffix: Some("Requires GM WebSocket connection."),
+        category: "party",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "include_owned": {
+                        "type": "boolean",
+                        "description": "Also include other actors each player owns (vehicles, companions), not just their assigned character (default false)"
+                    }
+                }
+            })
+        },
+    }
+}
@@ -0,0 +1,56 @@
+//! Rumor and plot hook tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [plot_hooks()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn plot_hooks() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::PlotHooks,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Draw rumors and plot hooks for a world or subsector from GM-only passages in indexed adventures. Retell each passage as a rumor the players might hear, without quoting it or naming the source book. Passages already drawn in this campaign are skipped; use reset to allow them again.",
+        mcp_suffix: None,
+        category: "plot_hooks",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "location": {
+                        "type": "string",
+                        "description": "World or subsector the party is in (e.g. 'Regina', 'District 268')"
+                    },
+                    "count": {
+                        "type": "integer",
+                        "description": "How many hooks (default 3, max 10)"
+                    },
+                    "tag": {
+                        "type": "string",
+                        "description": "Tag marking adventure documents (default 'adventure')"
+                    },
+                    "seed": {
+                        "type": "integer",
+                        "description": "Seed to reproduce a previous draw"
+                    },
+                    "reset": {
+                        "type": "boolean",
+                        "description": "Forget which hooks this campaign has already drawn before drawing"
+                    }
+                },
+                "required": ["location"]
+            })
+        },
+    }
+}
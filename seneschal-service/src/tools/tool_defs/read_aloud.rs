@@ -0,0 +1,49 @@
+//! Read-aloud text tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tool = read_aloud_get();
+    registry.insert(tool.name, tool);
+}
+
+fn read_aloud_get() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ReadAloudGet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Get the boxed read-aloud text an adventure has the GM read to the players, verbatim, for a scene or page. Quote the returned text unmodified when the GM wants to paste it into chat; do not paraphrase it. Chunks holding read-aloud text are also tagged 'read-aloud' for document_search.",
+        mcp_suffix: None,
+        category: "document",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "scene": {
+                        "type": "string",
+                        "description": "Scene or section name, or words from the passage (e.g. 'Arrival at Walston')"
+                    },
+                    "document_id": {
+                        "type": "string",
+                        "description": "Only passages from this document"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Only passages on this page"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum passages (default 5)"
+                    }
+                }
+            })
+        },
+    }
+}
@@ -24,6 +24,7 @@ fn render_page_region() -> ToolMetadata {
         mcp_suffix: None,
         category: "rendering",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -0,0 +1,106 @@
+//! Session recap and note tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [session_summary(), save_note()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn session_summary() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::SessionSummary,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Generate a structured session recap (scenes, NPCs met, loot, open threads) from a conversation transcript and the FVTT chat log including dice rolls. The recap is stored as a searchable document and can also be written to an FVTT journal. Pass the relevant conversation as 'transcript'; the server does not keep chat history.",
+        mcp_suffix: Some(
+            "Reading the chat log and writing journals requires GM WebSocket connection.",
+        ),
+        category: "session",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "title": {
+                        "type": "string",
+                        "description": "Title for the recap (e.g. 'Session 12 - The Regina Job')"
+                    },
+                    "transcript": {
+                        "type": "string",
+                        "description": "Conversation transcript or GM notes for the session"
+                    },
+                    "include_chat_log": {
+                        "type": "boolean",
+                        "description": "Include the FVTT chat log and dice rolls (default true)"
+                    },
+                    "since": {
+                        "type": "string",
+                        "description": "ISO 8601 timestamp of the session start; earlier chat messages are ignored"
+                    },
+                    "write_journal": {
+                        "type": "boolean",
+                        "description": "Also create an FVTT journal entry with the recap (default false)"
+                    },
+                    "folder": {
+                        "type": "string",
+                        "description": "Journal folder name or ID"
+                    },
+                    "access_level": {
+                        "type": "string",
+                        "enum": ["player", "trusted", "assistant", "gm_only"],
+                        "description": "Who can search the stored recap (default gm_only)"
+                    }
+                },
+                "required": ["title"]
+            })
+        },
+    }
+}
+
+fn save_note() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::SaveNote,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Save content from this conversation (a house rule, NPC write-up, ruling or other generated material) as a note document. Notes are tagged 'note', marked source=chat, and indexed like any other document so future searches find them. Use when the GM asks to keep or remember an answer.",
+        mcp_suffix: None,
+        category: "session",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "title": {
+                        "type": "string",
+                        "description": "Title for the note (e.g. 'House Rule: Misjumps'); generated from the content if omitted"
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "Note text in Markdown"
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Extra tags (e.g. 'house-rule', 'npc')"
+                    },
+                    "access_level": {
+                        "type": "string",
+                        "enum": ["player", "trusted", "assistant", "gm_only"],
+                        "description": "Who can search the note (default gm_only)"
+                    }
+                },
+                "required": ["content"]
+            })
+        },
+    }
+}
@@ -0,0 +1,40 @@
+//! Text-to-speech tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    registry.insert(speech_synthesize().name, speech_synthesize());
+}
+
+fn speech_synthesize() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::SpeechSynthesize,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Turn an answer or a read-aloud passage into spoken audio (WAV) with the configured text-to-speech server and deliver it to FVTT assets, so the GM can play it to the table. Give either text, or passage_id from read_aloud_get to speak an adventure's boxed text verbatim. Fails if no TTS server is configured.",
+        mcp_suffix: None,
+        category: "rendering",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "text": {
+                        "type": "string",
+                        "description": "Text to speak (markdown or HTML is reduced to plain text)"
+                    },
+                    "passage_id": {
+                        "type": "string",
+                        "description": "Read-aloud passage to speak (id from read_aloud_get)"
+                    }
+                }
+            })
+        },
+    }
+}
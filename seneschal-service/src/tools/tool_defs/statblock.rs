@@ -0,0 +1,122 @@
+//! Stat block and actor payload tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [statblock_search(), statblock_get(), fvtt_build_actor()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn statblock_search() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::StatblockSearch,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Search NPC and creature stat blocks extracted from documents by name or content. Returns IDs, names and source pages; use statblock_get for the full stat block.",
+        mcp_suffix: None,
+        category: "statblock",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Text matched against stat block names and contents (e.g. 'pirate', 'Pilot')"
+                    },
+                    "kind": {
+                        "type": "string",
+                        "enum": ["npc", "creature"],
+                        "description": "Only return NPCs or only creatures"
+                    },
+                    "document_id": {
+                        "type": "string",
+                        "description": "Only search stat blocks from this document"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum results (default 20)"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn statblock_get() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::StatblockGet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Get a stat block by ID with its parsed fields, source text, and an 'fvtt_actor' payload (see fvtt_build_actor).",
+        mcp_suffix: None,
+        category: "statblock",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "statblock_id": {
+                        "type": "string",
+                        "description": "Stat block ID from statblock_search"
+                    }
+                },
+                "required": ["statblock_id"]
+            })
+        },
+    }
+}
+
+fn fvtt_build_actor() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::FvttBuildActor,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Map a stat block to the mgt2e actor schema and return validated arguments for create_actor, plus warnings for anything dropped. Use this instead of guessing actor data with system_schema. Pass either a statblock_id or a stat_block object with name, kind (npc/creature), characteristics (STR/DEX/... or full names), skills, weapons, armour, equipment, and for creatures hits, speed, attacks and behaviour.",
+        mcp_suffix: None,
+        category: "statblock",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "statblock_id": {
+                        "type": "string",
+                        "description": "Stat block ID from statblock_search"
+                    },
+                    "stat_block": {
+                        "type": "object",
+                        "description": "Structured stat block, e.g. {\"name\": \"Thug\", \"kind\": \"npc\", \"characteristics\": {\"STR\": 9, ...}, \"skills\": [\"Melee (blade) 1\"], \"weapons\": [\"Dagger (1D+2)\"]}"
+                    },
+                    "actor_type": {
+                        "type": "string",
+                        "enum": ["traveller", "npc", "creature"],
+                        "description": "Actor type to create (defaults to the stat block's kind)"
+                    },
+                    "img": {
+                        "type": "string",
+                        "description": "Portrait path to include in the payload"
+                    },
+                    "folder": {
+                        "type": "string",
+                        "description": "Folder name or ID to include in the payload"
+                    },
+                    "pack_id": {
+                        "type": "string",
+                        "description": "Compendium pack ID to include in the payload"
+                    }
+                }
+            })
+        },
+    }
+}
@@ -0,0 +1,52 @@
+//! Subsector dossier tool definition.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [traveller_map_subsector_dossier()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn traveller_map_subsector_dossier() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerMapSubsectorDossier,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Build a GM dossier for a subsector: every world from the Traveller Map with its UWP decoded, trade codes, bases and travel zone, plus passages from indexed documents that name the world. Passages from adventure documents are listed as hooks. Returns structured JSON and formatted text.",
+        mcp_suffix: None,
+        category: "traveller_map",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "sector": {
+                        "type": "string",
+                        "description": "Sector name (e.g. 'Spinward Marches')"
+                    },
+                    "subsector": {
+                        "type": "string",
+                        "description": "Subsector letter (A-P) or name (e.g. 'C' or 'Regina')"
+                    },
+                    "max_mentions": {
+                        "type": "integer",
+                        "description": "Most library passages per world (default 3, max 10, 0 to skip the library)"
+                    },
+                    "tag": {
+                        "type": "string",
+                        "description": "Tag marking adventure documents (default 'adventure')"
+                    }
+                },
+                "required": ["sector", "subsector"]
+            })
+        },
+    }
+}
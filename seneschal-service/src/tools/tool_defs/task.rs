@@ -0,0 +1,93 @@
+//! Campaign task tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [task_add(), task_list(), task_complete()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn task_add() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TaskAdd,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Note a prep TODO or reminder for later (e.g. 'Stat up the Aslan patrol', 'Remind the players about the debt to Marc Hasting'). Open tasks are brought up at the start of the next conversation until marked done with task_complete. Use this when the GM asks to be reminded of something, or for prep work agreed on while planning.",
+        mcp_suffix: None,
+        category: "task",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "description": {
+                        "type": "string",
+                        "description": "What needs doing, as one self-contained sentence"
+                    }
+                },
+                "required": ["description"]
+            })
+        },
+    }
+}
+
+fn task_list() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TaskList,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "List the campaign's open prep tasks and reminders, oldest first.",
+        mcp_suffix: None,
+        category: "task",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "include_completed": {
+                        "type": "boolean",
+                        "description": "Also list tasks already done (default false)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum tasks (default 50)"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn task_complete() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TaskComplete,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Mark a prep task or reminder done so it stops being brought up. Only do this once the GM confirms it is done or no longer needed.",
+        mcp_suffix: None,
+        category: "task",
+        priority: 1,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "task_id": {
+                        "type": "string",
+                        "description": "ID of the task (from task_list or the server instructions)"
+                    }
+                },
+                "required": ["task_id"]
+            })
+        },
+    }
+}
@@ -0,0 +1,209 @@
+//! Campaign timeline tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [
+        timeline_query(),
+        timeline_add_event(),
+        timeline_extract(),
+        clock_get(),
+        clock_set(),
+        clock_advance(),
+    ];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn timeline_query() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TimelineQuery,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Query the campaign timeline: dated events extracted from documents and added by the GM, oldest first. Dates use the Imperial calendar, either a year ('1105') or day-year ('187-1105'). Use for questions like 'what happened in 1105 in the Spinward Marches' (from/to 1105, query 'Spinward Marches').",
+        mcp_suffix: None,
+        category: "timeline",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "from": {
+                        "type": "string",
+                        "description": "Earliest date, e.g. '1105' or '001-1105'"
+                    },
+                    "to": {
+                        "type": "string",
+                        "description": "Latest date; a bare year includes the whole year"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Text matched against event descriptions and locations (e.g. a world, sector or faction)"
+                    },
+                    "document_id": {
+                        "type": "string",
+                        "description": "Only events from this document"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum events (default 50)"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn timeline_add_event() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TimelineAddEvent,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Add an event to the campaign timeline, e.g. something the players did in a session. Only do this when the GM asks.",
+        mcp_suffix: None,
+        category: "timeline",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "date": {
+                        "type": "string",
+                        "description": "Imperial date: a year ('1105') or day-year ('187-1105')"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "What happened"
+                    },
+                    "location": {
+                        "type": "string",
+                        "description": "Where it happened (world, subsector or sector)"
+                    },
+                    "access_level": {
+                        "type": "string",
+                        "enum": ["player", "trusted", "assistant", "gm_only"],
+                        "description": "Who may see the event (default gm_only)"
+                    }
+                },
+                "required": ["date", "description"]
+            })
+        },
+    }
+}
+
+fn timeline_extract() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TimelineExtract,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Extract dated events from a document into the campaign timeline, replacing events extracted from it before (events added by hand are kept). Documents are not scanned automatically; run this for setting histories and scenario backgrounds.",
+        mcp_suffix: None,
+        category: "timeline",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "document_id": {
+                        "type": "string",
+                        "description": "The document ID (get from document_list or document_find)"
+                    }
+                },
+                "required": ["document_id"]
+            })
+        },
+    }
+}
+
+fn clock_get() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ClockGet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Get the campaign's current Imperial date. Check it before narrating anything that depends on when it is.",
+        mcp_suffix: None,
+        category: "timeline",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {}
+            })
+        },
+    }
+}
+
+fn clock_set() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ClockSet,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Set the campaign's current Imperial date. Only do this when the GM asks; use clock_advance as time passes.",
+        mcp_suffix: None,
+        category: "timeline",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "date": {
+                        "type": "string",
+                        "description": "Imperial date, day-year ('187-1105'); a bare year starts on day 001"
+                    }
+                },
+                "required": ["date"]
+            })
+        },
+    }
+}
+
+fn clock_advance() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ClockAdvance,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Advance the campaign's Imperial date as time passes. Jumps are rolled as 148 + 6D hours each (about a week); add days and weeks for travel in normal space, downtime and the like. An optional note is recorded on the timeline at the new date.",
+        mcp_suffix: None,
+        category: "timeline",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "jumps": {
+                        "type": "integer",
+                        "description": "Number of jumps made"
+                    },
+                    "days": {
+                        "type": "integer",
+                        "description": "Days passed"
+                    },
+                    "weeks": {
+                        "type": "integer",
+                        "description": "Weeks passed"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "What happened, added to the timeline (e.g. 'Jumped from Regina to Efate')"
+                    },
+                    "seed": {
+                        "type": "integer",
+                        "description": "Seed for the jump duration rolls"
+                    }
+                }
+            })
+        },
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use crate::tools::{
     ToolLocation,
     registry::{ToolMetadata, ToolName},
+    traveller_chargen::CAREERS,
 };
 
 pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
@@ -12,6 +13,9 @@ pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
         traveller_uwp_parse(),
         traveller_jump_calc(),
         traveller_skill_lookup(),
+        traveller_chargen(),
+        traveller_ship_design(),
+        name_generate(),
     ];
     for tool in tools {
         registry.insert(tool.name, tool);
@@ -27,6 +31,7 @@ fn traveller_uwp_parse() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -51,6 +56,7 @@ fn traveller_jump_calc() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -83,6 +89,7 @@ fn traveller_skill_lookup() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -101,3 +108,215 @@ fn traveller_skill_lookup() -> ToolMetadata {
         },
     }
 }
+
+fn traveller_chargen() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerChargen,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Generate a Mongoose Traveller 2e character term by term. Rolls are replayed from the seed, so send the same seed and all previous steps with one new step appended each call. Returns characteristics, skills, rolls and events to narrate, the allowed next steps, and a stat block for fvtt_build_actor.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            let careers: Vec<String> = CAREERS
+                .iter()
+                .map(|c| {
+                    let assignments: Vec<&str> = c.assignments.iter().map(|a| a.name).collect();
+                    format!("{} ({})", c.name, assignments.join(", "))
+                })
+                .collect();
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "seed": {
+                        "type": "integer",
+                        "description": "Dice seed; omit on the first call and reuse the returned one"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Character name"
+                    },
+                    "background_skills": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Background skills at level 0 (3 + EDU DM of them)"
+                    },
+                    "steps": {
+                        "type": "array",
+                        "description": "Every step taken so far, in order",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "action": {
+                                    "type": "string",
+                                    "enum": ["term", "muster_out"]
+                                },
+                                "career": {
+                                    "type": "string",
+                                    "description": format!("Career for a term: {}", careers.join("; "))
+                                },
+                                "assignment": {
+                                    "type": "string",
+                                    "description": "Assignment when entering a career (defaults to the first)"
+                                },
+                                "skill_table": {
+                                    "type": "string",
+                                    "enum": ["personal_development", "service_skills", "advanced_education", "officer", "assignment"],
+                                    "description": "Table for this term's skill roll (default service_skills)"
+                                },
+                                "commission": {
+                                    "type": "boolean",
+                                    "description": "Try for a commission instead of advancement"
+                                },
+                                "cash_rolls": {
+                                    "type": "integer",
+                                    "description": "Benefit rolls to take as cash when mustering out (max 3 per character)"
+                                }
+                            },
+                            "required": ["action"]
+                        }
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn traveller_ship_design() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerShipDesign,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Design or validate a Mongoose Traveller 2e starship or small craft (High Guard basics). Give the hull tonnage and component choices; omitted power plant, fuel and computer are sized automatically. Returns the design sheet with tonnage, power, fuel, hardpoint and cost budgets, and lists every rule an illegal build breaks.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "tonnage": {
+                        "type": "integer",
+                        "description": "Hull displacement tons (jump drives need 100+)"
+                    },
+                    "tech_level": {
+                        "type": "integer",
+                        "description": "Tech level of the design (default 12)"
+                    },
+                    "configuration": {
+                        "type": "string",
+                        "enum": ["standard", "streamlined", "sphere", "close", "dispersed"]
+                    },
+                    "hull_options": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": ["reinforced", "light"] }
+                    },
+                    "armour": {
+                        "type": "object",
+                        "properties": {
+                            "type": {
+                                "type": "string",
+                                "enum": ["titanium_steel", "crystaliron", "bonded_superdense"]
+                            },
+                            "points": { "type": "integer" }
+                        },
+                        "required": ["type", "points"]
+                    },
+                    "thrust": { "type": "integer", "description": "Manoeuvre drive thrust" },
+                    "jump": { "type": "integer", "description": "Jump drive rating" },
+                    "power_plant": {
+                        "type": "string",
+                        "enum": ["fission", "fusion_tl8", "fusion_tl12", "fusion_tl15"]
+                    },
+                    "power_plant_tons": { "type": "number" },
+                    "fuel_tons": { "type": "number" },
+                    "computer": {
+                        "type": "integer",
+                        "description": "Computer rating (5, 10, 15, 20, 25, 30 or 35)"
+                    },
+                    "sensors": {
+                        "type": "string",
+                        "enum": ["basic", "civilian", "military", "improved", "advanced"]
+                    },
+                    "staterooms": { "type": "integer" },
+                    "low_berths": { "type": "integer" },
+                    "fuel_processor_tons": { "type": "number" },
+                    "turrets": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "mount": { "type": "string", "enum": ["single", "double", "triple"] },
+                                "weapons": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "string",
+                                        "enum": ["sandcaster", "missile_rack", "pulse_laser", "beam_laser", "particle_beam"]
+                                    }
+                                }
+                            },
+                            "required": ["mount", "weapons"]
+                        }
+                    },
+                    "extras": {
+                        "type": "array",
+                        "description": "Other components from the rulebook, with their tons, cost and power",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "tons": { "type": "number" },
+                                "cost_mcr": { "type": "number" },
+                                "power": { "type": "number" }
+                            },
+                            "required": ["name"]
+                        }
+                    }
+                },
+                "required": ["tonnage"]
+            })
+        },
+    }
+}
+
+fn name_generate() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::NameGenerate,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Generate names for people, ships, corporations or worlds in the style of a Traveller culture (Vilani, Solomani, Aslan, Vargr). Use this instead of inventing names, so they fit the culture; the same seed gives the same names.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "kind": {
+                        "type": "string",
+                        "enum": ["person", "ship", "corporation", "world"],
+                        "description": "What to name (default person)"
+                    },
+                    "culture": {
+                        "type": "string",
+                        "enum": ["vilani", "solomani", "aslan", "vargr"],
+                        "description": "Naming culture (default solomani)"
+                    },
+                    "count": {
+                        "type": "integer",
+                        "description": "How many names (default 5, max 50)"
+                    },
+                    "seed": {
+                        "type": "integer",
+                        "description": "Seed to reproduce a previous list"
+                    }
+                }
+            })
+        },
+    }
+}
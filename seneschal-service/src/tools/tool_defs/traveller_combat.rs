@@ -0,0 +1,152 @@
+//! Traveller combat math tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [
+        traveller_attack(),
+        traveller_damage(),
+        traveller_opposed_check(),
+    ];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn seed_property() -> serde_json::Value {
+    serde_json::json!({
+        "type": "integer",
+        "description": "Dice seed to replay a result; omit for fresh dice (the seed used is returned)"
+    })
+}
+
+fn traveller_attack() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerAttack,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Roll a Mongoose Traveller 2e attack: 2D + DMs from skill, characteristic, range, cover, aiming and dodging against 8+, then damage after armour and AP on a hit. Returns the dice, each DM, effect and damage so you don't have to do the arithmetic.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "seed": seed_property(),
+                    "skill": {
+                        "type": "integer",
+                        "description": "Attacker's combat skill level; omit if untrained (DM-3)"
+                    },
+                    "characteristic_dm": {
+                        "type": "integer",
+                        "description": "DM of the characteristic used (DEX for ranged, STR or DEX for melee)"
+                    },
+                    "range_band": {
+                        "type": "string",
+                        "enum": ["melee", "short", "medium", "long", "extreme"],
+                        "description": "Range band; or give distance_m and weapon_range_m instead"
+                    },
+                    "distance_m": { "type": "number" },
+                    "weapon_range_m": { "type": "number" },
+                    "cover": { "type": "boolean", "description": "Target is in cover (DM-2)" },
+                    "target_prone": { "type": "boolean", "description": "Target is prone (DM-1 at range)" },
+                    "aim_rounds": { "type": "integer", "description": "Rounds spent aiming (DM+1 each, max +6)" },
+                    "laser_sight": { "type": "boolean", "description": "Laser sight, DM+1 when aiming" },
+                    "dodge": { "type": "integer", "description": "Target's dodge DM, subtracted from the attack" },
+                    "other_dms": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "reason": { "type": "string" },
+                                "dm": { "type": "integer" }
+                            },
+                            "required": ["reason", "dm"]
+                        }
+                    },
+                    "damage": { "type": "string", "description": "Weapon damage such as 3D or 2D+3" },
+                    "auto": { "type": "integer", "description": "Weapon's Auto rating" },
+                    "fire_mode": { "type": "string", "enum": ["single", "burst", "full_auto"] },
+                    "armour": { "type": "integer", "description": "Target's armour" },
+                    "ap": { "type": "integer", "description": "Weapon's AP rating" }
+                }
+            })
+        },
+    }
+}
+
+fn traveller_damage() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerDamage,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Roll Mongoose Traveller 2e damage, add the attack's effect, subtract armour after AP, and apply it to a target's END, then STR, then DEX. Reports the new characteristics and whether the target is unconscious or dead.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "seed": seed_property(),
+                    "damage": { "type": "string", "description": "Damage dice such as 3D or 2D+3" },
+                    "effect": { "type": "integer", "description": "Effect of the attack roll" },
+                    "armour": { "type": "integer" },
+                    "ap": { "type": "integer" },
+                    "characteristics": {
+                        "type": "object",
+                        "description": "Target's current physical characteristics",
+                        "properties": {
+                            "STR": { "type": "integer" },
+                            "DEX": { "type": "integer" },
+                            "END": { "type": "integer" }
+                        },
+                        "required": ["STR", "DEX", "END"]
+                    },
+                    "stun": { "type": "boolean", "description": "Stun damage only reduces END" }
+                },
+                "required": ["damage"]
+            })
+        },
+    }
+}
+
+fn traveller_opposed_check() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerOpposedCheck,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Resolve a Mongoose Traveller 2e opposed check: both sides roll 2D + DM and the higher effect wins. Returns both rolls, the winner and the margin.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            let side = serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "label": { "type": "string", "description": "Who is rolling" },
+                    "dm": { "type": "integer", "description": "Total DM (skill + characteristic + situational)" }
+                },
+                "required": ["label"]
+            });
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "seed": seed_property(),
+                    "first": side,
+                    "second": side
+                },
+                "required": ["first", "second"]
+            })
+        },
+    }
+}
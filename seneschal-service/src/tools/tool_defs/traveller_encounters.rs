@@ -0,0 +1,63 @@
+//! Animal encounter table tool definition.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [traveller_encounter_tables()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn traveller_encounter_tables() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerEncounterTables,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Get animal encounter tables for a world, one per terrain its UWP allows (atmosphere, hydrographics and size), per Mongoose Traveller 2e. Tables are generated once and saved, so a world keeps the same animals. Roll a table's formula with dice_roll and read the entry with that roll; roll the entry's quantity the same way.",
+        mcp_suffix: None,
+        category: "traveller",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "world": {
+                        "type": "string",
+                        "description": "World name (e.g. 'Regina'); defaults to the name on the Traveller Map when sector and hex are given"
+                    },
+                    "uwp": {
+                        "type": "string",
+                        "description": "Universal World Profile (e.g. 'A788899-C'); looked up on the Traveller Map when omitted"
+                    },
+                    "sector": {
+                        "type": "string",
+                        "description": "Sector to look the world up in when no UWP is given"
+                    },
+                    "hex": {
+                        "type": "string",
+                        "description": "Hex (XXYY) to look the world up at when no UWP is given"
+                    },
+                    "terrain": {
+                        "type": "string",
+                        "description": "Only return the table for this terrain (e.g. 'forest', 'ocean_shallows')"
+                    },
+                    "regenerate": {
+                        "type": "boolean",
+                        "description": "Replace the saved tables with new ones"
+                    },
+                    "seed": {
+                        "type": "integer",
+                        "description": "Seed to generate (and save) specific tables"
+                    }
+                }
+            })
+        },
+    }
+}
@@ -12,6 +12,7 @@ pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
         traveller_map_search(),
         traveller_map_jump_worlds(),
         traveller_map_route(),
+        traveller_map_plan_route(),
         traveller_map_world_data(),
         traveller_map_sector_data(),
         traveller_map_coordinates(),
@@ -20,6 +21,8 @@ pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
         traveller_map_jump_map_url(),
         traveller_map_save_poster(),
         traveller_map_save_jump_map(),
+        traveller_map_overlay(),
+        traveller_map_save_overlay(),
     ];
     for tool in tools {
         registry.insert(tool.name, tool);
@@ -35,6 +38,7 @@ fn traveller_map_search() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 1, // Frequently used for world lookup
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -63,6 +67,7 @@ fn traveller_map_jump_worlds() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -95,6 +100,7 @@ fn traveller_map_route() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -130,6 +136,63 @@ fn traveller_map_route() -> ToolMetadata {
     }
 }
 
+fn traveller_map_plan_route() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerMapPlanRoute,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Plan a jump route for a specific ship. Returns each leg with parsecs, jump fuel, where the ship must refuel and what fuel is available there (worlds without refined fuel are flagged), travel days including transit to and from the 100-diameter limit, warnings, and a Markdown table ready to present.",
+        mcp_suffix: None,
+        category: "traveller_map",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "start": {
+                        "type": "string",
+                        "description": "Starting location (e.g., 'Spinward Marches 1910' or 'Regina')"
+                    },
+                    "end": {
+                        "type": "string",
+                        "description": "Destination location (e.g., 'Spinward Marches 2118' or 'Efate')"
+                    },
+                    "jump": {
+                        "type": "integer",
+                        "description": "Ship's jump rating in parsecs (default: 2)"
+                    },
+                    "tonnage": {
+                        "type": "integer",
+                        "description": "Ship's hull tonnage, used for jump fuel (10% of hull per parsec)"
+                    },
+                    "thrust": {
+                        "type": "integer",
+                        "description": "Manoeuvre drive rating in G, used for 100D transit time (default: 1)"
+                    },
+                    "fuel_capacity": {
+                        "type": "number",
+                        "description": "Jump fuel tankage in tons (default: enough for one jump at the full rating)"
+                    },
+                    "wild": {
+                        "type": "boolean",
+                        "description": "If true, require wilderness refueling capability (unrefined fuel)"
+                    },
+                    "imperium_only": {
+                        "type": "boolean",
+                        "description": "If true, restrict route to Third Imperium member worlds"
+                    },
+                    "no_red_zones": {
+                        "type": "boolean",
+                        "description": "If true, avoid TAS Red Zone systems"
+                    }
+                },
+                "required": ["start", "end", "tonnage"]
+            })
+        },
+    }
+}
+
 fn traveller_map_world_data() -> ToolMetadata {
     ToolMetadata {
         name: ToolName::TravellerMapWorldData,
@@ -139,6 +202,7 @@ fn traveller_map_world_data() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 1, // Frequently used for world details
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -167,6 +231,7 @@ fn traveller_map_sector_data() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -195,6 +260,7 @@ fn traveller_map_coordinates() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -223,6 +289,7 @@ fn traveller_map_list_sectors() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -246,6 +313,7 @@ fn traveller_map_poster_url() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -279,6 +347,7 @@ fn traveller_map_jump_map_url() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -316,6 +385,7 @@ fn traveller_map_save_poster() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -353,10 +423,11 @@ fn traveller_map_save_jump_map() -> ToolMetadata {
         name: ToolName::TravellerMapSaveJumpMap,
         location: ToolLocation::Internal,
         mcp_enabled: true,
-        description: "Download a jump range map centered on a world and save it to FVTT assets. Returns the FVTT path for use in journal entries, scenes, etc.",
+        description: "Download a jump range map centered on a world and save it to FVTT assets. Returns the FVTT path for use in journal entries, and scene data (image dimensions, hex grid sized to one parsec per hex) ready for create_scene. Set create_scene to create the scene directly.",
         mcp_suffix: None,
         category: "traveller_map",
         priority: 2,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -385,6 +456,18 @@ fn traveller_map_save_jump_map() -> ToolMetadata {
                     "target_path": {
                         "type": "string",
                         "description": "Optional: custom path relative to assets directory"
+                    },
+                    "create_scene": {
+                        "type": "boolean",
+                        "description": "If true, also create an FVTT scene with the map as its background (default: false)"
+                    },
+                    "scene_name": {
+                        "type": "string",
+                        "description": "Scene name (default: '<sector> <hex> Jump-<n>')"
+                    },
+                    "scene_folder": {
+                        "type": "string",
+                        "description": "Folder name or ID to place the scene in"
                     }
                 },
                 "required": ["sector", "hex", "jump"]
@@ -392,3 +475,118 @@ fn traveller_map_save_jump_map() -> ToolMetadata {
         },
     }
 }
+
+/// Overlay properties shared by the overlay tools
+fn overlay_properties() -> serde_json::Value {
+    serde_json::json!({
+        "sector": {
+            "type": "string",
+            "description": "Sector name (e.g., 'Spinward Marches')"
+        },
+        "routes": {
+            "type": "array",
+            "description": "Routes traveled, each the hexes jumped between in order",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "hexes": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Hexes in XXYY format, at least two"
+                    },
+                    "color": {
+                        "type": "string",
+                        "description": "Route color (e.g. '#ffcc00', the default)"
+                    }
+                },
+                "required": ["hexes"]
+            }
+        },
+        "party_hex": {
+            "type": "string",
+            "description": "Hex the party is in (XXYY), shown with a marker"
+        },
+        "party_label": {
+            "type": "string",
+            "description": "Label for the party marker (default: 'Party')"
+        },
+        "territories": {
+            "type": "array",
+            "description": "Named groups of hexes to highlight, e.g. a patron's holdings or a war zone",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "hexes": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Hexes in XXYY format"
+                    },
+                    "color": {
+                        "type": "string",
+                        "description": "Highlight color (e.g. '#3399ff', the default)"
+                    }
+                },
+                "required": ["name", "hexes"]
+            }
+        },
+        "subsector": {
+            "type": "string",
+            "description": "Optional subsector (A-P letter or name) to limit the poster to"
+        },
+        "style": {
+            "type": "string",
+            "enum": ["poster", "print", "atlas", "candy", "draft", "fasa", "terminal", "mongoose"],
+            "description": "Visual style for the map"
+        }
+    })
+}
+
+fn traveller_map_overlay() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerMapOverlay,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Build a Traveller Map overlay from campaign data: routes traveled, the party's location and territory highlights within one sector. Returns an interactive map URL with the party marked, and the poster API parameters (sector metadata XML) that draw the full overlay. Use traveller_map_save_overlay to render and save it.",
+        mcp_suffix: None,
+        category: "traveller_map",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": overlay_properties(),
+                "required": ["sector"]
+            })
+        },
+    }
+}
+
+fn traveller_map_save_overlay() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerMapSaveOverlay,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Render a Traveller Map poster of a sector or subsector with a campaign overlay (routes traveled, party location, territory highlights) drawn on it, and save it to FVTT assets. Returns the FVTT path.",
+        mcp_suffix: None,
+        category: "traveller_map",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            let mut properties = overlay_properties();
+            properties["scale"] = serde_json::json!({
+                "type": "integer",
+                "description": "Pixels per parsec (default: 64, higher = larger file)"
+            });
+            properties["target_path"] = serde_json::json!({
+                "type": "string",
+                "description": "Optional: custom path relative to assets directory"
+            });
+            serde_json::json!({
+                "type": "object",
+                "properties": properties,
+                "required": ["sector"]
+            })
+        },
+    }
+}
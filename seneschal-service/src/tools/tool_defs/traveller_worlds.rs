@@ -30,6 +30,7 @@ fn traveller_worlds_canon_url() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_worlds",
         priority: 3, // Specialized tool
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -58,6 +59,7 @@ fn traveller_worlds_canon_save() -> ToolMetadata {
         mcp_suffix: Some("Requires geckodriver running."),
         category: "traveller_worlds",
         priority: 3,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -90,6 +92,7 @@ fn traveller_worlds_custom_url() -> ToolMetadata {
         mcp_suffix: None,
         category: "traveller_worlds",
         priority: 3,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
@@ -151,6 +154,7 @@ fn traveller_worlds_custom_save() -> ToolMetadata {
         mcp_suffix: Some("Requires geckodriver running."),
         category: "traveller_worlds",
         priority: 3,
+        result_schema: None,
         parameters: || {
             serde_json::json!({
                 "type": "object",
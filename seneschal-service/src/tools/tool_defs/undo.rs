@@ -0,0 +1,102 @@
+//! Undo log tool definitions.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [
+        list_recent_changes(),
+        undo_last_change(),
+        fvtt_undo_change(),
+    ];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn list_recent_changes() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::ListRecentChanges,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "List recent changes tools made to FVTT world documents (actors, items, scenes, journals, rollable tables), most recent first, with their change IDs. Embedded items and journal pages are listed as updates to their actor or journal.",
+        mcp_suffix: None,
+        category: "undo",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum changes (default 10)"
+                    },
+                    "include_undone": {
+                        "type": "boolean",
+                        "description": "Also list changes that were already undone (default false)"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn undo_last_change() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::UndoLastChange,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Revert a change a tool made to an FVTT world document: created documents are deleted, updated ones restored and deleted ones recreated. Without change_id, reverts the most recent change. Only the latest change to a document can be undone. Use when the GM asks to take back an edit.",
+        mcp_suffix: Some("Requires GM WebSocket connection."),
+        category: "undo",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "change_id": {
+                        "type": "string",
+                        "description": "ID of the change to revert (from list_recent_changes)"
+                    }
+                }
+            })
+        },
+    }
+}
+
+fn fvtt_undo_change() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::FvttUndoChange,
+        location: ToolLocation::External,
+        mcp_enabled: false,
+        description: "Revert a logged change to a world document in the FVTT client.",
+        mcp_suffix: None,
+        category: "undo",
+        priority: 3,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "document_type": { "type": "string" },
+                    "document_id": { "type": "string" },
+                    "operation": {
+                        "type": "string",
+                        "enum": ["create", "update", "delete"]
+                    },
+                    "before": {
+                        "type": "object",
+                        "description": "The document's data before the change"
+                    }
+                },
+                "required": ["document_type", "document_id", "operation"]
+            })
+        },
+    }
+}
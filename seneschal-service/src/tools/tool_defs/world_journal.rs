@@ -0,0 +1,72 @@
+//! World journal tool definition.
+
+use std::collections::HashMap;
+
+use crate::tools::{
+    ToolLocation,
+    registry::{ToolMetadata, ToolName},
+};
+
+pub fn register(registry: &mut HashMap<ToolName, ToolMetadata>) {
+    let tools = [traveller_map_world_journal()];
+    for tool in tools {
+        registry.insert(tool.name, tool);
+    }
+}
+
+fn traveller_map_world_journal() -> ToolMetadata {
+    ToolMetadata {
+        name: ToolName::TravellerMapWorldJournal,
+        location: ToolLocation::Internal,
+        mcp_enabled: true,
+        description: "Create an FVTT journal for a world with Overview, Starport, Notable Locations and Patrons pages. Facts come from the Traveller Map, passages naming the world from indexed documents, and prose from the model, kept consistent with both. Library images of the world are delivered and added as image pages. With create=false the journal is returned without being created.",
+        mcp_suffix: Some("Requires GM WebSocket connection to create the journal."),
+        category: "traveller_map",
+        priority: 2,
+        result_schema: None,
+        parameters: || {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "sector": {
+                        "type": "string",
+                        "description": "Sector name (e.g. 'Spinward Marches')"
+                    },
+                    "hex": {
+                        "type": "string",
+                        "description": "Hex location (e.g. '1910')"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Journal name (default: the world's name)"
+                    },
+                    "folder": {
+                        "type": "string",
+                        "description": "Folder ID or name for the journal"
+                    },
+                    "max_mentions": {
+                        "type": "integer",
+                        "description": "Most library passages to use (default 3, max 10, 0 to skip the library)"
+                    },
+                    "tag": {
+                        "type": "string",
+                        "description": "Tag marking adventure documents, whose passages become patron hooks (default 'adventure')"
+                    },
+                    "generate": {
+                        "type": "boolean",
+                        "description": "Write prose with the model (default true); false builds the journal from data and passages only"
+                    },
+                    "images": {
+                        "type": "integer",
+                        "description": "Library images to add (default 2, max 4)"
+                    },
+                    "create": {
+                        "type": "boolean",
+                        "description": "Create the journal in FVTT (default true)"
+                    }
+                },
+                "required": ["sector", "hex"]
+            })
+        },
+    }
+}
@@ -72,6 +72,12 @@ impl TravellerTool {
 
 /// Parse a UWP string into structured data
 fn parse_uwp(uwp: &str) -> Result<serde_json::Value, String> {
+    let parsed = decode_uwp(uwp)?;
+    serde_json::to_value(parsed).map_err(|e| e.to_string())
+}
+
+/// Decode a UWP string with descriptions of each code
+pub(crate) fn decode_uwp(uwp: &str) -> Result<ParsedUwp, String> {
     let uwp = uwp.trim().to_uppercase();
 
     // UWP format: Starport-Size-Atmo-Hydro-Pop-Gov-Law-TL (e.g., A867949-C)
@@ -108,7 +114,7 @@ fn parse_uwp(uwp: &str) -> Result<serde_json::Value, String> {
         0
     };
 
-    let parsed = ParsedUwp {
+    Ok(ParsedUwp {
         raw: uwp,
         starport,
         starport_quality: starport_quality(starport),
@@ -125,9 +131,7 @@ fn parse_uwp(uwp: &str) -> Result<serde_json::Value, String> {
         law_level,
         law_description: law_description(law_level),
         tech_level,
-    };
-
-    serde_json::to_value(parsed).map_err(|e| e.to_string())
+    })
 }
 
 fn parse_hex_digit(c: char) -> Option<u8> {
@@ -248,6 +252,17 @@ fn law_description(law: u8) -> String {
 }
 
 /// Calculate jump fuel and time requirements
+/// Jump time is approximately 1 week (168 hours) regardless of distance
+pub(crate) const JUMP_TIME_HOURS: u32 = 168;
+
+/// Jump fuel for a jump of `distance` parsecs.
+///
+/// Jump fuel consumption: 10% of hull tonnage per parsec jumped (simplified).
+/// Actual formula may vary by edition.
+pub(crate) fn jump_fuel_tons(tonnage: u32, distance: u8) -> f64 {
+    tonnage as f64 * 0.1 * distance as f64
+}
+
 fn calculate_jump(
     distance: u8,
     jump_rating: u8,
@@ -264,13 +279,8 @@ fn calculate_jump(
         ));
     }
 
-    // Jump fuel consumption: 10% of hull tonnage per parsec jumped (simplified)
-    // Actual formula may vary by edition
-    let fuel_per_parsec = tonnage as f64 * 0.1;
-    let total_fuel = fuel_per_parsec * distance as f64;
-
-    // Jump time is approximately 1 week (168 hours) regardless of distance
-    let jump_time_hours = 168;
+    let total_fuel = jump_fuel_tons(tonnage, distance);
+    let jump_time_hours = JUMP_TIME_HOURS;
 
     let result = serde_json::json!({
         "distance_parsecs": distance,
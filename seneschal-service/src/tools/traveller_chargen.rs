@@ -0,0 +1,836 @@
+//! Mongoose Traveller 2nd Edition lifepath character generation.
+//!
+//! Generation is replayed from a seed and the list of choices made so far,
+//! so every call is deterministic and stateless: the LLM sends the choices
+//! back with one more appended and narrates what the new rolls produced.
+//! The result includes a stat block for `fvtt_build_actor`.
+
+mod careers;
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub use careers::CAREERS;
+use careers::{Benefit, Career, Characteristic, EVENTS, Entry, EventEffect, RankBonus};
+
+/// Age at the start of the first term
+const STARTING_AGE: u32 = 18;
+
+/// Years per career term
+const TERM_YEARS: u32 = 4;
+
+/// Aging rolls start once a character reaches this age
+const AGING_STARTS_AT: u32 = 34;
+
+/// Cash rolls allowed across all careers
+const MAX_CASH_ROLLS: u32 = 3;
+
+/// Skills that can be picked as background skills, at level 0
+const BACKGROUND_SKILLS: [&str; 17] = [
+    "Admin",
+    "Animals",
+    "Art",
+    "Athletics",
+    "Carouse",
+    "Drive",
+    "Electronics",
+    "Flyer",
+    "Language",
+    "Mechanic",
+    "Medic",
+    "Profession",
+    "Science",
+    "Seafarer",
+    "Streetwise",
+    "Survival",
+    "Vacc Suit",
+];
+
+/// Choices made so far, replayed in order
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChargenRequest {
+    /// Seed for the dice; a random one is picked (and returned) if omitted
+    pub seed: Option<u64>,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub background_skills: Vec<String>,
+    #[serde(default)]
+    pub steps: Vec<ChargenStep>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ChargenStep {
+    /// Serve a term, entering the career first if not already in it
+    Term {
+        career: String,
+        assignment: Option<String>,
+        /// personal_development, service_skills, advanced_education,
+        /// officer or assignment
+        #[serde(default = "default_skill_table")]
+        skill_table: String,
+        /// Try for a commission instead of advancement this term
+        #[serde(default)]
+        commission: bool,
+    },
+    /// Leave the current career and roll its benefits
+    MusterOut {
+        /// Benefit rolls to take on the cash column
+        #[serde(default)]
+        cash_rolls: u32,
+    },
+}
+
+fn default_skill_table() -> String {
+    "service_skills".to_string()
+}
+
+/// A single 2D or 1D roll, recorded for narration
+#[derive(Debug, Clone, Serialize)]
+pub struct Roll {
+    pub purpose: String,
+    pub dice: Vec<u8>,
+    pub dm: i32,
+    pub total: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+/// What happened during one step
+#[derive(Debug, Clone, Serialize)]
+pub struct StepRecord {
+    pub step: usize,
+    pub summary: String,
+    pub rolls: Vec<Roll>,
+    pub events: Vec<String>,
+}
+
+/// A career the character has served in
+#[derive(Debug, Clone, Serialize)]
+pub struct CareerRecord {
+    pub career: String,
+    pub assignment: String,
+    pub terms: u32,
+    pub rank: u8,
+    pub rank_title: String,
+    pub officer: bool,
+    /// Terms that earned a benefit roll
+    pub benefit_terms: u32,
+    /// Why the character must leave, if they can't serve another term
+    pub must_leave: Option<String>,
+    pub mustered_out: bool,
+}
+
+/// Character state after replaying every step
+#[derive(Debug, Clone, Serialize)]
+pub struct ChargenState {
+    pub seed: u64,
+    pub name: Option<String>,
+    pub characteristics: BTreeMap<Characteristic, i32>,
+    pub age: u32,
+    pub terms: u32,
+    pub skills: BTreeMap<String, u8>,
+    pub careers: Vec<CareerRecord>,
+    pub cash: u32,
+    pub cash_rolls_used: u32,
+    pub benefits: Vec<String>,
+    pub history: Vec<StepRecord>,
+    /// What the next step can be
+    pub next: Vec<String>,
+}
+
+struct Dice(StdRng);
+
+impl Dice {
+    fn d6(&mut self) -> u8 {
+        self.0.gen_range(1..=6)
+    }
+
+    fn roll(&mut self, count: usize, purpose: &str, dm: i32, target: Option<i32>) -> Roll {
+        let dice: Vec<u8> = (0..count).map(|_| self.d6()).collect();
+        let total = dice.iter().map(|&d| d as i32).sum::<i32>() + dm;
+        Roll {
+            purpose: purpose.to_string(),
+            dice,
+            dm,
+            total,
+            target,
+            success: target.map(|t| total >= t),
+        }
+    }
+}
+
+/// Dice modifier for a characteristic value
+pub fn characteristic_dm(value: i32) -> i32 {
+    match value {
+        i32::MIN..=0 => -3,
+        1..=2 => -2,
+        3..=5 => -1,
+        6..=8 => 0,
+        9..=11 => 1,
+        12..=14 => 2,
+        _ => 3,
+    }
+}
+
+/// Replay a character's choices and return the resulting state
+pub fn generate(request: &ChargenRequest) -> Result<ChargenState, String> {
+    let seed = request.seed.unwrap_or_else(rand::random);
+    let mut dice = Dice(StdRng::seed_from_u64(seed));
+
+    let mut state = ChargenState {
+        seed,
+        name: request.name.clone(),
+        characteristics: BTreeMap::new(),
+        age: STARTING_AGE,
+        terms: 0,
+        skills: BTreeMap::new(),
+        careers: Vec::new(),
+        cash: 0,
+        cash_rolls_used: 0,
+        benefits: Vec::new(),
+        history: Vec::new(),
+        next: Vec::new(),
+    };
+
+    let mut rolls = Vec::new();
+    for characteristic in Characteristic::ALL {
+        let roll = dice.roll(2, characteristic.abbreviation(), 0, None);
+        state.characteristics.insert(characteristic, roll.total);
+        rolls.push(roll);
+    }
+    state.apply_background_skills(&request.background_skills)?;
+    state.history.push(StepRecord {
+        step: 0,
+        summary: "Rolled characteristics".to_string(),
+        rolls,
+        events: Vec::new(),
+    });
+
+    for (index, step) in request.steps.iter().enumerate() {
+        let record = match step {
+            ChargenStep::Term {
+                career,
+                assignment,
+                skill_table,
+                commission,
+            } => state.serve_term(
+                &mut dice,
+                career,
+                assignment.as_deref(),
+                skill_table,
+                *commission,
+            ),
+            ChargenStep::MusterOut { cash_rolls } => state.muster_out(&mut dice, *cash_rolls),
+        }
+        .map_err(|e| format!("Step {}: {}", index + 1, e))?;
+        state.history.push(StepRecord {
+            step: index + 1,
+            ..record
+        });
+    }
+
+    state.next = state.next_steps();
+    Ok(state)
+}
+
+impl ChargenState {
+    fn dm(&self, characteristic: Characteristic) -> i32 {
+        characteristic_dm(self.characteristics[&characteristic])
+    }
+
+    fn apply_background_skills(&mut self, skills: &[String]) -> Result<(), String> {
+        let allowed = (3 + self.dm(Characteristic::Edu)).max(0) as usize;
+        if skills.len() > allowed {
+            return Err(format!(
+                "{} background skills chosen but EDU allows {}",
+                skills.len(),
+                allowed
+            ));
+        }
+        for skill in skills {
+            let known = BACKGROUND_SKILLS
+                .iter()
+                .find(|s| s.eq_ignore_ascii_case(skill))
+                .ok_or_else(|| {
+                    format!(
+                        "'{}' is not a background skill; choose from {}",
+                        skill,
+                        BACKGROUND_SKILLS.join(", ")
+                    )
+                })?;
+            self.skills.entry(known.to_string()).or_insert(0);
+        }
+        Ok(())
+    }
+
+    fn current_career(&self) -> Option<usize> {
+        self.careers.iter().position(|c| !c.mustered_out)
+    }
+
+    fn serve_term(
+        &mut self,
+        dice: &mut Dice,
+        career_name: &str,
+        assignment_name: Option<&str>,
+        skill_table: &str,
+        try_commission: bool,
+    ) -> Result<StepRecord, String> {
+        let mut rolls = Vec::new();
+        let mut events = Vec::new();
+
+        let index = match self.current_career() {
+            Some(index) => {
+                let current = &self.careers[index];
+                if !current.career.eq_ignore_ascii_case(career_name) {
+                    return Err(format!(
+                        "Still in the {} career; muster out first",
+                        current.career
+                    ));
+                }
+                if let Some(reason) = &current.must_leave {
+                    return Err(format!(
+                        "{} must leave the {} career ({}); muster out",
+                        self.name.as_deref().unwrap_or("The character"),
+                        current.career,
+                        reason
+                    ));
+                }
+                index
+            }
+            None => {
+                self.enter_career(dice, career_name, assignment_name, &mut rolls, &mut events)?
+            }
+        };
+
+        let career = careers::career(&self.careers[index].career).expect("career exists");
+        let assignment = career
+            .assignment(&self.careers[index].assignment)
+            .expect("assignment exists");
+
+        if self.careers[index].terms == 0 {
+            self.basic_training(career, &mut events);
+        }
+
+        // Survival; failure means a mishap and leaving the career
+        let survival = dice.roll(
+            2,
+            "survival",
+            self.dm(assignment.survival.characteristic),
+            Some(assignment.survival.target),
+        );
+        let survived = survival.success == Some(true);
+        rolls.push(survival);
+
+        let mut advancement_dm = 0;
+        let mut automatic_promotion = false;
+        let mut extra_skill_rolls = 0;
+        if survived {
+            let event = dice.roll(2, "event", 0, None);
+            let (text, effect) = EVENTS[(event.total - 2) as usize];
+            rolls.push(event);
+            events.push(format!("Event: {}", text));
+            match effect {
+                EventEffect::None => {}
+                EventEffect::Mishap => {
+                    let mishap = dice.roll(1, "mishap", 0, None);
+                    events.push(format!(
+                        "Mishap: {}",
+                        career.mishaps[mishap.total as usize - 1]
+                    ));
+                    rolls.push(mishap);
+                }
+                EventEffect::AdvancementDm(dm) => advancement_dm += dm,
+                EventEffect::ExtraSkill => extra_skill_rolls += 1,
+                EventEffect::AutomaticPromotion => automatic_promotion = true,
+            }
+        } else {
+            let mishap = dice.roll(1, "mishap", 0, None);
+            events.push(format!(
+                "Mishap: {}",
+                career.mishaps[mishap.total as usize - 1]
+            ));
+            rolls.push(mishap);
+            self.careers[index].must_leave = Some("failed survival".to_string());
+        }
+
+        let table = self.skill_table(career, index, skill_table)?;
+        self.roll_skill(dice, &table, skill_table, &mut rolls, &mut events);
+
+        if survived {
+            let officer = self.careers[index].officer;
+            let commissioned = match career.commission {
+                Some(check) if try_commission && !officer => {
+                    let roll = dice.roll(
+                        2,
+                        "commission",
+                        self.dm(check.characteristic),
+                        Some(check.target),
+                    );
+                    let success = roll.success == Some(true);
+                    rolls.push(roll);
+                    if success {
+                        let record = &mut self.careers[index];
+                        record.officer = true;
+                        record.rank = 0;
+                        events.push("Commissioned as an officer.".to_string());
+                    }
+                    Some(success)
+                }
+                None if try_commission => {
+                    events.push(format!("The {} career has no commissions.", career.name));
+                    None
+                }
+                _ => None,
+            };
+
+            let promoted = match commissioned {
+                Some(success) => success,
+                None if automatic_promotion => true,
+                None => {
+                    let roll = dice.roll(
+                        2,
+                        "advancement",
+                        self.dm(assignment.advancement.characteristic) + advancement_dm,
+                        Some(assignment.advancement.target),
+                    );
+                    let success = roll.success == Some(true);
+                    // Rolling no higher than the terms served ends the career
+                    if roll.total <= self.careers[index].terms as i32 + 1 {
+                        self.careers[index].must_leave =
+                            Some("advancement roll too low to continue".to_string());
+                    }
+                    rolls.push(roll);
+                    success
+                }
+            };
+            if promoted && self.careers[index].rank < 6 {
+                self.promote(career, index, &mut events);
+                extra_skill_rolls += 1;
+            }
+            for _ in 0..extra_skill_rolls {
+                self.roll_skill(dice, &table, skill_table, &mut rolls, &mut events);
+            }
+            self.careers[index].benefit_terms += 1;
+        }
+
+        self.careers[index].terms += 1;
+        self.terms += 1;
+        self.age += TERM_YEARS;
+        if self.age >= AGING_STARTS_AT {
+            self.age_character(dice, &mut rolls, &mut events);
+        }
+
+        let record = &self.careers[index];
+        Ok(StepRecord {
+            step: 0,
+            summary: format!(
+                "Term {} as {} ({}){}",
+                record.terms,
+                record.career,
+                record.assignment,
+                if survived { "" } else { ", ended by a mishap" }
+            ),
+            rolls,
+            events,
+        })
+    }
+
+    /// Qualify for a career, or drift if qualification fails
+    fn enter_career(
+        &mut self,
+        dice: &mut Dice,
+        career_name: &str,
+        assignment_name: Option<&str>,
+        rolls: &mut Vec<Roll>,
+        events: &mut Vec<String>,
+    ) -> Result<usize, String> {
+        let career = careers::career(career_name).ok_or_else(|| {
+            format!(
+                "Unknown career '{}'; choose from {}",
+                career_name,
+                CAREERS
+                    .iter()
+                    .map(|c| c.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+        let assignment = match assignment_name {
+            Some(name) => career.assignment(name).ok_or_else(|| {
+                format!(
+                    "Unknown {} assignment '{}'; choose from {}",
+                    career.name,
+                    name,
+                    career
+                        .assignments
+                        .iter()
+                        .map(|a| a.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?,
+            None => &career.assignments[0],
+        };
+
+        let qualified = match career.qualification {
+            Some(check) => {
+                let roll = dice.roll(
+                    2,
+                    "qualification",
+                    self.dm(check.characteristic) - self.careers.len() as i32,
+                    Some(check.target),
+                );
+                let success = roll.success == Some(true);
+                rolls.push(roll);
+                success
+            }
+            None => true,
+        };
+
+        let (career, assignment) = if qualified {
+            (career, assignment)
+        } else {
+            let drifter = careers::career("Drifter").expect("Drifter career exists");
+            events.push(format!(
+                "Failed to qualify for the {}; drifting this term instead.",
+                career.name
+            ));
+            (drifter, &drifter.assignments[1])
+        };
+        self.careers.push(CareerRecord {
+            career: career.name.to_string(),
+            assignment: assignment.name.to_string(),
+            terms: 0,
+            rank: 0,
+            rank_title: career.ranks[0].title.to_string(),
+            officer: false,
+            benefit_terms: 0,
+            must_leave: None,
+            mustered_out: false,
+        });
+        Ok(self.careers.len() - 1)
+    }
+
+    /// Service skills at 0: all of them in the first career, one afterwards
+    fn basic_training(&mut self, career: &Career, events: &mut Vec<String>) {
+        let skills: Vec<&str> = career
+            .service_skills
+            .iter()
+            .filter_map(|entry| match entry {
+                Entry::Skill(skill) => Some(*skill),
+                Entry::Stat(_) => None,
+            })
+            .collect();
+        let trained: Vec<&str> = if self.careers.len() == 1 {
+            skills
+        } else {
+            skills
+                .into_iter()
+                .find(|s| !self.skills.contains_key(*s))
+                .into_iter()
+                .collect()
+        };
+        for skill in &trained {
+            self.skills.entry(skill.to_string()).or_insert(0);
+        }
+        if !trained.is_empty() {
+            events.push(format!("Basic training: {} at 0", trained.join(", ")));
+        }
+    }
+
+    fn skill_table(&self, career: &Career, index: usize, name: &str) -> Result<[Entry; 6], String> {
+        let record = &self.careers[index];
+        match name {
+            "personal_development" => Ok(career.personal_development),
+            "service_skills" => Ok(career.service_skills),
+            "advanced_education" => match career.advanced_education {
+                Some(_) if self.characteristics[&Characteristic::Edu] < 8 => {
+                    Err("advanced_education needs EDU 8+".to_string())
+                }
+                Some(table) => Ok(table),
+                None => Err(format!(
+                    "The {} career has no advanced_education table",
+                    career.name
+                )),
+            },
+            "officer" => match career.officer_skills {
+                Some(table) if record.officer => Ok(table),
+                _ => Err("The officer table needs a commission".to_string()),
+            },
+            "assignment" => Ok(career
+                .assignment(&record.assignment)
+                .expect("assignment exists")
+                .skills),
+            other => Err(format!(
+                "Unknown skill table '{}'; use personal_development, service_skills, advanced_education, officer or assignment",
+                other
+            )),
+        }
+    }
+
+    fn roll_skill(
+        &mut self,
+        dice: &mut Dice,
+        table: &[Entry; 6],
+        table_name: &str,
+        rolls: &mut Vec<Roll>,
+        events: &mut Vec<String>,
+    ) {
+        let roll = dice.roll(1, table_name, 0, None);
+        match table[roll.total as usize - 1] {
+            Entry::Skill(skill) => {
+                let level = self
+                    .skills
+                    .entry(skill.to_string())
+                    .and_modify(|l| *l += 1)
+                    .or_insert(1);
+                events.push(format!("Skill: {} {}", skill, level));
+            }
+            Entry::Stat(characteristic) => {
+                self.adjust(characteristic, 1);
+                events.push(format!("{} +1", characteristic.abbreviation()));
+            }
+        }
+        rolls.push(roll);
+    }
+
+    fn promote(&mut self, career: &Career, index: usize, events: &mut Vec<String>) {
+        let record = &mut self.careers[index];
+        record.rank += 1;
+        let ranks = match (record.officer, career.officer_ranks) {
+            (true, Some(ranks)) => ranks,
+            _ => career.ranks,
+        };
+        let rank = ranks[record.rank as usize];
+        record.rank_title = rank.title.to_string();
+        events.push(match rank.title {
+            "" => format!("Promoted to rank {}", record.rank),
+            title => format!("Promoted to {} (rank {})", title, record.rank),
+        });
+        match rank.bonus {
+            RankBonus::None => {}
+            RankBonus::Skill(skill, level) => {
+                let current = self.skills.entry(skill.to_string()).or_insert(0);
+                *current = (*current).max(level);
+                events.push(format!("Rank bonus: {} {}", skill, level));
+            }
+            RankBonus::Stat(characteristic) => {
+                self.adjust(characteristic, 1);
+                events.push(format!("Rank bonus: {} +1", characteristic.abbreviation()));
+            }
+        }
+    }
+
+    fn adjust(&mut self, characteristic: Characteristic, amount: i32) {
+        let value = self.characteristics.entry(characteristic).or_insert(0);
+        *value = (*value + amount).clamp(0, 15);
+    }
+
+    /// Aging: 2D minus total terms, with losses to physical characteristics
+    fn age_character(&mut self, dice: &mut Dice, rolls: &mut Vec<Roll>, events: &mut Vec<String>) {
+        let roll = dice.roll(2, "aging", -(self.terms as i32), None);
+        let losses: &[(Characteristic, i32)] = match roll.total {
+            i32::MIN..=-6 => &[
+                (Characteristic::Str, 2),
+                (Characteristic::Dex, 2),
+                (Characteristic::End, 2),
+                (Characteristic::Int, 1),
+            ],
+            -5 => &[
+                (Characteristic::Str, 2),
+                (Characteristic::Dex, 2),
+                (Characteristic::End, 2),
+            ],
+            -4 => &[
+                (Characteristic::Str, 2),
+                (Characteristic::Dex, 2),
+                (Characteristic::End, 1),
+            ],
+            -3 => &[
+                (Characteristic::Str, 2),
+                (Characteristic::Dex, 1),
+                (Characteristic::End, 1),
+            ],
+            -2 => &[
+                (Characteristic::Str, 1),
+                (Characteristic::Dex, 1),
+                (Characteristic::End, 1),
+            ],
+            -1 => &[(Characteristic::Str, 1), (Characteristic::Dex, 1)],
+            0 => &[(Characteristic::Str, 1)],
+            _ => &[],
+        };
+        rolls.push(roll);
+        for (characteristic, loss) in losses {
+            self.adjust(*characteristic, -loss);
+            events.push(format!(
+                "Aging: {} -{}",
+                characteristic.abbreviation(),
+                loss
+            ));
+        }
+    }
+
+    fn muster_out(&mut self, dice: &mut Dice, cash_rolls: u32) -> Result<StepRecord, String> {
+        let index = self
+            .current_career()
+            .ok_or("Not in a career; serve a term first")?;
+        let career = careers::career(&self.careers[index].career).expect("career exists");
+        let record = &self.careers[index];
+
+        let rank_rolls = match record.rank {
+            0 => 0,
+            1..=2 => 1,
+            3..=4 => 2,
+            _ => 3,
+        };
+        let total_rolls = record.benefit_terms + rank_rolls;
+        let cash_rolls = cash_rolls
+            .min(total_rolls)
+            .min(MAX_CASH_ROLLS - self.cash_rolls_used);
+        let benefit_dm = i32::from(record.rank >= 5);
+        let cash_dm = i32::from(self.skills.contains_key("Gambler"));
+
+        let mut rolls = Vec::new();
+        let mut events = Vec::new();
+        for n in 0..total_rolls {
+            if n < cash_rolls {
+                let roll = dice.roll(1, "cash", cash_dm, None);
+                let amount = career.cash[(roll.total.clamp(1, 7) - 1) as usize];
+                self.cash += amount;
+                self.cash_rolls_used += 1;
+                events.push(format!("Cash: Cr{}", amount));
+                rolls.push(roll);
+            } else {
+                let roll = dice.roll(1, "benefit", benefit_dm, None);
+                match career.benefits[(roll.total.clamp(1, 7) - 1) as usize] {
+                    Benefit::Item(item) => {
+                        self.benefits.push(item.to_string());
+                        events.push(format!("Benefit: {}", item));
+                    }
+                    Benefit::Stat(characteristic) => {
+                        self.adjust(characteristic, 1);
+                        events.push(format!("Benefit: {} +1", characteristic.abbreviation()));
+                    }
+                }
+                rolls.push(roll);
+            }
+        }
+
+        let record = &mut self.careers[index];
+        record.mustered_out = true;
+        Ok(StepRecord {
+            step: 0,
+            summary: format!(
+                "Mustered out of the {} after {} term(s) with {} benefit roll(s)",
+                record.career, record.terms, total_rolls
+            ),
+            rolls,
+            events,
+        })
+    }
+
+    fn next_steps(&self) -> Vec<String> {
+        match self.current_career().map(|i| &self.careers[i]) {
+            Some(record) if record.must_leave.is_some() => {
+                vec![format!("muster_out of the {}", record.career)]
+            }
+            Some(record) => vec![
+                format!("term in the {} again", record.career),
+                format!("muster_out of the {}", record.career),
+            ],
+            None if self.careers.is_empty() => vec!["term in a new career".to_string()],
+            None => vec![
+                "term in a new career".to_string(),
+                "finish: build the actor with fvtt_build_actor".to_string(),
+            ],
+        }
+    }
+
+    /// Stat block in the shape `fvtt_build_actor` accepts
+    pub fn stat_block(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name,
+            "kind": "traveller",
+            "characteristics": self
+                .characteristics
+                .iter()
+                .map(|(c, v)| (c.abbreviation().to_string(), serde_json::json!(v)))
+                .collect::<serde_json::Map<_, _>>(),
+            "skills": self.skills,
+            "equipment": self.benefits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(career: &str, assignment: &str) -> ChargenStep {
+        ChargenStep::Term {
+            career: career.to_string(),
+            assignment: Some(assignment.to_string()),
+            skill_table: default_skill_table(),
+            commission: false,
+        }
+    }
+
+    #[test]
+    fn test_characteristic_dm() {
+        assert_eq!(characteristic_dm(0), -3);
+        assert_eq!(characteristic_dm(5), -1);
+        assert_eq!(characteristic_dm(7), 0);
+        assert_eq!(characteristic_dm(12), 2);
+        assert_eq!(characteristic_dm(15), 3);
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let request = ChargenRequest {
+            seed: Some(2367),
+            name: Some("Ana Vance".to_string()),
+            background_skills: vec![],
+            steps: vec![
+                term("scout", "courier"),
+                ChargenStep::MusterOut { cash_rolls: 1 },
+            ],
+        };
+        let first = generate(&request).unwrap();
+        let second = generate(&request).unwrap();
+        assert_eq!(
+            serde_json::to_value(&first).unwrap(),
+            serde_json::to_value(&second).unwrap()
+        );
+
+        assert_eq!(first.terms, 1);
+        assert_eq!(first.age, STARTING_AGE + TERM_YEARS);
+        assert!(first.careers[0].mustered_out);
+        assert!(first.cash_rolls_used <= 1);
+        assert_eq!(first.history.len(), 3);
+        assert_eq!(first.stat_block()["kind"], "traveller");
+    }
+
+    #[test]
+    fn test_invalid_steps() {
+        let mut request = ChargenRequest {
+            seed: Some(1),
+            steps: vec![term("pirate", "corsair")],
+            ..Default::default()
+        };
+        assert!(generate(&request).unwrap_err().contains("Unknown career"));
+
+        request.steps = vec![ChargenStep::MusterOut { cash_rolls: 0 }];
+        assert!(generate(&request).unwrap_err().starts_with("Step 1"));
+
+        request.steps = vec![];
+        request.background_skills = vec!["Pilot".to_string()];
+        assert!(generate(&request).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Career tables for lifepath character generation.
+//!
+//! Covers five core careers: Army, Drifter, Merchant, Navy and Scout.
+//! Events use a shared table; career-specific event text is left to the
+//! rulebook (see `document_search`).
+
+use serde::Serialize;
+
+mod civilian;
+mod military;
+
+use civilian::{DRIFTER, MERCHANT, SCOUT};
+use military::{ARMY, NAVY};
+
+/// The six characteristics rolled at the start of generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum Characteristic {
+    #[serde(rename = "STR")]
+    Str,
+    #[serde(rename = "DEX")]
+    Dex,
+    #[serde(rename = "END")]
+    End,
+    #[serde(rename = "INT")]
+    Int,
+    #[serde(rename = "EDU")]
+    Edu,
+    #[serde(rename = "SOC")]
+    Soc,
+}
+
+impl Characteristic {
+    pub const ALL: [Characteristic; 6] = [
+        Characteristic::Str,
+        Characteristic::Dex,
+        Characteristic::End,
+        Characteristic::Int,
+        Characteristic::Edu,
+        Characteristic::Soc,
+    ];
+
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Characteristic::Str => "STR",
+            Characteristic::Dex => "DEX",
+            Characteristic::End => "END",
+            Characteristic::Int => "INT",
+            Characteristic::Edu => "EDU",
+            Characteristic::Soc => "SOC",
+        }
+    }
+}
+
+/// A 2D roll plus a characteristic DM against a target
+#[derive(Debug, Clone, Copy)]
+pub struct Check {
+    pub characteristic: Characteristic,
+    pub target: i32,
+}
+
+const fn check(characteristic: Characteristic, target: i32) -> Check {
+    Check {
+        characteristic,
+        target,
+    }
+}
+
+/// A skill table entry
+#[derive(Debug, Clone, Copy)]
+pub enum Entry {
+    /// Gain the skill at 1, or raise it by one
+    Skill(&'static str),
+    /// Raise the characteristic by one
+    Stat(Characteristic),
+}
+
+/// What reaching a rank grants
+#[derive(Debug, Clone, Copy)]
+pub enum RankBonus {
+    None,
+    /// Raise the skill to at least this level
+    Skill(&'static str, u8),
+    Stat(Characteristic),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rank {
+    pub title: &'static str,
+    pub bonus: RankBonus,
+}
+
+const fn rank(title: &'static str, bonus: RankBonus) -> Rank {
+    Rank { title, bonus }
+}
+
+/// A mustering-out benefit
+#[derive(Debug, Clone, Copy)]
+pub enum Benefit {
+    Item(&'static str),
+    Stat(Characteristic),
+}
+
+#[derive(Debug)]
+pub struct Assignment {
+    pub name: &'static str,
+    pub survival: Check,
+    pub advancement: Check,
+    pub skills: [Entry; 6],
+}
+
+#[derive(Debug)]
+pub struct Career {
+    pub name: &'static str,
+    /// `None` for careers anyone can enter
+    pub qualification: Option<Check>,
+    pub commission: Option<Check>,
+    pub assignments: [Assignment; 3],
+    pub personal_development: [Entry; 6],
+    pub service_skills: [Entry; 6],
+    /// Only available with EDU 8+
+    pub advanced_education: Option<[Entry; 6]>,
+    /// Only available to commissioned officers
+    pub officer_skills: Option<[Entry; 6]>,
+    pub ranks: [Rank; 7],
+    pub officer_ranks: Option<[Rank; 7]>,
+    /// 1D mishap table, paraphrased
+    pub mishaps: [&'static str; 6],
+    /// Cash and benefit columns for 1D results 1-7
+    pub cash: [u32; 7],
+    pub benefits: [Benefit; 7],
+}
+
+impl Career {
+    pub fn assignment(&self, name: &str) -> Option<&Assignment> {
+        self.assignments
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Effect of a result on the shared event table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventEffect {
+    None,
+    /// Roll on the mishap table but stay in the career
+    Mishap,
+    /// DM applied to this term's advancement roll
+    AdvancementDm(i32),
+    /// Gain one extra roll on the chosen skill table
+    ExtraSkill,
+    /// Promoted without rolling
+    AutomaticPromotion,
+}
+
+/// Shared 2D event table (results 2-12), paraphrased
+pub const EVENTS: [(&str, EventEffect); 11] = [
+    (
+        "Disaster strikes; roll on the mishap table, but you are not ejected from the career.",
+        EventEffect::Mishap,
+    ),
+    (
+        "You are caught up in a dangerous situation and come through it; gain a Contact or an Enemy.",
+        EventEffect::None,
+    ),
+    (
+        "You make a useful friend in the service; gain an Ally.",
+        EventEffect::None,
+    ),
+    (
+        "Your work takes you to unusual places and teaches you something; gain one extra skill roll.",
+        EventEffect::ExtraSkill,
+    ),
+    (
+        "You are given special training or an advanced assignment; DM+1 to advancement this term.",
+        EventEffect::AdvancementDm(1),
+    ),
+    (
+        "Life event: something happens outside your career (relationship, loss, windfall or crime). Narrate it.",
+        EventEffect::None,
+    ),
+    (
+        "You are caught in the middle of a rival's scheme; gain a Rival.",
+        EventEffect::None,
+    ),
+    (
+        "You perform well under pressure; DM+2 to advancement this term.",
+        EventEffect::AdvancementDm(2),
+    ),
+    (
+        "You have an opportunity to learn from an expert; gain one extra skill roll.",
+        EventEffect::ExtraSkill,
+    ),
+    (
+        "Your superiors take notice of your efforts; DM+1 to advancement this term.",
+        EventEffect::AdvancementDm(1),
+    ),
+    (
+        "Exceptional service; you are automatically promoted.",
+        EventEffect::AutomaticPromotion,
+    ),
+];
+
+pub const CAREERS: [Career; 5] = [ARMY, DRIFTER, MERCHANT, NAVY, SCOUT];
+
+/// Look up a career by name, ignoring case
+pub fn career(name: &str) -> Option<&'static Career> {
+    CAREERS.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+}
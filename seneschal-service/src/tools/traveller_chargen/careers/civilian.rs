@@ -0,0 +1,298 @@
+//! Civilian careers: Drifter, Merchant and Scout.
+
+use super::Characteristic::{Dex, Edu, End, Int, Soc, Str};
+use super::Entry::{Skill, Stat};
+use super::{Assignment, Benefit, Career, RankBonus, check, rank};
+
+pub(super) const DRIFTER: Career = Career {
+    name: "Drifter",
+    qualification: None,
+    commission: None,
+    assignments: [
+        Assignment {
+            name: "Barbarian",
+            survival: check(End, 7),
+            advancement: check(Str, 7),
+            skills: [
+                Skill("Animals"),
+                Skill("Carouse"),
+                Skill("Melee (blade)"),
+                Skill("Stealth"),
+                Skill("Seafarer"),
+                Skill("Survival"),
+            ],
+        },
+        Assignment {
+            name: "Wanderer",
+            survival: check(End, 7),
+            advancement: check(Int, 7),
+            skills: [
+                Skill("Drive"),
+                Skill("Deception"),
+                Skill("Recon"),
+                Skill("Stealth"),
+                Skill("Streetwise"),
+                Skill("Survival"),
+            ],
+        },
+        Assignment {
+            name: "Scavenger",
+            survival: check(Dex, 7),
+            advancement: check(End, 7),
+            skills: [
+                Skill("Pilot (small craft)"),
+                Skill("Mechanic"),
+                Skill("Astrogation"),
+                Skill("Vacc Suit"),
+                Skill("Profession"),
+                Skill("Gun Combat"),
+            ],
+        },
+    ],
+    personal_development: [
+        Stat(Str),
+        Stat(End),
+        Stat(Dex),
+        Skill("Language"),
+        Skill("Profession"),
+        Skill("Jack-of-all-Trades"),
+    ],
+    service_skills: [
+        Skill("Athletics"),
+        Skill("Melee (unarmed)"),
+        Skill("Recon"),
+        Skill("Streetwise"),
+        Skill("Stealth"),
+        Skill("Survival"),
+    ],
+    advanced_education: None,
+    officer_skills: None,
+    ranks: [
+        rank("", RankBonus::None),
+        rank("", RankBonus::Skill("Survival", 1)),
+        rank("", RankBonus::None),
+        rank("", RankBonus::Skill("Streetwise", 1)),
+        rank("", RankBonus::None),
+        rank("", RankBonus::None),
+        rank("", RankBonus::None),
+    ],
+    officer_ranks: None,
+    mishaps: [
+        "Severely injured.",
+        "Injured.",
+        "A team-mate betrays you; gain an Enemy.",
+        "You are forced to flee after a job goes wrong.",
+        "You are caught up in a local war or disaster and barely escape.",
+        "You are arrested for something you may or may not have done.",
+    ],
+    cash: [0, 0, 1_000, 2_000, 3_000, 4_000, 8_000],
+    benefits: [
+        Benefit::Item("Contact"),
+        Benefit::Item("Weapon"),
+        Benefit::Item("Ally"),
+        Benefit::Item("Weapon"),
+        Benefit::Stat(Edu),
+        Benefit::Item("Ship Share"),
+        Benefit::Item("Two Ship Shares"),
+    ],
+};
+
+pub(super) const MERCHANT: Career = Career {
+    name: "Merchant",
+    qualification: Some(check(Int, 4)),
+    commission: None,
+    assignments: [
+        Assignment {
+            name: "Merchant Marine",
+            survival: check(Edu, 5),
+            advancement: check(Int, 7),
+            skills: [
+                Skill("Pilot"),
+                Skill("Vacc Suit"),
+                Skill("Athletics"),
+                Skill("Mechanic"),
+                Skill("Engineer"),
+                Skill("Electronics"),
+            ],
+        },
+        Assignment {
+            name: "Free Trader",
+            survival: check(Dex, 6),
+            advancement: check(Int, 6),
+            skills: [
+                Skill("Pilot (spacecraft)"),
+                Skill("Vacc Suit"),
+                Skill("Deception"),
+                Skill("Mechanic"),
+                Skill("Streetwise"),
+                Skill("Gunner"),
+            ],
+        },
+        Assignment {
+            name: "Broker",
+            survival: check(Edu, 5),
+            advancement: check(Int, 7),
+            skills: [
+                Skill("Admin"),
+                Skill("Advocate"),
+                Skill("Broker"),
+                Skill("Streetwise"),
+                Skill("Deception"),
+                Skill("Persuade"),
+            ],
+        },
+    ],
+    personal_development: [
+        Stat(Str),
+        Stat(Dex),
+        Stat(End),
+        Stat(Int),
+        Skill("Language"),
+        Skill("Streetwise"),
+    ],
+    service_skills: [
+        Skill("Drive"),
+        Skill("Vacc Suit"),
+        Skill("Broker"),
+        Skill("Steward"),
+        Skill("Electronics"),
+        Skill("Persuade"),
+    ],
+    advanced_education: Some([
+        Skill("Engineer"),
+        Skill("Astrogation"),
+        Skill("Electronics"),
+        Skill("Pilot"),
+        Skill("Admin"),
+        Skill("Advocate"),
+    ]),
+    officer_skills: None,
+    ranks: [
+        rank("Crewman", RankBonus::None),
+        rank("Senior Crewman", RankBonus::Skill("Mechanic", 1)),
+        rank("4th Officer", RankBonus::None),
+        rank("3rd Officer", RankBonus::None),
+        rank("2nd Officer", RankBonus::Skill("Pilot", 1)),
+        rank("1st Officer", RankBonus::Stat(Soc)),
+        rank("Captain", RankBonus::None),
+    ],
+    officer_ranks: None,
+    mishaps: [
+        "Severely injured.",
+        "You are bankrupted by a rival; gain a Rival.",
+        "A sudden war destroys your trade routes.",
+        "Your ship or starport is destroyed by criminals; gain an Enemy.",
+        "Imperial trade restrictions force you out of business.",
+        "A series of bad deals and decisions ruins you.",
+    ],
+    cash: [1_000, 5_000, 10_000, 20_000, 20_000, 40_000, 40_000],
+    benefits: [
+        Benefit::Item("Blade"),
+        Benefit::Stat(Int),
+        Benefit::Stat(Edu),
+        Benefit::Item("Gun"),
+        Benefit::Item("Ship Share"),
+        Benefit::Item("Free Trader"),
+        Benefit::Item("Free Trader"),
+    ],
+};
+
+pub(super) const SCOUT: Career = Career {
+    name: "Scout",
+    qualification: Some(check(Int, 5)),
+    commission: None,
+    assignments: [
+        Assignment {
+            name: "Courier",
+            survival: check(End, 5),
+            advancement: check(Edu, 9),
+            skills: [
+                Skill("Electronics"),
+                Skill("Flyer"),
+                Skill("Pilot (spacecraft)"),
+                Skill("Engineer"),
+                Skill("Athletics"),
+                Skill("Astrogation"),
+            ],
+        },
+        Assignment {
+            name: "Surveyor",
+            survival: check(End, 6),
+            advancement: check(Int, 8),
+            skills: [
+                Skill("Electronics"),
+                Skill("Persuade"),
+                Skill("Pilot"),
+                Skill("Navigation"),
+                Skill("Diplomat"),
+                Skill("Streetwise"),
+            ],
+        },
+        Assignment {
+            name: "Explorer",
+            survival: check(End, 7),
+            advancement: check(Edu, 7),
+            skills: [
+                Skill("Electronics"),
+                Skill("Pilot"),
+                Skill("Engineer"),
+                Skill("Science"),
+                Skill("Stealth"),
+                Skill("Recon"),
+            ],
+        },
+    ],
+    personal_development: [
+        Stat(Str),
+        Stat(Dex),
+        Stat(End),
+        Stat(Int),
+        Stat(Edu),
+        Skill("Jack-of-all-Trades"),
+    ],
+    service_skills: [
+        Skill("Pilot"),
+        Skill("Survival"),
+        Skill("Mechanic"),
+        Skill("Astrogation"),
+        Skill("Vacc Suit"),
+        Skill("Gun Combat"),
+    ],
+    advanced_education: Some([
+        Skill("Medic"),
+        Skill("Navigation"),
+        Skill("Seafarer"),
+        Skill("Explosives"),
+        Skill("Science"),
+        Skill("Jack-of-all-Trades"),
+    ]),
+    officer_skills: None,
+    ranks: [
+        rank("", RankBonus::None),
+        rank("Scout", RankBonus::Skill("Vacc Suit", 1)),
+        rank("", RankBonus::None),
+        rank("Senior Scout", RankBonus::Skill("Pilot", 1)),
+        rank("", RankBonus::None),
+        rank("", RankBonus::None),
+        rank("", RankBonus::None),
+    ],
+    officer_ranks: None,
+    mishaps: [
+        "Severely injured.",
+        "Psychologically damaged by your time in the scouts.",
+        "Your ship is damaged and you have to hitch-hike home.",
+        "You inadvertently cause a conflict between the Imperium and a minor race; gain a Rival.",
+        "You have no idea what happened to you; they found your ship drifting on the fringes.",
+        "Injured.",
+    ],
+    cash: [20_000, 20_000, 30_000, 30_000, 50_000, 50_000, 50_000],
+    benefits: [
+        Benefit::Item("Ship Share"),
+        Benefit::Stat(Int),
+        Benefit::Stat(Edu),
+        Benefit::Item("Weapon"),
+        Benefit::Item("Weapon"),
+        Benefit::Item("Scout Ship"),
+        Benefit::Item("Scout Ship"),
+    ],
+};
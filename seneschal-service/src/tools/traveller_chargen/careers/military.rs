@@ -0,0 +1,235 @@
+//! Military careers: Army and Navy.
+
+use super::Characteristic::{Dex, Edu, End, Int, Soc, Str};
+use super::Entry::{Skill, Stat};
+use super::{Assignment, Benefit, Career, RankBonus, check, rank};
+
+pub(super) const ARMY: Career = Career {
+    name: "Army",
+    qualification: Some(check(End, 5)),
+    commission: Some(check(Soc, 8)),
+    assignments: [
+        Assignment {
+            name: "Support",
+            survival: check(End, 5),
+            advancement: check(Edu, 7),
+            skills: [
+                Skill("Mechanic"),
+                Skill("Drive"),
+                Skill("Profession"),
+                Skill("Explosives"),
+                Skill("Electronics (comms)"),
+                Skill("Medic"),
+            ],
+        },
+        Assignment {
+            name: "Infantry",
+            survival: check(Str, 6),
+            advancement: check(Edu, 6),
+            skills: [
+                Skill("Gun Combat"),
+                Skill("Melee"),
+                Skill("Heavy Weapons"),
+                Skill("Stealth"),
+                Skill("Athletics"),
+                Skill("Recon"),
+            ],
+        },
+        Assignment {
+            name: "Cavalry",
+            survival: check(Dex, 7),
+            advancement: check(Int, 5),
+            skills: [
+                Skill("Mechanic"),
+                Skill("Drive"),
+                Skill("Flyer"),
+                Skill("Recon"),
+                Skill("Heavy Weapons (vehicle)"),
+                Skill("Electronics (sensors)"),
+            ],
+        },
+    ],
+    personal_development: [
+        Stat(Str),
+        Stat(Dex),
+        Stat(End),
+        Skill("Gambler"),
+        Skill("Medic"),
+        Skill("Melee"),
+    ],
+    service_skills: [
+        Skill("Drive"),
+        Skill("Athletics"),
+        Skill("Gun Combat"),
+        Skill("Recon"),
+        Skill("Melee"),
+        Skill("Heavy Weapons"),
+    ],
+    advanced_education: Some([
+        Skill("Tactics (military)"),
+        Skill("Electronics"),
+        Skill("Navigation"),
+        Skill("Explosives"),
+        Skill("Engineer"),
+        Skill("Survival"),
+    ]),
+    officer_skills: Some([
+        Skill("Tactics (military)"),
+        Skill("Leadership"),
+        Skill("Advocate"),
+        Skill("Diplomat"),
+        Skill("Tactics (military)"),
+        Skill("Admin"),
+    ]),
+    ranks: [
+        rank("Private", RankBonus::Skill("Gun Combat", 1)),
+        rank("Lance Corporal", RankBonus::Skill("Recon", 1)),
+        rank("Corporal", RankBonus::None),
+        rank("Lance Sergeant", RankBonus::Skill("Leadership", 1)),
+        rank("Sergeant", RankBonus::None),
+        rank("Gunnery Sergeant", RankBonus::None),
+        rank("Sergeant Major", RankBonus::None),
+    ],
+    officer_ranks: Some([
+        rank("", RankBonus::None),
+        rank("Lieutenant", RankBonus::Skill("Leadership", 1)),
+        rank("Captain", RankBonus::None),
+        rank("Major", RankBonus::Skill("Tactics (military)", 1)),
+        rank("Lieutenant Colonel", RankBonus::None),
+        rank("Colonel", RankBonus::None),
+        rank("General", RankBonus::Stat(Soc)),
+    ]),
+    mishaps: [
+        "Severely injured in action.",
+        "Your unit is slaughtered in a disastrous battle; you blame your commander or they blame you.",
+        "You are sent to a hostile world and the posting goes badly wrong.",
+        "You discover your commanding officer is engaged in criminal activity and are forced out.",
+        "You quarrel with an officer or fellow soldier and are discharged; gain a Rival.",
+        "Injured; you are medically discharged.",
+    ],
+    cash: [2_000, 5_000, 10_000, 10_000, 10_000, 20_000, 30_000],
+    benefits: [
+        Benefit::Item("Combat Implant"),
+        Benefit::Stat(Int),
+        Benefit::Stat(Edu),
+        Benefit::Item("Weapon"),
+        Benefit::Item("Armour"),
+        Benefit::Stat(End),
+        Benefit::Stat(Soc),
+    ],
+};
+
+pub(super) const NAVY: Career = Career {
+    name: "Navy",
+    qualification: Some(check(Int, 6)),
+    commission: Some(check(Soc, 8)),
+    assignments: [
+        Assignment {
+            name: "Line/Crew",
+            survival: check(Int, 5),
+            advancement: check(Edu, 7),
+            skills: [
+                Skill("Electronics"),
+                Skill("Mechanic"),
+                Skill("Gun Combat"),
+                Skill("Flyer"),
+                Skill("Melee"),
+                Skill("Vacc Suit"),
+            ],
+        },
+        Assignment {
+            name: "Engineer/Gunner",
+            survival: check(Int, 6),
+            advancement: check(Edu, 6),
+            skills: [
+                Skill("Engineer"),
+                Skill("Mechanic"),
+                Skill("Electronics"),
+                Skill("Engineer"),
+                Skill("Gunner"),
+                Skill("Flyer"),
+            ],
+        },
+        Assignment {
+            name: "Flight",
+            survival: check(Dex, 7),
+            advancement: check(Edu, 5),
+            skills: [
+                Skill("Pilot"),
+                Skill("Flyer"),
+                Skill("Gunner"),
+                Skill("Pilot (small craft)"),
+                Skill("Astrogation"),
+                Skill("Electronics"),
+            ],
+        },
+    ],
+    personal_development: [
+        Stat(Str),
+        Stat(Dex),
+        Stat(End),
+        Stat(Int),
+        Stat(Edu),
+        Stat(Soc),
+    ],
+    service_skills: [
+        Skill("Pilot"),
+        Skill("Vacc Suit"),
+        Skill("Athletics"),
+        Skill("Gunner"),
+        Skill("Mechanic"),
+        Skill("Gun Combat"),
+    ],
+    advanced_education: Some([
+        Skill("Electronics"),
+        Skill("Astrogation"),
+        Skill("Engineer"),
+        Skill("Drive"),
+        Skill("Navigation"),
+        Skill("Admin"),
+    ]),
+    officer_skills: Some([
+        Skill("Leadership"),
+        Skill("Electronics"),
+        Skill("Pilot"),
+        Skill("Melee (blade)"),
+        Skill("Admin"),
+        Skill("Tactics (naval)"),
+    ]),
+    ranks: [
+        rank("Crewman", RankBonus::None),
+        rank("Able Spacehand", RankBonus::Skill("Mechanic", 1)),
+        rank("Petty Officer, 3rd class", RankBonus::Skill("Vacc Suit", 1)),
+        rank("Petty Officer, 2nd class", RankBonus::None),
+        rank("Petty Officer, 1st class", RankBonus::Stat(End)),
+        rank("Chief Petty Officer", RankBonus::None),
+        rank("Master Chief", RankBonus::None),
+    ],
+    officer_ranks: Some([
+        rank("", RankBonus::None),
+        rank("Ensign", RankBonus::Skill("Melee (blade)", 1)),
+        rank("Sublieutenant", RankBonus::Skill("Leadership", 1)),
+        rank("Lieutenant", RankBonus::None),
+        rank("Commander", RankBonus::Skill("Tactics (naval)", 1)),
+        rank("Captain", RankBonus::Stat(Soc)),
+        rank("Admiral", RankBonus::Stat(Soc)),
+    ]),
+    mishaps: [
+        "Severely injured.",
+        "Placed in the frozen watch and revived improperly; reduce a physical characteristic.",
+        "A battle goes badly and you are blamed for the loss.",
+        "You are tormented by a superior officer or crewmate; gain a Rival.",
+        "Your ship is sabotaged and you are the scapegoat.",
+        "Injured.",
+    ],
+    cash: [1_000, 5_000, 5_000, 10_000, 20_000, 50_000, 50_000],
+    benefits: [
+        Benefit::Item("Personal Vehicle or Ship Share"),
+        Benefit::Stat(Int),
+        Benefit::Stat(Edu),
+        Benefit::Item("Weapon"),
+        Benefit::Item("TAS Membership"),
+        Benefit::Item("Ship's Boat or two Ship Shares"),
+        Benefit::Stat(Soc),
+    ],
+};
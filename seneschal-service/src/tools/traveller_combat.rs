@@ -0,0 +1,551 @@
+//! Mongoose Traveller 2nd Edition combat math.
+//!
+//! Attack rolls, damage against armour and opposed checks, resolved with
+//! seedable dice so a ruling can be replayed. Every result carries the dice
+//! rolled and an itemized list of DMs for the LLM to narrate.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Target number for checks
+const CHECK_TARGET: i32 = 8;
+
+/// Untrained skill DM
+const UNSKILLED_DM: i32 = -3;
+
+/// Aiming grants DM+1 per round, up to this
+const MAX_AIM_DM: i32 = 6;
+
+/// A named dice modifier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Modifier {
+    pub reason: String,
+    pub dm: i32,
+}
+
+impl Modifier {
+    fn new(reason: impl Into<String>, dm: i32) -> Self {
+        Self {
+            reason: reason.into(),
+            dm,
+        }
+    }
+}
+
+/// A roll of one or more dice
+#[derive(Debug, Clone, Serialize)]
+pub struct DiceRoll {
+    pub dice: Vec<u8>,
+    pub modifier: i32,
+    pub total: i32,
+}
+
+/// Seeded dice; the seed is reported so results can be replayed
+pub struct Roller {
+    pub seed: u64,
+    rng: StdRng,
+}
+
+impl Roller {
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(rand::random);
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn roll(&mut self, count: u32, modifier: i32) -> DiceRoll {
+        let dice: Vec<u8> = (0..count).map(|_| self.rng.gen_range(1..=6)).collect();
+        let total = dice.iter().map(|&d| d as i32).sum::<i32>() + modifier;
+        DiceRoll {
+            dice,
+            modifier,
+            total,
+        }
+    }
+}
+
+/// Parse a damage expression such as `3D`, `2D+3`, `1D-1` or `4`
+pub fn parse_dice(expression: &str) -> Result<(u32, i32), String> {
+    let invalid = || {
+        format!(
+            "Invalid dice '{}'; use a form like 3D, 2D+3 or 4",
+            expression
+        )
+    };
+    let compact: String = expression
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    let Some((count, rest)) = compact.split_once('D') else {
+        return compact.parse().map(|n| (0, n)).map_err(|_| invalid());
+    };
+    let count = match count {
+        "" => 1,
+        n => n.parse().map_err(|_| invalid())?,
+    };
+    let modifier = match rest {
+        "" => 0,
+        m if m.starts_with('+') => m[1..].parse().map_err(|_| invalid())?,
+        m if m.starts_with('-') => m.parse().map_err(|_| invalid())?,
+        _ => return Err(invalid()),
+    };
+    Ok((count, modifier))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RangeBand {
+    Melee,
+    Short,
+    Medium,
+    Long,
+    Extreme,
+}
+
+impl RangeBand {
+    /// Band for a distance against the weapon's listed range
+    pub fn from_distance(distance_m: f64, weapon_range_m: f64) -> Option<Self> {
+        let ratio = distance_m / weapon_range_m.max(f64::EPSILON);
+        match ratio {
+            r if r <= 0.25 => Some(RangeBand::Short),
+            r if r <= 1.0 => Some(RangeBand::Medium),
+            r if r <= 2.0 => Some(RangeBand::Long),
+            r if r <= 4.0 => Some(RangeBand::Extreme),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            RangeBand::Melee => "melee",
+            RangeBand::Short => "short",
+            RangeBand::Medium => "medium",
+            RangeBand::Long => "long",
+            RangeBand::Extreme => "extreme",
+        }
+    }
+
+    fn dm(self) -> i32 {
+        match self {
+            RangeBand::Melee | RangeBand::Medium => 0,
+            RangeBand::Short => 1,
+            RangeBand::Long => -2,
+            RangeBand::Extreme => -4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FireMode {
+    #[default]
+    Single,
+    /// Adds the weapon's Auto rating in damage dice
+    Burst,
+    /// One attack per point of Auto rating
+    FullAuto,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AttackRequest {
+    pub seed: Option<u64>,
+    /// Skill level; omitted means untrained (DM-3)
+    pub skill: Option<i32>,
+    #[serde(default)]
+    pub characteristic_dm: i32,
+    pub range_band: Option<RangeBand>,
+    pub distance_m: Option<f64>,
+    pub weapon_range_m: Option<f64>,
+    #[serde(default)]
+    pub cover: bool,
+    #[serde(default)]
+    pub target_prone: bool,
+    #[serde(default)]
+    pub aim_rounds: u32,
+    #[serde(default)]
+    pub laser_sight: bool,
+    /// Target's dodge skill, subtracted from the attack
+    #[serde(default)]
+    pub dodge: i32,
+    #[serde(default)]
+    pub other_dms: Vec<Modifier>,
+    /// Weapon damage, e.g. `3D`; omit to only roll to hit
+    pub damage: Option<String>,
+    /// Weapon's Auto trait rating
+    #[serde(default)]
+    pub auto: u32,
+    #[serde(default)]
+    pub fire_mode: FireMode,
+    #[serde(default)]
+    pub armour: i32,
+    /// Weapon's AP trait rating
+    #[serde(default)]
+    pub ap: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttackResult {
+    pub roll: DiceRoll,
+    pub target: i32,
+    pub hit: bool,
+    pub effect: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub damage: Option<DamageResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttackOutcome {
+    pub seed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_band: Option<RangeBand>,
+    pub modifiers: Vec<Modifier>,
+    pub total_dm: i32,
+    pub attacks: Vec<AttackResult>,
+    pub ammo_used: u32,
+}
+
+/// Resolve an attack, and its damage if the weapon's damage is given
+pub fn resolve_attack(request: &AttackRequest) -> Result<AttackOutcome, String> {
+    let band = match (
+        request.range_band,
+        request.distance_m,
+        request.weapon_range_m,
+    ) {
+        (Some(band), ..) => Some(band),
+        (None, Some(distance), Some(range)) => {
+            Some(RangeBand::from_distance(distance, range).ok_or_else(|| {
+                format!(
+                    "Target at {}m is beyond four times the weapon's {}m range",
+                    distance, range
+                )
+            })?)
+        }
+        _ => None,
+    };
+
+    let mut modifiers = vec![match request.skill {
+        Some(level) => Modifier::new("skill", level),
+        None => Modifier::new("untrained", UNSKILLED_DM),
+    }];
+    if request.characteristic_dm != 0 {
+        modifiers.push(Modifier::new("characteristic", request.characteristic_dm));
+    }
+    if let Some(band) = band
+        && band.dm() != 0
+    {
+        modifiers.push(Modifier::new(format!("{} range", band.name()), band.dm()));
+    }
+    let ranged = band != Some(RangeBand::Melee);
+    if request.cover && ranged {
+        modifiers.push(Modifier::new("target in cover", -2));
+    }
+    if request.target_prone && ranged {
+        modifiers.push(Modifier::new("target prone", -1));
+    }
+    if request.aim_rounds > 0 {
+        modifiers.push(Modifier::new(
+            "aiming",
+            (request.aim_rounds as i32).min(MAX_AIM_DM),
+        ));
+        if request.laser_sight {
+            modifiers.push(Modifier::new("laser sight", 1));
+        }
+    }
+    if request.dodge > 0 {
+        modifiers.push(Modifier::new("target dodging", -request.dodge));
+    }
+    modifiers.extend(request.other_dms.iter().cloned());
+    let total_dm: i32 = modifiers.iter().map(|m| m.dm).sum();
+
+    let damage = request.damage.as_deref().map(parse_dice).transpose()?;
+    let (attacks, extra_dice, ammo_used) = match request.fire_mode {
+        FireMode::Single => (1, 0, 1),
+        FireMode::Burst if request.auto > 0 => (1, request.auto, request.auto),
+        FireMode::FullAuto if request.auto > 0 => (request.auto, 0, request.auto * 3),
+        _ => return Err("Burst and full auto fire need the weapon's Auto rating".to_string()),
+    };
+
+    let mut roller = Roller::new(request.seed);
+    let attacks = (0..attacks)
+        .map(|_| {
+            let roll = roller.roll(2, total_dm);
+            let effect = roll.total - CHECK_TARGET;
+            let hit = effect >= 0;
+            let damage = damage.filter(|_| hit).map(|(dice, modifier)| {
+                roll_damage(
+                    &mut roller,
+                    dice + extra_dice,
+                    modifier,
+                    effect,
+                    request.armour,
+                    request.ap,
+                )
+            });
+            AttackResult {
+                roll,
+                target: CHECK_TARGET,
+                hit,
+                effect,
+                damage,
+            }
+        })
+        .collect();
+
+    Ok(AttackOutcome {
+        seed: roller.seed,
+        range_band: band,
+        modifiers,
+        total_dm,
+        attacks,
+        ammo_used,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DamageResult {
+    pub roll: DiceRoll,
+    /// Attack effect added to the damage
+    pub effect: i32,
+    /// Armour left after AP
+    pub armour: i32,
+    pub damage: i32,
+}
+
+fn roll_damage(
+    roller: &mut Roller,
+    dice: u32,
+    modifier: i32,
+    effect: i32,
+    armour: i32,
+    ap: i32,
+) -> DamageResult {
+    let roll = roller.roll(dice, modifier);
+    let armour = (armour - ap).max(0);
+    DamageResult {
+        damage: (roll.total + effect - armour).max(0),
+        roll,
+        effect,
+        armour,
+    }
+}
+
+/// Physical characteristics that take damage
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub struct Physical {
+    pub str: i32,
+    pub dex: i32,
+    pub end: i32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DamageRequest {
+    pub seed: Option<u64>,
+    pub damage: String,
+    #[serde(default)]
+    pub effect: i32,
+    #[serde(default)]
+    pub armour: i32,
+    #[serde(default)]
+    pub ap: i32,
+    /// Target's current characteristics, to apply the damage to
+    pub characteristics: Option<Physical>,
+    /// Stun damage only reduces END
+    #[serde(default)]
+    pub stun: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DamageOutcome {
+    pub seed: u64,
+    #[serde(flatten)]
+    pub result: DamageResult,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub characteristics: Option<Physical>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<&'static str>,
+}
+
+/// Roll damage against armour and apply it to the target
+pub fn resolve_damage(request: &DamageRequest) -> Result<DamageOutcome, String> {
+    let (dice, modifier) = parse_dice(&request.damage)?;
+    let mut roller = Roller::new(request.seed);
+    let result = roll_damage(
+        &mut roller,
+        dice,
+        modifier,
+        request.effect,
+        request.armour,
+        request.ap,
+    );
+    let characteristics = request
+        .characteristics
+        .map(|c| apply_damage(c, result.damage, request.stun));
+
+    Ok(DamageOutcome {
+        seed: roller.seed,
+        condition: characteristics.map(condition),
+        result,
+        characteristics,
+    })
+}
+
+/// Damage comes off END first, then STR, then DEX
+pub fn apply_damage(mut target: Physical, damage: i32, stun: bool) -> Physical {
+    fn take(pool: &mut i32, remaining: &mut i32) {
+        let taken = (*remaining).min(*pool).max(0);
+        *pool -= taken;
+        *remaining -= taken;
+    }
+
+    let mut remaining = damage;
+    take(&mut target.end, &mut remaining);
+    if !stun {
+        take(&mut target.str, &mut remaining);
+        take(&mut target.dex, &mut remaining);
+    }
+    target
+}
+
+fn condition(target: Physical) -> &'static str {
+    match [target.str, target.dex, target.end]
+        .iter()
+        .filter(|&&v| v <= 0)
+        .count()
+    {
+        3 => "dead",
+        2 => "unconscious",
+        1 if target.end <= 0 => "end_exhausted",
+        _ => "conscious",
+    }
+}
+
+/// One side of an opposed check
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpposedSide {
+    pub label: String,
+    #[serde(default)]
+    pub dm: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpposedRequest {
+    pub seed: Option<u64>,
+    pub first: OpposedSide,
+    pub second: OpposedSide,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpposedRoll {
+    pub label: String,
+    pub roll: DiceRoll,
+    pub effect: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpposedOutcome {
+    pub seed: u64,
+    pub rolls: [OpposedRoll; 2],
+    /// Label of the side with the higher effect, or `None` on a tie
+    pub winner: Option<String>,
+    pub margin: i32,
+}
+
+/// Both sides roll 2D + DM; the higher effect wins
+pub fn resolve_opposed(request: &OpposedRequest) -> OpposedOutcome {
+    let mut roller = Roller::new(request.seed);
+    let mut side = |side: &OpposedSide| {
+        let roll = roller.roll(2, side.dm);
+        OpposedRoll {
+            label: side.label.clone(),
+            effect: roll.total - CHECK_TARGET,
+            roll,
+        }
+    };
+    let rolls = [side(&request.first), side(&request.second)];
+    let margin = (rolls[0].effect - rolls[1].effect).abs();
+    let winner = match rolls[0].effect.cmp(&rolls[1].effect) {
+        std::cmp::Ordering::Greater => Some(rolls[0].label.clone()),
+        std::cmp::Ordering::Less => Some(rolls[1].label.clone()),
+        std::cmp::Ordering::Equal => None,
+    };
+    OpposedOutcome {
+        seed: roller.seed,
+        rolls,
+        winner,
+        margin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dice() {
+        assert_eq!(parse_dice("3D").unwrap(), (3, 0));
+        assert_eq!(parse_dice("2d + 3").unwrap(), (2, 3));
+        assert_eq!(parse_dice("1D-1").unwrap(), (1, -1));
+        assert_eq!(parse_dice("4").unwrap(), (0, 4));
+        assert!(parse_dice("3DD").is_err());
+    }
+
+    #[test]
+    fn test_range_band() {
+        assert_eq!(RangeBand::from_distance(5.0, 50.0), Some(RangeBand::Short));
+        assert_eq!(RangeBand::from_distance(90.0, 50.0), Some(RangeBand::Long));
+        assert_eq!(RangeBand::from_distance(250.0, 50.0), None);
+    }
+
+    #[test]
+    fn test_resolve_attack() {
+        let request = AttackRequest {
+            seed: Some(42),
+            skill: Some(1),
+            characteristic_dm: 1,
+            distance_m: Some(150.0),
+            weapon_range_m: Some(100.0),
+            cover: true,
+            damage: Some("3D".to_string()),
+            armour: 8,
+            ap: 2,
+            ..Default::default()
+        };
+        let outcome = resolve_attack(&request).unwrap();
+        assert_eq!(outcome.range_band, Some(RangeBand::Long));
+        assert_eq!(outcome.total_dm, 1 + 1 - 2 - 2);
+        let attack = &outcome.attacks[0];
+        assert_eq!(attack.hit, attack.roll.total >= 8);
+        if let Some(damage) = &attack.damage {
+            assert_eq!(damage.armour, 6);
+            assert_eq!(
+                damage.damage,
+                (damage.roll.total + attack.effect - 6).max(0)
+            );
+        }
+
+        let repeat = resolve_attack(&request).unwrap();
+        assert_eq!(repeat.attacks[0].roll.dice, attack.roll.dice);
+    }
+
+    #[test]
+    fn test_apply_damage() {
+        let target = Physical {
+            str: 7,
+            dex: 8,
+            end: 6,
+        };
+        let hurt = apply_damage(target, 10, false);
+        assert_eq!((hurt.end, hurt.str, hurt.dex), (0, 3, 8));
+        assert_eq!(condition(hurt), "end_exhausted");
+
+        let stunned = apply_damage(target, 10, true);
+        assert_eq!((stunned.end, stunned.str), (0, 7));
+        assert_eq!(condition(apply_damage(target, 13, false)), "unconscious");
+        assert_eq!(condition(apply_damage(target, 30, false)), "dead");
+    }
+}
@@ -0,0 +1,402 @@
+//! Animal encounter tables keyed to a world's UWP.
+//!
+//! Follows the Mongoose Traveller 2nd Edition approach: hydrographics and
+//! atmosphere decide which terrains a world has, size and atmosphere shift
+//! how large its animals grow, and each terrain gets a 2D table of animals
+//! by diet and behaviour. Tables are generated from a seed, so a world keeps
+//! the same fauna once its tables are saved.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::traveller::ParsedUwp;
+
+/// Dice formula every table is rolled with, as the dice tool takes it
+pub const ENCOUNTER_FORMULA: &str = "2d6";
+
+/// Weight in kg by size roll, from 1 (or less) up to 13 (or more)
+const WEIGHTS_KG: [u32; 13] = [1, 3, 6, 12, 25, 50, 100, 200, 400, 800, 1600, 3200, 6000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Terrain {
+    Clear,
+    Plain,
+    Desert,
+    Hills,
+    Mountain,
+    Forest,
+    Jungle,
+    Rough,
+    Swamp,
+    Beach,
+    Riverbank,
+    OceanShallows,
+    OpenOcean,
+    DeepOcean,
+}
+
+impl Terrain {
+    /// Animal size DM
+    fn size_dm(self) -> i32 {
+        match self {
+            Terrain::Desert | Terrain::Forest => -2,
+            Terrain::Mountain | Terrain::Jungle | Terrain::Rough => -1,
+            Terrain::Clear | Terrain::Hills => 0,
+            Terrain::Plain | Terrain::Beach | Terrain::Riverbank => 1,
+            Terrain::Swamp | Terrain::OceanShallows => 2,
+            Terrain::OpenOcean => 3,
+            Terrain::DeepOcean => 4,
+        }
+    }
+
+    fn is_aquatic(self) -> bool {
+        matches!(
+            self,
+            Terrain::OceanShallows | Terrain::OpenOcean | Terrain::DeepOcean
+        )
+    }
+
+    /// Terrain at the water's edge, where amphibians turn up
+    fn is_shore(self) -> bool {
+        matches!(self, Terrain::Beach | Terrain::Riverbank | Terrain::Swamp)
+    }
+
+    /// Terrain too sparse to feed many herbivores
+    fn is_barren(self) -> bool {
+        matches!(
+            self,
+            Terrain::Desert | Terrain::Rough | Terrain::Mountain | Terrain::DeepOcean
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Diet {
+    Herbivore,
+    Omnivore,
+    Carnivore,
+    Scavenger,
+}
+
+impl Diet {
+    /// Behaviours an animal with this diet can have
+    fn behaviours(self) -> &'static [&'static str] {
+        match self {
+            Diet::Herbivore => &["Filter", "Intermittent", "Grazer"],
+            Diet::Omnivore => &["Gatherer", "Hunter", "Eater"],
+            Diet::Carnivore => &["Pouncer", "Chaser", "Trapper", "Siren", "Killer"],
+            Diet::Scavenger => &["Intimidator", "Hijacker", "Carrion-eater", "Reducer"],
+        }
+    }
+}
+
+/// How many turn up, as a dice formula, and how they react to travellers
+fn behaviour_traits(behaviour: &str) -> (&'static str, &'static str) {
+    match behaviour {
+        "Filter" => (
+            "1d6",
+            "Drifts through its food; ignores travellers unless struck",
+        ),
+        "Intermittent" => (
+            "1d3",
+            "Feeds warily; flees if approached, fights if cornered",
+        ),
+        "Grazer" => (
+            "2d6",
+            "Herd animal; stampedes away from threats, tramples if surprised",
+        ),
+        "Gatherer" => ("1d6", "Forages; flees unless it outnumbers the travellers"),
+        "Hunter" => (
+            "1",
+            "Stalks small prey; attacks a lone or wounded traveller",
+        ),
+        "Eater" => (
+            "1d3",
+            "Eats anything, travellers included; attacks if hungry",
+        ),
+        "Pouncer" => ("1", "Lies in ambush and springs on the nearest traveller"),
+        "Chaser" => ("1d6", "Pack hunter; runs down anyone who flees"),
+        "Trapper" => ("1", "Waits in a pit, web or snare for prey to blunder in"),
+        "Siren" => (
+            "1",
+            "Lures prey with a sound, scent or display, then strikes",
+        ),
+        "Killer" => ("1", "Attacks on sight and fights to the death"),
+        "Intimidator" => (
+            "1d3",
+            "Drives other animals off their kills with threat displays",
+        ),
+        "Hijacker" => (
+            "1d6",
+            "Steals kills in numbers; attacks if the travellers hold food",
+        ),
+        "Carrion-eater" => (
+            "1d6",
+            "Waits for the dying; keeps its distance from the healthy",
+        ),
+        "Reducer" => ("2d6", "Swarms over remains; harmless unless disturbed"),
+        _ => ("1", ""),
+    }
+}
+
+/// One result on an encounter table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterEntry {
+    /// Total on the table's formula that gives this result
+    pub roll: u8,
+    pub diet: Diet,
+    pub behaviour: String,
+    /// How many appear, as a dice formula
+    pub quantity: String,
+    pub weight_kg: u32,
+    pub locomotion: String,
+    pub reaction: String,
+}
+
+/// An encounter table for one terrain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterTable {
+    pub terrain: Terrain,
+    /// Roll this with the dice tool and read the entry with that roll
+    pub formula: String,
+    pub entries: Vec<EncounterEntry>,
+}
+
+/// Encounter tables for every terrain on a world
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldEncounterTables {
+    pub world: String,
+    pub uwp: String,
+    pub seed: u64,
+    /// Animal size DM from the world's size and atmosphere
+    pub world_size_dm: i32,
+    /// Why there are no tables, when there aren't
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub tables: Vec<EncounterTable>,
+}
+
+/// Terrains a world with this profile has, or None if it has no native animals
+fn world_terrains(uwp: &ParsedUwp) -> Option<Vec<Terrain>> {
+    use Terrain::*;
+
+    // Vacuum and trace atmospheres, and asteroid belts, support no animals
+    if uwp.atmosphere <= 1 || uwp.size == 0 {
+        return None;
+    }
+    let mut terrains = match uwp.hydrographics {
+        0 => vec![Desert, Rough, Mountain, Plain],
+        1..=3 => vec![Desert, Plain, Rough, Hills, Mountain, Riverbank],
+        4..=6 => vec![
+            Clear,
+            Plain,
+            Hills,
+            Forest,
+            Mountain,
+            Riverbank,
+            Beach,
+            OceanShallows,
+            OpenOcean,
+        ],
+        7..=9 => vec![
+            Clear,
+            Forest,
+            Swamp,
+            Beach,
+            OceanShallows,
+            OpenOcean,
+            DeepOcean,
+        ],
+        _ => vec![Beach, OceanShallows, OpenOcean, DeepOcean],
+    };
+
+    let thin = matches!(uwp.atmosphere, 2..=5 | 14);
+    let lush = matches!(uwp.atmosphere, 6..=9 | 13);
+    if lush && (5..=9).contains(&uwp.hydrographics) {
+        terrains.push(Jungle);
+    }
+    if thin {
+        terrains.retain(|t| !matches!(t, Jungle | Swamp));
+    }
+    Some(terrains)
+}
+
+/// Animal size DM from world size (gravity) and atmosphere density
+fn world_size_dm(uwp: &ParsedUwp) -> i32 {
+    let size_dm = match uwp.size {
+        0..=4 => -1,
+        5..=7 => 0,
+        _ => 1,
+    };
+    let atmosphere_dm = match uwp.atmosphere {
+        2..=5 | 14 => -1,
+        8 | 9 | 13 => 1,
+        _ => 0,
+    };
+    size_dm + atmosphere_dm
+}
+
+/// Diet at each row of a 2D table, from 2 to 12
+fn diet_for_roll(roll: u8, terrain: Terrain) -> Diet {
+    match roll {
+        2 | 4 => Diet::Scavenger,
+        3 | 5 | 10 => Diet::Omnivore,
+        8 if terrain.is_barren() => Diet::Scavenger,
+        6..=8 => Diet::Herbivore,
+        _ => Diet::Carnivore,
+    }
+}
+
+fn roll_2d(rng: &mut StdRng) -> i32 {
+    rng.gen_range(1..=6) + rng.gen_range(1..=6)
+}
+
+fn locomotion(terrain: Terrain, rng: &mut StdRng) -> &'static str {
+    let roll = rng.gen_range(1..=6);
+    if terrain.is_aquatic() {
+        if roll == 6 { "Flyer" } else { "Swimmer" }
+    } else if terrain.is_shore() && roll >= 5 {
+        "Amphibian"
+    } else if roll == 6 {
+        "Flyer"
+    } else {
+        "Walker"
+    }
+}
+
+fn generate_table(terrain: Terrain, world_size_dm: i32, rng: &mut StdRng) -> EncounterTable {
+    let entries = (2..=12u8)
+        .map(|roll| {
+            let diet = diet_for_roll(roll, terrain);
+            let behaviours = diet.behaviours();
+            let behaviour = if diet == Diet::Herbivore && terrain.is_aquatic() {
+                // Most large sea herbivores strain their food from the water
+                if rng.gen_bool(0.5) {
+                    "Filter"
+                } else {
+                    behaviours[rng.gen_range(0..behaviours.len())]
+                }
+            } else {
+                behaviours[rng.gen_range(0..behaviours.len())]
+            };
+            let size = roll_2d(rng) + terrain.size_dm() + world_size_dm;
+            let weight_kg = WEIGHTS_KG[(size.clamp(1, 13) - 1) as usize];
+            let (quantity, reaction) = behaviour_traits(behaviour);
+            EncounterEntry {
+                roll,
+                diet,
+                behaviour: behaviour.to_string(),
+                quantity: quantity.to_string(),
+                weight_kg,
+                locomotion: locomotion(terrain, rng).to_string(),
+                reaction: reaction.to_string(),
+            }
+        })
+        .collect();
+
+    EncounterTable {
+        terrain,
+        formula: ENCOUNTER_FORMULA.to_string(),
+        entries,
+    }
+}
+
+/// Generate encounter tables for a world. The same UWP and seed always give
+/// the same tables.
+pub fn generate_encounter_tables(
+    world: &str,
+    uwp: &ParsedUwp,
+    seed: Option<u64>,
+) -> WorldEncounterTables {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let world_size_dm = world_size_dm(uwp);
+
+    let (tables, note) = match world_terrains(uwp) {
+        Some(terrains) => (
+            terrains
+                .into_iter()
+                .map(|terrain| generate_table(terrain, world_size_dm, &mut rng))
+                .collect(),
+            None,
+        ),
+        None => (
+            Vec::new(),
+            Some(format!(
+                "No native animal life: {} atmosphere on a size {} world",
+                uwp.atmosphere_type, uwp.size
+            )),
+        ),
+    };
+
+    WorldEncounterTables {
+        world: world.to_string(),
+        uwp: uwp.raw.clone(),
+        seed,
+        world_size_dm,
+        note,
+        tables,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::traveller::decode_uwp;
+
+    #[test]
+    fn test_world_terrains() {
+        // Regina: dense atmosphere, 80% water
+        let regina = decode_uwp("A788899-C").unwrap();
+        let terrains = world_terrains(&regina).unwrap();
+        assert!(terrains.contains(&Terrain::DeepOcean));
+        assert!(terrains.contains(&Terrain::Jungle));
+        assert!(!terrains.contains(&Terrain::Desert));
+
+        // Thin, dry world
+        let dry = decode_uwp("C540556-8").unwrap();
+        let terrains = world_terrains(&dry).unwrap();
+        assert_eq!(terrains[0], Terrain::Desert);
+        assert!(!terrains.contains(&Terrain::Swamp));
+
+        // Vacuum world
+        let vacuum = decode_uwp("E300100-8").unwrap();
+        assert!(world_terrains(&vacuum).is_none());
+    }
+
+    #[test]
+    fn test_world_size_dm() {
+        assert_eq!(world_size_dm(&decode_uwp("A766899-C").unwrap()), 0);
+        assert_eq!(world_size_dm(&decode_uwp("A988899-C").unwrap()), 2);
+        assert_eq!(world_size_dm(&decode_uwp("C440556-8").unwrap()), -2);
+    }
+
+    #[test]
+    fn test_generate_is_seeded_and_rollable() {
+        let uwp = decode_uwp("B564500-9").unwrap();
+        let tables = generate_encounter_tables("Test", &uwp, Some(7));
+        assert!(!tables.tables.is_empty());
+        for table in &tables.tables {
+            assert_eq!(table.formula, ENCOUNTER_FORMULA);
+            let rolls: Vec<u8> = table.entries.iter().map(|e| e.roll).collect();
+            assert_eq!(rolls, (2..=12).collect::<Vec<u8>>());
+        }
+
+        let again = generate_encounter_tables("Test", &uwp, Some(7));
+        assert_eq!(
+            serde_json::to_value(&tables).unwrap(),
+            serde_json::to_value(&again).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_life_note() {
+        let uwp = decode_uwp("E300100-8").unwrap();
+        let tables = generate_encounter_tables("Rock", &uwp, Some(1));
+        assert!(tables.tables.is_empty());
+        assert!(tables.note.is_some());
+    }
+}
@@ -2,17 +2,26 @@
 //!
 //! This module provides tools for querying the Traveller Map web service
 //! (https://travellermap.com) to retrieve sector data, world information,
-//! jump routes, and more.
+//! jump routes, and more, and to draw campaign overlays on its maps.
 
+mod cache;
 mod client;
 mod error;
 mod options;
+mod overlay;
 mod responses;
+mod route_plan;
+mod scene;
+mod sector_data;
 mod tool;
 
+pub use cache::CacheSettings;
 pub use client::TravellerMapClient;
 pub use options::{JumpMapOptions, PosterOptions};
+pub use overlay::MapOverlay;
 pub use responses::WorldData;
+pub use scene::jump_map_scene;
+pub use sector_data::{SectorWorld, parse_sector_worlds};
 pub use tool::TravellerMapTool;
 
 /// Sanitize a string for use in a filename
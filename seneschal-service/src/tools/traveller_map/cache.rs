@@ -0,0 +1,183 @@
+//! Disk cache for Traveller Map responses.
+//!
+//! Every GET request is cached under `{data_dir}/traveller_map_cache/`, keyed
+//! by its path and query (sector, hex, style, scale, ...) so a change in any
+//! parameter is a different entry. Entries are served until they are older
+//! than the TTL; in offline mode they are served regardless of age and
+//! uncached requests fail instead of reaching the network.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// Cache behaviour that can change at runtime
+#[derive(Debug, Clone, Copy)]
+pub struct CacheSettings {
+    pub ttl: Duration,
+    pub offline: bool,
+}
+
+/// A cached or freshly fetched response body
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub body: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Sidecar metadata stored next to each cached body
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntryMeta {
+    request: String,
+    content_type: String,
+}
+
+/// Whether a cache lookup found a usable entry
+pub enum CacheLookup {
+    Fresh(CachedResponse),
+    /// Older than the TTL; usable as a fallback when the API is unreachable
+    Stale(CachedResponse),
+    Miss,
+}
+
+#[derive(Clone)]
+pub struct MapCache {
+    dir: PathBuf,
+    settings: Arc<RwLock<CacheSettings>>,
+}
+
+impl MapCache {
+    pub fn new(dir: PathBuf, settings: CacheSettings) -> Self {
+        Self {
+            dir,
+            settings: Arc::new(RwLock::new(settings)),
+        }
+    }
+
+    pub fn settings(&self) -> CacheSettings {
+        *self.settings.read().unwrap()
+    }
+
+    pub fn update_settings(&self, settings: CacheSettings) {
+        *self.settings.write().unwrap() = settings;
+    }
+
+    /// Look up a request (path and query, without the base URL)
+    pub fn get(&self, request: &str) -> CacheLookup {
+        let (body_path, meta_path) = self.entry_paths(request);
+
+        let Ok(meta) = std::fs::read(&meta_path) else {
+            return CacheLookup::Miss;
+        };
+        let Ok(meta) = serde_json::from_slice::<CacheEntryMeta>(&meta) else {
+            return CacheLookup::Miss;
+        };
+        let Ok(body) = std::fs::read(&body_path) else {
+            return CacheLookup::Miss;
+        };
+
+        let response = CachedResponse {
+            body,
+            content_type: meta.content_type,
+        };
+        if is_fresh(&body_path, self.settings().ttl) {
+            CacheLookup::Fresh(response)
+        } else {
+            CacheLookup::Stale(response)
+        }
+    }
+
+    /// Store a response. Failures are logged; the cache is best-effort.
+    pub fn put(&self, request: &str, response: &CachedResponse) {
+        let (body_path, meta_path) = self.entry_paths(request);
+        let meta = CacheEntryMeta {
+            request: request.to_string(),
+            content_type: response.content_type.clone(),
+        };
+
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| write_atomic(&body_path, &response.body))
+            .and_then(|_| {
+                write_atomic(
+                    &meta_path,
+                    &serde_json::to_vec(&meta).map_err(std::io::Error::other)?,
+                )
+            });
+        if let Err(e) = result {
+            warn!(request = %request, error = %e, "Failed to write Traveller Map cache entry");
+        }
+    }
+
+    fn entry_paths(&self, request: &str) -> (PathBuf, PathBuf) {
+        let key = format!("{:x}", Sha256::digest(request.as_bytes()));
+        (
+            self.dir.join(format!("{}.bin", key)),
+            self.dir.join(format!("{}.json", key)),
+        )
+    }
+}
+
+fn is_fresh(path: &Path, ttl: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < ttl)
+}
+
+/// Write via a temporary file so concurrent readers never see a partial entry
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip_and_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MapCache::new(
+            dir.path().to_path_buf(),
+            CacheSettings {
+                ttl: Duration::from_secs(3600),
+                offline: false,
+            },
+        );
+        let request = "/api/poster?sector=Spinward%20Marches&style=poster";
+
+        assert!(matches!(cache.get(request), CacheLookup::Miss));
+
+        cache.put(
+            request,
+            &CachedResponse {
+                body: vec![1, 2, 3],
+                content_type: "image/png".to_string(),
+            },
+        );
+        match cache.get(request) {
+            CacheLookup::Fresh(response) => {
+                assert_eq!(response.body, vec![1, 2, 3]);
+                assert_eq!(response.content_type, "image/png");
+            }
+            _ => panic!("expected a fresh entry"),
+        }
+
+        // A different style is a different entry
+        assert!(matches!(
+            cache.get("/api/poster?sector=Spinward%20Marches&style=atlas"),
+            CacheLookup::Miss
+        ));
+
+        cache.update_settings(CacheSettings {
+            ttl: Duration::ZERO,
+            offline: false,
+        });
+        assert!(matches!(cache.get(request), CacheLookup::Stale(_)));
+    }
+}
@@ -1,10 +1,14 @@
 //! Traveller Map API client implementation.
 
 use reqwest::Client;
+use std::path::PathBuf;
 use std::time::Duration;
+use tracing::{debug, warn};
 
+use super::cache::{CacheLookup, CacheSettings, CachedResponse, MapCache};
 use super::error::TravellerMapError;
 use super::options::{JumpMapOptions, PosterOptions, RouteOptions};
+use super::overlay::MapOverlay;
 use super::responses::{
     Coordinates, JumpWorldsResult, JumpWorldsWorldDataResponse, MilieuxResult, RouteResult,
     SearchResults, SectorMetadata, UniverseResult, WorldData,
@@ -21,6 +25,7 @@ const DEFAULT_TIMEOUT_SECS: u64 = 30;
 pub struct TravellerMapClient {
     client: Client,
     base_url: String,
+    cache: Option<MapCache>,
 }
 
 impl Default for TravellerMapClient {
@@ -41,23 +46,61 @@ impl TravellerMapClient {
         Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
+            cache: None,
         }
     }
 
-    /// Search for worlds, sectors, and subsectors by name or criteria
-    pub async fn search(
-        &self,
-        query: &str,
-        milieu: Option<&str>,
-    ) -> Result<SearchResults, TravellerMapError> {
-        let mut url = format!(
-            "{}/api/search?q={}",
-            self.base_url,
-            urlencoding::encode(query)
-        );
-        if let Some(m) = milieu {
-            url.push_str(&format!("&milieu={}", urlencoding::encode(m)));
+    /// Cache responses on disk under `cache_dir`
+    pub fn with_cache(mut self, cache_dir: PathBuf, settings: CacheSettings) -> Self {
+        self.cache = Some(MapCache::new(cache_dir, settings));
+        self
+    }
+
+    /// Apply changed cache settings (TTL, offline mode) without recreating the client
+    pub fn update_cache_settings(&self, settings: CacheSettings) {
+        if let Some(cache) = &self.cache {
+            cache.update_settings(settings);
         }
+    }
+
+    /// GET a request path (with query), going through the cache if configured.
+    ///
+    /// Fresh cache entries are served without a request. If the API can't be
+    /// reached, a stale entry is served instead of failing. In offline mode only
+    /// the cache is consulted.
+    async fn get(&self, request: &str) -> Result<CachedResponse, TravellerMapError> {
+        let Some(cache) = &self.cache else {
+            return self.fetch(request).await;
+        };
+
+        let stale = match cache.get(request) {
+            CacheLookup::Fresh(response) => return Ok(response),
+            CacheLookup::Stale(response) => Some(response),
+            CacheLookup::Miss => None,
+        };
+
+        if cache.settings().offline {
+            return stale.ok_or_else(|| TravellerMapError::NotCached {
+                request: request.to_string(),
+            });
+        }
+
+        match (self.fetch(request).await, stale) {
+            (Ok(response), _) => {
+                cache.put(request, &response);
+                Ok(response)
+            }
+            (Err(TravellerMapError::Request(e)), Some(stale)) => {
+                warn!(request = %request, error = %e, "Traveller Map unreachable; serving stale cache entry");
+                Ok(stale)
+            }
+            (Err(e), _) => Err(e),
+        }
+    }
+
+    async fn fetch(&self, request: &str) -> Result<CachedResponse, TravellerMapError> {
+        let url = format!("{}{}", self.base_url, request);
+        debug!(url = %url, "Traveller Map request");
 
         let response = self.client.get(&url).send().await?;
         if !response.status().is_success() {
@@ -67,7 +110,37 @@ impl TravellerMapClient {
             });
         }
 
-        let results: SearchResults = response.json().await?;
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(CachedResponse { body, content_type })
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: &str,
+    ) -> Result<T, TravellerMapError> {
+        let response = self.get(request).await?;
+        Ok(serde_json::from_slice(&response.body)?)
+    }
+
+    /// Search for worlds, sectors, and subsectors by name or criteria
+    pub async fn search(
+        &self,
+        query: &str,
+        milieu: Option<&str>,
+    ) -> Result<SearchResults, TravellerMapError> {
+        let mut request = format!("/api/search?q={}", urlencoding::encode(query));
+        if let Some(m) = milieu {
+            request.push_str(&format!("&milieu={}", urlencoding::encode(m)));
+        }
+
+        let results: SearchResults = self.get_json(&request).await?;
         Ok(results)
     }
 
@@ -78,23 +151,14 @@ impl TravellerMapClient {
         hex: &str,
         jump: u8,
     ) -> Result<JumpWorldsResult, TravellerMapError> {
-        let url = format!(
-            "{}/api/jumpworlds?sector={}&hex={}&jump={}",
-            self.base_url,
+        let request = format!(
+            "/api/jumpworlds?sector={}&hex={}&jump={}",
             urlencoding::encode(sector),
             urlencoding::encode(hex),
             jump
         );
 
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let results: JumpWorldsResult = response.json().await?;
+        let results: JumpWorldsResult = self.get_json(&request).await?;
         Ok(results)
     }
 
@@ -106,43 +170,35 @@ impl TravellerMapClient {
         jump: u8,
         options: RouteOptions,
     ) -> Result<RouteResult, TravellerMapError> {
-        let mut url = format!(
-            "{}/api/route?start={}&end={}&jump={}",
-            self.base_url,
+        let mut request = format!(
+            "/api/route?start={}&end={}&jump={}",
             urlencoding::encode(start),
             urlencoding::encode(end),
             jump
         );
 
         if options.wild {
-            url.push_str("&wild=1");
+            request.push_str("&wild=1");
         }
         if options.imperium_only {
-            url.push_str("&im=1");
+            request.push_str("&im=1");
         }
         if options.no_red_zones {
-            url.push_str("&nored=1");
+            request.push_str("&nored=1");
         }
         if options.allow_anomalies {
-            url.push_str("&aok=1");
+            request.push_str("&aok=1");
         }
 
-        let response = self.client.get(&url).send().await?;
-        if response.status() == 404 {
-            return Err(TravellerMapError::NoRouteFound {
-                start: start.to_string(),
-                end: end.to_string(),
-            });
+        match self.get_json(&request).await {
+            Err(TravellerMapError::ApiError { status: 404, .. }) => {
+                Err(TravellerMapError::NoRouteFound {
+                    start: start.to_string(),
+                    end: end.to_string(),
+                })
+            }
+            result => result,
         }
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let results: RouteResult = response.json().await?;
-        Ok(results)
     }
 
     /// Get complete world data for a specific location
@@ -154,22 +210,13 @@ impl TravellerMapClient {
         sector: &str,
         hex: &str,
     ) -> Result<WorldData, TravellerMapError> {
-        let url = format!(
-            "{}/api/jumpworlds?sector={}&hex={}&jump=0",
-            self.base_url,
+        let request = format!(
+            "/api/jumpworlds?sector={}&hex={}&jump=0",
             urlencoding::encode(sector),
             urlencoding::encode(hex)
         );
 
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let wrapper: JumpWorldsWorldDataResponse = response.json().await?;
+        let wrapper: JumpWorldsWorldDataResponse = self.get_json(&request).await?;
         wrapper
             .worlds
             .into_iter()
@@ -185,44 +232,20 @@ impl TravellerMapClient {
         sector: &str,
         hex: Option<&str>,
     ) -> Result<Coordinates, TravellerMapError> {
-        let mut url = format!(
-            "{}/api/coordinates?sector={}",
-            self.base_url,
-            urlencoding::encode(sector)
-        );
+        let mut request = format!("/api/coordinates?sector={}", urlencoding::encode(sector));
         if let Some(h) = hex {
-            url.push_str(&format!("&hex={}", urlencoding::encode(h)));
-        }
-
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            request.push_str(&format!("&hex={}", urlencoding::encode(h)));
         }
 
-        let coords: Coordinates = response.json().await?;
+        let coords: Coordinates = self.get_json(&request).await?;
         Ok(coords)
     }
 
     /// Get sector metadata
     pub async fn sector_metadata(&self, sector: &str) -> Result<SectorMetadata, TravellerMapError> {
-        let url = format!(
-            "{}/api/metadata?sector={}",
-            self.base_url,
-            urlencoding::encode(sector)
-        );
-
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
+        let request = format!("/api/metadata?sector={}", urlencoding::encode(sector));
 
-        let metadata: SectorMetadata = response.json().await?;
+        let metadata: SectorMetadata = self.get_json(&request).await?;
         Ok(metadata)
     }
 
@@ -232,25 +255,16 @@ impl TravellerMapClient {
         sector: &str,
         subsector: Option<&str>,
     ) -> Result<String, TravellerMapError> {
-        let mut url = format!(
-            "{}/api/sec?sector={}&type=TabDelimited",
-            self.base_url,
+        let mut request = format!(
+            "/api/sec?sector={}&type=TabDelimited",
             urlencoding::encode(sector)
         );
         if let Some(ss) = subsector {
-            url.push_str(&format!("&subsector={}", urlencoding::encode(ss)));
-        }
-
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            request.push_str(&format!("&subsector={}", urlencoding::encode(ss)));
         }
 
-        let data = response.text().await?;
-        Ok(data)
+        let response = self.get(&request).await?;
+        Ok(String::from_utf8_lossy(&response.body).into_owned())
     }
 
     /// List all sectors in the universe
@@ -259,7 +273,7 @@ impl TravellerMapClient {
         milieu: Option<&str>,
         require_data: bool,
     ) -> Result<UniverseResult, TravellerMapError> {
-        let mut url = format!("{}/api/universe", self.base_url);
+        let mut request = "/api/universe".to_string();
         let mut params = vec![];
 
         if let Some(m) = milieu {
@@ -270,60 +284,23 @@ impl TravellerMapClient {
         }
 
         if !params.is_empty() {
-            url.push('?');
-            url.push_str(&params.join("&"));
+            request.push('?');
+            request.push_str(&params.join("&"));
         }
 
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let results: UniverseResult = response.json().await?;
+        let results: UniverseResult = self.get_json(&request).await?;
         Ok(results)
     }
 
     /// Get available milieux (time periods)
     pub async fn milieux(&self) -> Result<MilieuxResult, TravellerMapError> {
-        let url = format!("{}/api/milieux", self.base_url);
-
-        let response = self.client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let results: MilieuxResult = response.json().await?;
+        let results: MilieuxResult = self.get_json("/api/milieux").await?;
         Ok(results)
     }
 
     /// Generate a URL for the poster/map image API
     pub fn poster_url(&self, sector: &str, options: &PosterOptions) -> String {
-        let mut url = format!(
-            "{}/api/poster?sector={}",
-            self.base_url,
-            urlencoding::encode(sector)
-        );
-
-        if let Some(ss) = &options.subsector {
-            url.push_str(&format!("&subsector={}", urlencoding::encode(ss)));
-        }
-        if let Some(scale) = options.scale {
-            url.push_str(&format!("&scale={}", scale));
-        }
-        if let Some(style) = &options.style {
-            url.push_str(&format!("&style={}", style));
-        }
-        if options.thumbnail {
-            url.push_str("&thumb=1");
-        }
-
-        url
+        format!("{}{}", self.base_url, poster_request(sector, options))
     }
 
     /// Generate a URL for jump map image
@@ -334,28 +311,11 @@ impl TravellerMapClient {
         jump: u8,
         options: &JumpMapOptions,
     ) -> String {
-        let mut url = format!(
-            "{}/api/jumpmap?sector={}&hex={}&jump={}",
+        format!(
+            "{}{}",
             self.base_url,
-            urlencoding::encode(sector),
-            urlencoding::encode(hex),
-            jump
-        );
-
-        if let Some(scale) = options.scale {
-            url.push_str(&format!("&scale={}", scale));
-        }
-        if let Some(style) = &options.style {
-            url.push_str(&format!("&style={}", style));
-        }
-        if !options.clip {
-            url.push_str("&clip=0");
-        }
-        if !options.border {
-            url.push_str("&border=0");
-        }
-
-        url
+            jump_map_request(sector, hex, jump, options)
+        )
     }
 
     /// Download a poster/sector map image
@@ -364,9 +324,55 @@ impl TravellerMapClient {
         sector: &str,
         options: &PosterOptions,
     ) -> Result<(Vec<u8>, String), TravellerMapError> {
-        let url = self.poster_url(sector, options);
-        let response = self.client.get(&url).send().await?;
+        let response = self.get(&poster_request(sector, options)).await?;
+        Ok((response.body, image_extension(&response.content_type)))
+    }
+
+    /// Generate a URL for the interactive map showing an overlay's party marker
+    pub fn overlay_map_url(&self, overlay: &MapOverlay, style: Option<&str>) -> String {
+        format!("{}/?{}", self.base_url, overlay.map_query(style))
+    }
 
+    /// Generate the URL an overlay poster is POSTed to, with the sector's
+    /// data and overlaid metadata as form fields
+    pub fn overlay_poster_url(&self, options: &PosterOptions) -> String {
+        format!(
+            "{}/api/poster?{}",
+            self.base_url,
+            poster_query(options).trim_start_matches('&')
+        )
+    }
+
+    /// Render a poster of an overlay's sector with the overlay drawn on it.
+    ///
+    /// The sector's data and metadata come through the cache; the render
+    /// itself is a POST and always goes to the API, so it fails in offline mode.
+    pub async fn download_overlay_poster(
+        &self,
+        overlay: &MapOverlay,
+        options: &PosterOptions,
+    ) -> Result<(Vec<u8>, String), TravellerMapError> {
+        let url = self.overlay_poster_url(options);
+        if self.cache.as_ref().is_some_and(|c| c.settings().offline) {
+            return Err(TravellerMapError::NotCached { request: url });
+        }
+
+        let data = self.sector_data(&overlay.sector, None).await?;
+        let metadata = self
+            .get(&format!(
+                "/api/metadata?sector={}&accept=text/xml",
+                urlencoding::encode(&overlay.sector)
+            ))
+            .await?;
+        let metadata = overlay.merge_into_metadata(&String::from_utf8_lossy(&metadata.body));
+
+        debug!(url = %url, sector = %overlay.sector, "Traveller Map overlay poster request");
+        let response = self
+            .client
+            .post(&url)
+            .form(&[("data", data), ("metadata", metadata)])
+            .send()
+            .await?;
         if !response.status().is_success() {
             return Err(TravellerMapError::ApiError {
                 status: response.status().as_u16(),
@@ -374,23 +380,16 @@ impl TravellerMapClient {
             });
         }
 
-        // Determine file extension from content-type
         let content_type = response
             .headers()
             .get("content-type")
             .and_then(|v| v.to_str().ok())
-            .unwrap_or("image/png");
-
-        let extension = match content_type {
-            "image/png" => "png",
-            "image/jpeg" => "jpg",
-            "application/pdf" => "pdf",
-            "image/svg+xml" => "svg",
-            _ => "png",
-        };
-
-        let bytes = response.bytes().await?.to_vec();
-        Ok((bytes, extension.to_string()))
+            .unwrap_or("image/png")
+            .to_string();
+        Ok((
+            response.bytes().await?.to_vec(),
+            image_extension(&content_type),
+        ))
     }
 
     /// Download a jump map image
@@ -401,32 +400,75 @@ impl TravellerMapClient {
         jump: u8,
         options: &JumpMapOptions,
     ) -> Result<(Vec<u8>, String), TravellerMapError> {
-        let url = self.jump_map_url(sector, hex, jump, options);
-        let response = self.client.get(&url).send().await?;
+        let response = self
+            .get(&jump_map_request(sector, hex, jump, options))
+            .await?;
+        Ok((response.body, image_extension(&response.content_type)))
+    }
+}
 
-        if !response.status().is_success() {
-            return Err(TravellerMapError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
+/// Request path for a poster image
+fn poster_request(sector: &str, options: &PosterOptions) -> String {
+    format!(
+        "/api/poster?sector={}{}",
+        urlencoding::encode(sector),
+        poster_query(options)
+    )
+}
 
-        // Determine file extension from content-type
-        let content_type = response
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("image/png");
-
-        let extension = match content_type {
-            "image/png" => "png",
-            "image/jpeg" => "jpg",
-            "application/pdf" => "pdf",
-            "image/svg+xml" => "svg",
-            _ => "png",
-        };
+/// Poster options as `&`-prefixed query parameters
+fn poster_query(options: &PosterOptions) -> String {
+    let mut request = String::new();
+
+    if let Some(ss) = &options.subsector {
+        request.push_str(&format!("&subsector={}", urlencoding::encode(ss)));
+    }
+    if let Some(scale) = options.scale {
+        request.push_str(&format!("&scale={}", scale));
+    }
+    if let Some(style) = &options.style {
+        request.push_str(&format!("&style={}", style));
+    }
+    if options.thumbnail {
+        request.push_str("&thumb=1");
+    }
 
-        let bytes = response.bytes().await?.to_vec();
-        Ok((bytes, extension.to_string()))
+    request
+}
+
+/// Request path for a jump map image
+fn jump_map_request(sector: &str, hex: &str, jump: u8, options: &JumpMapOptions) -> String {
+    let mut request = format!(
+        "/api/jumpmap?sector={}&hex={}&jump={}",
+        urlencoding::encode(sector),
+        urlencoding::encode(hex),
+        jump
+    );
+
+    if let Some(scale) = options.scale {
+        request.push_str(&format!("&scale={}", scale));
+    }
+    if let Some(style) = &options.style {
+        request.push_str(&format!("&style={}", style));
     }
+    if !options.clip {
+        request.push_str("&clip=0");
+    }
+    if !options.border {
+        request.push_str("&border=0");
+    }
+
+    request
+}
+
+/// File extension for a downloaded image's content type
+fn image_extension(content_type: &str) -> String {
+    let extension = match content_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "application/pdf" => "pdf",
+        "image/svg+xml" => "svg",
+        _ => "png",
+    };
+    extension.to_string()
 }
@@ -13,4 +13,12 @@ pub enum TravellerMapError {
 
     #[error("Not found: {message}")]
     NotFound { message: String },
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(#[from] serde_json::Error),
+
+    #[error(
+        "Traveller Map offline mode is enabled and this request has not been cached: {request}"
+    )]
+    NotCached { request: String },
 }
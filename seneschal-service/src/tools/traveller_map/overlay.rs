@@ -0,0 +1,273 @@
+//! Campaign overlays for Traveller Map.
+//!
+//! An overlay marks campaign data on a sector: routes the party has
+//! traveled, where the party is now, and territories to highlight. The
+//! interactive map can only show the party (as its "you are here" marker),
+//! so routes and territories are drawn by rendering a poster from the
+//! sector's data with the overlay merged into its metadata, the way the
+//! Traveller Map poster maker does.
+
+use serde::Deserialize;
+
+/// Default color of traveled routes
+const ROUTE_COLOR: &str = "#ffcc00";
+
+/// Default color of highlighted territories
+const TERRITORY_COLOR: &str = "#3399ff";
+
+/// Color of the party marker label
+const PARTY_COLOR: &str = "#ff3333";
+
+/// Campaign data to draw on one sector
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MapOverlay {
+    pub sector: String,
+    /// Routes traveled, each a sequence of hexes jumped between
+    #[serde(default)]
+    pub routes: Vec<OverlayRoute>,
+    /// Hex the party is in
+    pub party_hex: Option<String>,
+    /// Label for the party marker (default "Party")
+    pub party_label: Option<String>,
+    #[serde(default)]
+    pub territories: Vec<OverlayTerritory>,
+}
+
+/// A route traveled within the sector
+#[derive(Debug, Clone, Deserialize)]
+pub struct OverlayRoute {
+    /// Hexes in XXYY format, in the order they were visited
+    pub hexes: Vec<String>,
+    pub color: Option<String>,
+}
+
+/// A named group of hexes to highlight
+#[derive(Debug, Clone, Deserialize)]
+pub struct OverlayTerritory {
+    pub name: String,
+    pub hexes: Vec<String>,
+    pub color: Option<String>,
+}
+
+/// Whether `hex` is a sector hex in XXYY format (0101 to 3240)
+fn is_sector_hex(hex: &str) -> bool {
+    if hex.len() != 4 || !hex.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let (x, y) = hex.split_at(2);
+    let (x, y): (u8, u8) = (x.parse().unwrap_or(0), y.parse().unwrap_or(0));
+    (1..=32).contains(&x) && (1..=40).contains(&y)
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl MapOverlay {
+    /// Check that the overlay names a sector and only valid hexes
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sector.trim().is_empty() {
+            return Err("Missing required parameter: sector".to_string());
+        }
+        let hexes = self
+            .routes
+            .iter()
+            .flat_map(|r| &r.hexes)
+            .chain(self.territories.iter().flat_map(|t| &t.hexes))
+            .chain(&self.party_hex);
+        for hex in hexes {
+            if !is_sector_hex(hex) {
+                return Err(format!(
+                    "Invalid hex '{}': expected XXYY within the sector (0101-3240)",
+                    hex
+                ));
+            }
+        }
+        if self.routes.iter().any(|r| r.hexes.len() < 2) {
+            return Err("Each route needs at least two hexes".to_string());
+        }
+        if self.routes.is_empty() && self.party_hex.is_none() && self.territories.is_empty() {
+            return Err("Overlay is empty: give routes, party_hex or territories".to_string());
+        }
+        Ok(())
+    }
+
+    /// Query string for the interactive map, centered on the party (or the
+    /// end of the last route) with the party marked
+    pub fn map_query(&self, style: Option<&str>) -> String {
+        let sector = urlencoding::encode(&self.sector);
+        let mut query = format!("sector={}", sector);
+        let center = self
+            .party_hex
+            .as_ref()
+            .or_else(|| self.routes.last().and_then(|r| r.hexes.last()));
+        if let Some(hex) = center {
+            query.push_str(&format!("&hex={}", hex));
+        }
+        if let Some(hex) = &self.party_hex {
+            query.push_str(&format!("&yah_sector={}&yah_hex={}", sector, hex));
+        }
+        if let Some(style) = style {
+            query.push_str(&format!("&style={}", urlencoding::encode(style)));
+        }
+        query
+    }
+
+    fn route_elements(&self) -> Vec<String> {
+        self.routes
+            .iter()
+            .flat_map(|route| {
+                let color = escape_xml(route.color.as_deref().unwrap_or(ROUTE_COLOR));
+                route
+                    .hexes
+                    .windows(2)
+                    .filter(|leg| leg[0] != leg[1])
+                    .map(move |leg| {
+                        format!(
+                            r#"<Route Start="{}" End="{}" Color="{}" Style="Solid" />"#,
+                            leg[0], leg[1], color
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn region_elements(&self) -> Vec<String> {
+        self.territories
+            .iter()
+            .map(|territory| {
+                format!(
+                    r#"<Region Color="{}" Label="{}">{}</Region>"#,
+                    escape_xml(territory.color.as_deref().unwrap_or(TERRITORY_COLOR)),
+                    escape_xml(&territory.name),
+                    territory.hexes.join(" ")
+                )
+            })
+            .collect()
+    }
+
+    fn label_elements(&self) -> Vec<String> {
+        self.party_hex
+            .iter()
+            .map(|hex| {
+                format!(
+                    r#"<Label Hex="{}" Color="{}" Size="Large">{}</Label>"#,
+                    hex,
+                    PARTY_COLOR,
+                    escape_xml(self.party_label.as_deref().unwrap_or("Party"))
+                )
+            })
+            .collect()
+    }
+
+    /// The overlay alone as sector metadata
+    pub fn metadata_xml(&self) -> String {
+        self.merge_into_metadata(&format!(
+            "<Sector><Name>{}</Name></Sector>",
+            escape_xml(&self.sector)
+        ))
+    }
+
+    /// Add the overlay's routes, regions and labels to a sector's metadata
+    /// XML, keeping what it already has
+    pub fn merge_into_metadata(&self, metadata: &str) -> String {
+        let mut merged = metadata.to_string();
+        for (section, elements) in [
+            ("Routes", self.route_elements()),
+            ("Regions", self.region_elements()),
+            ("Labels", self.label_elements()),
+        ] {
+            if elements.is_empty() {
+                continue;
+            }
+            let elements = elements.concat();
+            let close = format!("</{}>", section);
+            let empty = format!("<{} />", section);
+            if let Some(at) = merged.rfind(&close) {
+                merged.insert_str(at, &elements);
+            } else if let Some(at) = merged.rfind(&empty) {
+                merged.replace_range(
+                    at..at + empty.len(),
+                    &format!("<{0}>{1}</{0}>", section, elements),
+                );
+            } else if let Some(at) = merged.rfind("</Sector>") {
+                merged.insert_str(at, &format!("<{0}>{1}</{0}>", section, elements));
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlay() -> MapOverlay {
+        MapOverlay {
+            sector: "Spinward Marches".to_string(),
+            routes: vec![OverlayRoute {
+                hexes: vec!["1910".to_string(), "2010".to_string(), "2110".to_string()],
+                color: None,
+            }],
+            party_hex: Some("2110".to_string()),
+            party_label: Some("Beowulf & crew".to_string()),
+            territories: vec![OverlayTerritory {
+                name: "Patron's claim".to_string(),
+                hexes: vec!["1909".to_string(), "1910".to_string()],
+                color: Some("#00ff00".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(overlay().validate().is_ok());
+
+        let mut bad = overlay();
+        bad.party_hex = Some("3341".to_string());
+        assert!(bad.validate().is_err());
+
+        let mut bad = overlay();
+        bad.routes[0].hexes.truncate(1);
+        assert!(bad.validate().is_err());
+
+        let empty = MapOverlay {
+            sector: "Spinward Marches".to_string(),
+            ..Default::default()
+        };
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn test_map_query() {
+        let query = overlay().map_query(Some("poster"));
+        assert_eq!(
+            query,
+            "sector=Spinward%20Marches&hex=2110&yah_sector=Spinward%20Marches&yah_hex=2110&style=poster"
+        );
+    }
+
+    #[test]
+    fn test_merge_into_metadata() {
+        let official = "<Sector><Name>Spinward Marches</Name>\
+            <Routes><Route Start=\"0101\" End=\"0102\" /></Routes><Labels /></Sector>";
+        let merged = overlay().merge_into_metadata(official);
+
+        // Official routes are kept, and each leg of the traveled route added
+        assert!(merged.contains(r#"<Route Start="0101" End="0102" />"#));
+        assert!(merged.contains(r##"<Route Start="1910" End="2010" Color="#ffcc00""##));
+        assert!(merged.contains(r##"<Route Start="2010" End="2110" Color="#ffcc00""##));
+        assert_eq!(merged.matches("<Routes>").count(), 1);
+
+        assert!(merged.contains(
+            r##"<Regions><Region Color="#00ff00" Label="Patron's claim">1909 1910</Region></Regions>"##
+        ));
+        assert!(merged.contains(
+            r##"<Labels><Label Hex="2110" Color="#ff3333" Size="Large">Beowulf &amp; crew</Label></Labels>"##
+        ));
+        assert!(merged.ends_with("</Sector>"));
+    }
+}
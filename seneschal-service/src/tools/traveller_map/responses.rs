@@ -86,6 +86,13 @@ pub struct RouteWorld {
     pub uwp: Option<String>,
     #[serde(default)]
     pub distance: f64,
+    #[serde(rename = "PBG")]
+    pub pbg: Option<String>,
+    pub zone: Option<String>,
+    pub sector_x: Option<i32>,
+    pub sector_y: Option<i32>,
+    pub hex_x: Option<i32>,
+    pub hex_y: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,389 @@
+//! Enriched route planning on top of the Traveller Map route API.
+//!
+//! The route API only returns the worlds along the path. This annotates each
+//! leg with parsecs and jump fuel, works out where the ship has to refuel
+//! given its fuel capacity, flags stops that can't supply refined fuel, and
+//! totals the travel time including transit to and from the 100-diameter
+//! jump limit at every world.
+
+use serde::Serialize;
+
+use super::responses::RouteWorld;
+use crate::tools::traveller::{JUMP_TIME_HOURS, jump_fuel_tons};
+
+/// Hexes per sector along each axis
+const SECTOR_WIDTH: i32 = 32;
+const SECTOR_HEIGHT: i32 = 40;
+
+/// World diameter per point of UWP size code
+const KM_PER_SIZE: f64 = 1600.0;
+
+/// Standard gravity, for converting thrust ratings to acceleration
+const G_METRES_PER_SEC2: f64 = 9.81;
+
+/// Ship characteristics used for planning
+#[derive(Debug, Clone, Copy)]
+pub struct ShipProfile {
+    pub jump_rating: u8,
+    pub tonnage: u32,
+    /// Manoeuvre drive rating in G
+    pub thrust: u8,
+    /// Jump fuel tankage in tons; defaults to one jump at the full rating
+    pub fuel_capacity: Option<f64>,
+}
+
+impl ShipProfile {
+    fn fuel_capacity(&self) -> f64 {
+        self.fuel_capacity
+            .unwrap_or_else(|| jump_fuel_tons(self.tonnage, self.jump_rating))
+    }
+}
+
+/// Best fuel a world can supply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FuelAvailability {
+    /// Class A or B starport
+    Refined,
+    /// Class C or D starport
+    Unrefined,
+    /// No starport fuel, but a gas giant or water to skim
+    Wilderness,
+    None,
+    /// No UWP available
+    Unknown,
+}
+
+impl FuelAvailability {
+    fn for_world(uwp: Option<&str>, pbg: Option<&str>) -> Self {
+        let Some(uwp) = uwp else {
+            return Self::Unknown;
+        };
+        match uwp.chars().next().map(|c| c.to_ascii_uppercase()) {
+            Some('A' | 'B') => Self::Refined,
+            Some('C' | 'D') => Self::Unrefined,
+            Some(_) => {
+                let gas_giants = pbg
+                    .and_then(|p| p.chars().nth(2))
+                    .and_then(|c| c.to_digit(16))
+                    .unwrap_or(0);
+                let hydrographics = uwp.chars().nth(3).and_then(|c| c.to_digit(16)).unwrap_or(0);
+                if gas_giants > 0 || hydrographics > 0 {
+                    Self::Wilderness
+                } else {
+                    Self::None
+                }
+            }
+            None => Self::Unknown,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Refined => "refined",
+            Self::Unrefined => "unrefined",
+            Self::Wilderness => "wilderness",
+            Self::None => "none",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// A world along the route
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteStop {
+    pub name: String,
+    pub sector: String,
+    pub hex: String,
+    pub uwp: Option<String>,
+    pub zone: Option<String>,
+    pub fuel: FuelAvailability,
+    /// Hours at the ship's thrust between the world and its 100-diameter limit
+    pub transit_hours: f64,
+}
+
+/// One jump between consecutive stops
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteLeg {
+    pub from: String,
+    pub to: String,
+    /// None when the route data had no coordinates to measure with
+    pub parsecs: Option<u8>,
+    pub fuel_tons: f64,
+    /// Whether the ship has to refuel at `from` before this jump
+    pub refuel_before: bool,
+    /// Transit out, jump, and transit in
+    pub days: f64,
+}
+
+/// A route annotated with fuel, refuelling and travel time
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutePlan {
+    pub jump_rating: u8,
+    pub tonnage: u32,
+    pub thrust: u8,
+    pub fuel_capacity_tons: f64,
+    pub stops: Vec<RouteStop>,
+    pub legs: Vec<RouteLeg>,
+    pub total_parsecs: u32,
+    pub total_fuel_tons: f64,
+    pub total_days: f64,
+    pub refuel_stops: Vec<String>,
+    pub warnings: Vec<String>,
+    /// Markdown table of the legs, ready to present
+    pub table: String,
+}
+
+/// Annotate the worlds returned by the route API.
+pub fn plan_route(worlds: &[RouteWorld], ship: &ShipProfile) -> RoutePlan {
+    let thrust = ship.thrust.max(1);
+    let capacity = ship.fuel_capacity();
+
+    let stops: Vec<RouteStop> = worlds
+        .iter()
+        .map(|w| RouteStop {
+            name: w.name.clone(),
+            sector: w.sector.clone(),
+            hex: w.hex.clone(),
+            uwp: w.uwp.clone(),
+            zone: w.zone.clone().filter(|z| !z.trim().is_empty()),
+            fuel: FuelAvailability::for_world(w.uwp.as_deref(), w.pbg.as_deref()),
+            transit_hours: transit_hours(w.uwp.as_deref(), thrust),
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    if capacity < jump_fuel_tons(ship.tonnage, 1) {
+        warnings.push(format!(
+            "Fuel capacity of {:.1} tons is less than a single parsec jump needs",
+            capacity
+        ));
+    }
+
+    let mut legs = Vec::new();
+    let mut refuel_stops = Vec::new();
+    // Assume the ship sets out with full tanks
+    let mut remaining = capacity;
+
+    for (i, pair) in worlds.windows(2).enumerate() {
+        let (from, to) = (&stops[i], &stops[i + 1]);
+        let parsecs = hex_distance(&pair[0], &pair[1]).map(|d| d.min(u8::MAX as u32) as u8);
+        let jump = match parsecs {
+            Some(d) => d,
+            None => {
+                warnings.push(format!(
+                    "No coordinates for {} to {}; assuming a full Jump-{}",
+                    from.name, to.name, ship.jump_rating
+                ));
+                ship.jump_rating
+            }
+        };
+        if jump > ship.jump_rating {
+            warnings.push(format!(
+                "{} to {} is {} parsecs, beyond Jump-{}",
+                from.name, to.name, jump, ship.jump_rating
+            ));
+        }
+
+        let fuel = jump_fuel_tons(ship.tonnage, jump);
+        let refuel_before = i > 0 && remaining + 1e-9 < fuel;
+        if refuel_before {
+            remaining = capacity;
+            refuel_stops.push(from.name.clone());
+            match from.fuel {
+                FuelAvailability::Refined => {}
+                FuelAvailability::Unrefined => warnings.push(format!(
+                    "{} only sells unrefined fuel (misjump risk unless processed)",
+                    from.name
+                )),
+                FuelAvailability::Wilderness => warnings.push(format!(
+                    "{} has no starport fuel; refuelling means skimming or collecting water",
+                    from.name
+                )),
+                FuelAvailability::None => warnings.push(format!(
+                    "{} has no fuel source; the ship cannot refuel there",
+                    from.name
+                )),
+                FuelAvailability::Unknown => {
+                    warnings.push(format!("Fuel availability at {} is unknown", from.name))
+                }
+            }
+        }
+        remaining -= fuel;
+
+        let hours = from.transit_hours + JUMP_TIME_HOURS as f64 + to.transit_hours;
+        legs.push(RouteLeg {
+            from: from.name.clone(),
+            to: to.name.clone(),
+            parsecs,
+            fuel_tons: fuel,
+            refuel_before,
+            days: hours / 24.0,
+        });
+    }
+
+    for stop in &stops {
+        if stop.zone.as_deref() == Some("R") {
+            warnings.push(format!("{} is a TAS Red Zone", stop.name));
+        }
+    }
+
+    let total_parsecs = legs
+        .iter()
+        .map(|l| l.parsecs.unwrap_or(ship.jump_rating) as u32)
+        .sum();
+    let total_fuel_tons = legs.iter().map(|l| l.fuel_tons).sum();
+    let total_days = legs.iter().map(|l| l.days).sum();
+
+    let mut plan = RoutePlan {
+        jump_rating: ship.jump_rating,
+        tonnage: ship.tonnage,
+        thrust,
+        fuel_capacity_tons: capacity,
+        stops,
+        legs,
+        total_parsecs,
+        total_fuel_tons,
+        total_days,
+        refuel_stops,
+        warnings,
+        table: String::new(),
+    };
+    plan.table = render_table(&plan);
+    plan
+}
+
+/// Parsecs between two worlds, using world-space hex coordinates.
+///
+/// Traveller Map hexes are columns with even-numbered columns shifted half a
+/// hex down, so offset coordinates are converted to cube coordinates first.
+fn hex_distance(a: &RouteWorld, b: &RouteWorld) -> Option<u32> {
+    let (ax, ay) = world_coordinates(a)?;
+    let (bx, by) = world_coordinates(b)?;
+    let cube = |x: i32, y: i32| (x, y - (x - (x & 1)) / 2);
+    let (aq, ar) = cube(ax, ay);
+    let (bq, br) = cube(bx, by);
+    let (dq, dr) = (bq - aq, br - ar);
+    Some(((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as u32)
+}
+
+/// Zero-based world-space column and row
+fn world_coordinates(world: &RouteWorld) -> Option<(i32, i32)> {
+    let (hex_x, hex_y) = match (world.hex_x, world.hex_y) {
+        (Some(x), Some(y)) => (x, y),
+        _ => parse_hex(&world.hex)?,
+    };
+    let (sector_x, sector_y) = (world.sector_x?, world.sector_y?);
+    Some((
+        sector_x * SECTOR_WIDTH + hex_x - 1,
+        sector_y * SECTOR_HEIGHT + hex_y - 1,
+    ))
+}
+
+/// Split an "XXYY" hex location into column and row
+fn parse_hex(hex: &str) -> Option<(i32, i32)> {
+    if hex.len() != 4 || !hex.is_ascii() {
+        return None;
+    }
+    Some((hex[..2].parse().ok()?, hex[2..].parse().ok()?))
+}
+
+/// Hours to cover 100 diameters at constant acceleration with turnover.
+fn transit_hours(uwp: Option<&str>, thrust: u8) -> f64 {
+    let size = uwp
+        .and_then(|u| u.chars().nth(1))
+        .and_then(|c| c.to_digit(16))
+        .unwrap_or(0);
+    let distance_m = 100.0 * size as f64 * KM_PER_SIZE * 1000.0;
+    let acceleration = thrust as f64 * G_METRES_PER_SEC2;
+    2.0 * (distance_m / acceleration).sqrt() / 3600.0
+}
+
+fn render_table(plan: &RoutePlan) -> String {
+    let mut table = String::from(
+        "| # | From | To | Parsecs | Fuel (t) | Refuel first | Fuel at origin | Days |\n\
+         |---|------|----|---------|----------|--------------|----------------|------|\n",
+    );
+    for (i, (leg, origin)) in plan.legs.iter().zip(&plan.stops).enumerate() {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {:.1} | {} | {} | {:.1} |\n",
+            i + 1,
+            leg.from,
+            leg.to,
+            leg.parsecs
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "?".to_string()),
+            leg.fuel_tons,
+            if leg.refuel_before { "yes" } else { "no" },
+            origin.fuel.label(),
+            leg.days,
+        ));
+    }
+    table.push_str(&format!(
+        "| | **Total** | | **{}** | **{:.1}** | {} | | **{:.1}** |\n",
+        plan.total_parsecs,
+        plan.total_fuel_tons,
+        plan.refuel_stops.len(),
+        plan.total_days,
+    ));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world(name: &str, hex: &str, uwp: &str, pbg: &str) -> RouteWorld {
+        RouteWorld {
+            sector: "Spinward Marches".to_string(),
+            hex: hex.to_string(),
+            name: name.to_string(),
+            uwp: Some(uwp.to_string()),
+            distance: 0.0,
+            pbg: Some(pbg.to_string()),
+            zone: None,
+            sector_x: Some(-4),
+            sector_y: Some(-1),
+            hex_x: None,
+            hex_y: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_route_legs_and_refuelling() {
+        let route = vec![
+            world("Regina", "1910", "A788899-C", "703"),
+            world("Roup", "2007", "C56758A-8", "301"),
+            world("Jenghe", "2108", "E7A0000-0", "000"),
+            world("Efate", "1705", "A646930-D", "701"),
+        ];
+        let ship = ShipProfile {
+            jump_rating: 2,
+            tonnage: 200,
+            thrust: 1,
+            fuel_capacity: None,
+        };
+        let plan = plan_route(&route, &ship);
+
+        let parsecs: Vec<_> = plan.legs.iter().map(|l| l.parsecs).collect();
+        assert_eq!(parsecs, vec![Some(3), Some(1), Some(5)]);
+        assert_eq!(plan.legs[1].fuel_tons, 20.0);
+        assert_eq!(plan.fuel_capacity_tons, 40.0);
+
+        // Tanks hold one Jump-2; every later leg refuels first
+        assert_eq!(plan.refuel_stops, vec!["Roup", "Jenghe"]);
+        assert_eq!(plan.stops[1].fuel, FuelAvailability::Unrefined);
+        assert_eq!(plan.stops[2].fuel, FuelAvailability::None);
+        assert!(
+            plan.warnings
+                .iter()
+                .any(|w| w.contains("Jenghe has no fuel"))
+        );
+        assert!(plan.warnings.iter().any(|w| w.contains("beyond Jump-2")));
+
+        // Size 7 world at 1G is a little under 6 hours to the jump limit
+        assert!((plan.stops[0].transit_hours - 5.9).abs() < 0.1);
+        assert!(plan.total_days > 21.0);
+        assert!(plan.table.contains("| 1 | Regina | Roup | 3 |"));
+    }
+}
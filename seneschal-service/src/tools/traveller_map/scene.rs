@@ -0,0 +1,110 @@
+//! FVTT scene data for saved jump maps.
+//!
+//! Traveller Map draws flat-topped hexes `scale` pixels from flat to flat,
+//! which is how FVTT sizes a column hex grid, so a grid of that size with one
+//! parsec per space lines up with the map's parsecs.
+
+use serde::Serialize;
+
+/// Traveller Map's jump map scale when none is requested
+const DEFAULT_JUMP_MAP_SCALE: u32 = 64;
+
+/// Arguments for the external `create_scene` tool
+#[derive(Debug, Clone, Serialize)]
+pub struct JumpMapScene {
+    pub name: String,
+    pub image_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub grid_size: u32,
+    pub grid_type: &'static str,
+    pub grid_distance: u32,
+    pub grid_units: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+/// Build scene data for a jump map image saved at `image_path`.
+///
+/// `hex` and `jump` decide which column the image starts on, and so whether
+/// odd or even grid columns are offset.
+pub fn jump_map_scene(
+    name: &str,
+    image_path: &str,
+    image: &[u8],
+    hex: &str,
+    jump: u8,
+    scale: Option<u32>,
+    folder: Option<String>,
+) -> Result<JumpMapScene, String> {
+    let (width, height) = image::ImageReader::new(std::io::Cursor::new(image))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .into_dimensions()
+        .map_err(|e| format!("Failed to read jump map dimensions: {}", e))?;
+
+    // Traveller Map columns are numbered from 1 with even columns shifted
+    // down; FVTT numbers grid columns from 0 starting at the image edge.
+    let center_column: i32 = hex.get(..2).and_then(|c| c.parse().ok()).unwrap_or(1);
+    let first_column = center_column - jump as i32;
+    let grid_type = if first_column.rem_euclid(2) == 1 {
+        "hex_odd_q"
+    } else {
+        "hex_even_q"
+    };
+
+    Ok(JumpMapScene {
+        name: name.to_string(),
+        image_path: image_path.to_string(),
+        width,
+        height,
+        grid_size: scale.unwrap_or(DEFAULT_JUMP_MAP_SCALE),
+        grid_type,
+        grid_distance: 1,
+        grid_units: "pc",
+        folder,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::RgbImage::new(width, height)
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_jump_map_scene() {
+        let image = png(320, 300);
+        let scene = jump_map_scene(
+            "Regina J-2",
+            "assets/traveller-map/regina.png",
+            &image,
+            "1910",
+            2,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!((scene.width, scene.height), (320, 300));
+        assert_eq!(scene.grid_size, 64);
+        assert_eq!(scene.grid_type, "hex_odd_q");
+
+        let scene = jump_map_scene("", "", &image, "2007", 1, Some(48), None).unwrap();
+        assert_eq!(scene.grid_size, 48);
+        assert_eq!(scene.grid_type, "hex_odd_q");
+
+        let scene = jump_map_scene("", "", &image, "2007", 2, None, None).unwrap();
+        assert_eq!(scene.grid_type, "hex_even_q");
+
+        assert!(jump_map_scene("", "", b"not an image", "1910", 2, None, None).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! Parsing of Traveller Map tab-delimited sector data.
+
+use serde::Serialize;
+
+/// One world from a sector data listing
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SectorWorld {
+    pub hex: String,
+    pub name: String,
+    pub uwp: String,
+    pub bases: String,
+    pub remarks: String,
+    /// "A" (Amber), "R" (Red), or empty for Green
+    pub zone: String,
+    pub pbg: String,
+    pub allegiance: String,
+}
+
+/// Parse tab-delimited sector data (as returned by `/api/sec`) into worlds.
+///
+/// Columns are found by their header names, so listings with extra or
+/// reordered columns parse the same; comment and blank lines are skipped.
+pub fn parse_sector_worlds(data: &str) -> Vec<SectorWorld> {
+    let mut lines = data
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<&str> = header.split('\t').map(str::trim).collect();
+    let column = |name: &str| columns.iter().position(|c| c.eq_ignore_ascii_case(name));
+    let (hex, name, uwp, bases, remarks, zone, pbg, allegiance) = (
+        column("Hex"),
+        column("Name"),
+        column("UWP"),
+        column("Bases"),
+        column("Remarks"),
+        column("Zone"),
+        column("PBG"),
+        column("Allegiance"),
+    );
+    let Some(hex) = hex else {
+        return Vec::new();
+    };
+
+    lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|i| fields.get(i))
+                    .map(|f| f.to_string())
+                    .unwrap_or_default()
+            };
+            let world = SectorWorld {
+                hex: field(Some(hex)),
+                name: field(name),
+                uwp: field(uwp),
+                bases: field(bases).replace('-', ""),
+                remarks: field(remarks),
+                zone: field(zone).replace(['-', 'G'], ""),
+                pbg: field(pbg),
+                allegiance: field(allegiance),
+            };
+            (!world.hex.is_empty()).then_some(world)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sector_worlds() {
+        let data = "Sector\tSS\tHex\tName\tUWP\tBases\tRemarks\tZone\tPBG\tAllegiance\tStars\n\
+            Spin\tC\t1910\tRegina\tA788899-C\tNS\tRi Pa Ph An Cp\t-\t703\tImDd\tF7 V BD M3 V\n\
+            # a comment\n\
+            Spin\tC\t1715\tYres\tB78A677-9\t-\tWa\tA\t424\tImDd\tG0 V\n";
+        let worlds = parse_sector_worlds(data);
+        assert_eq!(worlds.len(), 2);
+        assert_eq!(
+            worlds[0],
+            SectorWorld {
+                hex: "1910".to_string(),
+                name: "Regina".to_string(),
+                uwp: "A788899-C".to_string(),
+                bases: "NS".to_string(),
+                remarks: "Ri Pa Ph An Cp".to_string(),
+                zone: String::new(),
+                pbg: "703".to_string(),
+                allegiance: "ImDd".to_string(),
+            }
+        );
+        assert_eq!(worlds[1].bases, "");
+        assert_eq!(worlds[1].zone, "A");
+    }
+
+    #[test]
+    fn test_parse_sector_worlds_without_header() {
+        assert!(parse_sector_worlds("").is_empty());
+        assert!(parse_sector_worlds("Regina\tA788899-C\n").is_empty());
+    }
+}
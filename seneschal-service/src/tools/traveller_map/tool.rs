@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use super::client::TravellerMapClient;
 use super::options::{JumpMapOptions, PosterOptions, RouteOptions};
+use super::route_plan::{ShipProfile, plan_route};
 use super::sanitize_filename;
 
 /// Tool enum for integration with the service
@@ -37,6 +38,23 @@ pub enum TravellerMapTool {
         no_red_zones: bool,
     },
 
+    /// Calculate a jump route annotated with fuel, refuelling and travel time
+    PlanRoute {
+        start: String,
+        end: String,
+        jump: u8,
+        tonnage: u32,
+        #[serde(default = "default_thrust")]
+        thrust: u8,
+        fuel_capacity: Option<f64>,
+        #[serde(default)]
+        wild: bool,
+        #[serde(default)]
+        imperium_only: bool,
+        #[serde(default)]
+        no_red_zones: bool,
+    },
+
     /// Get world data
     WorldData { sector: String, hex: String },
 
@@ -132,6 +150,36 @@ impl TravellerMapTool {
                 serde_json::to_value(results).map_err(|e| e.to_string())
             }
 
+            TravellerMapTool::PlanRoute {
+                start,
+                end,
+                jump,
+                tonnage,
+                thrust,
+                fuel_capacity,
+                wild,
+                imperium_only,
+                no_red_zones,
+            } => {
+                let options = RouteOptions {
+                    wild: *wild,
+                    imperium_only: *imperium_only,
+                    no_red_zones: *no_red_zones,
+                    allow_anomalies: false,
+                };
+                let route = client
+                    .route(start, end, *jump, options)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let ship = ShipProfile {
+                    jump_rating: *jump,
+                    tonnage: *tonnage,
+                    thrust: *thrust,
+                    fuel_capacity: *fuel_capacity,
+                };
+                serde_json::to_value(plan_route(&route.route, &ship)).map_err(|e| e.to_string())
+            }
+
             TravellerMapTool::WorldData { sector, hex } => {
                 let data = client
                     .world_data(sector, hex)
@@ -317,3 +365,7 @@ impl TravellerMapTool {
         }
     }
 }
+
+fn default_thrust() -> u8 {
+    1
+}
@@ -0,0 +1,543 @@
+//! Starship design and validation (High Guard basics).
+//!
+//! A design is a hull tonnage plus component choices. Anything left out is
+//! filled in (power plant sized to demand, fuel for one jump and four weeks
+//! of operation, the smallest computer that runs the jump drive), then the
+//! tonnage, power and hardpoint budgets are checked. Illegal builds still
+//! come back with their numbers, plus the list of rules they break.
+
+mod components;
+mod spec;
+
+use serde::Serialize;
+
+use components::{
+    ARMOUR, COMPUTERS, HULL_CONFIGURATIONS, HULL_OPTIONS, POWER_PLANTS, SENSORS, TURRETS, WEAPONS,
+    bridge_tons, j_drive_tech_level, lookup, m_drive_tech_level, names,
+};
+pub use spec::ShipSpec;
+
+/// Smallest hull that can mount a jump drive
+const MIN_JUMP_HULL_TONS: u32 = 100;
+
+/// Weeks of power plant fuel carried when no fuel load is given
+const DEFAULT_OPERATION_WEEKS: f64 = 4.0;
+
+/// One line of the design sheet
+#[derive(Debug, Clone, Serialize)]
+pub struct DesignLine {
+    pub component: String,
+    pub tons: f64,
+    pub cost_mcr: f64,
+    pub power: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerBudget {
+    pub output: f64,
+    /// Basic systems, manoeuvre drive, sensors, weapons and everything else
+    pub normal_demand: f64,
+    /// Basic systems and the jump drive, with everything else shut down
+    pub jump_demand: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FuelBudget {
+    pub carried: f64,
+    pub per_jump: f64,
+    pub power_plant_per_month: f64,
+    /// Weeks of operation left after one full jump
+    pub weeks_after_jump: f64,
+}
+
+/// A checked design
+#[derive(Debug, Clone, Serialize)]
+pub struct ShipDesign {
+    pub name: Option<String>,
+    pub tonnage: u32,
+    pub tech_level: u8,
+    pub configuration: String,
+    pub streamlined: bool,
+    pub hull_points: f64,
+    pub armour_points: u32,
+    pub thrust: u32,
+    pub jump: u32,
+    pub lines: Vec<DesignLine>,
+    pub tons_used: f64,
+    pub cargo_tons: f64,
+    pub power: PowerBudget,
+    pub fuel: FuelBudget,
+    pub hardpoints: u32,
+    pub hardpoints_used: u32,
+    pub cost_mcr: f64,
+    pub maintenance_cr_per_month: f64,
+    pub legal: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn unknown<T>(what: &str, name: &str, table: &[T], key: fn(&T) -> &str) -> String {
+    format!(
+        "Unknown {} '{}'; choose from {}",
+        what,
+        name,
+        names(table, key)
+    )
+}
+
+/// Fill in and check a design
+///
+/// Unknown component names are an `Err`; rules violations are reported in
+/// the design's `errors`.
+pub fn design(spec: &ShipSpec) -> Result<ShipDesign, String> {
+    if spec.tonnage < 10 {
+        return Err("Hulls are at least 10 tons".to_string());
+    }
+    let hull = spec.tonnage as f64;
+    let tl = spec.tech_level;
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    // Hull
+    let configuration =
+        lookup(&HULL_CONFIGURATIONS, &spec.configuration, |c| c.name).ok_or_else(|| {
+            unknown(
+                "hull configuration",
+                &spec.configuration,
+                &HULL_CONFIGURATIONS,
+                |c| c.name,
+            )
+        })?;
+    let mut hull_cost = hull * components::HULL_COST_PER_TON * configuration.cost_multiplier;
+    let mut hull_points = hull / 2.5;
+    for option in &spec.hull_options {
+        let (_, cost, points) = lookup(&HULL_OPTIONS, option, |o| o.0)
+            .ok_or_else(|| unknown("hull option", option, &HULL_OPTIONS, |o| o.0))?;
+        hull_cost *= cost;
+        hull_points *= points;
+    }
+    lines.push(line(
+        format!("{}-ton {} hull", spec.tonnage, configuration.name),
+        0.0,
+        hull_cost,
+        0.0,
+    ));
+
+    let mut armour_points = 0;
+    if let Some(armour) = &spec.armour {
+        let kind = lookup(&ARMOUR, &armour.kind, |a| a.name)
+            .ok_or_else(|| unknown("armour", &armour.kind, &ARMOUR, |a| a.name))?;
+        require_tl(&mut errors, tl, kind.name, kind.tech_level);
+        if armour.points > tl as u32 {
+            errors.push(format!(
+                "Armour {} exceeds the TL{} maximum of {}",
+                armour.points, tl, tl
+            ));
+        }
+        let tons = hull * kind.percent_per_point / 100.0 * armour.points as f64;
+        armour_points = armour.points;
+        lines.push(line(
+            format!("{} armour {}", kind.name, armour.points),
+            tons,
+            tons * kind.cost_per_ton,
+            0.0,
+        ));
+    }
+
+    // Drives
+    let mut m_drive_power = 0.0;
+    if spec.thrust > 0 {
+        match m_drive_tech_level(spec.thrust) {
+            Some(needed) => require_tl(&mut errors, tl, "Manoeuvre drive", needed),
+            None => errors.push(format!(
+                "Thrust {} is beyond any manoeuvre drive",
+                spec.thrust
+            )),
+        }
+        let tons = hull * spec.thrust as f64 / 100.0;
+        m_drive_power = hull * spec.thrust as f64 / 10.0;
+        lines.push(line(
+            format!("Manoeuvre drive (thrust {})", spec.thrust),
+            tons,
+            tons * components::M_DRIVE_COST_PER_TON,
+            m_drive_power,
+        ));
+    }
+    let mut j_drive_power = 0.0;
+    if spec.jump > 0 {
+        match j_drive_tech_level(spec.jump) {
+            Some(needed) => require_tl(&mut errors, tl, "Jump drive", needed),
+            None => errors.push(format!("Jump-{} is beyond any jump drive", spec.jump)),
+        }
+        if spec.tonnage < MIN_JUMP_HULL_TONS {
+            errors.push(format!(
+                "Jump drives need a hull of at least {} tons",
+                MIN_JUMP_HULL_TONS
+            ));
+        }
+        let tons = hull * spec.jump as f64 * 0.025 + 5.0;
+        j_drive_power = hull * spec.jump as f64 / 10.0;
+        lines.push(line(
+            format!("Jump drive (jump-{})", spec.jump),
+            tons,
+            tons * components::J_DRIVE_COST_PER_TON,
+            j_drive_power,
+        ));
+    }
+
+    // Fixed fittings
+    let bridge = bridge_tons(hull);
+    lines.push(line(
+        "Bridge".to_string(),
+        bridge,
+        (hull / 100.0).ceil() * 0.5,
+        0.0,
+    ));
+
+    let jump_control = spec.jump * 5;
+    let computer = match spec.computer {
+        Some(rating) => COMPUTERS
+            .iter()
+            .find(|c| c.rating == rating)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown computer/{}; ratings are {}",
+                    rating,
+                    COMPUTERS
+                        .iter()
+                        .map(|c| c.rating.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?,
+        None => COMPUTERS
+            .iter()
+            .find(|c| c.rating >= jump_control && c.tech_level <= tl)
+            .unwrap_or(&COMPUTERS[COMPUTERS.len() - 1]),
+    };
+    require_tl(
+        &mut errors,
+        tl,
+        &format!("Computer/{}", computer.rating),
+        computer.tech_level,
+    );
+    if computer.rating < jump_control {
+        errors.push(format!(
+            "Jump-{} control needs a computer rating of {} (computer/{})",
+            spec.jump, jump_control, computer.rating
+        ));
+    }
+    lines.push(line(
+        format!("Computer/{}", computer.rating),
+        0.0,
+        computer.cost,
+        0.0,
+    ));
+
+    let sensors = lookup(&SENSORS, &spec.sensors, |s| s.name)
+        .ok_or_else(|| unknown("sensors", &spec.sensors, &SENSORS, |s| s.name))?;
+    require_tl(
+        &mut errors,
+        tl,
+        &format!("{} sensors", sensors.name),
+        sensors.tech_level,
+    );
+    lines.push(line(
+        format!("{} sensors", sensors.name),
+        sensors.tons,
+        sensors.cost,
+        sensors.power,
+    ));
+
+    // Weapons
+    let hardpoints = spec.tonnage / 100;
+    let mut weapons_power = 0.0;
+    for turret in &spec.turrets {
+        let mount = lookup(&TURRETS, &turret.mount, |t| t.name)
+            .ok_or_else(|| unknown("turret", &turret.mount, &TURRETS, |t| t.name))?;
+        require_tl(
+            &mut errors,
+            tl,
+            &format!("{} turret", mount.name),
+            mount.tech_level,
+        );
+        if turret.weapons.len() > mount.capacity {
+            errors.push(format!(
+                "A {} turret holds {} weapon(s), not {}",
+                mount.name,
+                mount.capacity,
+                turret.weapons.len()
+            ));
+        }
+        let mut cost = mount.cost;
+        let mut power = 1.0;
+        let mut fitted = Vec::new();
+        for name in &turret.weapons {
+            let weapon = lookup(&WEAPONS, name, |w| w.name)
+                .ok_or_else(|| unknown("weapon", name, &WEAPONS, |w| w.name))?;
+            require_tl(&mut errors, tl, weapon.name, weapon.tech_level);
+            cost += weapon.cost;
+            power += weapon.power;
+            fitted.push(weapon.name);
+        }
+        weapons_power += power;
+        lines.push(line(
+            format!("{} turret ({})", mount.name, fitted.join(", ")),
+            1.0,
+            cost,
+            power,
+        ));
+    }
+    let hardpoints_used = spec.turrets.len() as u32;
+    if hardpoints_used > hardpoints {
+        errors.push(format!(
+            "{} turrets need {} hardpoints; a {}-ton hull has {}",
+            hardpoints_used, hardpoints_used, spec.tonnage, hardpoints
+        ));
+    }
+
+    // Accommodation and extras
+    let mut other_power = 0.0;
+    if spec.staterooms > 0 {
+        lines.push(line(
+            format!("Staterooms x{}", spec.staterooms),
+            spec.staterooms as f64 * components::STATEROOM_TONS,
+            spec.staterooms as f64 * components::STATEROOM_COST,
+            0.0,
+        ));
+    }
+    if spec.low_berths > 0 {
+        lines.push(line(
+            format!("Low berths x{}", spec.low_berths),
+            spec.low_berths as f64 * components::LOW_BERTH_TONS,
+            spec.low_berths as f64 * components::LOW_BERTH_COST,
+            (spec.low_berths as f64 / 10.0).ceil(),
+        ));
+        other_power += (spec.low_berths as f64 / 10.0).ceil();
+    }
+    if spec.fuel_processor_tons > 0.0 {
+        lines.push(line(
+            format!(
+                "Fuel processor ({} tons/day)",
+                spec.fuel_processor_tons * 20.0
+            ),
+            spec.fuel_processor_tons,
+            spec.fuel_processor_tons * components::FUEL_PROCESSOR_COST_PER_TON,
+            spec.fuel_processor_tons,
+        ));
+        other_power += spec.fuel_processor_tons;
+    }
+    for extra in &spec.extras {
+        other_power += extra.power;
+        lines.push(line(
+            extra.name.clone(),
+            extra.tons,
+            extra.cost_mcr,
+            extra.power,
+        ));
+    }
+
+    // Power plant, sized to the larger of normal and jump demand
+    let basic_power = hull * components::BASIC_POWER_PER_TON;
+    let normal_demand = basic_power + m_drive_power + sensors.power + weapons_power + other_power;
+    let jump_demand = basic_power + j_drive_power;
+    let plant = match &spec.power_plant {
+        Some(name) => lookup(&POWER_PLANTS, name, |p| p.name)
+            .ok_or_else(|| unknown("power plant", name, &POWER_PLANTS, |p| p.name))?,
+        None => POWER_PLANTS
+            .iter()
+            .rev()
+            .find(|p| p.tech_level <= tl)
+            .unwrap_or(&POWER_PLANTS[0]),
+    };
+    require_tl(&mut errors, tl, plant.name, plant.tech_level);
+    let plant_tons = spec
+        .power_plant_tons
+        .unwrap_or_else(|| (normal_demand.max(jump_demand) / plant.power_per_ton).ceil());
+    let output = plant_tons * plant.power_per_ton;
+    lines.push(line(
+        format!("{} power plant", plant.name),
+        plant_tons,
+        plant_tons * plant.cost_per_ton,
+        0.0,
+    ));
+    if output < normal_demand {
+        errors.push(format!(
+            "Power plant output {} is below the {} needed in normal operation",
+            round(output),
+            round(normal_demand)
+        ));
+    }
+    if output < jump_demand {
+        errors.push(format!(
+            "Power plant output {} is below the {} needed to jump",
+            round(output),
+            round(jump_demand)
+        ));
+    }
+
+    // Fuel
+    let per_jump = hull * spec.jump as f64 / 10.0;
+    let per_month = (plant_tons / 10.0).max(1.0);
+    let fuel = spec
+        .fuel_tons
+        .unwrap_or_else(|| (per_jump + per_month * DEFAULT_OPERATION_WEEKS / 4.0).ceil());
+    if fuel < per_jump {
+        errors.push(format!(
+            "{} tons of fuel is short of the {} a jump-{} needs",
+            round(fuel),
+            round(per_jump),
+            spec.jump
+        ));
+    }
+    let weeks_after_jump = ((fuel - per_jump).max(0.0) / per_month * 4.0).floor();
+    if fuel >= per_jump && weeks_after_jump < 2.0 {
+        warnings.push(format!(
+            "Only {} week(s) of power plant fuel remain after a jump",
+            weeks_after_jump
+        ));
+    }
+    lines.push(line("Fuel".to_string(), fuel, 0.0, 0.0));
+
+    if spec.tonnage >= 100 && spec.staterooms == 0 {
+        warnings.push("No staterooms for the crew".to_string());
+    }
+
+    // Budgets
+    let tons_used: f64 = lines.iter().map(|l| l.tons).sum();
+    if tons_used > hull {
+        errors.push(format!(
+            "Components take {} tons of a {}-ton hull",
+            round(tons_used),
+            spec.tonnage
+        ));
+    }
+    let cost: f64 = lines.iter().map(|l| l.cost_mcr).sum();
+
+    Ok(ShipDesign {
+        name: spec.name.clone(),
+        tonnage: spec.tonnage,
+        tech_level: tl,
+        configuration: configuration.name.to_string(),
+        streamlined: configuration.streamlined,
+        hull_points: round(hull_points),
+        armour_points,
+        thrust: spec.thrust,
+        jump: spec.jump,
+        lines: lines
+            .into_iter()
+            .map(|l| DesignLine {
+                tons: round(l.tons),
+                cost_mcr: round(l.cost_mcr),
+                power: round(l.power),
+                ..l
+            })
+            .collect(),
+        tons_used: round(tons_used),
+        cargo_tons: round((hull - tons_used).max(0.0)),
+        power: PowerBudget {
+            output: round(output),
+            normal_demand: round(normal_demand),
+            jump_demand: round(jump_demand),
+        },
+        fuel: FuelBudget {
+            carried: round(fuel),
+            per_jump: round(per_jump),
+            power_plant_per_month: round(per_month),
+            weeks_after_jump,
+        },
+        hardpoints,
+        hardpoints_used,
+        cost_mcr: round(cost),
+        // Annual maintenance is 0.1% of the purchase price
+        maintenance_cr_per_month: (cost * 1_000_000.0 / 12_000.0).round(),
+        legal: errors.is_empty(),
+        errors,
+        warnings,
+    })
+}
+
+fn require_tl(errors: &mut Vec<String>, tl: u8, what: &str, needed: u8) {
+    if needed > tl {
+        errors.push(format!("{} needs TL{} (design is TL{})", what, needed, tl));
+    }
+}
+
+fn line(component: String, tons: f64, cost_mcr: f64, power: f64) -> DesignLine {
+    DesignLine {
+        component,
+        tons,
+        cost_mcr,
+        power,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(json: serde_json::Value) -> ShipSpec {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_design_fills_in_missing_components() {
+        let ship = design(&spec(serde_json::json!({
+            "name": "Corsair",
+            "tonnage": 200,
+            "configuration": "streamlined",
+            "thrust": 2,
+            "jump": 2,
+            "staterooms": 4,
+            "turrets": [{"mount": "double", "weapons": ["pulse_laser", "missile_rack"]}]
+        })))
+        .unwrap();
+
+        assert!(ship.legal, "{:?}", ship.errors);
+        assert_eq!(ship.fuel.per_jump, 40.0);
+        assert!(ship.fuel.carried >= 40.0);
+        assert!(ship.power.output >= ship.power.normal_demand);
+        assert!(ship.lines.iter().any(|l| l.component == "Computer/10"));
+        assert_eq!(ship.hardpoints, 2);
+        assert!(ship.cargo_tons > 0.0);
+    }
+
+    #[test]
+    fn test_design_reports_illegal_builds() {
+        let ship = design(&spec(serde_json::json!({
+            "tonnage": 50,
+            "tech_level": 9,
+            "staterooms": 5,
+            "thrust": 6,
+            "jump": 3,
+            "computer": 5,
+            "power_plant_tons": 1,
+            "turrets": [{"mount": "single", "weapons": ["beam_laser", "sandcaster"]}]
+        })))
+        .unwrap();
+
+        assert!(!ship.legal);
+        let errors = ship.errors.join("\n");
+        assert!(errors.contains("at least 100 tons"));
+        assert!(errors.contains("Jump-3 control"));
+        assert!(errors.contains("holds 1 weapon"));
+        assert!(errors.contains("hardpoints"));
+        assert!(errors.contains("Power plant output"));
+        assert!(errors.contains("Components take"));
+    }
+
+    #[test]
+    fn test_design_rejects_unknown_components() {
+        let err = design(&spec(serde_json::json!({
+            "tonnage": 100,
+            "sensors": "psionic"
+        })))
+        .unwrap_err();
+        assert!(err.contains("choose from basic"));
+    }
+}
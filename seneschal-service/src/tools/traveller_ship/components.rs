@@ -0,0 +1,332 @@
+//! Ship component tables (High Guard basics).
+//!
+//! Costs are in MCr and sizes in displacement tons. Only the common core
+//! components are listed; anything else goes in a design's `extras`.
+
+/// Hull cost per displacement ton for a standard hull
+pub const HULL_COST_PER_TON: f64 = 0.05;
+
+/// Basic ship systems need this much power per hull ton
+pub const BASIC_POWER_PER_TON: f64 = 0.2;
+
+pub const M_DRIVE_COST_PER_TON: f64 = 2.0;
+pub const J_DRIVE_COST_PER_TON: f64 = 1.5;
+
+pub const STATEROOM_TONS: f64 = 4.0;
+pub const STATEROOM_COST: f64 = 0.5;
+pub const LOW_BERTH_TONS: f64 = 0.5;
+pub const LOW_BERTH_COST: f64 = 0.05;
+pub const FUEL_PROCESSOR_COST_PER_TON: f64 = 0.05;
+
+#[derive(Debug)]
+pub struct HullConfiguration {
+    pub name: &'static str,
+    pub cost_multiplier: f64,
+    pub streamlined: bool,
+}
+
+pub const HULL_CONFIGURATIONS: [HullConfiguration; 5] = [
+    HullConfiguration {
+        name: "standard",
+        cost_multiplier: 1.0,
+        streamlined: false,
+    },
+    HullConfiguration {
+        name: "streamlined",
+        cost_multiplier: 1.2,
+        streamlined: true,
+    },
+    HullConfiguration {
+        name: "sphere",
+        cost_multiplier: 1.1,
+        streamlined: false,
+    },
+    HullConfiguration {
+        name: "close",
+        cost_multiplier: 0.9,
+        streamlined: false,
+    },
+    HullConfiguration {
+        name: "dispersed",
+        cost_multiplier: 0.5,
+        streamlined: false,
+    },
+];
+
+/// Hull options: (name, cost multiplier, hull point multiplier)
+pub const HULL_OPTIONS: [(&str, f64, f64); 2] = [("reinforced", 1.5, 1.1), ("light", 0.75, 0.9)];
+
+#[derive(Debug)]
+pub struct PowerPlant {
+    pub name: &'static str,
+    pub tech_level: u8,
+    pub power_per_ton: f64,
+    pub cost_per_ton: f64,
+}
+
+pub const POWER_PLANTS: [PowerPlant; 4] = [
+    PowerPlant {
+        name: "fission",
+        tech_level: 6,
+        power_per_ton: 8.0,
+        cost_per_ton: 0.4,
+    },
+    PowerPlant {
+        name: "fusion_tl8",
+        tech_level: 8,
+        power_per_ton: 10.0,
+        cost_per_ton: 0.5,
+    },
+    PowerPlant {
+        name: "fusion_tl12",
+        tech_level: 12,
+        power_per_ton: 15.0,
+        cost_per_ton: 1.0,
+    },
+    PowerPlant {
+        name: "fusion_tl15",
+        tech_level: 15,
+        power_per_ton: 20.0,
+        cost_per_ton: 2.0,
+    },
+];
+
+#[derive(Debug)]
+pub struct Computer {
+    pub rating: u32,
+    pub tech_level: u8,
+    pub cost: f64,
+}
+
+pub const COMPUTERS: [Computer; 7] = [
+    Computer {
+        rating: 5,
+        tech_level: 7,
+        cost: 0.03,
+    },
+    Computer {
+        rating: 10,
+        tech_level: 9,
+        cost: 0.16,
+    },
+    Computer {
+        rating: 15,
+        tech_level: 11,
+        cost: 2.0,
+    },
+    Computer {
+        rating: 20,
+        tech_level: 12,
+        cost: 5.0,
+    },
+    Computer {
+        rating: 25,
+        tech_level: 13,
+        cost: 10.0,
+    },
+    Computer {
+        rating: 30,
+        tech_level: 14,
+        cost: 20.0,
+    },
+    Computer {
+        rating: 35,
+        tech_level: 15,
+        cost: 30.0,
+    },
+];
+
+#[derive(Debug)]
+pub struct Sensors {
+    pub name: &'static str,
+    pub tech_level: u8,
+    pub tons: f64,
+    pub cost: f64,
+    pub power: f64,
+}
+
+pub const SENSORS: [Sensors; 5] = [
+    Sensors {
+        name: "basic",
+        tech_level: 8,
+        tons: 0.0,
+        cost: 0.0,
+        power: 0.0,
+    },
+    Sensors {
+        name: "civilian",
+        tech_level: 9,
+        tons: 1.0,
+        cost: 3.0,
+        power: 1.0,
+    },
+    Sensors {
+        name: "military",
+        tech_level: 10,
+        tons: 2.0,
+        cost: 4.1,
+        power: 2.0,
+    },
+    Sensors {
+        name: "improved",
+        tech_level: 12,
+        tons: 3.0,
+        cost: 4.3,
+        power: 4.0,
+    },
+    Sensors {
+        name: "advanced",
+        tech_level: 15,
+        tons: 5.0,
+        cost: 5.3,
+        power: 6.0,
+    },
+];
+
+#[derive(Debug)]
+pub struct Armour {
+    pub name: &'static str,
+    pub tech_level: u8,
+    /// Percent of hull tonnage per point of armour
+    pub percent_per_point: f64,
+    pub cost_per_ton: f64,
+}
+
+pub const ARMOUR: [Armour; 3] = [
+    Armour {
+        name: "titanium_steel",
+        tech_level: 7,
+        percent_per_point: 2.5,
+        cost_per_ton: 0.05,
+    },
+    Armour {
+        name: "crystaliron",
+        tech_level: 10,
+        percent_per_point: 1.25,
+        cost_per_ton: 0.2,
+    },
+    Armour {
+        name: "bonded_superdense",
+        tech_level: 14,
+        percent_per_point: 0.8,
+        cost_per_ton: 0.5,
+    },
+];
+
+/// A turret, taking one ton and one hardpoint
+#[derive(Debug)]
+pub struct Turret {
+    pub name: &'static str,
+    pub tech_level: u8,
+    pub capacity: usize,
+    pub cost: f64,
+}
+
+pub const TURRETS: [Turret; 3] = [
+    Turret {
+        name: "single",
+        tech_level: 7,
+        capacity: 1,
+        cost: 0.2,
+    },
+    Turret {
+        name: "double",
+        tech_level: 8,
+        capacity: 2,
+        cost: 0.5,
+    },
+    Turret {
+        name: "triple",
+        tech_level: 9,
+        capacity: 3,
+        cost: 1.0,
+    },
+];
+
+#[derive(Debug)]
+pub struct Weapon {
+    pub name: &'static str,
+    pub tech_level: u8,
+    pub power: f64,
+    pub cost: f64,
+}
+
+pub const WEAPONS: [Weapon; 5] = [
+    Weapon {
+        name: "sandcaster",
+        tech_level: 7,
+        power: 0.0,
+        cost: 0.25,
+    },
+    Weapon {
+        name: "missile_rack",
+        tech_level: 7,
+        power: 0.0,
+        cost: 0.75,
+    },
+    Weapon {
+        name: "pulse_laser",
+        tech_level: 9,
+        power: 4.0,
+        cost: 1.0,
+    },
+    Weapon {
+        name: "beam_laser",
+        tech_level: 10,
+        power: 4.0,
+        cost: 0.5,
+    },
+    Weapon {
+        name: "particle_beam",
+        tech_level: 12,
+        power: 8.0,
+        cost: 4.0,
+    },
+];
+
+/// Tech level needed for a manoeuvre drive of the given thrust
+pub fn m_drive_tech_level(thrust: u32) -> Option<u8> {
+    match thrust {
+        0 => Some(0),
+        1 => Some(9),
+        2..=3 => Some(10),
+        4 => Some(11),
+        5..=6 => Some(12),
+        7..=9 => Some(13),
+        _ => None,
+    }
+}
+
+/// Tech level needed for a jump drive of the given rating
+pub fn j_drive_tech_level(jump: u32) -> Option<u8> {
+    match jump {
+        0 => Some(0),
+        1 => Some(9),
+        2 => Some(11),
+        3..=6 => Some(jump as u8 + 9),
+        _ => None,
+    }
+}
+
+/// Bridge size for a hull
+pub fn bridge_tons(hull_tons: f64) -> f64 {
+    match hull_tons {
+        t if t <= 50.0 => 3.0,
+        t if t < 100.0 => 6.0,
+        t if t <= 200.0 => 10.0,
+        t if t <= 1000.0 => 20.0,
+        t if t <= 2000.0 => 40.0,
+        _ => 60.0,
+    }
+}
+
+/// Find a named table entry, ignoring case and treating spaces as `_`
+pub fn lookup<'a, T>(table: &'a [T], name: &str, key: fn(&T) -> &str) -> Option<&'a T> {
+    let wanted = name.trim().to_lowercase().replace([' ', '-'], "_");
+    table.iter().find(|entry| key(entry) == wanted)
+}
+
+/// Names in a table, for error messages
+pub fn names<T>(table: &[T], key: fn(&T) -> &str) -> String {
+    table.iter().map(key).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,79 @@
+//! Design input: a hull and its component choices.
+
+use serde::Deserialize;
+
+fn default_tech_level() -> u8 {
+    12
+}
+
+fn default_configuration() -> String {
+    "standard".to_string()
+}
+
+fn default_sensors() -> String {
+    "basic".to_string()
+}
+
+/// Component choices for a ship
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShipSpec {
+    pub name: Option<String>,
+    pub tonnage: u32,
+    #[serde(default = "default_tech_level")]
+    pub tech_level: u8,
+    #[serde(default = "default_configuration")]
+    pub configuration: String,
+    /// reinforced or light
+    #[serde(default)]
+    pub hull_options: Vec<String>,
+    pub armour: Option<ArmourSpec>,
+    #[serde(default)]
+    pub thrust: u32,
+    #[serde(default)]
+    pub jump: u32,
+    /// Power plant type; the best fusion plant at the tech level if omitted
+    pub power_plant: Option<String>,
+    /// Power plant size; sized to demand if omitted
+    pub power_plant_tons: Option<f64>,
+    pub fuel_tons: Option<f64>,
+    /// Computer rating; the smallest that runs the jump drive if omitted
+    pub computer: Option<u32>,
+    #[serde(default = "default_sensors")]
+    pub sensors: String,
+    #[serde(default)]
+    pub staterooms: u32,
+    #[serde(default)]
+    pub low_berths: u32,
+    #[serde(default)]
+    pub fuel_processor_tons: f64,
+    #[serde(default)]
+    pub turrets: Vec<TurretSpec>,
+    /// Components from the rulebook that aren't in the built-in tables
+    #[serde(default)]
+    pub extras: Vec<ExtraSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArmourSpec {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub points: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TurretSpec {
+    /// single, double or triple
+    pub mount: String,
+    pub weapons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtraSpec {
+    pub name: String,
+    #[serde(default)]
+    pub tons: f64,
+    #[serde(default)]
+    pub cost_mcr: f64,
+    #[serde(default)]
+    pub power: f64,
+}
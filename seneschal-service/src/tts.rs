@@ -0,0 +1,151 @@
+//! Text-to-speech client.
+//!
+//! Speech is synthesized by an external TTS server, configured in the `tts`
+//! section of the dynamic config. Two HTTP APIs are supported, both of which
+//! answer with a WAV file:
+//!
+//! - Piper (`python -m piper.http_server`): `POST /` with a JSON body of
+//!   `text` and an optional `voice`
+//! - Coqui TTS (`tts-server`): `GET /api/tts?text=...&speaker_id=...`
+
+use std::time::Duration;
+
+use reqwest::{Client, RequestBuilder};
+
+use crate::config::TtsConfig;
+use crate::error::{ServiceError, ServiceResult};
+
+/// Media type of synthesized speech
+pub const SPEECH_MIME_TYPE: &str = "audio/wav";
+
+/// A TTS server API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TtsEngine {
+    Piper,
+    Coqui,
+}
+
+impl TtsEngine {
+    fn parse(engine: &str) -> Option<Self> {
+        match engine.trim().to_lowercase().as_str() {
+            "piper" => Some(Self::Piper),
+            "coqui" => Some(Self::Coqui),
+            _ => None,
+        }
+    }
+}
+
+/// The request synthesizing `text` on the configured server
+fn speech_request(
+    client: &Client,
+    config: &TtsConfig,
+    text: &str,
+) -> ServiceResult<RequestBuilder> {
+    let base_url = config.url.trim().trim_end_matches('/');
+    if base_url.is_empty() {
+        return Err(ServiceError::Config {
+            message: "Text-to-speech is not configured (set tts.url)".to_string(),
+        });
+    }
+    let engine = TtsEngine::parse(&config.engine).ok_or_else(|| ServiceError::Config {
+        message: format!(
+            "Unknown TTS engine '{}' (expected piper or coqui)",
+            config.engine
+        ),
+    })?;
+    let voice = Some(config.voice.trim()).filter(|v| !v.is_empty());
+
+    Ok(match engine {
+        TtsEngine::Piper => {
+            let mut body = serde_json::json!({ "text": text });
+            if let Some(voice) = voice {
+                body["voice"] = serde_json::Value::String(voice.to_string());
+            }
+            client.post(format!("{}/", base_url)).json(&body)
+        }
+        TtsEngine::Coqui => {
+            let mut query = vec![("text", text)];
+            if let Some(voice) = voice {
+                query.push(("speaker_id", voice));
+            }
+            client.get(format!("{}/api/tts", base_url)).query(&query)
+        }
+    })
+}
+
+/// Synthesize `text` to WAV audio
+pub async fn synthesize(config: &TtsConfig, text: &str) -> ServiceResult<Vec<u8>> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .build()
+        .map_err(|e| ServiceError::Internal {
+            message: format!("Failed to create TTS client: {}", e),
+        })?;
+
+    let response = speech_request(&client, config, text)?
+        .send()
+        .await
+        .map_err(|e| ServiceError::Internal {
+            message: format!("TTS server request failed: {}", e),
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(ServiceError::Internal {
+            message: format!("TTS server returned {}: {}", status, message.trim()),
+        });
+    }
+
+    let audio = response.bytes().await.map_err(|e| ServiceError::Internal {
+        message: format!("Failed to read TTS response: {}", e),
+    })?;
+    if audio.is_empty() {
+        return Err(ServiceError::Internal {
+            message: "TTS server returned no audio".to_string(),
+        });
+    }
+    Ok(audio.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(engine: &str, voice: &str) -> TtsConfig {
+        TtsConfig {
+            url: "http://localhost:5002/".to_string(),
+            engine: engine.to_string(),
+            voice: voice.to_string(),
+            timeout_secs: 5,
+        }
+    }
+
+    #[test]
+    fn test_speech_request() {
+        let client = Client::new();
+
+        let request = speech_request(&client, &config("coqui", "p225"), "Jump in 2 days")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::GET);
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:5002/api/tts?text=Jump+in+2+days&speaker_id=p225"
+        );
+
+        let request = speech_request(&client, &config("Piper", ""), "Hello")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(request.url().as_str(), "http://localhost:5002/");
+        let body = request.body().and_then(|b| b.as_bytes()).unwrap();
+        assert_eq!(body, br#"{"text":"Hello"}"#);
+
+        assert!(speech_request(&client, &config("espeak", ""), "Hello").is_err());
+        let mut disabled = config("piper", "");
+        disabled.url = String::new();
+        assert!(speech_request(&client, &disabled, "Hello").is_err());
+    }
+}
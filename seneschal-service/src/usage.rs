@@ -0,0 +1,146 @@
+//! Ollama request accounting.
+//!
+//! Every request to Ollama (chat, vision and embeddings) is counted per
+//! model in memory since startup, for the admin dashboard. With a database
+//! attached, each request is also logged with its token counts, duration
+//! and attribution to the `model_usage` table:
+//!
+//! - feature: what the request was for (the routed task, e.g. `captioning`,
+//!   or `embedding`)
+//! - tool, conversation and user: the MCP tool call it ran under, its
+//!   session, and the client name the session was started with (MCP has no
+//!   user identity). Background work such as ingestion has none of these.
+//!
+//! Usage reports group the log to show what is keeping the model host busy.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::db::{Database, UsageRecord};
+
+/// Feature name of embedding requests
+pub const EMBEDDING_FEATURE: &str = "embedding";
+
+/// Tokens Ollama reported for a request, when it reports them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenCounts {
+    pub prompt: Option<u64>,
+    pub completion: Option<u64>,
+}
+
+/// Request counts and latency for one model since startup
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelUsage {
+    pub requests: u64,
+    pub failures: u64,
+    pub total_duration_ms: u64,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+/// Per-model usage, shared by every component that calls Ollama
+#[derive(Default)]
+pub struct ModelUsageTracker {
+    models: DashMap<String, ModelUsage>,
+    log: Option<Arc<Database>>,
+}
+
+impl ModelUsageTracker {
+    /// A tracker that also logs each request to the database
+    pub fn with_log(db: Arc<Database>) -> Self {
+        Self {
+            models: DashMap::new(),
+            log: Some(db),
+        }
+    }
+
+    /// Record one request to `model` for `feature` that began at `started`
+    pub fn record(
+        &self,
+        model: &str,
+        feature: &str,
+        started: Instant,
+        success: bool,
+        tokens: TokenCounts,
+    ) {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let mut usage = self.models.entry(model.to_string()).or_default();
+        usage.requests += 1;
+        if !success {
+            usage.failures += 1;
+        }
+        usage.total_duration_ms += duration_ms;
+        usage.last_used = Some(Utc::now());
+        drop(usage);
+
+        crate::call_trace::record_stage(&format!("ollama {}", model), started, success);
+
+        if let Some(db) = &self.log {
+            let (tool, conversation_id, user_name) = match crate::call_trace::current_call() {
+                Some((tool, session_id, client)) => (Some(tool), session_id, client),
+                None => (None, None, None),
+            };
+            let record = UsageRecord {
+                model: model.to_string(),
+                feature: feature.to_string(),
+                tool,
+                conversation_id,
+                user_name,
+                prompt_tokens: tokens.prompt,
+                completion_tokens: tokens.completion,
+                duration_ms,
+                success,
+                created_at: Utc::now(),
+            };
+            if let Err(e) = db.insert_usage_record(&record) {
+                warn!(model = %model, error = %e, "Failed to log model usage");
+            }
+        }
+    }
+
+    /// Usage for every model seen so far, by name
+    pub fn snapshot(&self) -> BTreeMap<String, ModelUsage> {
+        self.models
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_totals() {
+        let tracker = ModelUsageTracker::default();
+        let started = Instant::now();
+        tracker.record("qwen3:14b", "chat", started, true, TokenCounts::default());
+        tracker.record(
+            "qwen3:14b",
+            "summarization",
+            started,
+            false,
+            TokenCounts::default(),
+        );
+        tracker.record(
+            "nomic-embed-text",
+            EMBEDDING_FEATURE,
+            started,
+            true,
+            TokenCounts::default(),
+        );
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot["qwen3:14b"].requests, 2);
+        assert_eq!(snapshot["qwen3:14b"].failures, 1);
+        assert_eq!(snapshot["nomic-embed-text"].failures, 0);
+        assert!(snapshot["nomic-embed-text"].last_used.is_some());
+    }
+}
@@ -8,8 +8,13 @@ mod broadcast;
 mod handlers;
 mod manager;
 pub mod messages;
+mod routing;
 
 // Re-export public types
 pub use handlers::handle_ws_connection;
 pub use manager::WebSocketManager;
-pub use messages::{CaptioningProgressUpdate, DocumentProgressUpdate, ServerMessage};
+pub use messages::{
+    CaptioningProgressUpdate, DocumentProgressUpdate, ModelPullUpdate, ServerMessage,
+    UploadProgressUpdate,
+};
+pub use routing::GmRoute;
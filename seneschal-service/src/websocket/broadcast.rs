@@ -1,15 +1,69 @@
 //! Broadcast functions for WebSocket updates.
 //!
 //! Contains functions for broadcasting document progress updates,
-//! captioning progress, and other real-time notifications to
-//! subscribed clients.
+//! captioning progress, upload progress, model pulls, and other real-time
+//! notifications to subscribed clients.
 
 use tracing::debug;
 
 use super::manager::WebSocketManager;
-use super::messages::{CaptioningProgressUpdate, DocumentProgressUpdate, ServerMessage};
+use super::messages::{
+    CaptioningProgressUpdate, DocumentProgressUpdate, ModelPullUpdate, ServerMessage,
+    UploadProgressUpdate,
+};
 
 impl WebSocketManager {
+    /// Broadcast a message to the player channel: authenticated non-GM
+    /// connections with at least `min_role` in `world_id` (connections that
+    /// didn't name a world included). Returns how many were sent it.
+    pub fn broadcast_to_players(
+        &self,
+        min_role: u8,
+        world_id: Option<&str>,
+        msg: ServerMessage,
+    ) -> usize {
+        let mut sent_count = 0;
+
+        for entry in self.connections.iter() {
+            let conn = entry.value();
+            let same_world = match (world_id, conn.world_id.as_deref()) {
+                (Some(world), Some(own)) => world == own,
+                _ => true,
+            };
+            if conn.authenticated
+                && same_world
+                && conn
+                    .user_role
+                    .is_some_and(|role| role >= min_role && role < 4)
+                && conn.tx.send(msg.clone()).is_ok()
+            {
+                sent_count += 1;
+            }
+        }
+
+        debug!(sent_count = sent_count, "Broadcast to player connections");
+        sent_count
+    }
+
+    /// Broadcast a message to all authenticated GM connections. Returns how
+    /// many were sent it.
+    pub fn broadcast_to_gms(&self, msg: ServerMessage) -> usize {
+        let mut sent_count = 0;
+
+        for entry in self.connections.iter() {
+            let conn = entry.value();
+            if conn.authenticated
+                && conn.user_role.is_some_and(|role| role >= 4)
+                && conn.tx.send(msg.clone()).is_ok()
+            {
+                sent_count += 1;
+            }
+        }
+
+        debug!(sent_count = sent_count, "Broadcast to GM connections");
+        sent_count
+    }
+
     /// Broadcast a document progress update to all subscribed connections
     pub fn broadcast_document_update(&self, update: DocumentProgressUpdate) {
         let msg: ServerMessage = update.into();
@@ -55,4 +109,51 @@ impl WebSocketManager {
             );
         }
     }
+
+    /// Broadcast an upload progress update to all subscribed connections.
+    /// Clients pick out their own uploads by token.
+    pub fn broadcast_upload_update(&self, update: UploadProgressUpdate) {
+        let msg: ServerMessage = update.into();
+        let mut sent_count = 0;
+
+        for entry in self.connections.iter() {
+            let conn = entry.value();
+            if conn.authenticated
+                && conn.subscribed_to_documents
+                && conn.tx.send(msg.clone()).is_ok()
+            {
+                sent_count += 1;
+            }
+        }
+
+        if sent_count > 0 {
+            debug!(
+                sent_count = sent_count,
+                "Broadcast upload update to connections"
+            );
+        }
+    }
+
+    /// Broadcast a model pull progress update to all GM connections
+    pub fn broadcast_model_pull_update(&self, update: ModelPullUpdate) {
+        let msg: ServerMessage = update.into();
+        let mut sent_count = 0;
+
+        for entry in self.connections.iter() {
+            let conn = entry.value();
+            if conn.authenticated
+                && conn.user_role.is_some_and(|role| role >= 4)
+                && conn.tx.send(msg.clone()).is_ok()
+            {
+                sent_count += 1;
+            }
+        }
+
+        if sent_count > 0 {
+            debug!(
+                sent_count = sent_count,
+                "Broadcast model pull update to connections"
+            );
+        }
+    }
 }
@@ -10,7 +10,10 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
-use crate::service::SeneschalService;
+use crate::log_stream::LogFilter;
+use crate::service::{ApprovalDecision, SeneschalService};
+use crate::tool_budget::ToolBudgetOverride;
+use crate::tools::AccessLevel;
 
 use super::manager::WebSocketManager;
 use super::messages::{ClientMessage, ServerMessage};
@@ -19,11 +22,13 @@ use super::messages::{ClientMessage, ServerMessage};
 ///
 /// This function is called when a WebSocket connection is established.
 /// It manages the connection lifecycle, processes incoming messages,
-/// and forwards outgoing messages.
+/// and forwards outgoing messages. `gm_allowed` is false when GM connections
+/// need a client certificate and this one didn't present a verified one.
 pub async fn handle_ws_connection(
     socket: WebSocket,
     ws_manager: Arc<WebSocketManager>,
     service: Arc<SeneschalService>,
+    gm_allowed: bool,
 ) {
     let session_id = uuid::Uuid::new_v4().to_string();
     info!(session_id = %session_id, "New WebSocket connection");
@@ -67,6 +72,7 @@ pub async fn handle_ws_connection(
                     &text,
                     ws_manager_for_recv.clone(),
                     service_for_recv.clone(),
+                    gm_allowed,
                 )
                 .await;
             }
@@ -78,6 +84,7 @@ pub async fn handle_ws_connection(
                         &text,
                         ws_manager_for_recv.clone(),
                         service_for_recv.clone(),
+                        gm_allowed,
                     )
                     .await;
                 }
@@ -102,6 +109,7 @@ pub async fn handle_ws_connection(
 
     // Clean up
     ws_manager.remove_connection(&session_id);
+    service.pending_approvals.cancel_for_session(&session_id);
     send_task.abort();
     info!(session_id = %session_id, "WebSocket connection closed");
 }
@@ -112,6 +120,7 @@ async fn handle_client_message(
     text: &str,
     ws_manager: Arc<WebSocketManager>,
     service: Arc<SeneschalService>,
+    gm_allowed: bool,
 ) {
     let msg: ClientMessage = match serde_json::from_str(text) {
         Ok(msg) => msg,
@@ -140,6 +149,11 @@ async fn handle_client_message(
             user_name,
             role,
             session_id: client_session_id,
+            owned_actor_ids,
+            character_id,
+            world_id,
+            allow_write_tools,
+            system_id,
         } => {
             debug!(
                 session_id = %session_id,
@@ -147,11 +161,40 @@ async fn handle_client_message(
                 user_name = %user_name,
                 role = role,
                 client_session_id = ?client_session_id,
+                world_id = ?world_id,
+                system_id = ?system_id,
                 "Processing auth message"
             );
 
+            if role >= 4 && !gm_allowed {
+                warn!(
+                    session_id = %session_id,
+                    user_id = %user_id,
+                    "Rejected GM authentication without a client certificate"
+                );
+                ws_manager.send_to(
+                    session_id,
+                    ServerMessage::AuthResponse {
+                        success: false,
+                        session_id: session_id.to_string(),
+                        message: Some("Client certificate required for GM connections".to_string()),
+                    },
+                );
+                return;
+            }
+
+            // GM clients tell us which game system their world runs
+            if role >= 4
+                && let (Some(world_id), Some(system_id)) = (&world_id, &system_id)
+                && let Err(e) = service.set_world_system(world_id, system_id)
+            {
+                warn!(world_id = %world_id, error = %e, "Failed to record world game system");
+            }
+
             // Authenticate the connection
             ws_manager.authenticate(session_id, user_id.clone(), user_name, role);
+            ws_manager.set_character_context(session_id, owned_actor_ids, character_id);
+            ws_manager.set_routing_context(session_id, world_id, allow_write_tools);
 
             // Send success response
             ws_manager.send_to(
@@ -204,12 +247,309 @@ async fn handle_client_message(
                     .await;
             }
         }
+        ClientMessage::JournalSync {
+            journal_id,
+            name,
+            pages,
+        } => {
+            if !require_gm(session_id, &ws_manager, "sync journal entries") {
+                return;
+            }
+
+            let response = match service
+                .sync_fvtt_journal(
+                    &journal_id,
+                    &name,
+                    &pages,
+                    ws_manager.world_id(session_id).as_deref(),
+                )
+                .await
+            {
+                Ok((status, document_id)) => ServerMessage::JournalSyncResult {
+                    journal_id,
+                    status: status.as_str().to_string(),
+                    document_id,
+                    error: None,
+                },
+                Err(e) => {
+                    warn!(journal_id = %journal_id, error = %e, "Failed to sync journal entry");
+                    ServerMessage::JournalSyncResult {
+                        journal_id,
+                        status: "failed".to_string(),
+                        document_id: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+            ws_manager.send_to(session_id, response);
+        }
+        ClientMessage::JournalRemove { journal_id } => {
+            if !require_gm(session_id, &ws_manager, "sync journal entries") {
+                return;
+            }
+
+            let (status, error) = match service.remove_fvtt_journal(&journal_id) {
+                Ok(_) => ("removed", None),
+                Err(e) => {
+                    warn!(journal_id = %journal_id, error = %e, "Failed to remove journal entry");
+                    ("failed", Some(e.to_string()))
+                }
+            };
+            ws_manager.send_to(
+                session_id,
+                ServerMessage::JournalSyncResult {
+                    journal_id,
+                    status: status.to_string(),
+                    document_id: None,
+                    error,
+                },
+            );
+        }
+        ClientMessage::SetConversationMode {
+            session_id: conversation,
+            mode,
+        } => {
+            if !require_gm(session_id, &ws_manager, "set conversation modes") {
+                return;
+            }
+
+            let modes = &service.conversation_modes;
+            if let Some(conversation) = conversation.as_deref()
+                && !modes.is_open(conversation)
+            {
+                ws_manager.send_to(
+                    session_id,
+                    ServerMessage::Error {
+                        code: "not_found".to_string(),
+                        message: format!("No open MCP conversation {}", conversation),
+                        recoverable: true,
+                    },
+                );
+                return;
+            }
+
+            modes.set(conversation.as_deref(), mode);
+            info!(
+                conversation = ?conversation,
+                mode = mode.as_str(),
+                "Conversation mode set"
+            );
+            send_conversation_modes(session_id, &ws_manager, &service);
+        }
+        ClientMessage::GetConversationModes => {
+            if !require_gm(session_id, &ws_manager, "list conversation modes") {
+                return;
+            }
+            send_conversation_modes(session_id, &ws_manager, &service);
+        }
+        ClientMessage::SetToolBudget {
+            session_id: conversation,
+            tool_call_pause_threshold,
+            time_pause_threshold_secs,
+            hard_timeout_secs,
+        } => {
+            if !require_gm(session_id, &ws_manager, "set tool budgets") {
+                return;
+            }
+
+            if let Some(conversation) = conversation.as_deref()
+                && !service.conversation_modes.is_open(conversation)
+            {
+                ws_manager.send_to(
+                    session_id,
+                    ServerMessage::Error {
+                        code: "not_found".to_string(),
+                        message: format!("No open MCP conversation {}", conversation),
+                        recoverable: true,
+                    },
+                );
+                return;
+            }
+
+            let overrides = ToolBudgetOverride {
+                tool_call_pause_threshold,
+                time_pause_threshold_secs,
+                hard_timeout_secs,
+            };
+            service.tool_budgets.set(conversation.as_deref(), overrides);
+            info!(
+                conversation = ?conversation,
+                ?overrides,
+                "Tool budget set"
+            );
+            send_tool_budgets(session_id, &ws_manager, &service);
+        }
+        ClientMessage::GetToolBudgets => {
+            if !require_gm(session_id, &ws_manager, "list tool budgets") {
+                return;
+            }
+            send_tool_budgets(session_id, &ws_manager, &service);
+        }
+        ClientMessage::RegisterTools { tools } => {
+            if !require_gm(session_id, &ws_manager, "register tools") {
+                return;
+            }
+            let Some(world_id) = ws_manager.world_id(session_id) else {
+                ws_manager.send_to(
+                    session_id,
+                    ServerMessage::Error {
+                        code: "invalid_request".to_string(),
+                        message: "Tools can only be registered by a connection with a world"
+                            .to_string(),
+                        recoverable: true,
+                    },
+                );
+                return;
+            };
+
+            let (registered, rejected) = service.client_tools.register(&world_id, tools);
+            for tool in &rejected {
+                warn!(world_id = %world_id, tool = %tool.name, reason = %tool.reason, "Client tool refused");
+            }
+            info!(world_id = %world_id, count = registered.len(), "Client tools registered");
+            ws_manager.send_to(
+                session_id,
+                ServerMessage::ToolsRegistered {
+                    registered,
+                    rejected,
+                },
+            );
+        }
+        ClientMessage::AssetUsage {
+            document_type,
+            document_id,
+            document_name,
+            paths,
+        } => {
+            if !require_gm(session_id, &ws_manager, "report asset usage") {
+                return;
+            }
+            if let Err(e) = service.record_asset_usage(
+                ws_manager.world_id(session_id),
+                &document_type,
+                &document_id,
+                document_name.as_deref(),
+                &paths,
+            ) {
+                warn!(document_id = %document_id, error = %e, "Failed to record asset usage");
+            }
+        }
+        ClientMessage::ShareAnswer {
+            content,
+            title,
+            access_level,
+            document_ids,
+        } => {
+            if !require_gm(session_id, &ws_manager, "share answers with players") {
+                return;
+            }
+            match service.share_answer_with_players(
+                &content,
+                title,
+                access_level.map(AccessLevel::from_u8),
+                &document_ids,
+                ws_manager.world_id(session_id).as_deref(),
+            ) {
+                Ok(shared) => ws_manager.send_to(
+                    session_id,
+                    ServerMessage::ShareAnswerResult {
+                        share_id: shared.share_id,
+                        access_level: shared.access_level as u8,
+                        recipients: shared.recipients,
+                    },
+                ),
+                Err(e) => ws_manager.send_to(
+                    session_id,
+                    ServerMessage::Error {
+                        code: "share_failed".to_string(),
+                        message: e.to_string(),
+                        recoverable: true,
+                    },
+                ),
+            }
+        }
+        ClientMessage::ToolApproval {
+            request_id,
+            approved,
+            reason,
+        } => {
+            if !require_gm(session_id, &ws_manager, "approve tool calls") {
+                return;
+            }
+            service.handle_tool_approval(&request_id, ApprovalDecision { approved, reason });
+        }
+        ClientMessage::SubscribeLogs { level, targets } => {
+            if !require_gm(session_id, &ws_manager, "watch the service log") {
+                return;
+            }
+            match LogFilter::new(level.as_deref(), targets) {
+                Ok(filter) => {
+                    debug!(session_id = %session_id, filter = ?filter, "Subscribed to log stream");
+                    ws_manager.start_log_stream(session_id, filter);
+                }
+                Err(message) => ws_manager.send_to(
+                    session_id,
+                    ServerMessage::Error {
+                        code: "invalid_log_filter".to_string(),
+                        message,
+                        recoverable: true,
+                    },
+                ),
+            }
+        }
+        ClientMessage::UnsubscribeLogs => {
+            ws_manager.stop_log_stream(session_id);
+            debug!(session_id = %session_id, "Unsubscribed from log stream");
+        }
+    }
+}
+
+fn send_conversation_modes(
+    session_id: &str,
+    ws_manager: &WebSocketManager,
+    service: &SeneschalService,
+) {
+    ws_manager.send_to(
+        session_id,
+        ServerMessage::ConversationModes {
+            default_mode: service.conversation_modes.default_mode(),
+            sessions: service.conversation_modes.sessions(),
+        },
+    );
+}
+
+fn send_tool_budgets(session_id: &str, ws_manager: &WebSocketManager, service: &SeneschalService) {
+    let config = service.runtime_config.dynamic();
+    ws_manager.send_to(
+        session_id,
+        ServerMessage::ToolBudgets {
+            default_budget: service.tool_budgets.budget(None, &config.agentic_loop),
+            sessions: service.tool_budgets.sessions(&config.agentic_loop),
+        },
+    );
+}
+
+/// Reject a GM-only request from a non-GM connection, returning whether it may proceed
+fn require_gm(session_id: &str, ws_manager: &WebSocketManager, action: &str) -> bool {
+    if ws_manager.is_gm(session_id) {
+        return true;
     }
+
+    ws_manager.send_to(
+        session_id,
+        ServerMessage::Error {
+            code: "forbidden".to_string(),
+            message: format!("Only GM connections may {}", action),
+            recoverable: true,
+        },
+    );
+    false
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conversation_mode::ConversationMode;
 
     #[test]
     fn test_client_message_deserialization() {
@@ -221,8 +561,18 @@ mod tests {
                 user_name,
                 role,
                 session_id,
+                owned_actor_ids,
+                character_id,
+                world_id,
+                allow_write_tools,
+                system_id,
             } => {
                 assert_eq!(user_id, "user123");
+                assert!(owned_actor_ids.is_empty());
+                assert!(character_id.is_none());
+                assert!(world_id.is_none());
+                assert!(!allow_write_tools);
+                assert!(system_id.is_none());
                 assert_eq!(user_name, "Test User");
                 assert_eq!(role, 4);
                 assert!(session_id.is_none());
@@ -242,6 +592,19 @@ mod tests {
         let msg: ClientMessage = serde_json::from_str(unsub_json).unwrap();
         assert!(matches!(msg, ClientMessage::UnsubscribeDocuments));
 
+        let journal_json = r#"{"type":"journal_sync","journal_id":"j1","name":"Lore","pages":[{"name":"Intro","html":"<p>Hi</p>"}]}"#;
+        let msg: ClientMessage = serde_json::from_str(journal_json).unwrap();
+        match msg {
+            ClientMessage::JournalSync {
+                journal_id, pages, ..
+            } => {
+                assert_eq!(journal_id, "j1");
+                assert_eq!(pages.len(), 1);
+                assert!(pages[0].access_level.is_none());
+            }
+            _ => panic!("Expected JournalSync"),
+        }
+
         let tool_result_json = r#"{"type":"tool_result","conversation_id":"mcp:123","tool_call_id":"tc_0","result":{"success":true}}"#;
         let msg: ClientMessage = serde_json::from_str(tool_result_json).unwrap();
         match msg {
@@ -256,6 +619,48 @@ mod tests {
             }
             _ => panic!("Expected ToolResult"),
         }
+
+        let mode_json = r#"{"type":"set_conversation_mode","mode":"no_tools"}"#;
+        let msg: ClientMessage = serde_json::from_str(mode_json).unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::SetConversationMode {
+                session_id: None,
+                mode: ConversationMode::NoTools
+            }
+        ));
+
+        let usage_json = r#"{"type":"asset_usage","document_type":"scene","document_id":"s1"}"#;
+        let msg: ClientMessage = serde_json::from_str(usage_json).unwrap();
+        match msg {
+            ClientMessage::AssetUsage {
+                document_type,
+                document_name,
+                paths,
+                ..
+            } => {
+                assert_eq!(document_type, "scene");
+                assert!(document_name.is_none());
+                assert!(paths.is_empty());
+            }
+            _ => panic!("Expected AssetUsage"),
+        }
+
+        let share_json = r#"{"type":"share_answer","content":"<p>Walston</p>"}"#;
+        let msg: ClientMessage = serde_json::from_str(share_json).unwrap();
+        match msg {
+            ClientMessage::ShareAnswer {
+                title,
+                access_level,
+                document_ids,
+                ..
+            } => {
+                assert!(title.is_none());
+                assert!(access_level.is_none());
+                assert!(document_ids.is_empty());
+            }
+            _ => panic!("Expected ShareAnswer"),
+        }
     }
 
     #[test]
@@ -291,6 +696,7 @@ mod tests {
             id: "tc_0".to_string(),
             tool: "search".to_string(),
             args: serde_json::json!({"query": "test"}),
+            correlation_id: None,
         };
         let json = serde_json::to_string(&tool_call).unwrap();
         assert!(json.contains(r#""type":"chat_tool_call""#));
@@ -3,11 +3,16 @@
 //! Handles connection lifecycle, authentication, and state tracking
 //! for all active WebSocket connections.
 
+use std::sync::atomic::AtomicUsize;
+
+use chrono::Utc;
 use dashmap::DashMap;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::AbortHandle;
 use tracing::debug;
 
 use super::messages::ServerMessage;
+use crate::log_stream::{self, LogFilter, LogRecord};
 
 /// State for a single WebSocket connection
 pub(crate) struct ConnectionState {
@@ -16,16 +21,35 @@ pub(crate) struct ConnectionState {
     pub(crate) user_id: Option<String>,
     pub(crate) user_name: Option<String>,
     pub(crate) user_role: Option<u8>,
+    pub(crate) owned_actor_ids: Vec<String>,
+    pub(crate) character_id: Option<String>,
+    pub(crate) world_id: Option<String>,
+    pub(crate) allow_write_tools: bool,
     pub(crate) tx: mpsc::UnboundedSender<ServerMessage>,
     pub(crate) subscribed_to_documents: bool,
+    /// Task forwarding the live log to this connection, while it watches
+    pub(crate) log_stream: Option<AbortHandle>,
     pub(crate) authenticated: bool,
 }
 
+/// A connected non-GM user and the actors they play
+#[derive(Debug, Clone)]
+pub struct ConnectedPlayer {
+    pub user_id: String,
+    pub user_name: String,
+    pub character_id: Option<String>,
+    pub owned_actor_ids: Vec<String>,
+}
+
 /// Manager for all WebSocket connections
 ///
 /// Handles connection lifecycle and message broadcasting.
 pub struct WebSocketManager {
     pub(crate) connections: DashMap<String, ConnectionState>,
+    /// GM connection each MCP session's external tool calls were last routed to
+    pub(crate) gm_affinity: DashMap<String, String>,
+    /// Round-robin position for external tool calls without affinity
+    pub(crate) next_gm: AtomicUsize,
 }
 
 impl Default for WebSocketManager {
@@ -39,6 +63,8 @@ impl WebSocketManager {
     pub fn new() -> Self {
         Self {
             connections: DashMap::new(),
+            gm_affinity: DashMap::new(),
+            next_gm: AtomicUsize::new(0),
         }
     }
 
@@ -56,8 +82,13 @@ impl WebSocketManager {
                 user_id: None,
                 user_name: None,
                 user_role: None,
+                owned_actor_ids: Vec::new(),
+                character_id: None,
+                world_id: None,
+                allow_write_tools: false,
                 tx,
                 subscribed_to_documents: false,
+                log_stream: None,
                 authenticated: false,
             },
         );
@@ -66,7 +97,12 @@ impl WebSocketManager {
     /// Remove a connection
     pub(crate) fn remove_connection(&self, session_id: &str) {
         debug!(session_id = %session_id, "Removing WebSocket connection");
-        self.connections.remove(session_id);
+        if let Some((_, conn)) = self.connections.remove(session_id)
+            && let Some(log_stream) = conn.log_stream
+        {
+            log_stream.abort();
+        }
+        self.gm_affinity.retain(|_, gm| gm != session_id);
     }
 
     /// Authenticate a connection
@@ -88,6 +124,45 @@ impl WebSocketManager {
         }
     }
 
+    /// Record the actors a connection's user owns and plays
+    pub(crate) fn set_character_context(
+        &self,
+        session_id: &str,
+        owned_actor_ids: Vec<String>,
+        character_id: Option<String>,
+    ) -> bool {
+        if let Some(mut conn) = self.connections.get_mut(session_id) {
+            conn.owned_actor_ids = owned_actor_ids;
+            conn.character_id = character_id;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Record the world a connection is in and whether it accepts write tools
+    pub(crate) fn set_routing_context(
+        &self,
+        session_id: &str,
+        world_id: Option<String>,
+        allow_write_tools: bool,
+    ) -> bool {
+        if let Some(mut conn) = self.connections.get_mut(session_id) {
+            conn.world_id = world_id;
+            conn.allow_write_tools = allow_write_tools;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The world a connection is in, if it said
+    pub(crate) fn world_id(&self, session_id: &str) -> Option<String> {
+        self.connections
+            .get(session_id)
+            .and_then(|conn| conn.world_id.clone())
+    }
+
     /// Set document subscription status for a connection
     pub(crate) fn set_document_subscription(&self, session_id: &str, subscribed: bool) {
         if let Some(mut conn) = self.connections.get_mut(session_id) {
@@ -100,6 +175,57 @@ impl WebSocketManager {
         }
     }
 
+    /// Stream log events matching `filter` to a connection, replacing any
+    /// filter it was watching with before
+    pub(crate) fn start_log_stream(&self, session_id: &str, filter: LogFilter) {
+        let Some(mut conn) = self.connections.get_mut(session_id) else {
+            return;
+        };
+        let tx = conn.tx.clone();
+        let mut records = log_stream::subscribe();
+        let task = tokio::spawn(async move {
+            loop {
+                let record = match records.recv().await {
+                    Ok(record) if filter.matches(&record) => record,
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => LogRecord {
+                        timestamp: Utc::now(),
+                        level: "WARN".to_string(),
+                        target: module_path!().to_string(),
+                        message: format!(
+                            "Skipped {} log events while the connection caught up",
+                            skipped
+                        ),
+                        fields: String::new(),
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if tx.send(ServerMessage::LogEvent(record)).is_err() {
+                    break;
+                }
+            }
+        });
+        if let Some(previous) = conn.log_stream.replace(task.abort_handle()) {
+            previous.abort();
+        }
+    }
+
+    /// Stop streaming log events to a connection
+    pub(crate) fn stop_log_stream(&self, session_id: &str) {
+        if let Some(mut conn) = self.connections.get_mut(session_id)
+            && let Some(log_stream) = conn.log_stream.take()
+        {
+            log_stream.abort();
+        }
+    }
+
+    /// Check whether a connection is authenticated with GM role (4+)
+    pub(crate) fn is_gm(&self, session_id: &str) -> bool {
+        self.connections
+            .get(session_id)
+            .is_some_and(|conn| conn.authenticated && conn.user_role.is_some_and(|r| r >= 4))
+    }
+
     /// Send a message to a specific connection
     pub fn send_to(&self, session_id: &str, msg: ServerMessage) {
         if let Some(conn) = self.connections.get(session_id)
@@ -124,18 +250,28 @@ impl WebSocketManager {
             .count()
     }
 
-    /// Get first available authenticated GM connection for MCP routing
-    ///
-    /// Returns the session_id of an authenticated connection with GM role (4+),
-    /// or None if no GM is currently connected.
-    pub fn get_any_gm_connection(&self) -> Option<String> {
+    /// Get the connected non-GM users, one entry per user
+    pub fn connected_players(&self) -> Vec<ConnectedPlayer> {
+        let mut players: Vec<ConnectedPlayer> = Vec::new();
         for entry in self.connections.iter() {
             let conn = entry.value();
-            if conn.authenticated && conn.user_role.is_some_and(|r| r >= 4) {
-                return Some(entry.key().clone());
+            if !conn.authenticated || conn.user_role.is_none_or(|r| r >= 4) {
+                continue;
+            }
+            let (Some(user_id), Some(user_name)) = (&conn.user_id, &conn.user_name) else {
+                continue;
+            };
+            if players.iter().any(|p| &p.user_id == user_id) {
+                continue;
             }
+            players.push(ConnectedPlayer {
+                user_id: user_id.clone(),
+                user_name: user_name.clone(),
+                character_id: conn.character_id.clone(),
+                owned_actor_ids: conn.owned_actor_ids.clone(),
+            });
         }
-        None
+        players
     }
 }
 
@@ -168,4 +304,24 @@ mod tests {
         manager.remove_connection("session1");
         assert_eq!(manager.connection_count(), 0);
     }
+
+    #[test]
+    fn test_broadcast_to_players_by_world() {
+        let manager = WebSocketManager::new();
+        let mut receivers = Vec::new();
+        for (session, world) in [("p1", Some("varn")), ("p2", Some("regina")), ("p3", None)] {
+            let (tx, rx) = mpsc::unbounded_channel();
+            receivers.push(rx);
+            manager.add_connection(session.to_string(), tx);
+            manager.authenticate(session, session.to_string(), session.to_string(), 1);
+            manager.set_routing_context(session, world.map(str::to_string), false);
+        }
+
+        let sent =
+            manager.broadcast_to_players(1, Some("varn"), ServerMessage::Pong { timestamp: 0 });
+        assert_eq!(sent, 2);
+        assert!(receivers[0].try_recv().is_ok());
+        assert!(receivers[1].try_recv().is_err());
+        assert!(receivers[2].try_recv().is_ok());
+    }
 }
@@ -5,6 +5,13 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::conversation_mode::{ConversationMode, SessionMode};
+use crate::ingestion::fvtt::JournalPage;
+use crate::log_stream::LogRecord;
+use crate::service::DiskSpaceLevel;
+use crate::tool_budget::{SessionToolBudget, ToolBudget};
+use crate::tools::client_tools::{ClientToolDefinition, RejectedClientTool};
+
 /// Messages sent from client to server
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -15,6 +22,21 @@ pub enum ClientMessage {
         user_name: String,
         role: u8,
         session_id: Option<String>,
+        /// Actors the user owns (used for character context)
+        #[serde(default)]
+        owned_actor_ids: Vec<String>,
+        /// The user's assigned character, if any
+        #[serde(default)]
+        character_id: Option<String>,
+        /// The FVTT world the client is connected to
+        #[serde(default)]
+        world_id: Option<String>,
+        /// Whether this client accepts MCP write tools when they are restricted
+        #[serde(default)]
+        allow_write_tools: bool,
+        /// The world's game system (FVTT's `game.system.id`)
+        #[serde(default)]
+        system_id: Option<String>,
     },
     /// Keepalive ping
     Ping,
@@ -28,6 +50,86 @@ pub enum ClientMessage {
         tool_call_id: String,
         result: serde_json::Value,
     },
+    /// Import or re-sync a Foundry VTT journal entry (GM only)
+    JournalSync {
+        journal_id: String,
+        name: String,
+        /// Text pages, each carrying its own access level
+        pages: Vec<JournalPage>,
+    },
+    /// Remove an indexed journal entry that was deleted in Foundry VTT (GM only)
+    JournalRemove { journal_id: String },
+    /// Set an MCP conversation's retrieval mode, or without a session the
+    /// mode of new conversations (GM only)
+    SetConversationMode {
+        #[serde(default)]
+        session_id: Option<String>,
+        mode: ConversationMode,
+    },
+    /// List the retrieval modes of MCP conversations (GM only)
+    GetConversationModes,
+    /// Set an MCP conversation's tool-use budget, or without a session the
+    /// budget new conversations start with (GM only). Unset limits use the
+    /// configured ones; all are capped at the configured ceilings.
+    SetToolBudget {
+        #[serde(default)]
+        session_id: Option<String>,
+        #[serde(default)]
+        tool_call_pause_threshold: Option<u32>,
+        #[serde(default)]
+        time_pause_threshold_secs: Option<u64>,
+        #[serde(default)]
+        hard_timeout_secs: Option<u64>,
+    },
+    /// List the tool-use budgets of MCP conversations (GM only)
+    GetToolBudgets,
+    /// Register external tools for the connection's world, replacing those
+    /// registered before (GM only)
+    RegisterTools {
+        #[serde(default)]
+        tools: Vec<ClientToolDefinition>,
+    },
+    /// Report the delivered assets a world document uses, replacing what was
+    /// reported for it before; an empty list clears it (GM only)
+    AssetUsage {
+        document_type: String,
+        document_id: String,
+        #[serde(default)]
+        document_name: Option<String>,
+        #[serde(default)]
+        paths: Vec<String>,
+    },
+    /// Share an answer with connected players (GM only)
+    ShareAnswer {
+        /// The answer, as chat message HTML or text
+        content: String,
+        #[serde(default)]
+        title: Option<String>,
+        /// Lowest role to share with (defaults to all players)
+        #[serde(default)]
+        access_level: Option<u8>,
+        /// Documents the answer cites; players who can't read them don't get it
+        #[serde(default)]
+        document_ids: Vec<String>,
+    },
+    /// Approve or deny a tool call held for approval (GM only)
+    ToolApproval {
+        request_id: String,
+        approved: bool,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Watch the service log live, replacing any earlier filter (GM only)
+    SubscribeLogs {
+        /// Most verbose level to send: "error" through "trace" (default "info")
+        #[serde(default)]
+        level: Option<String>,
+        /// Modules to watch, e.g. "ingestion" (default: the whole service)
+        #[serde(default)]
+        targets: Vec<String>,
+    },
+    /// Stop watching the service log
+    UnsubscribeLogs,
 }
 
 /// Messages sent from server to client
@@ -82,6 +184,139 @@ pub enum ServerMessage {
         id: String,
         tool: String,
         args: serde_json::Value,
+        /// Correlation ID of the MCP tool call, for matching client and server logs
+        #[serde(skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    /// A tool call that changes the world is waiting for GM approval
+    ChatApprovalRequired {
+        request_id: String,
+        tool: String,
+        args: serde_json::Value,
+        /// Seconds until the call is refused unanswered
+        timeout_secs: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    /// An MCP conversation started a tool call (sent to GMs)
+    ToolCallStarted {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        tool: String,
+        /// Position of the call in the conversation's current turn, from 1
+        call: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    /// An MCP conversation's tool call finished (sent to GMs)
+    ToolCallFinished {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        tool: String,
+        call: u32,
+        elapsed_ms: u64,
+        success: bool,
+    },
+    /// An MCP tool call is waiting for GM approval (sent to GMs)
+    ToolCallAwaitingApproval {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        tool: String,
+        /// Position among calls waiting for approval, from 1
+        queue_position: usize,
+    },
+    /// An MCP conversation's tool chain paused for the user or ran out of
+    /// time (sent to GMs)
+    ToolChainPaused {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        /// Calls since the turn started
+        calls: u32,
+        elapsed_secs: u64,
+        /// True when the turn hit its hard timeout rather than pausing
+        stopped: bool,
+    },
+    /// Ollama model pull progress (sent to GMs)
+    ModelPullProgress {
+        model: String,
+        /// Ollama's status text, e.g. "pulling manifest", "success"
+        status: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        completed: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+        /// Whether the pull has finished, successfully or not
+        done: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Server-side progress of an HTTP document upload, keyed by the
+    /// client's `X-Upload-Token`
+    UploadProgress {
+        upload_token: String,
+        /// Stage: "receiving", "received", "hashing", "validated", "queued", "failed"
+        stage: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bytes: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+        /// Set once the document record exists
+        #[serde(skip_serializing_if = "Option::is_none")]
+        document_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Result of a journal sync or removal request
+    JournalSyncResult {
+        journal_id: String,
+        /// Status: "queued", "unchanged", "empty", "removed", "failed"
+        status: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        document_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Retrieval modes of MCP conversations, in reply to setting or listing them
+    ConversationModes {
+        /// Mode new conversations start in
+        default_mode: ConversationMode,
+        /// Open conversations
+        sessions: Vec<SessionMode>,
+    },
+    /// Tool-use budgets of MCP conversations
+    ToolBudgets {
+        /// Budget new conversations start with
+        default_budget: ToolBudget,
+        /// Conversations with their own budget
+        sessions: Vec<SessionToolBudget>,
+    },
+    /// Outcome of registering external tools
+    ToolsRegistered {
+        registered: Vec<String>,
+        rejected: Vec<RejectedClientTool>,
+    },
+    /// An answer a GM shared with the players, sanitized for them
+    SharedAnswer {
+        share_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Plain text
+        content: String,
+    },
+    /// How many players an answer was shared with, in reply to sharing it
+    ShareAnswerResult {
+        share_id: String,
+        /// Lowest role that was sent the answer
+        access_level: u8,
+        recipients: usize,
+    },
+    /// A service log event, sent to connections watching the log
+    LogEvent(LogRecord),
+    /// Free space of the data directory crossed a threshold (sent to GMs)
+    DiskSpaceStatus {
+        level: DiskSpaceLevel,
+        free_bytes: u64,
+        total_bytes: u64,
     },
 }
 
@@ -113,6 +348,30 @@ impl From<DocumentProgressUpdate> for ServerMessage {
     }
 }
 
+/// Data for broadcasting upload progress updates
+#[derive(Debug, Clone)]
+pub struct UploadProgressUpdate {
+    pub upload_token: String,
+    pub stage: String,
+    pub bytes: Option<u64>,
+    pub total: Option<u64>,
+    pub document_id: Option<String>,
+    pub error: Option<String>,
+}
+
+impl From<UploadProgressUpdate> for ServerMessage {
+    fn from(update: UploadProgressUpdate) -> Self {
+        ServerMessage::UploadProgress {
+            upload_token: update.upload_token,
+            stage: update.stage,
+            bytes: update.bytes,
+            total: update.total,
+            document_id: update.document_id,
+            error: update.error,
+        }
+    }
+}
+
 /// Data for broadcasting captioning progress updates
 #[derive(Debug, Clone)]
 pub struct CaptioningProgressUpdate {
@@ -134,3 +393,27 @@ impl From<CaptioningProgressUpdate> for ServerMessage {
         }
     }
 }
+
+/// Data for broadcasting model pull progress
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelPullUpdate {
+    pub model: String,
+    pub status: String,
+    pub completed: Option<u64>,
+    pub total: Option<u64>,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+impl From<ModelPullUpdate> for ServerMessage {
+    fn from(update: ModelPullUpdate) -> Self {
+        ServerMessage::ModelPullProgress {
+            model: update.model,
+            status: update.status,
+            completed: update.completed,
+            total: update.total,
+            done: update.done,
+            error: update.error,
+        }
+    }
+}
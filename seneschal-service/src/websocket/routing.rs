@@ -0,0 +1,136 @@
+//! Choosing the GM connection that executes an MCP external tool call.
+//!
+//! With several GMs connected, calls go to a GM in the MCP client's world
+//! when one is known. Calls from the same MCP session stick to the GM that
+//! handled the previous one, so multi-step edits land in one client. Calls
+//! without affinity are spread round-robin.
+
+use std::sync::atomic::Ordering;
+
+use tracing::debug;
+
+use super::manager::WebSocketManager;
+
+/// What an external tool call needs from the GM connection that runs it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GmRoute<'a> {
+    /// FVTT world the caller works in; GMs known to be elsewhere are skipped
+    pub world_id: Option<&'a str>,
+    /// Calls sharing a key go to the same GM while it stays eligible
+    pub affinity_key: Option<&'a str>,
+    /// Only GMs whose client opted in to write tools may run the call
+    pub require_write_access: bool,
+}
+
+impl WebSocketManager {
+    /// Pick the GM connection to run an external tool call.
+    ///
+    /// Returns the session_id of the chosen connection, or None if no
+    /// connected GM is eligible.
+    pub fn route_gm_connection(&self, route: &GmRoute) -> Option<String> {
+        let mut candidates: Vec<(String, Option<String>)> = self
+            .connections
+            .iter()
+            .filter(|entry| {
+                let conn = entry.value();
+                conn.authenticated
+                    && conn.user_role.is_some_and(|r| r >= 4)
+                    && (!route.require_write_access || conn.allow_write_tools)
+            })
+            .map(|entry| (entry.key().clone(), entry.value().world_id.clone()))
+            .collect();
+
+        if let Some(world_id) = route.world_id {
+            candidates.retain(|(_, world)| world.as_deref().is_none_or(|w| w == world_id));
+            // GMs that reported the world beat those that reported none
+            if candidates
+                .iter()
+                .any(|(_, world)| world.as_deref() == Some(world_id))
+            {
+                candidates.retain(|(_, world)| world.is_some());
+            }
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+        // Stable order so round-robin visits every GM in turn
+        candidates.sort();
+
+        if let Some(key) = route.affinity_key
+            && let Some(gm) = self.gm_affinity.get(key)
+            && candidates
+                .iter()
+                .any(|(session_id, _)| session_id == gm.value())
+        {
+            return Some(gm.value().clone());
+        }
+
+        let index = self.next_gm.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        let session_id = candidates.swap_remove(index).0;
+        if let Some(key) = route.affinity_key {
+            debug!(affinity_key = %key, session_id = %session_id, "Pinned MCP session to GM connection");
+            self.gm_affinity.insert(key.to_string(), session_id.clone());
+        }
+        Some(session_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_route_gm_connection() {
+        let manager = WebSocketManager::new();
+        for (session_id, world, writes) in [
+            ("gm-a", Some("spinward"), false),
+            ("gm-b", Some("spinward"), true),
+            ("gm-c", Some("trojan-reach"), true),
+            ("player", Some("spinward"), true),
+        ] {
+            let (tx, _rx) = mpsc::unbounded_channel();
+            manager.add_connection(session_id.to_string(), tx);
+            let role = if session_id == "player" { 1 } else { 4 };
+            manager.authenticate(
+                session_id,
+                session_id.to_string(),
+                session_id.to_string(),
+                role,
+            );
+            manager.set_routing_context(session_id, world.map(String::from), writes);
+        }
+
+        // Round-robin across the GMs in the requested world
+        let route = GmRoute {
+            world_id: Some("spinward"),
+            ..Default::default()
+        };
+        assert_eq!(manager.route_gm_connection(&route).unwrap(), "gm-a");
+        assert_eq!(manager.route_gm_connection(&route).unwrap(), "gm-b");
+
+        // Affinity holds until the GM disconnects
+        let route = GmRoute {
+            affinity_key: Some("mcp-1"),
+            ..Default::default()
+        };
+        assert_eq!(manager.route_gm_connection(&route).unwrap(), "gm-c");
+        assert_eq!(manager.route_gm_connection(&route).unwrap(), "gm-c");
+        manager.remove_connection("gm-c");
+        assert_ne!(manager.route_gm_connection(&route).unwrap(), "gm-c");
+
+        // Write tools only go to GMs that opted in
+        let route = GmRoute {
+            world_id: Some("spinward"),
+            require_write_access: true,
+            ..Default::default()
+        };
+        assert_eq!(manager.route_gm_connection(&route).unwrap(), "gm-b");
+        let route = GmRoute {
+            world_id: Some("trojan-reach"),
+            require_write_access: true,
+            ..Default::default()
+        };
+        assert!(manager.route_gm_connection(&route).is_none());
+    }
+}